@@ -1,3 +1,4 @@
+use event_store::{Event, EventQuery};
 use reqwest::Client;
 use shared_types::*;
 use shared_utils::crypto::{generate_salt, create_commitment};
@@ -20,6 +21,7 @@ pub enum ApiError {
 pub struct ApiClient {
     client: Client,
     base_url: String,
+    api_key: Option<String>,
 }
 
 impl ApiClient {
@@ -28,14 +30,21 @@ impl ApiClient {
         Ok(Self {
             client,
             base_url: base_url.to_string(),
+            api_key: None,
         })
     }
     
+    /// Use `api_key` for the admin routes
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key;
+        self
+    }
+    
     /// Create a new vote
     pub async fn create_vote(&self, config: VoteConfig) -> Result<CreateVoteResponse, ApiError> {
         debug!("Creating vote: {}", config.title);
         
-        let request = CreateVoteRequest { config };
+        let request = CreateVoteRequest { config, client_request_id: None };
         let response = self.client
             .post(&format!("{}/api/v1/votes", self.base_url))
             .json(&request)
@@ -225,6 +234,30 @@ impl ApiClient {
         }
     }
     
+    /// Query stored events
+    pub async fn query_events(&self, query: &EventQuery) -> Result<Paginated<Event>, ApiError> {
+        debug!("Querying events");
+        
+        let mut request = self.client
+            .post(format!("{}/admin/events/query", self.base_url))
+            .json(query);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await?;
+        
+        if response.status().is_success() {
+            let result: Paginated<Event> = response.json().await?;
+            Ok(result)
+        } else {
+            let status = response.status();
+            let text = response.text().await?;
+            Err(ApiError::ApiError {
+                message: format!("HTTP {}: {}", status, text),
+            })
+        }
+    }
+    
     /// Health check
     pub async fn health_check(&self) -> Result<HealthResponse, ApiError> {
         debug!("Performing health check");
@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+use event_store::{EventQuery, EventType, QueryBuilder};
 use serde_json::json;
 use shared_types::*;
 use crate::client::{ApiClient, ApiError};
@@ -26,6 +28,9 @@ pub async fn create_vote(
         template_params,
         commitment_duration_hours: commitment_hours,
         reveal_duration_hours: reveal_hours,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
     
     match client.create_vote(config).await {
@@ -143,6 +148,7 @@ pub async fn commit_vote(
         voter,
         commitment_hash,
         salt,
+        range_proof: None,
     };
     
     match client.commit_vote(&vote_id, request).await {
@@ -175,6 +181,8 @@ pub async fn reveal_vote(
         voter,
         value: parsed_value,
         salt,
+        range_blinding: None,
+        ciphertext: None,
     };
     
     match client.reveal_vote(&vote_id, request).await {
@@ -222,9 +230,10 @@ pub async fn list_templates(client: &ApiClient) -> Result<(), ApiError> {
             
             if let Some(templates) = response.get("templates").and_then(|t| t.as_array()) {
                 for template in templates {
-                    if let Some(template_str) = template.as_str() {
-                        println!("• {}", template_str);
-                    }
+                    let id = template.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+                    let name = template.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+                    let description = template.get("description").and_then(|v| v.as_str()).unwrap_or_default();
+                    println!("• {} ({}): {}", id, name, description);
                 }
             }
         }
@@ -332,3 +341,96 @@ pub async fn health_check(client: &ApiClient) -> Result<(), ApiError> {
     
     Ok(())
 }
+
+/// How `events query` prints matching events
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum EventFormat {
+    Table,
+    Json,
+    Ndjson,
+}
+
+/// Parse `--type`: a built-in event type name, or `custom:<name>` for a custom event type
+///
+/// Any other name is rejected rather than being taken as a custom type that matches nothing.
+pub fn parse_event_type(name: &str) -> Result<EventType, String> {
+    if let Some(custom) = name.strip_prefix("custom:") {
+        return Ok(EventType::Custom(custom.to_string()));
+    }
+    let Ok(event_type) = name.parse::<EventType>();
+    match event_type {
+        EventType::Custom(_) => Err(format!(
+            "unknown event type `{}`; expected one of SessionCreated, CommitmentSubmitted, RevealPhaseStarted, \
+             RevealCompleted, ResultGenerated, SystemError, or custom:<name>",
+            name
+        )),
+        event_type => Ok(event_type),
+    }
+}
+
+/// Build an `EventQuery` from the `events query` filter flags; an open-ended time bound is unbounded
+pub fn build_event_query(
+    event_type: Option<EventType>,
+    session: Option<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> EventQuery {
+    let mut builder = QueryBuilder::new();
+    if let Some(event_type) = event_type {
+        builder = builder.event_type_equals(event_type);
+    }
+    if let Some(session) = session {
+        builder = builder.session_id_equals(session);
+    }
+    if since.is_some() || until.is_some() {
+        builder = builder.time_range(
+            since.unwrap_or(DateTime::<Utc>::MIN_UTC),
+            until.unwrap_or(DateTime::<Utc>::MAX_UTC),
+        );
+    }
+    builder.build()
+}
+
+/// Override the query's page size when `--limit` was given
+pub fn with_limit(mut query: EventQuery, limit: Option<usize>) -> EventQuery {
+    if let Some(limit) = limit {
+        query.pagination.limit = limit;
+    }
+    query
+}
+
+/// Query stored events
+pub async fn query_events(client: &ApiClient, query: EventQuery, format: EventFormat) -> Result<(), ApiError> {
+    let result = match client.query_events(&query).await {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Failed to query events: {}", e);
+            return Err(e);
+        }
+    };
+    
+    match format {
+        EventFormat::Json => println!("{}", serde_json::to_string_pretty(&result.items)?),
+        EventFormat::Ndjson => {
+            for event in &result.items {
+                println!("{}", serde_json::to_string(event)?);
+            }
+        }
+        EventFormat::Table => {
+            println!("{:<25} {:<22} {:<38} {:>7}  MESSAGE", "TIME", "TYPE", "SESSION", "VERSION");
+            for event in &result.items {
+                println!(
+                    "{:<25} {:<22} {:<38} {:>7}  {}",
+                    event.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                    event.event_type.to_string(),
+                    event.session_id.as_deref().unwrap_or("-"),
+                    event.version,
+                    event.message,
+                );
+            }
+            println!("Showing {} of {} events", result.items.len(), result.total);
+        }
+    }
+    
+    Ok(())
+}
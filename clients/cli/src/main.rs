@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 use shared_logging::init_logging_from_env;
 use tracing::info;
@@ -20,6 +21,10 @@ struct Cli {
     #[arg(long, default_value = "http://localhost:8080")]
     api_url: String,
     
+    /// Admin API key, sent as a bearer token to the `/admin` routes
+    #[arg(long, env = "VOTE_ADMIN_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+    
     /// Verbose output
     #[arg(short, long)]
     verbose: bool,
@@ -138,6 +143,46 @@ enum Commands {
     
     /// Health check
     Health,
+    
+    /// Inspect stored events
+    Events {
+        #[command(subcommand)]
+        command: EventsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum EventsCommand {
+    /// Query events with filter flags or a JSON EventQuery
+    Query {
+        /// Full EventQuery as JSON, instead of the filter flags
+        #[arg(long, conflicts_with_all = ["event_type", "session", "since", "until"])]
+        query: Option<String>,
+        
+        /// Event type, e.g. CommitmentSubmitted, or custom:<name> for a custom event type
+        #[arg(long = "type", value_parser = parse_event_type)]
+        event_type: Option<event_store::EventType>,
+        
+        /// Session (vote) ID
+        #[arg(long)]
+        session: Option<String>,
+        
+        /// Only events at or after this RFC 3339 time
+        #[arg(long)]
+        since: Option<DateTime<Utc>>,
+        
+        /// Only events at or before this RFC 3339 time
+        #[arg(long)]
+        until: Option<DateTime<Utc>>,
+        
+        /// Maximum number of events to return
+        #[arg(long)]
+        limit: Option<usize>,
+        
+        /// Output format
+        #[arg(long, value_enum, default_value_t = EventFormat::Table)]
+        format: EventFormat,
+    },
 }
 
 #[tokio::main]
@@ -154,7 +199,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Starting vote CLI");
     
     // Create API client
-    let client = client::ApiClient::new(&cli.api_url)?;
+    let client = client::ApiClient::new(&cli.api_url)?.with_api_key(cli.api_key);
     
     // Execute command
     match cli.command {
@@ -204,6 +249,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Health => {
             health_check(&client).await?;
         }
+        
+        Commands::Events { command: EventsCommand::Query { query, event_type, session, since, until, limit, format } } => {
+            let query = match query {
+                Some(json) => serde_json::from_str(&json)?,
+                None => build_event_query(event_type, session, since, until),
+            };
+            query_events(&client, with_limit(query, limit), format).await?;
+        }
     }
     
     Ok(())
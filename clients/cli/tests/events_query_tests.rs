@@ -0,0 +1,145 @@
+// The API fixtures of the vote-api tests
+#[path = "../../../services/vote-api/tests/common/mod.rs"]
+mod common;
+
+use event_store::store::MemoryEventStore;
+use event_store::{Event, EventSeverity, EventStorage, EventType};
+use shared_config::ServerConfig;
+use std::process::Command;
+use std::sync::Arc;
+use vote_api::{create_router, AppComponents, AppState};
+
+const ADMIN_KEY: &str = "admin-key";
+
+fn event(event_type: EventType, session_id: &str) -> Event {
+    Event::new(
+        event_type,
+        EventSeverity::Info,
+        "vote-engine".to_string(),
+        "seeded".to_string(),
+        Some(session_id.to_string()),
+        None,
+    )
+}
+
+/// Serve vote-api over a seeded event store and return its base URL
+async fn spawn_api() -> String {
+    let store = MemoryEventStore::new();
+    for (event_type, session_id) in [
+        (EventType::SessionCreated, "vote-1"),
+        (EventType::CommitmentSubmitted, "vote-1"),
+        (EventType::CommitmentSubmitted, "vote-2"),
+        (EventType::RevealCompleted, "vote-1"),
+    ] {
+        store.store_event(event(event_type, session_id)).await.unwrap();
+    }
+
+    let config = common::config_with(ServerConfig { admin_api_key: Some(ADMIN_KEY.to_string()), ..Default::default() });
+    let components = AppComponents::in_memory().with_event_store(Arc::new(store));
+    let router = create_router(Arc::new(AppState::new(config, components)));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+/// Run the CLI off the runtime so the in-process server keeps serving
+async fn run_cli_with_key(api_key: Option<&str>, args: &[&str]) -> std::process::Output {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let api_key = api_key.map(str::to_string);
+    tokio::task::spawn_blocking(move || {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_vote"));
+        command.env("RUST_LOG", "off").env_remove("VOTE_ADMIN_API_KEY").args(&args);
+        if let Some(api_key) = api_key {
+            command.env("VOTE_ADMIN_API_KEY", api_key);
+        }
+        command.output().unwrap()
+    })
+    .await
+    .unwrap()
+}
+
+async fn run_cli(args: &[&str]) -> String {
+    let output = run_cli_with_key(Some(ADMIN_KEY), args).await;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[tokio::test]
+async fn test_query_by_type_returns_only_matching_events() {
+    let url = spawn_api().await;
+    let stdout = run_cli(&["--api-url", &url, "events", "query", "--type", "CommitmentSubmitted", "--format", "ndjson"]).await;
+
+    let events: Vec<Event> = stdout.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|e| e.event_type == EventType::CommitmentSubmitted));
+
+    // Filter flags combine
+    let stdout = run_cli(&[
+        "--api-url", &url, "events", "query", "--type", "CommitmentSubmitted", "--session", "vote-2", "--format", "json",
+    ])
+    .await;
+    let events: Vec<Event> = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].session_id.as_deref(), Some("vote-2"));
+}
+
+#[tokio::test]
+async fn test_json_query_and_limit() {
+    let url = spawn_api().await;
+    let query = serde_json::to_string(&event_store::EventQuery::new()).unwrap();
+    let stdout = run_cli(&["--api-url", &url, "events", "query", "--query", &query, "--limit", "3", "--format", "ndjson"]).await;
+    assert_eq!(stdout.lines().count(), 3);
+
+    let table = run_cli(&["--api-url", &url, "events", "query", "--session", "vote-1"]).await;
+    assert!(table.contains("RevealCompleted"), "{}", table);
+    assert!(table.trim_end().ends_with("Showing 3 of 3 events"), "{}", table);
+}
+
+#[tokio::test]
+async fn test_query_requires_the_admin_key() {
+    let url = spawn_api().await;
+    let output = run_cli_with_key(None, &["--api-url", &url, "events", "query"]).await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("401"), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let output = run_cli_with_key(Some("wrong-key"), &["--api-url", &url, "events", "query"]).await;
+    assert!(!output.status.success());
+
+    let stdout = run_cli(&["--api-url", &url, "--api-key", ADMIN_KEY, "events", "query", "--format", "ndjson"]).await;
+    assert_eq!(stdout.lines().count(), 4);
+}
+
+#[tokio::test]
+async fn test_unknown_type_is_rejected_unless_marked_custom() {
+    let url = spawn_api().await;
+    let output = run_cli_with_key(Some(ADMIN_KEY), &["--api-url", &url, "events", "query", "--type", "CommitmentSubmited"]).await;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown event type"), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let stdout = run_cli(&["--api-url", &url, "events", "query", "--type", "custom:Audit", "--format", "ndjson"]).await;
+    assert_eq!(stdout.lines().count(), 0);
+}
+
+#[tokio::test]
+async fn test_endpoint_returns_a_paginated_list() {
+    let url = spawn_api().await;
+    let mut query = event_store::EventQuery::new();
+    query.pagination.limit = 3;
+    let page: shared_types::Paginated<Event> = reqwest::Client::new()
+        .post(format!("{}/admin/events/query", url))
+        .bearer_auth(ADMIN_KEY)
+        .json(&query)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 3);
+    assert_eq!(page.total, 4);
+    assert_eq!(page.total_pages, 2);
+    assert_eq!(page.next_cursor.as_deref(), Some("3"));
+}
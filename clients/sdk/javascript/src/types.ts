@@ -97,6 +97,7 @@ export interface Page<T> {
   page: number;
   page_size: number;
   total_pages: number;
+  next_cursor?: string | null;
 }
 
 export interface VoteStats {
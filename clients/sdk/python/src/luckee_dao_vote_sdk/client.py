@@ -103,7 +103,8 @@ class VoteClient:
                 total=data["total"],
                 page=data["page"],
                 page_size=data["page_size"],
-                total_pages=data["total_pages"]
+                total_pages=data["total_pages"],
+                next_cursor=data.get("next_cursor")
             )
         except httpx.HTTPStatusError as e:
             raise self._handle_http_error(e)
@@ -117,6 +117,7 @@ class Page(BaseModel):
     page: int
     page_size: int
     total_pages: int
+    next_cursor: Optional[str] = None
 
 
 class VoteStats(BaseModel):
@@ -88,6 +88,25 @@ impl CommitmentAlgorithm for Blake2bCommitmentAlgorithm {
     }
 }
 
+/// Built-in hash schemes selectable by name in configuration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitmentHash {
+    #[default]
+    Sha256,
+    Blake2b,
+}
+
+impl CommitmentHash {
+    /// Algorithm implementing this scheme
+    pub fn algorithm(self) -> std::sync::Arc<dyn CommitmentAlgorithm> {
+        match self {
+            CommitmentHash::Sha256 => std::sync::Arc::new(Sha256CommitmentAlgorithm::new()),
+            CommitmentHash::Blake2b => std::sync::Arc::new(Blake2bCommitmentAlgorithm::new()),
+        }
+    }
+}
+
 /// Registry for commitment algorithms
 pub struct CommitmentAlgorithmRegistry {
     algorithms: std::collections::HashMap<String, std::sync::Arc<dyn CommitmentAlgorithm>>,
@@ -1,14 +1,18 @@
+use std::borrow::Cow;
 use std::sync::Arc;
+use rand::RngCore;
 use tracing::{info, debug};
 use shared_utils::generate_salt;
 
-use crate::algorithms::CommitmentAlgorithm;
+use crate::algorithms::{CommitmentAlgorithm, CommitmentHash};
 use crate::validators::CommitmentValidator;
 
 /// Commitment engine for handling vote commitments
 pub struct CommitmentEngine {
     algorithm: Arc<dyn CommitmentAlgorithm>,
     validator: Arc<CommitmentValidator>,
+    salt_len: Option<usize>,
+    domain_separator: String,
 }
 
 impl CommitmentEngine {
@@ -16,6 +20,37 @@ impl CommitmentEngine {
         Self {
             algorithm,
             validator: Arc::new(CommitmentValidator::new()),
+            salt_len: None,
+            domain_separator: String::new(),
+        }
+    }
+
+    /// Configure the hash scheme, salt length and domain separator
+    pub fn builder() -> CommitmentEngineBuilder {
+        CommitmentEngineBuilder::default()
+    }
+
+    /// Commitment hash for `value` under `salt`, including the domain separator
+    pub async fn compute_commitment(&self, value: &str, salt: &str) -> Result<String, CommitmentError> {
+        self.algorithm.create_commitment(&self.separated(value), salt).await
+    }
+
+    fn separated<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.domain_separator.is_empty() {
+            Cow::Borrowed(value)
+        } else {
+            Cow::Owned(format!("{}|{}", self.domain_separator, value))
+        }
+    }
+
+    fn generate_salt(&self) -> String {
+        match self.salt_len {
+            Some(len) => {
+                let mut bytes = vec![0u8; len];
+                rand::thread_rng().fill_bytes(&mut bytes);
+                hex::encode(bytes)
+            }
+            None => generate_salt(),
         }
     }
 
@@ -24,10 +59,10 @@ impl CommitmentEngine {
         info!("Creating commitment for voter: {}", voter);
         
         // Generate salt
-        let salt = generate_salt();
+        let salt = self.generate_salt();
         
         // Create commitment using the algorithm
-        let commitment_hash = self.algorithm.create_commitment(value, &salt).await?;
+        let commitment_hash = self.compute_commitment(value, &salt).await?;
         
         let commitment_data = CommitmentData {
             commitment_hash,
@@ -45,7 +80,7 @@ impl CommitmentEngine {
         debug!("Verifying commitment");
         
         // Use the algorithm to verify
-        let is_valid = self.algorithm.verify_commitment(value, salt, commitment_hash).await?;
+        let is_valid = self.algorithm.verify_commitment(&self.separated(value), salt, commitment_hash).await?;
         
         debug!("Commitment verification result: {}", is_valid);
         Ok(is_valid)
@@ -65,6 +100,66 @@ impl CommitmentEngine {
     }
 }
 
+/// Builder for a [`CommitmentEngine`] with a non-default scheme
+///
+/// Defaults match [`CommitmentEngine::new`] with SHA-256: UUID salts and no domain separator.
+#[derive(Default)]
+pub struct CommitmentEngineBuilder {
+    algorithm: Option<Arc<dyn CommitmentAlgorithm>>,
+    salt_len: Option<usize>,
+    domain_separator: String,
+}
+
+impl CommitmentEngineBuilder {
+    /// Use one of the built-in hash schemes
+    pub fn hash(mut self, hash: CommitmentHash) -> Self {
+        self.algorithm = Some(hash.algorithm());
+        self
+    }
+
+    /// Use a custom algorithm
+    pub fn algorithm(mut self, algorithm: Arc<dyn CommitmentAlgorithm>) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Generate salts of `len` random bytes, hex encoded
+    pub fn salt_len(mut self, len: usize) -> Self {
+        self.salt_len = Some(len);
+        self
+    }
+
+    /// Prefix hashed values with `separator` so commitments from different schemes never collide
+    ///
+    /// This covers the workspace services only. The standalone `service/` crate hashes its own
+    /// `commit|<domain_tag>|<vote_id>|...` preimage, set by its `commitments.domain_tag` config,
+    /// and does not go through this engine.
+    pub fn domain_separator(mut self, separator: &str) -> Self {
+        self.domain_separator = separator.to_string();
+        self
+    }
+
+    pub fn build(self) -> Result<CommitmentEngine, CommitmentError> {
+        if self.salt_len == Some(0) {
+            return Err(CommitmentError::InvalidData {
+                message: "Salt length must be at least one byte".to_string(),
+            });
+        }
+        if self.domain_separator.contains('|') {
+            return Err(CommitmentError::InvalidData {
+                message: "Domain separator cannot contain '|'".to_string(),
+            });
+        }
+
+        let algorithm = self.algorithm.unwrap_or_else(|| CommitmentHash::default().algorithm());
+        Ok(CommitmentEngine {
+            salt_len: self.salt_len,
+            domain_separator: self.domain_separator,
+            ..CommitmentEngine::new(algorithm)
+        })
+    }
+}
+
 /// Data structure for commitment information
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CommitmentData {
@@ -1,7 +1,13 @@
 pub mod engine;
 pub mod algorithms;
 pub mod validators;
+pub mod merkle;
+#[cfg(feature = "zkp")]
+pub mod range_proof;
 
 pub use engine::*;
 pub use algorithms::*;
 pub use validators::*;
+pub use merkle::*;
+#[cfg(feature = "zkp")]
+pub use range_proof::*;
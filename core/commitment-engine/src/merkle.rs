@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Domain-separation prefixes so a leaf can never be mistaken for an interior node
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+type Hash = [u8; 32];
+
+fn hash_leaf(commitment_hash: &str) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(commitment_hash.as_bytes());
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// One level of an inclusion proof: the sibling hash and which side it sits on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    pub sibling: String,
+    pub sibling_is_left: bool,
+}
+
+/// Path from a commitment's leaf to the tree root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub commitment_hash: String,
+    pub steps: Vec<ProofStep>,
+}
+
+impl InclusionProof {
+    /// Whether this proof places `commitment_hash` under `root`
+    pub fn verify(&self, root: &str) -> bool {
+        let mut current = hash_leaf(&self.commitment_hash);
+        for step in &self.steps {
+            let Some(sibling) = hex::decode(&step.sibling).ok().and_then(|b| Hash::try_from(b).ok()) else {
+                return false;
+            };
+            current = if step.sibling_is_left {
+                hash_node(&sibling, &current)
+            } else {
+                hash_node(&current, &sibling)
+            };
+        }
+        hex::encode(current) == root
+    }
+}
+
+/// Merkle tree over a vote's commitment hashes
+///
+/// Leaves are the sorted, de-duplicated commitment hashes, so the root depends only on the set
+/// of commitments and not on submission order. A node without a sibling is promoted unchanged
+/// to the next level rather than paired with itself.
+#[derive(Debug, Clone)]
+pub struct CommitmentTree {
+    leaves: Vec<String>,
+    levels: Vec<Vec<Hash>>,
+}
+
+impl CommitmentTree {
+    pub fn new<I, S>(commitment_hashes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut leaves: Vec<String> = commitment_hashes.into_iter().map(Into::into).collect();
+        leaves.sort();
+        leaves.dedup();
+
+        let mut levels = vec![leaves.iter().map(|leaf| hash_leaf(leaf)).collect::<Vec<_>>()];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { leaves, levels }
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Hex-encoded root; the tree of no commitments has the hash of the empty string as its root
+    pub fn root(&self) -> String {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => hex::encode(root),
+            None => hex::encode(Sha256::digest(b"")),
+        }
+    }
+
+    /// Inclusion proof for `commitment_hash`, or `None` if it is not in the tree
+    pub fn proof(&self, commitment_hash: &str) -> Option<InclusionProof> {
+        let mut index = self.leaves.binary_search_by(|leaf| leaf.as_str().cmp(commitment_hash)).ok()?;
+        let mut steps = Vec::new();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if let Some(hash) = level.get(sibling) {
+                steps.push(ProofStep { sibling: hex::encode(hash), sibling_is_left: sibling < index });
+            }
+            index /= 2;
+        }
+
+        Some(InclusionProof { commitment_hash: commitment_hash.to_string(), steps })
+    }
+}
@@ -0,0 +1,100 @@
+use bulletproofs::{BulletproofGens, PedersenGens, RangeProof as Bulletproof};
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use shared_types::RangeProof;
+
+use crate::engine::CommitmentError;
+
+/// Bit width of each proven value; wide enough for any `u64` range
+const RANGE_BITS: usize = 64;
+
+/// Transcript label binding proofs to this protocol
+const TRANSCRIPT_LABEL: &[u8] = b"ddv-numeric-range-proof";
+
+fn transcript(min: u64, max: u64) -> Transcript {
+    let mut transcript = Transcript::new(TRANSCRIPT_LABEL);
+    transcript.append_u64(b"min", min);
+    transcript.append_u64(b"max", max);
+    transcript
+}
+
+fn generators() -> (PedersenGens, BulletproofGens) {
+    (PedersenGens::default(), BulletproofGens::new(RANGE_BITS, 2))
+}
+
+fn invalid(message: impl Into<String>) -> CommitmentError {
+    CommitmentError::InvalidData { message: message.into() }
+}
+
+fn decode_32(field: &str, value: &str) -> Result<[u8; 32], CommitmentError> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| invalid(format!("{} must be 32 hex-encoded bytes", field)))
+}
+
+fn check_bounds(min: u64, max: u64) -> Result<(), CommitmentError> {
+    if min > max {
+        return Err(invalid(format!("invalid range [{}, {}]", min, max)));
+    }
+    Ok(())
+}
+
+/// Prove `min <= value <= max` without disclosing `value`
+///
+/// The proof covers `value - min` and `max - value` together; the second commitment is derived
+/// from the first, so the pair only verifies when both differences are non-negative. Returns the
+/// proof and the hex blinding factor the voter keeps until reveal.
+pub fn prove_range(value: u64, min: u64, max: u64) -> Result<(RangeProof, String), CommitmentError> {
+    check_bounds(min, max)?;
+    if value < min || value > max {
+        return Err(invalid(format!("value {} is outside range [{}, {}]", value, min, max)));
+    }
+
+    let (pc_gens, bp_gens) = generators();
+    let blinding = Scalar::random(&mut rand::thread_rng());
+    let (proof, commitments) = Bulletproof::prove_multiple(
+        &bp_gens,
+        &pc_gens,
+        &mut transcript(min, max),
+        &[value - min, max - value],
+        &[blinding, -blinding],
+        RANGE_BITS,
+    )
+    .map_err(|e| CommitmentError::AlgorithmError { message: e.to_string() })?;
+
+    let range_proof = RangeProof {
+        commitment: hex::encode(commitments[0].as_bytes()),
+        proof: hex::encode(proof.to_bytes()),
+    };
+    Ok((range_proof, hex::encode(blinding.as_bytes())))
+}
+
+/// Check that `proof` commits to a value within `[min, max]`
+pub fn verify_range(proof: &RangeProof, min: u64, max: u64) -> Result<(), CommitmentError> {
+    check_bounds(min, max)?;
+
+    let (pc_gens, bp_gens) = generators();
+    let lower = CompressedRistretto(decode_32("range proof commitment", &proof.commitment)?);
+    let lower_point = lower.decompress().ok_or_else(|| invalid("range proof commitment is not a valid point"))?;
+    // Commitment to `max - value` with blinding `-r`, derived rather than trusted from the voter
+    let upper = (pc_gens.B * Scalar::from(max - min) - lower_point).compress();
+
+    let bytes = hex::decode(&proof.proof).map_err(|_| invalid("range proof must be hex encoded"))?;
+    let bulletproof = Bulletproof::from_bytes(&bytes).map_err(|e| invalid(e.to_string()))?;
+    bulletproof
+        .verify_multiple(&bp_gens, &pc_gens, &mut transcript(min, max), &[lower, upper], RANGE_BITS)
+        .map_err(|e| CommitmentError::VerificationFailed { message: e.to_string() })
+}
+
+/// Whether `value` and `blinding_hex` open the proof's commitment
+pub fn verify_range_opening(proof: &RangeProof, value: u64, min: u64, blinding_hex: &str) -> Result<bool, CommitmentError> {
+    let Some(offset) = value.checked_sub(min) else {
+        return Ok(false);
+    };
+    let blinding = Option::<Scalar>::from(Scalar::from_canonical_bytes(decode_32("range blinding", blinding_hex)?))
+        .ok_or_else(|| invalid("range blinding is not a canonical scalar"))?;
+    let expected = PedersenGens::default().commit(Scalar::from(offset), blinding).compress();
+    Ok(hex::encode(expected.as_bytes()) == proof.commitment)
+}
@@ -1,7 +1,6 @@
 use std::sync::Arc;
 use commitment_engine::*;
 use sha2::{Sha256, Digest};
-use hex;
 
 // Mock implementation for testing
 struct MockCommitmentAlgorithm {
@@ -188,3 +187,50 @@ async fn test_commitment_serialization() {
     assert_eq!(deserialized.salt, commitment_data.salt);
     assert_eq!(deserialized.algorithm, commitment_data.algorithm);
 }
+
+#[tokio::test]
+async fn test_domain_separators_produce_different_commitments() {
+    let votes = CommitmentEngine::builder().domain_separator("vote").build().unwrap();
+    let lottery = CommitmentEngine::builder().domain_separator("lottery").build().unwrap();
+
+    let a = votes.compute_commitment("yes", "salt").await.unwrap();
+    let b = lottery.compute_commitment("yes", "salt").await.unwrap();
+    assert_ne!(a, b);
+
+    // A commitment only opens under the separator it was made with
+    let data = votes.create_commitment("yes", "test_voter").await.unwrap();
+    assert!(votes.verify_commitment("yes", &data.salt, &data.commitment_hash).await.unwrap());
+    assert!(!lottery.verify_commitment("yes", &data.salt, &data.commitment_hash).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_builder_defaults_match_new() {
+    let built = CommitmentEngine::builder().build().unwrap();
+    let plain = CommitmentEngine::new(Arc::new(Sha256CommitmentAlgorithm::new()));
+
+    assert_eq!(
+        built.compute_commitment("yes", "salt").await.unwrap(),
+        plain.compute_commitment("yes", "salt").await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_builder_hash_and_salt_len() {
+    let engine = CommitmentEngine::builder()
+        .hash(CommitmentHash::Blake2b)
+        .salt_len(16)
+        .build()
+        .unwrap();
+
+    let data = engine.create_commitment("yes", "test_voter").await.unwrap();
+    assert_eq!(data.algorithm, "blake2b");
+    assert_eq!(data.salt.len(), 32);
+    assert!(data.salt.chars().all(|c| c.is_ascii_hexdigit()));
+    assert!(engine.verify_commitment("yes", &data.salt, &data.commitment_hash).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_builder_rejects_invalid_settings() {
+    assert!(CommitmentEngine::builder().salt_len(0).build().is_err());
+    assert!(CommitmentEngine::builder().domain_separator("a|b").build().is_err());
+}
@@ -0,0 +1,55 @@
+use commitment_engine::CommitmentTree;
+use sha2::{Digest, Sha256};
+
+fn commitment(n: u32) -> String {
+    hex::encode(Sha256::digest(format!("ballot-{}", n)))
+}
+
+fn commitments(count: u32) -> Vec<String> {
+    (0..count).map(commitment).collect()
+}
+
+#[test]
+fn test_valid_proof_verifies_against_root() {
+    // Odd and even sizes exercise the promoted-node path
+    for count in [1, 2, 5, 8, 13] {
+        let tree = CommitmentTree::new(commitments(count));
+        let root = tree.root();
+        for hash in commitments(count) {
+            let proof = tree.proof(&hash).unwrap();
+            assert!(proof.verify(&root), "proof for {} of {} failed", hash, count);
+        }
+    }
+}
+
+#[test]
+fn test_non_member_has_no_proof_and_forged_proof_fails() {
+    let tree = CommitmentTree::new(commitments(6));
+    let outsider = commitment(99);
+    assert!(tree.proof(&outsider).is_none());
+
+    // Reusing a member's path for a different commitment must not verify
+    let mut forged = tree.proof(&commitment(2)).unwrap();
+    forged.commitment_hash = outsider;
+    assert!(!forged.verify(&tree.root()));
+}
+
+#[test]
+fn test_proof_fails_against_other_root() {
+    let tree = CommitmentTree::new(commitments(4));
+    let other = CommitmentTree::new(commitments(5));
+    let proof = tree.proof(&commitment(1)).unwrap();
+    assert!(!proof.verify(&other.root()));
+}
+
+#[test]
+fn test_root_ignores_order_and_duplicates() {
+    let mut shuffled = commitments(7);
+    shuffled.reverse();
+    shuffled.push(commitment(3));
+
+    let tree = CommitmentTree::new(shuffled);
+    assert_eq!(tree.len(), 7);
+    assert_eq!(tree.root(), CommitmentTree::new(commitments(7)).root());
+    assert!(CommitmentTree::new(Vec::<String>::new()).is_empty());
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "zkp")]
+
+use commitment_engine::{prove_range, verify_range, verify_range_opening};
+
+#[test]
+fn test_in_range_proof_verifies() {
+    let (proof, blinding) = prove_range(7, 1, 10).unwrap();
+
+    assert!(verify_range(&proof, 1, 10).is_ok());
+    assert!(verify_range_opening(&proof, 7, 1, &blinding).unwrap());
+}
+
+#[test]
+fn test_range_boundaries_verify() {
+    for value in [1, 10] {
+        let (proof, _) = prove_range(value, 1, 10).unwrap();
+        assert!(verify_range(&proof, 1, 10).is_ok());
+    }
+}
+
+#[test]
+fn test_out_of_range_value_cannot_be_proven() {
+    assert!(prove_range(0, 1, 10).is_err());
+    assert!(prove_range(11, 1, 10).is_err());
+}
+
+#[test]
+fn test_proof_does_not_verify_against_narrower_range() {
+    let (proof, _) = prove_range(50, 0, 100).unwrap();
+
+    assert!(verify_range(&proof, 0, 10).is_err());
+}
+
+#[test]
+fn test_opening_rejects_other_value() {
+    let (proof, blinding) = prove_range(7, 1, 10).unwrap();
+
+    assert!(!verify_range_opening(&proof, 8, 1, &blinding).unwrap());
+}
+
+#[test]
+fn test_tampered_proof_fails() {
+    let (mut proof, _) = prove_range(7, 1, 10).unwrap();
+    let (other, _) = prove_range(3, 1, 10).unwrap();
+    proof.commitment = other.commitment;
+
+    assert!(verify_range(&proof, 1, 10).is_err());
+}
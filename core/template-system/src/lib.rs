@@ -1,7 +1,9 @@
 pub mod registry;
 pub mod templates;
+pub mod tie_break;
 pub mod validators;
 
 pub use registry::*;
 pub use templates::*;
+pub use tie_break::*;
 pub use validators::*;
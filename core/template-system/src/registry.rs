@@ -51,6 +51,13 @@ impl TemplateRegistry {
         self.templates.keys().cloned().collect()
     }
 
+    /// All registered templates, ordered by ID
+    pub fn templates(&self) -> Vec<Arc<dyn VoteTemplate>> {
+        let mut templates: Vec<_> = self.templates.values().cloned().collect();
+        templates.sort_by(|a, b| a.id().cmp(b.id()));
+        templates
+    }
+
     /// Check if a template exists
     pub fn exists(&self, id: &str) -> bool {
         self.templates.contains_key(id)
@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use tracing::warn;
 
 use crate::registry::TemplateError;
+use crate::tie_break::resolve_winner;
+use shared_types::{TieBreak, WinnerOutcome};
 
 /// Trait for vote value templates
 #[async_trait]
@@ -28,6 +30,9 @@ pub trait VoteTemplate: Send + Sync + std::fmt::Debug {
     
     /// Get the expected value schema
     fn get_schema(&self) -> Value;
+
+    /// Get the schema of the template params
+    fn params_schema(&self) -> Value;
 }
 
 /// Yes/No voting template
@@ -106,6 +111,13 @@ impl VoteTemplate for YesNoTemplate {
             "description": "true for yes, false for no"
         })
     }
+
+    fn params_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "description": "No params are required"
+        })
+    }
 }
 
 /// Multiple choice voting template
@@ -122,6 +134,28 @@ impl MultipleChoiceTemplate {
     pub fn new() -> Self {
         Self
     }
+
+    /// Pick the single winner from an `aggregate` result, breaking ties with `policy`
+    pub fn winner(&self, aggregate: &Value, params: &Value, policy: &TieBreak, seed: &str) -> Result<WinnerOutcome, TemplateError> {
+        let choices = params.get("choices")
+            .and_then(|c| c.as_array())
+            .ok_or_else(|| TemplateError::AggregationFailed {
+                message: "Template params must contain 'choices' array".to_string(),
+            })?;
+        let results = aggregate.get("results")
+            .and_then(|r| r.as_object())
+            .ok_or_else(|| TemplateError::AggregationFailed {
+                message: "Aggregate must contain 'results' object".to_string(),
+            })?;
+
+        let counts: Vec<(String, u64)> = choices
+            .iter()
+            .filter_map(|choice| choice.as_str())
+            .map(|choice| (choice.to_string(), results.get(choice).and_then(|c| c.as_u64()).unwrap_or(0)))
+            .collect();
+
+        Ok(resolve_winner(&counts, policy, seed))
+    }
 }
 
 #[async_trait]
@@ -208,6 +242,20 @@ impl VoteTemplate for MultipleChoiceTemplate {
             "description": "One of the available choices"
         })
     }
+
+    fn params_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "choices": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "The available choices"
+                }
+            },
+            "required": ["choices"]
+        })
+    }
 }
 
 /// Numeric range voting template
@@ -264,8 +312,20 @@ impl VoteTemplate for NumericRangeTemplate {
     }
     
     async fn canonicalize(&self, value: &Value, _params: &Value) -> Result<Vec<u8>, TemplateError> {
+        // Integers keep their exact digits; going through f64 would map distinct
+        // values above 2^53 to the same bytes
+        if let Some(n) = value.as_i64() {
+            return Ok(n.to_string().into_bytes());
+        }
+        if let Some(n) = value.as_u64() {
+            return Ok(n.to_string().into_bytes());
+        }
         match value.as_f64() {
-            Some(n) => Ok(n.to_string().as_bytes().to_vec()),
+            Some(n) => {
+                // -0.0 compares equal to 0 and must commit to the same bytes
+                let n = if n == 0.0 { 0.0 } else { n };
+                Ok(n.to_string().as_bytes().to_vec())
+            }
             None => Err(TemplateError::CanonicalizationFailed {
                 message: "Value must be a number".to_string(),
             }),
@@ -303,6 +363,16 @@ impl VoteTemplate for NumericRangeTemplate {
             "description": "A numeric value within the specified range"
         })
     }
+
+    fn params_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "min": { "type": "number", "description": "Lowest accepted value, unbounded if omitted" },
+                "max": { "type": "number", "description": "Highest accepted value, unbounded if omitted" }
+            }
+        })
+    }
 }
 
 /// Ranking voting template
@@ -373,10 +443,12 @@ impl VoteTemplate for RankingTemplate {
     async fn canonicalize(&self, value: &Value, _params: &Value) -> Result<Vec<u8>, TemplateError> {
         match value.as_array() {
             Some(arr) => {
-                let ranking_str = arr.iter()
-                    .map(|v| v.as_str().unwrap_or(""))
-                    .collect::<Vec<_>>()
-                    .join(",");
+                let ranking = arr.iter()
+                    .map(|v| v.as_str().ok_or_else(|| TemplateError::CanonicalizationFailed {
+                        message: "Ranking items must be strings".to_string(),
+                    }))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let ranking_str = ranking.join(",");
                 Ok(ranking_str.as_bytes().to_vec())
             }
             None => Err(TemplateError::CanonicalizationFailed {
@@ -436,4 +508,18 @@ impl VoteTemplate for RankingTemplate {
             "description": "Array of options in order of preference"
         })
     }
+
+    fn params_schema(&self) -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "options": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "The options to rank, each ranked exactly once"
+                }
+            },
+            "required": ["options"]
+        })
+    }
 }
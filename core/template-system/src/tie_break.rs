@@ -0,0 +1,44 @@
+use shared_types::{TieBreak, WinnerOutcome};
+use shared_utils::crypto::hash_value;
+
+/// Pick a single winner from per-option counts, given in choice-list order.
+///
+/// Ties for the top count are resolved with `policy`; `seed` is only used by
+/// `TieBreak::Random` and should be the vote's combined randomness seed.
+pub fn resolve_winner(counts: &[(String, u64)], policy: &TieBreak, seed: &str) -> WinnerOutcome {
+    let top = counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    let leaders: Vec<String> = counts
+        .iter()
+        .filter(|(_, count)| top > 0 && *count == top)
+        .map(|(option, _)| option.clone())
+        .collect();
+
+    if leaders.len() <= 1 {
+        return WinnerOutcome {
+            winner: leaders.into_iter().next(),
+            tied: Vec::new(),
+            tie_break: None,
+            seed: None,
+        };
+    }
+
+    let (winner, seed) = match policy {
+        TieBreak::Random { .. } => (Some(leaders[random_index(seed, leaders.len())].clone()), Some(seed.to_string())),
+        TieBreak::FirstListed => (Some(leaders[0].clone()), None),
+        TieBreak::Runoff | TieBreak::NoWinner => (None, None),
+    };
+
+    WinnerOutcome {
+        winner,
+        tied: leaders,
+        tie_break: Some(policy.clone()),
+        seed,
+    }
+}
+
+/// Deterministic index in `0..len` derived from `seed`
+fn random_index(seed: &str, len: usize) -> usize {
+    let digest = hash_value(&format!("tie-break:{}", seed));
+    let value = u64::from_str_radix(&digest[..16], 16).unwrap_or(0);
+    (value % len as u64) as usize
+}
@@ -1,4 +1,5 @@
 use template_system::*;
+use shared_types::{SeedSource, TieBreak};
 
 #[tokio::test]
 async fn test_template_registry_register_and_get() {
@@ -138,6 +139,73 @@ async fn test_multiple_choice_template_aggregation() {
     assert_eq!(result["results"]["C"], serde_json::json!(1));
 }
 
+/// Aggregate with B and C tied for first place, listed in that order
+async fn tied_aggregate() -> (serde_json::Value, serde_json::Value) {
+    let template = MultipleChoiceTemplate::new();
+    let params = serde_json::json!({"choices": ["A", "B", "C"]});
+    let values = vec![serde_json::json!("A"), serde_json::json!("B"), serde_json::json!("C"), serde_json::json!("B"), serde_json::json!("C")];
+    (template.aggregate(&values, &params).await.unwrap(), params)
+}
+
+#[tokio::test]
+async fn test_multiple_choice_winner_without_tie() {
+    let template = MultipleChoiceTemplate::new();
+    let params = serde_json::json!({"choices": ["A", "B"]});
+    let aggregate = template.aggregate(&[serde_json::json!("B"), serde_json::json!("B"), serde_json::json!("A")], &params).await.unwrap();
+
+    let outcome = template.winner(&aggregate, &params, &TieBreak::NoWinner, "seed").unwrap();
+    assert_eq!(outcome.winner.as_deref(), Some("B"));
+    assert!(outcome.tied.is_empty());
+    assert_eq!(outcome.tie_break, None);
+}
+
+#[tokio::test]
+async fn test_tie_break_first_listed() {
+    let (aggregate, params) = tied_aggregate().await;
+    let outcome = MultipleChoiceTemplate::new().winner(&aggregate, &params, &TieBreak::FirstListed, "seed").unwrap();
+
+    assert_eq!(outcome.winner.as_deref(), Some("B"));
+    assert_eq!(outcome.tied, vec!["B".to_string(), "C".to_string()]);
+    assert_eq!(outcome.tie_break, Some(TieBreak::FirstListed));
+}
+
+#[tokio::test]
+async fn test_tie_break_runoff_and_no_winner() {
+    let (aggregate, params) = tied_aggregate().await;
+    let template = MultipleChoiceTemplate::new();
+
+    let runoff = template.winner(&aggregate, &params, &TieBreak::Runoff, "seed").unwrap();
+    assert_eq!(runoff.winner, None);
+    assert_eq!(runoff.tied, vec!["B".to_string(), "C".to_string()]);
+    assert_eq!(runoff.tie_break, Some(TieBreak::Runoff));
+
+    let none = template.winner(&aggregate, &params, &TieBreak::NoWinner, "seed").unwrap();
+    assert_eq!(none.winner, None);
+    assert_eq!(none.tie_break, Some(TieBreak::NoWinner));
+}
+
+#[tokio::test]
+async fn test_tie_break_random_is_deterministic_per_seed() {
+    let (aggregate, params) = tied_aggregate().await;
+    let template = MultipleChoiceTemplate::new();
+    let policy = TieBreak::Random { seed_source: SeedSource::RevealSalts };
+
+    let seed = shared_utils::crypto::combined_seed(&["salt-1", "salt-2"]);
+    let first = template.winner(&aggregate, &params, &policy, &seed).unwrap();
+    let again = template.winner(&aggregate, &params, &policy, &seed).unwrap();
+    assert_eq!(first, again);
+    assert!(matches!(first.winner.as_deref(), Some("B") | Some("C")));
+    assert_eq!(first.seed.as_deref(), Some(seed.as_str()));
+    // the seed does not depend on reveal order
+    assert_eq!(seed, shared_utils::crypto::combined_seed(&["salt-2", "salt-1"]));
+
+    // different seeds reach both tied options
+    let winners: std::collections::HashSet<String> = (0..32)
+        .map(|i| template.winner(&aggregate, &params, &policy, &format!("seed-{}", i)).unwrap().winner.unwrap())
+        .collect();
+    assert_eq!(winners.len(), 2);
+}
+
 #[tokio::test]
 async fn test_numeric_range_template_validation() {
     let template = NumericRangeTemplate::new();
@@ -174,6 +242,30 @@ async fn test_numeric_range_template_aggregation() {
     assert_eq!(result["max"], serde_json::json!(5.0));
 }
 
+#[tokio::test]
+async fn test_numeric_range_canonicalization_is_exact() {
+    let template = NumericRangeTemplate::new();
+    let params = serde_json::json!({});
+
+    // Integers above 2^53 are indistinguishable as f64 but must not share a commitment
+    let a = template.canonicalize(&serde_json::json!(9007199254740992u64), &params).await.unwrap();
+    let b = template.canonicalize(&serde_json::json!(9007199254740993u64), &params).await.unwrap();
+    assert_ne!(a, b);
+
+    assert_eq!(template.canonicalize(&serde_json::json!(5), &params).await.unwrap(), b"5");
+    assert_eq!(template.canonicalize(&serde_json::json!(5.0), &params).await.unwrap(), b"5");
+    assert_eq!(template.canonicalize(&serde_json::json!(-0.0), &params).await.unwrap(), b"0");
+}
+
+#[tokio::test]
+async fn test_ranking_canonicalization_rejects_non_strings() {
+    let template = RankingTemplate::new();
+    let params = serde_json::json!({"options": ["a", "b"]});
+
+    assert!(template.canonicalize(&serde_json::json!(["a", 1]), &params).await.is_err());
+    assert_eq!(template.canonicalize(&serde_json::json!(["b", "a"]), &params).await.unwrap(), b"b,a");
+}
+
 #[tokio::test]
 async fn test_ranking_template_validation() {
     let template = RankingTemplate::new();
@@ -222,3 +314,20 @@ async fn test_ranking_template_aggregation() {
     assert!(options.contains(&"B".to_string()));
     assert!(options.contains(&"C".to_string()));
 }
+
+#[tokio::test]
+async fn test_default_templates_describe_their_params() {
+    let registry = DefaultTemplateRegistry::new();
+    let templates = registry.templates();
+    let ids: Vec<&str> = templates.iter().map(|t| t.id()).collect();
+    assert_eq!(ids, ["multiple_choice", "numeric_range", "ranking", "yes_no"]);
+
+    for template in &templates {
+        assert!(template.get_schema()["type"].is_string());
+        assert_eq!(template.params_schema()["type"], "object");
+    }
+    let choice = registry.get("multiple_choice").unwrap();
+    assert_eq!(choice.params_schema()["required"], serde_json::json!(["choices"]));
+    let ranking = registry.get("ranking").unwrap();
+    assert_eq!(ranking.params_schema()["required"], serde_json::json!(["options"]));
+}
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use commitment_engine::{CommitmentTree, InclusionProof, ProofStep};
+use serde::Serialize;
+use shared_types::*;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+/// Writes commitment hashes to an external ledger, such as a blockchain
+#[async_trait]
+pub trait CommitmentAnchorer: Send + Sync {
+    async fn anchor(&self, commitment: &Commitment) -> Result<CommitmentAnchor, VoteError>;
+
+    /// Write the Merkle root of a batch of `batch_size` commitments
+    async fn anchor_root(&self, root: &str, batch_size: usize) -> Result<AnchorReceipt, VoteError> {
+        let _ = (root, batch_size);
+        Err(VoteError::StorageError {
+            message: "Batch anchoring is not supported by this anchorer".to_string(),
+        })
+    }
+}
+
+/// Where and when a batch root was written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorReceipt {
+    pub chain: String,
+    pub tx_hash: String,
+    pub block_number: Option<u64>,
+    pub anchored_at: DateTime<Utc>,
+}
+
+/// When a batch of pending commitments is anchored
+#[derive(Debug, Clone)]
+pub struct BatchConfig {
+    /// Anchor as soon as this many commitments are pending
+    pub max_batch_size: usize,
+    /// Anchor whatever is pending at least this often
+    pub max_interval: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 100,
+            max_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Outcome of [`crate::VoteEngine::reconcile_anchors`]
+#[derive(Debug, Clone, Serialize)]
+pub struct AnchorReconciliation {
+    pub vote_id: String,
+    /// Commitments examined
+    pub checked: usize,
+    /// Commitments that already had a confirmed anchor
+    pub confirmed: usize,
+    /// Anchors written for commitments that had none, or whose anchor did not hold up
+    pub resubmitted: Vec<CommitmentAnchor>,
+}
+
+/// Whether `anchor` is a confirmed anchor of `commitment`
+///
+/// The anchor must be for the same hash and included in a block, and a batched anchor's
+/// inclusion proof must lead to its batch root.
+pub fn anchor_confirms(anchor: &CommitmentAnchor, commitment: &Commitment) -> bool {
+    if anchor.commitment_hash != commitment.commitment_hash || anchor.block_number.is_none() {
+        return false;
+    }
+    match &anchor.batch_root {
+        Some(root) => batch_proof(anchor).verify(root),
+        None => true,
+    }
+}
+
+/// The inclusion proof recorded with a batched anchor
+pub fn batch_proof(anchor: &CommitmentAnchor) -> InclusionProof {
+    InclusionProof {
+        commitment_hash: anchor.commitment_hash.clone(),
+        steps: anchor.batch_proof.iter()
+            .map(|step| ProofStep { sibling: step.sibling.clone(), sibling_is_left: step.sibling_is_left })
+            .collect(),
+    }
+}
+
+/// Storage for anchor records, keyed by vote and voter
+#[async_trait]
+pub trait AnchorStore: Send + Sync {
+    /// Record an anchor, replacing any earlier one for the same voter
+    async fn save_anchor(&self, anchor: CommitmentAnchor) -> Result<(), VoteError>;
+    async fn list_anchors(&self, vote_id: &str) -> Result<Vec<CommitmentAnchor>, VoteError>;
+}
+
+/// In-memory anchor store
+#[derive(Default)]
+pub struct MemoryAnchorStore {
+    anchors: RwLock<HashMap<String, HashMap<String, CommitmentAnchor>>>,
+}
+
+impl MemoryAnchorStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AnchorStore for MemoryAnchorStore {
+    async fn save_anchor(&self, anchor: CommitmentAnchor) -> Result<(), VoteError> {
+        self.anchors.write().await
+            .entry(anchor.vote_id.clone())
+            .or_default()
+            .insert(anchor.voter.clone(), anchor);
+        Ok(())
+    }
+
+    async fn list_anchors(&self, vote_id: &str) -> Result<Vec<CommitmentAnchor>, VoteError> {
+        let mut anchors: Vec<CommitmentAnchor> = self.anchors.read().await
+            .get(vote_id)
+            .map(|by_voter| by_voter.values().cloned().collect())
+            .unwrap_or_default();
+        anchors.sort_by(|a, b| a.voter.cmp(&b.voter));
+        Ok(anchors)
+    }
+}
+
+/// Anchors accepted commitments in the background and records the result
+///
+/// Commitments are anchored one transaction each, or, in batched mode, collected
+/// and anchored as the root of a [`CommitmentTree`] with a local inclusion proof
+/// recorded for every commitment.
+#[derive(Clone)]
+pub struct Anchoring {
+    anchorer: Arc<dyn CommitmentAnchorer>,
+    store: Arc<dyn AnchorStore>,
+    batch: Option<Arc<Batch>>,
+}
+
+struct Batch {
+    config: BatchConfig,
+    pending: Mutex<Vec<Commitment>>,
+}
+
+impl Anchoring {
+    pub fn new(anchorer: Arc<dyn CommitmentAnchorer>, store: Arc<dyn AnchorStore>) -> Self {
+        Self { anchorer, store, batch: None }
+    }
+
+    /// Anchor commitments in batches; must be called within a Tokio runtime, which
+    /// runs the interval flush for as long as the returned value (or a clone) lives
+    pub fn batched(anchorer: Arc<dyn CommitmentAnchorer>, store: Arc<dyn AnchorStore>, config: BatchConfig) -> Self {
+        let batch = Arc::new(Batch { config, pending: Mutex::new(Vec::new()) });
+        let anchoring = Self { anchorer, store, batch: Some(batch.clone()) };
+        anchoring.spawn_interval_flush(Arc::downgrade(&batch));
+        anchoring
+    }
+
+    pub fn store(&self) -> &Arc<dyn AnchorStore> {
+        &self.store
+    }
+
+    /// Anchor `commitment` and record the anchor
+    pub async fn anchor(&self, commitment: &Commitment) -> Result<CommitmentAnchor, VoteError> {
+        let anchor = self.anchorer.anchor(commitment).await?;
+        self.store.save_anchor(anchor.clone()).await?;
+        info!(
+            "Anchored commitment for voter {} in vote {}: tx {}",
+            commitment.voter, commitment.vote_id, anchor.tx_hash
+        );
+        Ok(anchor)
+    }
+
+    /// Anchor without blocking the caller; failures are logged and leave the commitment unanchored
+    ///
+    /// In batched mode the commitment is queued, and the batch is anchored once it is full.
+    /// A batch that fails to anchor goes back on the queue for the next attempt.
+    pub fn spawn_anchor(&self, commitment: Commitment) {
+        let anchoring = self.clone();
+        tokio::spawn(async move {
+            let result = match &anchoring.batch {
+                Some(batch) => {
+                    let full = {
+                        let mut pending = batch.pending.lock().await;
+                        pending.push(commitment);
+                        if pending.len() >= batch.config.max_batch_size {
+                            std::mem::take(&mut *pending)
+                        } else {
+                            Vec::new()
+                        }
+                    };
+                    anchoring.anchor_drained(batch, full).await.map(|_| ())
+                }
+                None => anchoring.anchor(&commitment).await.map(|_| ()),
+            };
+            if let Err(e) = result {
+                warn!("Failed to anchor commitments: {}", e);
+            }
+        });
+    }
+
+    /// Anchor `commitments` now rather than in the background
+    ///
+    /// In batched mode they form one batch of their own. Any of them still queued are taken
+    /// off the queue so the next batch does not anchor them again, and go back on it if
+    /// the resubmission fails.
+    pub async fn resubmit(&self, commitments: Vec<Commitment>) -> Result<Vec<CommitmentAnchor>, VoteError> {
+        if let Some(batch) = &self.batch {
+            let queued: Vec<Commitment> = {
+                let mut pending = batch.pending.lock().await;
+                let (queued, rest) = std::mem::take(&mut *pending).into_iter().partition(|queued| {
+                    commitments.iter().any(|c| c.vote_id == queued.vote_id && c.voter == queued.voter)
+                });
+                *pending = rest;
+                queued
+            };
+            return match self.anchor_batch(&commitments).await {
+                Ok(anchors) => Ok(anchors),
+                Err(e) => {
+                    requeue(batch, queued).await;
+                    Err(e)
+                }
+            };
+        }
+        let mut anchors = Vec::with_capacity(commitments.len());
+        for commitment in &commitments {
+            anchors.push(self.anchor(commitment).await?);
+        }
+        Ok(anchors)
+    }
+
+    /// Number of commitments waiting for the next batch
+    pub async fn pending(&self) -> usize {
+        match &self.batch {
+            Some(batch) => batch.pending.lock().await.len(),
+            None => 0,
+        }
+    }
+
+    /// Anchor everything pending in batched mode, returning how many commitments were anchored
+    ///
+    /// On failure the commitments stay pending.
+    pub async fn flush(&self) -> Result<usize, VoteError> {
+        let Some(batch) = &self.batch else {
+            return Ok(0);
+        };
+        let pending = std::mem::take(&mut *batch.pending.lock().await);
+        self.anchor_drained(batch, pending).await
+    }
+
+    /// Anchor commitments taken off the queue, putting them back ahead of anything queued since if it fails
+    async fn anchor_drained(&self, batch: &Batch, commitments: Vec<Commitment>) -> Result<usize, VoteError> {
+        match self.anchor_batch(&commitments).await {
+            Ok(anchors) => Ok(anchors.len()),
+            Err(e) => {
+                requeue(batch, commitments).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Write the batch's Merkle root once and record an anchor with an inclusion proof per commitment
+    async fn anchor_batch(&self, commitments: &[Commitment]) -> Result<Vec<CommitmentAnchor>, VoteError> {
+        if commitments.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let tree = CommitmentTree::new(commitments.iter().map(|c| c.commitment_hash.clone()));
+        let root = tree.root();
+        let receipt = self.anchorer.anchor_root(&root, commitments.len()).await?;
+
+        let mut anchors = Vec::with_capacity(commitments.len());
+        for commitment in commitments {
+            let proof = tree.proof(&commitment.commitment_hash)
+                .expect("every batched commitment is a leaf of the batch tree");
+            let anchor = CommitmentAnchor {
+                vote_id: commitment.vote_id.clone(),
+                voter: commitment.voter.clone(),
+                commitment_hash: commitment.commitment_hash.clone(),
+                chain: receipt.chain.clone(),
+                tx_hash: receipt.tx_hash.clone(),
+                block_number: receipt.block_number,
+                anchored_at: receipt.anchored_at,
+                batch_root: Some(root.clone()),
+                batch_proof: proof.steps.into_iter()
+                    .map(|step| AnchorProofStep { sibling: step.sibling, sibling_is_left: step.sibling_is_left })
+                    .collect(),
+            };
+            self.store.save_anchor(anchor.clone()).await?;
+            anchors.push(anchor);
+        }
+
+        info!("Anchored batch of {} commitments under root {}: tx {}", anchors.len(), root, receipt.tx_hash);
+        Ok(anchors)
+    }
+
+    fn spawn_interval_flush(&self, batch: Weak<Batch>) {
+        let anchorer = self.anchorer.clone();
+        let store = self.store.clone();
+        let Some(interval) = batch.upgrade().map(|b| b.config.max_interval) else {
+            return;
+        };
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(batch) = batch.upgrade() else {
+                    break;
+                };
+                let anchoring = Anchoring { anchorer: anchorer.clone(), store: store.clone(), batch: Some(batch) };
+                if let Err(e) = anchoring.flush().await {
+                    warn!("Failed to anchor commitment batch: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Put `commitments` back at the front of the queue, ahead of anything queued since they were taken
+async fn requeue(batch: &Batch, mut commitments: Vec<Commitment>) {
+    let mut pending = batch.pending.lock().await;
+    commitments.append(&mut pending);
+    *pending = commitments;
+}
@@ -1,16 +1,34 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use shared_types::*;
-use shared_utils::crypto::generate_id;
+use shared_utils::crypto::{combined_seed, derive_key, ecies_decrypt, generate_ecies_keypair, generate_id, open, seal};
+use template_system::{MultipleChoiceTemplate, VoteTemplate};
+use commitment_engine::{CommitmentTree, InclusionProof};
 use chrono::{Utc, Duration};
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::anchoring::{anchor_confirms, batch_proof, AnchorReconciliation, Anchoring};
+use crate::models::ResultsRecomputation;
 use crate::services::VoteService;
+use crate::transitions::{TransitionObserver, SYSTEM_ACTOR};
 use crate::validators::VoteValidator;
 
+/// Creator of votes created without an authenticated caller
+pub const SYSTEM_CREATOR: &str = "system";
+
 /// Core voting engine that orchestrates the voting process
 pub struct VoteEngine {
     vote_service: Arc<dyn VoteService>,
     validator: Arc<VoteValidator>,
+    fixed_seed: Option<[u8; 32]>,
+    anchoring: Option<Anchoring>,
+    /// Serializes creates carrying a client request id so retries cannot race past the lookup
+    idempotent_create: tokio::sync::Mutex<()>,
+    /// Serializes result writes so a read-compare-store cannot interleave with another one
+    results_write: tokio::sync::Mutex<()>,
+    /// Seals the secret keys of votes with encrypted reveals before they are escrowed in the store
+    key_encryption_key: Option<[u8; 32]>,
+    observers: Vec<Arc<dyn TransitionObserver>>,
 }
 
 impl VoteEngine {
@@ -18,18 +36,82 @@ impl VoteEngine {
         Self {
             vote_service,
             validator: Arc::new(VoteValidator::new()),
+            fixed_seed: None,
+            anchoring: None,
+            idempotent_create: tokio::sync::Mutex::new(()),
+            results_write: tokio::sync::Mutex::new(()),
+            key_encryption_key: None,
+            observers: Vec::new(),
         }
     }
 
-    /// Create a new vote
+    /// Notify `observer` of every status change made through this engine
+    pub fn with_transition_observer(mut self, observer: Arc<dyn TransitionObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Seal the secret keys of votes with encrypted reveals under a key derived from `secret`.
+    ///
+    /// The sealed keys are escrowed through the vote service, so any engine configured with the
+    /// same secret can tally those votes; without one, encrypted-reveal votes are refused.
+    pub fn with_key_encryption_key(mut self, secret: &str) -> Self {
+        self.key_encryption_key = Some(derive_key(secret, "decentralized-decision-vote/reveal-key-escrow/v1"));
+        self
+    }
+
+    /// Anchor each accepted commitment through `anchoring`.
+    ///
+    /// Anchoring runs in the background after the commit is acknowledged; the
+    /// recorded anchors are reported by [`VoteEngine::verify_results`].
+    pub fn with_anchoring(mut self, anchoring: Anchoring) -> Self {
+        self.anchoring = Some(anchoring);
+        self
+    }
+
+    /// Use `seed` in place of the combined seed derived from the vote's public values.
+    ///
+    /// Intended for tests that need to assert a specific winner; production engines
+    /// should keep deriving the seed from reveals or commitments.
+    pub fn with_seed(mut self, seed: [u8; 32]) -> Self {
+        self.fixed_seed = Some(seed);
+        self
+    }
+
+    /// Create a new vote on behalf of [`SYSTEM_CREATOR`]
     pub async fn create_vote(&self, config: VoteConfig) -> Result<String, VoteError> {
+        Ok(self.create_vote_with_request_id(SYSTEM_CREATOR, config, None).await?.0)
+    }
+
+    /// Create a new vote for `creator`, or find the vote an earlier request from the same creator
+    /// with the same `client_request_id` created; returns the vote ID and whether the vote is new
+    ///
+    /// Request IDs are scoped to the creator, so two callers picking the same ID get separate votes.
+    pub async fn create_vote_with_request_id(
+        &self,
+        creator: &str,
+        config: VoteConfig,
+        client_request_id: Option<String>,
+    ) -> Result<(String, bool), VoteError> {
+        let _guard = match &client_request_id {
+            Some(request_id) => {
+                let guard = self.idempotent_create.lock().await;
+                if let Some(vote) = self.vote_service.find_vote_by_client_request_id(creator, request_id).await? {
+                    info!("Vote {} already created for request {}", vote.id, request_id);
+                    return Ok((vote.id, false));
+                }
+                Some(guard)
+            }
+            None => None,
+        };
+
         info!("Creating new vote: {}", config.title);
         
         // Validate the vote configuration
         self.validator.validate_vote_config(&config)?;
         
         // Generate vote ID
-        let vote_id = generate_id();
+        let vote_id = VoteId::generate();
         
         // Calculate phase timestamps
         let now = Utc::now();
@@ -37,15 +119,24 @@ impl VoteEngine {
         let commitment_end = now + Duration::hours(config.commitment_duration_hours as i64);
         let reveal_start = commitment_end;
         let reveal_end = reveal_start + Duration::hours(config.reveal_duration_hours as i64);
+        let reveal_key = match (config.encrypted_reveals, &self.key_encryption_key) {
+            (false, _) => None,
+            (true, Some(kek)) => Some((generate_ecies_keypair(), kek)),
+            (true, None) => {
+                return Err(VoteError::InvalidConfig {
+                    message: "Encrypted reveals need a key encryption key configured on the engine".to_string(),
+                });
+            }
+        };
         
         // Create vote object
         let vote = Vote {
-            id: vote_id.clone(),
+            id: vote_id.to_string(),
             title: config.title,
             description: config.description,
             template_id: config.template_id,
             template_params: config.template_params,
-            creator: "system".to_string(), // TODO: Get from authentication context
+            creator: creator.to_string(),
             created_at: now,
             commitment_start,
             commitment_end,
@@ -53,27 +144,42 @@ impl VoteEngine {
             reveal_end,
             status: VoteStatus::Created,
             results: None,
+            tie_break: config.tie_break,
+            legal_hold: false,
+            completion_webhook_url: config.completion_webhook_url,
+            client_request_id,
+            reveal_public_key: reveal_key.as_ref().map(|(key, _)| key.public_key.clone()),
         };
         
+        // Escrow the reveal key first so a stored vote never lacks one
+        if let Some((key, kek)) = reveal_key {
+            let sealed = seal(kek, key.secret_key.as_bytes(), vote_id.as_str().as_bytes());
+            self.vote_service.save_reveal_key(&vote_id, &sealed).await?;
+        }
         // Save to storage
         self.vote_service.create_vote(vote).await?;
         
         info!("Vote created successfully: {}", vote_id);
-        Ok(vote_id)
+        Ok((vote_id.into_string(), true))
     }
 
     /// Submit a commitment
     pub async fn commit_vote(&self, vote_id: &str, request: CommitRequest) -> Result<CommitResponse, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
         info!("Processing commitment for vote: {}", vote_id);
         
         // Get the vote
         let vote = self.vote_service.get_vote(vote_id).await?;
         
         // Validate vote state
+        self.validator.validate_accepting_ballots(&vote)?;
         self.validator.validate_commitment_phase(&vote)?;
         
         // Validate commitment
         self.validator.validate_commitment(&request)?;
+        if let Some(range_proof) = &request.range_proof {
+            self.validator.validate_range_proof(&vote, range_proof)?;
+        }
         
         // Create commitment object
         let commitment = Commitment {
@@ -83,14 +189,18 @@ impl VoteEngine {
             commitment_hash: request.commitment_hash,
             salt: request.salt,
             created_at: Utc::now(),
+            range_proof: request.range_proof,
         };
         
         // Save commitment
         self.vote_service.save_commitment(commitment.clone()).await?;
+        if let Some(anchoring) = &self.anchoring {
+            anchoring.spawn_anchor(commitment.clone());
+        }
         
         // Update vote status if needed
         if matches!(vote.status, VoteStatus::Created) {
-            self.vote_service.update_vote_status(vote_id, VoteStatus::CommitmentPhase).await?;
+            self.transition(&vote, VoteStatus::CommitmentPhase, &commitment.voter).await?;
         }
         
         info!("Commitment saved successfully for vote: {}", vote_id);
@@ -103,12 +213,14 @@ impl VoteEngine {
 
     /// Submit a reveal
     pub async fn reveal_vote(&self, vote_id: &str, request: RevealRequest) -> Result<RevealResponse, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
         info!("Processing reveal for vote: {}", vote_id);
         
         // Get the vote
         let vote = self.vote_service.get_vote(vote_id).await?;
         
         // Validate vote state
+        self.validator.validate_accepting_ballots(&vote)?;
         self.validator.validate_reveal_phase(&vote)?;
         
         // Get the commitment
@@ -117,8 +229,20 @@ impl VoteEngine {
                 message: "No commitment found for this voter".to_string() 
             })?;
         
-        // Validate reveal against commitment
-        self.validator.validate_reveal(&request, &commitment)?;
+        // Validate reveal against commitment; encrypted reveals are checked once opened at tally
+        match (&vote.reveal_public_key, &request.ciphertext) {
+            (Some(_), Some(ciphertext)) => self.validator.validate_encrypted_reveal(&request, ciphertext)?,
+            (None, None) => {
+                self.validator.validate_reveal(&request, &commitment)?;
+                self.validator.validate_range_opening(&vote, &request, &commitment)?;
+            }
+            (Some(_), None) => return Err(VoteError::InvalidReveal {
+                message: "This vote takes encrypted reveals".to_string(),
+            }),
+            (None, Some(_)) => return Err(VoteError::InvalidReveal {
+                message: "This vote takes plaintext reveals".to_string(),
+            }),
+        }
         
         // Create reveal object
         let reveal = Reveal {
@@ -128,6 +252,7 @@ impl VoteEngine {
             value: request.value,
             salt: request.salt,
             created_at: Utc::now(),
+            ciphertext: request.ciphertext,
         };
         
         // Save reveal
@@ -135,7 +260,7 @@ impl VoteEngine {
         
         // Update vote status if needed
         if matches!(vote.status, VoteStatus::CommitmentPhase) {
-            self.vote_service.update_vote_status(vote_id, VoteStatus::RevealPhase).await?;
+            self.transition(&vote, VoteStatus::RevealPhase, &reveal.voter).await?;
         }
         
         info!("Reveal saved successfully for vote: {}", vote_id);
@@ -148,10 +273,20 @@ impl VoteEngine {
 
     /// Get vote results
     pub async fn get_results(&self, vote_id: &str) -> Result<VoteResults, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
         info!("Getting results for vote: {}", vote_id);
         
         // Get the vote
         let vote = self.vote_service.get_vote(vote_id).await?;
+        if vote.status == VoteStatus::Cancelled {
+            return Err(VoteError::VoteCancelled);
+        }
+        
+        // Stored results of a completed vote are final, even if it was advanced before
+        // `reveal_end`; recomputing would only move `calculated_at`
+        if let Some(results) = Self::final_results(&vote) {
+            return Ok(results);
+        }
         
         // Check if vote has ended
         if Utc::now() < vote.reveal_end {
@@ -161,24 +296,258 @@ impl VoteEngine {
             });
         }
         
-        // Get all reveals
-        let reveals = self.vote_service.list_reveals(vote_id).await?;
-        
-        // Calculate results using template system
-        let results = self.vote_service.calculate_results(&vote, &reveals).await?;
+        // Another caller may have stored the results while this one waited for the lock
+        let _guard = self.results_write.lock().await;
+        let vote = self.vote_service.get_vote(vote_id).await?;
+        if let Some(results) = Self::final_results(&vote) {
+            return Ok(results);
+        }
+        let results = self.compute_results(&vote).await?;
         
         // Update vote with results
         self.vote_service.update_vote_results(vote_id, &results).await?;
         
         // Update vote status
-        self.vote_service.update_vote_status(vote_id, VoteStatus::Completed).await?;
+        if vote.status != VoteStatus::Completed {
+            self.transition(&vote, VoteStatus::Completed, SYSTEM_ACTOR).await?;
+        }
         
         info!("Results calculated successfully for vote: {}", vote_id);
         Ok(results)
     }
 
+    fn final_results(vote: &Vote) -> Option<VoteResults> {
+        vote.results.clone().filter(|_| vote.status == VoteStatus::Completed)
+    }
+
+    /// Recompute a completed vote's results from its current reveals
+    ///
+    /// The recomputed results are stored only when they differ from the stored ones and `apply`
+    /// is set, so callers can preview a change before committing to it. The comparison and the
+    /// store happen under the results lock, so the `before` reported is what was replaced.
+    pub async fn recompute_results(&self, vote_id: &str, apply: bool) -> Result<ResultsRecomputation, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
+        let _guard = self.results_write.lock().await;
+        let vote = self.vote_service.get_vote(vote_id).await?;
+        if vote.status != VoteStatus::Completed {
+            return Err(VoteError::InvalidState {
+                expected: "Completed".to_string(),
+                actual: format!("{:?}", vote.status),
+            });
+        }
+
+        let after = self.compute_results(&vote).await?;
+        let changed = vote.results.as_ref().is_none_or(|before| !same_outcome(before, &after));
+        let applied = changed && apply;
+        if applied {
+            self.vote_service.update_vote_results(vote_id, &after).await?;
+            info!("Stored recomputed results for vote: {}", vote_id);
+        }
+        Ok(ResultsRecomputation { vote_id: vote_id.to_string(), before: vote.results, after, changed, applied })
+    }
+
+    /// Calculate results from the vote's reveals, including winner and commitment root
+    async fn compute_results(&self, vote: &Vote) -> Result<VoteResults, VoteError> {
+        let vote_id = &VoteId::parse(vote.id.as_str())?;
+        let reveals = self.opened_reveals(vote).await?;
+        
+        // Calculate results using template system
+        let mut results = self.vote_service.calculate_results(vote, &reveals).await?;
+        if results.winner.is_none() {
+            results.winner = self.single_winner(vote, &reveals).await?;
+        }
+        results.commitment_root = Some(self.commitment_tree(vote_id.as_str()).await?.root());
+        if vote.reveal_public_key.is_some() {
+            results.reveal_secret_key = Some(self.reveal_secret_key(vote).await?);
+        }
+        Ok(results)
+    }
+
+    /// The vote's reveals, with encrypted ones decrypted and checked against their commitments
+    ///
+    /// Encrypted reveals that fail to open or do not match their commitment are left out, so
+    /// verification reports their commitments as unrevealed.
+    async fn opened_reveals(&self, vote: &Vote) -> Result<Vec<Reveal>, VoteError> {
+        let vote_id = &VoteId::parse(vote.id.as_str())?;
+        let reveals = self.vote_service.list_reveals(vote_id).await?;
+        if vote.reveal_public_key.is_none() {
+            return Ok(reveals);
+        }
+        
+        let secret_key = self.reveal_secret_key(vote).await?;
+        let mut opened = Vec::with_capacity(reveals.len());
+        for mut reveal in reveals {
+            let Some(ciphertext) = reveal.ciphertext.as_deref() else {
+                warn!("Dropping plaintext reveal from {} to encrypted vote {}", reveal.voter, vote_id);
+                continue;
+            };
+            let opening = match ecies_decrypt(&secret_key, ciphertext)
+                .map_err(|e| e.to_string())
+                .and_then(|plaintext| serde_json::from_slice::<RevealOpening>(&plaintext).map_err(|e| e.to_string()))
+            {
+                Ok(opening) => opening,
+                Err(e) => {
+                    warn!("Dropping reveal from {} to vote {}: {}", reveal.voter, vote_id, e);
+                    continue;
+                }
+            };
+            let request = RevealRequest {
+                voter: reveal.voter.clone(),
+                value: opening.value,
+                salt: opening.salt,
+                range_blinding: opening.range_blinding,
+                ciphertext: None,
+            };
+            let Some(commitment) = self.vote_service.get_commitment(vote_id, &reveal.voter).await? else {
+                continue;
+            };
+            let checked = self.validator.validate_reveal(&request, &commitment)
+                .and_then(|_| self.validator.validate_range_opening(vote, &request, &commitment));
+            if let Err(e) = checked {
+                warn!("Dropping reveal from {} to vote {}: {}", reveal.voter, vote_id, e);
+                continue;
+            }
+            reveal.value = request.value;
+            reveal.salt = request.salt;
+            opened.push(reveal);
+        }
+        Ok(opened)
+    }
+
+    /// Secret key of a vote with encrypted reveals, available only once its reveal phase is over
+    async fn reveal_secret_key(&self, vote: &Vote) -> Result<String, VoteError> {
+        if Utc::now() < vote.reveal_end {
+            return Err(VoteError::InvalidState {
+                expected: "Reveal phase over".to_string(),
+                actual: "Reveal phase still open".to_string(),
+            });
+        }
+        if let Some(secret_key) = vote.results.as_ref().and_then(|results| results.reveal_secret_key.clone()) {
+            return Ok(secret_key);
+        }
+        let unavailable = |actual: &str| VoteError::InvalidState {
+            expected: "Reveal key escrowed for this vote".to_string(),
+            actual: actual.to_string(),
+        };
+        let kek = self.key_encryption_key.as_ref().ok_or_else(|| unavailable("No key encryption key configured"))?;
+        let sealed = self.vote_service.get_reveal_key(&VoteId::parse(vote.id.as_str())?).await?
+            .ok_or_else(|| unavailable("Reveal key unavailable"))?;
+        let secret_key = open(kek, &sealed, vote.id.as_bytes())
+            .map_err(|e| unavailable(&format!("Reveal key does not open: {}", e)))?;
+        String::from_utf8(secret_key).map_err(|_| unavailable("Reveal key is not valid hex"))
+    }
+
+    /// Resolve the single winner of a multiple-choice vote, applying its tie-break policy
+    async fn single_winner(&self, vote: &Vote, reveals: &[Reveal]) -> Result<Option<WinnerOutcome>, VoteError> {
+        let template = MultipleChoiceTemplate::new();
+        if vote.template_id != template.id() {
+            return Ok(None);
+        }
+
+        let values: Vec<serde_json::Value> = reveals.iter().map(|r| r.value.clone()).collect();
+        let aggregate = template.aggregate(&values, &vote.template_params).await
+            .map_err(|e| VoteError::TemplateError { message: e.to_string() })?;
+        let seed = self.randomness_seed(vote, reveals).await?;
+        let outcome = template.winner(&aggregate, &vote.template_params, &vote.tie_break, &seed)
+            .map_err(|e| VoteError::TemplateError { message: e.to_string() })?;
+        Ok(Some(outcome))
+    }
+
+    /// Combined randomness seed for the vote, built from the source its tie-break policy names
+    async fn randomness_seed(&self, vote: &Vote, reveals: &[Reveal]) -> Result<String, VoteError> {
+        if let Some(seed) = &self.fixed_seed {
+            return Ok(seed.iter().map(|b| format!("{:02x}", b)).collect());
+        }
+        let source = match &vote.tie_break {
+            TieBreak::Random { seed_source } => *seed_source,
+            _ => SeedSource::default(),
+        };
+        let parts: Vec<String> = match source {
+            SeedSource::RevealSalts => reveals.iter().map(|r| r.salt.clone()).collect(),
+            SeedSource::CommitmentHashes => self.vote_service.list_commitments(&VoteId::parse(vote.id.as_str())?).await?
+                .into_iter()
+                .map(|c| c.commitment_hash)
+                .collect(),
+        };
+        Ok(combined_seed(&parts))
+    }
+
+    /// Merkle tree over the vote's current commitments
+    pub async fn commitment_tree(&self, vote_id: &str) -> Result<CommitmentTree, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
+        let commitments = self.vote_service.list_commitments(vote_id).await?;
+        Ok(CommitmentTree::new(commitments.into_iter().map(|c| c.commitment_hash)))
+    }
+
+    /// Proof that `commitment_hash` is among the vote's commitments
+    pub async fn commitment_proof(&self, vote_id: &str, commitment_hash: &str) -> Result<InclusionProof, VoteError> {
+        self.commitment_tree(vote_id).await?
+            .proof(commitment_hash)
+            .ok_or_else(|| VoteError::InvalidCommitment {
+                message: format!("Commitment {} is not part of vote {}", commitment_hash, vote_id),
+            })
+    }
+
+    /// Move a vote to the next phase ahead of schedule, returning the new status
+    pub async fn advance_phase(&self, vote_id: &str) -> Result<VoteStatus, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
+        let _guard = self.results_write.lock().await;
+        let vote = self.vote_service.get_vote(vote_id).await?;
+        let next = vote.status.next_phase().ok_or_else(|| VoteError::InvalidState {
+            expected: "Vote not completed or cancelled".to_string(),
+            actual: format!("{:?}", vote.status),
+        })?;
+        if next == VoteStatus::Completed {
+            let results = self.compute_results(&vote).await?;
+            self.vote_service.update_vote_results(vote_id, &results).await?;
+        }
+        self.transition(&vote, next.clone(), SYSTEM_ACTOR).await?;
+        info!("Vote {} advanced from {:?} to {:?}", vote_id, vote.status, next);
+        Ok(next)
+    }
+
+    /// Cancel a vote that has not completed yet, returning the updated vote
+    ///
+    /// Holds the results lock so a cancellation cannot race the vote's completion.
+    pub async fn cancel_vote(&self, vote_id: &str, actor: &str) -> Result<Vote, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
+        let _guard = self.results_write.lock().await;
+        let vote = self.vote_service.get_vote(vote_id).await?;
+        self.transition(&vote, VoteStatus::Cancelled, actor).await?;
+        info!("Vote {} cancelled by {}", vote_id, actor);
+        self.vote_service.get_vote(vote_id).await
+    }
+
+    /// Place or lift a legal hold on a vote, returning the updated vote
+    pub async fn set_legal_hold(&self, vote_id: &str, hold: bool) -> Result<Vote, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
+        self.vote_service.set_legal_hold(vote_id, hold).await?;
+        info!("Legal hold on vote {} set to {}", vote_id, hold);
+        self.vote_service.get_vote(vote_id).await
+    }
+
+    /// Persist a status change after checking it against the phase state machine
+    async fn transition(&self, vote: &Vote, to: VoteStatus, actor: &str) -> Result<(), VoteError> {
+        if !vote.status.can_transition_to(&to) {
+            return Err(VoteError::InvalidState {
+                expected: format!("A status that can move to {:?}", to),
+                actual: format!("{:?}", vote.status),
+            });
+        }
+        let vote_id = VoteId::parse(vote.id.as_str())?;
+        self.vote_service.update_vote_status(&vote_id, to).await?;
+        if !self.observers.is_empty() {
+            let updated = self.vote_service.get_vote(&vote_id).await?;
+            for observer in &self.observers {
+                observer.status_changed(&updated, &vote.status, actor).await;
+            }
+        }
+        Ok(())
+    }
+
     /// Get vote information
     pub async fn get_vote(&self, vote_id: &str) -> Result<Vote, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
         self.vote_service.get_vote(vote_id).await
     }
 
@@ -187,8 +556,41 @@ impl VoteEngine {
         self.vote_service.list_votes(query).await
     }
 
+    /// One page of the votes the voter has committed or revealed in, newest first
+    pub async fn get_voter_activity(&self, voter: &str, page: u32, page_size: u32) -> Result<Page<VoteParticipation>, VoteError> {
+        self.vote_service.get_voter_activity(voter, page, page_size).await
+    }
+
+    /// Commitments recorded for a vote
+    pub async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, VoteError> {
+        self.vote_service.list_commitments(&VoteId::parse(vote_id)?).await
+    }
+
+    /// Reveals recorded for a vote
+    pub async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, VoteError> {
+        self.vote_service.list_reveals(&VoteId::parse(vote_id)?).await
+    }
+
+    /// Commitments recorded for each of `vote_ids`, fetched together
+    pub async fn list_commitments_for(&self, vote_ids: &[String]) -> Result<HashMap<String, Vec<Commitment>>, VoteError> {
+        let ids = vote_ids.iter().map(|id| VoteId::parse(id.as_str())).collect::<Result<Vec<_>, _>>()?;
+        self.vote_service.list_commitments_for(&ids).await
+    }
+
+    /// Reveals recorded for each of `vote_ids`, fetched together
+    pub async fn list_reveals_for(&self, vote_ids: &[String]) -> Result<HashMap<String, Vec<Reveal>>, VoteError> {
+        let ids = vote_ids.iter().map(|id| VoteId::parse(id.as_str())).collect::<Result<Vec<_>, _>>()?;
+        self.vote_service.list_reveals_for(&ids).await
+    }
+
+    /// Number of votes in each status
+    pub async fn vote_status_counts(&self) -> Result<VoteStatusCounts, VoteError> {
+        self.vote_service.count_votes_by_status().await
+    }
+
     /// Verify vote results
     pub async fn verify_results(&self, vote_id: &str) -> Result<VerificationResult, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
         info!("Verifying results for vote: {}", vote_id);
         
         // Get the vote
@@ -203,12 +605,25 @@ impl VoteEngine {
         
         // Get all commitments and reveals
         let commitments = self.vote_service.list_commitments(vote_id).await?;
-        let reveals = self.vote_service.list_reveals(vote_id).await?;
+        let reveals = self.opened_reveals(&vote).await?;
         
         let mut all_issues = Vec::new();
         
         // Verify commitments
-        let commitment_verification = self.verify_commitments(&commitments, &reveals).await?;
+        let mut commitment_verification = self.verify_commitments(&commitments, &reveals).await?;
+        let root = CommitmentTree::new(commitments.iter().map(|c| c.commitment_hash.clone())).root();
+        if let Some(recorded) = &results.commitment_root {
+            if *recorded != root {
+                commitment_verification.commitment_issues.push(format!(
+                    "Commitment root mismatch: recorded {}, recomputed {}",
+                    recorded, root
+                ));
+            }
+        }
+        commitment_verification.commitment_root = Some(root);
+        if let Some(anchoring) = &self.anchoring {
+            self.verify_anchors(anchoring, &commitments, &mut commitment_verification).await?;
+        }
         all_issues.extend(commitment_verification.commitment_issues.clone());
         
         // Verify results
@@ -266,9 +681,85 @@ impl VoteEngine {
             verified_commitments: verified_count,
             failed_commitments: failed_count,
             commitment_issues: issues,
+            commitment_root: None,
+            anchors: Vec::new(),
+            unanchored_voters: Vec::new(),
         })
     }
 
+    /// Re-anchor every commitment of the vote that lacks a confirmed anchor
+    ///
+    /// A commitment counts as anchored when its anchor is for the stored hash, is included in a
+    /// block and, for a batched anchor, has a proof leading to the batch root. Everything else is
+    /// submitted again right away.
+    pub async fn reconcile_anchors(&self, vote_id: &str) -> Result<AnchorReconciliation, VoteError> {
+        let vote_id = &VoteId::parse(vote_id)?;
+        let anchoring = self.anchoring.as_ref().ok_or_else(|| VoteError::InvalidConfig {
+            message: "Anchoring is not configured".to_string(),
+        })?;
+        self.vote_service.get_vote(vote_id).await?;
+
+        let commitments = self.vote_service.list_commitments(vote_id).await?;
+        let anchors = anchoring.store().list_anchors(vote_id).await?;
+        let checked = commitments.len();
+        let missing: Vec<Commitment> = commitments.into_iter()
+            .filter(|commitment| !anchors.iter().any(|a| a.voter == commitment.voter && anchor_confirms(a, commitment)))
+            .collect();
+        let confirmed = checked - missing.len();
+
+        let resubmitted = anchoring.resubmit(missing).await?;
+        info!(
+            "Reconciled anchors for vote {}: {} confirmed, {} resubmitted",
+            vote_id, confirmed, resubmitted.len()
+        );
+        Ok(AnchorReconciliation {
+            vote_id: vote_id.to_string(),
+            checked,
+            confirmed,
+            resubmitted,
+        })
+    }
+
+    /// Compare recorded anchors with the stored commitments
+    ///
+    /// An anchor for a different hash than the one stored is an issue; a missing
+    /// anchor is only reported, since anchoring is best-effort.
+    async fn verify_anchors(
+        &self,
+        anchoring: &Anchoring,
+        commitments: &[Commitment],
+        verification: &mut CommitmentVerification,
+    ) -> Result<(), VoteError> {
+        let vote_id = match commitments.first() {
+            Some(commitment) => &commitment.vote_id,
+            None => return Ok(()),
+        };
+        let anchors = anchoring.store().list_anchors(vote_id).await?;
+        for commitment in commitments {
+            match anchors.iter().find(|a| a.voter == commitment.voter) {
+                Some(anchor) if anchor.commitment_hash != commitment.commitment_hash => {
+                    verification.commitment_issues.push(format!(
+                        "Anchored hash for voter {} does not match the stored commitment (tx {})",
+                        commitment.voter, anchor.tx_hash
+                    ));
+                }
+                Some(anchor) => {
+                    if let Some(root) = &anchor.batch_root {
+                        if !batch_proof(anchor).verify(root) {
+                            verification.commitment_issues.push(format!(
+                                "Batch proof for voter {} does not lead to anchored root {} (tx {})",
+                                commitment.voter, root, anchor.tx_hash
+                            ));
+                        }
+                    }
+                }
+                None => verification.unanchored_voters.push(commitment.voter.clone()),
+            }
+        }
+        verification.anchors = anchors;
+        Ok(())
+    }
+
     /// Verify results calculation
     async fn verify_results_calculation(
         &self,
@@ -307,3 +798,8 @@ impl VoteEngine {
         })
     }
 }
+
+/// Whether two results report the same outcome, whenever each was calculated
+fn same_outcome(a: &VoteResults, b: &VoteResults) -> bool {
+    a.total_votes == b.total_votes && a.results == b.results && a.winner == b.winner && a.commitment_root == b.commitment_root
+}
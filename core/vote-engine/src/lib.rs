@@ -1,9 +1,15 @@
+pub mod anchoring;
 pub mod engine;
 pub mod models;
+pub mod report;
 pub mod services;
+pub mod transitions;
 pub mod validators;
 
+pub use anchoring::*;
 pub use engine::*;
 pub use models::*;
+pub use report::*;
 pub use services::*;
+pub use transitions::*;
 pub use validators::*;
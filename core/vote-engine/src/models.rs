@@ -1,6 +1,7 @@
 // Additional models specific to the vote engine
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use shared_types::VoteResults;
 
 /// Vote statistics for monitoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,3 +44,17 @@ pub struct AuditEvent {
     pub user: Option<String>,
     pub metadata: serde_json::Value,
 }
+
+/// Outcome of recomputing a completed vote's results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultsRecomputation {
+    pub vote_id: String,
+    /// Results stored before the recomputation
+    pub before: Option<VoteResults>,
+    /// Results recomputed from the current reveals
+    pub after: VoteResults,
+    /// Whether the recomputed results differ from the stored ones, ignoring `calculated_at`
+    pub changed: bool,
+    /// Whether the recomputed results were stored
+    pub applied: bool,
+}
@@ -0,0 +1,186 @@
+//! Self-contained HTML report of a vote's results
+
+use shared_types::*;
+use shared_utils::crypto::hash_value;
+
+use crate::engine::VoteEngine;
+
+impl VoteEngine {
+    /// Render a shareable HTML report of the vote's stored results
+    ///
+    /// The report covers the configuration, participation, per-option tally, winner and
+    /// verification status, and ends with the receipt hash from [`report_receipt_hash`] so a
+    /// copy can be checked against the service. It has no external assets.
+    pub async fn generate_vote_report(&self, vote_id: &str) -> Result<String, VoteError> {
+        let vote = self.get_vote(vote_id).await?;
+        let results = vote.results.clone().ok_or_else(|| VoteError::InvalidState {
+            expected: "Vote with results".to_string(),
+            actual: "Vote without results".to_string(),
+        })?;
+        let verification = self.verify_results(vote_id).await?;
+        Ok(render_report(&vote, &results, &verification))
+    }
+}
+
+/// SHA-256 over the vote ID, commitment root and results, printed at the foot of the report
+pub fn report_receipt_hash(results: &VoteResults) -> String {
+    hash_value(&format!(
+        "vote-report|{}|{}|{}|{}",
+        results.vote_id,
+        results.commitment_root.as_deref().unwrap_or(""),
+        results.total_votes,
+        results.results,
+    ))
+}
+
+/// Options and their counts, highest first; empty when the results are not a count per option
+fn tally(results: &VoteResults) -> Vec<(String, u64)> {
+    let Some(counts) = results.results.as_object() else { return Vec::new() };
+    let mut tally: Vec<(String, u64)> = counts
+        .iter()
+        .filter_map(|(option, count)| Some((option_label(option), count.as_u64()?)))
+        .collect();
+    tally.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    tally
+}
+
+/// Tally keys are JSON-encoded reveal values; show strings without their quotes
+fn option_label(key: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(key) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => key.to_string(),
+    }
+}
+
+/// The recorded winner, or else the option with the single highest count
+fn winner(results: &VoteResults, tally: &[(String, u64)]) -> Option<String> {
+    if let Some(outcome) = &results.winner {
+        return outcome.winner.clone();
+    }
+    match tally {
+        [(top, first), rest @ ..] if rest.first().is_none_or(|(_, second)| second < first) => Some(top.clone()),
+        _ => None,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn render_report(vote: &Vote, results: &VoteResults, verification: &VerificationResult) -> String {
+    let tally = tally(results);
+    let commitments = verification.commitment_verification.total_commitments;
+    let reveals = verification.results_verification.total_reveals;
+    let participation = if commitments == 0 { 0.0 } else { reveals as f64 * 100.0 / commitments as f64 };
+    let time = |t: &chrono::DateTime<chrono::Utc>| t.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+
+    let rows = if tally.is_empty() {
+        format!("<tr><td colspan=\"2\"><code>{}</code></td></tr>", escape(&results.results.to_string()))
+    } else {
+        tally
+            .iter()
+            .map(|(option, count)| format!("<tr><td>{}</td><td>{}</td></tr>", escape(option), count))
+            .collect::<Vec<_>>()
+            .join("")
+    };
+    let winner = match winner(results, &tally) {
+        Some(winner) => escape(&winner),
+        None => "No single winner".to_string(),
+    };
+    let tied = results.winner.as_ref().filter(|w| !w.tied.is_empty()).map_or(String::new(), |w| {
+        let tied: Vec<String> = w.tied.iter().map(|o| escape(o)).collect();
+        format!("<p>Tied options: {}</p>", tied.join(", "))
+    });
+    let (status_class, status) = if verification.is_valid { ("success", "Verified") } else { ("error", "Verification failed") };
+    let issues = verification
+        .issues
+        .iter()
+        .map(|issue| format!("<li>{}</li>", escape(issue)))
+        .collect::<Vec<_>>()
+        .join("");
+
+    format!(r#"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <title>Vote report: {title}</title>
+    <style>
+        body {{ font-family: Arial, sans-serif; margin: 20px; }}
+        .header {{ background-color: #f0f0f0; padding: 20px; border-radius: 5px; }}
+        .section {{ margin: 20px 0; padding: 15px; border: 1px solid #ddd; border-radius: 5px; }}
+        .metric {{ display: inline-block; margin: 10px; padding: 10px; background-color: #e8f4f8; border-radius: 3px; }}
+        table {{ border-collapse: collapse; }}
+        td, th {{ padding: 5px 15px; border-bottom: 1px solid #ddd; text-align: left; }}
+        code {{ word-break: break-all; }}
+        .success {{ color: #28a745; }}
+        .error {{ color: #dc3545; }}
+    </style>
+</head>
+<body>
+    <div class="header">
+        <h1>{title}</h1>
+        <p>{description}</p>
+        <p>Vote <code>{vote_id}</code>, results calculated {calculated_at}</p>
+    </div>
+
+    <div class="section">
+        <h2>Configuration</h2>
+        <div class="metric">Template: {template}</div>
+        <div class="metric">Status: {status_name:?}</div>
+        <div class="metric">Commitment phase: {commitment_start} to {commitment_end}</div>
+        <div class="metric">Reveal phase: {reveal_start} to {reveal_end}</div>
+    </div>
+
+    <div class="section">
+        <h2>Participation</h2>
+        <div class="metric">Commitments: {commitments}</div>
+        <div class="metric">Reveals: {reveals}</div>
+        <div class="metric">Reveal rate: {participation:.1}%</div>
+    </div>
+
+    <div class="section">
+        <h2>Tally</h2>
+        <table>
+            <tr><th>Option</th><th>Votes</th></tr>
+            {rows}
+        </table>
+        <h3>Winner: <span class="winner">{winner}</span></h3>
+        {tied}
+    </div>
+
+    <div class="section">
+        <h2>Verification</h2>
+        <p class="{status_class}">{status}</p>
+        <ul>{issues}</ul>
+        <p>Commitment root: <code class="commitment-root">{commitment_root}</code></p>
+    </div>
+
+    <div class="section">
+        <h2>Receipt</h2>
+        <p>Receipt hash: <code class="receipt">{receipt}</code></p>
+    </div>
+</body>
+</html>
+"#,
+        title = escape(&vote.title),
+        description = escape(&vote.description),
+        vote_id = escape(&vote.id),
+        calculated_at = time(&results.calculated_at),
+        template = escape(&vote.template_id),
+        status_name = vote.status,
+        commitment_start = time(&vote.commitment_start),
+        commitment_end = time(&vote.commitment_end),
+        reveal_start = time(&vote.reveal_start),
+        reveal_end = time(&vote.reveal_end),
+        commitment_root = escape(
+            results.commitment_root.as_deref()
+                .or(verification.commitment_verification.commitment_root.as_deref())
+                .unwrap_or("none"),
+        ),
+        receipt = report_receipt_hash(results),
+    )
+}
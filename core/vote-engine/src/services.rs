@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 use shared_types::*;
 
@@ -6,19 +7,110 @@ use shared_types::*;
 #[async_trait]
 pub trait VoteService: Send + Sync {
     async fn create_vote(&self, vote: Vote) -> Result<(), VoteError>;
-    async fn get_vote(&self, id: &str) -> Result<Vote, VoteError>;
+    async fn get_vote(&self, id: &VoteId) -> Result<Vote, VoteError>;
     async fn list_votes(&self, query: ListQuery) -> Result<Page<Vote>, VoteError>;
-    async fn update_vote_status(&self, id: &str, status: VoteStatus) -> Result<(), VoteError>;
-    async fn update_vote_results(&self, id: &str, results: &VoteResults) -> Result<(), VoteError>;
+    async fn update_vote_status(&self, id: &VoteId, status: VoteStatus) -> Result<(), VoteError>;
+    async fn update_vote_results(&self, id: &VoteId, results: &VoteResults) -> Result<(), VoteError>;
     
     async fn save_commitment(&self, commitment: Commitment) -> Result<(), VoteError>;
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, VoteError>;
-    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, VoteError>;
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, VoteError>;
+    async fn list_commitments(&self, vote_id: &VoteId) -> Result<Vec<Commitment>, VoteError>;
     
     async fn save_reveal(&self, reveal: Reveal) -> Result<(), VoteError>;
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, VoteError>;
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, VoteError>;
     
     async fn calculate_results(&self, vote: &Vote, reveals: &[Reveal]) -> Result<VoteResults, VoteError>;
+
+    /// Place or lift a legal hold, which exempts the vote from retention cleanup
+    async fn set_legal_hold(&self, _id: &VoteId, _hold: bool) -> Result<(), VoteError> {
+        Err(VoteError::StorageError { message: "Legal holds are not supported by this store".to_string() })
+    }
+
+    /// Escrow the sealed secret key of a vote with encrypted reveals
+    ///
+    /// The engine seals the key before handing it over, so stores keep it as an opaque string.
+    async fn save_reveal_key(&self, _id: &VoteId, _sealed_key: &str) -> Result<(), VoteError> {
+        Err(VoteError::StorageError { message: "Reveal key escrow is not supported by this store".to_string() })
+    }
+
+    /// The sealed secret key escrowed for a vote, if any
+    async fn get_reveal_key(&self, _id: &VoteId) -> Result<Option<String>, VoteError> {
+        Ok(None)
+    }
+
+    /// The vote `creator` created with `client_request_id`, if any
+    ///
+    /// The default pages through `list_votes`; stores that index the request id should override it.
+    async fn find_vote_by_client_request_id(&self, creator: &str, client_request_id: &str) -> Result<Option<Vote>, VoteError> {
+        let mut page = 0;
+        loop {
+            let query = ListQuery { page, page_size: 100, status: None, creator: Some(creator.to_string()) };
+            let votes = self.list_votes(query).await?;
+            let found = votes.items.into_iter().find(|vote| {
+                vote.creator == creator && vote.client_request_id.as_deref() == Some(client_request_id)
+            });
+            if found.is_some() || page + 1 >= votes.total_pages {
+                return Ok(found);
+            }
+            page += 1;
+        }
+    }
+
+    /// Commitments of each of `vote_ids`, keyed by vote ID
+    async fn list_commitments_for(&self, vote_ids: &[VoteId]) -> Result<HashMap<String, Vec<Commitment>>, VoteError> {
+        let mut commitments = HashMap::new();
+        for id in vote_ids {
+            commitments.insert(id.to_string(), self.list_commitments(id).await?);
+        }
+        Ok(commitments)
+    }
+
+    /// Reveals of each of `vote_ids`, keyed by vote ID
+    async fn list_reveals_for(&self, vote_ids: &[VoteId]) -> Result<HashMap<String, Vec<Reveal>>, VoteError> {
+        let mut reveals = HashMap::new();
+        for id in vote_ids {
+            reveals.insert(id.to_string(), self.list_reveals(id).await?);
+        }
+        Ok(reveals)
+    }
+
+    /// Number of votes in each status
+    async fn count_votes_by_status(&self) -> Result<VoteStatusCounts, VoteError> {
+        let mut counts = VoteStatusCounts::default();
+        let mut page = 0;
+        loop {
+            let listed = self.list_votes(ListQuery { page, page_size: 100, status: None, creator: None }).await?;
+            listed.items.iter().for_each(|vote| counts.add(&vote.status));
+            page += 1;
+            if page >= listed.total_pages {
+                return Ok(counts);
+            }
+        }
+    }
+
+    /// Every vote `voter` has a commitment or reveal in, newest first
+    ///
+    /// The default checks each vote in turn; services backed by an index over `voter` should override it.
+    async fn get_voter_activity(&self, voter: &str, page: u32, page_size: u32) -> Result<Page<VoteParticipation>, VoteError> {
+        let mut votes = Vec::new();
+        let mut listed_page = 0;
+        loop {
+            let listed = self.list_votes(ListQuery { page: listed_page, page_size: 100, status: None, creator: None }).await?;
+            for vote in listed.items {
+                let id = VoteId::parse(vote.id.clone())?;
+                let committed_at = self.get_commitment(&id, voter).await?.map(|c| c.created_at);
+                let revealed_at = self.list_reveals(&id).await?.into_iter().find(|r| r.voter == voter).map(|r| r.created_at);
+                if committed_at.is_some() || revealed_at.is_some() {
+                    let participation = VoteParticipation { vote_id: vote.id, title: vote.title, status: vote.status, committed_at, revealed_at };
+                    votes.push((vote.created_at, participation));
+                }
+            }
+            listed_page += 1;
+            if listed_page >= listed.total_pages {
+                return Ok(VoteParticipation::newest_first(votes, page, page_size));
+            }
+        }
+    }
 }
 
 /// In-memory implementation of VoteService for testing
@@ -26,6 +118,7 @@ pub struct MemoryVoteService {
     votes: Arc<tokio::sync::RwLock<std::collections::HashMap<String, Vote>>>,
     commitments: Arc<tokio::sync::RwLock<std::collections::HashMap<String, Commitment>>>,
     reveals: Arc<tokio::sync::RwLock<std::collections::HashMap<String, Reveal>>>,
+    reveal_keys: Arc<tokio::sync::RwLock<std::collections::HashMap<String, String>>>,
 }
 
 impl Default for MemoryVoteService {
@@ -40,6 +133,7 @@ impl MemoryVoteService {
             votes: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
             commitments: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
             reveals: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            reveal_keys: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
         }
     }
 }
@@ -52,9 +146,9 @@ impl VoteService for MemoryVoteService {
         Ok(())
     }
 
-    async fn get_vote(&self, id: &str) -> Result<Vote, VoteError> {
+    async fn get_vote(&self, id: &VoteId) -> Result<Vote, VoteError> {
         let votes = self.votes.read().await;
-        votes.get(id)
+        votes.get(id.as_str())
             .cloned()
             .ok_or_else(|| VoteError::VoteNotFound { id: id.to_string() })
     }
@@ -82,9 +176,9 @@ impl VoteService for MemoryVoteService {
         })
     }
 
-    async fn update_vote_status(&self, id: &str, status: VoteStatus) -> Result<(), VoteError> {
+    async fn update_vote_status(&self, id: &VoteId, status: VoteStatus) -> Result<(), VoteError> {
         let mut votes = self.votes.write().await;
-        if let Some(vote) = votes.get_mut(id) {
+        if let Some(vote) = votes.get_mut(id.as_str()) {
             vote.status = status;
             Ok(())
         } else {
@@ -92,9 +186,9 @@ impl VoteService for MemoryVoteService {
         }
     }
 
-    async fn update_vote_results(&self, id: &str, results: &VoteResults) -> Result<(), VoteError> {
+    async fn update_vote_results(&self, id: &VoteId, results: &VoteResults) -> Result<(), VoteError> {
         let mut votes = self.votes.write().await;
-        if let Some(vote) = votes.get_mut(id) {
+        if let Some(vote) = votes.get_mut(id.as_str()) {
             vote.results = Some(results.clone());
             Ok(())
         } else {
@@ -108,18 +202,18 @@ impl VoteService for MemoryVoteService {
         Ok(())
     }
 
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, VoteError> {
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, VoteError> {
         let commitments = self.commitments.read().await;
         let commitment = commitments.values()
-            .find(|c| c.vote_id == vote_id && c.voter == voter)
+            .find(|c| c.vote_id == vote_id.as_str() && c.voter == voter)
             .cloned();
         Ok(commitment)
     }
 
-    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, VoteError> {
+    async fn list_commitments(&self, vote_id: &VoteId) -> Result<Vec<Commitment>, VoteError> {
         let commitments = self.commitments.read().await;
         let vote_commitments: Vec<Commitment> = commitments.values()
-            .filter(|c| c.vote_id == vote_id)
+            .filter(|c| c.vote_id == vote_id.as_str())
             .cloned()
             .collect();
         Ok(vote_commitments)
@@ -131,15 +225,67 @@ impl VoteService for MemoryVoteService {
         Ok(())
     }
 
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, VoteError> {
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, VoteError> {
         let reveals = self.reveals.read().await;
         let vote_reveals: Vec<Reveal> = reveals.values()
-            .filter(|r| r.vote_id == vote_id)
+            .filter(|r| r.vote_id == vote_id.as_str())
             .cloned()
             .collect();
         Ok(vote_reveals)
     }
 
+    async fn list_commitments_for(&self, vote_ids: &[VoteId]) -> Result<HashMap<String, Vec<Commitment>>, VoteError> {
+        let mut grouped: HashMap<String, Vec<Commitment>> = vote_ids.iter().map(|id| (id.to_string(), Vec::new())).collect();
+        for commitment in self.commitments.read().await.values() {
+            if let Some(commitments) = grouped.get_mut(&commitment.vote_id) {
+                commitments.push(commitment.clone());
+            }
+        }
+        Ok(grouped)
+    }
+
+    async fn list_reveals_for(&self, vote_ids: &[VoteId]) -> Result<HashMap<String, Vec<Reveal>>, VoteError> {
+        let mut grouped: HashMap<String, Vec<Reveal>> = vote_ids.iter().map(|id| (id.to_string(), Vec::new())).collect();
+        for reveal in self.reveals.read().await.values() {
+            if let Some(reveals) = grouped.get_mut(&reveal.vote_id) {
+                reveals.push(reveal.clone());
+            }
+        }
+        Ok(grouped)
+    }
+
+    async fn count_votes_by_status(&self) -> Result<VoteStatusCounts, VoteError> {
+        let mut counts = VoteStatusCounts::default();
+        self.votes.read().await.values().for_each(|vote| counts.add(&vote.status));
+        Ok(counts)
+    }
+
+    async fn set_legal_hold(&self, id: &VoteId, hold: bool) -> Result<(), VoteError> {
+        let mut votes = self.votes.write().await;
+        if let Some(vote) = votes.get_mut(id.as_str()) {
+            vote.legal_hold = hold;
+            Ok(())
+        } else {
+            Err(VoteError::VoteNotFound { id: id.to_string() })
+        }
+    }
+
+    async fn save_reveal_key(&self, id: &VoteId, sealed_key: &str) -> Result<(), VoteError> {
+        self.reveal_keys.write().await.insert(id.to_string(), sealed_key.to_string());
+        Ok(())
+    }
+
+    async fn get_reveal_key(&self, id: &VoteId) -> Result<Option<String>, VoteError> {
+        Ok(self.reveal_keys.read().await.get(id.as_str()).cloned())
+    }
+
+    async fn get_voter_activity(&self, voter: &str, page: u32, page_size: u32) -> Result<Page<VoteParticipation>, VoteError> {
+        let votes = self.votes.read().await;
+        let commitments = self.commitments.read().await;
+        let reveals = self.reveals.read().await;
+        Ok(VoteParticipation::page_in_memory(voter, &votes, commitments.values(), reveals.values(), page, page_size))
+    }
+
     async fn calculate_results(&self, vote: &Vote, reveals: &[Reveal]) -> Result<VoteResults, VoteError> {
         // Simple aggregation for now - in real implementation, this would use the template system
         let total_votes = reveals.len() as u32;
@@ -158,6 +304,9 @@ impl VoteService for MemoryVoteService {
             results: serde_json::to_value(results_map)
                 .map_err(VoteError::SerializationError)?,
             calculated_at: chrono::Utc::now(),
+            winner: None,
+            commitment_root: None,
+            reveal_secret_key: None,
         };
         
         Ok(results)
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use shared_types::{Vote, VoteStatus};
+
+/// Actor of transitions no caller asked for, such as completing a vote once its results are read
+pub const SYSTEM_ACTOR: &str = "system";
+
+/// Told about every status change the engine persists
+///
+/// Observers run after the change is stored, in the order they were added; they cannot veto it.
+#[async_trait]
+pub trait TransitionObserver: Send + Sync {
+    /// `vote` is read back after the change, so a completed vote carries its stored results;
+    /// `actor` is the voter whose ballot moved the vote on, the caller that cancelled it, or
+    /// [`SYSTEM_ACTOR`]
+    async fn status_changed(&self, vote: &Vote, from: &VoteStatus, actor: &str);
+}
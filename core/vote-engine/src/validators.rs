@@ -1,5 +1,5 @@
 use shared_types::*;
-use shared_utils::{crypto::verify_commitment, validation::*};
+use shared_utils::{crypto::{is_ecies_ciphertext, verify_commitment}, validation::*};
 use chrono::Utc;
 
 /// Validator for vote-related operations
@@ -79,6 +79,16 @@ impl VoteValidator {
         Ok(())
     }
 
+    /// Validate that the vote still takes commitments and reveals: cancelled and completed votes are closed
+    /// whatever their windows say
+    pub fn validate_accepting_ballots(&self, vote: &Vote) -> Result<(), VoteError> {
+        match vote.status {
+            VoteStatus::Cancelled => Err(VoteError::VoteCancelled),
+            VoteStatus::Completed => Err(VoteError::VoteEnded),
+            _ => Ok(()),
+        }
+    }
+
     /// Validate that the vote is in reveal phase
     pub fn validate_reveal_phase(&self, vote: &Vote) -> Result<(), VoteError> {
         let now = Utc::now();
@@ -144,4 +154,77 @@ impl VoteValidator {
         
         Ok(())
     }
+
+    /// Validate the shape of an encrypted reveal; its contents are checked once opened at tally
+    pub fn validate_encrypted_reveal(&self, request: &RevealRequest, ciphertext: &str) -> Result<(), VoteError> {
+        if !request.value.is_null() || !request.salt.is_empty() {
+            return Err(VoteError::InvalidReveal {
+                message: "Encrypted reveals must not carry a plaintext value or salt".to_string(),
+            });
+        }
+        if !is_ecies_ciphertext(ciphertext) {
+            return Err(VoteError::InvalidReveal {
+                message: "Not an encrypted reveal".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate a range proof submitted with a commitment against the vote's numeric range
+    pub fn validate_range_proof(&self, vote: &Vote, proof: &RangeProof) -> Result<(), VoteError> {
+        let (min, max) = range_bounds(vote)?;
+        verify_range_proof(proof, min, max)
+    }
+
+    /// Check that a reveal opens the range proof stored with its commitment
+    pub fn validate_range_opening(&self, vote: &Vote, request: &RevealRequest, commitment: &Commitment) -> Result<(), VoteError> {
+        let Some(proof) = &commitment.range_proof else {
+            return Ok(());
+        };
+        let (min, _) = range_bounds(vote)?;
+        let invalid = |message: &str| VoteError::InvalidReveal { message: message.to_string() };
+        let value = request.value.as_u64().ok_or_else(|| invalid("Range-proven values must be non-negative integers"))?;
+        let blinding = request.range_blinding.as_deref().ok_or_else(|| invalid("range_blinding is required to open the range proof"))?;
+        if !open_range_proof(proof, value, min, blinding)? {
+            return Err(invalid("Reveal does not match range proof commitment"));
+        }
+        Ok(())
+    }
+}
+
+/// Integer bounds of a `numeric_range` vote, which range proofs are checked against
+fn range_bounds(vote: &Vote) -> Result<(u64, u64), VoteError> {
+    let invalid = |message: &str| VoteError::InvalidCommitment { message: message.to_string() };
+    if vote.template_id != "numeric_range" {
+        return Err(invalid("Range proofs are only supported for numeric_range votes"));
+    }
+    let bound = |key: &str| vote.template_params.get(key).and_then(|v| v.as_u64());
+    match (bound("min"), bound("max")) {
+        (Some(min), Some(max)) => Ok((min, max)),
+        _ => Err(invalid("Range proofs require non-negative integer min and max parameters")),
+    }
+}
+
+#[cfg(feature = "zkp")]
+fn verify_range_proof(proof: &RangeProof, min: u64, max: u64) -> Result<(), VoteError> {
+    commitment_engine::verify_range(proof, min, max).map_err(|e| VoteError::InvalidCommitment {
+        message: format!("Invalid range proof: {}", e),
+    })
+}
+
+#[cfg(feature = "zkp")]
+fn open_range_proof(proof: &RangeProof, value: u64, min: u64, blinding: &str) -> Result<bool, VoteError> {
+    commitment_engine::verify_range_opening(proof, value, min, blinding).map_err(|e| VoteError::InvalidReveal {
+        message: e.to_string(),
+    })
+}
+
+#[cfg(not(feature = "zkp"))]
+fn verify_range_proof(_proof: &RangeProof, _min: u64, _max: u64) -> Result<(), VoteError> {
+    Err(VoteError::InvalidCommitment { message: "Range proofs are not supported by this build".to_string() })
+}
+
+#[cfg(not(feature = "zkp"))]
+fn open_range_proof(_proof: &RangeProof, _value: u64, _min: u64, _blinding: &str) -> Result<bool, VoteError> {
+    Err(VoteError::InvalidReveal { message: "Range proofs are not supported by this build".to_string() })
 }
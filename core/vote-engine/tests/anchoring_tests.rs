@@ -0,0 +1,382 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use shared_types::*;
+use vote_engine::*;
+
+/// Stand-in for a blockchain client: every anchor lands in the next block
+struct MockChain {
+    next_block: AtomicU64,
+    fail: bool,
+}
+
+impl MockChain {
+    fn new() -> Self {
+        Self { next_block: AtomicU64::new(100), fail: false }
+    }
+
+    fn failing() -> Self {
+        Self { next_block: AtomicU64::new(100), fail: true }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommitmentAnchorer for MockChain {
+    async fn anchor(&self, commitment: &Commitment) -> Result<CommitmentAnchor, VoteError> {
+        if self.fail {
+            return Err(VoteError::StorageError { message: "chain unavailable".to_string() });
+        }
+        let block = self.next_block.fetch_add(1, Ordering::SeqCst);
+        Ok(CommitmentAnchor {
+            vote_id: commitment.vote_id.clone(),
+            voter: commitment.voter.clone(),
+            commitment_hash: commitment.commitment_hash.clone(),
+            chain: "mock".to_string(),
+            tx_hash: format!("0x{:064x}", block),
+            block_number: Some(block),
+            anchored_at: Utc::now(),
+            batch_root: None,
+            batch_proof: Vec::new(),
+        })
+    }
+}
+
+/// Stand-in for a chain that only accepts batch roots, counting submissions
+#[derive(Default)]
+struct BatchingChain {
+    submissions: AtomicU64,
+    offline: AtomicBool,
+}
+
+#[async_trait::async_trait]
+impl CommitmentAnchorer for BatchingChain {
+    async fn anchor(&self, _commitment: &Commitment) -> Result<CommitmentAnchor, VoteError> {
+        panic!("batched anchoring must not submit commitments one by one");
+    }
+
+    async fn anchor_root(&self, _root: &str, _batch_size: usize) -> Result<AnchorReceipt, VoteError> {
+        if self.offline.load(Ordering::SeqCst) {
+            return Err(VoteError::StorageError { message: "chain unreachable".to_string() });
+        }
+        let block = self.submissions.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(AnchorReceipt {
+            chain: "mock".to_string(),
+            tx_hash: format!("0x{:064x}", block),
+            block_number: Some(block),
+            anchored_at: Utc::now(),
+        })
+    }
+}
+
+fn batched(chain: Arc<BatchingChain>, store: Arc<MemoryAnchorStore>, max_batch_size: usize) -> Anchoring {
+    Anchoring::batched(chain, store, BatchConfig {
+        max_batch_size,
+        max_interval: Duration::from_secs(3600),
+    })
+}
+
+fn vote_config() -> VoteConfig {
+    VoteConfig {
+        title: "Anchored Vote".to_string(),
+        description: "Commitments are anchored on a mock chain".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: serde_json::json!({}),
+        commitment_duration_hours: 24,
+        reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }
+}
+
+fn commit_request(voter: &str, hash_byte: char) -> CommitRequest {
+    CommitRequest {
+        voter: voter.to_string(),
+        commitment_hash: hash_byte.to_string().repeat(64),
+        salt: "test_salt".to_string(),
+        range_proof: None,
+    }
+}
+
+/// Wait for the background anchoring task to record `count` anchors
+async fn wait_for_anchors(store: &MemoryAnchorStore, vote_id: &str, count: usize) -> Vec<CommitmentAnchor> {
+    for _ in 0..1000 {
+        let anchors = store.list_anchors(vote_id).await.unwrap();
+        if anchors.len() >= count {
+            return anchors;
+        }
+        tokio::task::yield_now().await;
+    }
+    panic!("anchors were not recorded in time");
+}
+
+async fn finish_vote(service: &MemoryVoteService, vote_id: &str) {
+    service.update_vote_results(&VoteId::parse(vote_id).unwrap(), &VoteResults {
+        vote_id: vote_id.to_string(),
+        total_votes: 0,
+        results: serde_json::json!({}),
+        calculated_at: Utc::now(),
+        winner: None,
+        commitment_root: None,
+        reveal_secret_key: None,
+    }).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_committed_hash_gets_anchor_record() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(Anchoring::new(Arc::new(MockChain::new()), anchors.clone()));
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("alice", 'a')).await.unwrap();
+
+    let recorded = wait_for_anchors(&anchors, &vote_id, 1).await;
+    assert_eq!(recorded[0].voter, "alice");
+    assert_eq!(recorded[0].commitment_hash, "a".repeat(64));
+    assert_eq!(recorded[0].block_number, Some(100));
+    assert!(!recorded[0].tx_hash.is_empty());
+
+    finish_vote(&service, &vote_id).await;
+    let verification = engine.verify_results(&vote_id).await.unwrap();
+    assert_eq!(verification.commitment_verification.anchors, recorded);
+    assert!(verification.commitment_verification.unanchored_voters.is_empty());
+}
+
+#[tokio::test]
+async fn test_anchoring_failure_does_not_block_commit() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(Anchoring::new(Arc::new(MockChain::failing()), anchors.clone()));
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    let response = engine.commit_vote(&vote_id, commit_request("bob", 'b')).await.unwrap();
+    assert!(response.success);
+
+    finish_vote(&service, &vote_id).await;
+    let verification = engine.verify_results(&vote_id).await.unwrap();
+    assert!(verification.commitment_verification.anchors.is_empty());
+    assert_eq!(verification.commitment_verification.unanchored_voters, vec!["bob".to_string()]);
+}
+
+#[tokio::test]
+async fn test_mismatched_anchor_is_reported() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(Anchoring::new(Arc::new(MockChain::new()), anchors.clone()));
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("carol", 'c')).await.unwrap();
+    let mut anchor = wait_for_anchors(&anchors, &vote_id, 1).await.remove(0);
+
+    // The stored commitment no longer matches what was anchored
+    anchor.commitment_hash = "d".repeat(64);
+    anchors.save_anchor(anchor).await.unwrap();
+
+    finish_vote(&service, &vote_id).await;
+    let verification = engine.verify_results(&vote_id).await.unwrap();
+    assert!(!verification.is_valid);
+    assert!(verification.issues.iter().any(|issue| issue.contains("Anchored hash for voter carol")));
+}
+
+#[tokio::test]
+async fn test_batch_is_anchored_with_one_submission() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let chain = Arc::new(BatchingChain::default());
+    let anchoring = batched(chain.clone(), anchors.clone(), 100);
+    let engine = VoteEngine::new(service.clone()).with_anchoring(anchoring.clone());
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    let voters = [("alice", 'a'), ("bob", 'b'), ("carol", 'c'), ("dave", 'd'), ("erin", 'e')];
+    for (voter, hash_byte) in voters {
+        engine.commit_vote(&vote_id, commit_request(voter, hash_byte)).await.unwrap();
+    }
+    for _ in 0..1000 {
+        if anchoring.pending().await == voters.len() {
+            break;
+        }
+        tokio::task::yield_now().await;
+    }
+
+    assert_eq!(anchoring.flush().await.unwrap(), voters.len());
+    assert_eq!(chain.submissions.load(Ordering::SeqCst), 1);
+
+    let recorded = anchors.list_anchors(&vote_id).await.unwrap();
+    assert_eq!(recorded.len(), voters.len());
+    let root = recorded[0].batch_root.clone().expect("batched anchors carry the batch root");
+    for anchor in &recorded {
+        assert_eq!(anchor.batch_root.as_ref(), Some(&root));
+        assert_eq!(anchor.tx_hash, recorded[0].tx_hash);
+        assert!(batch_proof(anchor).verify(&root), "proof for {} does not verify", anchor.voter);
+    }
+
+    finish_vote(&service, &vote_id).await;
+    let verification = engine.verify_results(&vote_id).await.unwrap();
+    assert!(verification.commitment_verification.unanchored_voters.is_empty());
+    assert!(!verification.issues.iter().any(|issue| issue.contains("Batch proof")));
+}
+
+#[tokio::test]
+async fn test_full_batch_is_anchored_without_flush() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let chain = Arc::new(BatchingChain::default());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(batched(chain.clone(), anchors.clone(), 3));
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    for (voter, hash_byte) in [("alice", 'a'), ("bob", 'b'), ("carol", 'c')] {
+        engine.commit_vote(&vote_id, commit_request(voter, hash_byte)).await.unwrap();
+    }
+
+    let recorded = wait_for_anchors(&anchors, &vote_id, 3).await;
+    assert_eq!(chain.submissions.load(Ordering::SeqCst), 1);
+    assert!(recorded.iter().all(|anchor| batch_proof(anchor).verify(anchor.batch_root.as_ref().unwrap())));
+}
+
+#[tokio::test]
+async fn test_failed_batch_stays_pending() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let chain = Arc::new(BatchingChain::default());
+    let anchoring = batched(chain.clone(), anchors.clone(), 100);
+    let engine = VoteEngine::new(service.clone()).with_anchoring(anchoring.clone());
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("alice", 'a')).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("bob", 'b')).await.unwrap();
+    for _ in 0..1000 {
+        if anchoring.pending().await == 2 {
+            break;
+        }
+        tokio::task::yield_now().await;
+    }
+
+    chain.offline.store(true, Ordering::SeqCst);
+    assert!(anchoring.flush().await.is_err());
+    assert_eq!(anchoring.pending().await, 2);
+    assert!(anchors.list_anchors(&vote_id).await.unwrap().is_empty());
+
+    chain.offline.store(false, Ordering::SeqCst);
+    assert_eq!(anchoring.flush().await.unwrap(), 2);
+    assert_eq!(anchoring.pending().await, 0);
+    assert_eq!(anchors.list_anchors(&vote_id).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_tampered_batch_proof_is_reported() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(batched(Arc::new(BatchingChain::default()), anchors.clone(), 2));
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("alice", 'a')).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("bob", 'b')).await.unwrap();
+    let mut anchor = wait_for_anchors(&anchors, &vote_id, 2).await.remove(0);
+
+    anchor.batch_proof[0].sibling = "f".repeat(64);
+    anchors.save_anchor(anchor).await.unwrap();
+
+    finish_vote(&service, &vote_id).await;
+    let verification = engine.verify_results(&vote_id).await.unwrap();
+    assert!(!verification.is_valid);
+    assert!(verification.issues.iter().any(|issue| issue.contains("Batch proof for voter alice")));
+}
+
+#[tokio::test]
+async fn test_reconcile_resubmits_missing_anchor() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let chain = Arc::new(MockChain::new());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(Anchoring::new(chain.clone(), anchors.clone()));
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("alice", 'a')).await.unwrap();
+    wait_for_anchors(&anchors, &vote_id, 1).await;
+
+    // Committed while the chain was unreachable, so never anchored
+    let vote = engine.get_vote(&vote_id).await.unwrap();
+    service.save_commitment(Commitment {
+        id: "offline".to_string(),
+        vote_id: vote_id.clone(),
+        voter: "bob".to_string(),
+        commitment_hash: "b".repeat(64),
+        salt: "test_salt".to_string(),
+        created_at: vote.commitment_start,
+        range_proof: None,
+    }).await.unwrap();
+
+    let report = engine.reconcile_anchors(&vote_id).await.unwrap();
+    assert_eq!(report.checked, 2);
+    assert_eq!(report.confirmed, 1);
+    assert_eq!(report.resubmitted.len(), 1);
+    assert_eq!(report.resubmitted[0].voter, "bob");
+    assert_eq!(report.resubmitted[0].commitment_hash, "b".repeat(64));
+
+    assert_eq!(anchors.list_anchors(&vote_id).await.unwrap().len(), 2);
+    let report = engine.reconcile_anchors(&vote_id).await.unwrap();
+    assert_eq!(report.confirmed, 2);
+    assert!(report.resubmitted.is_empty());
+}
+
+#[tokio::test]
+async fn test_reconcile_rebatches_broken_batch_proof() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let chain = Arc::new(BatchingChain::default());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(batched(chain.clone(), anchors.clone(), 2));
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("alice", 'a')).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("bob", 'b')).await.unwrap();
+    let mut anchor = wait_for_anchors(&anchors, &vote_id, 2).await.remove(0);
+    anchor.batch_proof.clear();
+    anchors.save_anchor(anchor).await.unwrap();
+
+    let report = engine.reconcile_anchors(&vote_id).await.unwrap();
+    assert_eq!(report.confirmed, 1);
+    assert_eq!(report.resubmitted.len(), 1);
+    assert_eq!(report.resubmitted[0].voter, "alice");
+    assert_eq!(chain.submissions.load(Ordering::SeqCst), 2);
+
+    let recorded = anchors.list_anchors(&vote_id).await.unwrap();
+    assert!(recorded.iter().all(|anchor| batch_proof(anchor).verify(anchor.batch_root.as_ref().unwrap())));
+}
+
+#[tokio::test]
+async fn test_reconcile_takes_resubmitted_commitments_off_the_queue() {
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let chain = Arc::new(BatchingChain::default());
+    let anchoring = batched(chain.clone(), anchors.clone(), 100);
+    let engine = VoteEngine::new(service.clone()).with_anchoring(anchoring.clone());
+
+    let vote_id = engine.create_vote(vote_config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("alice", 'a')).await.unwrap();
+    for _ in 0..1000 {
+        if anchoring.pending().await == 1 {
+            break;
+        }
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(anchoring.pending().await, 1);
+
+    let report = engine.reconcile_anchors(&vote_id).await.unwrap();
+    assert_eq!(report.resubmitted.len(), 1);
+    assert_eq!(report.resubmitted[0].voter, "alice");
+    assert_eq!(anchoring.pending().await, 0);
+
+    // The next batch does not anchor alice a second time
+    assert_eq!(anchoring.flush().await.unwrap(), 0);
+    assert_eq!(chain.submissions.load(Ordering::SeqCst), 1);
+    assert_eq!(anchors.list_anchors(&vote_id).await.unwrap().len(), 1);
+}
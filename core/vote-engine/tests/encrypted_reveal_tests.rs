@@ -0,0 +1,184 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use shared_types::*;
+use shared_utils::crypto::{create_commitment, ecies_decrypt, ecies_encrypt, generate_ecies_keypair};
+use vote_engine::services::MemoryVoteService;
+use vote_engine::*;
+
+fn encrypted_config() -> VoteConfig {
+    VoteConfig {
+        title: "Board election".to_string(),
+        description: "Secret ballot".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: json!({}),
+        commitment_duration_hours: 1,
+        reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: true,
+    }
+}
+
+/// Engine over `service` that escrows reveal keys under a fixed key encryption key
+fn engine(service: &Arc<MemoryVoteService>) -> VoteEngine {
+    VoteEngine::new(service.clone()).with_key_encryption_key("test-kek")
+}
+
+/// Move the vote's windows so that `phase` is the current one
+async fn shift_to(service: &MemoryVoteService, vote_id: &str, phase: VoteStatus) {
+    let mut vote = service.get_vote(&VoteId::parse(vote_id).unwrap()).await.unwrap();
+    let now = Utc::now();
+    let (reveal_start, reveal_end) = match phase {
+        VoteStatus::RevealPhase => (now - Duration::minutes(1), now + Duration::hours(1)),
+        _ => (now - Duration::hours(1), now - Duration::minutes(1)),
+    };
+    vote.commitment_end = reveal_start;
+    vote.reveal_start = reveal_start;
+    vote.reveal_end = reveal_end;
+    service.create_vote(vote).await.unwrap();
+}
+
+async fn commit(engine: &VoteEngine, vote_id: &str, voter: &str, value: &serde_json::Value) -> String {
+    let salt = format!("{}-salt", voter);
+    let commitment_hash = create_commitment(&serde_json::to_string(value).unwrap(), &salt);
+    engine.commit_vote(vote_id, CommitRequest {
+        voter: voter.to_string(),
+        commitment_hash,
+        salt: salt.clone(),
+        range_proof: None,
+    }).await.unwrap();
+    salt
+}
+
+fn encrypted_reveal(public_key: &str, voter: &str, value: serde_json::Value, salt: String) -> RevealRequest {
+    let opening = RevealOpening { value, salt, range_blinding: None };
+    RevealRequest {
+        voter: voter.to_string(),
+        value: serde_json::Value::Null,
+        salt: String::new(),
+        range_blinding: None,
+        ciphertext: Some(ecies_encrypt(public_key, &serde_json::to_vec(&opening).unwrap()).unwrap()),
+    }
+}
+
+#[tokio::test]
+async fn test_encrypted_reveals_are_stored_as_ciphertext() {
+    let service = Arc::new(MemoryVoteService::new());
+    let engine = engine(&service);
+    let vote_id = engine.create_vote(encrypted_config()).await.unwrap();
+    let public_key = engine.get_vote(&vote_id).await.unwrap().reveal_public_key.expect("vote has a reveal key");
+
+    let salt = commit(&engine, &vote_id, "alice", &json!("yes")).await;
+    shift_to(&service, &vote_id, VoteStatus::RevealPhase).await;
+
+    // Plaintext is refused so it never reaches storage
+    let plaintext = RevealRequest {
+        voter: "alice".to_string(),
+        value: json!("yes"),
+        salt: salt.clone(),
+        range_blinding: None,
+        ciphertext: None,
+    };
+    assert!(matches!(engine.reveal_vote(&vote_id, plaintext).await, Err(VoteError::InvalidReveal { .. })));
+
+    let request = encrypted_reveal(&public_key, "alice", json!("yes"), salt.clone());
+    let ciphertext = request.ciphertext.clone().unwrap();
+    engine.reveal_vote(&vote_id, request).await.unwrap();
+
+    let stored = engine.list_reveals(&vote_id).await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].ciphertext.as_deref(), Some(ciphertext.as_str()));
+    assert_eq!(stored[0].value, serde_json::Value::Null);
+    assert!(stored[0].salt.is_empty());
+
+    // The key stays sealed while the reveal phase is open
+    let err = engine.get_results(&vote_id).await.unwrap_err();
+    assert!(matches!(err, VoteError::InvalidState { .. }), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_encrypted_reveals_are_opened_and_tallied_after_reveal_phase() {
+    let service = Arc::new(MemoryVoteService::new());
+    let engine = engine(&service);
+    let vote_id = engine.create_vote(encrypted_config()).await.unwrap();
+    let public_key = engine.get_vote(&vote_id).await.unwrap().reveal_public_key.unwrap();
+
+    let mut salts = Vec::new();
+    for (voter, value) in [("alice", "yes"), ("bob", "yes"), ("carol", "no"), ("dave", "no")] {
+        salts.push((voter, value, commit(&engine, &vote_id, voter, &json!(value)).await));
+    }
+    shift_to(&service, &vote_id, VoteStatus::RevealPhase).await;
+    for (voter, value, salt) in salts {
+        let request = match voter {
+            // Encrypted to someone else's key, so it cannot be opened at tally
+            "dave" => encrypted_reveal(&generate_ecies_keypair().public_key, voter, json!(value), salt),
+            _ => encrypted_reveal(&public_key, voter, json!(value), salt),
+        };
+        engine.reveal_vote(&vote_id, request).await.unwrap();
+    }
+
+    shift_to(&service, &vote_id, VoteStatus::Completed).await;
+    let results = engine.get_results(&vote_id).await.unwrap();
+    assert_eq!(results.total_votes, 3);
+    assert_eq!(results.results, json!({ "\"yes\"": 2, "\"no\"": 1 }));
+
+    // The released key opens every stored ciphertext that was encrypted to the vote
+    let secret_key = results.reveal_secret_key.clone().expect("key released with results");
+    let alice = engine.list_reveals(&vote_id).await.unwrap().into_iter().find(|r| r.voter == "alice").unwrap();
+    let opening: RevealOpening = serde_json::from_slice(&ecies_decrypt(&secret_key, alice.ciphertext.as_deref().unwrap()).unwrap()).unwrap();
+    assert_eq!(opening.value, json!("yes"));
+
+    let verification = engine.verify_results(&vote_id).await.unwrap();
+    assert_eq!(verification.commitment_verification.verified_commitments, 3);
+    assert_eq!(verification.commitment_verification.commitment_issues, vec!["No reveal found for commitment from voter: dave".to_string()]);
+}
+
+#[tokio::test]
+async fn test_plaintext_votes_refuse_ciphertext() {
+    let service = Arc::new(MemoryVoteService::new());
+    let engine = engine(&service);
+    let vote_id = engine.create_vote(VoteConfig { encrypted_reveals: false, ..encrypted_config() }).await.unwrap();
+    assert!(engine.get_vote(&vote_id).await.unwrap().reveal_public_key.is_none());
+
+    let salt = commit(&engine, &vote_id, "alice", &json!("yes")).await;
+    shift_to(&service, &vote_id, VoteStatus::RevealPhase).await;
+    let request = encrypted_reveal(&generate_ecies_keypair().public_key, "alice", json!("yes"), salt);
+    assert!(matches!(engine.reveal_vote(&vote_id, request).await, Err(VoteError::InvalidReveal { .. })));
+}
+
+#[tokio::test]
+async fn test_rebuilt_engine_tallies_with_escrowed_key() {
+    let service = Arc::new(MemoryVoteService::new());
+    let (vote_id, public_key) = {
+        let engine = engine(&service);
+        let vote_id = engine.create_vote(encrypted_config()).await.unwrap();
+        let public_key = engine.get_vote(&vote_id).await.unwrap().reveal_public_key.unwrap();
+        (vote_id, public_key)
+    };
+
+    assert!(service.get_reveal_key(&VoteId::parse(vote_id.as_str()).unwrap()).await.unwrap().is_some());
+
+    // A fresh engine, as after a restart, takes the ballots and the tally
+    let engine = engine(&service);
+    let salt = commit(&engine, &vote_id, "alice", &json!("yes")).await;
+    shift_to(&service, &vote_id, VoteStatus::RevealPhase).await;
+    engine.reveal_vote(&vote_id, encrypted_reveal(&public_key, "alice", json!("yes"), salt)).await.unwrap();
+    shift_to(&service, &vote_id, VoteStatus::Completed).await;
+
+    // The wrong key encryption key cannot open the escrow
+    let stranger = VoteEngine::new(service.clone()).with_key_encryption_key("other-kek");
+    assert!(matches!(stranger.get_results(&vote_id).await, Err(VoteError::InvalidState { .. })));
+
+    let results = engine.get_results(&vote_id).await.unwrap();
+    assert_eq!(results.total_votes, 1);
+    assert_eq!(results.results, json!({ "\"yes\"": 1 }));
+}
+
+#[tokio::test]
+async fn test_encrypted_reveals_need_key_encryption_key() {
+    let service = Arc::new(MemoryVoteService::new());
+    let err = VoteEngine::new(service.clone()).create_vote(encrypted_config()).await.unwrap_err();
+    assert!(matches!(err, VoteError::InvalidConfig { .. }), "{:?}", err);
+    assert!(service.list_votes(ListQuery { page: 0, page_size: 10, status: None, creator: None }).await.unwrap().items.is_empty());
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use shared_types::*;
+use shared_utils::crypto::create_commitment;
+use vote_engine::*;
+
+/// A completed yes/no vote where two of three voters chose "yes", with its results stored
+async fn completed_vote(title: &str) -> (VoteEngine, String) {
+    let service = Arc::new(MemoryVoteService::new());
+    let engine = VoteEngine::new(service.clone());
+    let vote_id = engine
+        .create_vote(VoteConfig {
+            title: title.to_string(),
+            description: "Approve the budget".to_string(),
+            template_id: "yes_no".to_string(),
+            template_params: serde_json::json!({}),
+            commitment_duration_hours: 1,
+            reveal_duration_hours: 1,
+            tie_break: TieBreak::default(),
+            completion_webhook_url: None,
+            encrypted_reveals: false,
+        })
+        .await
+        .unwrap();
+
+    for (voter, value) in [("alice", "yes"), ("bob", "yes"), ("carol", "no")] {
+        let value = serde_json::json!(value);
+        let salt = format!("{}-salt", voter);
+        let commitment_hash = create_commitment(&serde_json::to_string(&value).unwrap(), &salt);
+        service.save_commitment(Commitment {
+            id: format!("c-{}", voter),
+            vote_id: vote_id.clone(),
+            voter: voter.to_string(),
+            commitment_hash,
+            salt: salt.clone(),
+            created_at: Utc::now(),
+            range_proof: None,
+        }).await.unwrap();
+        service.save_reveal(Reveal {
+            id: format!("r-{}", voter),
+            vote_id: vote_id.clone(),
+            voter: voter.to_string(),
+            value,
+            salt,
+            created_at: Utc::now(),
+            ciphertext: None,
+        }).await.unwrap();
+    }
+    for _ in 0..3 {
+        engine.advance_phase(&vote_id).await.unwrap();
+    }
+    engine.recompute_results(&vote_id, true).await.unwrap();
+    (engine, vote_id)
+}
+
+#[tokio::test]
+async fn test_report_contains_winner_and_commitment_root() {
+    let (engine, vote_id) = completed_vote("Budget <2031>").await;
+    let report = engine.generate_vote_report(&vote_id).await.unwrap();
+
+    let root = engine.commitment_tree(&vote_id).await.unwrap().root();
+    assert!(report.contains(&format!("<code class=\"commitment-root\">{}</code>", root)));
+    assert!(report.contains("<span class=\"winner\">yes</span>"));
+    assert!(report.contains("<p class=\"success\">Verified</p>"));
+
+    let results = engine.get_vote(&vote_id).await.unwrap().results.unwrap();
+    assert!(report.contains(&report_receipt_hash(&results)));
+    // user-supplied text is escaped
+    assert!(report.contains("Budget &lt;2031&gt;"));
+    assert!(!report.contains("<2031>"));
+}
+
+#[tokio::test]
+async fn test_report_requires_results() {
+    let engine = VoteEngine::new(Arc::new(MemoryVoteService::new()));
+    let vote_id = engine
+        .create_vote(VoteConfig {
+            title: "Pending".to_string(),
+            description: "No results yet".to_string(),
+            template_id: "yes_no".to_string(),
+            template_params: serde_json::json!({}),
+            commitment_duration_hours: 1,
+            reveal_duration_hours: 1,
+            tie_break: TieBreak::default(),
+            completion_webhook_url: None,
+            encrypted_reveals: false,
+        })
+        .await
+        .unwrap();
+
+    assert!(matches!(engine.generate_vote_report(&vote_id).await, Err(VoteError::InvalidState { .. })));
+}
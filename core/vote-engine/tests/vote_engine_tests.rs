@@ -30,9 +30,9 @@ impl VoteService for MockVoteService {
         Ok(())
     }
 
-    async fn get_vote(&self, vote_id: &str) -> Result<Vote, VoteError> {
+    async fn get_vote(&self, vote_id: &VoteId) -> Result<Vote, VoteError> {
         let votes = self.votes.lock().unwrap();
-        votes.get(vote_id)
+        votes.get(vote_id.as_str())
             .cloned()
             .ok_or_else(|| VoteError::VoteNotFound {
                 id: vote_id.to_string(),
@@ -52,15 +52,15 @@ impl VoteService for MockVoteService {
         })
     }
 
-    async fn update_vote_status(&self, vote_id: &str, status: VoteStatus) -> Result<(), VoteError> {
+    async fn update_vote_status(&self, vote_id: &VoteId, status: VoteStatus) -> Result<(), VoteError> {
         let mut votes = self.votes.lock().unwrap();
-        if let Some(vote) = votes.get_mut(vote_id) {
+        if let Some(vote) = votes.get_mut(vote_id.as_str()) {
             vote.status = status;
         }
         Ok(())
     }
 
-    async fn update_vote_results(&self, _vote_id: &str, _results: &VoteResults) -> Result<(), VoteError> {
+    async fn update_vote_results(&self, _vote_id: &VoteId, _results: &VoteResults) -> Result<(), VoteError> {
         Ok(())
     }
 
@@ -72,17 +72,17 @@ impl VoteService for MockVoteService {
         Ok(())
     }
 
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, VoteError> {
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, VoteError> {
         let key = format!("{}:{}", vote_id, voter);
         let commitments = self.commitments.lock().unwrap();
         Ok(commitments.get(&key).cloned())
     }
 
-    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, VoteError> {
+    async fn list_commitments(&self, vote_id: &VoteId) -> Result<Vec<Commitment>, VoteError> {
         let commitments_guard = self.commitments.lock().unwrap();
         let commitments: Vec<Commitment> = commitments_guard
             .values()
-            .filter(|c| c.vote_id == vote_id)
+            .filter(|c| c.vote_id == vote_id.as_str())
             .cloned()
             .collect();
         Ok(commitments)
@@ -96,11 +96,11 @@ impl VoteService for MockVoteService {
         Ok(())
     }
 
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, VoteError> {
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, VoteError> {
         let reveals_guard = self.reveals.lock().unwrap();
         let reveals: Vec<Reveal> = reveals_guard
             .values()
-            .filter(|r| r.vote_id == vote_id)
+            .filter(|r| r.vote_id == vote_id.as_str())
             .cloned()
             .collect();
         Ok(reveals)
@@ -112,6 +112,9 @@ impl VoteService for MockVoteService {
             total_votes: 0,
             results: serde_json::Value::Object(serde_json::Map::new()),
             calculated_at: Utc::now(),
+            winner: None,
+            commitment_root: None,
+            reveal_secret_key: None,
         })
     }
 }
@@ -128,6 +131,9 @@ async fn test_create_vote_success() {
         template_params: serde_json::Value::Object(serde_json::Map::new()),
         commitment_duration_hours: 24,
         reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
 
     let result = engine.create_vote(config).await;
@@ -149,12 +155,91 @@ async fn test_create_vote_invalid_config() {
         template_params: serde_json::Value::Object(serde_json::Map::new()),
         commitment_duration_hours: 24,
         reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
 
     let result = engine.create_vote(config).await;
     assert!(result.is_err());
 }
 
+fn request_id_config(title: &str) -> VoteConfig {
+    VoteConfig {
+        title: title.to_string(),
+        description: "A retried create".to_string(),
+        template_id: "simple".to_string(),
+        template_params: serde_json::Value::Object(serde_json::Map::new()),
+        commitment_duration_hours: 24,
+        reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }
+}
+
+#[tokio::test]
+async fn test_create_vote_with_same_request_id_returns_existing_vote() {
+    let mock_service = Arc::new(MockVoteService::new());
+    let engine = VoteEngine::new(mock_service.clone());
+
+    let (first_id, created) = engine.create_vote_with_request_id(SYSTEM_CREATOR, request_id_config("Retried"), Some("req-1".to_string())).await.unwrap();
+    assert!(created);
+    let (second_id, created) = engine.create_vote_with_request_id(SYSTEM_CREATOR, request_id_config("Retried"), Some("req-1".to_string())).await.unwrap();
+    assert!(!created);
+    assert_eq!(first_id, second_id);
+    assert_eq!(mock_service.votes.lock().unwrap().len(), 1);
+    let vote = engine.get_vote(&first_id).await.unwrap();
+    assert_eq!(vote.client_request_id.as_deref(), Some("req-1"));
+
+    // a different request id, or none at all, creates a new vote
+    let (other_id, created) = engine.create_vote_with_request_id(SYSTEM_CREATOR, request_id_config("Retried"), Some("req-2".to_string())).await.unwrap();
+    assert!(created);
+    assert_ne!(other_id, first_id);
+    engine.create_vote(request_id_config("Retried")).await.unwrap();
+    assert_eq!(mock_service.votes.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_request_ids_are_scoped_to_the_creator() {
+    let mock_service = Arc::new(MockVoteService::new());
+    let engine = VoteEngine::new(mock_service.clone());
+
+    let (alice_id, created) = engine.create_vote_with_request_id("alice", request_id_config("Shared"), Some("req-1".to_string())).await.unwrap();
+    assert!(created);
+    let (bob_id, created) = engine.create_vote_with_request_id("bob", request_id_config("Shared"), Some("req-1".to_string())).await.unwrap();
+    assert!(created);
+    assert_ne!(alice_id, bob_id);
+    assert_eq!(engine.get_vote(&bob_id).await.unwrap().creator, "bob");
+
+    let (retried_id, created) = engine.create_vote_with_request_id("alice", request_id_config("Shared"), Some("req-1".to_string())).await.unwrap();
+    assert!(!created);
+    assert_eq!(retried_id, alice_id);
+    assert_eq!(mock_service.votes.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_concurrent_creates_with_same_request_id_yield_one_vote() {
+    let mock_service = Arc::new(MockVoteService::new());
+    let engine = Arc::new(VoteEngine::new(mock_service.clone()));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let engine = engine.clone();
+            tokio::spawn(async move {
+                engine.create_vote_with_request_id(SYSTEM_CREATOR, request_id_config("Raced"), Some("req-race".to_string())).await.unwrap().0
+            })
+        })
+        .collect();
+    let mut ids = Vec::new();
+    for handle in handles {
+        ids.push(handle.await.unwrap());
+    }
+    ids.dedup();
+    assert_eq!(ids.len(), 1);
+    assert_eq!(mock_service.votes.lock().unwrap().len(), 1);
+}
+
 #[tokio::test]
 async fn test_commit_vote_success() {
     let mock_service = Arc::new(MockVoteService::new());
@@ -168,6 +253,9 @@ async fn test_commit_vote_success() {
         template_params: serde_json::Value::Object(serde_json::Map::new()),
         commitment_duration_hours: 24,
         reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
 
     let vote_id = engine.create_vote(config).await.unwrap();
@@ -177,6 +265,7 @@ async fn test_commit_vote_success() {
         voter: "test_voter".to_string(),
         commitment_hash: "a".repeat(64), // Valid hash length
         salt: "test_salt".to_string(),
+        range_proof: None,
     };
 
     let result = engine.commit_vote(&vote_id, commit_request).await;
@@ -196,6 +285,7 @@ async fn test_commit_vote_invalid_vote_id() {
         voter: "test_voter".to_string(),
         commitment_hash: "a".repeat(64), // Valid hash length
         salt: "test_salt".to_string(),
+        range_proof: None,
     };
 
     let result = engine.commit_vote("nonexistent_vote", commit_request).await;
@@ -215,6 +305,9 @@ async fn test_reveal_vote_success() {
         template_params: serde_json::Value::Object(serde_json::Map::new()),
         commitment_duration_hours: 1, // Keep 1 hour for now
         reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
 
     let vote_id = engine.create_vote(config).await.unwrap();
@@ -229,6 +322,7 @@ async fn test_reveal_vote_success() {
         voter: "test_voter".to_string(),
         commitment_hash,
         salt: salt.clone(),
+        range_proof: None,
     };
 
     let commit_result = engine.commit_vote(&vote_id, commit_request).await;
@@ -236,7 +330,7 @@ async fn test_reveal_vote_success() {
 
     // Manually advance the vote to reveal phase for testing
     // In a real scenario, this would happen automatically when the commitment phase ends
-    let vote = mock_service.get_vote(&vote_id).await.unwrap();
+    let vote = mock_service.get_vote(&VoteId::parse(vote_id.as_str()).unwrap()).await.unwrap();
     let mut updated_vote = vote.clone();
     updated_vote.status = VoteStatus::RevealPhase;
     updated_vote.reveal_start = chrono::Utc::now() - chrono::Duration::hours(1); // Set reveal start to 1 hour ago
@@ -247,6 +341,8 @@ async fn test_reveal_vote_success() {
         voter: "test_voter".to_string(),
         value,
         salt,
+        range_blinding: None,
+        ciphertext: None,
     };
 
     let result = engine.reveal_vote(&vote_id, reveal_request).await;
@@ -270,6 +366,9 @@ async fn test_get_results_vote_not_ended() {
         template_params: serde_json::Value::Object(serde_json::Map::new()),
         commitment_duration_hours: 24,
         reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
 
     let vote_id = engine.create_vote(config).await.unwrap();
@@ -298,3 +397,84 @@ async fn test_list_votes() {
     assert_eq!(page.page, 1);
     assert_eq!(page.page_size, 10);
 }
+
+/// Ended multiple-choice vote with a two-way tie, broken randomly from reveal salts
+async fn tied_vote(service: &MockVoteService, salts: [&str; 2]) -> String {
+    let now = Utc::now();
+    let vote = Vote {
+        id: "tied-vote".to_string(),
+        title: "Tied".to_string(),
+        description: "Two options with one vote each".to_string(),
+        template_id: "multiple_choice".to_string(),
+        template_params: serde_json::json!({"choices": ["a", "b"]}),
+        creator: "test".to_string(),
+        created_at: now - chrono::Duration::hours(3),
+        commitment_start: now - chrono::Duration::hours(3),
+        commitment_end: now - chrono::Duration::hours(2),
+        reveal_start: now - chrono::Duration::hours(2),
+        reveal_end: now - chrono::Duration::hours(1),
+        status: VoteStatus::RevealPhase,
+        results: None,
+        tie_break: TieBreak::Random { seed_source: SeedSource::RevealSalts },
+        legal_hold: false,
+        completion_webhook_url: None,
+        client_request_id: None,
+        reveal_public_key: None,
+    };
+    service.create_vote(vote.clone()).await.unwrap();
+    for (voter, (choice, salt)) in ["alice", "bob"].iter().zip(["a", "b"].iter().zip(salts)) {
+        service.save_reveal(Reveal {
+            id: format!("reveal-{}", voter),
+            vote_id: vote.id.clone(),
+            voter: voter.to_string(),
+            value: serde_json::json!(choice),
+            salt: salt.to_string(),
+            created_at: now,
+            ciphertext: None,
+        }).await.unwrap();
+    }
+    vote.id
+}
+
+#[tokio::test]
+async fn test_fixed_seed_selects_same_winner() {
+    let seed = [7u8; 32];
+
+    let first_service = Arc::new(MockVoteService::new());
+    let vote_id = tied_vote(&first_service, ["salt-1", "salt-2"]).await;
+    let first = VoteEngine::new(first_service).with_seed(seed).get_results(&vote_id).await.unwrap();
+
+    let second_service = Arc::new(MockVoteService::new());
+    tied_vote(&second_service, ["salt-1", "salt-2"]).await;
+    let second = VoteEngine::new(second_service).with_seed(seed).get_results(&vote_id).await.unwrap();
+
+    let first = first.winner.unwrap();
+    let second = second.winner.unwrap();
+    assert_eq!(first.tied, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(first.winner.as_deref(), Some("a"));
+    assert_eq!(second.winner.as_deref(), Some("a"));
+    assert_eq!(first.seed.as_deref(), Some("07".repeat(32).as_str()));
+
+    // a different seed breaks the same tie the other way
+    let other_service = Arc::new(MockVoteService::new());
+    tied_vote(&other_service, ["salt-1", "salt-2"]).await;
+    let other = VoteEngine::new(other_service).with_seed([9u8; 32]).get_results(&vote_id).await.unwrap();
+    assert_eq!(other.winner.unwrap().winner.as_deref(), Some("b"));
+}
+
+#[tokio::test]
+async fn test_fixed_seed_overrides_reveal_salts() {
+    let seed = [42u8; 32];
+
+    let first_service = Arc::new(MockVoteService::new());
+    let vote_id = tied_vote(&first_service, ["salt-1", "salt-2"]).await;
+    let first = VoteEngine::new(first_service).with_seed(seed).get_results(&vote_id).await.unwrap();
+
+    let second_service = Arc::new(MockVoteService::new());
+    tied_vote(&second_service, ["other-1", "other-2"]).await;
+    let second = VoteEngine::new(second_service).with_seed(seed).get_results(&vote_id).await.unwrap();
+
+    let (first, second) = (first.winner.unwrap(), second.winner.unwrap());
+    assert_eq!(first.seed, second.seed);
+    assert_eq!(first.winner, second.winner);
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use shared_types::*;
+use shared_utils::crypto::create_commitment;
+use vote_engine::services::MemoryVoteService;
+use vote_engine::*;
+
+fn config() -> VoteConfig {
+    VoteConfig {
+        title: "Budget".to_string(),
+        description: "Approve the budget".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: json!({}),
+        commitment_duration_hours: 1,
+        reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }
+}
+
+/// Move the vote's windows so that `phase` is the current one, and give it `status`
+async fn set_phase(service: &MemoryVoteService, vote_id: &str, phase: VoteStatus, status: VoteStatus) {
+    let mut vote = service.get_vote(&VoteId::parse(vote_id).unwrap()).await.unwrap();
+    let now = Utc::now();
+    let (commitment_end, reveal_end) = match phase {
+        VoteStatus::CommitmentPhase => (now + Duration::hours(1), now + Duration::hours(2)),
+        VoteStatus::RevealPhase => (now - Duration::minutes(1), now + Duration::hours(1)),
+        _ => (now - Duration::hours(1), now - Duration::minutes(1)),
+    };
+    vote.commitment_start = now - Duration::hours(2);
+    vote.commitment_end = commitment_end;
+    vote.reveal_start = commitment_end;
+    vote.reveal_end = reveal_end;
+    vote.status = status;
+    service.create_vote(vote).await.unwrap();
+}
+
+fn commit_request(voter: &str) -> CommitRequest {
+    CommitRequest {
+        voter: voter.to_string(),
+        commitment_hash: create_commitment("\"yes\"", "pepper"),
+        salt: "pepper".to_string(),
+        range_proof: None,
+    }
+}
+
+fn reveal_request(voter: &str) -> RevealRequest {
+    RevealRequest {
+        voter: voter.to_string(),
+        value: json!("yes"),
+        salt: "pepper".to_string(),
+        range_blinding: None,
+        ciphertext: None,
+    }
+}
+
+/// A vote with one commitment from alice, still in its commitment phase
+async fn committed_vote() -> (Arc<MemoryVoteService>, VoteEngine, String) {
+    let service = Arc::new(MemoryVoteService::new());
+    let engine = VoteEngine::new(service.clone());
+    let vote_id = engine.create_vote(config()).await.unwrap();
+    engine.commit_vote(&vote_id, commit_request("alice")).await.unwrap();
+    (service, engine, vote_id)
+}
+
+#[tokio::test]
+async fn test_cancelled_vote_rejects_commit() {
+    let (service, engine, vote_id) = committed_vote().await;
+    set_phase(&service, &vote_id, VoteStatus::CommitmentPhase, VoteStatus::Cancelled).await;
+
+    let err = engine.commit_vote(&vote_id, commit_request("bob")).await.unwrap_err();
+    assert!(matches!(err, VoteError::VoteCancelled), "{:?}", err);
+    assert_eq!(ApiError::from(err).code, "vote.cancelled");
+}
+
+#[tokio::test]
+async fn test_cancelled_vote_rejects_reveal() {
+    let (service, engine, vote_id) = committed_vote().await;
+    set_phase(&service, &vote_id, VoteStatus::RevealPhase, VoteStatus::Cancelled).await;
+
+    let err = engine.reveal_vote(&vote_id, reveal_request("alice")).await.unwrap_err();
+    assert!(matches!(err, VoteError::VoteCancelled), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_cancelled_vote_rejects_results() {
+    let (service, engine, vote_id) = committed_vote().await;
+    set_phase(&service, &vote_id, VoteStatus::Completed, VoteStatus::Cancelled).await;
+
+    let err = engine.get_results(&vote_id).await.unwrap_err();
+    assert!(matches!(err, VoteError::VoteCancelled), "{:?}", err);
+    let vote = engine.get_vote(&vote_id).await.unwrap();
+    assert_eq!(vote.status, VoteStatus::Cancelled);
+    assert!(vote.results.is_none());
+}
+
+#[tokio::test]
+async fn test_completed_vote_rejects_commit() {
+    let (service, engine, vote_id) = committed_vote().await;
+    set_phase(&service, &vote_id, VoteStatus::CommitmentPhase, VoteStatus::Completed).await;
+
+    let err = engine.commit_vote(&vote_id, commit_request("bob")).await.unwrap_err();
+    assert!(matches!(err, VoteError::VoteEnded), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_completed_vote_rejects_reveal() {
+    let (service, engine, vote_id) = committed_vote().await;
+    set_phase(&service, &vote_id, VoteStatus::RevealPhase, VoteStatus::Completed).await;
+
+    let err = engine.reveal_vote(&vote_id, reveal_request("alice")).await.unwrap_err();
+    assert!(matches!(err, VoteError::VoteEnded), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_completed_vote_still_serves_results() {
+    let (service, engine, vote_id) = committed_vote().await;
+    set_phase(&service, &vote_id, VoteStatus::RevealPhase, VoteStatus::RevealPhase).await;
+    engine.reveal_vote(&vote_id, reveal_request("alice")).await.unwrap();
+    set_phase(&service, &vote_id, VoteStatus::Completed, VoteStatus::RevealPhase).await;
+
+    let first = engine.get_results(&vote_id).await.unwrap();
+    assert_eq!(engine.get_vote(&vote_id).await.unwrap().status, VoteStatus::Completed);
+    let again = engine.get_results(&vote_id).await.unwrap();
+    assert_eq!(again.total_votes, 1);
+    assert_eq!(again.results, first.results);
+}
+
+#[tokio::test]
+async fn test_cancel_vote_stops_ballots_and_is_final() {
+    let (_service, engine, vote_id) = committed_vote().await;
+
+    let vote = engine.cancel_vote(&vote_id, "operator").await.unwrap();
+    assert_eq!(vote.status, VoteStatus::Cancelled);
+    let err = engine.commit_vote(&vote_id, commit_request("bob")).await.unwrap_err();
+    assert!(matches!(err, VoteError::VoteCancelled), "{:?}", err);
+
+    let err = engine.cancel_vote(&vote_id, "operator").await.unwrap_err();
+    assert!(matches!(err, VoteError::InvalidState { .. }), "{:?}", err);
+}
@@ -0,0 +1,25 @@
+#![no_main]
+
+use event_store::query::{QueryCondition, QueryField};
+use event_store::{Event, QueryExecutor};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok((event, field, condition)) = serde_json::from_slice::<(Event, QueryField, QueryCondition)>(data) else {
+        return;
+    };
+
+    let matched = QueryExecutor::evaluate_condition(&event, &field, &condition);
+    assert_eq!(matched, QueryExecutor::evaluate_condition(&event, &field, &condition));
+
+    // Complementary conditions must disagree on every event
+    let complement = match &condition {
+        QueryCondition::Equals(v) => Some(QueryCondition::NotEquals(v.clone())),
+        QueryCondition::In(v) => Some(QueryCondition::NotIn(v.clone())),
+        QueryCondition::Exists => Some(QueryCondition::NotExists),
+        _ => None,
+    };
+    if let Some(complement) = complement {
+        assert_ne!(matched, QueryExecutor::evaluate_condition(&event, &field, &complement));
+    }
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use ddv_fuzz::fuzz_template;
+use libfuzzer_sys::fuzz_target;
+use template_system::YesNoTemplate;
+
+fuzz_target!(|data: &[u8]| {
+    fuzz_template(&YesNoTemplate::new(), data);
+});
@@ -0,0 +1,59 @@
+//! Shared harness for the fuzz targets
+//!
+//! Each target feeds arbitrary bytes through a `VoteTemplate` and checks the
+//! properties commitments rely on: no panics, the same input always produces
+//! the same result, and anything `validate` accepts can be canonicalized.
+
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+use template_system::VoteTemplate;
+
+/// Params used when the input does not carry its own
+pub fn default_params() -> Value {
+    json!({
+        "choices": ["a", "b", "c"],
+        "options": ["a", "b", "c"],
+        "min": 0,
+        "max": 100,
+    })
+}
+
+/// Interpret fuzz input as `[value, params]`, or as a bare value with [`default_params`]
+pub fn split_input(data: &[u8]) -> Option<(Value, Value)> {
+    let input: Value = serde_json::from_slice(data).ok()?;
+    match input {
+        Value::Array(mut pair) if pair.len() == 2 => {
+            let params = pair.pop()?;
+            let value = pair.pop()?;
+            Some((value, params))
+        }
+        value => Some((value, default_params())),
+    }
+}
+
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("failed to build fuzz runtime")
+    })
+}
+
+/// Run `validate` and `canonicalize` twice on the input and assert the invariants
+pub fn fuzz_template<T: VoteTemplate>(template: &T, data: &[u8]) {
+    let Some((value, params)) = split_input(data) else {
+        return;
+    };
+
+    runtime().block_on(async {
+        let valid = template.validate(&value, &params).await.is_ok();
+        let canonical = template.canonicalize(&value, &params).await.ok();
+
+        assert_eq!(valid, template.validate(&value, &params).await.is_ok(), "validate is not deterministic");
+        assert_eq!(canonical, template.canonicalize(&value, &params).await.ok(), "canonicalize is not deterministic");
+        if valid {
+            assert!(canonical.is_some(), "validated value failed to canonicalize: {}", value);
+        }
+    });
+}
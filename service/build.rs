@@ -0,0 +1,67 @@
+// Generates the gRPC server and client stubs for proto/vote.proto when the `grpc` feature is on.
+// The service's rpcs are read from the .proto itself, so no protoc is needed; the message types
+// are hand-written prost structs in src/api/grpc.rs, checked against the .proto by
+// tests/grpc_proto_tests.rs.
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    grpc::generate();
+}
+
+#[cfg(feature = "grpc")]
+mod grpc {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    const PROTO: &str = "proto/vote.proto";
+
+    /// `(route, input, output)` of every `rpc Route(Input) returns (Output);` line
+    fn rpcs(proto: &str) -> Vec<(String, String, String)> {
+        proto
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("rpc "))
+            .map(|rpc| {
+                let names: Vec<&str> = rpc
+                    .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .filter(|word| !word.is_empty() && *word != "returns")
+                    .collect();
+                match names.as_slice() {
+                    [route, input, output] => (route.to_string(), input.to_string(), output.to_string()),
+                    _ => panic!("cannot parse rpc `{}` in {}", rpc, PROTO),
+                }
+            })
+            .collect()
+    }
+
+    /// `CreateVote` -> `create_vote`
+    fn snake_case(route: &str) -> String {
+        let mut name = String::new();
+        for (i, c) in route.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+
+    pub fn generate() {
+        println!("cargo:rerun-if-changed=build.rs");
+        println!("cargo:rerun-if-changed={}", PROTO);
+        let proto = std::fs::read_to_string(PROTO).expect("read proto/vote.proto");
+        let service = rpcs(&proto).into_iter().fold(
+            Service::builder().name("VoteService").package("ddv.vote.v1"),
+            |service, (route, input, output)| {
+                service.method(
+                    Method::builder()
+                        .name(snake_case(&route))
+                        .route_name(route)
+                        .input_type(format!("crate::api::grpc::proto::{}", input))
+                        .output_type(format!("crate::api::grpc::proto::{}", output))
+                        .codec_path("tonic::codec::ProstCodec")
+                        .build(),
+                )
+            },
+        );
+        Builder::new().compile(&[service.build()]);
+    }
+}
@@ -0,0 +1,102 @@
+//! Request authentication built from `ApiAuth` and the API-key store.
+//!
+//! A request is let through when it carries a valid `X-API-Key` holding the route's scope, or
+//! `Authorization: Bearer <token>` for one of the static tokens, which grant every scope.
+
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::config::ApiAuth;
+use crate::model::api_key::ApiKey;
+use crate::model::response::ApiResponse;
+use crate::store::api_keys::ApiKeyStore;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+pub struct AuthState {
+    pub config: ApiAuth,
+    pub keys: Arc<dyn ApiKeyStore>,
+    /// Per-key fixed one-minute windows: (window start, requests so far).
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl AuthState {
+    pub fn new(config: ApiAuth, keys: Arc<dyn ApiKeyStore>) -> Self {
+        Self { config, keys, windows: Mutex::new(HashMap::new()) }
+    }
+
+    /// Check the presented credentials for `scope`; an API key takes precedence over a bearer token.
+    /// Always admits when auth is disabled. Errors carry the HTTP status and message to reject with.
+    pub async fn authorize(&self, api_key: Option<&str>, bearer: Option<&str>, scope: &str) -> Result<(), (StatusCode, String)> {
+        if !self.config.enabled { return Ok(()); }
+        if let Some(presented) = api_key { return self.check_key(presented, scope).await; }
+        match bearer {
+            Some(token) if is_known_token(&self.config.tokens, token) => Ok(()),
+            _ => Err(denied(StatusCode::UNAUTHORIZED, "unauthorized")),
+        }
+    }
+
+    async fn check_key(&self, presented: &str, scope: &str) -> Result<(), (StatusCode, String)> {
+        let (id, secret) = presented.split_once('.').ok_or_else(|| denied(StatusCode::UNAUTHORIZED, "unauthorized"))?;
+        let key = match self.keys.get_key(id).await {
+            Ok(Some(key)) => key,
+            Ok(None) => return Err(denied(StatusCode::UNAUTHORIZED, "unauthorized")),
+            Err(e) => return Err(denied(StatusCode::INTERNAL_SERVER_ERROR, &format!("{}", e))),
+        };
+        if !constant_time_eq(key.hashed_secret.as_bytes(), ApiKey::hash_secret(secret).as_bytes()) {
+            return Err(denied(StatusCode::UNAUTHORIZED, "unauthorized"));
+        }
+        if key.revoked { return Err(denied(StatusCode::UNAUTHORIZED, "api key revoked")); }
+        if key.is_expired(chrono::Utc::now().timestamp()) { return Err(denied(StatusCode::UNAUTHORIZED, "api key expired")); }
+        if !key.has_scope(scope) { return Err(denied(StatusCode::FORBIDDEN, &format!("api key lacks scope {}", scope))); }
+        if let Some(limit) = key.rate_limit_per_minute {
+            if !self.within_rate_limit(&key.id, limit) { return Err(denied(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded")); }
+        }
+        Ok(())
+    }
+
+    fn within_rate_limit(&self, id: &str, limit: u32) -> bool {
+        let mut windows = self.windows.lock().unwrap();
+        let now = Instant::now();
+        let window = windows.entry(id.to_string()).or_insert((now, 0));
+        if now.duration_since(window.0) >= RATE_WINDOW { *window = (now, 0); }
+        if window.1 >= limit { return false; }
+        window.1 += 1;
+        true
+    }
+}
+
+/// Admits the request if auth is disabled or it is authorized for `scope`; see the module docs.
+pub async fn require_scope(State((auth, scope)): State<(Arc<AuthState>, &'static str)>, req: Request, next: Next) -> Response {
+    let api_key = req.headers().get(API_KEY_HEADER).map(|v| v.to_str().unwrap_or_default());
+    let bearer = req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let authorized = auth.authorize(api_key, bearer, scope).await;
+    match authorized {
+        Ok(()) => next.run(req).await,
+        Err((status, message)) => (status, Json(ApiResponse::<()>::error(&message))).into_response(),
+    }
+}
+
+fn denied(status: StatusCode, message: &str) -> (StatusCode, String) {
+    (status, message.to_string())
+}
+
+/// Every configured token is compared, so the time taken does not reveal which one (if any) matched.
+fn is_known_token(tokens: &[String], presented: &str) -> bool {
+    tokens.iter().fold(false, |found, t| constant_time_eq(t.as_bytes(), presented.as_bytes()) | found)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() { return false; }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -0,0 +1,29 @@
+//! CORS policy built from `CorsConfig`.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use crate::config::CorsConfig;
+
+/// Entries that are not valid header values are logged and skipped.
+pub fn cors_layer(cfg: &CorsConfig) -> CorsLayer {
+    let origins = if cfg.allowed_origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(parse_all(&cfg.allowed_origins, |o| HeaderValue::from_str(o).ok()))
+    };
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(parse_all(&cfg.allowed_methods, |m| Method::from_bytes(m.as_bytes()).ok()))
+        .allow_headers(parse_all(&cfg.allowed_headers, |h| HeaderName::from_bytes(h.as_bytes()).ok()))
+        .allow_credentials(cfg.allow_credentials)
+        .max_age(Duration::from_secs(cfg.max_age_secs))
+}
+
+fn parse_all<T>(entries: &[String], parse: impl Fn(&str) -> Option<T>) -> Vec<T> {
+    entries.iter().filter_map(|e| {
+        let parsed = parse(e);
+        if parsed.is_none() { tracing::warn!("ignoring invalid CORS entry: {}", e); }
+        parsed
+    }).collect()
+}
@@ -0,0 +1,311 @@
+//! gRPC interface mirroring the REST vote routes, defined by `proto/vote.proto`.
+//!
+//! Requests go through the same `VoteService` and API-key checks as the HTTP routes: credentials
+//! are read from the `x-api-key` and `authorization` metadata. Enabled with the `grpc` feature.
+
+// every handler returns `tonic::Status`; helpers follow suit rather than boxing it
+#![allow(clippy::result_large_err)]
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use axum::http::StatusCode;
+use serde_json::Value;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Code, Request, Response, Status};
+use crate::api::auth::{AuthState, API_KEY_HEADER};
+use crate::core::state::AppState;
+use crate::model::api_key::{SCOPE_VOTES_READ, SCOPE_VOTES_WRITE};
+use crate::model::vote::{VoteConfig, VoteDetailDto, VoteResultsDto, VoteSummaryDto};
+use crate::service::ServiceError;
+use proto::vote_service_server::{VoteService, VoteServiceServer};
+
+/// Message types of `proto/vote.proto` plus the generated client and server.
+pub mod proto {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CreateVoteRequest {
+        #[prost(string, tag = "1")] pub config_json: String,
+        #[prost(string, optional, tag = "2")] pub id_nonce: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CreateVoteResponse {
+        #[prost(string, tag = "1")] pub id: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListVotesRequest {
+        #[prost(uint64, tag = "1")] pub offset: u64,
+        #[prost(uint64, tag = "2")] pub limit: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct VoteSummary {
+        #[prost(string, tag = "1")] pub id: String,
+        #[prost(string, tag = "2")] pub title: String,
+        #[prost(uint64, tag = "3")] pub commit_start_height: u64,
+        #[prost(uint64, tag = "4")] pub commit_end_height: u64,
+        #[prost(uint64, tag = "5")] pub reveal_start_height: u64,
+        #[prost(uint64, tag = "6")] pub reveal_end_height: u64,
+        #[prost(string, tag = "7")] pub status: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ListVotesResponse {
+        #[prost(message, repeated, tag = "1")] pub items: Vec<VoteSummary>,
+        #[prost(uint64, tag = "2")] pub total: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetVoteRequest {
+        #[prost(string, tag = "1")] pub id: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Vote {
+        #[prost(string, tag = "1")] pub id: String,
+        #[prost(string, tag = "2")] pub config_json: String,
+        #[prost(int64, tag = "3")] pub created_ts: i64,
+        #[prost(uint64, tag = "4")] pub num_commitments: u64,
+        #[prost(uint64, tag = "5")] pub num_reveals: u64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CommitRequest {
+        #[prost(string, tag = "1")] pub id: String,
+        #[prost(string, tag = "2")] pub voter: String,
+        #[prost(string, tag = "3")] pub value_json: String,
+        #[prost(string, tag = "4")] pub salt_hex: String,
+        #[prost(string, optional, tag = "5")] pub signature_hex: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct CommitResponse {
+        #[prost(string, tag = "1")] pub commitment_hex: String,
+        #[prost(int64, tag = "2")] pub ts: i64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RevealRequest {
+        #[prost(string, tag = "1")] pub id: String,
+        #[prost(string, tag = "2")] pub voter: String,
+        #[prost(string, tag = "3")] pub value_json: String,
+        #[prost(string, tag = "4")] pub salt_hex: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RevealResponse {
+        #[prost(bool, tag = "1")] pub accepted: bool,
+        #[prost(int64, tag = "2")] pub ts: i64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct GetResultsRequest {
+        #[prost(string, tag = "1")] pub id: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct VoteResults {
+        #[prost(string, tag = "1")] pub vote_id: String,
+        #[prost(string, tag = "2")] pub result_json: String,
+        #[prost(uint64, tag = "3")] pub total_eligible: u64,
+        #[prost(uint64, tag = "4")] pub total_revealed: u64,
+        #[prost(uint64, tag = "5")] pub total_abstained: u64,
+        #[prost(double, tag = "6")] pub participation_rate: f64,
+        #[prost(bool, tag = "7")] pub quorum_met: bool,
+        #[prost(bool, tag = "8")] pub inconclusive: bool,
+        #[prost(string, repeated, tag = "9")] pub non_revealers: Vec<String>,
+        #[prost(string, repeated, tag = "10")] pub penalized: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct VerifyCommitmentRequest {
+        #[prost(string, tag = "1")] pub id: String,
+        #[prost(string, tag = "2")] pub voter: String,
+        #[prost(string, tag = "3")] pub value_json: String,
+        #[prost(string, tag = "4")] pub salt_hex: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct VerifyCommitmentResponse {
+        #[prost(bool, tag = "1")] pub valid: bool,
+        #[prost(string, tag = "2")] pub commitment_hex: String,
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/ddv.vote.v1.VoteService.rs"));
+}
+
+use proto::*;
+
+impl From<ServiceError> for Status {
+    fn from(e: ServiceError) -> Self {
+        let code = match &e {
+            ServiceError::BadRequest(_) => Code::InvalidArgument,
+            ServiceError::NotFound => Code::NotFound,
+            ServiceError::Conflict => Code::AlreadyExists,
+            ServiceError::Forbidden => Code::PermissionDenied,
+            ServiceError::InsufficientReveals { .. } => Code::FailedPrecondition,
+            ServiceError::Internal => Code::Internal,
+        };
+        Status::new(code, e.to_string())
+    }
+}
+
+/// Server side of the gRPC interface, backed by the app's `VoteService`.
+pub struct GrpcVoteService {
+    state: Arc<AppState>,
+    auth: AuthState,
+}
+
+impl GrpcVoteService {
+    pub fn new(state: Arc<AppState>) -> Self {
+        let auth = AuthState::new(state.api_auth.clone(), state.api_keys.clone());
+        Self { state, auth }
+    }
+
+    async fn authorize<T>(&self, req: &Request<T>, scope: &str) -> Result<(), Status> {
+        let api_key = req.metadata().get(API_KEY_HEADER).and_then(|v| v.to_str().ok());
+        let bearer = req.metadata().get("authorization").and_then(|v| v.to_str().ok()).and_then(|v| v.strip_prefix("Bearer "));
+        self.auth.authorize(api_key, bearer, scope).await.map_err(|(status, message)| {
+            let code = match status {
+                StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+                StatusCode::FORBIDDEN => Code::PermissionDenied,
+                StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+                _ => Code::Internal,
+            };
+            Status::new(code, message)
+        })
+    }
+
+    async fn authorize_read<T>(&self, req: &Request<T>) -> Result<(), Status> {
+        if !self.state.api_auth.protect_reads { return Ok(()); }
+        self.authorize(req, SCOPE_VOTES_READ).await
+    }
+}
+
+/// The vote service wrapped for `tonic::transport::Server::add_service`.
+pub fn grpc_service(state: Arc<AppState>) -> VoteServiceServer<GrpcVoteService> {
+    VoteServiceServer::new(GrpcVoteService::new(state))
+}
+
+/// Serve the gRPC interface on `listener` until `shutdown` resolves.
+pub async fn serve_grpc(state: Arc<AppState>, listener: tokio::net::TcpListener, shutdown: impl Future<Output = ()>) -> Result<(), tonic::transport::Error> {
+    let incoming = TcpIncoming::from_listener(listener, true, None).expect("tcp incoming from a bound listener");
+    tonic::transport::Server::builder()
+        .add_service(grpc_service(state))
+        .serve_with_incoming_shutdown(incoming, shutdown)
+        .await
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(field: &str, json: &str) -> Result<T, Status> {
+    serde_json::from_str(json).map_err(|e| Status::invalid_argument(format!("{} is not valid: {}", field, e)))
+}
+
+fn to_json(value: &impl serde::Serialize) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Same input guards as the REST commit and reveal handlers.
+fn check_voter_and_salt(voter: &str, salt_hex: &str) -> Result<(), Status> {
+    if voter.trim().is_empty() { return Err(Status::invalid_argument("voter is required")); }
+    if salt_hex.len() < 2 { return Err(Status::invalid_argument("salt_hex is required")); }
+    Ok(())
+}
+
+impl From<VoteSummaryDto> for VoteSummary {
+    fn from(v: VoteSummaryDto) -> Self {
+        Self {
+            id: v.id,
+            title: v.title,
+            commit_start_height: v.commit_window.0,
+            commit_end_height: v.commit_window.1,
+            reveal_start_height: v.reveal_window.0,
+            reveal_end_height: v.reveal_window.1,
+            status: v.status,
+        }
+    }
+}
+
+impl From<VoteDetailDto> for Vote {
+    fn from(v: VoteDetailDto) -> Self {
+        Self { id: v.id, config_json: to_json(&v.config), created_ts: v.created_ts, num_commitments: v.num_commitments, num_reveals: v.num_reveals }
+    }
+}
+
+impl From<VoteResultsDto> for VoteResults {
+    fn from(r: VoteResultsDto) -> Self {
+        Self {
+            vote_id: r.vote_id,
+            result_json: to_json(&r.result),
+            total_eligible: r.total_eligible,
+            total_revealed: r.total_revealed,
+            total_abstained: r.total_abstained,
+            participation_rate: r.participation_rate,
+            quorum_met: r.quorum_met,
+            inconclusive: r.inconclusive,
+            non_revealers: r.non_revealers,
+            penalized: r.penalized,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl VoteService for GrpcVoteService {
+    async fn create_vote(&self, req: Request<CreateVoteRequest>) -> Result<Response<CreateVoteResponse>, Status> {
+        self.authorize(&req, SCOPE_VOTES_WRITE).await?;
+        let req = req.into_inner();
+        let config: VoteConfig = parse_json("config_json", &req.config_json)?;
+        if config.title.trim().is_empty() { return Err(Status::invalid_argument("title cannot be empty")); }
+        if config.commit_end_height > config.reveal_start_height { return Err(Status::invalid_argument("commit window must end before reveal starts")); }
+        let id = self.state.service.create_vote_with_nonce(config, req.id_nonce).await?;
+        Ok(Response::new(CreateVoteResponse { id }))
+    }
+
+    async fn list_votes(&self, req: Request<ListVotesRequest>) -> Result<Response<ListVotesResponse>, Status> {
+        self.authorize_read(&req).await?;
+        let req = req.into_inner();
+        let limit = if req.limit == 0 { 50 } else { req.limit };
+        let (items, total) = self.state.service.list_votes(req.offset, limit).await?;
+        Ok(Response::new(ListVotesResponse { items: items.into_iter().map(Into::into).collect(), total }))
+    }
+
+    async fn get_vote(&self, req: Request<GetVoteRequest>) -> Result<Response<Vote>, Status> {
+        self.authorize_read(&req).await?;
+        let vote = self.state.service.get_vote(&req.into_inner().id).await?;
+        Ok(Response::new(vote.into()))
+    }
+
+    async fn commit(&self, req: Request<CommitRequest>) -> Result<Response<CommitResponse>, Status> {
+        self.authorize(&req, SCOPE_VOTES_WRITE).await?;
+        let req = req.into_inner();
+        check_voter_and_salt(&req.voter, &req.salt_hex)?;
+        let value: Value = parse_json("value_json", &req.value_json)?;
+        let r = self.state.service.commit_signed(&req.id, &req.voter, value, req.salt_hex, req.signature_hex).await?;
+        Ok(Response::new(CommitResponse { commitment_hex: r.commitment_hex, ts: r.ts }))
+    }
+
+    async fn reveal(&self, req: Request<RevealRequest>) -> Result<Response<RevealResponse>, Status> {
+        self.authorize(&req, SCOPE_VOTES_WRITE).await?;
+        let req = req.into_inner();
+        check_voter_and_salt(&req.voter, &req.salt_hex)?;
+        let value: Value = parse_json("value_json", &req.value_json)?;
+        let r = self.state.service.reveal(&req.id, &req.voter, value, req.salt_hex).await?;
+        Ok(Response::new(RevealResponse { accepted: r.accepted, ts: r.ts }))
+    }
+
+    async fn get_results(&self, req: Request<GetResultsRequest>) -> Result<Response<VoteResults>, Status> {
+        self.authorize_read(&req).await?;
+        let h = self.state.current_height.load(Ordering::Relaxed);
+        let results = self.state.service.results_at(&req.into_inner().id, Some(h)).await?;
+        Ok(Response::new(results.into()))
+    }
+
+    async fn verify_commitment(&self, req: Request<VerifyCommitmentRequest>) -> Result<Response<VerifyCommitmentResponse>, Status> {
+        self.authorize_read(&req).await?;
+        let req = req.into_inner();
+        check_voter_and_salt(&req.voter, &req.salt_hex)?;
+        let value: Value = parse_json("value_json", &req.value_json)?;
+        let check = self.state.service.verify_commitment(&req.id, &req.voter, value, &req.salt_hex).await?;
+        Ok(Response::new(VerifyCommitmentResponse { valid: check.valid, commitment_hex: check.commitment_hex }))
+    }
+}
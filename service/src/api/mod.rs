@@ -1,2 +1,8 @@
+pub mod auth;
+pub mod cors;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod routes;
+pub use auth::*;
+pub use cors::*;
 pub use routes::*;
@@ -16,12 +16,20 @@
  * - 集成区块链状态同步
  */
 
-use axum::{routing::get, Router, Json};
+use axum::{middleware, routing::{get, post}, Router, Json};
+use axum::body::Body;
 use axum::extract::ws::{WebSocketUpgrade, Message, WebSocket};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use futures_util::StreamExt;
+use std::convert::Infallible;
 use std::sync::Arc;
+use tokio::sync::mpsc;
+use crate::api::auth::{require_scope, AuthState};
 use crate::core::state::AppState;
 use axum::extract::{State, Path, Query};
 use crate::model::response::ApiResponse;
+use crate::model::api_key::*;
 use crate::model::vote::*;
 
 /**
@@ -47,17 +55,56 @@ async fn height_handler(State(state): State<Arc<AppState>>) -> Json<ApiResponse<
     Json(ApiResponse::success(Some(ChainHeightDto { height: h })))
 }
 
+/**
+ * 存储统计处理器
+ * 返回投票总数、按阶段统计的投票数以及承诺和揭示总数
+ * 
+ * @param state - 应用状态，包含存储和当前区块高度
+ * @returns JSON响应，包含存储统计信息
+ */
+async fn stats_handler(State(state): State<Arc<AppState>>) -> Json<ApiResponse<StoreStatsDto>> {
+    let h = state.current_height.load(std::sync::atomic::Ordering::Relaxed);
+    match state.store.get_stats(h).await {
+        Ok(stats) => Json(ApiResponse::success(Some(stats))),
+        Err(e) => Json(ApiResponse::error(&format!("{}", e))),
+    }
+}
+
+/**
+ * 构建路由
+ * 创建、克隆、修改配置、承诺和揭示需要 `votes:write`，批量导入揭示需要 `votes:import`，
+ * 密钥管理需要 `keys:admin`（`api.enabled` 时生效），
+ * 读取路由仅在 `api.protect_reads` 时要求 `votes:read`
+ */
 pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
+    let auth = Arc::new(AuthState::new(state.api_auth.clone(), state.api_keys.clone()));
+    let guard = |scope: &'static str| middleware::from_fn_with_state((auth.clone(), scope), require_scope);
+    let mut reads = Router::new()
         .route("/api/status", get(status_handler))
         .route("/api/height", get(height_handler))
+        .route("/api/stats", get(stats_handler))
         .route("/api/ws/height", get(ws_height))
-        .route("/api/votes", get(list_votes).post(create_vote))
+        .route("/api/votes", get(list_votes))
         .route("/api/votes/:id", get(get_vote))
-        .route("/api/votes/:id/commit", axum::routing::post(commit_vote))
-        .route("/api/votes/:id/reveal", axum::routing::post(reveal_vote))
-        .route("/api/votes/:id/results", get(results_vote))
-        .with_state(state)
+        .route("/api/votes/:id/results", get(results_vote));
+    if state.api_auth.protect_reads {
+        reads = reads.route_layer(guard(SCOPE_VOTES_READ));
+    }
+    let writes = Router::new()
+        .route("/api/votes", post(create_vote))
+        .route("/api/votes/:id/config", post(update_vote_config))
+        .route("/api/votes/:id/clone", post(clone_vote))
+        .route("/api/votes/:id/commit", post(commit_vote))
+        .route("/api/votes/:id/reveal", post(reveal_vote))
+        .route_layer(guard(SCOPE_VOTES_WRITE));
+    let imports = Router::new()
+        .route("/api/votes/:id/reveals/import", post(import_reveals))
+        .route_layer(guard(SCOPE_VOTES_IMPORT));
+    let admin = Router::new()
+        .route("/api/admin/keys", get(list_api_keys).post(create_api_key))
+        .route("/api/admin/keys/:id/revoke", post(revoke_api_key))
+        .route_layer(guard(SCOPE_KEYS_ADMIN));
+    reads.merge(writes).merge(imports).merge(admin).with_state(state)
 }
 
 async fn list_votes(State(state): State<Arc<AppState>>, Query(q): Query<PaginationQuery>) -> Json<ApiResponse<Page<VoteSummaryDto>>> {
@@ -77,7 +124,7 @@ async fn create_vote(State(state): State<Arc<AppState>>, Json(req): Json<CreateV
     if req.config.commit_end_height > req.config.reveal_start_height {
         return Json(ApiResponse::error("commit window must end before reveal starts"));
     }
-    match state.service.create_vote(req.config).await {
+    match state.service.create_vote_with_nonce(req.config, req.id_nonce).await {
         Ok(id) => Json(ApiResponse::success(Some(id))),
         Err(e) => Json(ApiResponse::error(&format!("{}", e))),
     }
@@ -90,31 +137,221 @@ async fn get_vote(State(state): State<Arc<AppState>>, Path(id): Path<String>) ->
     }
 }
 
+/**
+ * 修改投票配置
+ * 首个承诺之后模板及其参数不可再修改，否则返回冲突
+ */
+async fn update_vote_config(State(state): State<Arc<AppState>>, Path(id): Path<String>, Json(req): Json<UpdateVoteConfigRequest>) -> Json<ApiResponse<VoteDetailDto>> {
+    if req.config.title.trim().is_empty() {
+        return Json(ApiResponse::error("title cannot be empty"));
+    }
+    match state.service.update_vote_config(&id, req.config).await {
+        Ok(v) => Json(ApiResponse::success(Some(v))),
+        Err(e) => Json(ApiResponse::error(&format!("{}", e))),
+    }
+}
+
+/**
+ * 克隆投票
+ * 复制源投票的配置（标题、模板、参数、参与者），按需覆盖标题、描述或整体平移窗口高度，
+ * 创建一个没有承诺和揭示的新投票，返回新投票ID
+ */
+async fn clone_vote(State(state): State<Arc<AppState>>, Path(id): Path<String>, Json(overrides): Json<VoteConfigOverrides>) -> Json<ApiResponse<String>> {
+    if overrides.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return Json(ApiResponse::error("title cannot be empty"));
+    }
+    match state.service.clone_vote(&id, overrides).await {
+        Ok(id) => Json(ApiResponse::success(Some(id))),
+        Err(e) => Json(ApiResponse::error(&format!("{}", e))),
+    }
+}
+
 async fn commit_vote(State(state): State<Arc<AppState>>, Path(id): Path<String>, Json(req): Json<CommitRequest>) -> Json<ApiResponse<CommitResponse>> {
     if req.voter.trim().is_empty() { return Json(ApiResponse::error("voter is required")); }
     if req.salt_hex.len() < 2 { return Json(ApiResponse::error("salt_hex is required")); }
-    match state.service.commit(&id, &req.voter, req.vote_value, req.salt_hex).await {
+    let result = match req.delegation {
+        Some(d) if d.delegate != req.voter => return Json(ApiResponse::error("delegation is not for this voter")),
+        Some(d) => state.service.commit_delegated(&id, d, req.vote_value, req.salt_hex, req.signature_hex).await,
+        None => state.service.commit_signed(&id, &req.voter, req.vote_value, req.salt_hex, req.signature_hex).await,
+    };
+    match result {
         Ok(r) => Json(ApiResponse::success(Some(r))),
         Err(e) => Json(ApiResponse::error(&format!("{}", e))),
     }
 }
 
+/**
+ * 揭示请求的基本输入检查，单条揭示与批量导入共用
+ */
+fn check_reveal_request(req: &RevealRequest) -> Result<(), &'static str> {
+    if req.voter.trim().is_empty() { return Err("voter is required"); }
+    if req.salt_hex.len() < 2 { return Err("salt_hex is required"); }
+    Ok(())
+}
+
 async fn reveal_vote(State(state): State<Arc<AppState>>, Path(id): Path<String>, Json(req): Json<RevealRequest>) -> Json<ApiResponse<RevealResponse>> {
-    if req.voter.trim().is_empty() { return Json(ApiResponse::error("voter is required")); }
-    if req.salt_hex.len() < 2 { return Json(ApiResponse::error("salt_hex is required")); }
+    if let Err(e) = check_reveal_request(&req) { return Json(ApiResponse::error(e)); }
     match state.service.reveal(&id, &req.voter, req.vote_value, req.salt_hex).await {
         Ok(r) => Json(ApiResponse::success(Some(r))),
         Err(e) => Json(ApiResponse::error(&format!("{}", e))),
     }
 }
 
+/// 每批校验并写入的行数
+const IMPORT_CHUNK_LINES: usize = 500;
+/// 单行的最大字节数，超出后停止导入
+const IMPORT_MAX_LINE_BYTES: usize = 64 * 1024;
+
+/**
+ * 批量导入揭示
+ * 请求体为NDJSON，每行一个揭示请求；边读取边按批校验并写入，不缓冲整个请求体。
+ * 每行的校验与 `reveal` 相同。响应同样是NDJSON：每个非空行一条 `RevealImportLine`，
+ * 最后一行为 `RevealImportSummary`
+ */
+async fn import_reveals(State(state): State<Arc<AppState>>, Path(id): Path<String>, body: Body) -> Response {
+    if let Err(e) = state.service.get_vote(&id).await {
+        return Json(ApiResponse::<RevealImportSummary>::error(&format!("{}", e))).into_response();
+    }
+    let (tx, rx) = mpsc::channel(IMPORT_CHUNK_LINES);
+    tokio::spawn(RevealImport { state, id, tx, pending: Vec::new(), summary: RevealImportSummary { imported: 0, failed: 0 } }.run(body));
+    let lines = futures_util::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|line| (Ok::<_, Infallible>(line), rx)) });
+    ([(header::CONTENT_TYPE, "application/x-ndjson")], Body::from_stream(lines)).into_response()
+}
+
+/**
+ * 一次导入的状态：待处理的行（行号, 原始字节）和累计结果，结果行通过 `tx` 发回客户端
+ */
+struct RevealImport {
+    state: Arc<AppState>,
+    id: String,
+    tx: mpsc::Sender<String>,
+    pending: Vec<(u64, Vec<u8>)>,
+    summary: RevealImportSummary,
+}
+
+impl RevealImport {
+    async fn run(mut self, body: Body) {
+        let mut stream = body.into_data_stream();
+        let mut buf: Vec<u8> = Vec::new();
+        let mut line_no = 0u64;
+        loop {
+            let done = match stream.next().await {
+                Some(Ok(bytes)) => { buf.extend_from_slice(&bytes); false }
+                Some(Err(e)) => {
+                    let _ = self.flush().await;
+                    self.send_error(line_no + 1, &format!("request body: {}", e)).await;
+                    break;
+                }
+                None => true,
+            };
+            while let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+                line_no += 1;
+                let line: Vec<u8> = buf.drain(..=pos).collect();
+                self.pending.push((line_no, line));
+                if self.pending.len() >= IMPORT_CHUNK_LINES && !self.flush().await { return; }
+            }
+            if buf.len() > IMPORT_MAX_LINE_BYTES {
+                if !self.flush().await { return; }
+                self.send_error(line_no + 1, "line too long").await;
+                break;
+            }
+            if done {
+                // the last line may not end with a newline
+                if !buf.is_empty() { self.pending.push((line_no + 1, std::mem::take(&mut buf))); }
+                if !self.flush().await { return; }
+                break;
+            }
+        }
+        let summary = serde_json::to_string(&self.summary).unwrap_or_default();
+        let _ = self.tx.send(summary + "\n").await;
+    }
+
+    /**
+     * 校验并写入待处理的行，按行号顺序发回结果；客户端已断开时返回 false
+     */
+    async fn flush(&mut self) -> bool {
+        let mut results = Vec::with_capacity(self.pending.len());
+        let mut requests = Vec::new();
+        for (line, raw) in std::mem::take(&mut self.pending) {
+            if raw.iter().all(|b| b.is_ascii_whitespace()) { continue; }
+            let parsed = serde_json::from_slice::<RevealRequest>(&raw).map_err(|e| e.to_string())
+                .and_then(|req| check_reveal_request(&req).map(|_| req).map_err(str::to_string));
+            match parsed {
+                Ok(req) => requests.push((line, req)),
+                Err(e) => results.push(RevealImportLine { line, voter: None, accepted: false, error: Some(e) }),
+            }
+        }
+        let voters: Vec<(u64, String)> = requests.iter().map(|(line, req)| (*line, req.voter.clone())).collect();
+        let outcomes = match self.state.service.import_reveals(&self.id, requests.into_iter().map(|(_, req)| req).collect()).await {
+            Ok(outcomes) => outcomes.into_iter().map(|o| o.err().map(|e| e.to_string())).collect(),
+            Err(e) => vec![Some(e.to_string()); voters.len()],
+        };
+        for ((line, voter), error) in voters.into_iter().zip(outcomes) {
+            results.push(RevealImportLine { line, voter: Some(voter), accepted: error.is_none(), error });
+        }
+        results.sort_by_key(|r| r.line);
+        for result in results {
+            if result.accepted { self.summary.imported += 1 } else { self.summary.failed += 1 }
+            let line = serde_json::to_string(&result).unwrap_or_default();
+            if self.tx.send(line + "\n").await.is_err() { return false; }
+        }
+        true
+    }
+
+    async fn send_error(&mut self, line: u64, error: &str) {
+        self.summary.failed += 1;
+        let result = RevealImportLine { line, voter: None, accepted: false, error: Some(error.to_string()) };
+        let _ = self.tx.send(serde_json::to_string(&result).unwrap_or_default() + "\n").await;
+    }
+}
+
 async fn results_vote(State(state): State<Arc<AppState>>, Path(id): Path<String>) -> Json<ApiResponse<VoteResultsDto>> {
-    match state.service.results(&id).await {
+    let h = state.current_height.load(std::sync::atomic::Ordering::Relaxed);
+    match state.service.results_at(&id, Some(h)).await {
         Ok(r) => Json(ApiResponse::success(Some(r))),
         Err(e) => Json(ApiResponse::error(&format!("{}", e))),
     }
 }
 
+/**
+ * 创建API密钥
+ * 密钥明文只在此响应中返回一次，存储中仅保留其哈希
+ */
+async fn create_api_key(State(state): State<Arc<AppState>>, Json(req): Json<CreateApiKeyRequest>) -> Json<ApiResponse<CreatedApiKeyDto>> {
+    if req.scopes.is_empty() { return Json(ApiResponse::error("scopes cannot be empty")); }
+    if let Some(unknown) = req.scopes.iter().find(|s| ![SCOPE_VOTES_READ, SCOPE_VOTES_WRITE, SCOPE_VOTES_IMPORT, SCOPE_KEYS_ADMIN].contains(&s.as_str())) {
+        return Json(ApiResponse::error(&format!("unknown scope {}", unknown)));
+    }
+    let secret = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+    let key = ApiKey {
+        id: uuid::Uuid::new_v4().simple().to_string(),
+        hashed_secret: ApiKey::hash_secret(&secret),
+        scopes: req.scopes,
+        created_at: chrono::Utc::now().timestamp(),
+        expires_at: req.expires_at,
+        revoked: false,
+        rate_limit_per_minute: req.rate_limit_per_minute,
+    };
+    match state.api_keys.create_key(key.clone()).await {
+        Ok(()) => Json(ApiResponse::success(Some(CreatedApiKeyDto { api_key: format!("{}.{}", key.id, secret), key: (&key).into() }))),
+        Err(e) => Json(ApiResponse::error(&format!("{}", e))),
+    }
+}
+
+async fn list_api_keys(State(state): State<Arc<AppState>>) -> Json<ApiResponse<Vec<ApiKeyDto>>> {
+    match state.api_keys.list_keys().await {
+        Ok(keys) => Json(ApiResponse::success(Some(keys.iter().map(ApiKeyDto::from).collect()))),
+        Err(e) => Json(ApiResponse::error(&format!("{}", e))),
+    }
+}
+
+async fn revoke_api_key(State(state): State<Arc<AppState>>, Path(id): Path<String>) -> Json<ApiResponse<()>> {
+    match state.api_keys.revoke_key(&id).await {
+        Ok(()) => Json(ApiResponse::success(None)),
+        Err(e) => Json(ApiResponse::error(&format!("{}", e))),
+    }
+}
+
 async fn ws_height(State(state): State<Arc<AppState>>, ws: WebSocketUpgrade) -> impl axum::response::IntoResponse {
     ws.on_upgrade(move |socket| ws_height_loop(state, socket))
 }
@@ -1,6 +1,7 @@
 use clap::{Parser, Subcommand, Args};
 use serde_json::json;
-use crate::service::{VoteService, VoteServiceImpl};
+use crate::service::{VoteService, VoteServiceImpl, DEFAULT_DOMAIN_TAG, DEFAULT_MAX_PARTICIPANTS};
+use crate::config::Config;
 use crate::core::template::TemplateRegistry;
 use crate::store::{VoteStore, memory::MemoryVoteStore};
 use std::sync::Arc;
@@ -36,6 +37,10 @@ pub struct CreateArgs {
     #[arg(long)] pub participants: Vec<String>,
     #[arg(long, default_value="option_index")] pub value_template: String,
     #[arg(long, default_value_t=0)] pub template_max: u64,
+    #[arg(long, default_value_t=0.0)] pub quorum_threshold: f64,
+    #[arg(long, default_value_t=0)] pub reveal_threshold: u64,
+    /// Derive the vote ID from the config and this nonce instead of a random UUID
+    #[arg(long)] pub id_nonce: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -44,6 +49,7 @@ pub struct CommitArgs {
     #[arg(long)] pub voter: String,
     #[arg(long)] pub vote_value: u64,
     #[arg(long)] pub salt_hex: String,
+    #[arg(long)] pub signature_hex: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -59,11 +65,16 @@ pub fn parse_args() -> Cli { Cli::parse() }
 pub async fn execute_cli(cli: Cli) -> i32 {
     // build in-memory service and registry to reuse core logic
     let store: Arc<dyn VoteStore> = Arc::new(MemoryVoteStore::default());
-    let mut reg = TemplateRegistry::new();
-    reg.register(crate::core::template::BitTemplate);
-    reg.register(crate::core::template::OptionIndexTemplate);
-    reg.register(crate::core::template::StringTemplate);
-    let service = VoteServiceImpl::new(store.clone(), Arc::new(reg));
+    let mut reg = TemplateRegistry::builtin();
+    // config-declared templates are optional for the CLI; a missing config file just means none
+    let mut max_participants = DEFAULT_MAX_PARTICIPANTS;
+    let mut domain_tag = DEFAULT_DOMAIN_TAG.to_string();
+    if let Ok(cfg) = Config::load_from_env_or_default() {
+        if let Err(e) = reg.register_definitions(&cfg.templates) { eprintln!("warning: {}", e); }
+        max_participants = cfg.limits.max_participants;
+        domain_tag = cfg.commitments.domain_tag;
+    }
+    let service = VoteServiceImpl::new(store.clone(), Arc::new(reg)).with_max_participants(max_participants).with_domain_tag(domain_tag);
     match cli.command {
         Some(Commands::Create(args)) => {
             let cfg = VoteConfig {
@@ -75,16 +86,23 @@ pub async fn execute_cli(cli: Cli) -> i32 {
                 reveal_start_height: args.reveal_start,
                 reveal_end_height: args.reveal_end,
                 participants: args.participants.clone(),
+                participant_keys: Default::default(),
+                quorum_threshold: args.quorum_threshold,
+                non_revealer_policy: Default::default(),
+                reveal_threshold: args.reveal_threshold,
                 value_template: args.value_template,
+                template_version: None,
                 template_params: json!({"max": args.template_max}),
+                max_participants: None,
+                commitment_scheme: None,
             };
-            match service.create_vote(cfg).await {
+            match service.create_vote_with_nonce(cfg, args.id_nonce).await {
                 Ok(id) => { println!("{}", id); 0 }
                 Err(e) => { eprintln!("error: {}", e); 1 }
             }
         }
         Some(Commands::Commit(args)) => {
-            match service.commit(&args.vote_id, &args.voter, json!(args.vote_value), args.salt_hex).await {
+            match service.commit_signed(&args.vote_id, &args.voter, json!(args.vote_value), args.salt_hex, args.signature_hex).await {
                 Ok(r) => { println!("{}", r.commitment_hex); 0 }
                 Err(e) => { eprintln!("error: {}", e); 2 }
             }
@@ -1,15 +1,112 @@
 use serde::Deserialize;
 use std::fs;
 use std::path::Path;
+use crate::core::template::{TemplateDefinition, TemplateRegistry};
+use crate::service::{DEFAULT_DOMAIN_TAG, DEFAULT_MAX_PARTICIPANTS};
 
 #[derive(Debug, Deserialize, Clone)]
-pub struct ServerConfig { pub host: String, pub port: u16 }
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// Address the gRPC interface listens on, e.g. `0.0.0.0:50051`; only served with the `grpc` feature.
+    #[serde(default)]
+    pub grpc_bind: Option<String>,
+}
+
+/// Request auth; when `enabled`, create/commit/reveal need an API key or one of the static `tokens`, and reads do too if `protect_reads`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ApiAuth {
+    pub enabled: bool,
+    pub tokens: Vec<String>,
+    #[serde(default)]
+    pub protect_reads: bool,
+}
+
+/// In-memory store persistence; snapshots are disabled unless `snapshot_path` is set.
+#[derive(Debug, Deserialize, Clone)]
+pub struct StoreConfig {
+    #[serde(default)]
+    pub snapshot_path: Option<String>,
+    #[serde(default = "default_snapshot_interval_secs")]
+    pub snapshot_interval_secs: u64,
+}
+
+fn default_snapshot_interval_secs() -> u64 { 60 }
 
+impl Default for StoreConfig {
+    fn default() -> Self { Self { snapshot_path: None, snapshot_interval_secs: default_snapshot_interval_secs() } }
+}
+
+/// Cross-origin policy; with no `allowed_origins` no cross-origin request is allowed.
 #[derive(Debug, Deserialize, Clone)]
-pub struct ApiAuth { pub enabled: bool, pub tokens: Vec<String> }
+pub struct CorsConfig {
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    #[serde(default = "default_cors_methods")]
+    pub allowed_methods: Vec<String>,
+    #[serde(default = "default_cors_headers")]
+    pub allowed_headers: Vec<String>,
+    #[serde(default)]
+    pub allow_credentials: bool,
+    #[serde(default = "default_cors_max_age_secs")]
+    pub max_age_secs: u64,
+}
+
+fn default_cors_methods() -> Vec<String> { vec!["GET".into(), "POST".into()] }
+fn default_cors_headers() -> Vec<String> { vec!["content-type".into(), "authorization".into()] }
+fn default_cors_max_age_secs() -> u64 { 600 }
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![],
+            allowed_methods: default_cors_methods(),
+            allowed_headers: default_cors_headers(),
+            allow_credentials: false,
+            max_age_secs: default_cors_max_age_secs(),
+        }
+    }
+}
 
+/// Per-vote bounds that keep a single vote from exhausting storage.
 #[derive(Debug, Deserialize, Clone)]
-pub struct Config { pub server: ServerConfig, pub api: ApiAuth }
+pub struct LimitsConfig {
+    /// Participant cap applied to open votes created without their own `max_participants`.
+    #[serde(default = "default_max_participants")]
+    pub max_participants: u64,
+}
+
+fn default_max_participants() -> u64 { DEFAULT_MAX_PARTICIPANTS }
+
+impl Default for LimitsConfig {
+    fn default() -> Self { Self { max_participants: default_max_participants() } }
+}
+
+/// How commitments are bound to this deployment.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CommitmentConfig {
+    /// Mixed into every commitment hash so commitments made against one deployment (say, staging) never open in another.
+    #[serde(default = "default_domain_tag")]
+    pub domain_tag: String,
+}
+
+fn default_domain_tag() -> String { DEFAULT_DOMAIN_TAG.to_string() }
+
+impl Default for CommitmentConfig {
+    fn default() -> Self { Self { domain_tag: default_domain_tag() } }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub api: ApiAuth,
+    #[serde(default)] pub store: StoreConfig,
+    #[serde(default)] pub cors: CorsConfig,
+    #[serde(default)] pub limits: LimitsConfig,
+    #[serde(default)] pub commitments: CommitmentConfig,
+    /// Extra templates registered at startup on top of the built-in ones.
+    #[serde(default)] pub templates: Vec<TemplateDefinition>,
+}
 
 impl Config {
     pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
@@ -27,7 +124,17 @@ impl Config {
     pub fn validate(&self) -> Result<(), String> {
         if self.server.host.trim().is_empty() { return Err("server.host cannot be empty".into()); }
         if self.server.port == 0 { return Err("server.port cannot be 0".into()); }
+        if let Some(bind) = &self.server.grpc_bind {
+            bind.parse::<std::net::SocketAddr>().map_err(|e| format!("server.grpc_bind {}: {}", bind, e))?;
+        }
         if self.api.enabled && self.api.tokens.is_empty() { return Err("api.tokens must be non-empty when api.enabled".into()); }
+        if self.limits.max_participants == 0 { return Err("limits.max_participants cannot be 0".into()); }
+        let tag = &self.commitments.domain_tag;
+        if tag.trim().is_empty() || tag.contains('|') { return Err("commitments.domain_tag must be non-empty and cannot contain '|'".into()); }
+        if self.store.snapshot_path.is_some() && self.store.snapshot_interval_secs == 0 { return Err("store.snapshot_interval_secs cannot be 0".into()); }
+        let any_origin = self.cors.allowed_origins.iter().any(|o| o == "*");
+        if any_origin && self.cors.allow_credentials { return Err("cors.allowed_origins cannot contain \"*\" when cors.allow_credentials is true".into()); }
+        TemplateRegistry::builtin().register_definitions(&self.templates).map_err(|e| format!("templates: {}", e))?;
         Ok(())
     }
 }
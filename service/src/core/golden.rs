@@ -0,0 +1,53 @@
+//! Golden canonicalization vectors checked at startup.
+//!
+//! Commitments are hashes over `canonicalize` output, so any change to those bytes for an existing
+//! template version breaks every in-flight reveal. A changed vector means the template needs a new
+//! `version()` instead.
+
+use serde_json::Value;
+use crate::core::template::TemplateRegistry;
+
+pub struct GoldenVector {
+    pub template: &'static str,
+    pub version: u32,
+    pub params: &'static str,
+    pub value: &'static str,
+    pub canonical_hex: &'static str,
+}
+
+const fn vector(template: &'static str, params: &'static str, value: &'static str, canonical_hex: &'static str) -> GoldenVector {
+    GoldenVector { template, version: 1, params, value, canonical_hex }
+}
+
+/// Expected canonical bytes for the built-in templates.
+pub const GOLDEN_VECTORS: &[GoldenVector] = &[
+    vector("bit", "{}", "true", "01"),
+    vector("bit", "{}", "false", "00"),
+    vector("bit", "{}", "1", "01"),
+    vector("bit", "{}", "0", "00"),
+    vector("option_index", r#"{"max":5}"#, "0", "0000000000000000"),
+    vector("option_index", r#"{"max":300}"#, "258", "0000000000000102"),
+    vector("string", "{}", r#""yes""#, "796573"),
+    vector("string", "{}", r#""""#, ""),
+    vector("string", r#"{"max_len":16}"#, r#""héllo""#, "68c3a96c6c6f"),
+];
+
+/// Check every vector against the registered template version. Returns one message per mismatching vector,
+/// or per vector whose template version is not registered at all.
+pub fn verify_golden_vectors(registry: &TemplateRegistry, vectors: &[GoldenVector]) -> Result<(), Vec<String>> {
+    let mut failures = Vec::new();
+    for v in vectors {
+        let tpl = match registry.get_version(v.template, v.version) {
+            Ok(tpl) => tpl,
+            Err(e) => { failures.push(format!("{} v{} is not registered: {}", v.template, v.version, e)); continue; }
+        };
+        let params: Value = serde_json::from_str(v.params).unwrap_or(Value::Null);
+        let value: Value = serde_json::from_str(v.value).unwrap_or(Value::Null);
+        match tpl.canonicalize(&value, &params) {
+            Ok(bytes) if hex::encode(&bytes) == v.canonical_hex => {}
+            Ok(bytes) => failures.push(format!("{} v{} canonicalizes {} to {}, expected {}", v.template, v.version, v.value, hex::encode(&bytes), v.canonical_hex)),
+            Err(e) => failures.push(format!("{} v{} rejected {}: {}", v.template, v.version, v.value, e)),
+        }
+    }
+    if failures.is_empty() { Ok(()) } else { Err(failures) }
+}
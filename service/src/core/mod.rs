@@ -1,4 +1,8 @@
+pub mod golden;
 pub mod state;
+pub mod tally;
 pub mod template;
+pub use golden::*;
 pub use state::*;
+pub use tally::*;
 pub use template::*;
@@ -1,9 +1,9 @@
 use std::sync::{Arc, atomic::{AtomicU64, Ordering}};
 use tokio::sync::Mutex;
 use chrono::Utc;
-use crate::core::template::{TemplateRegistry, BitTemplate, OptionIndexTemplate, StringTemplate};
-use crate::config::Config;
-use crate::store::{VoteStore, memory::MemoryVoteStore};
+use crate::core::template::TemplateRegistry;
+use crate::config::{ApiAuth, Config, CorsConfig, ServerConfig};
+use crate::store::{VoteStore, api_keys::{ApiKeyStore, MemoryApiKeyStore}, memory::MemoryVoteStore};
 use crate::service::{VoteService, VoteServiceImpl};
 
 pub struct AppState {
@@ -13,21 +13,42 @@ pub struct AppState {
     pub started_at: std::time::Instant,
     pub store: Arc<dyn VoteStore>,
     pub service: Arc<dyn VoteService>,
+    pub cors: CorsConfig,
+    pub api_auth: ApiAuth,
+    pub api_keys: Arc<dyn ApiKeyStore>,
+    pub server: ServerConfig,
 }
 
 impl AppState {
     pub async fn new() -> Arc<Self> {
-        let _cfg = Config::load_from_env_or_default().unwrap_or_else(|e| {
+        let cfg = Config::load_from_env_or_default().unwrap_or_else(|e| {
             tracing::warn!("config load failed: {} - using defaults", e);
-            Config { server: crate::config::ServerConfig { host: "0.0.0.0".into(), port: 8080 }, api: crate::config::ApiAuth { enabled: false, tokens: vec![] } }
+            Config { server: crate::config::ServerConfig { host: "0.0.0.0".into(), port: 8080, grpc_bind: None }, api: Default::default(), store: Default::default(), cors: Default::default(), limits: Default::default(), commitments: Default::default(), templates: Vec::new() }
         });
-        let mut reg = TemplateRegistry::new();
-        reg.register(BitTemplate);
-        reg.register(OptionIndexTemplate);
-        reg.register(StringTemplate);
-        let store: Arc<dyn VoteStore> = Arc::new(MemoryVoteStore::default());
+        Self::with_config(cfg).await
+    }
+
+    /// State built from an already loaded config; starts the height ticker.
+    pub async fn with_config(cfg: Config) -> Arc<Self> {
+        let mut reg = TemplateRegistry::builtin();
+        if let Err(e) = reg.register_definitions(&cfg.templates) {
+            tracing::warn!("config templates not registered: {}", e);
+        }
+        let store: Arc<dyn VoteStore> = match &cfg.store.snapshot_path {
+            Some(path) => match MemoryVoteStore::with_snapshot(path).await {
+                Ok(memory) => {
+                    memory.spawn_snapshot_task(std::time::Duration::from_secs(cfg.store.snapshot_interval_secs));
+                    Arc::new(memory)
+                }
+                Err(e) => {
+                    tracing::warn!("snapshot {} unusable: {} - falling back to pure in-memory store", path, e);
+                    Arc::new(MemoryVoteStore::default())
+                }
+            },
+            None => Arc::new(MemoryVoteStore::default()),
+        };
         let registry = Arc::new(reg);
-        let service: Arc<dyn VoteService> = Arc::new(VoteServiceImpl::new(store.clone(), registry.clone()));
+        let service: Arc<dyn VoteService> = Arc::new(VoteServiceImpl::new(store.clone(), registry.clone()).with_max_participants(cfg.limits.max_participants).with_domain_tag(cfg.commitments.domain_tag.clone()));
         let state = Arc::new(Self {
             current_height: Arc::new(AtomicU64::new(0)),
             votes_count: Mutex::new(0),
@@ -35,6 +56,10 @@ impl AppState {
             started_at: std::time::Instant::now(),
             store,
             service,
+            cors: cfg.cors,
+            api_auth: cfg.api,
+            api_keys: Arc::new(MemoryApiKeyStore::default()),
+            server: cfg.server,
         });
         // background height ticker
         tokio::spawn({
@@ -51,6 +76,11 @@ impl AppState {
         state
     }
 
+    /// Flush the store before the process exits.
+    pub async fn shutdown(&self) {
+        if let Err(e) = self.store.flush().await { tracing::error!("failed to flush store on shutdown: {}", e); }
+    }
+
     pub async fn get_status_json(&self) -> serde_json::Value {
         let h = self.current_height.load(Ordering::Relaxed);
         let v = *self.votes_count.lock().await;
@@ -0,0 +1,45 @@
+//! Tally strategies: how a template's `reduce` is applied to a vote's revealed values.
+use rayon::prelude::*;
+use serde_json::Value;
+
+use crate::core::template::VoteValueTemplate;
+
+/// Reveal count from which `results` switches to the large-vote strategy.
+pub const DEFAULT_PARALLEL_TALLY_THRESHOLD: usize = 100_000;
+
+/// Reveals reduced together by one `ParallelTally` worker.
+pub const DEFAULT_TALLY_CHUNK_SIZE: usize = 10_000;
+
+pub trait TallyStrategy: Send + Sync {
+    /// Must produce exactly what `tpl.reduce(values)` would.
+    fn tally(&self, tpl: &dyn VoteValueTemplate, values: &[Value]) -> Value;
+}
+
+/// One `reduce` over every value on the calling thread.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SequentialTally;
+
+impl TallyStrategy for SequentialTally {
+    fn tally(&self, tpl: &dyn VoteValueTemplate, values: &[Value]) -> Value { tpl.reduce(values) }
+}
+
+/// Reduces fixed-size chunks of the values on the rayon pool and merges the partial results with
+/// `reduce_incremental`. Templates without that hook are reduced sequentially instead.
+#[derive(Clone, Copy, Debug)]
+pub struct ParallelTally { pub chunk_size: usize }
+
+impl Default for ParallelTally {
+    fn default() -> Self { Self { chunk_size: DEFAULT_TALLY_CHUNK_SIZE } }
+}
+
+impl TallyStrategy for ParallelTally {
+    fn tally(&self, tpl: &dyn VoteValueTemplate, values: &[Value]) -> Value {
+        let partials: Vec<Value> = values.par_chunks(self.chunk_size.max(1)).map(|chunk| tpl.reduce(chunk)).collect();
+        // chunks are merged in order, so the hook only needs to be associative
+        let mut merged = Some(tpl.reduce(&[]));
+        for partial in &partials {
+            merged = merged.and_then(|acc| tpl.reduce_incremental(&acc, partial));
+        }
+        merged.unwrap_or_else(|| tpl.reduce(values))
+    }
+}
@@ -1,27 +1,78 @@
+use serde::Deserialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+/// Version assumed for templates that don't declare one and for votes created before pinning.
+pub const DEFAULT_TEMPLATE_VERSION: u32 = 1;
+
 pub trait VoteValueTemplate: Send + Sync {
-    fn id(&self) -> &'static str;
+    fn id(&self) -> &str;
+    /// Bump whenever `validate` or `canonicalize` changes, and keep the old version registered while votes use it.
+    fn version(&self) -> u32 { DEFAULT_TEMPLATE_VERSION }
     fn validate(&self, raw: &Value, params: &Value) -> Result<(), String>;
     fn canonicalize(&self, raw: &Value, params: &Value) -> Result<Vec<u8>, String>;
+    /// Feed the `canonicalize` bytes to `sink` in order, in one or more chunks. Override for templates that can
+    /// hand out their canonical form from the parsed value without first copying it into a new buffer.
+    fn canonicalize_streaming(&self, raw: &Value, params: &Value, sink: &mut dyn FnMut(&[u8])) -> Result<(), String> {
+        sink(&self.canonicalize(raw, params)?);
+        Ok(())
+    }
     fn reduce(&self, values: &[Value]) -> Value { serde_json::json!(values.len()) }
+    /// Merge two `reduce` outputs into the `reduce` of both inputs. Must be associative with `reduce(&[])` as
+    /// identity; `None` means the template can't be tallied in chunks.
+    fn reduce_incremental(&self, _acc: &Value, _partial: &Value) -> Option<Value> { None }
+}
+
+/// `reduce_incremental` for the default count `reduce`.
+pub fn merge_counts(acc: &Value, partial: &Value) -> Option<Value> {
+    Some(serde_json::json!(acc.as_u64()?.checked_add(partial.as_u64()?)?))
 }
 
+/// Templates keyed by ID, with every registered version kept side by side.
 #[derive(Default)]
 pub struct TemplateRegistry {
-    inner: HashMap<String, Arc<dyn VoteValueTemplate>>,
+    inner: HashMap<String, BTreeMap<u32, Arc<dyn VoteValueTemplate>>>,
 }
 
 impl TemplateRegistry {
     pub fn new() -> Self { Self { inner: HashMap::new() } }
+    /// Registry holding the compiled-in templates.
+    pub fn builtin() -> Self {
+        let mut reg = Self::new();
+        reg.register(BitTemplate);
+        reg.register(OptionIndexTemplate);
+        reg.register(StringTemplate);
+        reg
+    }
     pub fn register<T: VoteValueTemplate + 'static>(&mut self, t: T) {
-        self.inner.insert(t.id().to_string(), Arc::new(t));
+        self.inner.entry(t.id().to_string()).or_default().insert(t.version(), Arc::new(t));
+    }
+    /// Like `register`, but refuses to replace a template with the same ID and version.
+    pub fn try_register(&mut self, t: Arc<dyn VoteValueTemplate>) -> Result<(), String> {
+        let versions = self.inner.entry(t.id().to_string()).or_default();
+        if versions.contains_key(&t.version()) { return Err(format!("template already registered: {} v{}", t.id(), t.version())); }
+        versions.insert(t.version(), t);
+        Ok(())
+    }
+    /// Register each definition on top of its already registered base, in order.
+    pub fn register_definitions(&mut self, defs: &[TemplateDefinition]) -> Result<(), String> {
+        for def in defs {
+            let base = self.get(&def.base).map_err(|e| format!("template {}: base {}", def.id, e))?;
+            self.try_register(Arc::new(ParameterizedTemplate::new(def.id.clone(), base, def.params.clone())))?;
+        }
+        Ok(())
     }
+    /// Latest registered version of the template.
     pub fn get(&self, id: &str) -> Result<Arc<dyn VoteValueTemplate>, String> {
-        self.inner.get(id).cloned().ok_or_else(|| format!("template not found: {}", id))
+        self.inner.get(id).and_then(|versions| versions.values().next_back()).cloned().ok_or_else(|| format!("template not found: {}", id))
+    }
+    /// Exactly `version` of the template, which may be older than the latest.
+    pub fn get_version(&self, id: &str, version: u32) -> Result<Arc<dyn VoteValueTemplate>, String> {
+        let versions = self.inner.get(id).ok_or_else(|| format!("template not found: {}", id))?;
+        versions.get(&version).cloned().ok_or_else(|| format!("template {} v{} is no longer registered", id, version))
     }
+    pub fn versions(&self, id: &str) -> Vec<u32> { self.inner.get(id).map(|v| v.keys().copied().collect()).unwrap_or_default() }
     pub fn list_ids(&self) -> Vec<String> { self.inner.keys().cloned().collect() }
 }
 
@@ -43,6 +94,7 @@ impl VoteValueTemplate for BitTemplate {
         };
         Ok(vec![b])
     }
+    fn reduce_incremental(&self, acc: &Value, partial: &Value) -> Option<Value> { merge_counts(acc, partial) }
 }
 
 pub struct OptionIndexTemplate;
@@ -58,19 +110,76 @@ impl VoteValueTemplate for OptionIndexTemplate {
         let idx = raw.as_u64().unwrap();
         Ok(idx.to_be_bytes().to_vec())
     }
+    fn reduce_incremental(&self, acc: &Value, partial: &Value) -> Option<Value> { merge_counts(acc, partial) }
 }
 
+/// Upper bound on string values in bytes; a larger `max_len` param is clamped to it.
+pub const STRING_TEMPLATE_MAX_LEN: u64 = 4096;
+
+/// Largest chunk `canonicalize_streaming` hands to its sink for the built-in templates.
+pub const CANONICAL_CHUNK_LEN: usize = 1024;
+
 pub struct StringTemplate;
 impl VoteValueTemplate for StringTemplate {
     fn id(&self) -> &'static str { "string" }
     fn validate(&self, raw: &Value, params: &Value) -> Result<(), String> {
-        if !raw.is_string() { return Err("string expects string".into()); }
-        if let Some(max_len) = params.get("max_len").and_then(|v| v.as_u64()) {
-            if raw.as_str().unwrap().len() as u64 > max_len { return Err("string too long".into()); }
-        }
+        let value = raw.as_str().ok_or("string expects string")?;
+        let max_len = params.get("max_len").and_then(|v| v.as_u64()).map_or(STRING_TEMPLATE_MAX_LEN, |m| m.min(STRING_TEMPLATE_MAX_LEN));
+        if value.len() as u64 > max_len { return Err("string too long".into()); }
         Ok(())
     }
-    fn canonicalize(&self, raw: &Value, _params: &Value) -> Result<Vec<u8>, String> {
+    fn canonicalize(&self, raw: &Value, params: &Value) -> Result<Vec<u8>, String> {
+        self.validate(raw, params)?;
         Ok(raw.as_str().unwrap().as_bytes().to_vec())
     }
+    fn canonicalize_streaming(&self, raw: &Value, params: &Value, sink: &mut dyn FnMut(&[u8])) -> Result<(), String> {
+        self.validate(raw, params)?;
+        raw.as_str().unwrap().as_bytes().chunks(CANONICAL_CHUNK_LEN).for_each(sink);
+        Ok(())
+    }
+    fn reduce_incremental(&self, acc: &Value, partial: &Value) -> Option<Value> { merge_counts(acc, partial) }
+}
+
+/// Config-declared template: an existing template with some params fixed.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TemplateDefinition {
+    pub id: String,
+    pub base: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// Delegates to `base` with the preset params laid over the vote's own `template_params`.
+pub struct ParameterizedTemplate {
+    id: String,
+    base: Arc<dyn VoteValueTemplate>,
+    preset: Value,
+}
+
+impl ParameterizedTemplate {
+    pub fn new(id: String, base: Arc<dyn VoteValueTemplate>, preset: Value) -> Self { Self { id, base, preset } }
+
+    /// Preset keys win over the vote's params; a non-object preset is ignored.
+    fn params(&self, params: &Value) -> Value {
+        let Value::Object(preset) = &self.preset else { return params.clone() };
+        let mut merged = params.as_object().cloned().unwrap_or_default();
+        merged.extend(preset.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Value::Object(merged)
+    }
+}
+
+impl VoteValueTemplate for ParameterizedTemplate {
+    fn id(&self) -> &str { &self.id }
+    fn version(&self) -> u32 { self.base.version() }
+    fn validate(&self, raw: &Value, params: &Value) -> Result<(), String> {
+        self.base.validate(raw, &self.params(params))
+    }
+    fn canonicalize(&self, raw: &Value, params: &Value) -> Result<Vec<u8>, String> {
+        self.base.canonicalize(raw, &self.params(params))
+    }
+    fn canonicalize_streaming(&self, raw: &Value, params: &Value, sink: &mut dyn FnMut(&[u8])) -> Result<(), String> {
+        self.base.canonicalize_streaming(raw, &self.params(params), sink)
+    }
+    fn reduce(&self, values: &[Value]) -> Value { self.base.reduce(values) }
+    fn reduce_incremental(&self, acc: &Value, partial: &Value) -> Option<Value> { self.base.reduce_incremental(acc, partial) }
 }
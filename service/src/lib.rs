@@ -5,6 +5,8 @@ pub mod core;
 pub mod model;
 pub mod store;
 pub mod service;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub use api::*;
 pub use cli::*;
 pub use config::*;
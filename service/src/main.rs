@@ -1,9 +1,9 @@
 use axum::Router;
 use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use tower_http::cors::{CorsLayer, Any};
 use tower_http::trace::TraceLayer;
-use decentralized_decision_vote::api::routes::create_router;
+use decentralized_decision_vote::api::{cors_layer, routes::create_router};
+use decentralized_decision_vote::core::golden::{verify_golden_vectors, GOLDEN_VECTORS};
 use decentralized_decision_vote::core::state::AppState;
 use decentralized_decision_vote::cli::{parse_args, execute_cli};
 
@@ -24,14 +24,30 @@ async fn main() {
     }
 
     let state = AppState::new().await;
+    if let Err(failures) = verify_golden_vectors(&state.registry, GOLDEN_VECTORS) {
+        for failure in &failures { tracing::error!("canonicalization self-test failed: {}", failure); }
+        std::process::exit(70);
+    }
     let app: Router = create_router(state.clone())
-        .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any))
+        .layer(cors_layer(&state.cors))
         .layer(TraceLayer::new_for_http());
 
+    #[cfg(feature = "grpc")]
+    if let Some(bind) = state.server.grpc_bind.clone() {
+        let listener = tokio::net::TcpListener::bind(&bind).await.unwrap();
+        tracing::info!("gRPC listening on {}", bind);
+        let grpc_state = state.clone();
+        tokio::spawn(async move {
+            let served = decentralized_decision_vote::api::grpc::serve_grpc(grpc_state, listener, shutdown_signal()).await;
+            if let Err(e) = served { tracing::error!("gRPC server failed: {}", e); }
+        });
+    }
+
     let addr: SocketAddr = "0.0.0.0:8080".parse().unwrap();
     tracing::info!("listening on {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     axum::serve(listener, app).with_graceful_shutdown(shutdown_signal()).await.unwrap();
+    state.shutdown().await;
 }
 
 async fn shutdown_signal() {
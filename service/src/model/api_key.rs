@@ -0,0 +1,75 @@
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+
+/// Read votes, commitments and results.
+pub const SCOPE_VOTES_READ: &str = "votes:read";
+/// Create votes, commit and reveal.
+pub const SCOPE_VOTES_WRITE: &str = "votes:write";
+/// Create, list and revoke API keys.
+pub const SCOPE_KEYS_ADMIN: &str = "keys:admin";
+/// Bulk-import reveals, e.g. when migrating historical votes.
+pub const SCOPE_VOTES_IMPORT: &str = "votes:import";
+
+/// A client credential; only the SHA-256 of the secret is kept. Timestamps are unix seconds.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiKey {
+    pub id: String,
+    pub hashed_secret: String,
+    pub scopes: Vec<String>,
+    pub created_at: i64,
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub revoked: bool,
+    /// Requests allowed per minute; unlimited when unset.
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+impl ApiKey {
+    pub fn hash_secret(secret: &str) -> String { hex::encode(Sha256::digest(secret.as_bytes())) }
+
+    pub fn has_scope(&self, scope: &str) -> bool { self.scopes.iter().any(|s| s == scope) }
+
+    pub fn is_expired(&self, now: i64) -> bool { self.expires_at.is_some_and(|t| t <= now) }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CreateApiKeyRequest {
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+/// A key as listed to admins, without its hash.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiKeyDto {
+    pub id: String,
+    pub scopes: Vec<String>,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+    pub revoked: bool,
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+impl From<&ApiKey> for ApiKeyDto {
+    fn from(k: &ApiKey) -> Self {
+        Self {
+            id: k.id.clone(),
+            scopes: k.scopes.clone(),
+            created_at: k.created_at,
+            expires_at: k.expires_at,
+            revoked: k.revoked,
+            rate_limit_per_minute: k.rate_limit_per_minute,
+        }
+    }
+}
+
+/// Returned once on creation; `api_key` is what clients send in `X-API-Key` and cannot be recovered later.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CreatedApiKeyDto {
+    pub api_key: String,
+    pub key: ApiKeyDto,
+}
@@ -11,8 +11,77 @@ pub struct VoteConfig {
     pub reveal_start_height: u64,
     pub reveal_end_height: u64,
     pub participants: Vec<String>,
+    /// Optional voter_id -> ed25519 public key (hex) bindings; bound voters must sign their commits.
+    #[serde(default)]
+    pub participant_keys: std::collections::HashMap<String, String>,
+    /// Fraction of eligible voters (0.0-1.0) that must reveal for the result to count; 0 disables the check.
+    #[serde(default)]
+    pub quorum_threshold: f64,
+    /// How committers who never reveal are treated once the reveal window has closed.
+    #[serde(default)]
+    pub non_revealer_policy: NonRevealerPolicy,
+    /// Number of reveals required before results are released; 0 releases them immediately.
+    #[serde(default)]
+    pub reveal_threshold: u64,
     pub value_template: String,
+    /// Version of `value_template` the vote is pinned to; unset at creation means the latest registered version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_version: Option<u32>,
     pub template_params: Value,
+    /// Most voters that may commit. Allow-listed votes are capped at the list size; open votes default to the
+    /// service's configured limit. Reveals need a commitment, so this bounds them too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_participants: Option<u64>,
+    /// Commitment preimage the vote hashes with, set by the service at creation. Votes stored before it
+    /// was recorded have none and keep the legacy preimage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commitment_scheme: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NonRevealerPolicy {
+    /// Count non-revealers as abstentions.
+    #[default]
+    Ignore,
+    /// Drop non-revealers from the eligible set before computing quorum.
+    Exclude,
+    /// Count them as abstentions and list them in `penalized` for downstream slashing.
+    Penalize,
+}
+
+impl VoteConfig {
+    /// Voters allowed to take part: `participants` plus any key-bound voters. Empty for open votes.
+    pub fn eligible_voters(&self) -> std::collections::BTreeSet<&str> {
+        self.participants.iter().map(String::as_str).chain(self.participant_keys.keys().map(String::as_str)).collect()
+    }
+
+    /// Whether both configs hash values the same way: same template, version and params.
+    pub fn same_template(&self, other: &VoteConfig) -> bool {
+        self.value_template == other.value_template && self.template_version == other.template_version && self.template_params == other.template_params
+    }
+
+    /// Whether both configs agree on everything fixed once a vote has commitments: the template, who may vote and
+    /// with which keys, the windows, quorum, reveal threshold and participant cap.
+    pub fn same_rules(&self, other: &VoteConfig) -> bool {
+        self.same_template(other)
+            && self.participants == other.participants
+            && self.participant_keys == other.participant_keys
+            && (self.commit_start_height, self.commit_end_height, self.reveal_start_height, self.reveal_end_height)
+                == (other.commit_start_height, other.commit_end_height, other.reveal_start_height, other.reveal_end_height)
+            && self.quorum_threshold == other.quorum_threshold
+            && self.reveal_threshold == other.reveal_threshold
+            && self.max_participants == other.max_participants
+    }
+
+    /// Phase of the vote at the given block height.
+    pub fn phase_at(&self, height: u64) -> &'static str {
+        if height < self.commit_start_height { "pending" }
+        else if height <= self.commit_end_height { "commit" }
+        else if height < self.reveal_start_height { "awaiting_reveal" }
+        else if height <= self.reveal_end_height { "reveal" }
+        else { "closed" }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -28,7 +97,38 @@ pub struct PaginationQuery { pub offset: Option<u64>, pub limit: Option<u64> }
 pub struct Page<T> { pub items: Vec<T>, pub total: u64 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct CreateVoteRequest { pub config: VoteConfig }
+pub struct CreateVoteRequest { pub config: VoteConfig, #[serde(default)] pub id_nonce: Option<String> }
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UpdateVoteConfigRequest { pub config: VoteConfig }
+
+/// Changes applied to a source vote's config when cloning it; unset fields are copied as-is.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct VoteConfigOverrides {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Blocks to move all four window heights forward by, e.g. one voting period for a recurring vote.
+    #[serde(default)]
+    pub shift_heights: Option<u64>,
+}
+
+impl VoteConfigOverrides {
+    /// The source config with the overrides applied; `Err` if a shifted height overflows.
+    pub fn apply(self, mut cfg: VoteConfig) -> Result<VoteConfig, String> {
+        if let Some(title) = self.title { cfg.title = title; }
+        if let Some(description) = self.description { cfg.description = Some(description); }
+        if let Some(shift) = self.shift_heights {
+            let shifted = |h: u64| h.checked_add(shift).ok_or_else(|| "shifted window height overflows".to_string());
+            cfg.commit_start_height = shifted(cfg.commit_start_height)?;
+            cfg.commit_end_height = shifted(cfg.commit_end_height)?;
+            cfg.reveal_start_height = shifted(cfg.reveal_start_height)?;
+            cfg.reveal_end_height = shifted(cfg.reveal_end_height)?;
+        }
+        Ok(cfg)
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct VoteSummaryDto {
@@ -49,7 +149,11 @@ pub struct VoteDetailDto {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct CommitRequest { pub voter: String, pub vote_value: Value, pub salt_hex: String }
+pub struct CommitRequest { pub voter: String, pub vote_value: Value, pub salt_hex: String, #[serde(default)] pub signature_hex: Option<String>, #[serde(default)] pub delegation: Option<Delegation> }
+
+/// Authorization from `delegator` for `delegate` to commit on their behalf, signed by the delegator's bound key.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Delegation { pub delegator: String, pub delegate: String, pub expires_at: i64, pub signature_hex: String }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CommitResponse { pub commitment_hex: String, pub ts: i64 }
@@ -60,10 +164,51 @@ pub struct RevealRequest { pub voter: String, pub vote_value: Value, pub salt_he
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct RevealResponse { pub accepted: bool, pub ts: i64 }
 
+/// Outcome of one line of a bulk reveal import; `line` is 1-based and `error` is set when it was not stored.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RevealImportLine {
+    pub line: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voter: Option<String>,
+    pub accepted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Last line of a bulk reveal import response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RevealImportSummary { pub imported: u64, pub failed: u64 }
+
+/// Outcome of checking a value and salt against a stored commitment.
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct ChainHeightDto { pub height: u64 }
+pub struct CommitmentCheckDto { pub valid: bool, pub commitment_hex: String }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct VoteResultsDto { pub vote_id: String, pub result: Value }
+pub struct ChainHeightDto { pub height: u64 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct VoteResultsDto {
+    pub vote_id: String,
+    pub result: Value,
+    /// Size of the eligible set; for open votes, the number of voters who committed.
+    pub total_eligible: u64,
+    pub total_revealed: u64,
+    /// Eligible voters who did not reveal.
+    pub total_abstained: u64,
+    pub participation_rate: f64,
+    pub quorum_met: bool,
+    /// Set when quorum was not reached; `result` should not be treated as final.
+    pub inconclusive: bool,
+    /// Voters who committed but did not reveal; only filled in after the reveal window.
+    pub non_revealers: Vec<String>,
+    /// Non-revealers flagged under `NonRevealerPolicy::Penalize`.
+    pub penalized: Vec<String>,
+}
 
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct StoreStatsDto {
+    pub total_votes: u64,
+    pub votes_by_status: std::collections::BTreeMap<String, u64>,
+    pub total_commitments: u64,
+    pub total_reveals: u64,
+}
@@ -1,13 +1,16 @@
 use async_trait::async_trait;
 use serde_json::Value;
+use std::collections::HashSet;
 use std::sync::Arc;
-use chrono::Utc;
+use shared_utils::clock::{Clock, SystemClock};
 use sha2::{Sha256, Digest};
 use hex::ToHex;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 
 use crate::model::vote::*;
 use crate::store::{VoteStore, StoreError};
-use crate::core::template::TemplateRegistry;
+use crate::core::tally::{ParallelTally, SequentialTally, TallyStrategy, DEFAULT_PARALLEL_TALLY_THRESHOLD};
+use crate::core::template::{TemplateRegistry, VoteValueTemplate, DEFAULT_TEMPLATE_VERSION};
 
 #[derive(thiserror::Error, Debug)]
 pub enum ServiceError { 
@@ -15,40 +18,287 @@ pub enum ServiceError {
     #[error("not found")] NotFound, 
     #[error("conflict")] Conflict, 
     #[error("forbidden")] Forbidden, 
+    #[error("insufficient reveals: {revealed} of {required}")] InsufficientReveals { revealed: u64, required: u64 },
     #[error("internal")] Internal 
 }
 
 impl From<StoreError> for ServiceError {
-    fn from(e: StoreError) -> Self { match e { StoreError::NotFound => ServiceError::NotFound, StoreError::Conflict => ServiceError::Conflict, _ => ServiceError::Internal } }
+    fn from(e: StoreError) -> Self {
+        match e {
+            StoreError::NotFound => ServiceError::NotFound,
+            StoreError::Conflict => ServiceError::Conflict,
+            StoreError::Full => ServiceError::BadRequest("participant limit reached".into()),
+            _ => ServiceError::Internal,
+        }
+    }
 }
 
 #[async_trait]
 pub trait VoteService: Send + Sync {
-    async fn create_vote(&self, cfg: VoteConfig) -> Result<String, ServiceError>;
+    async fn create_vote(&self, cfg: VoteConfig) -> Result<String, ServiceError> {
+        self.create_vote_with_nonce(cfg, None).await
+    }
+    /// With a nonce the ID is `derive_vote_id(cfg, nonce)`, so re-creating the same config returns the existing vote.
+    async fn create_vote_with_nonce(&self, cfg: VoteConfig, id_nonce: Option<String>) -> Result<String, ServiceError>;
     async fn list_votes(&self, offset: u64, limit: u64) -> Result<(Vec<VoteSummaryDto>, u64), ServiceError>;
     async fn get_vote(&self, id: &str) -> Result<VoteDetailDto, ServiceError>;
-    async fn commit(&self, id: &str, voter: &str, raw_value: Value, salt_hex: String) -> Result<CommitResponse, ServiceError>;
+    /// Edit a vote's config. Any field may change before the first commitment; after it the template, participants
+    /// and their keys, the windows, quorum, reveal threshold and participant cap are fixed and changing them is `Conflict`.
+    async fn update_vote_config(&self, id: &str, cfg: VoteConfig) -> Result<VoteDetailDto, ServiceError>;
+    /// Create a fresh vote from `source_id`'s config with `overrides` applied, validated like a normal create.
+    /// The clone keeps the source's pinned template version and starts with no commitments or reveals.
+    async fn clone_vote(&self, source_id: &str, overrides: VoteConfigOverrides) -> Result<String, ServiceError>;
+    async fn commit(&self, id: &str, voter: &str, raw_value: Value, salt_hex: String) -> Result<CommitResponse, ServiceError> {
+        self.commit_signed(id, voter, raw_value, salt_hex, None).await
+    }
+    /// Commit with an optional ed25519 signature over `commit_signing_message`; required for voters with a bound key.
+    async fn commit_signed(&self, id: &str, voter: &str, raw_value: Value, salt_hex: String, signature_hex: Option<String>) -> Result<CommitResponse, ServiceError>;
+    /// Commit on behalf of `delegation.delegator`; the delegate signs like a bound voter if it has a key.
+    async fn commit_delegated(&self, id: &str, delegation: Delegation, raw_value: Value, salt_hex: String, signature_hex: Option<String>) -> Result<CommitResponse, ServiceError>;
+    /// Open `voter`'s commitment; the hash is recomputed against vote `id`, so a commitment carried over from
+    /// another vote never opens here.
     async fn reveal(&self, id: &str, voter: &str, raw_value: Value, salt_hex: String) -> Result<RevealResponse, ServiceError>;
-    async fn results(&self, id: &str) -> Result<VoteResultsDto, ServiceError>;
+    /// Bulk import for migrations: every reveal is checked exactly like `reveal` and the accepted ones are stored
+    /// in one batch. Outcomes are per reveal, in order; only an unknown vote fails the whole call.
+    async fn import_reveals(&self, id: &str, reveals: Vec<RevealRequest>) -> Result<Vec<Result<RevealResponse, ServiceError>>, ServiceError>;
+    /// Check a value and salt against `voter`'s stored commitment without recording a reveal; `NotFound` if there is none.
+    async fn verify_commitment(&self, id: &str, voter: &str, raw_value: Value, salt_hex: &str) -> Result<CommitmentCheckDto, ServiceError>;
+    async fn results(&self, id: &str) -> Result<VoteResultsDto, ServiceError> {
+        self.results_at(id, None).await
+    }
+    /// Results as of `current_height`; the non-revealer policy only applies once the reveal window has closed.
+    async fn results_at(&self, id: &str, current_height: Option<u64>) -> Result<VoteResultsDto, ServiceError>;
+}
+
+/// Participant cap for open votes when neither the vote nor the config sets one.
+pub const DEFAULT_MAX_PARTICIPANTS: u64 = 10_000;
+
+/// Commitment domain for deployments that do not configure their own. This is this crate's counterpart to the
+/// workspace `CommitmentEngineBuilder::domain_separator`; the two hash different preimages and are set separately.
+pub const DEFAULT_DOMAIN_TAG: &str = "ddv";
+
+/// Commitment preimage `commit|<value>|<salt>`, kept for votes created before schemes were recorded.
+pub const LEGACY_COMMITMENT_SCHEME: u32 = 1;
+
+/// Commitment preimage `commit|<domain_tag>|<vote_id>|<value>|<salt>`, given to every new vote.
+pub const COMMITMENT_SCHEME: u32 = 2;
+
+/// Commitment to a canonical value: sha256 over `commit|<domain_tag>|<vote_id>|<value>|<salt>`, so a commitment
+/// only opens in the deployment and vote it was made for.
+pub fn commitment_hash(domain_tag: &str, vote_id: &str, canonical_value: &[u8], salt: &[u8]) -> String {
+    let mut hasher = CommitmentHasher::new(domain_tag, vote_id);
+    hasher.update(canonical_value);
+    hasher.finalize(salt)
+}
+
+/// `commitment_hash` fed the canonical value in chunks, as `canonicalize_streaming` produces them. The raw value is
+/// still parsed in full first; this only saves the copy into one canonical buffer.
+pub struct CommitmentHasher(Sha256);
+
+impl CommitmentHasher {
+    pub fn new(domain_tag: &str, vote_id: &str) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"commit|");
+        hasher.update(domain_tag.as_bytes());
+        hasher.update(b"|");
+        hasher.update(vote_id.as_bytes());
+        hasher.update(b"|");
+        Self(hasher)
+    }
+
+    /// Hasher for the legacy `commit|<value>|<salt>` preimage, which binds neither domain nor vote.
+    pub fn legacy() -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"commit|");
+        Self(hasher)
+    }
+
+    /// Hasher for the commitment scheme recorded on the vote's config.
+    pub fn for_vote(domain_tag: &str, vote_id: &str, cfg: &VoteConfig) -> Result<Self, String> {
+        match cfg.commitment_scheme.unwrap_or(LEGACY_COMMITMENT_SCHEME) {
+            LEGACY_COMMITMENT_SCHEME => Ok(Self::legacy()),
+            COMMITMENT_SCHEME => Ok(Self::new(domain_tag, vote_id)),
+            other => Err(format!("unknown commitment scheme {}", other)),
+        }
+    }
+
+    /// Append the next chunk of the canonical value.
+    pub fn update(&mut self, canonical_chunk: &[u8]) { self.0.update(canonical_chunk); }
+
+    pub fn finalize(mut self, salt: &[u8]) -> String {
+        self.0.update(b"|");
+        self.0.update(salt);
+        self.0.finalize().encode_hex()
+    }
+}
+
+/// Deterministic vote ID: sha256 over the nonce and the config's canonical JSON (object keys sorted).
+pub fn derive_vote_id(cfg: &VoteConfig, nonce: &str) -> String {
+    let canonical = serde_json::to_value(cfg).map(|v| v.to_string()).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(b"vote|");
+    hasher.update(nonce.as_bytes());
+    hasher.update(b"|");
+    hasher.update(canonical.as_bytes());
+    hasher.finalize().encode_hex()
+}
+
+/// Bytes a bound voter signs to authorize a commitment.
+pub fn commit_signing_message(vote_id: &str, voter: &str, commitment_hex: &str) -> Vec<u8> {
+    format!("commit|{}|{}|{}", vote_id, voter, commitment_hex).into_bytes()
+}
+
+/// Bytes a delegator signs to let `delegate` commit on their behalf until `expires_at` (unix seconds).
+pub fn delegation_signing_message(vote_id: &str, delegator: &str, delegate: &str, expires_at: i64) -> Vec<u8> {
+    format!("delegate|{}|{}|{}|{}", vote_id, delegator, delegate, expires_at).into_bytes()
+}
+
+/// Verify an ed25519 signature; malformed keys or signatures simply fail verification.
+fn verify_signature(pubkey_hex: &str, message: &[u8], signature_hex: &str) -> bool {
+    let Ok(key_bytes) = hex::decode(pubkey_hex) else { return false };
+    let Ok(key_bytes) = <[u8; 32]>::try_from(key_bytes.as_slice()) else { return false };
+    let Ok(key) = VerifyingKey::from_bytes(&key_bytes) else { return false };
+    let Ok(sig_bytes) = hex::decode(signature_hex) else { return false };
+    let Ok(signature) = Signature::from_slice(&sig_bytes) else { return false };
+    key.verify(message, &signature).is_ok()
 }
 
 pub struct VoteServiceImpl {
     store: Arc<dyn VoteStore>,
     registry: Arc<TemplateRegistry>,
+    clock: Arc<dyn Clock>,
+    max_participants: u64,
+    domain_tag: String,
+    large_tally: Arc<dyn TallyStrategy>,
+    large_tally_threshold: usize,
 }
 
 impl VoteServiceImpl {
-    pub fn new(store: Arc<dyn VoteStore>, registry: Arc<TemplateRegistry>) -> Self { Self { store, registry } }
+    pub fn new(store: Arc<dyn VoteStore>, registry: Arc<TemplateRegistry>) -> Self {
+        Self {
+            store, registry, clock: Arc::new(SystemClock), max_participants: DEFAULT_MAX_PARTICIPANTS, domain_tag: DEFAULT_DOMAIN_TAG.to_string(),
+            large_tally: Arc::new(ParallelTally::default()), large_tally_threshold: DEFAULT_PARALLEL_TALLY_THRESHOLD,
+        }
+    }
+
+    /// Replace the clock used for commit/reveal timestamps and delegation expiry.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self { self.clock = clock; self }
+
+    /// Participant cap given to open votes created without their own `max_participants`.
+    pub fn with_max_participants(mut self, max_participants: u64) -> Self { self.max_participants = max_participants; self }
+
+    /// Deployment-wide tag mixed into every commitment; commits and reveals must run under the same tag.
+    pub fn with_domain_tag(mut self, domain_tag: impl Into<String>) -> Self { self.domain_tag = domain_tag.into(); self }
+
+    /// Tally votes with at least `threshold` reveals using `strategy`; smaller ones are reduced sequentially.
+    pub fn with_large_tally(mut self, threshold: usize, strategy: Arc<dyn TallyStrategy>) -> Self {
+        self.large_tally_threshold = threshold;
+        self.large_tally = strategy;
+        self
+    }
+
+    /// Large votes go to the large-vote strategy on the blocking pool, so its rayon work never
+    /// holds up the async workers; smaller ones are reduced inline.
+    async fn tally(&self, tpl: Arc<dyn VoteValueTemplate>, values: Vec<Value>) -> Result<Value, ServiceError> {
+        if values.len() < self.large_tally_threshold { return Ok(SequentialTally.tally(tpl.as_ref(), &values)); }
+        let strategy = self.large_tally.clone();
+        tokio::task::spawn_blocking(move || strategy.tally(tpl.as_ref(), &values)).await.map_err(|_| ServiceError::Internal)
+    }
+
+    /// The exact template version the vote was created with.
+    fn pinned_template(&self, cfg: &VoteConfig) -> Result<Arc<dyn VoteValueTemplate>, ServiceError> {
+        let version = cfg.template_version.unwrap_or(DEFAULT_TEMPLATE_VERSION);
+        self.registry.get_version(&cfg.value_template, version).map_err(ServiceError::BadRequest)
+    }
+
+    /// Validate the value against the vote's template and compute its commitment hash.
+    fn commitment_hex(&self, vote: &VoteDetailDto, raw_value: &Value, salt_hex: &str) -> Result<String, ServiceError> {
+        let tpl = self.pinned_template(&vote.config)?;
+        tpl.validate(raw_value, &vote.config.template_params).map_err(ServiceError::BadRequest)?;
+        let mut hasher = CommitmentHasher::for_vote(&self.domain_tag, &vote.id, &vote.config).map_err(ServiceError::BadRequest)?;
+        tpl.canonicalize_streaming(raw_value, &vote.config.template_params, &mut |chunk| hasher.update(chunk))
+            .map_err(ServiceError::BadRequest)?;
+        let salt_bytes = hex::decode(salt_hex).map_err(|_| ServiceError::BadRequest("bad salt".into()))?;
+        Ok(hasher.finalize(&salt_bytes))
+    }
+
+    /// Sanity-check a new or edited config and pin its template to the requested version, or the latest one.
+    fn check_config(&self, cfg: &mut VoteConfig) -> Result<(), ServiceError> {
+        if cfg.commit_start_height > cfg.commit_end_height || cfg.reveal_start_height > cfg.reveal_end_height { return Err(ServiceError::BadRequest("invalid windows".into())); }
+        if !(0.0..=1.0).contains(&cfg.quorum_threshold) { return Err(ServiceError::BadRequest("quorum_threshold must be between 0 and 1".into())); }
+        let eligible = cfg.eligible_voters().len() as u64;
+        if eligible > 0 && cfg.reveal_threshold > eligible { return Err(ServiceError::BadRequest("reveal_threshold exceeds the number of eligible voters".into())); }
+        if cfg.max_participants == Some(0) { return Err(ServiceError::BadRequest("max_participants must be positive".into())); }
+        cfg.max_participants = Some(if eligible > 0 { eligible } else { cfg.max_participants.unwrap_or(self.max_participants) });
+        let tpl = match cfg.template_version {
+            Some(version) => self.registry.get_version(&cfg.value_template, version),
+            None => self.registry.get(&cfg.value_template),
+        }.map_err(ServiceError::BadRequest)?;
+        cfg.template_version = Some(tpl.version());
+        Ok(())
+    }
+
+    /// Check a reveal against the vote's template and `voter`'s stored commitment, stamping it with the current time.
+    async fn checked_reveal(&self, vote: &VoteDetailDto, voter: &str, raw_value: Value, salt_hex: String) -> Result<Reveal, ServiceError> {
+        // a value the template now rejects can never match, so report that rather than a mismatch
+        self.pinned_template(&vote.config)?.validate(&raw_value, &vote.config.template_params)
+            .map_err(|e| ServiceError::BadRequest(format!("value no longer valid for template: {}", e)))?;
+        // recompute and compare with stored commitment
+        let commitment_hex = self.commitment_hex(vote, &raw_value, &salt_hex)?;
+        if let Some(comm) = self.store.get_commitment(&vote.id, voter).await? {
+            if comm.commitment_hex != commitment_hex { return Err(ServiceError::BadRequest("commitment mismatch".into())); }
+        } else { return Err(ServiceError::BadRequest("no commitment".into())); }
+        Ok(Reveal { voter: voter.to_string(), vote_value: raw_value, salt_hex, ts: self.clock.now().timestamp() })
+    }
+
+    /// Check that `voter` may take part in the vote at all.
+    fn ensure_eligible(vote: &VoteDetailDto, voter: &str) -> Result<(), ServiceError> {
+        let restricted = !vote.config.participants.is_empty() || !vote.config.participant_keys.is_empty();
+        let listed = vote.config.participants.iter().any(|p| p == voter) || vote.config.participant_keys.contains_key(voter);
+        if restricted && !listed { return Err(ServiceError::Forbidden); }
+        Ok(())
+    }
+
+    /// Commitments an open vote may hold before the store refuses more; allow-listed votes are already
+    /// bounded by `ensure_eligible`. The store enforces it atomically with the write.
+    fn commitment_cap(vote: &VoteDetailDto) -> Option<u64> {
+        if vote.config.eligible_voters().is_empty() { vote.config.max_participants } else { None }
+    }
+
+    /// Require a valid commit signature from `signer` over `voter`'s commitment if `signer` has a bound key.
+    fn ensure_signed(vote: &VoteDetailDto, signer: &str, voter: &str, commitment_hex: &str, signature_hex: Option<&str>) -> Result<(), ServiceError> {
+        if let Some(pubkey_hex) = vote.config.participant_keys.get(signer) {
+            let message = commit_signing_message(&vote.id, voter, commitment_hex);
+            let signature_hex = signature_hex.ok_or(ServiceError::Forbidden)?;
+            if !verify_signature(pubkey_hex, &message, signature_hex) { return Err(ServiceError::Forbidden); }
+        }
+        Ok(())
+    }
+
+    /// A delegation must be signed by the delegator's bound key, unexpired, and not self-referential.
+    fn verify_delegation(&self, vote: &VoteDetailDto, delegation: &Delegation) -> Result<(), ServiceError> {
+        if delegation.delegator == delegation.delegate { return Err(ServiceError::BadRequest("self-delegation".into())); }
+        if delegation.expires_at <= self.clock.now().timestamp() { return Err(ServiceError::BadRequest("delegation expired".into())); }
+        let pubkey_hex = vote.config.participant_keys.get(&delegation.delegator).ok_or(ServiceError::Forbidden)?;
+        let message = delegation_signing_message(&vote.id, &delegation.delegator, &delegation.delegate, delegation.expires_at);
+        if !verify_signature(pubkey_hex, &message, &delegation.signature_hex) { return Err(ServiceError::Forbidden); }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl VoteService for VoteServiceImpl {
-    async fn create_vote(&self, cfg: VoteConfig) -> Result<String, ServiceError> {
-        // basic sanity
-        if cfg.commit_start_height > cfg.commit_end_height || cfg.reveal_start_height > cfg.reveal_end_height { return Err(ServiceError::BadRequest("invalid windows".into())); }
-        // template exists
-        let _ = self.registry.get(&cfg.value_template).map_err(ServiceError::BadRequest)?;
-        self.store.create_vote(cfg).await.map_err(Into::into)
+    async fn create_vote_with_nonce(&self, mut cfg: VoteConfig, id_nonce: Option<String>) -> Result<String, ServiceError> {
+        // the ID is derived from the config as submitted, so a retry still maps to the same vote after a newer version is registered
+        let id = id_nonce.map(|nonce| derive_vote_id(&cfg, &nonce));
+        self.check_config(&mut cfg)?;
+        cfg.commitment_scheme = Some(COMMITMENT_SCHEME);
+        let Some(id) = id else { return self.store.create_vote(cfg).await.map_err(Into::into) };
+        match self.store.create_vote_with_id(&id, cfg).await {
+            // same id means same config and nonce, so the existing vote is the one being asked for
+            Ok(()) | Err(StoreError::Conflict) => Ok(id),
+            Err(e) => Err(e.into()),
+        }
     }
 
     async fn list_votes(&self, offset: u64, limit: u64) -> Result<(Vec<VoteSummaryDto>, u64), ServiceError> {
@@ -59,52 +309,120 @@ impl VoteService for VoteServiceImpl {
         self.store.get_vote(id).await.map_err(Into::into)
     }
 
-    async fn commit(&self, id: &str, voter: &str, raw_value: Value, salt_hex: String) -> Result<CommitResponse, ServiceError> {
+    async fn update_vote_config(&self, id: &str, mut cfg: VoteConfig) -> Result<VoteDetailDto, ServiceError> {
+        let current = self.store.get_vote(id).await?;
+        // an edit that leaves the version out keeps the pinned one rather than moving to the latest
+        if cfg.template_version.is_none() && cfg.value_template == current.config.value_template {
+            cfg.template_version = current.config.template_version;
+        }
+        // existing commitments were hashed under the recorded scheme, so edits never change it
+        cfg.commitment_scheme = current.config.commitment_scheme;
+        self.check_config(&mut cfg)?;
+        // the store re-checks for commitments atomically with the write
+        self.store.update_vote_config(id, cfg).await?;
+        self.store.get_vote(id).await.map_err(Into::into)
+    }
+
+    async fn clone_vote(&self, source_id: &str, overrides: VoteConfigOverrides) -> Result<String, ServiceError> {
+        let source = self.store.get_vote(source_id).await?;
+        let cfg = overrides.apply(source.config).map_err(ServiceError::BadRequest)?;
+        self.create_vote(cfg).await
+    }
+
+    async fn commit_signed(&self, id: &str, voter: &str, raw_value: Value, salt_hex: String, signature_hex: Option<String>) -> Result<CommitResponse, ServiceError> {
         let vote = self.store.get_vote(id).await?;
-        if !vote.config.participants.is_empty() && !vote.config.participants.iter().any(|p| p == voter) { return Err(ServiceError::Forbidden); }
-        let tpl = self.registry.get(&vote.config.value_template).map_err(ServiceError::BadRequest)?;
-        tpl.validate(&raw_value, &vote.config.template_params).map_err(ServiceError::BadRequest)?;
-        let canon = tpl.canonicalize(&raw_value, &vote.config.template_params).map_err(ServiceError::BadRequest)?;
-        let salt_bytes = hex::decode(&salt_hex).map_err(|_| ServiceError::BadRequest("bad salt".into()))?;
-        let mut hasher = Sha256::new();
-        hasher.update(b"commit|");
-        hasher.update(&canon);
-        hasher.update(b"|");
-        hasher.update(&salt_bytes);
-        let commitment_hex: String = hasher.finalize().encode_hex();
-        let ts = Utc::now().timestamp();
-        self.store.put_commitment(id, Commitment { voter: voter.to_string(), commitment_hex: commitment_hex.clone(), ts }).await?;
+        Self::ensure_eligible(&vote, voter)?;
+        let commitment_hex = self.commitment_hex(&vote, &raw_value, &salt_hex)?;
+        Self::ensure_signed(&vote, voter, voter, &commitment_hex, signature_hex.as_deref())?;
+        let ts = self.clock.now().timestamp();
+        self.store.put_commitment(id, Commitment { voter: voter.to_string(), commitment_hex: commitment_hex.clone(), ts }, Self::commitment_cap(&vote)).await?;
+        Ok(CommitResponse { commitment_hex, ts })
+    }
+
+    async fn commit_delegated(&self, id: &str, delegation: Delegation, raw_value: Value, salt_hex: String, signature_hex: Option<String>) -> Result<CommitResponse, ServiceError> {
+        let vote = self.store.get_vote(id).await?;
+        self.verify_delegation(&vote, &delegation)?;
+        Self::ensure_eligible(&vote, &delegation.delegator)?;
+        let commitment_hex = self.commitment_hex(&vote, &raw_value, &salt_hex)?;
+        // a delegate with its own bound key must still sign, over the delegator's commitment
+        Self::ensure_signed(&vote, &delegation.delegate, &delegation.delegator, &commitment_hex, signature_hex.as_deref())?;
+        let ts = self.clock.now().timestamp();
+        let voter = delegation.delegator.clone();
+        self.store.put_commitment(id, Commitment { voter, commitment_hex: commitment_hex.clone(), ts }, Self::commitment_cap(&vote)).await?;
+        self.store.put_delegation(id, delegation).await?;
         Ok(CommitResponse { commitment_hex, ts })
     }
 
     async fn reveal(&self, id: &str, voter: &str, raw_value: Value, salt_hex: String) -> Result<RevealResponse, ServiceError> {
         let vote = self.store.get_vote(id).await?;
-        let tpl = self.registry.get(&vote.config.value_template).map_err(ServiceError::BadRequest)?;
-        tpl.validate(&raw_value, &vote.config.template_params).map_err(ServiceError::BadRequest)?;
-        let canon = tpl.canonicalize(&raw_value, &vote.config.template_params).map_err(ServiceError::BadRequest)?;
-        let salt_bytes = hex::decode(&salt_hex).map_err(|_| ServiceError::BadRequest("bad salt".into()))?;
-        // recompute and compare with stored commitment
-        let mut hasher = Sha256::new();
-        hasher.update(b"commit|");
-        hasher.update(&canon);
-        hasher.update(b"|");
-        hasher.update(&salt_bytes);
-        let commitment_hex: String = hasher.finalize().encode_hex();
-        if let Some(comm) = self.store.get_commitment(id, voter).await? {
-            if comm.commitment_hex != commitment_hex { return Err(ServiceError::BadRequest("commitment mismatch".into())); }
-        } else { return Err(ServiceError::BadRequest("no commitment".into())); }
-        let ts = Utc::now().timestamp();
-        self.store.put_reveal(id, Reveal { voter: voter.to_string(), vote_value: raw_value, salt_hex, ts }).await?;
+        let reveal = self.checked_reveal(&vote, voter, raw_value, salt_hex).await?;
+        let ts = reveal.ts;
+        self.store.put_reveal(id, reveal).await?;
         Ok(RevealResponse { accepted: true, ts })
     }
 
-    async fn results(&self, id: &str) -> Result<VoteResultsDto, ServiceError> {
+    async fn import_reveals(&self, id: &str, reveals: Vec<RevealRequest>) -> Result<Vec<Result<RevealResponse, ServiceError>>, ServiceError> {
+        let vote = self.store.get_vote(id).await?;
+        let mut outcomes = Vec::with_capacity(reveals.len());
+        let mut accepted = Vec::new();
+        for req in reveals {
+            match self.checked_reveal(&vote, &req.voter, req.vote_value, req.salt_hex).await {
+                Ok(reveal) => {
+                    outcomes.push(Ok(RevealResponse { accepted: true, ts: reveal.ts }));
+                    accepted.push(reveal);
+                }
+                Err(e) => outcomes.push(Err(e)),
+            }
+        }
+        let mut stored = self.store.put_reveals(id, accepted).await?.into_iter();
+        for outcome in outcomes.iter_mut().filter(|o| o.is_ok()) {
+            if let Some(Err(e)) = stored.next() { *outcome = Err(e.into()); }
+        }
+        Ok(outcomes)
+    }
+
+    async fn verify_commitment(&self, id: &str, voter: &str, raw_value: Value, salt_hex: &str) -> Result<CommitmentCheckDto, ServiceError> {
+        let vote = self.store.get_vote(id).await?;
+        let stored = self.store.get_commitment(id, voter).await?.ok_or(ServiceError::NotFound)?;
+        let commitment_hex = self.commitment_hex(&vote, &raw_value, salt_hex)?;
+        Ok(CommitmentCheckDto { valid: stored.commitment_hex == commitment_hex, commitment_hex })
+    }
+
+    async fn results_at(&self, id: &str, current_height: Option<u64>) -> Result<VoteResultsDto, ServiceError> {
         let vote = self.store.get_vote(id).await?;
         let reveals = self.store.list_reveals(id).await?;
+        // stored reveals have already been checked against their commitments, so every one counts
+        let revealed_count = reveals.len() as u64;
+        if revealed_count < vote.config.reveal_threshold { return Err(ServiceError::InsufficientReveals { revealed: revealed_count, required: vote.config.reveal_threshold }); }
+        let reveal_closed = current_height.is_some_and(|h| h > vote.config.reveal_end_height);
+        let mut non_revealers: Vec<String> = Vec::new();
+        if reveal_closed {
+            let revealed: HashSet<&str> = reveals.iter().map(|r| r.voter.as_str()).collect();
+            non_revealers = self.store.list_commitments(id).await?.into_iter().map(|c| c.voter).filter(|v| !revealed.contains(v.as_str())).collect();
+            non_revealers.sort();
+        }
+        let eligible = vote.config.eligible_voters();
+        let mut total_eligible = if eligible.is_empty() { vote.num_commitments } else { eligible.len() as u64 };
+        if vote.config.non_revealer_policy == NonRevealerPolicy::Exclude { total_eligible = total_eligible.saturating_sub(non_revealers.len() as u64); }
+        let penalized = if vote.config.non_revealer_policy == NonRevealerPolicy::Penalize { non_revealers.clone() } else { Vec::new() };
+        let total_revealed = revealed_count;
+        let participation_rate = if total_eligible == 0 { 0.0 } else { total_revealed as f64 / total_eligible as f64 };
+        let quorum_met = participation_rate >= vote.config.quorum_threshold;
         let values: Vec<Value> = reveals.into_iter().map(|r| r.vote_value).collect();
-        let tpl = self.registry.get(&vote.config.value_template).map_err(ServiceError::BadRequest)?;
-        let aggregated = tpl.reduce(&values);
-        Ok(VoteResultsDto { vote_id: id.to_string(), result: aggregated })
+        let tpl = self.pinned_template(&vote.config)?;
+        let aggregated = self.tally(tpl, values).await?;
+        Ok(VoteResultsDto {
+            vote_id: id.to_string(),
+            result: aggregated,
+            total_eligible,
+            total_revealed,
+            total_abstained: total_eligible.saturating_sub(total_revealed),
+            participation_rate,
+            quorum_met,
+            inconclusive: !quorum_met,
+            non_revealers,
+            penalized,
+        })
     }
 }
 
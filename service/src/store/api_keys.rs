@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use async_trait::async_trait;
+use crate::model::api_key::ApiKey;
+use super::StoreError;
+
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// `Conflict` if a key with the same ID exists.
+    async fn create_key(&self, key: ApiKey) -> Result<(), StoreError>;
+    async fn get_key(&self, id: &str) -> Result<Option<ApiKey>, StoreError>;
+    async fn list_keys(&self) -> Result<Vec<ApiKey>, StoreError>;
+    /// Mark the key revoked; revoking twice is fine, an unknown ID is `NotFound`.
+    async fn revoke_key(&self, id: &str) -> Result<(), StoreError>;
+}
+
+#[derive(Default, Clone)]
+pub struct MemoryApiKeyStore {
+    keys: Arc<RwLock<HashMap<String, ApiKey>>>,
+}
+
+#[async_trait]
+impl ApiKeyStore for MemoryApiKeyStore {
+    async fn create_key(&self, key: ApiKey) -> Result<(), StoreError> {
+        let mut keys = self.keys.write().await;
+        if keys.contains_key(&key.id) { return Err(StoreError::Conflict); }
+        keys.insert(key.id.clone(), key);
+        Ok(())
+    }
+
+    async fn get_key(&self, id: &str) -> Result<Option<ApiKey>, StoreError> {
+        Ok(self.keys.read().await.get(id).cloned())
+    }
+
+    async fn list_keys(&self) -> Result<Vec<ApiKey>, StoreError> {
+        let mut keys: Vec<ApiKey> = self.keys.read().await.values().cloned().collect();
+        keys.sort_by_key(|k| (k.created_at, k.id.clone()));
+        Ok(keys)
+    }
+
+    async fn revoke_key(&self, id: &str) -> Result<(), StoreError> {
+        let mut keys = self.keys.write().await;
+        let key = keys.get_mut(id).ok_or(StoreError::NotFound)?;
+        key.revoked = true;
+        Ok(())
+    }
+}
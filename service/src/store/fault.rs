@@ -0,0 +1,208 @@
+//! A `VoteStore` decorator that fails or stalls on demand, for exercising error paths in tests.
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use crate::model::vote::*;
+use super::{VoteStore, StoreError};
+
+/// The `VoteStore` methods faults can target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StoreMethod {
+    CreateVote,
+    CreateVoteWithId,
+    GetVote,
+    UpdateVoteConfig,
+    ListVotes,
+    PutCommitment,
+    GetCommitment,
+    ListCommitments,
+    PutReveal,
+    PutReveals,
+    ListReveals,
+    PutDelegation,
+    ListDelegations,
+    GetStats,
+    Flush,
+}
+
+/// Wraps a store and injects the configured faults before delegating. Faults are deterministic:
+/// call counts are per method, and rate-based errors draw from a seeded generator.
+///
+/// ```ignore
+/// let store = FaultInjectingVoteStore::new(MemoryVoteStore::new())
+///     .fail_on_call(StoreMethod::GetVote, 2, StoreError::Io)
+///     .error_rate_for(StoreMethod::PutCommitment, 0.25, StoreError::Conflict)
+///     .latency(Duration::from_millis(5));
+/// ```
+pub struct FaultInjectingVoteStore<S> {
+    inner: S,
+    on_call: HashMap<(StoreMethod, u64), StoreError>,
+    rates: Vec<(Option<StoreMethod>, f64, StoreError)>,
+    latencies: Vec<(Option<StoreMethod>, Duration)>,
+    state: Mutex<FaultState>,
+}
+
+struct FaultState {
+    calls: HashMap<StoreMethod, u64>,
+    rng: u64,
+}
+
+impl<S: VoteStore> FaultInjectingVoteStore<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            on_call: HashMap::new(),
+            rates: Vec::new(),
+            latencies: Vec::new(),
+            state: Mutex::new(FaultState { calls: HashMap::new(), rng: 0 }),
+        }
+    }
+
+    /// Fail the `n`th call (1-based) to `method` with `error`; other calls go through.
+    pub fn fail_on_call(mut self, method: StoreMethod, n: u64, error: StoreError) -> Self {
+        self.on_call.insert((method, n), error);
+        self
+    }
+
+    /// Fail each call to any method with probability `rate`.
+    pub fn error_rate(mut self, rate: f64, error: StoreError) -> Self {
+        self.rates.push((None, rate, error));
+        self
+    }
+
+    /// Fail each call to `method` with probability `rate`.
+    pub fn error_rate_for(mut self, method: StoreMethod, rate: f64, error: StoreError) -> Self {
+        self.rates.push((Some(method), rate, error));
+        self
+    }
+
+    /// Delay every call by `delay`, before any injected error is returned.
+    pub fn latency(mut self, delay: Duration) -> Self {
+        self.latencies.push((None, delay));
+        self
+    }
+
+    /// Delay calls to `method` by `delay`, on top of any store-wide latency.
+    pub fn latency_for(mut self, method: StoreMethod, delay: Duration) -> Self {
+        self.latencies.push((Some(method), delay));
+        self
+    }
+
+    /// Seed for rate-based errors; the same seed and call sequence fail the same calls.
+    pub fn seed(self, seed: u64) -> Self {
+        self.state.lock().unwrap().rng = seed;
+        self
+    }
+
+    /// Calls made to `method` so far, including failed ones.
+    pub fn calls(&self, method: StoreMethod) -> u64 {
+        self.state.lock().unwrap().calls.get(&method).copied().unwrap_or(0)
+    }
+
+    pub fn inner(&self) -> &S { &self.inner }
+
+    async fn inject(&self, method: StoreMethod) -> Result<(), StoreError> {
+        let targets = |m: &Option<StoreMethod>| m.is_none_or(|m| m == method);
+        let error = {
+            let mut state = self.state.lock().unwrap();
+            let call = state.calls.entry(method).or_insert(0);
+            *call += 1;
+            let call = *call;
+            self.on_call.get(&(method, call)).copied().or_else(|| {
+                self.rates.iter().filter(|(m, _, _)| targets(m)).find_map(|(_, rate, error)| (next_unit(&mut state.rng) < *rate).then_some(*error))
+            })
+        };
+        let delay: Duration = self.latencies.iter().filter(|(m, _)| targets(m)).map(|(_, d)| *d).sum();
+        if !delay.is_zero() { tokio::time::sleep(delay).await; }
+        error.map_or(Ok(()), Err)
+    }
+}
+
+/// splitmix64, scaled to [0, 1).
+fn next_unit(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[async_trait]
+impl<S: VoteStore> VoteStore for FaultInjectingVoteStore<S> {
+    async fn create_vote(&self, cfg: VoteConfig) -> Result<String, StoreError> {
+        self.inject(StoreMethod::CreateVote).await?;
+        self.inner.create_vote(cfg).await
+    }
+
+    async fn create_vote_with_id(&self, id: &str, cfg: VoteConfig) -> Result<(), StoreError> {
+        self.inject(StoreMethod::CreateVoteWithId).await?;
+        self.inner.create_vote_with_id(id, cfg).await
+    }
+
+    async fn get_vote(&self, id: &str) -> Result<VoteDetailDto, StoreError> {
+        self.inject(StoreMethod::GetVote).await?;
+        self.inner.get_vote(id).await
+    }
+
+    async fn update_vote_config(&self, id: &str, cfg: VoteConfig) -> Result<(), StoreError> {
+        self.inject(StoreMethod::UpdateVoteConfig).await?;
+        self.inner.update_vote_config(id, cfg).await
+    }
+
+    async fn list_votes(&self, offset: u64, limit: u64) -> Result<(Vec<VoteSummaryDto>, u64), StoreError> {
+        self.inject(StoreMethod::ListVotes).await?;
+        self.inner.list_votes(offset, limit).await
+    }
+
+    async fn put_commitment(&self, vote_id: &str, commitment: Commitment, max_commitments: Option<u64>) -> Result<(), StoreError> {
+        self.inject(StoreMethod::PutCommitment).await?;
+        self.inner.put_commitment(vote_id, commitment, max_commitments).await
+    }
+
+    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, StoreError> {
+        self.inject(StoreMethod::GetCommitment).await?;
+        self.inner.get_commitment(vote_id, voter).await
+    }
+
+    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, StoreError> {
+        self.inject(StoreMethod::ListCommitments).await?;
+        self.inner.list_commitments(vote_id).await
+    }
+
+    async fn put_reveal(&self, vote_id: &str, reveal: Reveal) -> Result<(), StoreError> {
+        self.inject(StoreMethod::PutReveal).await?;
+        self.inner.put_reveal(vote_id, reveal).await
+    }
+
+    async fn put_reveals(&self, vote_id: &str, reveals: Vec<Reveal>) -> Result<Vec<Result<(), StoreError>>, StoreError> {
+        self.inject(StoreMethod::PutReveals).await?;
+        self.inner.put_reveals(vote_id, reveals).await
+    }
+
+    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, StoreError> {
+        self.inject(StoreMethod::ListReveals).await?;
+        self.inner.list_reveals(vote_id).await
+    }
+
+    async fn put_delegation(&self, vote_id: &str, delegation: Delegation) -> Result<(), StoreError> {
+        self.inject(StoreMethod::PutDelegation).await?;
+        self.inner.put_delegation(vote_id, delegation).await
+    }
+
+    async fn list_delegations(&self, vote_id: &str) -> Result<Vec<Delegation>, StoreError> {
+        self.inject(StoreMethod::ListDelegations).await?;
+        self.inner.list_delegations(vote_id).await
+    }
+
+    async fn get_stats(&self, current_height: u64) -> Result<StoreStatsDto, StoreError> {
+        self.inject(StoreMethod::GetStats).await?;
+        self.inner.get_stats(current_height).await
+    }
+
+    async fn flush(&self) -> Result<(), StoreError> {
+        self.inject(StoreMethod::Flush).await?;
+        self.inner.flush().await
+    }
+}
@@ -1,15 +1,19 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 use chrono::Utc;
+use serde::{Serialize, Deserialize};
 use crate::model::vote::*;
 use super::{VoteStore, StoreError};
 use async_trait::async_trait;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MemoryVoteStore {
-    inner: Arc<RwLock<MemoryDb>>,    
+    inner: Arc<RwLock<MemoryDb>>,
+    snapshot_path: Option<PathBuf>,
 }
 
 #[derive(Default)]
@@ -17,6 +21,39 @@ struct MemoryDb {
     votes: HashMap<String, (VoteConfig, i64)>,
     commitments: HashMap<(String, String), Commitment>,
     reveals: HashMap<(String, String), Reveal>,
+    delegations: HashMap<(String, String), Delegation>,
+}
+
+/// On-disk form of `MemoryDb`; tuple keys are flattened since JSON keys must be strings.
+#[derive(Serialize, Deserialize, Default)]
+struct Snapshot {
+    votes: Vec<(String, VoteConfig, i64)>,
+    commitments: Vec<(String, Commitment)>,
+    reveals: Vec<(String, Reveal)>,
+    #[serde(default)]
+    delegations: Vec<(String, Delegation)>,
+}
+
+impl From<&MemoryDb> for Snapshot {
+    fn from(db: &MemoryDb) -> Self {
+        Self {
+            votes: db.votes.iter().map(|(id, (cfg, ts))| (id.clone(), cfg.clone(), *ts)).collect(),
+            commitments: db.commitments.iter().map(|((vid, _), c)| (vid.clone(), c.clone())).collect(),
+            reveals: db.reveals.iter().map(|((vid, _), r)| (vid.clone(), r.clone())).collect(),
+            delegations: db.delegations.iter().map(|((vid, _), d)| (vid.clone(), d.clone())).collect(),
+        }
+    }
+}
+
+impl From<Snapshot> for MemoryDb {
+    fn from(s: Snapshot) -> Self {
+        Self {
+            votes: s.votes.into_iter().map(|(id, cfg, ts)| (id, (cfg, ts))).collect(),
+            commitments: s.commitments.into_iter().map(|(vid, c)| ((vid, c.voter.clone()), c)).collect(),
+            reveals: s.reveals.into_iter().map(|(vid, r)| ((vid, r.voter.clone()), r)).collect(),
+            delegations: s.delegations.into_iter().map(|(vid, d)| ((vid, d.delegator.clone()), d)).collect(),
+        }
+    }
 }
 
 #[async_trait]
@@ -29,6 +66,13 @@ impl VoteStore for MemoryVoteStore {
         Ok(id)
     }
 
+    async fn create_vote_with_id(&self, id: &str, cfg: VoteConfig) -> Result<(), StoreError> {
+        let mut g = self.inner.write().await;
+        if g.votes.contains_key(id) { return Err(StoreError::Conflict); }
+        g.votes.insert(id.to_string(), (cfg, Utc::now().timestamp()));
+        Ok(())
+    }
+
     async fn get_vote(&self, id: &str) -> Result<VoteDetailDto, StoreError> {
         let g = self.inner.read().await;
         let (cfg, created_ts) = g.votes.get(id).ok_or(StoreError::NotFound)?.clone();
@@ -37,6 +81,15 @@ impl VoteStore for MemoryVoteStore {
         Ok(VoteDetailDto { id: id.to_string(), config: cfg, created_ts, num_commitments, num_reveals })
     }
 
+    async fn update_vote_config(&self, id: &str, cfg: VoteConfig) -> Result<(), StoreError> {
+        let mut g = self.inner.write().await;
+        let committed = g.commitments.keys().any(|(vid, _)| vid == id);
+        let (current, _) = g.votes.get_mut(id).ok_or(StoreError::NotFound)?;
+        if committed && !current.same_rules(&cfg) { return Err(StoreError::Conflict); }
+        *current = cfg;
+        Ok(())
+    }
+
     async fn list_votes(&self, offset: u64, limit: u64) -> Result<(Vec<VoteSummaryDto>, u64), StoreError> {
         let g = self.inner.read().await;
         let mut items: Vec<(String, (VoteConfig, i64))> = g.votes.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
@@ -50,10 +103,14 @@ impl VoteStore for MemoryVoteStore {
         Ok((summaries, total))
     }
 
-    async fn put_commitment(&self, vote_id: &str, commitment: Commitment) -> Result<(), StoreError> {
+    async fn put_commitment(&self, vote_id: &str, commitment: Commitment, max_commitments: Option<u64>) -> Result<(), StoreError> {
         let mut g = self.inner.write().await;
         let key = (vote_id.to_string(), commitment.voter.clone());
         if g.commitments.contains_key(&key) { return Err(StoreError::Conflict); }
+        if let Some(max) = max_commitments {
+            let count = g.commitments.keys().filter(|(vid, _)| vid == vote_id).count() as u64;
+            if count >= max { return Err(StoreError::Full); }
+        }
         g.commitments.insert(key, commitment);
         Ok(())
     }
@@ -63,6 +120,11 @@ impl VoteStore for MemoryVoteStore {
         Ok(g.commitments.get(&(vote_id.to_string(), voter.to_string())).cloned())
     }
 
+    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, StoreError> {
+        let g = self.inner.read().await;
+        Ok(g.commitments.iter().filter(|((vid, _), _)| vid == vote_id).map(|(_, c)| c.clone()).collect())
+    }
+
     async fn put_reveal(&self, vote_id: &str, reveal: Reveal) -> Result<(), StoreError> {
         let mut g = self.inner.write().await;
         let key = (vote_id.to_string(), reveal.voter.clone());
@@ -71,14 +133,107 @@ impl VoteStore for MemoryVoteStore {
         Ok(())
     }
 
+    async fn put_reveals(&self, vote_id: &str, reveals: Vec<Reveal>) -> Result<Vec<Result<(), StoreError>>, StoreError> {
+        let mut g = self.inner.write().await;
+        Ok(reveals.into_iter().map(|reveal| {
+            let key = (vote_id.to_string(), reveal.voter.clone());
+            if g.reveals.contains_key(&key) { return Err(StoreError::Conflict); }
+            g.reveals.insert(key, reveal);
+            Ok(())
+        }).collect())
+    }
+
     async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, StoreError> {
         let g = self.inner.read().await;
         Ok(g.reveals.iter().filter(|((vid, _), _)| vid == vote_id).map(|(_, v)| v.clone()).collect())
     }
+
+    async fn put_delegation(&self, vote_id: &str, delegation: Delegation) -> Result<(), StoreError> {
+        let mut g = self.inner.write().await;
+        let key = (vote_id.to_string(), delegation.delegator.clone());
+        g.delegations.insert(key, delegation);
+        Ok(())
+    }
+
+    async fn list_delegations(&self, vote_id: &str) -> Result<Vec<Delegation>, StoreError> {
+        let g = self.inner.read().await;
+        Ok(g.delegations.iter().filter(|((vid, _), _)| vid == vote_id).map(|(_, d)| d.clone()).collect())
+    }
+
+    async fn get_stats(&self, current_height: u64) -> Result<StoreStatsDto, StoreError> {
+        let g = self.inner.read().await;
+        let mut stats = StoreStatsDto { total_votes: g.votes.len() as u64, total_commitments: g.commitments.len() as u64, total_reveals: g.reveals.len() as u64, ..Default::default() };
+        for (cfg, _) in g.votes.values() {
+            *stats.votes_by_status.entry(cfg.phase_at(current_height).to_string()).or_insert(0) += 1;
+        }
+        Ok(stats)
+    }
+
+    async fn flush(&self) -> Result<(), StoreError> {
+        self.snapshot().await
+    }
 }
 
 impl MemoryVoteStore {
     pub fn new() -> Self { Self::default() }
-}
 
+    /// Store backed by a JSON snapshot at `path`, reloading it if the file already exists.
+    ///
+    /// The file is read and parsed on the blocking pool.
+    pub async fn with_snapshot<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        let path = path.as_ref().to_path_buf();
+        let load = path.clone();
+        let db = tokio::task::spawn_blocking(move || Self::load_snapshot(&load)).await.map_err(|_| StoreError::Internal)??;
+        Ok(Self { inner: Arc::new(RwLock::new(db)), snapshot_path: Some(path) })
+    }
+
+    fn load_snapshot(path: &Path) -> Result<MemoryDb, StoreError> {
+        if !path.exists() {
+            return Ok(MemoryDb::default());
+        }
+        let content = std::fs::read(path).map_err(|e| {
+            tracing::error!("failed to read snapshot {}: {}", path.display(), e);
+            StoreError::Io
+        })?;
+        let snapshot: Snapshot = serde_json::from_slice(&content).map_err(|e| {
+            tracing::error!("failed to parse snapshot {}: {}", path.display(), e);
+            StoreError::Internal
+        })?;
+        tracing::info!("loaded {} votes from snapshot {}", snapshot.votes.len(), path.display());
+        Ok(MemoryDb::from(snapshot))
+    }
+
+    /// Write the current state to the snapshot path, if one is configured.
+    ///
+    /// The state is copied under the read lock, then serialized and written on the
+    /// blocking pool. The file is written next to the target and renamed into place
+    /// so a crash mid-write never leaves a truncated snapshot behind.
+    pub async fn snapshot(&self) -> Result<(), StoreError> {
+        let Some(path) = self.snapshot_path.clone() else { return Ok(()) };
+        let snapshot = Snapshot::from(&*self.inner.read().await);
+        tokio::task::spawn_blocking(move || {
+            let content = serde_json::to_vec(&snapshot).map_err(|_| StoreError::Internal)?;
+            let tmp = path.with_extension("tmp");
+            let write = std::fs::write(&tmp, content).and_then(|_| std::fs::rename(&tmp, &path));
+            write.map_err(|e| {
+                tracing::error!("failed to write snapshot {}: {}", path.display(), e);
+                StoreError::Io
+            })
+        })
+        .await
+        .map_err(|_| StoreError::Internal)?
+    }
 
+    /// Spawn a task that snapshots the store every `interval`.
+    pub fn spawn_snapshot_task(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if let Err(e) = store.snapshot().await { tracing::warn!("periodic snapshot failed: {}", e); }
+            }
+        })
+    }
+}
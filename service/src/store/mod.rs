@@ -1,18 +1,43 @@
+pub mod api_keys;
 pub mod memory;
+#[cfg(feature = "test-util")]
+pub mod fault;
 use async_trait::async_trait;
 use crate::model::vote::*;
 
-#[derive(thiserror::Error, Debug)]
-pub enum StoreError { #[error("not found")] NotFound, #[error("conflict")] Conflict, #[error("io")] Io, #[error("internal")] Internal }
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreError { #[error("not found")] NotFound, #[error("conflict")] Conflict, #[error("full")] Full, #[error("io")] Io, #[error("internal")] Internal }
 
 #[async_trait]
 pub trait VoteStore: Send + Sync {
     async fn create_vote(&self, cfg: VoteConfig) -> Result<String, StoreError>;
+    /// Create a vote under a caller-chosen ID; `Conflict` if the ID is taken.
+    async fn create_vote_with_id(&self, id: &str, cfg: VoteConfig) -> Result<(), StoreError>;
     async fn get_vote(&self, id: &str) -> Result<VoteDetailDto, StoreError>;
+    /// Replace the vote's config, keeping its creation time. Once a commitment exists, `Conflict` if the
+    /// template, its version or its params would change, since stored commitments were hashed under them, or
+    /// if any other rule in `VoteConfig::same_rules` would, since voters committed under those.
+    async fn update_vote_config(&self, id: &str, cfg: VoteConfig) -> Result<(), StoreError>;
     async fn list_votes(&self, offset: u64, limit: u64) -> Result<(Vec<VoteSummaryDto>, u64), StoreError>;
-    async fn put_commitment(&self, vote_id: &str, commitment: Commitment) -> Result<(), StoreError>;
+    /// Store a voter's commitment; `Conflict` if the voter already committed, `Full` if the vote already holds
+    /// `max_commitments`. Both are checked atomically with the write, so concurrent commits cannot overshoot.
+    async fn put_commitment(&self, vote_id: &str, commitment: Commitment, max_commitments: Option<u64>) -> Result<(), StoreError>;
     async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, StoreError>;
+    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, StoreError>;
     async fn put_reveal(&self, vote_id: &str, reveal: Reveal) -> Result<(), StoreError>;
+    /// Store a batch of reveals with one outcome per reveal, in order; a failed one does not stop the rest.
+    async fn put_reveals(&self, vote_id: &str, reveals: Vec<Reveal>) -> Result<Vec<Result<(), StoreError>>, StoreError> {
+        let mut outcomes = Vec::with_capacity(reveals.len());
+        for reveal in reveals { outcomes.push(self.put_reveal(vote_id, reveal).await); }
+        Ok(outcomes)
+    }
     async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, StoreError>;
+    /// Record a delegation used for a commit; a later one from the same delegator replaces it.
+    async fn put_delegation(&self, vote_id: &str, delegation: Delegation) -> Result<(), StoreError>;
+    async fn list_delegations(&self, vote_id: &str) -> Result<Vec<Delegation>, StoreError>;
+    /// Aggregate counts, with vote status evaluated at `current_height`.
+    async fn get_stats(&self, current_height: u64) -> Result<StoreStatsDto, StoreError>;
+    /// Persist any buffered state; called on graceful shutdown. No-op by default.
+    async fn flush(&self) -> Result<(), StoreError> { Ok(()) }
 }
 
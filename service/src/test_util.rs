@@ -0,0 +1,126 @@
+//! Deterministic fixture driving a vote through its whole lifecycle, for downstream tests.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::Value;
+use sha2::{Sha256, Digest};
+use hex::ToHex;
+use shared_utils::clock::MockClock;
+
+use crate::core::template::TemplateRegistry;
+use crate::model::vote::*;
+use crate::service::{ServiceError, VoteService, VoteServiceImpl};
+use crate::store::memory::MemoryVoteStore;
+use crate::store::VoteStore;
+
+/// One voter's choice; the salt is derived from the voter so runs are reproducible.
+#[derive(Clone, Debug)]
+pub struct Ballot { pub voter: String, pub value: Value, pub salt_hex: String }
+
+impl Ballot {
+    pub fn new(voter: impl Into<String>, value: Value) -> Self {
+        let voter = voter.into();
+        let salt_hex = Sha256::digest(format!("salt|{}", voter).as_bytes()).encode_hex();
+        Self { voter, value, salt_hex }
+    }
+}
+
+/// A `VoteServiceImpl` over an in-memory store with the built-in templates, a mock clock starting at
+/// 2030-01-01 that ticks one second per operation, and a simulated chain height that each phase moves
+/// into its window. Vote ids are derived from a per-harness counter, so commitments (which are
+/// bound to the vote id) repeat across runs too.
+pub struct TestVoteHarness {
+    pub service: VoteServiceImpl,
+    pub clock: MockClock,
+    height: AtomicU64,
+    created: AtomicU64,
+}
+
+impl Default for TestVoteHarness {
+    fn default() -> Self { Self::new() }
+}
+
+impl TestVoteHarness {
+    pub fn new() -> Self { Self::with_store(Arc::new(MemoryVoteStore::new())) }
+
+    /// Drive the flow against another store, e.g. a fault-injecting one.
+    pub fn with_store(store: Arc<dyn VoteStore>) -> Self {
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+        let service = VoteServiceImpl::new(store, Arc::new(TemplateRegistry::builtin())).with_clock(Arc::new(clock.clone()));
+        Self { service, clock, height: AtomicU64::new(0), created: AtomicU64::new(0) }
+    }
+
+    /// A bare `VoteServiceImpl` over an in-memory store with the built-in templates and the system
+    /// clock, for tests that call the service directly instead of driving it through a harness.
+    pub fn memory_service() -> VoteServiceImpl {
+        VoteServiceImpl::new(Arc::new(MemoryVoteStore::new()), Arc::new(TemplateRegistry::builtin()))
+    }
+
+    /// An open vote with commit window 0..=100 and reveal window 101..=200.
+    pub fn config(title: &str, template: &str, params: Value) -> VoteConfig {
+        VoteConfig {
+            title: title.to_string(),
+            description: None,
+            options: vec![],
+            commit_start_height: 0,
+            commit_end_height: 100,
+            reveal_start_height: 101,
+            reveal_end_height: 200,
+            participants: vec![],
+            participant_keys: Default::default(),
+            quorum_threshold: 0.0,
+            non_revealer_policy: Default::default(),
+            reveal_threshold: 0,
+            value_template: template.to_string(),
+            template_version: None,
+            template_params: params,
+            max_participants: None,
+            commitment_scheme: None,
+        }
+    }
+
+    /// Current simulated chain height.
+    pub fn height(&self) -> u64 { self.height.load(Ordering::SeqCst) }
+
+    pub fn set_height(&self, height: u64) { self.height.store(height, Ordering::SeqCst); }
+
+    pub async fn create(&self, cfg: VoteConfig) -> Result<String, ServiceError> {
+        let nonce = format!("harness-{}", self.created.fetch_add(1, Ordering::SeqCst));
+        let id = self.service.create_vote_with_nonce(cfg, Some(nonce)).await?;
+        self.tick();
+        Ok(id)
+    }
+
+    /// Commit every ballot in order at the start of the commit window; stops at the first error.
+    pub async fn commit_all(&self, vote_id: &str, ballots: &[Ballot]) -> Result<Vec<CommitResponse>, ServiceError> {
+        let vote = self.service.get_vote(vote_id).await?;
+        self.set_height(vote.config.commit_start_height);
+        let mut responses = Vec::with_capacity(ballots.len());
+        for ballot in ballots {
+            responses.push(self.service.commit(vote_id, &ballot.voter, ballot.value.clone(), ballot.salt_hex.clone()).await?);
+            self.tick();
+        }
+        Ok(responses)
+    }
+
+    /// Reveal every ballot in order at the start of the reveal window; stops at the first error.
+    pub async fn reveal_all(&self, vote_id: &str, ballots: &[Ballot]) -> Result<Vec<RevealResponse>, ServiceError> {
+        let vote = self.service.get_vote(vote_id).await?;
+        self.set_height(vote.config.reveal_start_height);
+        let mut responses = Vec::with_capacity(ballots.len());
+        for ballot in ballots {
+            responses.push(self.service.reveal(vote_id, &ballot.voter, ballot.value.clone(), ballot.salt_hex.clone()).await?);
+            self.tick();
+        }
+        Ok(responses)
+    }
+
+    /// Close the reveal window and compute the final results, non-revealer policy applied.
+    pub async fn finalize(&self, vote_id: &str) -> Result<VoteResultsDto, ServiceError> {
+        let vote = self.service.get_vote(vote_id).await?;
+        self.set_height(vote.config.reveal_end_height + 1);
+        self.service.results_at(vote_id, Some(self.height())).await
+    }
+
+    fn tick(&self) { self.clock.advance(Duration::seconds(1)); }
+}
@@ -0,0 +1,102 @@
+use axum::{body::{to_bytes, Body}, http::{header, Method, Request, StatusCode}, Router};
+use decentralized_decision_vote::api::routes::create_router;
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::core::state::AppState;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "admin-token";
+
+async fn app() -> Router {
+    let yaml = format!("server: {{ host: \"0.0.0.0\", port: 8080 }}\napi: {{ enabled: true, tokens: [\"{}\"], protect_reads: true }}\n", ADMIN_TOKEN);
+    let cfg: Config = serde_yaml::from_str(&yaml).unwrap();
+    create_router(AppState::with_config(cfg).await)
+}
+
+fn vote_config() -> Value {
+    json!({ "config": {
+        "title": "Keys", "description": null, "options": ["yes", "no"],
+        "commit_start_height": 0, "commit_end_height": 100, "reveal_start_height": 101, "reveal_end_height": 200,
+        "participants": ["alice"], "value_template": "bit", "template_params": {}
+    }})
+}
+
+enum Auth<'a> { Bearer(&'a str), Key(&'a str) }
+
+async fn send(app: &Router, method: Method, uri: &str, auth: Auth<'_>, body: Option<Value>) -> (StatusCode, Value) {
+    let req = Request::builder().method(method).uri(uri).header(header::CONTENT_TYPE, "application/json");
+    let req = match auth {
+        Auth::Bearer(token) => req.header(header::AUTHORIZATION, format!("Bearer {}", token)),
+        Auth::Key(key) => req.header("X-API-Key", key),
+    };
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_else(Body::empty);
+    let resp = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn create_key(app: &Router, request: Value) -> (String, String) {
+    let (status, body) = send(app, Method::POST, "/api/admin/keys", Auth::Bearer(ADMIN_TOKEN), Some(request)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], 0, "{}", body);
+    assert!(body["data"]["key"].get("hashed_secret").is_none());
+    (body["data"]["api_key"].as_str().unwrap().to_string(), body["data"]["key"]["id"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn key_scopes_are_enforced_per_route() {
+    let app = app().await;
+    let (reader, _) = create_key(&app, json!({ "scopes": ["votes:read"] })).await;
+    let (writer, _) = create_key(&app, json!({ "scopes": ["votes:read", "votes:write"] })).await;
+
+    let (status, _) = send(&app, Method::GET, "/api/votes", Auth::Key(&reader), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = send(&app, Method::POST, "/api/votes", Auth::Key(&reader), Some(vote_config())).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["message"], "api key lacks scope votes:write");
+
+    let (status, body) = send(&app, Method::POST, "/api/votes", Auth::Key(&writer), Some(vote_config())).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], 0, "{}", body);
+    let (status, _) = send(&app, Method::GET, "/api/admin/keys", Auth::Key(&writer), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A wrong secret for a real key ID is rejected like an unknown key
+    let forged = format!("{}.{}", writer.split_once('.').unwrap().0, "0".repeat(64));
+    let (status, _) = send(&app, Method::GET, "/api/votes", Auth::Key(&forged), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn revoked_key_is_rejected() {
+    let app = app().await;
+    let (key, id) = create_key(&app, json!({ "scopes": ["votes:read"] })).await;
+    let (status, _) = send(&app, Method::GET, "/api/votes", Auth::Key(&key), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, Method::POST, &format!("/api/admin/keys/{}/revoke", id), Auth::Bearer(ADMIN_TOKEN), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], 0, "{}", body);
+
+    let (status, body) = send(&app, Method::GET, "/api/votes", Auth::Key(&key), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "api key revoked");
+}
+
+#[tokio::test]
+async fn expired_and_rate_limited_keys_are_rejected() {
+    let app = app().await;
+    let (expired, _) = create_key(&app, json!({ "scopes": ["votes:read"], "expires_at": 1 })).await;
+    let (status, body) = send(&app, Method::GET, "/api/votes", Auth::Key(&expired), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "api key expired");
+
+    let (limited, _) = create_key(&app, json!({ "scopes": ["votes:read"], "rate_limit_per_minute": 2 })).await;
+    for _ in 0..2 {
+        let (status, _) = send(&app, Method::GET, "/api/votes", Auth::Key(&limited), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, _) = send(&app, Method::GET, "/api/votes", Auth::Key(&limited), None).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+}
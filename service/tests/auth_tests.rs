@@ -0,0 +1,67 @@
+use axum::{body::{to_bytes, Body}, http::{header, Method, Request, StatusCode}, Router};
+use decentralized_decision_vote::api::routes::create_router;
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::core::state::AppState;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn app(api: &str) -> Router {
+    let yaml = format!("server: {{ host: \"0.0.0.0\", port: 8080 }}\napi: {}\n", api);
+    let cfg: Config = serde_yaml::from_str(&yaml).unwrap();
+    cfg.validate().unwrap();
+    create_router(AppState::with_config(cfg).await)
+}
+
+fn create_body() -> Body {
+    let config = json!({
+        "title": "Auth", "description": null, "options": ["yes", "no"],
+        "commit_start_height": 0, "commit_end_height": 100, "reveal_start_height": 101, "reveal_end_height": 200,
+        "participants": ["alice"], "value_template": "bit", "template_params": {}
+    });
+    Body::from(json!({ "config": config }).to_string())
+}
+
+async fn send(app: &Router, method: Method, uri: &str, token: Option<&str>) -> (StatusCode, Value) {
+    let mut req = Request::builder().method(method.clone()).uri(uri).header(header::CONTENT_TYPE, "application/json");
+    if let Some(token) = token { req = req.header(header::AUTHORIZATION, format!("Bearer {}", token)); }
+    let body = if method == Method::POST { create_body() } else { Body::empty() };
+    let resp = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn valid_token_can_create_vote() {
+    let app = app("{ enabled: true, tokens: [\"t1\", \"t2\"] }").await;
+    let (status, body) = send(&app, Method::POST, "/api/votes", Some("t2")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], 0, "{}", body);
+}
+
+#[tokio::test]
+async fn bad_or_missing_token_is_unauthorized() {
+    let app = app("{ enabled: true, tokens: [\"t1\"] }").await;
+    for token in [Some("t2"), Some("t1x"), None] {
+        let (status, body) = send(&app, Method::POST, "/api/votes", token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["message"], "unauthorized");
+    }
+    // Reads stay public unless protect_reads is set
+    let (status, _) = send(&app, Method::GET, "/api/votes", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = self::app("{ enabled: true, tokens: [\"t1\"], protect_reads: true }").await;
+    let (status, _) = send(&app, Method::GET, "/api/votes", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, Method::GET, "/api/votes", Some("t1")).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn disabled_auth_leaves_routes_open() {
+    let app = app("{ enabled: false, tokens: [] }").await;
+    let (status, body) = send(&app, Method::POST, "/api/votes", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], 0, "{}", body);
+}
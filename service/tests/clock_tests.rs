@@ -0,0 +1,69 @@
+use decentralized_decision_vote::service::{delegation_signing_message, ServiceError, VoteService, VoteServiceImpl};
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::core::template::{TemplateRegistry, BitTemplate};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use chrono::{Duration, TimeZone, Utc};
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::json;
+use shared_utils::clock::MockClock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn service(clock: &MockClock) -> VoteServiceImpl {
+    let mut registry = TemplateRegistry::new();
+    registry.register(BitTemplate);
+    VoteServiceImpl::new(Arc::new(MemoryVoteStore::default()), Arc::new(registry)).with_clock(Arc::new(clock.clone()))
+}
+
+fn config(alice: &SigningKey) -> VoteConfig {
+    VoteConfig {
+        participants: vec!["bob".to_string()],
+        participant_keys: HashMap::from([("alice".to_string(), hex::encode(alice.verifying_key().to_bytes()))]),
+        ..TestVoteHarness::config("Clocked", "bit", json!({}))
+    }
+}
+
+fn delegation(key: &SigningKey, vote_id: &str, expires_at: i64) -> Delegation {
+    let message = delegation_signing_message(vote_id, "alice", "bob", expires_at);
+    Delegation {
+        delegator: "alice".to_string(),
+        delegate: "bob".to_string(),
+        expires_at,
+        signature_hex: hex::encode(key.sign(&message).to_bytes()),
+    }
+}
+
+#[tokio::test]
+async fn test_timestamps_come_from_injected_clock() {
+    let start = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    let clock = MockClock::new(start);
+    let service = service(&clock);
+    let vote_id = service.create_vote(config(&SigningKey::from_bytes(&[1u8; 32]))).await.unwrap();
+
+    let commit = service.commit(&vote_id, "bob", json!(1), "abcd".to_string()).await.unwrap();
+    assert_eq!(commit.ts, start.timestamp());
+
+    clock.advance(Duration::minutes(5));
+    let reveal = service.reveal(&vote_id, "bob", json!(1), "abcd".to_string()).await.unwrap();
+    assert_eq!(reveal.ts, (start + Duration::minutes(5)).timestamp());
+}
+
+#[tokio::test]
+async fn test_delegation_expires_when_clock_passes_it() {
+    let start = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    let clock = MockClock::new(start);
+    let service = service(&clock);
+    let alice = SigningKey::from_bytes(&[1u8; 32]);
+    let vote_id = service.create_vote(config(&alice)).await.unwrap();
+    let d = delegation(&alice, &vote_id, (start + Duration::hours(1)).timestamp());
+
+    // an hour later the same delegation is no longer usable, with no real time passing
+    clock.advance(Duration::hours(1));
+    let result = service.commit_delegated(&vote_id, d.clone(), json!(1), "abcd".to_string(), None).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+
+    clock.set(start + Duration::minutes(59));
+    let result = service.commit_delegated(&vote_id, d, json!(1), "abcd".to_string(), None).await;
+    assert!(result.is_ok(), "{:?}", result.err());
+}
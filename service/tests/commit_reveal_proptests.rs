@@ -0,0 +1,154 @@
+//! Property tests for the commit/reveal invariants and template canonicalization.
+
+use decentralized_decision_vote::core::template::{BitTemplate, OptionIndexTemplate, StringTemplate, VoteValueTemplate};
+use decentralized_decision_vote::service::{ServiceError, VoteService, VoteServiceImpl};
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use proptest::prelude::*;
+use serde_json::{json, Value};
+use std::future::Future;
+
+/// A template together with params and a value it accepts.
+#[derive(Debug, Clone)]
+struct Case {
+    template: &'static str,
+    params: Value,
+    value: Value,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future)
+}
+
+fn template(id: &str) -> Box<dyn VoteValueTemplate> {
+    match id {
+        "bit" => Box::new(BitTemplate),
+        "option_index" => Box::new(OptionIndexTemplate),
+        _ => Box::new(StringTemplate),
+    }
+}
+
+async fn open_vote(service: &VoteServiceImpl, case: &Case) -> String {
+    let cfg = TestVoteHarness::config("Properties", case.template, case.params.clone());
+    service.create_vote(cfg).await.unwrap()
+}
+
+/// Values each template accepts, with the params that make them valid.
+fn valid_case() -> impl Strategy<Value = Case> {
+    prop_oneof![
+        prop_oneof![any::<bool>().prop_map(Value::from), (0u64..=1).prop_map(Value::from)]
+            .prop_map(|value| Case { template: "bit", params: json!({}), value }),
+        (1u64..64)
+            .prop_flat_map(|max| (Just(max), 0..max))
+            .prop_map(|(max, index)| Case { template: "option_index", params: json!({ "max": max }), value: json!(index) }),
+        ".{0,32}".prop_map(|s| Case { template: "string", params: json!({ "max_len": 4096 }), value: json!(s) }),
+    ]
+}
+
+/// Two values for the same template and params.
+fn case_with_other_value() -> impl Strategy<Value = (Case, Value)> {
+    valid_case().prop_flat_map(|case| {
+        let other = match case.template {
+            "bit" => prop_oneof![any::<bool>().prop_map(Value::from), (0u64..=1).prop_map(Value::from)].boxed(),
+            "option_index" => (0..case.params["max"].as_u64().unwrap()).prop_map(Value::from).boxed(),
+            _ => ".{0,32}".prop_map(Value::from).boxed(),
+        };
+        (Just(case), other)
+    })
+}
+
+/// Arbitrary JSON; leaves shrink towards `null`, containers towards empty.
+fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        any::<f64>().prop_filter("finite", |f| f.is_finite()).prop_map(Value::from),
+        ".{0,16}".prop_map(Value::from),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+            prop::collection::btree_map("[a-z]{0,4}", inner, 0..4).prop_map(|m| Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+fn salt() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..32)
+}
+
+proptest! {
+    #[test]
+    fn same_value_and_salt_give_same_commitment(case in valid_case(), salt in salt()) {
+        let (first, second) = block_on(async {
+            let service = TestVoteHarness::memory_service();
+            let id = open_vote(&service, &case).await;
+            let first = service.commit(&id, "alice", case.value.clone(), hex::encode(&salt)).await.unwrap();
+            let second = service.commit(&id, "bob", case.value.clone(), hex::encode(&salt)).await.unwrap();
+            (first.commitment_hex, second.commitment_hex)
+        });
+        prop_assert_eq!(first, second);
+    }
+
+    #[test]
+    fn salt_hex_case_does_not_change_commitment(case in valid_case(), salt in salt()) {
+        let (lower, upper) = block_on(async {
+            let service = TestVoteHarness::memory_service();
+            let id = open_vote(&service, &case).await;
+            let lower = service.commit(&id, "alice", case.value.clone(), hex::encode(&salt)).await.unwrap();
+            let upper = service.commit(&id, "bob", case.value.clone(), hex::encode_upper(&salt)).await.unwrap();
+            (lower.commitment_hex, upper.commitment_hex)
+        });
+        prop_assert_eq!(lower, upper);
+    }
+
+    #[test]
+    fn different_salts_give_different_commitments(case in valid_case(), a in salt(), b in salt()) {
+        prop_assume!(a != b);
+        let (first, second) = block_on(async {
+            let service = TestVoteHarness::memory_service();
+            let id = open_vote(&service, &case).await;
+            let first = service.commit(&id, "alice", case.value.clone(), hex::encode(&a)).await.unwrap();
+            let second = service.commit(&id, "bob", case.value.clone(), hex::encode(&b)).await.unwrap();
+            (first.commitment_hex, second.commitment_hex)
+        });
+        prop_assert_ne!(first, second);
+    }
+
+    #[test]
+    fn reveal_accepts_only_the_committed_opening(
+        (case, other_value) in case_with_other_value(),
+        salt in salt(),
+        other_salt in salt(),
+    ) {
+        let tpl = template(case.template);
+        let same_value = tpl.canonicalize(&case.value, &case.params) == tpl.canonicalize(&other_value, &case.params);
+        prop_assume!(!same_value || salt != other_salt);
+
+        let (wrong, right) = block_on(async {
+            let service = TestVoteHarness::memory_service();
+            let id = open_vote(&service, &case).await;
+            service.commit(&id, "alice", case.value.clone(), hex::encode(&salt)).await.unwrap();
+            let wrong = service.reveal(&id, "alice", other_value.clone(), hex::encode(&other_salt)).await;
+            let right = service.reveal(&id, "alice", case.value.clone(), hex::encode(&salt)).await;
+            (wrong, right)
+        });
+        prop_assert!(matches!(wrong, Err(ServiceError::BadRequest(ref message)) if message == "commitment mismatch"));
+        prop_assert!(right.is_ok());
+    }
+
+    #[test]
+    fn canonicalization_is_stable(value in json_value(), max in 1u64..64, max_len in 0u64..8192) {
+        let params = [("bit", json!({})), ("option_index", json!({ "max": max })), ("string", json!({ "max_len": max_len }))];
+        // what the server sees after the value travels over the wire
+        let reparsed: Value = serde_json::from_str(&serde_json::to_string(&value).unwrap()).unwrap();
+        for (id, params) in params {
+            let tpl = template(id);
+            let canonical = tpl.canonicalize(&value, &params);
+            prop_assert_eq!(&canonical, &tpl.canonicalize(&value, &params));
+            prop_assert_eq!(&canonical, &tpl.canonicalize(&reparsed, &params));
+            prop_assert_eq!(canonical.is_ok(), tpl.validate(&value, &params).is_ok(), "{} validate/canonicalize disagree on {}", id, value);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::core::template::TemplateRegistry;
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::service::{commitment_hash, ServiceError, VoteService, VoteServiceImpl, COMMITMENT_SCHEME, DEFAULT_DOMAIN_TAG};
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::store::VoteStore;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+fn config() -> VoteConfig {
+    TestVoteHarness::config("Pick a colour", "option_index", json!({ "max": 3 }))
+}
+
+fn service(store: Arc<dyn VoteStore>, domain_tag: &str) -> VoteServiceImpl {
+    VoteServiceImpl::new(store, Arc::new(TemplateRegistry::builtin())).with_domain_tag(domain_tag)
+}
+
+#[test]
+fn test_same_value_and_salt_differ_across_domains_and_votes() {
+    let base = commitment_hash("prod", "vote-1", b"1", b"salt");
+    assert_eq!(base, commitment_hash("prod", "vote-1", b"1", b"salt"));
+    assert_ne!(base, commitment_hash("staging", "vote-1", b"1", b"salt"));
+    assert_ne!(base, commitment_hash("prod", "vote-2", b"1", b"salt"));
+}
+
+#[tokio::test]
+async fn test_commitment_is_bound_to_its_vote() {
+    let service = service(Arc::new(MemoryVoteStore::default()), DEFAULT_DOMAIN_TAG);
+    let first = service.create_vote(config()).await.unwrap();
+    let second = service.create_vote(config()).await.unwrap();
+
+    let a = service.commit(&first, "alice", json!(1), "abcd".to_string()).await.unwrap();
+    let b = service.commit(&second, "alice", json!(1), "abcd".to_string()).await.unwrap();
+    assert_ne!(a.commitment_hex, b.commitment_hex);
+}
+
+#[tokio::test]
+async fn test_reveal_only_opens_under_the_committing_domain() {
+    let store: Arc<dyn VoteStore> = Arc::new(MemoryVoteStore::default());
+    let staging = service(store.clone(), "staging");
+    let prod = service(store, "prod");
+    let vote_id = staging.create_vote(config()).await.unwrap();
+    staging.commit(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+
+    let check = prod.verify_commitment(&vote_id, "alice", json!(2), "abcd").await.unwrap();
+    assert!(!check.valid);
+    let err = prod.reveal(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap_err();
+    assert!(matches!(err, ServiceError::BadRequest(ref m) if m == "commitment mismatch"), "{:?}", err);
+
+    staging.reveal(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+}
+
+#[test]
+fn test_domain_tag_config() {
+    let base = "server: { host: \"0.0.0.0\", port: 8080 }\napi: { enabled: false, tokens: [] }\n";
+    let cfg: Config = serde_yaml::from_str(base).unwrap();
+    assert_eq!(cfg.commitments.domain_tag, DEFAULT_DOMAIN_TAG);
+
+    let cfg: Config = serde_yaml::from_str(&format!("{}commitments: {{ domain_tag: \"staging\" }}\n", base)).unwrap();
+    assert_eq!(cfg.commitments.domain_tag, "staging");
+    assert!(cfg.validate().is_ok());
+
+    let cfg: Config = serde_yaml::from_str(&format!("{}commitments: {{ domain_tag: \"a|b\" }}\n", base)).unwrap();
+    assert!(cfg.validate().unwrap_err().contains("domain_tag"));
+}
+
+#[tokio::test]
+async fn test_commitment_replayed_into_another_vote_is_rejected() {
+    let store: Arc<dyn VoteStore> = Arc::new(MemoryVoteStore::default());
+    let service = service(store.clone(), DEFAULT_DOMAIN_TAG);
+    let vote_a = service.create_vote(config()).await.unwrap();
+    let vote_b = service.create_vote(config()).await.unwrap();
+    service.commit(&vote_a, "alice", json!(1), "abcd".to_string()).await.unwrap();
+
+    // copy alice's commitment from vote A into vote B as-is
+    let captured = store.get_commitment(&vote_a, "alice").await.unwrap().unwrap();
+    store.put_commitment(&vote_b, captured, None).await.unwrap();
+
+    assert!(!service.verify_commitment(&vote_b, "alice", json!(1), "abcd").await.unwrap().valid);
+    let err = service.reveal(&vote_b, "alice", json!(1), "abcd".to_string()).await.unwrap_err();
+    assert!(matches!(err, ServiceError::BadRequest(ref m) if m == "commitment mismatch"), "{:?}", err);
+
+    service.reveal(&vote_a, "alice", json!(1), "abcd".to_string()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_pre_upgrade_commitment_still_reveals() {
+    let store: Arc<dyn VoteStore> = Arc::new(MemoryVoteStore::default());
+    let service = service(store.clone(), DEFAULT_DOMAIN_TAG);
+    assert_eq!(service.get_vote(&service.create_vote(config()).await.unwrap()).await.unwrap().config.commitment_scheme, Some(COMMITMENT_SCHEME));
+
+    // a vote stored before schemes were recorded, holding a commitment over `commit|<value>|<salt>`
+    let vote_id = store.create_vote(config()).await.unwrap();
+    // editing the vote keeps it on the legacy scheme
+    let edited = service.update_vote_config(&vote_id, VoteConfig { title: "Pick a color".to_string(), ..config() }).await.unwrap();
+    assert_eq!(edited.config.commitment_scheme, None);
+    let canonical = TemplateRegistry::builtin().get("option_index").unwrap().canonicalize(&json!(2), &json!({ "max": 3 })).unwrap();
+    let mut hasher = Sha256::new();
+    hasher.update(b"commit|");
+    hasher.update(&canonical);
+    hasher.update(b"|");
+    hasher.update([0xab, 0xcd]);
+    let commitment_hex = hex::encode(hasher.finalize());
+    store.put_commitment(&vote_id, Commitment { voter: "alice".to_string(), commitment_hex, ts: 0 }, None).await.unwrap();
+
+    assert!(service.verify_commitment(&vote_id, "alice", json!(2), "abcd").await.unwrap().valid);
+    service.reveal(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+    // new commitments to the legacy vote use its scheme too, so they open as well
+    service.commit(&vote_id, "bob", json!(1), "beef".to_string()).await.unwrap();
+    service.reveal(&vote_id, "bob", json!(1), "beef".to_string()).await.unwrap();
+}
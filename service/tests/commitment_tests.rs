@@ -1,4 +1,4 @@
-use decentralized_decision_vote::core::template::{BitTemplate, OptionIndexTemplate, StringTemplate, VoteValueTemplate};
+use decentralized_decision_vote::core::template::{BitTemplate, OptionIndexTemplate, StringTemplate, VoteValueTemplate, STRING_TEMPLATE_MAX_LEN};
 use serde_json::json;
 
 #[test]
@@ -76,6 +76,19 @@ fn test_string_template_canonicalize() {
     assert_eq!(template.canonicalize(&json!(""), &json!({})).unwrap(), b"".to_vec());
 }
 
+#[test]
+fn test_string_template_bounds_unlimited_input() {
+    let template = StringTemplate;
+    let at_cap = "a".repeat(STRING_TEMPLATE_MAX_LEN as usize);
+    let over_cap = "a".repeat(STRING_TEMPLATE_MAX_LEN as usize + 1);
+
+    assert!(template.canonicalize(&json!(at_cap), &json!({})).is_ok());
+    assert!(template.canonicalize(&json!(over_cap), &json!({})).is_err());
+    // A larger max_len cannot lift the cap
+    assert!(template.canonicalize(&json!(over_cap), &json!({"max_len": 1_000_000})).is_err());
+    assert!(template.canonicalize(&json!(123), &json!({})).is_err());
+}
+
 #[test]
 fn test_commitment_algorithm_consistency() {
     use sha2::{Sha256, Digest};
@@ -0,0 +1,45 @@
+use axum::{body::Body, http::{header, Method, Request}, routing::get, Router};
+use decentralized_decision_vote::api::cors_layer;
+use decentralized_decision_vote::config::CorsConfig;
+use tower::ServiceExt;
+
+fn cors() -> CorsConfig {
+    CorsConfig { allowed_origins: vec!["https://ui.example".into()], ..Default::default() }
+}
+
+async fn preflight(cfg: &CorsConfig, origin: &str) -> axum::response::Response {
+    let app = Router::new().route("/api/votes", get(|| async { "[]" })).layer(cors_layer(cfg));
+    let req = Request::builder()
+        .method(Method::OPTIONS)
+        .uri("/api/votes")
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+        .body(Body::empty())
+        .unwrap();
+    app.oneshot(req).await.unwrap()
+}
+
+#[tokio::test]
+async fn allowed_origin_is_reflected() {
+    let resp = preflight(&cors(), "https://ui.example").await;
+    assert_eq!(resp.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://ui.example");
+}
+
+#[tokio::test]
+async fn disallowed_origin_is_not_reflected() {
+    let resp = preflight(&cors(), "https://evil.example").await;
+    assert!(resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    let resp = preflight(&CorsConfig::default(), "https://ui.example").await;
+    assert!(resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+}
+
+#[test]
+fn wildcard_with_credentials_is_rejected() {
+    let yaml = r#"
+server: { host: "0.0.0.0", port: 8080 }
+api: { enabled: false, tokens: [] }
+cors: { allowed_origins: ["*"], allow_credentials: true }
+"#;
+    let cfg: decentralized_decision_vote::config::Config = serde_yaml::from_str(yaml).unwrap();
+    assert!(cfg.validate().unwrap_err().contains("allow_credentials"));
+}
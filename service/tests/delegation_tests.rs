@@ -0,0 +1,84 @@
+use decentralized_decision_vote::service::{delegation_signing_message, ServiceError, VoteService, VoteServiceImpl};
+use decentralized_decision_vote::store::{VoteStore, memory::MemoryVoteStore};
+use decentralized_decision_vote::core::template::{TemplateRegistry, BitTemplate};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn service(store: MemoryVoteStore) -> VoteServiceImpl {
+    let mut registry = TemplateRegistry::new();
+    registry.register(BitTemplate);
+    VoteServiceImpl::new(Arc::new(store), Arc::new(registry))
+}
+
+fn config(alice: &SigningKey) -> VoteConfig {
+    VoteConfig {
+        participant_keys: HashMap::from([("alice".to_string(), hex::encode(alice.verifying_key().to_bytes()))]),
+        ..TestVoteHarness::config("Delegated", "bit", json!({}))
+    }
+}
+
+fn delegation(key: &SigningKey, vote_id: &str, delegator: &str, delegate: &str, expires_at: i64) -> Delegation {
+    let message = delegation_signing_message(vote_id, delegator, delegate, expires_at);
+    Delegation {
+        delegator: delegator.to_string(),
+        delegate: delegate.to_string(),
+        expires_at,
+        signature_hex: hex::encode(key.sign(&message).to_bytes()),
+    }
+}
+
+fn tomorrow() -> i64 { chrono::Utc::now().timestamp() + 86_400 }
+
+#[tokio::test]
+async fn test_delegated_commit_is_attributed_to_delegator() {
+    let store = MemoryVoteStore::default();
+    let service = service(store.clone());
+    let alice = SigningKey::from_bytes(&[1u8; 32]);
+    let vote_id = service.create_vote(config(&alice)).await.unwrap();
+
+    let d = delegation(&alice, &vote_id, "alice", "bob", tomorrow());
+    let result = service.commit_delegated(&vote_id, d.clone(), json!(1), "abcd".to_string(), None).await;
+    assert!(result.is_ok(), "{:?}", result.err());
+
+    assert!(store.get_commitment(&vote_id, "alice").await.unwrap().is_some());
+    assert!(store.get_commitment(&vote_id, "bob").await.unwrap().is_none());
+    assert_eq!(store.list_delegations(&vote_id).await.unwrap(), vec![d]);
+
+    // the delegator reveals their own vote
+    let reveal = service.reveal(&vote_id, "alice", json!(1), "abcd".to_string()).await;
+    assert!(reveal.is_ok(), "{:?}", reveal.err());
+}
+
+#[tokio::test]
+async fn test_forged_delegation_is_rejected() {
+    let store = MemoryVoteStore::default();
+    let service = service(store.clone());
+    let alice = SigningKey::from_bytes(&[1u8; 32]);
+    let mallory = SigningKey::from_bytes(&[2u8; 32]);
+    let vote_id = service.create_vote(config(&alice)).await.unwrap();
+
+    let forged = delegation(&mallory, &vote_id, "alice", "mallory", tomorrow());
+    let result = service.commit_delegated(&vote_id, forged, json!(1), "abcd".to_string(), None).await;
+    assert!(matches!(result, Err(ServiceError::Forbidden)));
+    assert!(store.get_commitment(&vote_id, "alice").await.unwrap().is_none());
+    assert!(store.list_delegations(&vote_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_expired_and_self_delegations_are_rejected() {
+    let service = service(MemoryVoteStore::default());
+    let alice = SigningKey::from_bytes(&[1u8; 32]);
+    let vote_id = service.create_vote(config(&alice)).await.unwrap();
+
+    let expired = delegation(&alice, &vote_id, "alice", "bob", chrono::Utc::now().timestamp() - 1);
+    let result = service.commit_delegated(&vote_id, expired, json!(1), "abcd".to_string(), None).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+
+    let to_self = delegation(&alice, &vote_id, "alice", "alice", tomorrow());
+    let result = service.commit_delegated(&vote_id, to_self, json!(1), "abcd".to_string(), None).await;
+    assert!(matches!(result, Err(ServiceError::BadRequest(_))));
+}
@@ -0,0 +1,50 @@
+use decentralized_decision_vote::store::fault::{FaultInjectingVoteStore, StoreMethod};
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::store::{StoreError, VoteStore};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+fn test_config() -> VoteConfig {
+    VoteConfig {
+        options: vec!["Yes".to_string(), "No".to_string()],
+        ..TestVoteHarness::config("Faulty", "bit", json!({}))
+    }
+}
+
+#[tokio::test]
+async fn test_second_get_vote_fails() {
+    let store = FaultInjectingVoteStore::new(MemoryVoteStore::new()).fail_on_call(StoreMethod::GetVote, 2, StoreError::Io);
+    let id = store.create_vote(test_config()).await.unwrap();
+
+    assert!(store.get_vote(&id).await.is_ok());
+    assert_eq!(store.get_vote(&id).await.unwrap_err(), StoreError::Io);
+    assert!(store.get_vote(&id).await.is_ok());
+    assert_eq!(store.calls(StoreMethod::GetVote), 3);
+    // other methods are unaffected
+    assert!(store.list_commitments(&id).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_error_rate_is_reproducible_with_a_seed() {
+    let outcomes = |seed| async move {
+        let store = FaultInjectingVoteStore::new(MemoryVoteStore::new())
+            .error_rate_for(StoreMethod::ListVotes, 0.5, StoreError::Conflict)
+            .seed(seed);
+        let mut failed = Vec::new();
+        for _ in 0..32 { failed.push(store.list_votes(0, 10).await.is_err()); }
+        failed
+    };
+    let first = outcomes(7).await;
+    assert_eq!(first, outcomes(7).await);
+    assert!(first.contains(&true) && first.contains(&false));
+}
+
+#[tokio::test]
+async fn test_latency_delays_calls() {
+    let store = FaultInjectingVoteStore::new(MemoryVoteStore::new()).latency_for(StoreMethod::GetStats, Duration::from_millis(50));
+    let started = Instant::now();
+    store.get_stats(0).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(50));
+}
@@ -0,0 +1,38 @@
+use decentralized_decision_vote::core::golden::{verify_golden_vectors, GOLDEN_VECTORS};
+use decentralized_decision_vote::core::template::{TemplateRegistry, VoteValueTemplate};
+use serde_json::Value;
+
+#[test]
+fn test_builtin_templates_match_golden_vectors() {
+    assert_eq!(verify_golden_vectors(&TemplateRegistry::builtin(), GOLDEN_VECTORS), Ok(()));
+}
+
+/// `bit` v1 with its canonical byte flipped, as an accidental edit might do.
+struct ChangedBit;
+impl VoteValueTemplate for ChangedBit {
+    fn id(&self) -> &str { "bit" }
+    fn validate(&self, _raw: &Value, _params: &Value) -> Result<(), String> { Ok(()) }
+    fn canonicalize(&self, raw: &Value, _params: &Value) -> Result<Vec<u8>, String> {
+        Ok(vec![if raw.as_bool() == Some(true) || raw.as_u64() == Some(1) { b'1' } else { b'0' }])
+    }
+}
+
+#[test]
+fn test_changed_canonicalization_is_caught() {
+    let mut registry = TemplateRegistry::builtin();
+    registry.register(ChangedBit);
+    let failures = verify_golden_vectors(&registry, GOLDEN_VECTORS).unwrap_err();
+    assert_eq!(failures.len(), 4);
+    assert!(failures.iter().all(|f| f.starts_with("bit v1")), "{:?}", failures);
+    assert!(failures[0].contains("expected 01"), "{:?}", failures);
+}
+
+#[test]
+fn test_missing_template_version_is_caught() {
+    let mut registry = TemplateRegistry::new();
+    registry.register(ChangedBit);
+    let failures = verify_golden_vectors(&registry, GOLDEN_VECTORS).unwrap_err();
+    assert_eq!(failures.len(), GOLDEN_VECTORS.len());
+    assert_eq!(failures.iter().filter(|f| f.contains("is not registered")).count(), 5, "{:?}", failures);
+    assert!(failures.iter().any(|f| f.starts_with("option_index v1 is not registered")), "{:?}", failures);
+}
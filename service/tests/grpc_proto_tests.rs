@@ -0,0 +1,126 @@
+#![cfg(feature = "grpc")]
+
+//! The hand-written prost messages in src/api/grpc.rs must match proto/vote.proto.
+//!
+//! Every field declared in the .proto is encoded on the wire with its tag and type, decoded into
+//! the Rust message and encoded again: a field the Rust type lacks or declares with another tag
+//! is dropped, one with another type fails to decode. Field names are compared through `Debug`.
+
+use decentralized_decision_vote::api::grpc::proto::*;
+use prost::encoding::{encode_key, encode_varint, WireType};
+use prost::Message;
+use std::fmt::Debug;
+
+const PROTO: &str = include_str!("../proto/vote.proto");
+
+/// A field as declared in the .proto
+struct Field {
+    label: Option<String>,
+    ty: String,
+    name: String,
+    tag: u32,
+}
+
+/// Messages of the .proto with their fields, in declaration order
+fn messages() -> Vec<(String, Vec<Field>)> {
+    let mut messages = Vec::new();
+    let mut current: Option<(String, Vec<Field>)> = None;
+    for line in PROTO.lines().map(|line| line.split("//").next().unwrap().trim()) {
+        if let Some(rest) = line.strip_prefix("message ") {
+            current = Some((rest.trim_end_matches('{').trim().to_string(), Vec::new()));
+        } else if line == "}" {
+            messages.extend(current.take());
+        } else if let Some((_, fields)) = current.as_mut().filter(|_| !line.is_empty()) {
+            let (declaration, tag) = line.trim_end_matches(';').split_once('=').expect("field with a tag");
+            let words: Vec<&str> = declaration.split_whitespace().collect();
+            let (label, ty, name) = match words.as_slice() {
+                [ty, name] => (None, ty, name),
+                [label, ty, name] => (Some(label.to_string()), ty, name),
+                _ => panic!("cannot parse field `{}`", line),
+            };
+            fields.push(Field { label, ty: ty.to_string(), name: name.to_string(), tag: tag.trim().parse().unwrap() });
+        }
+    }
+    messages
+}
+
+/// One non-default value of `field`, encoded with its tag
+fn encode_field(field: &Field, buf: &mut Vec<u8>) {
+    match field.ty.as_str() {
+        "string" => prost::encoding::string::encode(field.tag, &"x".to_string(), buf),
+        "bool" | "uint64" | "int64" => {
+            encode_key(field.tag, WireType::Varint, buf);
+            encode_varint(1, buf);
+        }
+        "double" => prost::encoding::double::encode(field.tag, &1.5, buf),
+        // embedded messages are sent empty
+        _ => {
+            encode_key(field.tag, WireType::LengthDelimited, buf);
+            encode_varint(0, buf);
+        }
+    }
+}
+
+/// Decode `bytes` as `M` and encode it again, with the field names `M` declares
+fn round_trip<M: Message + Default + Debug>(bytes: &[u8]) -> Result<(Vec<u8>, Vec<String>), prost::DecodeError> {
+    let message = M::decode(bytes)?;
+    let debug = format!("{:?}", M::default());
+    let body = debug.split_once('{').map_or("", |(_, body)| body.trim_end_matches('}'));
+    let names = body.split(", ").filter_map(|field| field.split_once(':')).map(|(name, _)| name.trim().to_string()).collect();
+    Ok((message.encode_to_vec(), names))
+}
+
+fn round_trip_named(name: &str, bytes: &[u8]) -> Result<(Vec<u8>, Vec<String>), prost::DecodeError> {
+    match name {
+        "CreateVoteRequest" => round_trip::<CreateVoteRequest>(bytes),
+        "CreateVoteResponse" => round_trip::<CreateVoteResponse>(bytes),
+        "ListVotesRequest" => round_trip::<ListVotesRequest>(bytes),
+        "VoteSummary" => round_trip::<VoteSummary>(bytes),
+        "ListVotesResponse" => round_trip::<ListVotesResponse>(bytes),
+        "GetVoteRequest" => round_trip::<GetVoteRequest>(bytes),
+        "Vote" => round_trip::<Vote>(bytes),
+        "CommitRequest" => round_trip::<CommitRequest>(bytes),
+        "CommitResponse" => round_trip::<CommitResponse>(bytes),
+        "RevealRequest" => round_trip::<RevealRequest>(bytes),
+        "RevealResponse" => round_trip::<RevealResponse>(bytes),
+        "GetResultsRequest" => round_trip::<GetResultsRequest>(bytes),
+        "VoteResults" => round_trip::<VoteResults>(bytes),
+        "VerifyCommitmentRequest" => round_trip::<VerifyCommitmentRequest>(bytes),
+        "VerifyCommitmentResponse" => round_trip::<VerifyCommitmentResponse>(bytes),
+        _ => panic!("message {} of vote.proto has no Rust type in src/api/grpc.rs", name),
+    }
+}
+
+#[test]
+fn test_rust_messages_match_vote_proto() {
+    let messages = messages();
+    assert_eq!(messages.len(), 15);
+    for (name, fields) in &messages {
+        let mut bytes = Vec::new();
+        fields.iter().for_each(|field| encode_field(field, &mut bytes));
+
+        let (encoded, rust_fields) = round_trip_named(name, &bytes).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        assert_eq!(encoded, bytes, "{} does not carry every field of vote.proto with its tag", name);
+        let proto_fields: Vec<String> = fields.iter().map(|field| field.name.clone()).collect();
+        assert_eq!(rust_fields, proto_fields, "field names of {}", name);
+    }
+}
+
+#[test]
+fn test_optional_and_repeated_fields_keep_their_labels() {
+    let messages = messages();
+    let label = |message: &str, field: &str| {
+        let (_, fields) = messages.iter().find(|(name, _)| name == message).unwrap();
+        fields.iter().find(|f| f.name == field).unwrap().label.clone()
+    };
+    // optional strings are `Option<String>`: unset stays off the wire, set-but-empty does not
+    assert_eq!(label("CreateVoteRequest", "id_nonce").as_deref(), Some("optional"));
+    let unset = CreateVoteRequest { config_json: String::new(), id_nonce: None }.encode_to_vec();
+    let empty = CreateVoteRequest { config_json: String::new(), id_nonce: Some(String::new()) }.encode_to_vec();
+    assert!(unset.is_empty());
+    assert!(!empty.is_empty());
+
+    assert_eq!(label("VoteResults", "non_revealers").as_deref(), Some("repeated"));
+    let results = VoteResults { non_revealers: vec!["a".into(), "b".into()], ..Default::default() };
+    assert_eq!(VoteResults::decode(results.encode_to_vec().as_slice()).unwrap().non_revealers, ["a", "b"]);
+}
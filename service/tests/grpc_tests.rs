@@ -0,0 +1,90 @@
+#![cfg(feature = "grpc")]
+
+use decentralized_decision_vote::api::grpc::proto::vote_service_client::VoteServiceClient;
+use decentralized_decision_vote::api::grpc::proto::*;
+use decentralized_decision_vote::api::grpc::serve_grpc;
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::core::state::AppState;
+use serde_json::json;
+use tonic::transport::Channel;
+use tonic::Code;
+
+async fn client(api: &str) -> VoteServiceClient<Channel> {
+    let yaml = format!("server: {{ host: \"0.0.0.0\", port: 8080 }}\napi: {}\n", api);
+    let cfg: Config = serde_yaml::from_str(&yaml).unwrap();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(serve_grpc(AppState::with_config(cfg).await, listener, std::future::pending()));
+    VoteServiceClient::connect(format!("http://{}", addr)).await.unwrap()
+}
+
+fn create_request() -> CreateVoteRequest {
+    let config = json!({
+        "title": "gRPC", "description": null, "options": ["yes", "no"],
+        "commit_start_height": 0, "commit_end_height": 100, "reveal_start_height": 101, "reveal_end_height": 200,
+        "participants": ["alice", "bob"], "value_template": "bit", "template_params": {}
+    });
+    CreateVoteRequest { config_json: config.to_string(), id_nonce: None }
+}
+
+#[tokio::test]
+async fn vote_lifecycle_over_grpc() {
+    let mut client = client("{ enabled: false, tokens: [] }").await;
+    let id = client.create_vote(create_request()).await.unwrap().into_inner().id;
+
+    let list = client.list_votes(ListVotesRequest { offset: 0, limit: 0 }).await.unwrap().into_inner();
+    assert_eq!(list.total, 1);
+    assert_eq!(list.items[0].id, id);
+    assert_eq!(list.items[0].reveal_start_height, 101);
+
+    let commit = CommitRequest { id: id.clone(), voter: "alice".into(), value_json: "1".into(), salt_hex: "deadbeef".into(), signature_hex: None };
+    let commitment_hex = client.commit(commit).await.unwrap().into_inner().commitment_hex;
+
+    let check = |salt_hex: &str| VerifyCommitmentRequest { id: id.clone(), voter: "alice".into(), value_json: "1".into(), salt_hex: salt_hex.into() };
+    let verified = client.verify_commitment(check("deadbeef")).await.unwrap().into_inner();
+    assert!(verified.valid);
+    assert_eq!(verified.commitment_hex, commitment_hex);
+    assert!(!client.verify_commitment(check("cafebabe")).await.unwrap().into_inner().valid);
+
+    let reveal = RevealRequest { id: id.clone(), voter: "alice".into(), value_json: "1".into(), salt_hex: "deadbeef".into() };
+    assert!(client.reveal(reveal).await.unwrap().into_inner().accepted);
+
+    let vote = client.get_vote(GetVoteRequest { id: id.clone() }).await.unwrap().into_inner();
+    assert_eq!((vote.num_commitments, vote.num_reveals), (1, 1));
+    let config: serde_json::Value = serde_json::from_str(&vote.config_json).unwrap();
+    assert_eq!(config["title"], "gRPC");
+
+    let results = client.get_results(GetResultsRequest { id }).await.unwrap().into_inner();
+    assert_eq!(results.total_revealed, 1);
+    assert_eq!(results.total_eligible, 2);
+}
+
+#[tokio::test]
+async fn service_errors_map_to_status_codes() {
+    let mut client = client("{ enabled: false, tokens: [] }").await;
+    let missing = client.get_vote(GetVoteRequest { id: "missing".into() }).await.unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+
+    let bad_config = CreateVoteRequest { config_json: "{".into(), id_nonce: None };
+    assert_eq!(client.create_vote(bad_config).await.unwrap_err().code(), Code::InvalidArgument);
+
+    let id = client.create_vote(create_request()).await.unwrap().into_inner().id;
+    let outsider = CommitRequest { id: id.clone(), voter: "mallory".into(), value_json: "1".into(), salt_hex: "deadbeef".into(), signature_hex: None };
+    assert_eq!(client.commit(outsider).await.unwrap_err().code(), Code::PermissionDenied);
+
+    let uncommitted = VerifyCommitmentRequest { id, voter: "bob".into(), value_json: "1".into(), salt_hex: "deadbeef".into() };
+    assert_eq!(client.verify_commitment(uncommitted).await.unwrap_err().code(), Code::NotFound);
+}
+
+#[tokio::test]
+async fn writes_require_credentials_when_auth_enabled() {
+    let mut client = client("{ enabled: true, tokens: [\"t1\"] }").await;
+    let denied = client.create_vote(create_request()).await.unwrap_err();
+    assert_eq!(denied.code(), Code::Unauthenticated);
+
+    let mut request = tonic::Request::new(create_request());
+    request.metadata_mut().insert("authorization", "Bearer t1".parse().unwrap());
+    assert!(!client.create_vote(request).await.unwrap().into_inner().id.is_empty());
+    // reads stay open unless protect_reads is set
+    assert_eq!(client.list_votes(ListVotesRequest { offset: 0, limit: 10 }).await.unwrap().into_inner().total, 1);
+}
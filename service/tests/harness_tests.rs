@@ -0,0 +1,43 @@
+use decentralized_decision_vote::model::vote::NonRevealerPolicy;
+use decentralized_decision_vote::test_util::{Ballot, TestVoteHarness};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_harness_runs_a_full_lifecycle() {
+    let harness = TestVoteHarness::new();
+    let mut cfg = TestVoteHarness::config("Lifecycle", "option_index", json!({"max": 3}));
+    cfg.non_revealer_policy = NonRevealerPolicy::Exclude;
+    let vote_id = harness.create(cfg).await.unwrap();
+
+    let ballots = [Ballot::new("alice", json!(0)), Ballot::new("bob", json!(2)), Ballot::new("carol", json!(1))];
+    let commits = harness.commit_all(&vote_id, &ballots).await.unwrap();
+    assert_eq!(harness.height(), 0);
+    // the mock clock ticks once per operation, starting after the create
+    let start = commits[0].ts;
+    assert_eq!(commits.iter().map(|c| c.ts - start).collect::<Vec<_>>(), vec![0, 1, 2]);
+
+    // carol never reveals
+    let reveals = harness.reveal_all(&vote_id, &ballots[..2]).await.unwrap();
+    assert_eq!(harness.height(), 101);
+    assert!(reveals.iter().all(|r| r.accepted));
+    assert_eq!(reveals[0].ts - start, 3);
+
+    let results = harness.finalize(&vote_id).await.unwrap();
+    assert_eq!(harness.height(), 201);
+    assert_eq!(results.result, json!(2));
+    assert_eq!(results.total_revealed, 2);
+    assert_eq!(results.total_eligible, 2);
+    assert_eq!(results.non_revealers, vec!["carol".to_string()]);
+}
+
+#[tokio::test]
+async fn test_harness_is_deterministic() {
+    let run = || async {
+        let harness = TestVoteHarness::new();
+        let vote_id = harness.create(TestVoteHarness::config("Replay", "bit", json!({}))).await.unwrap();
+        let ballots = [Ballot::new("alice", json!(1)), Ballot::new("bob", json!(0))];
+        let commits = harness.commit_all(&vote_id, &ballots).await.unwrap();
+        commits.into_iter().map(|c| (c.commitment_hex, c.ts)).collect::<Vec<_>>()
+    };
+    assert_eq!(run().await, run().await);
+}
@@ -2,6 +2,7 @@ use decentralized_decision_vote::service::{VoteService, VoteServiceImpl};
 use decentralized_decision_vote::store::memory::MemoryVoteStore;
 use decentralized_decision_vote::core::template::{TemplateRegistry, BitTemplate, OptionIndexTemplate, StringTemplate};
 use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
 use serde_json::json;
 use std::sync::Arc;
 
@@ -20,16 +21,10 @@ async fn test_vote_lifecycle() {
     
     // Create vote
     let config = VoteConfig {
-        title: "Test Vote".to_string(),
         description: Some("Test description".to_string()),
         options: vec!["Yes".to_string(), "No".to_string()],
-        commit_start_height: 0,
-        commit_end_height: 100,
-        reveal_start_height: 101,
-        reveal_end_height: 200,
         participants: vec!["alice".to_string(), "bob".to_string()],
-        value_template: "option_index".to_string(),
-        template_params: json!({"max": 2}),
+        ..TestVoteHarness::config("Test Vote", "option_index", json!({"max": 2}))
     };
     
     let vote_id = service.create_vote(config).await.unwrap();
@@ -59,16 +54,9 @@ async fn test_participant_whitelist() {
     let service = create_test_service().await;
     
     let config = VoteConfig {
-        title: "Whitelist Test".to_string(),
-        description: None,
         options: vec!["Option 1".to_string()],
-        commit_start_height: 0,
-        commit_end_height: 100,
-        reveal_start_height: 101,
-        reveal_end_height: 200,
         participants: vec!["alice".to_string()], // Only alice allowed
-        value_template: "bit".to_string(),
-        template_params: json!({}),
+        ..TestVoteHarness::config("Whitelist Test", "bit", json!({}))
     };
     
     let vote_id = service.create_vote(config).await.unwrap();
@@ -87,16 +75,8 @@ async fn test_idempotent_commit_reveal() {
     let service = create_test_service().await;
     
     let config = VoteConfig {
-        title: "Idempotent Test".to_string(),
-        description: None,
         options: vec!["Option 1".to_string()],
-        commit_start_height: 0,
-        commit_end_height: 100,
-        reveal_start_height: 101,
-        reveal_end_height: 200,
-        participants: vec![],
-        value_template: "bit".to_string(),
-        template_params: json!({}),
+        ..TestVoteHarness::config("Idempotent Test", "bit", json!({}))
     };
     
     let vote_id = service.create_vote(config).await.unwrap();
@@ -123,16 +103,8 @@ async fn test_commitment_mismatch() {
     let service = create_test_service().await;
     
     let config = VoteConfig {
-        title: "Commitment Test".to_string(),
-        description: None,
         options: vec!["Option 1".to_string()],
-        commit_start_height: 0,
-        commit_end_height: 100,
-        reveal_start_height: 101,
-        reveal_end_height: 200,
-        participants: vec![],
-        value_template: "bit".to_string(),
-        template_params: json!({}),
+        ..TestVoteHarness::config("Commitment Test", "bit", json!({}))
     };
     
     let vote_id = service.create_vote(config).await.unwrap();
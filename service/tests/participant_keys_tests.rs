@@ -0,0 +1,65 @@
+use decentralized_decision_vote::service::{commit_signing_message, commitment_hash, ServiceError, VoteService, DEFAULT_DOMAIN_TAG};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn bound_config(participant_keys: HashMap<String, String>) -> VoteConfig {
+    VoteConfig {
+        participant_keys,
+        ..TestVoteHarness::config("Bound", "bit", json!({}))
+    }
+}
+
+/// Commitment for a bit value of 1, matching `VoteServiceImpl::commit`.
+fn commitment_hex(vote_id: &str, salt: &[u8]) -> String {
+    commitment_hash(DEFAULT_DOMAIN_TAG, vote_id, &[1u8], salt)
+}
+
+fn sign(key: &SigningKey, vote_id: &str, voter: &str) -> String {
+    let message = commit_signing_message(vote_id, voter, &commitment_hex(vote_id, &[0xab, 0xcd]));
+    hex::encode(key.sign(&message).to_bytes())
+}
+
+#[tokio::test]
+async fn test_signed_commit_from_bound_voter() {
+    let service = TestVoteHarness::memory_service();
+    let alice = SigningKey::from_bytes(&[1u8; 32]);
+    let keys = HashMap::from([("alice".to_string(), hex::encode(alice.verifying_key().to_bytes()))]);
+    let vote_id = service.create_vote(bound_config(keys)).await.unwrap();
+
+    let signature = sign(&alice, &vote_id, "alice");
+    let result = service.commit_signed(&vote_id, "alice", json!(1), "abcd".to_string(), Some(signature)).await;
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_commit_signed_with_wrong_key_is_forbidden() {
+    let service = TestVoteHarness::memory_service();
+    let alice = SigningKey::from_bytes(&[1u8; 32]);
+    let mallory = SigningKey::from_bytes(&[2u8; 32]);
+    let keys = HashMap::from([("alice".to_string(), hex::encode(alice.verifying_key().to_bytes()))]);
+    let vote_id = service.create_vote(bound_config(keys)).await.unwrap();
+
+    let forged = sign(&mallory, &vote_id, "alice");
+    let result = service.commit_signed(&vote_id, "alice", json!(1), "abcd".to_string(), Some(forged)).await;
+    assert!(matches!(result, Err(ServiceError::Forbidden)));
+
+    // an unsigned commit for a bound voter is rejected too
+    let result = service.commit(&vote_id, "alice", json!(1), "abcd".to_string()).await;
+    assert!(matches!(result, Err(ServiceError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_unknown_voter_is_forbidden() {
+    let service = TestVoteHarness::memory_service();
+    let alice = SigningKey::from_bytes(&[1u8; 32]);
+    let bob = SigningKey::from_bytes(&[3u8; 32]);
+    let keys = HashMap::from([("alice".to_string(), hex::encode(alice.verifying_key().to_bytes()))]);
+    let vote_id = service.create_vote(bound_config(keys)).await.unwrap();
+
+    let signature = sign(&bob, &vote_id, "bob");
+    let result = service.commit_signed(&vote_id, "bob", json!(1), "abcd".to_string(), Some(signature)).await;
+    assert!(matches!(result, Err(ServiceError::Forbidden)));
+}
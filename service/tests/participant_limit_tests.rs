@@ -0,0 +1,82 @@
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::service::{ServiceError, VoteService};
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+use std::sync::Arc;
+
+fn config(participants: &[&str], max_participants: Option<u64>) -> VoteConfig {
+    VoteConfig {
+        options: vec!["yes".to_string(), "no".to_string()],
+        participants: participants.iter().map(|p| p.to_string()).collect(),
+        max_participants,
+        ..TestVoteHarness::config("Capped", "bit", json!({}))
+    }
+}
+
+#[tokio::test]
+async fn test_open_vote_rejects_commits_past_the_cap() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config(&[], Some(2))).await.unwrap();
+    service.commit(&vote_id, "alice", json!(1), "aa".to_string()).await.unwrap();
+    service.commit(&vote_id, "bob", json!(0), "bb".to_string()).await.unwrap();
+
+    let err = service.commit(&vote_id, "carol", json!(1), "cc".to_string()).await.unwrap_err();
+    assert!(matches!(&err, ServiceError::BadRequest(msg) if msg == "participant limit reached"), "{:?}", err);
+    assert_eq!(service.get_vote(&vote_id).await.unwrap().num_commitments, 2);
+
+    // voters already in can still reveal
+    service.reveal(&vote_id, "alice", json!(1), "aa".to_string()).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_commits_never_overshoot_the_cap() {
+    let service = Arc::new(TestVoteHarness::memory_service());
+    let vote_id = service.create_vote(config(&[], Some(5))).await.unwrap();
+
+    let commits: Vec<_> = (0..40).map(|i| {
+        let (service, vote_id) = (service.clone(), vote_id.clone());
+        tokio::spawn(async move { service.commit(&vote_id, &format!("voter-{}", i), json!(i % 2), format!("{:02x}", i)).await })
+    }).collect();
+    let mut accepted = 0;
+    for commit in commits {
+        match commit.await.unwrap() {
+            Ok(_) => accepted += 1,
+            Err(err) => assert!(matches!(&err, ServiceError::BadRequest(msg) if msg == "participant limit reached"), "{:?}", err),
+        }
+    }
+    assert_eq!(accepted, 5);
+    assert_eq!(service.get_vote(&vote_id).await.unwrap().num_commitments, 5);
+}
+
+#[tokio::test]
+async fn test_open_vote_gets_the_configured_default_cap() {
+    let service = TestVoteHarness::memory_service().with_max_participants(1);
+    let vote_id = service.create_vote(config(&[], None)).await.unwrap();
+    assert_eq!(service.get_vote(&vote_id).await.unwrap().config.max_participants, Some(1));
+
+    service.commit(&vote_id, "alice", json!(1), "aa".to_string()).await.unwrap();
+    let err = service.commit(&vote_id, "bob", json!(1), "bb".to_string()).await.unwrap_err();
+    assert!(matches!(err, ServiceError::BadRequest(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_allow_listed_vote_is_capped_at_list_size() {
+    let service = TestVoteHarness::memory_service().with_max_participants(1);
+    let vote_id = service.create_vote(config(&["alice", "bob"], Some(5))).await.unwrap();
+    assert_eq!(service.get_vote(&vote_id).await.unwrap().config.max_participants, Some(2));
+
+    // the whole list can commit even though the open-vote default is lower
+    service.commit(&vote_id, "alice", json!(1), "aa".to_string()).await.unwrap();
+    service.commit(&vote_id, "bob", json!(1), "bb".to_string()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_zero_cap_is_rejected() {
+    let err = TestVoteHarness::memory_service().create_vote(config(&[], Some(0))).await.unwrap_err();
+    assert!(matches!(err, ServiceError::BadRequest(_)), "{:?}", err);
+
+    let yaml = "server: { host: \"0.0.0.0\", port: 8080 }\napi: { enabled: false, tokens: [] }\nlimits: { max_participants: 0 }\n";
+    let cfg: Config = serde_yaml::from_str(yaml).unwrap();
+    assert!(cfg.validate().is_err());
+}
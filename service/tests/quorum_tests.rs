@@ -0,0 +1,106 @@
+use decentralized_decision_vote::service::{VoteService, VoteServiceImpl};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+
+fn config(quorum_threshold: f64, non_revealer_policy: NonRevealerPolicy) -> VoteConfig {
+    VoteConfig {
+        participants: vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()],
+        quorum_threshold,
+        non_revealer_policy,
+        ..TestVoteHarness::config("Quorum", "bit", json!({}))
+    }
+}
+
+async fn commit_and_reveal(service: &VoteServiceImpl, vote_id: &str, voters: &[&str]) {
+    for voter in voters {
+        service.commit(vote_id, voter, json!(1), "abcd".to_string()).await.unwrap();
+        service.reveal(vote_id, voter, json!(1), "abcd".to_string()).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_results_meet_quorum() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config(0.5, NonRevealerPolicy::Ignore)).await.unwrap();
+    commit_and_reveal(&service, &vote_id, &["a", "b", "c"]).await;
+
+    let results = service.results(&vote_id).await.unwrap();
+    assert_eq!(results.total_eligible, 4);
+    assert_eq!(results.total_revealed, 3);
+    assert_eq!(results.total_abstained, 1);
+    assert_eq!(results.participation_rate, 0.75);
+    assert!(results.quorum_met);
+    assert!(!results.inconclusive);
+}
+
+#[tokio::test]
+async fn test_results_below_quorum_are_inconclusive() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config(0.5, NonRevealerPolicy::Ignore)).await.unwrap();
+    commit_and_reveal(&service, &vote_id, &["a"]).await;
+    // committed but never revealed counts as an abstention
+    service.commit(&vote_id, "b", json!(1), "abcd".to_string()).await.unwrap();
+
+    let results = service.results(&vote_id).await.unwrap();
+    assert_eq!(results.total_eligible, 4);
+    assert_eq!(results.total_revealed, 1);
+    assert_eq!(results.total_abstained, 3);
+    assert_eq!(results.participation_rate, 0.25);
+    assert!(!results.quorum_met);
+    assert!(results.inconclusive);
+}
+
+#[tokio::test]
+async fn test_invalid_quorum_threshold_is_rejected() {
+    let service = TestVoteHarness::memory_service();
+    assert!(service.create_vote(config(1.5, NonRevealerPolicy::Ignore)).await.is_err());
+}
+
+/// a and b reveal, c commits but never reveals, d never takes part
+async fn vote_with_non_revealer(service: &VoteServiceImpl, policy: NonRevealerPolicy) -> String {
+    let vote_id = service.create_vote(config(0.6, policy)).await.unwrap();
+    commit_and_reveal(service, &vote_id, &["a", "b"]).await;
+    service.commit(&vote_id, "c", json!(1), "abcd".to_string()).await.unwrap();
+    vote_id
+}
+
+#[tokio::test]
+async fn test_non_revealer_policies_after_reveal_window() {
+    let service = TestVoteHarness::memory_service();
+
+    let ignored = vote_with_non_revealer(&service, NonRevealerPolicy::Ignore).await;
+    let results = service.results_at(&ignored, Some(201)).await.unwrap();
+    assert_eq!(results.non_revealers, vec!["c".to_string()]);
+    assert!(results.penalized.is_empty());
+    assert_eq!(results.total_eligible, 4);
+    assert_eq!(results.participation_rate, 0.5);
+    assert!(results.inconclusive);
+
+    let excluded = vote_with_non_revealer(&service, NonRevealerPolicy::Exclude).await;
+    let results = service.results_at(&excluded, Some(201)).await.unwrap();
+    assert_eq!(results.non_revealers, vec!["c".to_string()]);
+    assert!(results.penalized.is_empty());
+    assert_eq!(results.total_eligible, 3);
+    assert_eq!(results.total_abstained, 1);
+    assert!(results.quorum_met);
+
+    let penalized = vote_with_non_revealer(&service, NonRevealerPolicy::Penalize).await;
+    let results = service.results_at(&penalized, Some(201)).await.unwrap();
+    assert_eq!(results.penalized, vec!["c".to_string()]);
+    assert_eq!(results.total_eligible, 4);
+    assert!(results.inconclusive);
+}
+
+#[tokio::test]
+async fn test_non_revealers_are_not_reported_before_reveal_window_closes() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = vote_with_non_revealer(&service, NonRevealerPolicy::Exclude).await;
+
+    let during = service.results_at(&vote_id, Some(150)).await.unwrap();
+    assert!(during.non_revealers.is_empty());
+    assert_eq!(during.total_eligible, 4);
+
+    let unknown_height = service.results(&vote_id).await.unwrap();
+    assert!(unknown_height.non_revealers.is_empty());
+}
@@ -0,0 +1,119 @@
+use axum::{body::{to_bytes, Body}, http::{header, Method, Request, StatusCode}, Router};
+use decentralized_decision_vote::api::routes::create_router;
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::core::state::AppState;
+use decentralized_decision_vote::model::vote::{RevealImportLine, RevealImportSummary};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "admin-token";
+
+async fn app(auth_enabled: bool) -> Router {
+    let yaml = format!("server: {{ host: \"0.0.0.0\", port: 8080 }}\napi: {{ enabled: {}, tokens: [\"{}\"] }}\n", auth_enabled, ADMIN_TOKEN);
+    let cfg: Config = serde_yaml::from_str(&yaml).unwrap();
+    create_router(AppState::with_config(cfg).await)
+}
+
+async fn send(app: &Router, method: Method, uri: &str, headers: &[(&str, &str)], body: Body) -> (StatusCode, Vec<u8>) {
+    let mut req = Request::builder().method(method).uri(uri).header(header::CONTENT_TYPE, "application/json");
+    for (name, value) in headers { req = req.header(*name, *value); }
+    let resp = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    let status = resp.status();
+    (status, to_bytes(resp.into_body(), usize::MAX).await.unwrap().to_vec())
+}
+
+async fn send_json(app: &Router, uri: &str, body: Value) -> Value {
+    let auth = format!("Bearer {}", ADMIN_TOKEN);
+    let (status, bytes) = send(app, Method::POST, uri, &[("authorization", &auth)], Body::from(body.to_string())).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["code"], 0, "{}", body);
+    body
+}
+
+async fn vote_with_commitments(app: &Router, voters: &[&str]) -> String {
+    let body = send_json(app, "/api/votes", json!({ "config": {
+        "title": "Import", "description": null, "options": [],
+        "commit_start_height": 0, "commit_end_height": 100, "reveal_start_height": 101, "reveal_end_height": 200,
+        "participants": [], "value_template": "option_index", "template_params": { "max": 3 }
+    }})).await;
+    let id = body["data"].as_str().unwrap().to_string();
+    for (i, voter) in voters.iter().enumerate() {
+        send_json(app, &format!("/api/votes/{}/commit", id), json!({ "voter": voter, "vote_value": i, "salt_hex": "abcd" })).await;
+    }
+    id
+}
+
+#[tokio::test]
+async fn test_import_stores_good_lines_and_reports_bad_ones() {
+    let app = app(false).await;
+    let id = vote_with_commitments(&app, &["alice", "bob", "carol"]).await;
+
+    let ndjson = [
+        json!({ "voter": "alice", "vote_value": 0, "salt_hex": "abcd" }).to_string(),
+        "{ not json".to_string(),
+        String::new(),
+        json!({ "voter": "bob", "vote_value": 1, "salt_hex": "abcd" }).to_string(),
+        json!({ "voter": "carol", "vote_value": 2, "salt_hex": "abcd" }).to_string(),
+    ].join("\n");
+    // deliver the body in pieces that split lines, as a slow upload would
+    let pieces: Vec<Result<Vec<u8>, Infallible>> = ndjson.as_bytes().chunks(7).map(|c| Ok(c.to_vec())).collect();
+    let body = Body::from_stream(futures_util::stream::iter(pieces));
+    let (status, bytes) = send(&app, Method::POST, &format!("/api/votes/{}/reveals/import", id), &[], body).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let text = String::from_utf8(bytes).unwrap();
+    let mut lines: Vec<&str> = text.lines().collect();
+    let summary: RevealImportSummary = serde_json::from_str(lines.pop().unwrap()).unwrap();
+    assert_eq!(summary, RevealImportSummary { imported: 3, failed: 1 });
+    let results: Vec<RevealImportLine> = lines.iter().map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert_eq!(results.iter().map(|r| (r.line, r.accepted)).collect::<Vec<_>>(), vec![(1, true), (2, false), (4, true), (5, true)]);
+    assert_eq!(results[0].voter.as_deref(), Some("alice"));
+    assert!(results[1].error.is_some());
+
+    let (_, bytes) = send(&app, Method::GET, &format!("/api/votes/{}/results", id), &[], Body::empty()).await;
+    let results: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(results["data"]["total_revealed"], 3);
+}
+
+#[tokio::test]
+async fn test_import_applies_reveal_validation() {
+    let app = app(false).await;
+    let id = vote_with_commitments(&app, &["alice", "bob"]).await;
+    send_json(&app, &format!("/api/votes/{}/reveal", id), json!({ "voter": "alice", "vote_value": 0, "salt_hex": "abcd" })).await;
+
+    let ndjson = [
+        json!({ "voter": "alice", "vote_value": 0, "salt_hex": "abcd" }),
+        json!({ "voter": "bob", "vote_value": 0, "salt_hex": "abcd" }),
+        json!({ "voter": "dave", "vote_value": 2, "salt_hex": "abcd" }),
+        json!({ "voter": "", "vote_value": 2, "salt_hex": "abcd" }),
+    ].iter().map(|l| l.to_string() + "\n").collect::<String>();
+    let (_, bytes) = send(&app, Method::POST, &format!("/api/votes/{}/reveals/import", id), &[], Body::from(ndjson)).await;
+    let text = String::from_utf8(bytes).unwrap();
+    let errors: Vec<Option<String>> = text.lines().take(4).map(|l| serde_json::from_str::<RevealImportLine>(l).unwrap().error).collect();
+    assert_eq!(errors, vec![
+        Some("conflict".to_string()),
+        Some("bad request: commitment mismatch".to_string()),
+        Some("bad request: no commitment".to_string()),
+        Some("voter is required".to_string()),
+    ]);
+}
+
+#[tokio::test]
+async fn test_import_requires_import_scope_and_known_vote() {
+    let app = app(true).await;
+    let id = vote_with_commitments(&app, &["alice"]).await;
+    let body = send_json(&app, "/api/admin/keys", json!({ "scopes": ["votes:write"] })).await;
+    let writer = body["data"]["api_key"].as_str().unwrap().to_string();
+
+    let uri = format!("/api/votes/{}/reveals/import", id);
+    let (status, _) = send(&app, Method::POST, &uri, &[("x-api-key", &writer)], Body::empty()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let auth = format!("Bearer {}", ADMIN_TOKEN);
+    let (status, bytes) = send(&app, Method::POST, "/api/votes/missing/reveals/import", &[("authorization", &auth)], Body::empty()).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["message"], "not found");
+}
@@ -0,0 +1,62 @@
+use decentralized_decision_vote::service::{ServiceError, VoteService, VoteServiceImpl};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+
+fn config(reveal_threshold: u64) -> VoteConfig {
+    VoteConfig {
+        participants: vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()],
+        reveal_threshold,
+        ..TestVoteHarness::config("Threshold", "bit", json!({}))
+    }
+}
+
+async fn commit_and_reveal(service: &VoteServiceImpl, vote_id: &str, voters: &[&str]) {
+    for voter in voters {
+        service.commit(vote_id, voter, json!(1), "abcd".to_string()).await.unwrap();
+        service.reveal(vote_id, voter, json!(1), "abcd".to_string()).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_results_withheld_below_threshold() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config(3)).await.unwrap();
+    commit_and_reveal(&service, &vote_id, &["a", "b"]).await;
+    // a commitment without a reveal does not count towards the threshold
+    service.commit(&vote_id, "c", json!(1), "abcd".to_string()).await.unwrap();
+
+    match service.results(&vote_id).await {
+        Err(ServiceError::InsufficientReveals { revealed, required }) => {
+            assert_eq!(revealed, 2);
+            assert_eq!(required, 3);
+        }
+        other => panic!("expected insufficient reveals, got {:?}", other.map(|r| r.total_revealed)),
+    }
+}
+
+#[tokio::test]
+async fn test_results_released_at_threshold() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config(3)).await.unwrap();
+    commit_and_reveal(&service, &vote_id, &["a", "b", "c"]).await;
+
+    let results = service.results(&vote_id).await.unwrap();
+    assert_eq!(results.total_revealed, 3);
+}
+
+#[tokio::test]
+async fn test_results_released_above_threshold() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config(2)).await.unwrap();
+    commit_and_reveal(&service, &vote_id, &["a", "b", "c", "d"]).await;
+
+    let results = service.results(&vote_id).await.unwrap();
+    assert_eq!(results.total_revealed, 4);
+}
+
+#[tokio::test]
+async fn test_threshold_above_eligible_voters_rejected() {
+    let service = TestVoteHarness::memory_service();
+    assert!(matches!(service.create_vote(config(5)).await, Err(ServiceError::BadRequest(_))));
+}
@@ -0,0 +1,61 @@
+use decentralized_decision_vote::core::template::{TemplateRegistry, VoteValueTemplate};
+use decentralized_decision_vote::service::{ServiceError, VoteService, VoteServiceImpl};
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::store::VoteStore;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Accepts only the listed choices, standing in for a template whose choices change between deployments.
+struct ChoiceTemplate { allowed: Vec<u64> }
+
+impl VoteValueTemplate for ChoiceTemplate {
+    fn id(&self) -> &str { "choice" }
+    fn validate(&self, raw: &Value, _params: &Value) -> Result<(), String> {
+        let choice = raw.as_u64().ok_or("choice expects number")?;
+        if self.allowed.contains(&choice) { Ok(()) } else { Err(format!("unknown choice {}", choice)) }
+    }
+    fn canonicalize(&self, raw: &Value, params: &Value) -> Result<Vec<u8>, String> {
+        self.validate(raw, params)?;
+        Ok(raw.as_u64().unwrap().to_be_bytes().to_vec())
+    }
+}
+
+fn service(store: Arc<dyn VoteStore>, allowed: Vec<u64>) -> VoteServiceImpl {
+    let mut registry = TemplateRegistry::new();
+    registry.register(ChoiceTemplate { allowed });
+    VoteServiceImpl::new(store, Arc::new(registry))
+}
+
+fn bad_request(result: Result<impl std::fmt::Debug, ServiceError>) -> String {
+    match result {
+        Err(ServiceError::BadRequest(message)) => message,
+        other => panic!("expected a bad request, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_reveal_of_a_removed_choice_is_reported_as_invalid() {
+    let store: Arc<dyn VoteStore> = Arc::new(MemoryVoteStore::new());
+    let before = service(store.clone(), vec![0, 1, 2]);
+    let vote_id = before.create_vote(TestVoteHarness::config("Choices", "choice", json!({}))).await.unwrap();
+    before.commit(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+
+    // choice 2 is dropped before alice reveals
+    let after = service(store, vec![0, 1]);
+    let message = bad_request(after.reveal(&vote_id, "alice", json!(2), "abcd".to_string()).await);
+    assert_eq!(message, "value no longer valid for template: unknown choice 2");
+}
+
+#[tokio::test]
+async fn test_wrong_salt_is_still_a_commitment_mismatch() {
+    let store: Arc<dyn VoteStore> = Arc::new(MemoryVoteStore::new());
+    let service = service(store, vec![0, 1, 2]);
+    let vote_id = service.create_vote(TestVoteHarness::config("Choices", "choice", json!({}))).await.unwrap();
+    service.commit(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+
+    let message = bad_request(service.reveal(&vote_id, "alice", json!(2), "abce".to_string()).await);
+    assert_eq!(message, "commitment mismatch");
+    let message = bad_request(service.reveal(&vote_id, "alice", json!(1), "abcd".to_string()).await);
+    assert_eq!(message, "commitment mismatch");
+}
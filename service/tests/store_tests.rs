@@ -0,0 +1,66 @@
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::store::VoteStore;
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+
+fn test_config(title: &str) -> VoteConfig {
+    VoteConfig {
+        options: vec!["Yes".to_string(), "No".to_string()],
+        ..TestVoteHarness::config(title, "bit", json!({}))
+    }
+}
+
+#[tokio::test]
+async fn test_snapshot_survives_restart() {
+    let path = std::env::temp_dir().join(format!("ddv-snapshot-{}.json", uuid::Uuid::new_v4()));
+
+    let store = MemoryVoteStore::with_snapshot(&path).await.unwrap();
+    let vote_id = store.create_vote(test_config("Persisted")).await.unwrap();
+    store.put_commitment(&vote_id, Commitment { voter: "alice".into(), commitment_hex: "ab".into(), ts: 1 }, None).await.unwrap();
+    store.put_reveal(&vote_id, Reveal { voter: "alice".into(), vote_value: json!(1), salt_hex: "cd".into(), ts: 2 }).await.unwrap();
+    store.flush().await.unwrap();
+    drop(store);
+
+    let restarted = MemoryVoteStore::with_snapshot(&path).await.unwrap();
+    let vote = restarted.get_vote(&vote_id).await.unwrap();
+    assert_eq!(vote.config.title, "Persisted");
+    assert_eq!(vote.num_commitments, 1);
+    assert_eq!(vote.num_reveals, 1);
+    assert!(restarted.get_commitment(&vote_id, "alice").await.unwrap().is_some());
+    // duplicate detection still works after reload
+    assert!(restarted.put_reveal(&vote_id, Reveal { voter: "alice".into(), vote_value: json!(0), salt_hex: "cd".into(), ts: 3 }).await.is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_default_store_does_not_persist() {
+    let store = MemoryVoteStore::new();
+    store.create_vote(test_config("Ephemeral")).await.unwrap();
+    assert!(store.flush().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_stats_counts() {
+    let store = MemoryVoteStore::new();
+    let open = store.create_vote(test_config("Open")).await.unwrap();
+    let mut late = test_config("Late");
+    late.commit_start_height = 50;
+    store.create_vote(late).await.unwrap();
+
+    for voter in ["alice", "bob"] {
+        store.put_commitment(&open, Commitment { voter: voter.into(), commitment_hex: "ab".into(), ts: 1 }, None).await.unwrap();
+    }
+    store.put_reveal(&open, Reveal { voter: "alice".into(), vote_value: json!(1), salt_hex: "cd".into(), ts: 2 }).await.unwrap();
+
+    let stats = store.get_stats(10).await.unwrap();
+    assert_eq!(stats.total_votes, 2);
+    assert_eq!(stats.total_commitments, 2);
+    assert_eq!(stats.total_reveals, 1);
+    assert_eq!(stats.votes_by_status.get("commit"), Some(&1));
+    assert_eq!(stats.votes_by_status.get("pending"), Some(&1));
+
+    let stats = store.get_stats(500).await.unwrap();
+    assert_eq!(stats.votes_by_status.get("closed"), Some(&2));
+}
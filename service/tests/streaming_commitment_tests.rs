@@ -0,0 +1,79 @@
+use decentralized_decision_vote::core::template::{StringTemplate, TemplateRegistry, VoteValueTemplate, CANONICAL_CHUNK_LEN, STRING_TEMPLATE_MAX_LEN};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::service::{commitment_hash, CommitmentHasher, VoteService, VoteServiceImpl, DEFAULT_DOMAIN_TAG};
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// `{ "byte": b, "len": n }` canonicalizes to `n` copies of `b`; streaming never holds more than one chunk.
+struct RepeatTemplate;
+impl RepeatTemplate {
+    fn parse(raw: &Value) -> Result<(u8, usize), String> {
+        let byte = raw.get("byte").and_then(|v| v.as_u64()).filter(|b| *b <= 255).ok_or("repeat expects byte")?;
+        let len = raw.get("len").and_then(|v| v.as_u64()).ok_or("repeat expects len")?;
+        Ok((byte as u8, len as usize))
+    }
+}
+impl VoteValueTemplate for RepeatTemplate {
+    fn id(&self) -> &str { "repeat" }
+    fn validate(&self, raw: &Value, _params: &Value) -> Result<(), String> { Self::parse(raw).map(|_| ()) }
+    fn canonicalize(&self, raw: &Value, _params: &Value) -> Result<Vec<u8>, String> {
+        let (byte, len) = Self::parse(raw)?;
+        Ok(vec![byte; len])
+    }
+    fn canonicalize_streaming(&self, raw: &Value, _params: &Value, sink: &mut dyn FnMut(&[u8])) -> Result<(), String> {
+        let (byte, len) = Self::parse(raw)?;
+        let chunk = [byte; CANONICAL_CHUNK_LEN];
+        let mut remaining = len;
+        while remaining > 0 {
+            let n = remaining.min(CANONICAL_CHUNK_LEN);
+            sink(&chunk[..n]);
+            remaining -= n;
+        }
+        Ok(())
+    }
+}
+
+fn config(value_template: &str) -> VoteConfig {
+    TestVoteHarness::config("Large values", value_template, json!({}))
+}
+
+#[tokio::test]
+async fn test_streamed_large_value_commits_like_materialized_hash() {
+    let mut registry = TemplateRegistry::builtin();
+    registry.register(RepeatTemplate);
+    let service = VoteServiceImpl::new(Arc::new(MemoryVoteStore::default()), Arc::new(registry));
+    let vote_id = service.create_vote(config("repeat")).await.unwrap();
+
+    let value = json!({ "byte": 0x5a, "len": 16 * 1024 * 1024 + 7 });
+    let committed = service.commit(&vote_id, "alice", value.clone(), "abcd".to_string()).await.unwrap();
+
+    let canonical = RepeatTemplate.canonicalize(&value, &json!({})).unwrap();
+    assert_eq!(committed.commitment_hex, commitment_hash(DEFAULT_DOMAIN_TAG, &vote_id, &canonical, &[0xab, 0xcd]));
+    assert!(service.verify_commitment(&vote_id, "alice", value, "abcd").await.unwrap().valid);
+}
+
+#[test]
+fn test_hasher_matches_commitment_hash_for_any_chunking() {
+    let canonical: Vec<u8> = (0..3 * CANONICAL_CHUNK_LEN + 11).map(|i| i as u8).collect();
+    let expected = commitment_hash("prod", "vote-1", &canonical, b"salt");
+    for chunk_len in [1, 7, CANONICAL_CHUNK_LEN, canonical.len()] {
+        let mut hasher = CommitmentHasher::new("prod", "vote-1");
+        canonical.chunks(chunk_len).for_each(|chunk| hasher.update(chunk));
+        assert_eq!(hasher.finalize(b"salt"), expected, "chunk_len {}", chunk_len);
+    }
+    // nothing fed is the empty canonical value
+    assert_eq!(CommitmentHasher::new("prod", "vote-1").finalize(b"salt"), commitment_hash("prod", "vote-1", b"", b"salt"));
+}
+
+#[test]
+fn test_string_template_streams_its_canonical_bytes_in_chunks() {
+    let value = json!("é".repeat(STRING_TEMPLATE_MAX_LEN as usize / 2));
+    let mut chunks = Vec::new();
+    StringTemplate.canonicalize_streaming(&value, &json!({}), &mut |chunk| chunks.push(chunk.to_vec())).unwrap();
+
+    assert_eq!(chunks.len(), STRING_TEMPLATE_MAX_LEN as usize / CANONICAL_CHUNK_LEN);
+    assert_eq!(chunks.concat(), StringTemplate.canonicalize(&value, &json!({})).unwrap());
+    assert!(StringTemplate.canonicalize_streaming(&json!(1), &json!({}), &mut |_| {}).is_err());
+}
@@ -0,0 +1,98 @@
+use decentralized_decision_vote::core::tally::{ParallelTally, SequentialTally, TallyStrategy};
+use decentralized_decision_vote::core::template::{TemplateRegistry, VoteValueTemplate};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::service::{VoteService, VoteServiceImpl};
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::ThreadId;
+
+fn values(template: &str, n: usize) -> Vec<Value> {
+    (0..n).map(|i| match template {
+        "bit" => json!(i % 2 == 0),
+        "option_index" => json!(i % 3),
+        _ => json!(format!("choice-{}", i % 5)),
+    }).collect()
+}
+
+#[test]
+fn test_parallel_tally_matches_sequential_for_builtin_templates() {
+    let registry = TemplateRegistry::builtin();
+    for id in ["bit", "option_index", "string"] {
+        let tpl = registry.get(id).unwrap();
+        for n in [0, 1, 7, 1_000, 25_001] {
+            let values = values(id, n);
+            let expected = SequentialTally.tally(tpl.as_ref(), &values);
+            for chunk_size in [1, 3, 1_000, 100_000] {
+                assert_eq!(ParallelTally { chunk_size }.tally(tpl.as_ref(), &values), expected, "{} n={} chunk={}", id, n, chunk_size);
+            }
+        }
+    }
+}
+
+/// Keeps only the last value, which has no associative merge.
+struct LastValueTemplate;
+impl VoteValueTemplate for LastValueTemplate {
+    fn id(&self) -> &str { "last" }
+    fn validate(&self, _raw: &Value, _params: &Value) -> Result<(), String> { Ok(()) }
+    fn canonicalize(&self, raw: &Value, _params: &Value) -> Result<Vec<u8>, String> { Ok(raw.to_string().into_bytes()) }
+    fn reduce(&self, values: &[Value]) -> Value { values.last().cloned().unwrap_or(Value::Null) }
+}
+
+#[test]
+fn test_parallel_tally_without_merge_hook_falls_back_to_sequential() {
+    let values: Vec<Value> = (0..10).map(|i| json!(i)).collect();
+    assert_eq!(ParallelTally { chunk_size: 3 }.tally(&LastValueTemplate, &values), json!(9));
+}
+
+struct CountingTally(AtomicUsize);
+impl TallyStrategy for CountingTally {
+    fn tally(&self, tpl: &dyn VoteValueTemplate, values: &[Value]) -> Value {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        SequentialTally.tally(tpl, values)
+    }
+}
+
+fn threshold_config() -> VoteConfig {
+    TestVoteHarness::config("Threshold", "bit", json!({}))
+}
+
+#[tokio::test]
+async fn test_results_switch_strategy_at_the_threshold() {
+    let large = Arc::new(CountingTally(AtomicUsize::new(0)));
+    let service = VoteServiceImpl::new(Arc::new(MemoryVoteStore::new()), Arc::new(TemplateRegistry::builtin()))
+        .with_large_tally(2, large.clone());
+    let id = service.create_vote(threshold_config()).await.unwrap();
+
+    for (i, voter) in ["alice", "bob"].into_iter().enumerate() {
+        service.commit(&id, voter, json!(1), "abcd".to_string()).await.unwrap();
+        service.reveal(&id, voter, json!(1), "abcd".to_string()).await.unwrap();
+        let results = service.results(&id).await.unwrap();
+        assert_eq!(results.result, json!(i + 1));
+        assert_eq!(large.0.load(Ordering::SeqCst), i, "large strategy used from the second reveal on");
+    }
+}
+
+struct ThreadRecordingTally(Mutex<Option<ThreadId>>);
+impl TallyStrategy for ThreadRecordingTally {
+    fn tally(&self, tpl: &dyn VoteValueTemplate, values: &[Value]) -> Value {
+        *self.0.lock().unwrap() = Some(std::thread::current().id());
+        SequentialTally.tally(tpl, values)
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_large_tally_runs_off_the_async_worker() {
+    let large = Arc::new(ThreadRecordingTally(Mutex::new(None)));
+    let service = VoteServiceImpl::new(Arc::new(MemoryVoteStore::new()), Arc::new(TemplateRegistry::builtin()))
+        .with_large_tally(1, large.clone());
+    let id = service.create_vote(threshold_config()).await.unwrap();
+    service.commit(&id, "alice", json!(1), "abcd".to_string()).await.unwrap();
+    service.reveal(&id, "alice", json!(1), "abcd".to_string()).await.unwrap();
+
+    assert_eq!(service.results(&id).await.unwrap().result, json!(1));
+    let tally_thread = large.0.lock().unwrap().expect("large strategy used");
+    assert_ne!(tally_thread, std::thread::current().id());
+}
@@ -0,0 +1,42 @@
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::core::state::AppState;
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+
+fn config(templates: &str) -> Config {
+    let yaml = format!("server: {{ host: \"0.0.0.0\", port: 8080 }}\napi: {{ enabled: false, tokens: [] }}\ntemplates: {}\n", templates);
+    serde_yaml::from_str(&yaml).unwrap()
+}
+
+fn vote_config(template: &str) -> VoteConfig {
+    VoteConfig {
+        options: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        ..TestVoteHarness::config("Pick", template, json!({ "max": 100 }))
+    }
+}
+
+#[tokio::test]
+async fn test_config_declared_template_is_registered_and_usable() {
+    let cfg = config("[{ id: top3, base: option_index, params: { max: 3 } }]");
+    cfg.validate().unwrap();
+    let state = AppState::with_config(cfg).await;
+    assert!(state.registry.list_ids().contains(&"top3".to_string()));
+
+    let vote_id = state.service.create_vote(vote_config("top3")).await.unwrap();
+    state.service.commit(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+    // the preset max wins over the vote's own template_params
+    assert!(state.service.commit(&vote_id, "bob", json!(3), "abcd".to_string()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_invalid_template_definitions_are_rejected() {
+    let collision = config("[{ id: bit, base: option_index, params: { max: 3 } }]");
+    assert!(collision.validate().unwrap_err().contains("already registered"));
+
+    let duplicate = config("[{ id: top3, base: option_index }, { id: top3, base: bit }]");
+    assert!(duplicate.validate().unwrap_err().contains("already registered"));
+
+    let unknown_base = config("[{ id: weighted, base: weighted_choice }]");
+    assert!(unknown_base.validate().unwrap_err().contains("template not found"));
+}
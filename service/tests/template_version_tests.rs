@@ -0,0 +1,70 @@
+use decentralized_decision_vote::core::template::{BitTemplate, TemplateRegistry, VoteValueTemplate};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::service::{ServiceError, VoteService, VoteServiceImpl};
+use decentralized_decision_vote::store::memory::MemoryVoteStore;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// `bit` v2: same values, but canonicalized with a version prefix, so v1 commitments no longer match.
+struct BitV2;
+impl VoteValueTemplate for BitV2 {
+    fn id(&self) -> &str { "bit" }
+    fn version(&self) -> u32 { 2 }
+    fn validate(&self, raw: &Value, params: &Value) -> Result<(), String> { BitTemplate.validate(raw, params) }
+    fn canonicalize(&self, raw: &Value, params: &Value) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![2];
+        bytes.extend(BitTemplate.canonicalize(raw, params)?);
+        Ok(bytes)
+    }
+}
+
+fn service(store: &Arc<MemoryVoteStore>, v1: bool, v2: bool) -> VoteServiceImpl {
+    let mut registry = TemplateRegistry::new();
+    if v1 { registry.register(BitTemplate); }
+    if v2 { registry.register(BitV2); }
+    VoteServiceImpl::new(store.clone(), Arc::new(registry))
+}
+
+fn config(template_version: Option<u32>) -> VoteConfig {
+    VoteConfig {
+        template_version,
+        ..TestVoteHarness::config("Versioned", "bit", json!({}))
+    }
+}
+
+#[tokio::test]
+async fn test_vote_created_under_v1_still_validates_after_v2_is_registered() {
+    let store = Arc::new(MemoryVoteStore::default());
+    let before = service(&store, true, false);
+    let vote_id = before.create_vote(config(None)).await.unwrap();
+    assert_eq!(before.get_vote(&vote_id).await.unwrap().config.template_version, Some(1));
+    before.commit(&vote_id, "alice", json!(1), "abcd".to_string()).await.unwrap();
+
+    // restart with v2 registered alongside v1
+    let after = service(&store, true, true);
+    after.commit(&vote_id, "bob", json!(0), "abcd".to_string()).await.unwrap();
+    after.reveal(&vote_id, "alice", json!(1), "abcd".to_string()).await.unwrap();
+    after.reveal(&vote_id, "bob", json!(0), "abcd".to_string()).await.unwrap();
+    assert_eq!(after.results(&vote_id).await.unwrap().total_revealed, 2);
+
+    // new votes pick up the latest version unless one is requested
+    let latest = after.create_vote(config(None)).await.unwrap();
+    assert_eq!(after.get_vote(&latest).await.unwrap().config.template_version, Some(2));
+    let pinned = after.create_vote(config(Some(1))).await.unwrap();
+    assert_eq!(after.get_vote(&pinned).await.unwrap().config.template_version, Some(1));
+}
+
+#[tokio::test]
+async fn test_unregistered_version_is_an_error() {
+    let store = Arc::new(MemoryVoteStore::default());
+    let vote_id = service(&store, true, false).create_vote(config(None)).await.unwrap();
+    service(&store, true, false).commit(&vote_id, "alice", json!(1), "abcd".to_string()).await.unwrap();
+
+    // v1 dropped: the v1 vote must not silently fall back to v2
+    let v2_only = service(&store, false, true);
+    let err = v2_only.reveal(&vote_id, "alice", json!(1), "abcd".to_string()).await.unwrap_err();
+    assert!(matches!(err, ServiceError::BadRequest(ref m) if m.contains("no longer registered")), "{:?}", err);
+    assert!(v2_only.results(&vote_id).await.is_err());
+    assert!(v2_only.create_vote(config(Some(1))).await.is_err());
+}
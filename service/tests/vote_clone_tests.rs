@@ -0,0 +1,82 @@
+use axum::{body::{to_bytes, Body}, http::{header, Method, Request}};
+use decentralized_decision_vote::api::routes::create_router;
+use decentralized_decision_vote::config::Config;
+use decentralized_decision_vote::core::state::AppState;
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::service::{ServiceError, VoteService};
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+fn config() -> VoteConfig {
+    VoteConfig {
+        description: Some("Pick the slot".to_string()),
+        options: vec!["mon".to_string(), "tue".to_string(), "wed".to_string()],
+        participants: vec!["alice".to_string(), "bob".to_string()],
+        quorum_threshold: 0.5,
+        ..TestVoteHarness::config("Weekly sync", "option_index", json!({ "max": 3 }))
+    }
+}
+
+#[tokio::test]
+async fn test_clone_has_independent_state() {
+    let service = TestVoteHarness::memory_service();
+    let source_id = service.create_vote(config()).await.unwrap();
+    service.commit(&source_id, "alice", json!(1), "abcd".to_string()).await.unwrap();
+    service.reveal(&source_id, "alice", json!(1), "abcd".to_string()).await.unwrap();
+
+    let clone_id = service.clone_vote(&source_id, VoteConfigOverrides::default()).await.unwrap();
+    assert_ne!(clone_id, source_id);
+    let clone = service.get_vote(&clone_id).await.unwrap();
+    assert_eq!((clone.num_commitments, clone.num_reveals), (0, 0));
+    assert_eq!(clone.config.title, "Weekly sync");
+    assert_eq!(clone.config.participants, vec!["alice", "bob"]);
+    assert_eq!(clone.config.template_params, json!({ "max": 3 }));
+    assert_eq!(clone.config.template_version, Some(1));
+
+    // voting on the clone leaves the source untouched
+    service.commit(&clone_id, "bob", json!(2), "beef".to_string()).await.unwrap();
+    let source = service.get_vote(&source_id).await.unwrap();
+    assert_eq!((source.num_commitments, source.num_reveals), (1, 1));
+    assert!(matches!(service.commit(&clone_id, "mallory", json!(0), "beef".to_string()).await, Err(ServiceError::Forbidden)));
+}
+
+#[tokio::test]
+async fn test_clone_overrides_apply() {
+    let service = TestVoteHarness::memory_service();
+    let source_id = service.create_vote(config()).await.unwrap();
+
+    let overrides = VoteConfigOverrides { title: Some("Next week's sync".to_string()), description: None, shift_heights: Some(1000) };
+    let clone = service.get_vote(&service.clone_vote(&source_id, overrides).await.unwrap()).await.unwrap();
+    assert_eq!(clone.config.title, "Next week's sync");
+    assert_eq!(clone.config.description.as_deref(), Some("Pick the slot"));
+    assert_eq!(
+        (clone.config.commit_start_height, clone.config.commit_end_height, clone.config.reveal_start_height, clone.config.reveal_end_height),
+        (1000, 1100, 1101, 1200)
+    );
+
+    let overflow = VoteConfigOverrides { shift_heights: Some(u64::MAX), ..Default::default() };
+    assert!(matches!(service.clone_vote(&source_id, overflow).await, Err(ServiceError::BadRequest(_))));
+    assert!(matches!(service.clone_vote("missing", VoteConfigOverrides::default()).await, Err(ServiceError::NotFound)));
+}
+
+async fn post(app: &axum::Router, uri: &str, body: Value) -> Value {
+    let req = Request::builder().method(Method::POST).uri(uri).header(header::CONTENT_TYPE, "application/json");
+    let resp = app.clone().oneshot(req.body(Body::from(body.to_string())).unwrap()).await.unwrap();
+    serde_json::from_slice(&to_bytes(resp.into_body(), usize::MAX).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_clone_route_creates_new_vote() {
+    let cfg: Config = serde_yaml::from_str("server: { host: \"0.0.0.0\", port: 8080 }\napi: { enabled: false, tokens: [] }\n").unwrap();
+    let app = create_router(AppState::with_config(cfg).await);
+    let created = post(&app, "/api/votes", json!({ "config": config() })).await;
+    let source_id = created["data"].as_str().unwrap().to_string();
+
+    let cloned = post(&app, &format!("/api/votes/{}/clone", source_id), json!({ "title": "Again", "shift_heights": 500 })).await;
+    let clone_id = cloned["data"].as_str().unwrap();
+    assert_ne!(clone_id, source_id);
+
+    let blank = post(&app, &format!("/api/votes/{}/clone", source_id), json!({ "title": " " })).await;
+    assert_eq!(blank["code"], 1);
+}
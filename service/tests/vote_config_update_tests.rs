@@ -0,0 +1,102 @@
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::service::{ServiceError, VoteService};
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+
+fn config() -> VoteConfig {
+    VoteConfig {
+        options: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+        ..TestVoteHarness::config("Pick a colour", "option_index", json!({ "max": 3 }))
+    }
+}
+
+#[tokio::test]
+async fn test_params_can_change_before_any_commit() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config()).await.unwrap();
+
+    let mut edited = config();
+    edited.options.push("yellow".to_string());
+    edited.template_params = json!({ "max": 4 });
+    let vote = service.update_vote_config(&vote_id, edited).await.unwrap();
+    assert_eq!(vote.config.template_params, json!({ "max": 4 }));
+    assert_eq!(vote.config.template_version, Some(1));
+
+    // the new param is what commits are validated against
+    service.commit(&vote_id, "alice", json!(3), "abcd".to_string()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_params_are_fixed_after_first_commit() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config()).await.unwrap();
+    service.commit(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+
+    let mut edited = config();
+    edited.template_params = json!({ "max": 4 });
+    let err = service.update_vote_config(&vote_id, edited).await.unwrap_err();
+    assert!(matches!(err, ServiceError::Conflict), "{:?}", err);
+
+    let mut retemplated = config();
+    retemplated.value_template = "string".to_string();
+    assert!(matches!(service.update_vote_config(&vote_id, retemplated).await, Err(ServiceError::Conflict)));
+
+    // other fields stay editable
+    let mut retitled = config();
+    retitled.title = "Pick a color".to_string();
+    let vote = service.update_vote_config(&vote_id, retitled).await.unwrap();
+    assert_eq!(vote.config.title, "Pick a color");
+    assert_eq!(vote.config.template_params, json!({ "max": 3 }));
+    service.reveal(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+}
+
+type Edit = fn(&mut VoteConfig);
+
+#[tokio::test]
+async fn test_voting_rules_are_fixed_after_first_commit() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config()).await.unwrap();
+    service.commit(&vote_id, "alice", json!(2), "abcd".to_string()).await.unwrap();
+
+    let edits: [(&str, Edit); 7] = [
+        ("participants", |c| c.participants = vec!["alice".to_string()]),
+        ("participant_keys", |c| { c.participant_keys.insert("bob".to_string(), "00".repeat(32)); }),
+        ("commit window", |c| c.commit_end_height = 150),
+        ("reveal window", |c| { c.reveal_start_height = 151; c.reveal_end_height = 300 }),
+        ("quorum", |c| c.quorum_threshold = 0.5),
+        ("reveal threshold", |c| c.reveal_threshold = 1),
+        ("max participants", |c| c.max_participants = Some(5)),
+    ];
+    for (field, edit) in edits {
+        let mut edited = config();
+        edit(&mut edited);
+        let err = service.update_vote_config(&vote_id, edited).await.unwrap_err();
+        assert!(matches!(err, ServiceError::Conflict), "{}: {:?}", field, err);
+    }
+
+    let vote = service.get_vote(&vote_id).await.unwrap();
+    assert_eq!((vote.config.commit_end_height, vote.config.quorum_threshold), (100, 0.0));
+    assert!(vote.config.participants.is_empty());
+}
+
+#[tokio::test]
+async fn test_voting_rules_can_change_before_any_commit() {
+    let service = TestVoteHarness::memory_service();
+    let vote_id = service.create_vote(config()).await.unwrap();
+
+    let mut edited = config();
+    edited.participants = vec!["alice".to_string(), "bob".to_string()];
+    edited.commit_end_height = 150;
+    edited.reveal_start_height = 151;
+    edited.quorum_threshold = 0.5;
+    edited.reveal_threshold = 1;
+    let vote = service.update_vote_config(&vote_id, edited).await.unwrap();
+    assert_eq!(vote.config.max_participants, Some(2));
+    assert_eq!(vote.config.commit_end_height, 150);
+}
+
+#[tokio::test]
+async fn test_update_of_unknown_vote_is_not_found() {
+    let err = TestVoteHarness::memory_service().update_vote_config("missing", config()).await.unwrap_err();
+    assert!(matches!(err, ServiceError::NotFound), "{:?}", err);
+}
@@ -0,0 +1,58 @@
+use decentralized_decision_vote::service::{derive_vote_id, VoteService};
+use decentralized_decision_vote::model::vote::*;
+use decentralized_decision_vote::test_util::TestVoteHarness;
+use serde_json::json;
+
+fn config() -> VoteConfig {
+    VoteConfig {
+        participants: vec!["a".to_string(), "b".to_string()],
+        ..TestVoteHarness::config("Deterministic", "bit", json!({}))
+    }
+}
+
+#[tokio::test]
+async fn test_identical_config_yields_identical_id() {
+    let service = TestVoteHarness::memory_service();
+    let first = service.create_vote_with_nonce(config(), Some("n1".to_string())).await.unwrap();
+    let second = service.create_vote_with_nonce(config(), Some("n1".to_string())).await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first, derive_vote_id(&config(), "n1"));
+
+    let (_, total) = service.list_votes(0, 10).await.unwrap();
+    assert_eq!(total, 1);
+}
+
+#[tokio::test]
+async fn test_random_ids_remain_the_default() {
+    let service = TestVoteHarness::memory_service();
+    let first = service.create_vote(config()).await.unwrap();
+    let second = service.create_vote(config()).await.unwrap();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_changing_any_field_changes_the_id() {
+    let base = derive_vote_id(&config(), "n1");
+    assert_ne!(base, derive_vote_id(&config(), "n2"));
+
+    let mutations: Vec<fn(&mut VoteConfig)> = vec![
+        |c| c.title.push('!'),
+        |c| c.description = Some("desc".to_string()),
+        |c| c.options.push("x".to_string()),
+        |c| c.commit_start_height += 1,
+        |c| c.commit_end_height += 1,
+        |c| c.reveal_start_height += 1,
+        |c| c.reveal_end_height += 1,
+        |c| c.participants.push("c".to_string()),
+        |c| { c.participant_keys.insert("a".to_string(), "00".to_string()); },
+        |c| c.quorum_threshold = 0.5,
+        |c| c.non_revealer_policy = NonRevealerPolicy::Exclude,
+        |c| c.value_template = "option_index".to_string(),
+        |c| c.template_params = json!({"max": 3}),
+    ];
+    for mutate in mutations {
+        let mut cfg = config();
+        mutate(&mut cfg);
+        assert_ne!(base, derive_vote_id(&cfg, "n1"));
+    }
+}
@@ -1,11 +1,15 @@
 //! Authentication and authorization for admin API
 
 use crate::AdminError;
+use crate::mailer::PasswordResetMailer;
+use crate::password_policy::PasswordPolicy;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use shared_utils::clock::{Clock, SystemClock};
+use std::sync::{Arc, Mutex, RwLock};
 use jsonwebtoken::{encode, decode, Header, Algorithm, Validation, EncodingKey, DecodingKey};
 
 /// 用户角色
@@ -60,6 +64,20 @@ pub struct Claims {
     pub role: String,
     pub exp: usize,  // 过期时间
     pub iat: usize,  // 签发时间
+    pub jti: String, // 令牌ID，用于撤销
+}
+
+/// 密码重置令牌的 `purpose` 声明，避免与访问令牌混用
+const PASSWORD_RESET_PURPOSE: &str = "password_reset";
+
+/// 密码重置令牌声明
+#[derive(Debug, Serialize, Deserialize)]
+struct PasswordResetClaims {
+    sub: String,
+    jti: String,
+    purpose: String,
+    exp: usize,
+    iat: usize,
 }
 
 /// 登录请求
@@ -130,13 +148,97 @@ pub struct ChangePasswordRequest {
     pub new_password: String,
 }
 
+/// 申请密码重置请求
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetRequest {
+    pub username: String,
+}
+
+/// 确认密码重置请求
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetConfirmRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// 已撤销令牌列表
+///
+/// 记录每个用户已签发的令牌以便整体撤销；条目只保留到令牌本身过期为止
+#[derive(Debug, Default)]
+pub struct RevocationList {
+    /// 已撤销的jti -> 令牌过期时间
+    revoked: HashMap<String, DateTime<Utc>>,
+    /// 用户ID -> (jti -> 令牌过期时间)
+    issued: HashMap<Uuid, HashMap<String, DateTime<Utc>>>,
+}
+
+impl RevocationList {
+    /// 记录新签发的令牌
+    pub fn record_issued(&mut self, user_id: Uuid, jti: String, expires_at: DateTime<Utc>) {
+        self.issued.entry(user_id).or_default().insert(jti, expires_at);
+    }
+
+    /// 撤销单个令牌
+    pub fn revoke(&mut self, jti: &str, expires_at: DateTime<Utc>) {
+        for tokens in self.issued.values_mut() {
+            tokens.remove(jti);
+        }
+        self.revoked.insert(jti.to_string(), expires_at);
+    }
+
+    /// 撤销用户所有已签发的令牌，返回撤销数量
+    pub fn revoke_all_for_user(&mut self, user_id: Uuid) -> usize {
+        let tokens = self.issued.remove(&user_id).unwrap_or_default();
+        let count = tokens.len();
+        self.revoked.extend(tokens);
+        count
+    }
+
+    pub fn is_revoked(&self, jti: &str) -> bool {
+        self.revoked.contains_key(jti)
+    }
+
+    /// 清理已自然过期的条目
+    pub fn purge_expired(&mut self, now: DateTime<Utc>) {
+        self.revoked.retain(|_, expires_at| *expires_at > now);
+        for tokens in self.issued.values_mut() {
+            tokens.retain(|_, expires_at| *expires_at > now);
+        }
+        self.issued.retain(|_, tokens| !tokens.is_empty());
+    }
+
+    /// 仍在保留期内的已撤销令牌数量
+    pub fn len(&self) -> usize {
+        self.revoked.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.revoked.is_empty()
+    }
+}
+
+/// 用户表
+#[derive(Debug, Default)]
+struct UserDirectory {
+    users: HashMap<Uuid, User>,
+    username_to_id: HashMap<String, Uuid>,
+}
+
 /// 认证服务
 #[derive(Clone)]
 pub struct AuthService {
     jwt_secret: String,
     jwt_expiry_hours: u64,
-    users: HashMap<Uuid, User>,
-    username_to_id: HashMap<String, Uuid>,
+    /// 用户表，克隆间共享，处理器对克隆所做的修改对所有请求可见
+    directory: Arc<RwLock<UserDirectory>>,
+    clock: Arc<dyn Clock>,
+    reset_mailer: Option<Arc<dyn PasswordResetMailer>>,
+    reset_token_ttl: Duration,
+    /// 未使用的重置令牌：jti -> 用户ID，克隆间共享以保证令牌只能使用一次
+    pending_resets: Arc<Mutex<HashMap<String, Uuid>>>,
+    /// 令牌撤销列表，克隆间共享
+    revocations: Arc<Mutex<RevocationList>>,
+    password_policy: PasswordPolicy,
 }
 
 impl AuthService {
@@ -144,8 +246,13 @@ impl AuthService {
         let mut service = Self {
             jwt_secret,
             jwt_expiry_hours,
-            users: HashMap::new(),
-            username_to_id: HashMap::new(),
+            directory: Arc::new(RwLock::new(UserDirectory::default())),
+            clock: Arc::new(SystemClock),
+            reset_mailer: None,
+            reset_token_ttl: Duration::minutes(30),
+            pending_resets: Arc::new(Mutex::new(HashMap::new())),
+            revocations: Arc::new(Mutex::new(RevocationList::default())),
+            password_policy: PasswordPolicy::default(),
         };
         
         // 创建默认管理员用户
@@ -153,6 +260,25 @@ impl AuthService {
         service
     }
 
+    /// 替换时钟（用于测试锁定过期等时间相关逻辑）
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 替换密码策略
+    pub fn with_password_policy(mut self, policy: PasswordPolicy) -> Self {
+        self.password_policy = policy;
+        self
+    }
+
+    /// 启用密码重置，令牌通过 `mailer` 投递，有效期为 `ttl`
+    pub fn with_password_reset(mut self, mailer: Arc<dyn PasswordResetMailer>, ttl: Duration) -> Self {
+        self.reset_mailer = Some(mailer);
+        self.reset_token_ttl = ttl;
+        self
+    }
+
     /// 创建默认管理员用户
     fn create_default_admin(&mut self) {
         let admin_id = Uuid::new_v4();
@@ -162,53 +288,56 @@ impl AuthService {
             email: Some("admin@example.com".to_string()),
             role: Role::Admin,
             is_active: true,
-            created_at: Utc::now(),
+            created_at: self.clock.now(),
             last_login: None,
-            password_hash: self.hash_password("admin123"), // 默认密码，生产环境应该更改
+            password_hash: Self::hash_password("admin123") // 默认密码，生产环境应该更改
+                .expect("hashing the default admin password"),
             failed_login_attempts: 0,
             locked_until: None,
         };
         
-        self.users.insert(admin_id, admin_user);
-        self.username_to_id.insert("admin".to_string(), admin_id);
+        let mut directory = self.directory.write().unwrap();
+        directory.users.insert(admin_id, admin_user);
+        directory.username_to_id.insert("admin".to_string(), admin_id);
     }
 
     /// 用户登录
     pub async fn login(&mut self, request: LoginRequest) -> Result<LoginResponse, AdminError> {
-        // 查找用户
-        let user_id = self.username_to_id.get(&request.username)
-            .ok_or_else(|| AdminError::Authentication("Invalid username or password".to_string()))?;
-        
-        // 先获取用户信息进行密码验证
-        let user_info = self.users.get(user_id)
-            .ok_or_else(|| AdminError::Authentication("User not found".to_string()))?;
+        // 查找用户，复制所需字段后释放读锁，哈希校验期间不阻塞其他请求
+        let (user_id, locked_until, password_hash) = {
+            let directory = self.directory.read().unwrap();
+            let user_id = *directory.username_to_id.get(&request.username)
+                .ok_or_else(|| AdminError::Authentication("Invalid username or password".to_string()))?;
+            let user_info = directory.users.get(&user_id)
+                .ok_or_else(|| AdminError::Authentication("User not found".to_string()))?;
+            (user_id, user_info.locked_until, user_info.password_hash.clone())
+        };
 
         // 检查用户是否被锁定
-        if let Some(locked_until) = user_info.locked_until {
-            if Utc::now() < locked_until {
+        if let Some(locked_until) = locked_until {
+            if self.clock.now() < locked_until {
                 return Err(AdminError::Authentication("Account is locked".to_string()));
             }
         }
 
         // 验证密码
-        let password_valid = self.verify_password(&request.password, &user_info.password_hash);
+        let password_valid = self.verify_password(&request.password, &password_hash);
         
+        let mut directory = self.directory.write().unwrap();
+        let user = directory.users.get_mut(&user_id)
+            .ok_or_else(|| AdminError::Authentication("User not found".to_string()))?;
+
         if !password_valid {
-            // 获取可变引用进行失败计数更新
-            let user = self.users.get_mut(user_id).unwrap();
             user.failed_login_attempts += 1;
             
             // 检查是否需要锁定账户
             if user.failed_login_attempts >= 5 {
-                user.locked_until = Some(Utc::now() + chrono::Duration::minutes(15));
+                user.locked_until = Some(self.clock.now() + chrono::Duration::minutes(15));
             }
             
             return Err(AdminError::Authentication("Invalid username or password".to_string()));
         }
 
-        // 获取可变引用进行成功登录更新
-        let user = self.users.get_mut(user_id).unwrap();
-        
         // 解锁账户（如果之前被锁定）
         if user.locked_until.is_some() {
             user.locked_until = None;
@@ -217,16 +346,25 @@ impl AuthService {
 
         // 重置失败次数
         user.failed_login_attempts = 0;
-        user.last_login = Some(Utc::now());
+        user.last_login = Some(self.clock.now());
 
         // 创建用户副本用于生成令牌
         let user_for_token = user.clone();
+        drop(directory);
         let jwt_secret = self.jwt_secret.clone();
         let jwt_expiry_hours = self.jwt_expiry_hours;
+        let now = self.clock.now();
 
         // 生成JWT令牌
-        let access_token = AuthService::generate_access_token_static(&user_for_token, &jwt_secret, jwt_expiry_hours)?;
-        let refresh_token = AuthService::generate_refresh_token_static(&user_for_token, &jwt_secret, jwt_expiry_hours)?;
+        let (access_token, access_jti) = AuthService::generate_access_token_static(&user_for_token, &jwt_secret, jwt_expiry_hours, now)?;
+        let (refresh_token, refresh_jti) = AuthService::generate_refresh_token_static(&user_for_token, &jwt_secret, jwt_expiry_hours, now)?;
+
+        let expires_at = now + chrono::Duration::hours(jwt_expiry_hours as i64);
+        let mut revocations = self.revocations.lock().unwrap();
+        revocations.purge_expired(now);
+        revocations.record_issued(user_for_token.id, access_jti, expires_at);
+        revocations.record_issued(user_for_token.id, refresh_jti, expires_at);
+        drop(revocations);
 
         Ok(LoginResponse {
             access_token,
@@ -245,21 +383,45 @@ impl AuthService {
             &Validation::new(Algorithm::HS256),
         ).map_err(|_| AdminError::Authentication("Invalid token".to_string()))?;
 
+        if self.revocations.lock().unwrap().is_revoked(&token_data.claims.jti) {
+            return Err(AdminError::Authentication("Token has been revoked".to_string()));
+        }
+
         Ok(token_data.claims)
     }
 
-    /// 创建用户
-    pub async fn create_user(&mut self, request: CreateUserRequest) -> Result<UserInfo, AdminError> {
+    /// 撤销单个令牌（登出）
+    pub fn revoke_token(&self, token: &str) -> Result<(), AdminError> {
+        let claims = self.verify_token(token)?;
+        let expires_at = DateTime::<Utc>::from_timestamp(claims.exp as i64, 0)
+            .ok_or_else(|| AdminError::Authentication("Invalid token".to_string()))?;
+        self.revocations.lock().unwrap().revoke(&claims.jti, expires_at);
+        Ok(())
+    }
+
+    /// 撤销用户所有已签发的令牌，返回撤销数量
+    pub fn revoke_all_for_user(&self, user_id: Uuid) -> usize {
+        self.revocations.lock().unwrap().revoke_all_for_user(user_id)
+    }
+
+    /// 检查新用户请求是否可以创建（用户名未被占用且密码足够强）
+    pub fn check_new_user(&self, request: &CreateUserRequest) -> Result<(), AdminError> {
         // 检查用户名是否已存在
-        if self.username_to_id.contains_key(&request.username) {
+        if self.directory.read().unwrap().username_to_id.contains_key(&request.username) {
             return Err(AdminError::Validation("Username already exists".to_string()));
         }
 
         // 验证密码强度
-        self.validate_password(&request.password)?;
+        self.validate_password(&request.password)
+    }
+
+    /// 创建用户
+    pub async fn create_user(&mut self, request: CreateUserRequest) -> Result<UserInfo, AdminError> {
+        self.check_new_user(&request)?;
 
         let user_id = Uuid::new_v4();
         let role = Role::from_string(&request.role);
+        let password_hash = Self::hash_password(&request.password)?;
         
         let user = User {
             id: user_id,
@@ -267,32 +429,39 @@ impl AuthService {
             email: request.email,
             role,
             is_active: true,
-            created_at: Utc::now(),
+            created_at: self.clock.now(),
             last_login: None,
-            password_hash: self.hash_password(&request.password),
+            password_hash,
             failed_login_attempts: 0,
             locked_until: None,
         };
 
-        self.users.insert(user_id, user.clone());
-        self.username_to_id.insert(request.username, user_id);
+        // 哈希期间可能有同名用户被并发创建，持写锁时再检查一次
+        let mut directory = self.directory.write().unwrap();
+        if directory.username_to_id.contains_key(&request.username) {
+            return Err(AdminError::Validation("Username already exists".to_string()));
+        }
+        directory.users.insert(user_id, user.clone());
+        directory.username_to_id.insert(request.username, user_id);
 
         Ok(UserInfo::from(user))
     }
 
     /// 更新用户
     pub async fn update_user(&mut self, user_id: Uuid, request: UpdateUserRequest) -> Result<UserInfo, AdminError> {
-        let user = self.users.get_mut(&user_id)
+        let mut guard = self.directory.write().unwrap();
+        let directory = &mut *guard;
+        let user = directory.users.get_mut(&user_id)
             .ok_or_else(|| AdminError::NotFound("User not found".to_string()))?;
 
         if let Some(username) = request.username {
-            if username != user.username && self.username_to_id.contains_key(&username) {
+            if username != user.username && directory.username_to_id.contains_key(&username) {
                 return Err(AdminError::Validation("Username already exists".to_string()));
             }
             
             // 更新用户名映射
-            self.username_to_id.remove(&user.username);
-            self.username_to_id.insert(username.clone(), user_id);
+            directory.username_to_id.remove(&user.username);
+            directory.username_to_id.insert(username.clone(), user_id);
             user.username = username;
         }
 
@@ -308,17 +477,25 @@ impl AuthService {
             user.is_active = is_active;
         }
 
-        Ok(UserInfo::from(user.clone()))
+        let info = UserInfo::from(user.clone());
+        drop(guard);
+        // 禁用用户时使其已签发的令牌立即失效
+        if !info.is_active {
+            self.revoke_all_for_user(user_id);
+        }
+
+        Ok(info)
     }
 
     /// 更改密码
     pub async fn change_password(&mut self, user_id: Uuid, request: ChangePasswordRequest) -> Result<(), AdminError> {
         // 先获取用户信息进行密码验证
-        let user_info = self.users.get(&user_id)
+        let password_hash = self.directory.read().unwrap().users.get(&user_id)
+            .map(|user| user.password_hash.clone())
             .ok_or_else(|| AdminError::NotFound("User not found".to_string()))?;
 
         // 验证当前密码
-        let current_password_valid = self.verify_password(&request.current_password, &user_info.password_hash);
+        let current_password_valid = self.verify_password(&request.current_password, &password_hash);
         if !current_password_valid {
             return Err(AdminError::Authentication("Current password is incorrect".to_string()));
         }
@@ -327,41 +504,132 @@ impl AuthService {
         self.validate_password(&request.new_password)?;
 
         // 生成新密码哈希
-        let jwt_secret = self.jwt_secret.clone();
-        let new_password_hash = AuthService::hash_password_static(&request.new_password, &jwt_secret);
+        let new_password_hash = Self::hash_password(&request.new_password)?;
 
-        // 获取可变引用进行密码更新
-        let user = self.users.get_mut(&user_id).unwrap();
+        let mut directory = self.directory.write().unwrap();
+        let user = directory.users.get_mut(&user_id)
+            .ok_or_else(|| AdminError::NotFound("User not found".to_string()))?;
         user.password_hash = new_password_hash;
 
         Ok(())
     }
 
+    /// 申请密码重置
+    ///
+    /// 用户不存在或未设置邮箱时静默返回，避免暴露账户是否存在
+    pub async fn request_password_reset(&mut self, username: &str) -> Result<(), AdminError> {
+        let mailer = self.reset_mailer.clone()
+            .ok_or_else(|| AdminError::Configuration("Password reset delivery is not configured".to_string()))?;
+
+        let recipient = {
+            let directory = self.directory.read().unwrap();
+            directory.username_to_id.get(username)
+                .and_then(|id| directory.users.get(id))
+                .map(|user| (user.id, user.email.clone()))
+        };
+        let Some((user_id, email)) = recipient else {
+            tracing::info!("Password reset requested for unknown user {}", username);
+            return Ok(());
+        };
+        let Some(email) = email else {
+            tracing::warn!("Password reset requested for {} but no email is on file", username);
+            return Ok(());
+        };
+
+        let issued_at = self.clock.now();
+        let expires_at = issued_at + self.reset_token_ttl;
+        let jti = Uuid::new_v4().to_string();
+        let claims = PasswordResetClaims {
+            sub: user_id.to_string(),
+            jti: jti.clone(),
+            purpose: PASSWORD_RESET_PURPOSE.to_string(),
+            exp: expires_at.timestamp() as usize,
+            iat: issued_at.timestamp() as usize,
+        };
+        let token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_ref()),
+        ).map_err(|e| AdminError::Internal(format!("Failed to generate reset token: {}", e)))?;
+
+        self.pending_resets.lock().unwrap().insert(jti.clone(), user_id);
+        if let Err(e) = mailer.send_reset(&email, username, &token, expires_at).await {
+            self.pending_resets.lock().unwrap().remove(&jti);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// 使用重置令牌设置新密码，令牌使用后立即失效
+    pub async fn reset_password(&mut self, token: &str, new_password: &str) -> Result<(), AdminError> {
+        let invalid = || AdminError::Authentication("Invalid reset token".to_string());
+
+        // 过期时间按注入的时钟判断
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_exp = false;
+        let claims = decode::<PasswordResetClaims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
+            &validation,
+        ).map_err(|_| invalid())?.claims;
+        if claims.purpose != PASSWORD_RESET_PURPOSE {
+            return Err(invalid());
+        }
+
+        if self.clock.now().timestamp() as usize >= claims.exp {
+            self.pending_resets.lock().unwrap().remove(&claims.jti);
+            return Err(AdminError::Authentication("Reset token has expired".to_string()));
+        }
+
+        let user_id = self.pending_resets.lock().unwrap().get(&claims.jti)
+            .copied()
+            .filter(|id| id.to_string() == claims.sub)
+            .ok_or_else(|| AdminError::Authentication("Reset token has already been used".to_string()))?;
+
+        self.validate_password(new_password)?;
+        let password_hash = Self::hash_password(new_password)?;
+
+        let mut directory = self.directory.write().unwrap();
+        let user = directory.users.get_mut(&user_id)
+            .ok_or_else(|| AdminError::NotFound("User not found".to_string()))?;
+        user.password_hash = password_hash;
+        user.failed_login_attempts = 0;
+        user.locked_until = None;
+        drop(directory);
+
+        // 同一用户之前申请的令牌一并作废
+        self.pending_resets.lock().unwrap().retain(|_, id| *id != user_id);
+        Ok(())
+    }
+
     /// 获取用户信息
     pub fn get_user(&self, user_id: Uuid) -> Option<UserInfo> {
-        self.users.get(&user_id).map(|user| UserInfo::from(user.clone()))
+        self.directory.read().unwrap().users.get(&user_id).map(|user| UserInfo::from(user.clone()))
     }
 
     /// 获取所有用户
     pub fn get_all_users(&self) -> Vec<UserInfo> {
-        self.users.values().map(|user| UserInfo::from(user.clone())).collect()
+        self.directory.read().unwrap().users.values().map(|user| UserInfo::from(user.clone())).collect()
     }
 
     /// 删除用户
     pub async fn delete_user(&mut self, user_id: Uuid) -> Result<(), AdminError> {
-        if let Some(user) = self.users.remove(&user_id) {
-            self.username_to_id.remove(&user.username);
-            Ok(())
-        } else {
-            Err(AdminError::NotFound("User not found".to_string()))
-        }
+        let mut directory = self.directory.write().unwrap();
+        let user = directory.users.remove(&user_id)
+            .ok_or_else(|| AdminError::NotFound("User not found".to_string()))?;
+        directory.username_to_id.remove(&user.username);
+        drop(directory);
+
+        self.revoke_all_for_user(user_id);
+        Ok(())
     }
 
 
     /// 生成访问令牌（静态方法）
-    fn generate_access_token_static(user: &User, jwt_secret: &str, jwt_expiry_hours: u64) -> Result<String, AdminError> {
-        let now = Utc::now().timestamp() as usize;
+    fn generate_access_token_static(user: &User, jwt_secret: &str, jwt_expiry_hours: u64, issued_at: DateTime<Utc>) -> Result<(String, String), AdminError> {
+        let now = issued_at.timestamp() as usize;
         let exp = now + (jwt_expiry_hours * 3600) as usize;
+        let jti = Uuid::new_v4().to_string();
 
         let claims = Claims {
             sub: user.id.to_string(),
@@ -369,63 +637,56 @@ impl AuthService {
             role: user.role.as_str().to_string(),
             exp,
             iat: now,
+            jti: jti.clone(),
         };
 
         encode(
             &Header::default(),
             &claims,
             &EncodingKey::from_secret(jwt_secret.as_ref()),
-        ).map_err(|e| AdminError::Internal(format!("Failed to generate token: {}", e)))
+        )
+        .map(|token| (token, jti))
+        .map_err(|e| AdminError::Internal(format!("Failed to generate token: {}", e)))
     }
 
     /// 生成刷新令牌（静态方法）
-    fn generate_refresh_token_static(user: &User, jwt_secret: &str, jwt_expiry_hours: u64) -> Result<String, AdminError> {
+    fn generate_refresh_token_static(user: &User, jwt_secret: &str, jwt_expiry_hours: u64, issued_at: DateTime<Utc>) -> Result<(String, String), AdminError> {
         // 简化实现，实际应用中应该使用更安全的刷新令牌机制
-        Self::generate_access_token_static(user, jwt_secret, jwt_expiry_hours)
+        Self::generate_access_token_static(user, jwt_secret, jwt_expiry_hours, issued_at)
     }
 
-    /// 哈希密码
-    fn hash_password(&self, password: &str) -> String {
-        // 简化实现，实际应用中应该使用更安全的密码哈希算法
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        hasher.update(password.as_bytes());
-        hasher.update(self.jwt_secret.as_bytes());
-        format!("{:x}", hasher.finalize())
+    /// 哈希密码（Argon2），创建用户、修改密码与重置密码共用
+    fn hash_password(password: &str) -> Result<String, AdminError> {
+        use argon2::password_hash::{rand_core::OsRng, PasswordHasher, SaltString};
+        let salt = SaltString::generate(&mut OsRng);
+        argon2::Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| AdminError::Internal(format!("Failed to hash password: {}", e)))
     }
 
-    /// 哈希密码（静态方法）
-    fn hash_password_static(password: &str, jwt_secret: &str) -> String {
-        // 简化实现，实际应用中应该使用更安全的密码哈希算法
+    /// 旧版SHA-256密码哈希，仅用于校验升级前保存的密码
+    fn legacy_hash_password(&self, password: &str) -> String {
         use sha2::{Sha256, Digest};
         let mut hasher = Sha256::new();
         hasher.update(password.as_bytes());
-        hasher.update(jwt_secret.as_bytes());
+        hasher.update(self.jwt_secret.as_bytes());
         format!("{:x}", hasher.finalize())
     }
 
-    /// 验证密码
+    /// 验证密码，兼容Argon2哈希与旧的SHA-256哈希
     fn verify_password(&self, password: &str, hash: &str) -> bool {
-        self.hash_password(password) == *hash
+        if hash.starts_with("$argon2") {
+            use argon2::password_hash::{PasswordHash, PasswordVerifier};
+            return PasswordHash::new(hash)
+                .map(|parsed| argon2::Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+                .unwrap_or(false);
+        }
+        self.legacy_hash_password(password) == *hash
     }
 
-    /// 验证密码强度
+    /// 按密码策略校验密码
     fn validate_password(&self, password: &str) -> Result<(), AdminError> {
-        if password.len() < 8 {
-            return Err(AdminError::Validation("Password must be at least 8 characters long".to_string()));
-        }
-
-        let has_uppercase = password.chars().any(|c| c.is_uppercase());
-        let has_lowercase = password.chars().any(|c| c.is_lowercase());
-        let has_digit = password.chars().any(|c| c.is_ascii_digit());
-        let has_special = password.chars().any(|c| "!@#$%^&*()_+-=[]{}|;:,.<>?".contains(c));
-
-        if !has_uppercase || !has_lowercase || !has_digit || !has_special {
-            return Err(AdminError::Validation(
-                "Password must contain at least one uppercase letter, one lowercase letter, one digit, and one special character".to_string()
-            ));
-        }
-
-        Ok(())
+        self.password_policy.validate(password)
     }
 }
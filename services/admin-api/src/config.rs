@@ -1,5 +1,6 @@
 //! Admin API Configuration
 
+use crate::password_policy::PasswordPolicy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -50,7 +51,11 @@ impl Default for ServerConfig {
 }
 
 /// 认证配置
+///
+/// 拒绝未知字段：已移入 `password_policy` 的 `min_password_length` 与 `password_complexity`
+/// 若仍留在配置中会导致加载失败，而不是被静默忽略
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AuthConfig {
     /// JWT密钥
     pub jwt_secret: String,
@@ -58,34 +63,30 @@ pub struct AuthConfig {
     pub jwt_expiry_hours: u64,
     /// 刷新令牌过期时间（天）
     pub refresh_token_expiry_days: u64,
-    /// 密码最小长度
-    pub min_password_length: usize,
-    /// 密码复杂度要求
-    pub password_complexity: PasswordComplexityConfig,
+    /// 密码策略
+    #[serde(default)]
+    pub password_policy: PasswordPolicy,
     /// 登录失败锁定配置
     pub lockout: LockoutConfig,
+    /// 密码重置配置
+    #[serde(default)]
+    pub password_reset: PasswordResetConfig,
 }
 
-/// 密码复杂度配置
+/// 密码重置配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PasswordComplexityConfig {
-    /// 需要大写字母
-    pub require_uppercase: bool,
-    /// 需要小写字母
-    pub require_lowercase: bool,
-    /// 需要数字
-    pub require_numbers: bool,
-    /// 需要特殊字符
-    pub require_special_chars: bool,
+pub struct PasswordResetConfig {
+    /// 重置令牌有效期（分钟）
+    pub token_ttl_minutes: u64,
+    /// 通知服务地址，用于发送重置邮件；未配置时无法申请重置
+    pub notification_url: Option<String>,
 }
 
-impl Default for PasswordComplexityConfig {
+impl Default for PasswordResetConfig {
     fn default() -> Self {
         Self {
-            require_uppercase: true,
-            require_lowercase: true,
-            require_numbers: true,
-            require_special_chars: true,
+            token_ttl_minutes: 30,
+            notification_url: None,
         }
     }
 }
@@ -117,9 +118,9 @@ impl Default for AuthConfig {
             jwt_secret: "your-secret-key-here".to_string(),
             jwt_expiry_hours: 24,
             refresh_token_expiry_days: 7,
-            min_password_length: 8,
-            password_complexity: PasswordComplexityConfig::default(),
+            password_policy: PasswordPolicy::default(),
             lockout: LockoutConfig::default(),
+            password_reset: PasswordResetConfig::default(),
         }
     }
 }
@@ -151,6 +152,9 @@ impl Default for PermissionsConfig {
             "view_logs".to_string(),
             "manage_permissions".to_string(),
             "view_statistics".to_string(),
+            "manage_session_phase".to_string(),
+            "recompute_results".to_string(),
+            "manage_legal_hold".to_string(),
         ]);
         default_roles.insert("moderator".to_string(), vec![
             "view_session".to_string(),
@@ -346,6 +350,13 @@ pub struct MonitoringConfig {
     pub metrics_path: String,
     /// 是否启用性能监控
     pub performance_monitoring: bool,
+    /// 慢请求阈值（毫秒），超过时记录警告
+    #[serde(default = "default_slow_request_threshold_ms")]
+    pub slow_request_threshold_ms: u64,
+}
+
+fn default_slow_request_threshold_ms() -> u64 {
+    1000
 }
 
 impl Default for MonitoringConfig {
@@ -356,6 +367,7 @@ impl Default for MonitoringConfig {
             metrics: true,
             metrics_path: "/metrics".to_string(),
             performance_monitoring: true,
+            slow_request_threshold_ms: default_slow_request_threshold_ms(),
         }
     }
 }
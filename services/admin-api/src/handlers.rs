@@ -1,19 +1,23 @@
 //! HTTP handlers for admin API
 
 use crate::{
-    OperationResult, SystemStatistics, 
-    SessionManagementInfo, ConfigManagementInfo, LogEntry,
-    auth::{LoginRequest, LoginResponse, CreateUserRequest, UpdateUserRequest, ChangePasswordRequest, UserInfo},
-    middleware::AuthMiddlewareState,
+    AdminOperation, OperationResult, SystemStatistics, 
+    SessionManagementInfo, SessionPhaseInfo, SessionLegalHold, ConfigManagementInfo, LogEntry,
+    auth::{LoginRequest, LoginResponse, CreateUserRequest, UpdateUserRequest, ChangePasswordRequest, PasswordResetRequest, PasswordResetConfirmRequest, UserInfo},
+    middleware::{authenticate, authorize, with_panic_recovery, AuthMiddlewareState},
+    import::{UserImportParams, UserImportReport},
 };
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
     routing::{get, post, put},
     Router,
 };
 use serde::Deserialize;
+use shared_types::{ApiError, Paginated, SessionId};
+use std::collections::HashMap;
+use vote_engine::ResultsRecomputation;
 use tracing::{info, warn, error};
 use uuid::Uuid;
 
@@ -27,12 +31,22 @@ pub struct PaginationParams {
 impl Default for PaginationParams {
     fn default() -> Self {
         Self {
-            page: Some(1),
+            page: Some(0),
             limit: Some(20),
         }
     }
 }
 
+impl PaginationParams {
+    /// 从完整列表中截取当前页，页码从0开始
+    pub fn paginate<T>(&self, all: Vec<T>) -> Paginated<T> {
+        let defaults = Self::default();
+        let page = self.page.or(defaults.page).unwrap_or(0);
+        let limit = self.limit.or(defaults.limit).unwrap_or(20);
+        Paginated::from_all(all, page, limit)
+    }
+}
+
 /// 会话查询参数
 #[derive(Debug, Deserialize)]
 pub struct SessionQueryParams {
@@ -51,23 +65,41 @@ pub struct UserQueryParams {
     pub created_before: Option<String>,
 }
 
+/// 重新计算结果的参数
+#[derive(Debug, Default, Deserialize)]
+pub struct RecomputeResultsParams {
+    /// 结果会发生变化时必须显式确认才会写入
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// 设置法律保留的请求体
+#[derive(Debug, Deserialize)]
+pub struct LegalHoldRequest {
+    /// `true` 设置保留，`false` 解除保留
+    pub hold: bool,
+}
+
 /// 日志查询参数
 #[derive(Debug, Deserialize)]
 pub struct LogQueryParams {
     pub level: Option<String>,
     pub source: Option<String>,
     pub user_id: Option<Uuid>,
-    pub session_id: Option<String>,
+    pub session_id: Option<SessionId>,
     pub start_time: Option<String>,
     pub end_time: Option<String>,
 }
 
 /// 创建HTTP路由
 pub fn create_http_router(state: AuthMiddlewareState) -> Router {
-    Router::new()
+    let router = Router::new()
         // 认证相关路由（不需要认证）
         .route("/auth/login", post(login))
         .route("/auth/refresh", post(refresh_token))
+        .route("/auth/logout", post(logout))
+        .route("/auth/password-reset", post(request_password_reset))
+        .route("/auth/password-reset/confirm", post(confirm_password_reset))
         
         // 健康检查和状态
         .route("/health", get(health_check))
@@ -76,6 +108,7 @@ pub fn create_http_router(state: AuthMiddlewareState) -> Router {
         
         // 用户管理（需要认证和权限）
         .route("/users", get(list_users).post(create_user))
+        .route("/users/import", post(import_users))
         .route("/users/:id", get(get_user).put(update_user).delete(delete_user))
         .route("/users/:id/password", put(change_password))
         .route("/users/:id/roles", get(get_user_roles).post(assign_role).delete(remove_role))
@@ -83,6 +116,9 @@ pub fn create_http_router(state: AuthMiddlewareState) -> Router {
         // 会话管理
         .route("/sessions", get(list_sessions))
         .route("/sessions/:id", get(get_session).delete(delete_session))
+        .route("/sessions/:id/advance", post(advance_session))
+        .route("/sessions/:id/recompute-results", post(recompute_results))
+        .route("/sessions/:id/legal-hold", put(set_legal_hold))
         
         // 配置管理
         .route("/config", get(get_config).put(update_config))
@@ -97,14 +133,16 @@ pub fn create_http_router(state: AuthMiddlewareState) -> Router {
         .route("/roles/:name", get(get_role).put(update_role).delete(delete_role))
         .route("/permissions", get(list_permissions))
         
-        .with_state(state)
+        .with_state(state);
+
+    with_panic_recovery(router)
 }
 
 /// 用户登录
 async fn login(
     State(state): State<AuthMiddlewareState>,
     Json(request): Json<LoginRequest>,
-) -> Result<Json<LoginResponse>, StatusCode> {
+) -> Result<Json<LoginResponse>, ApiError> {
     let username = request.username.clone();
     info!("Login attempt for user: {}", username);
     
@@ -116,18 +154,73 @@ async fn login(
         }
         Err(e) => {
             warn!("Login failed for user {}: {}", username, e);
-            Err(StatusCode::UNAUTHORIZED)
+            Err(e.into())
         }
     }
 }
 
+/// 登出，撤销当前请求携带的令牌
+async fn logout(
+    State(state): State<AuthMiddlewareState>,
+    headers: HeaderMap,
+) -> Result<Json<OperationResult>, ApiError> {
+    let user = authenticate(&state, &headers)?;
+    let token = headers
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    state.auth_service.revoke_token(token)?;
+
+    info!("User {} logged out", user.username);
+    Ok(Json(OperationResult::success("Logged out".to_string(), None)))
+}
+
 /// 刷新令牌
 async fn refresh_token(
     State(_state): State<AuthMiddlewareState>,
     Json(_request): Json<serde_json::Value>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // 简化实现，实际应用中应该验证刷新令牌
-    Err(StatusCode::NOT_IMPLEMENTED)
+    Err(ApiError::new(501, "auth.refresh_unsupported", "Token refresh is not implemented"))
+}
+
+/// 申请密码重置，无论用户是否存在均返回202
+async fn request_password_reset(
+    State(state): State<AuthMiddlewareState>,
+    Json(request): Json<PasswordResetRequest>,
+) -> Result<(StatusCode, Json<OperationResult>), ApiError> {
+    let mut auth_service = (*state.auth_service).clone();
+    auth_service.request_password_reset(&request.username).await.map_err(|e| {
+        error!("Failed to issue password reset for {}: {}", request.username, e);
+        ApiError::from(e)
+    })?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(OperationResult::success(
+            "If the account exists, a password reset email has been sent".to_string(),
+            None,
+        )),
+    ))
+}
+
+/// 使用重置令牌设置新密码
+async fn confirm_password_reset(
+    State(state): State<AuthMiddlewareState>,
+    Json(request): Json<PasswordResetConfirmRequest>,
+) -> Result<Json<OperationResult>, ApiError> {
+    let mut auth_service = (*state.auth_service).clone();
+    match auth_service.reset_password(&request.token, &request.new_password).await {
+        Ok(()) => {
+            info!("Password reset completed");
+            Ok(Json(OperationResult::success("Password has been reset".to_string(), None)))
+        }
+        Err(e) => {
+            warn!("Password reset rejected: {}", e);
+            Err(e.into())
+        }
+    }
 }
 
 /// 健康检查
@@ -142,7 +235,7 @@ async fn health_check() -> Json<serde_json::Value> {
 /// 获取系统状态
 async fn get_system_status(
     State(_state): State<AuthMiddlewareState>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // 这里应该从实际的服务获取状态信息
     let status = serde_json::json!({
         "status": "running",
@@ -157,7 +250,7 @@ async fn get_system_status(
 /// 获取系统统计信息
 async fn get_statistics(
     State(_state): State<AuthMiddlewareState>,
-) -> Result<Json<SystemStatistics>, StatusCode> {
+) -> Result<Json<SystemStatistics>, ApiError> {
     // 这里应该从实际的服务获取统计信息
     let stats = SystemStatistics {
         total_sessions: 100,
@@ -185,18 +278,19 @@ async fn get_statistics(
 async fn list_users(
     State(state): State<AuthMiddlewareState>,
     Query(_params): Query<UserQueryParams>,
-    Query(_pagination): Query<PaginationParams>,
-) -> Result<Json<Vec<UserInfo>>, StatusCode> {
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<Paginated<UserInfo>>, ApiError> {
     // 这里应该从数据库获取用户列表
-    let users = state.auth_service.get_all_users();
-    Ok(Json(users))
+    let mut users = state.auth_service.get_all_users();
+    users.sort_by(|a, b| a.username.cmp(&b.username));
+    Ok(Json(pagination.paginate(users)))
 }
 
 /// 创建用户
 async fn create_user(
     State(state): State<AuthMiddlewareState>,
     Json(request): Json<CreateUserRequest>,
-) -> Result<Json<UserInfo>, StatusCode> {
+) -> Result<Json<UserInfo>, ApiError> {
     info!("Creating user: {}", request.username);
     
     let mut auth_service = (*state.auth_service).clone();
@@ -207,19 +301,42 @@ async fn create_user(
         }
         Err(e) => {
             error!("Failed to create user: {}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(e.into())
         }
     }
 }
 
+/// 从CSV批量导入用户，逐行返回结果
+async fn import_users(
+    State(state): State<AuthMiddlewareState>,
+    headers: HeaderMap,
+    Query(params): Query<UserImportParams>,
+    body: String,
+) -> Result<Json<UserImportReport>, ApiError> {
+    let user = authenticate(&state, &headers)?;
+    authorize(&state, &user, &AdminOperation::CreateUser)?;
+
+    let known_roles = state.permission_manager.lock()
+        .map_err(|_| ApiError::internal("Permission manager unavailable"))?
+        .get_all_roles();
+    let mut auth_service = (*state.auth_service).clone();
+    let report = crate::import::import_users(&mut auth_service, &body, &known_roles, params.dry_run).await;
+
+    info!(
+        "User {} imported users (dry_run: {}): {} succeeded, {} failed",
+        user.username, report.dry_run, report.succeeded, report.failed
+    );
+    Ok(Json(report))
+}
+
 /// 获取用户信息
 async fn get_user(
     State(state): State<AuthMiddlewareState>,
     Path(user_id): Path<Uuid>,
-) -> Result<Json<UserInfo>, StatusCode> {
+) -> Result<Json<UserInfo>, ApiError> {
     match state.auth_service.get_user(user_id) {
         Some(user) => Ok(Json(user)),
-        None => Err(StatusCode::NOT_FOUND),
+        None => Err(ApiError::not_found("user.not_found", format!("User not found: {}", user_id))),
     }
 }
 
@@ -228,7 +345,7 @@ async fn update_user(
     State(state): State<AuthMiddlewareState>,
     Path(user_id): Path<Uuid>,
     Json(request): Json<UpdateUserRequest>,
-) -> Result<Json<UserInfo>, StatusCode> {
+) -> Result<Json<UserInfo>, ApiError> {
     info!("Updating user: {}", user_id);
     
     let mut auth_service = (*state.auth_service).clone();
@@ -239,7 +356,7 @@ async fn update_user(
         }
         Err(e) => {
             error!("Failed to update user: {}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(e.into())
         }
     }
 }
@@ -248,7 +365,7 @@ async fn update_user(
 async fn delete_user(
     State(state): State<AuthMiddlewareState>,
     Path(user_id): Path<Uuid>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     info!("Deleting user: {}", user_id);
     
     let mut auth_service = (*state.auth_service).clone();
@@ -262,7 +379,7 @@ async fn delete_user(
         }
         Err(e) => {
             error!("Failed to delete user: {}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(e.into())
         }
     }
 }
@@ -272,7 +389,7 @@ async fn change_password(
     State(state): State<AuthMiddlewareState>,
     Path(user_id): Path<Uuid>,
     Json(request): Json<ChangePasswordRequest>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     info!("Changing password for user: {}", user_id);
     
     let mut auth_service = (*state.auth_service).clone();
@@ -286,7 +403,7 @@ async fn change_password(
         }
         Err(e) => {
             error!("Failed to change password: {}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(e.into())
         }
     }
 }
@@ -295,7 +412,7 @@ async fn change_password(
 async fn get_user_roles(
     State(_state): State<AuthMiddlewareState>,
     Path(_user_id): Path<Uuid>,
-) -> Result<Json<Vec<String>>, StatusCode> {
+) -> Result<Json<Vec<String>>, ApiError> {
     // 这里应该从数据库获取用户角色
     let roles = vec!["admin".to_string()];
     Ok(Json(roles))
@@ -306,7 +423,7 @@ async fn assign_role(
     State(_state): State<AuthMiddlewareState>,
     Path(_user_id): Path<Uuid>,
     Json(_request): Json<serde_json::Value>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Role assigned successfully".to_string(),
@@ -319,7 +436,7 @@ async fn remove_role(
     State(_state): State<AuthMiddlewareState>,
     Path(_user_id): Path<Uuid>,
     Path(_role): Path<String>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Role removed successfully".to_string(),
@@ -331,27 +448,27 @@ async fn remove_role(
 async fn list_sessions(
     State(_state): State<AuthMiddlewareState>,
     Query(_params): Query<SessionQueryParams>,
-    Query(_pagination): Query<PaginationParams>,
-) -> Result<Json<Vec<SessionManagementInfo>>, StatusCode> {
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<Paginated<SessionManagementInfo>>, ApiError> {
     // 这里应该从数据库获取会话列表
     let sessions = vec![];
-    Ok(Json(sessions))
+    Ok(Json(pagination.paginate(sessions)))
 }
 
 /// 获取会话信息
 async fn get_session(
     State(_state): State<AuthMiddlewareState>,
-    Path(_session_id): Path<String>,
-) -> Result<Json<SessionManagementInfo>, StatusCode> {
+    Path(session_id): Path<SessionId>,
+) -> Result<Json<SessionManagementInfo>, ApiError> {
     // 这里应该从数据库获取会话信息
-    Err(StatusCode::NOT_FOUND)
+    Err(ApiError::not_found("session.not_found", format!("Session not found: {}", session_id)))
 }
 
 /// 删除会话
 async fn delete_session(
     State(_state): State<AuthMiddlewareState>,
-    Path(_session_id): Path<String>,
-) -> Result<Json<OperationResult>, StatusCode> {
+    Path(_session_id): Path<SessionId>,
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Session deleted successfully".to_string(),
@@ -359,10 +476,99 @@ async fn delete_session(
     )))
 }
 
+/// 将会话推进到下一阶段，经过与自动转换相同的状态机校验
+async fn advance_session(
+    State(state): State<AuthMiddlewareState>,
+    headers: HeaderMap,
+    Path(session_id): Path<SessionId>,
+) -> Result<Json<SessionPhaseInfo>, ApiError> {
+    let user = authenticate(&state, &headers)?;
+    authorize(&state, &user, &AdminOperation::ManageSessionPhase)?;
+
+    let vote_engine = state.vote_engine.as_ref()
+        .ok_or_else(|| ApiError::new(503, "session.phase_unavailable", "Session phase management is not configured"))?;
+    let status = vote_engine.advance_phase(&session_id).await?;
+
+    info!("User {} advanced session {} to {:?}", user.username, session_id, status);
+    Ok(Json(SessionPhaseInfo { session_id, status }))
+}
+
+/// 用当前的揭示重新计算已完成会话的结果
+///
+/// 结果不变时直接返回；结果会变化时需要 `confirm=true` 才会写入，写入后记录包含前后结果的审计事件
+async fn recompute_results(
+    State(state): State<AuthMiddlewareState>,
+    headers: HeaderMap,
+    Path(session_id): Path<SessionId>,
+    Query(params): Query<RecomputeResultsParams>,
+) -> Result<Json<ResultsRecomputation>, ApiError> {
+    let user = authenticate(&state, &headers)?;
+    authorize(&state, &user, &AdminOperation::RecomputeResults)?;
+
+    let vote_engine = state.vote_engine.as_ref()
+        .ok_or_else(|| ApiError::new(503, "session.results_unavailable", "Result recomputation is not configured"))?;
+    let recomputation = vote_engine.recompute_results(&session_id, params.confirm).await?;
+    if recomputation.changed && !recomputation.applied {
+        return Err(ApiError::conflict("session.recompute_unconfirmed", "Recomputed results differ from the stored results; repeat with confirm=true to apply")
+            .with_details(serde_json::json!({ "before": recomputation.before, "after": recomputation.after })));
+    }
+
+    if recomputation.applied {
+        let metadata = HashMap::from([
+            ("action".to_string(), serde_json::json!("recompute_results")),
+            ("before".to_string(), serde_json::to_value(&recomputation.before).map_err(|e| ApiError::internal(e.to_string()))?),
+            ("after".to_string(), serde_json::to_value(&recomputation.after).map_err(|e| ApiError::internal(e.to_string()))?),
+        ]);
+        state.audit_log.record(
+            format!("User {} recomputed results of session {}", user.username, session_id),
+            Some(user.user_id),
+            Some(session_id.clone()),
+            metadata,
+        ).await?;
+        warn!("User {} replaced the results of session {}", user.username, session_id);
+    } else {
+        info!("User {} recomputed results of session {}: unchanged", user.username, session_id);
+    }
+    Ok(Json(recomputation))
+}
+
+/// 设置或解除会话的法律保留，保留期间的会话不会被保留期清理删除
+///
+/// 每次变更都会记录审计事件
+async fn set_legal_hold(
+    State(state): State<AuthMiddlewareState>,
+    headers: HeaderMap,
+    Path(session_id): Path<SessionId>,
+    Json(request): Json<LegalHoldRequest>,
+) -> Result<Json<SessionLegalHold>, ApiError> {
+    let user = authenticate(&state, &headers)?;
+    authorize(&state, &user, &AdminOperation::ManageLegalHold)?;
+
+    let vote_engine = state.vote_engine.as_ref()
+        .ok_or_else(|| ApiError::new(503, "session.legal_hold_unavailable", "Legal hold management is not configured"))?;
+    let before = vote_engine.get_vote(&session_id).await?.legal_hold;
+    let vote = vote_engine.set_legal_hold(&session_id, request.hold).await?;
+
+    let (action, verb) = if request.hold { ("set_legal_hold", "placed") } else { ("clear_legal_hold", "lifted") };
+    let metadata = HashMap::from([
+        ("action".to_string(), serde_json::json!(action)),
+        ("before".to_string(), serde_json::json!(before)),
+        ("after".to_string(), serde_json::json!(vote.legal_hold)),
+    ]);
+    state.audit_log.record(
+        format!("User {} {} a legal hold on session {}", user.username, verb, session_id),
+        Some(user.user_id),
+        Some(session_id.clone()),
+        metadata,
+    ).await?;
+    info!("User {} {} a legal hold on session {}", user.username, verb, session_id);
+    Ok(Json(SessionLegalHold { session_id, legal_hold: vote.legal_hold }))
+}
+
 /// 获取配置
 async fn get_config(
     State(_state): State<AuthMiddlewareState>,
-) -> Result<Json<Vec<ConfigManagementInfo>>, StatusCode> {
+) -> Result<Json<Vec<ConfigManagementInfo>>, ApiError> {
     // 这里应该从配置存储获取配置
     let configs = vec![];
     Ok(Json(configs))
@@ -372,7 +578,7 @@ async fn get_config(
 async fn update_config(
     State(_state): State<AuthMiddlewareState>,
     Json(_request): Json<serde_json::Value>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Configuration updated successfully".to_string(),
@@ -383,10 +589,10 @@ async fn update_config(
 /// 获取配置值
 async fn get_config_value(
     State(_state): State<AuthMiddlewareState>,
-    Path(_key): Path<String>,
-) -> Result<Json<ConfigManagementInfo>, StatusCode> {
+    Path(key): Path<String>,
+) -> Result<Json<ConfigManagementInfo>, ApiError> {
     // 简化实现
-    Err(StatusCode::NOT_FOUND)
+    Err(ApiError::not_found("config.not_found", format!("Configuration key not found: {}", key)))
 }
 
 /// 设置配置值
@@ -394,7 +600,7 @@ async fn set_config_value(
     State(_state): State<AuthMiddlewareState>,
     Path(_key): Path<String>,
     Json(_request): Json<serde_json::Value>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Configuration value set successfully".to_string(),
@@ -406,7 +612,7 @@ async fn set_config_value(
 async fn delete_config_value(
     State(_state): State<AuthMiddlewareState>,
     Path(_key): Path<String>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Configuration value deleted successfully".to_string(),
@@ -414,33 +620,46 @@ async fn delete_config_value(
     )))
 }
 
-/// 列出日志
+/// 列出审计日志
 async fn list_logs(
-    State(_state): State<AuthMiddlewareState>,
-    Query(_params): Query<LogQueryParams>,
-    Query(_pagination): Query<PaginationParams>,
-) -> Result<Json<Vec<LogEntry>>, StatusCode> {
-    // 这里应该从日志存储获取日志
-    let logs = vec![];
-    Ok(Json(logs))
-}
-
-/// 获取日志条目
+    State(state): State<AuthMiddlewareState>,
+    headers: HeaderMap,
+    Query(params): Query<LogQueryParams>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<Paginated<LogEntry>>, ApiError> {
+    let user = authenticate(&state, &headers)?;
+    authorize(&state, &user, &AdminOperation::ViewLogs)?;
+
+    let logs = state.audit_log.entries().await?.into_iter()
+        .filter(|entry| params.level.as_ref().is_none_or(|level| entry.level == *level))
+        .filter(|entry| params.source.as_ref().is_none_or(|source| entry.source == *source))
+        .filter(|entry| params.user_id.is_none_or(|id| entry.user_id == Some(id)))
+        .filter(|entry| params.session_id.as_ref().is_none_or(|id| entry.session_id.as_ref() == Some(id)))
+        .collect();
+    Ok(Json(pagination.paginate(logs)))
+}
+
+/// 获取审计日志条目
 async fn get_log_entry(
-    State(_state): State<AuthMiddlewareState>,
-    Path(_log_id): Path<Uuid>,
-) -> Result<Json<LogEntry>, StatusCode> {
-    // 简化实现
-    Err(StatusCode::NOT_FOUND)
+    State(state): State<AuthMiddlewareState>,
+    headers: HeaderMap,
+    Path(log_id): Path<Uuid>,
+) -> Result<Json<LogEntry>, ApiError> {
+    let user = authenticate(&state, &headers)?;
+    authorize(&state, &user, &AdminOperation::ViewLogs)?;
+
+    state.audit_log.get(log_id).await?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("log.not_found", format!("Log entry not found: {}", log_id)))
 }
 
 /// 列出角色
 async fn list_roles(
     State(state): State<AuthMiddlewareState>,
-) -> Result<Json<Vec<String>>, StatusCode> {
+) -> Result<Json<Vec<String>>, ApiError> {
     let roles = {
         let permission_manager = state.permission_manager.lock()
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(|_| ApiError::internal("Permission manager unavailable"))?;
         permission_manager.get_all_roles()
     };
     Ok(Json(roles))
@@ -450,7 +669,7 @@ async fn list_roles(
 async fn create_role(
     State(_state): State<AuthMiddlewareState>,
     Json(_request): Json<serde_json::Value>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Role created successfully".to_string(),
@@ -461,10 +680,10 @@ async fn create_role(
 /// 获取角色信息
 async fn get_role(
     State(_state): State<AuthMiddlewareState>,
-    Path(_role_name): Path<String>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+    Path(role_name): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
     // 简化实现
-    Err(StatusCode::NOT_FOUND)
+    Err(ApiError::not_found("role.not_found", format!("Role not found: {}", role_name)))
 }
 
 /// 更新角色
@@ -472,7 +691,7 @@ async fn update_role(
     State(_state): State<AuthMiddlewareState>,
     Path(_role_name): Path<String>,
     Json(_request): Json<serde_json::Value>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Role updated successfully".to_string(),
@@ -484,7 +703,7 @@ async fn update_role(
 async fn delete_role(
     State(_state): State<AuthMiddlewareState>,
     Path(_role_name): Path<String>,
-) -> Result<Json<OperationResult>, StatusCode> {
+) -> Result<Json<OperationResult>, ApiError> {
     // 简化实现
     Ok(Json(OperationResult::success(
         "Role deleted successfully".to_string(),
@@ -495,7 +714,7 @@ async fn delete_role(
 /// 列出权限
 async fn list_permissions(
     State(_state): State<AuthMiddlewareState>,
-) -> Result<Json<Vec<String>>, StatusCode> {
+) -> Result<Json<Vec<String>>, ApiError> {
     // 简化实现
     let permissions = vec![
         "view_session".to_string(),
@@ -510,6 +729,9 @@ async fn list_permissions(
         "view_logs".to_string(),
         "manage_permissions".to_string(),
         "view_statistics".to_string(),
+        "manage_session_phase".to_string(),
+        "recompute_results".to_string(),
+        "manage_legal_hold".to_string(),
     ];
     Ok(Json(permissions))
 }
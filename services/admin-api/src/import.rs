@@ -0,0 +1,136 @@
+//! 用户批量导入
+//!
+//! CSV首行为表头 `username,email,role,initial_password`，每行独立处理，
+//! 单行失败不会中断其余行的导入
+
+use crate::auth::{AuthService, CreateUserRequest, UserInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 导入请求参数
+#[derive(Debug, Default, Deserialize)]
+pub struct UserImportParams {
+    /// 仅校验，不创建用户
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 单行导入结果
+#[derive(Debug, Serialize)]
+pub struct UserImportRow {
+    /// CSV中的行号（表头为第1行）
+    pub line: u64,
+    pub username: Option<String>,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<UserInfo>,
+}
+
+/// 导入汇总
+#[derive(Debug, Serialize)]
+pub struct UserImportReport {
+    pub dry_run: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub rows: Vec<UserImportRow>,
+}
+
+/// CSV中的一行
+#[derive(Debug, Deserialize)]
+struct UserRecord {
+    username: String,
+    email: Option<String>,
+    role: String,
+    initial_password: String,
+}
+
+/// 逐行导入用户，`known_roles` 之外的角色视为无效
+pub async fn import_users(auth: &mut AuthService, csv: &str, known_roles: &[String], dry_run: bool) -> UserImportReport {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(csv.as_bytes());
+    let mut rows = Vec::new();
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => {
+            rows.push(UserImportRow { line: 1, username: None, success: false, error: Some(e.to_string()), user: None });
+            return UserImportReport::new(dry_run, rows);
+        }
+    };
+
+    // 试运行不会真正创建用户，需要自行记录本批次已出现的用户名
+    let mut seen = HashSet::new();
+    for record in reader.records() {
+        let (line, parsed) = match record {
+            Ok(record) => {
+                let line = record.position().map_or(0, |p| p.line());
+                (line, record.deserialize::<UserRecord>(Some(&headers)).map_err(|e| e.to_string()))
+            }
+            Err(e) => (e.position().map_or(0, |p| p.line()), Err(e.to_string())),
+        };
+
+        let row = match parsed {
+            Ok(record) => import_row(auth, record, known_roles, dry_run, &mut seen, line).await,
+            Err(error) => UserImportRow { line, username: None, success: false, error: Some(error), user: None },
+        };
+        rows.push(row);
+    }
+
+    UserImportReport::new(dry_run, rows)
+}
+
+async fn import_row(
+    auth: &mut AuthService,
+    record: UserRecord,
+    known_roles: &[String],
+    dry_run: bool,
+    seen: &mut HashSet<String>,
+    line: u64,
+) -> UserImportRow {
+    let username = record.username.clone();
+    let failed = |error: String| UserImportRow {
+        line,
+        username: Some(username.clone()),
+        success: false,
+        error: Some(error),
+        user: None,
+    };
+
+    if record.username.is_empty() {
+        return failed("Username is required".to_string());
+    }
+    if !known_roles.contains(&record.role) {
+        return failed(format!("Unknown role: {}", record.role));
+    }
+    if !seen.insert(record.username.clone()) {
+        return failed("Duplicate username in import".to_string());
+    }
+
+    let request = CreateUserRequest {
+        username: record.username,
+        email: record.email.filter(|email| !email.is_empty()),
+        password: record.initial_password,
+        role: record.role,
+    };
+    let result = if dry_run {
+        auth.check_new_user(&request).map(|_| None)
+    } else {
+        auth.create_user(request).await.map(Some)
+    };
+
+    match result {
+        Ok(user) => UserImportRow { line, username: Some(username), success: true, error: None, user },
+        Err(e) => failed(e.to_string()),
+    }
+}
+
+impl UserImportReport {
+    fn new(dry_run: bool, rows: Vec<UserImportRow>) -> Self {
+        let succeeded = rows.iter().filter(|row| row.success).count();
+        Self { dry_run, succeeded, failed: rows.len() - succeeded, rows }
+    }
+}
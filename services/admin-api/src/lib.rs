@@ -8,11 +8,16 @@ pub mod handlers;
 pub mod auth;
 pub mod permissions;
 pub mod middleware;
+pub mod import;
+pub mod mailer;
+pub mod password_policy;
+pub mod audit;
 
 pub use config::AdminConfig;
 pub use service::AdminApiService;
 pub use auth::{AuthService, User, Role};
 pub use permissions::{Permission, PermissionManager};
+pub use audit::AuditLog;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -46,6 +51,12 @@ pub enum AdminOperation {
     ManagePermissions,
     /// 查看统计信息
     ViewStatistics,
+    /// 手动推进会话阶段
+    ManageSessionPhase,
+    /// 重新计算已完成会话的结果
+    RecomputeResults,
+    /// 设置或解除会话的法律保留
+    ManageLegalHold,
 }
 
 /// 操作结果
@@ -105,7 +116,7 @@ pub struct NetworkStatistics {
 /// 会话管理信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionManagementInfo {
-    pub session_id: String,
+    pub session_id: shared_types::SessionId,
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub participants: u32,
@@ -113,6 +124,20 @@ pub struct SessionManagementInfo {
     pub expires_at: Option<DateTime<Utc>>,
 }
 
+/// 会话阶段推进结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPhaseInfo {
+    pub session_id: shared_types::SessionId,
+    pub status: shared_types::VoteStatus,
+}
+
+/// 会话的法律保留状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionLegalHold {
+    pub session_id: shared_types::SessionId,
+    pub legal_hold: bool,
+}
+
 /// 用户管理信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserManagementInfo {
@@ -146,7 +171,7 @@ pub struct LogEntry {
     pub timestamp: DateTime<Utc>,
     pub source: String,
     pub user_id: Option<Uuid>,
-    pub session_id: Option<String>,
+    pub session_id: Option<shared_types::SessionId>,
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
@@ -186,3 +211,24 @@ pub enum AdminError {
     #[error("Boxed error: {0}")]
     Boxed(#[from] Box<dyn std::error::Error>),
 }
+
+impl From<AdminError> for shared_types::ApiError {
+    fn from(error: AdminError) -> Self {
+        use shared_types::ApiError;
+
+        let message = error.to_string();
+        match error {
+            AdminError::Authentication(_) => ApiError::unauthorized("auth.unauthenticated", message),
+            AdminError::Authorization(_) => ApiError::forbidden("auth.forbidden", message),
+            AdminError::Validation(_) => ApiError::bad_request("request.invalid", message),
+            AdminError::NotFound(_) => ApiError::not_found("admin.not_found", message),
+            AdminError::Configuration(_) => ApiError::new(500, "admin.configuration", message),
+            AdminError::Database(_) => ApiError::new(500, "storage.error", message),
+            AdminError::Internal(_)
+            | AdminError::Serialization(_)
+            | AdminError::Io(_)
+            | AdminError::Other(_)
+            | AdminError::Boxed(_) => ApiError::internal(message),
+        }
+    }
+}
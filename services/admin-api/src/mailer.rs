@@ -0,0 +1,59 @@
+//! 密码重置邮件投递
+
+use crate::AdminError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+/// 密码重置令牌的投递方式
+#[async_trait]
+pub trait PasswordResetMailer: Send + Sync {
+    async fn send_reset(&self, recipient: &str, username: &str, token: &str, expires_at: DateTime<Utc>) -> Result<(), AdminError>;
+}
+
+/// 通过通知服务的 `POST /notifications` 发送重置邮件
+pub struct NotificationServiceMailer {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl NotificationServiceMailer {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl PasswordResetMailer for NotificationServiceMailer {
+    async fn send_reset(&self, recipient: &str, username: &str, token: &str, expires_at: DateTime<Utc>) -> Result<(), AdminError> {
+        let request = json!({
+            "notification_type": { "Custom": "password_reset" },
+            "priority": "High",
+            "title": "Password reset",
+            "content": format!(
+                "A password reset was requested for {}. Use this token before {}: {}",
+                username, expires_at.to_rfc3339(), token
+            ),
+            "recipient": recipient,
+            "metadata": {
+                "username": username,
+                "reset_token": token,
+                "expires_at": expires_at,
+            },
+        });
+
+        let response = self.client
+            .post(format!("{}/notifications", self.base_url))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AdminError::Internal(format!("Failed to reach notification service: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(AdminError::Internal(format!("Notification service rejected reset email: {}", response.status())));
+        }
+        Ok(())
+    }
+}
@@ -1,22 +1,31 @@
 //! Middleware for admin API
 
-use crate::{AdminOperation, auth::AuthService, permissions::PermissionManager};
+use crate::{AdminOperation, AuditLog, auth::AuthService, permissions::PermissionManager};
 use axum::{
-    extract::{Request, State},
+    extract::{MatchedPath, Request, State},
     http::{HeaderMap, StatusCode},
     middleware::Next,
     response::Response,
+    Router,
 };
 use serde::{Deserialize, Serialize};
+use shared_types::{panic_response, ApiError};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tower_http::{catch_panic::CatchPanicLayer, trace::TraceLayer};
 use tracing::{info, warn, error};
 use uuid::Uuid;
+use vote_engine::VoteEngine;
 
 /// 认证中间件状态
 #[derive(Clone)]
 pub struct AuthMiddlewareState {
     pub auth_service: Arc<AuthService>,
     pub permission_manager: Arc<Mutex<PermissionManager>>,
+    /// 投票引擎，用于会话阶段管理；未配置时相关接口返回503
+    pub vote_engine: Option<Arc<VoteEngine>>,
+    /// 管理操作审计日志
+    pub audit_log: Arc<AuditLog>,
 }
 
 /// 用户上下文
@@ -33,20 +42,30 @@ pub async fn auth_middleware(
     headers: HeaderMap,
     request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, ApiError> {
+    let user_context = authenticate(&state, &headers)?;
+
+    let mut request = request;
+    request.extensions_mut().insert(user_context);
+
+    Ok(next.run(request).await)
+}
+
+/// 校验请求中的Bearer令牌并返回用户上下文
+pub fn authenticate(state: &AuthMiddlewareState, headers: &HeaderMap) -> Result<UserContext, ApiError> {
     // 提取Authorization头部
     let auth_header = headers
         .get("Authorization")
         .and_then(|header| header.to_str().ok())
         .ok_or_else(|| {
             warn!("Missing Authorization header");
-            StatusCode::UNAUTHORIZED
+            ApiError::unauthorized("auth.missing_token", "Missing Authorization header")
         })?;
 
     // 检查Bearer token格式
     if !auth_header.starts_with("Bearer ") {
         warn!("Invalid Authorization header format");
-        return Err(StatusCode::UNAUTHORIZED);
+        return Err(ApiError::unauthorized("auth.invalid_token", "Authorization header must use the Bearer scheme"));
     }
 
     let token = &auth_header[7..]; // 移除"Bearer "前缀
@@ -55,32 +74,26 @@ pub async fn auth_middleware(
     let claims = state.auth_service.verify_token(token)
         .map_err(|e| {
             error!("Token verification failed: {}", e);
-            StatusCode::UNAUTHORIZED
+            ApiError::unauthorized("auth.invalid_token", e.to_string())
         })?;
 
     // 检查用户是否存在且活跃
     let user = state.auth_service.get_user(Uuid::parse_str(&claims.sub).unwrap_or_default())
         .ok_or_else(|| {
             warn!("User not found: {}", claims.sub);
-            StatusCode::UNAUTHORIZED
+            ApiError::unauthorized("auth.unknown_user", "Token subject does not exist")
         })?;
 
     if !user.is_active {
         warn!("User account is inactive: {}", user.username);
-        return Err(StatusCode::FORBIDDEN);
+        return Err(ApiError::forbidden("auth.user_inactive", "User account is inactive"));
     }
 
-    // 将用户上下文添加到请求扩展中
-    let user_context = UserContext {
+    Ok(UserContext {
         user_id: user.id,
         username: user.username,
         role: user.role,
-    };
-
-    let mut request = request;
-    request.extensions_mut().insert(user_context);
-
-    Ok(next.run(request).await)
+    })
 }
 
 /// 权限检查中间件
@@ -89,26 +102,33 @@ pub async fn permission_middleware(
     operation: AdminOperation,
     request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, ApiError> {
     // 从请求扩展中获取用户上下文
     let user_context = request.extensions()
         .get::<UserContext>()
         .ok_or_else(|| {
             error!("User context not found in request");
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiError::internal("User context not found in request")
         })?;
 
+    authorize(&state, user_context, &operation)?;
+
+    Ok(next.run(request).await)
+}
+
+/// 检查用户是否有权限执行操作
+pub fn authorize(state: &AuthMiddlewareState, user_context: &UserContext, operation: &AdminOperation) -> Result<(), ApiError> {
     // 检查用户权限
     let has_permission = {
         let mut permission_manager = state.permission_manager.lock()
             .map_err(|_| {
                 error!("Failed to acquire permission manager lock");
-                StatusCode::INTERNAL_SERVER_ERROR
+                ApiError::internal("Permission manager unavailable")
             })?;
-        permission_manager.check_permission(&user_context.username, &operation)
+        permission_manager.check_permission(&user_context.username, operation)
             .map_err(|e| {
                 error!("Permission check failed: {}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
+                ApiError::from(e)
             })?
     };
 
@@ -117,7 +137,7 @@ pub async fn permission_middleware(
             "User {} does not have permission for operation: {:?}",
             user_context.username, operation
         );
-        return Err(StatusCode::FORBIDDEN);
+        return Err(ApiError::forbidden("auth.permission_denied", format!("Operation {:?} is not permitted", operation)));
     }
 
     info!(
@@ -125,7 +145,7 @@ pub async fn permission_middleware(
         user_context.username, operation
     );
 
-    Ok(next.run(request).await)
+    Ok(())
 }
 
 /// 日志中间件
@@ -163,6 +183,38 @@ pub async fn logging_middleware(
     response
 }
 
+/// 请求计时中间件，超过阈值时记录慢请求警告
+pub async fn timing_middleware(
+    State(threshold): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    // 按路由模式而非具体路径打标签，避免指标基数膨胀
+    let route = request.extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    shared_logging::metrics().record_request(
+        "admin-api",
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+        threshold,
+    );
+    response
+}
+
+/// 为路由添加请求计时
+pub fn with_request_timing(router: Router, threshold: Duration) -> Router {
+    router.layer(axum::middleware::from_fn_with_state(threshold, timing_middleware))
+}
+
 /// 速率限制中间件
 pub async fn rate_limit_middleware(
     request: Request,
@@ -220,6 +272,22 @@ pub async fn security_headers_middleware(
     response
 }
 
+/// 捕获处理器panic并返回500 JSON错误，日志带有请求的关联ID
+pub fn with_panic_recovery(router: Router) -> Router {
+    router
+        .layer(CatchPanicLayer::custom(panic_response))
+        .layer(axum::middleware::from_fn(shared_types::localize_errors))
+        .layer(TraceLayer::new_for_http().make_span_with(|request: &Request| {
+            let correlation_id = request
+                .headers()
+                .get("x-correlation-id")
+                .and_then(|h| h.to_str().ok())
+                .map(str::to_string)
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            shared_logging::correlation_span(&correlation_id)
+        }))
+}
+
 /// 请求ID中间件
 pub async fn request_id_middleware(
     mut request: Request,
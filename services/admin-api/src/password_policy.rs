@@ -0,0 +1,175 @@
+//! 密码策略
+//!
+//! 长度、字符类别、禁用密码列表与强度要求均可按部署配置，
+//! 校验失败时逐条返回未满足的规则
+
+use crate::AdminError;
+use serde::{Deserialize, Serialize};
+
+/// 视为特殊字符的集合
+const SPECIAL_CHARS: &str = "!@#$%^&*()_+-=[]{}|;:,.<>?";
+
+/// 强度估算时直接判为最弱的常见密码
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "123456", "12345678", "123456789", "qwerty", "abc123", "111111",
+    "letmein", "welcome", "admin", "iloveyou", "monkey", "dragon", "football",
+    "baseball", "sunshine", "princess", "master", "login", "passw0rd", "trustno1",
+];
+
+/// 密码策略配置，拼错的字段会导致加载失败
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PasswordPolicy {
+    /// 最小长度（字符数）
+    pub min_length: usize,
+    /// 最大长度（字符数）
+    pub max_length: usize,
+    /// 需要大写字母
+    pub require_uppercase: bool,
+    /// 需要小写字母
+    pub require_lowercase: bool,
+    /// 需要数字
+    pub require_digit: bool,
+    /// 需要特殊字符
+    pub require_special: bool,
+    /// 禁止使用的密码（不区分大小写）
+    #[serde(default)]
+    pub banned_passwords: Vec<String>,
+    /// 最小熵（比特），按长度与所用字符类别估算
+    #[serde(default)]
+    pub min_entropy_bits: Option<f64>,
+    /// 最低强度评分（0-4），见 [`estimate_strength`]
+    #[serde(default)]
+    pub min_strength_score: Option<u8>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 128,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_special: true,
+            banned_passwords: Vec::new(),
+            min_entropy_bits: None,
+            min_strength_score: None,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// 返回所有未满足的规则说明，空列表表示通过
+    pub fn violations(&self, password: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+        let length = password.chars().count();
+
+        if length < self.min_length {
+            violations.push(format!("Password must be at least {} characters long", self.min_length));
+        }
+        if length > self.max_length {
+            violations.push(format!("Password must be at most {} characters long", self.max_length));
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            violations.push("Password must contain at least one uppercase letter".to_string());
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            violations.push("Password must contain at least one lowercase letter".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push("Password must contain at least one digit".to_string());
+        }
+        if self.require_special && !password.chars().any(|c| SPECIAL_CHARS.contains(c)) {
+            violations.push("Password must contain at least one special character".to_string());
+        }
+        if self.banned_passwords.iter().any(|banned| banned.eq_ignore_ascii_case(password)) {
+            violations.push("Password is not allowed".to_string());
+        }
+        if let Some(min_bits) = self.min_entropy_bits {
+            let bits = entropy_bits(password);
+            if bits < min_bits {
+                violations.push(format!("Password entropy is {:.1} bits, at least {:.1} required", bits, min_bits));
+            }
+        }
+        if let Some(min_score) = self.min_strength_score {
+            let score = estimate_strength(password, &self.banned_passwords);
+            if score < min_score {
+                violations.push(format!("Password strength score is {}, at least {} required", score, min_score));
+            }
+        }
+
+        violations
+    }
+
+    /// 校验密码，失败时错误信息包含每条未满足的规则
+    pub fn validate(&self, password: &str) -> Result<(), AdminError> {
+        let violations = self.violations(password);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(AdminError::Validation(violations.join("; ")))
+        }
+    }
+}
+
+/// 密码所用字符类别的字符池大小
+fn pool_size(password: &str) -> u32 {
+    let mut pool = 0;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if password.chars().any(|c| !c.is_ascii_alphanumeric()) {
+        pool += 33;
+    }
+    pool
+}
+
+/// 按长度与字符池大小估算的熵（比特）
+pub fn entropy_bits(password: &str) -> f64 {
+    match pool_size(password) {
+        0 => 0.0,
+        pool => password.chars().count() as f64 * (pool as f64).log2(),
+    }
+}
+
+/// zxcvbn风格的强度评分（0-4）
+///
+/// 先估算猜测次数的对数：常见或禁用密码（忽略末尾数字和符号）直接视为最弱，
+/// 重复字符和连续字符（如 `aaa`、`abc`、`321`）几乎不增加猜测次数；
+/// 再按与zxcvbn相同的阈值（10^3、10^6、10^8、10^10）映射为评分
+pub fn estimate_strength(password: &str, banned_passwords: &[String]) -> u8 {
+    let lowered = password.to_lowercase();
+    let base = lowered.trim_end_matches(|c: char| !c.is_alphabetic());
+    let is_known = |candidate: &str| {
+        COMMON_PASSWORDS.contains(&candidate) || banned_passwords.iter().any(|banned| banned.eq_ignore_ascii_case(candidate))
+    };
+    if is_known(&lowered) || (!base.is_empty() && is_known(base)) {
+        return 0;
+    }
+
+    let pool_log10 = (pool_size(password).max(1) as f64).log10();
+    let chars: Vec<char> = password.chars().collect();
+    let mut guesses_log10 = 0.0;
+    for (i, c) in chars.iter().enumerate() {
+        let predictable = i > 0 && {
+            let diff = *c as i64 - chars[i - 1] as i64;
+            diff.abs() <= 1
+        };
+        guesses_log10 += if predictable { 2f64.log10() } else { pool_log10 };
+    }
+
+    match guesses_log10 {
+        g if g < 3.0 => 0,
+        g if g < 6.0 => 1,
+        g if g < 8.0 => 2,
+        g if g < 10.0 => 3,
+        _ => 4,
+    }
+}
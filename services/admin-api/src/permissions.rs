@@ -21,6 +21,9 @@ pub enum Permission {
     ViewLogs,
     ManagePermissions,
     ViewStatistics,
+    ManageSessionPhase,
+    RecomputeResults,
+    ManageLegalHold,
     Custom(String),
 }
 
@@ -39,6 +42,9 @@ impl Permission {
             Permission::ViewLogs => "view_logs",
             Permission::ManagePermissions => "manage_permissions",
             Permission::ViewStatistics => "view_statistics",
+            Permission::ManageSessionPhase => "manage_session_phase",
+            Permission::RecomputeResults => "recompute_results",
+            Permission::ManageLegalHold => "manage_legal_hold",
             Permission::Custom(name) => name,
         }
     }
@@ -57,6 +63,9 @@ impl Permission {
             "view_logs" => Permission::ViewLogs,
             "manage_permissions" => Permission::ManagePermissions,
             "view_statistics" => Permission::ViewStatistics,
+            "manage_session_phase" => Permission::ManageSessionPhase,
+            "recompute_results" => Permission::RecomputeResults,
+            "manage_legal_hold" => Permission::ManageLegalHold,
             name => Permission::Custom(name.to_string()),
         }
     }
@@ -75,6 +84,9 @@ impl Permission {
             AdminOperation::ViewLogs => Permission::ViewLogs,
             AdminOperation::ManagePermissions => Permission::ManagePermissions,
             AdminOperation::ViewStatistics => Permission::ViewStatistics,
+            AdminOperation::ManageSessionPhase => Permission::ManageSessionPhase,
+            AdminOperation::RecomputeResults => Permission::RecomputeResults,
+            AdminOperation::ManageLegalHold => Permission::ManageLegalHold,
         }
     }
 }
@@ -159,6 +171,9 @@ impl PermissionManager {
         admin_role.add_permission(Permission::ViewLogs);
         admin_role.add_permission(Permission::ManagePermissions);
         admin_role.add_permission(Permission::ViewStatistics);
+        admin_role.add_permission(Permission::ManageSessionPhase);
+        admin_role.add_permission(Permission::RecomputeResults);
+        admin_role.add_permission(Permission::ManageLegalHold);
         self.role_permissions.insert("admin".to_string(), admin_role);
 
         // 版主角色
@@ -1,20 +1,27 @@
 //! Main admin API service implementation
 
 use crate::{
-    AdminConfig, AdminError, AuthService, PermissionManager,
-    middleware::AuthMiddlewareState,
+    AdminConfig, AdminError, AuditLog, AuthService, PermissionManager,
+    middleware::{with_request_timing, AuthMiddlewareState},
     handlers::create_http_router,
+    mailer::NotificationServiceMailer,
 };
 use anyhow::Result;
+use event_store::{EventStorage, store::MemoryEventStore};
+use axum::{http::header, routing::get};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::task::JoinHandle;
 use tracing::{info, error};
+use vote_engine::VoteEngine;
 
 /// 管理API服务
 pub struct AdminApiService {
     config: AdminConfig,
     auth_service: Arc<AuthService>,
     permission_manager: Arc<Mutex<PermissionManager>>,
+    vote_engine: Option<Arc<VoteEngine>>,
+    audit_log: Arc<AuditLog>,
     http_server_handle: Option<JoinHandle<()>>,
 }
 
@@ -24,10 +31,17 @@ impl AdminApiService {
         info!("Initializing admin API service");
         
         // 创建认证服务
-        let auth_service = Arc::new(AuthService::new(
+        let mut auth_service = AuthService::new(
             config.auth.jwt_secret.clone(),
             config.auth.jwt_expiry_hours,
-        ));
+        ).with_password_policy(config.auth.password_policy.clone());
+        if let Some(url) = &config.auth.password_reset.notification_url {
+            auth_service = auth_service.with_password_reset(
+                Arc::new(NotificationServiceMailer::new(url.clone())),
+                chrono::Duration::minutes(config.auth.password_reset.token_ttl_minutes as i64),
+            );
+        }
+        let auth_service = Arc::new(auth_service);
         
         // 创建权限管理器
         let permission_manager = Arc::new(Mutex::new(PermissionManager::new()));
@@ -36,10 +50,24 @@ impl AdminApiService {
             config,
             auth_service,
             permission_manager,
+            vote_engine: None,
+            audit_log: Arc::new(AuditLog::new(Arc::new(MemoryEventStore::new()))),
             http_server_handle: None,
         })
     }
     
+    /// 配置投票引擎，启用会话阶段管理接口
+    pub fn with_vote_engine(mut self, vote_engine: Arc<VoteEngine>) -> Self {
+        self.vote_engine = Some(vote_engine);
+        self
+    }
+    
+    /// 配置审计日志使用的事件存储，默认为内存存储
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStorage>) -> Self {
+        self.audit_log = Arc::new(AuditLog::new(event_store));
+        self
+    }
+    
     /// 启动管理API服务
     pub async fn start(&mut self) -> Result<(), AdminError> {
         info!("Starting admin API service");
@@ -72,7 +100,7 @@ impl AdminApiService {
                 "server": self.config.server,
                 "auth": {
                     "jwt_expiry_hours": self.config.auth.jwt_expiry_hours,
-                    "min_password_length": self.config.auth.min_password_length,
+                    "password_policy": self.config.auth.password_policy,
                 },
                 "permissions": {
                     "cache_ttl": self.config.permissions.cache_ttl,
@@ -88,9 +116,15 @@ impl AdminApiService {
         let middleware_state = AuthMiddlewareState {
             auth_service: Arc::clone(&self.auth_service),
             permission_manager: Arc::clone(&self.permission_manager),
+            vote_engine: self.vote_engine.clone(),
+            audit_log: Arc::clone(&self.audit_log),
         };
         
-        let app = create_http_router(middleware_state);
+        let mut app = create_http_router(middleware_state);
+        if self.config.monitoring.metrics {
+            app = app.route(&self.config.monitoring.metrics_path, get(metrics));
+        }
+        let app = with_request_timing(app, Duration::from_millis(self.config.monitoring.slow_request_threshold_ms));
         
         let listener = tokio::net::TcpListener::bind(format!("{}:{}", self.config.server.host, self.config.server.port))
             .await
@@ -107,3 +141,8 @@ impl AdminApiService {
         Ok(())
     }
 }
+
+/// Prometheus格式的请求与查询耗时指标
+async fn metrics() -> ([(header::HeaderName, &'static str); 1], String) {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], shared_logging::metrics().render())
+}
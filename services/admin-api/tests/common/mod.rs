@@ -0,0 +1,109 @@
+//! HTTP harness shared by the admin API integration tests
+#![allow(dead_code)]
+
+use admin_api::auth::LoginRequest;
+use admin_api::handlers::create_http_router;
+use admin_api::middleware::AuthMiddlewareState;
+use admin_api::{AuditLog, AuthService, PermissionManager};
+use axum::body::{to_bytes, Body};
+use axum::http::{Method, Request, StatusCode};
+use event_store::store::MemoryEventStore;
+use serde_json::{json, Value};
+use shared_types::{TieBreak, VoteConfig};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use vote_engine::{MemoryVoteService, VoteEngine};
+
+/// 路由及其背后的服务，`token` 为以 `role` 身份登录的默认管理员令牌
+pub struct Harness {
+    pub router: axum::Router,
+    pub auth: AuthService,
+    pub engine: Arc<VoteEngine>,
+    pub votes: Arc<MemoryVoteService>,
+    pub audit_log: Arc<AuditLog>,
+    /// 审计日志背后的事件存储
+    pub event_store: Arc<MemoryEventStore>,
+    pub token: String,
+}
+
+pub async fn harness(role: &str) -> Harness {
+    harness_with(AuthService::new("secret".to_string(), 1), role).await
+}
+
+/// 使用给定的认证服务构建测试路由
+pub async fn harness_with(mut auth: AuthService, role: &str) -> Harness {
+    let token = auth
+        .login(LoginRequest { username: "admin".to_string(), password: "admin123".to_string() })
+        .await
+        .unwrap()
+        .access_token;
+    let mut permissions = PermissionManager::new();
+    permissions.assign_role("admin", role.to_string()).unwrap();
+    let votes = Arc::new(MemoryVoteService::new());
+    let engine = Arc::new(VoteEngine::new(votes.clone()));
+    let event_store = Arc::new(MemoryEventStore::new());
+    let audit_log = Arc::new(AuditLog::new(event_store.clone()));
+
+    let router = create_http_router(AuthMiddlewareState {
+        auth_service: Arc::new(auth.clone()),
+        permission_manager: Arc::new(Mutex::new(permissions)),
+        vote_engine: Some(engine.clone()),
+        audit_log: audit_log.clone(),
+    });
+    Harness { router, auth, engine, votes, audit_log, event_store, token }
+}
+
+impl Harness {
+    /// 携带管理员令牌发送请求，返回状态码与JSON响应体（空响应体为 `Null`）
+    pub async fn request(&self, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        self.send(method, uri, Some(&self.token), body).await
+    }
+
+    /// 以任意令牌（或不带令牌）发送请求
+    pub async fn send(&self, method: Method, uri: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header("Authorization", format!("Bearer {}", token));
+        }
+        let request = match body {
+            Some(body) => builder.header("Content-Type", "application/json").body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap();
+        self.call(request).await
+    }
+
+    pub async fn call(&self, request: Request<Body>) -> (StatusCode, Value) {
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    /// 通过 `/auth/login` 登录，返回访问令牌
+    pub async fn login(&self, username: &str, password: &str) -> Result<String, StatusCode> {
+        let body = json!({ "username": username, "password": password });
+        match self.send(Method::POST, "/auth/login", None, Some(body)).await {
+            (StatusCode::OK, body) => Ok(body["access_token"].as_str().unwrap().to_string()),
+            (status, _) => Err(status),
+        }
+    }
+
+    pub async fn create_vote(&self, title: &str) -> String {
+        self.engine.create_vote(vote_config(title)).await.unwrap()
+    }
+}
+
+pub fn vote_config(title: &str) -> VoteConfig {
+    VoteConfig {
+        title: title.to_string(),
+        description: format!("{} description", title),
+        template_id: "yes_no".to_string(),
+        template_params: json!({}),
+        commitment_duration_hours: 24,
+        reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }
+}
@@ -0,0 +1,24 @@
+use admin_api::AdminError;
+use axum::{body::to_bytes, response::IntoResponse};
+use shared_types::ApiError;
+
+#[tokio::test]
+async fn test_not_found_renders_structured_body() {
+    let response = ApiError::from(AdminError::NotFound("user 42".to_string())).into_response();
+    assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "admin.not_found");
+    assert_eq!(body["message"], "Not found: user 42");
+    assert!(body.get("details").is_none());
+}
+
+#[test]
+fn test_admin_error_codes() {
+    let unauthenticated = ApiError::from(AdminError::Authentication("bad password".to_string()));
+    assert_eq!((unauthenticated.status_code(), unauthenticated.code), (401, "auth.unauthenticated"));
+
+    let invalid = ApiError::from(AdminError::Validation("empty username".to_string()));
+    assert_eq!((invalid.status_code(), invalid.code), (400, "request.invalid"));
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use admin_api::AuditLog;
+use axum::http::{Method, StatusCode};
+use common::{harness, Harness};
+use serde_json::{json, Value};
+
+async fn set_hold(harness: &Harness, vote_id: &str, hold: bool) -> (StatusCode, Value) {
+    harness.request(Method::PUT, &format!("/sessions/{}/legal-hold", vote_id), Some(json!({ "hold": hold }))).await
+}
+
+#[tokio::test]
+async fn test_legal_hold_is_set_cleared_and_audited() {
+    let harness = harness("admin").await;
+    let vote_id = harness.create_vote("Disputed").await;
+
+    let (status, body) = set_hold(&harness, &vote_id, true).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "session_id": vote_id, "legal_hold": true }));
+    assert!(harness.engine.get_vote(&vote_id).await.unwrap().legal_hold);
+
+    let (status, body) = set_hold(&harness, &vote_id, false).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["legal_hold"], false);
+    assert!(!harness.engine.get_vote(&vote_id).await.unwrap().legal_hold);
+
+    let entries = harness.audit_log.entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.session_id.as_deref() == Some(vote_id.as_str())));
+    assert_eq!(entries[0].metadata["action"], "set_legal_hold");
+    assert_eq!(entries[0].metadata["before"], false);
+    assert_eq!(entries[1].metadata["action"], "clear_legal_hold");
+    assert_eq!(entries[1].metadata["after"], false);
+}
+
+#[tokio::test]
+async fn test_audit_entries_are_read_back_from_the_event_store() {
+    let harness = harness("admin").await;
+    let vote_id = harness.create_vote("Disputed").await;
+    set_hold(&harness, &vote_id, true).await;
+
+    // 重启后的服务使用同一事件存储重新构建审计日志
+    let reopened = AuditLog::new(harness.event_store.clone());
+    let entries = reopened.entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].metadata["action"], "set_legal_hold");
+    assert_eq!(reopened.get(entries[0].id).await.unwrap().unwrap().message, entries[0].message);
+
+    let (status, body) = harness.request(Method::GET, &format!("/logs/{}", entries[0].id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["session_id"], vote_id);
+    // 未知的记录 id
+    let (status, _) = harness.request(Method::GET, &format!("/logs/{}", uuid::Uuid::new_v4()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_legal_hold_on_unknown_vote_is_not_found() {
+    let harness = harness("admin").await;
+
+    let (status, _) = set_hold(&harness, "missing-vote", true).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(harness.audit_log.entries().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_legal_hold_requires_manage_legal_hold_permission() {
+    let harness = harness("moderator").await;
+    let vote_id = harness.create_vote("Disputed").await;
+
+    let (status, body) = set_hold(&harness, &vote_id, true).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "auth.permission_denied");
+    assert!(!harness.engine.get_vote(&vote_id).await.unwrap().legal_hold);
+}
@@ -0,0 +1,39 @@
+use admin_api::auth::LoginRequest;
+use admin_api::{AdminError, AuthService};
+use chrono::{Duration, TimeZone, Utc};
+use shared_utils::clock::MockClock;
+use std::sync::Arc;
+
+fn login(password: &str) -> LoginRequest {
+    LoginRequest { username: "admin".to_string(), password: password.to_string() }
+}
+
+async fn lock_out(auth: &mut AuthService) {
+    for _ in 0..5 {
+        assert!(auth.login(login("wrong")).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_lockout_blocks_correct_password_until_it_expires() {
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+    let mut auth = AuthService::new("secret".to_string(), 1).with_clock(Arc::new(clock.clone()));
+    lock_out(&mut auth).await;
+
+    clock.advance(Duration::minutes(14));
+    let result = auth.login(login("admin123")).await;
+    assert!(matches!(result, Err(AdminError::Authentication(ref message)) if message == "Account is locked"));
+
+    clock.advance(Duration::minutes(1));
+    let response = auth.login(login("admin123")).await.unwrap();
+    assert_eq!(response.user.username, "admin");
+}
+
+#[tokio::test]
+async fn test_last_login_uses_injected_clock() {
+    let now = Utc.with_ymd_and_hms(2030, 6, 1, 12, 0, 0).unwrap();
+    let mut auth = AuthService::new("secret".to_string(), 1).with_clock(Arc::new(MockClock::new(now)));
+
+    let response = auth.login(login("admin123")).await.unwrap();
+    assert_eq!(auth.get_user(response.user.id).unwrap().last_login, Some(now));
+}
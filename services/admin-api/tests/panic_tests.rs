@@ -0,0 +1,33 @@
+use admin_api::middleware::with_panic_recovery;
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use tower::ServiceExt;
+
+async fn panics() -> &'static str {
+    panic!("deliberate handler panic")
+}
+
+fn app() -> Router {
+    with_panic_recovery(
+        Router::new()
+            .route("/panic", get(panics))
+            .route("/ok", get(|| async { "fine" })),
+    )
+}
+
+#[tokio::test]
+async fn test_handler_panic_becomes_500_json_error() {
+    let app = app();
+    let request = Request::get("/panic").header("x-correlation-id", "panic-test").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["code"], "internal.panic");
+
+    // The worker keeps serving
+    let response = app.oneshot(Request::get("/ok").body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,123 @@
+use admin_api::auth::CreateUserRequest;
+use admin_api::config::AuthConfig;
+use admin_api::password_policy::{entropy_bits, estimate_strength, PasswordPolicy};
+use admin_api::{AdminError, AuthService};
+
+/// Policy with every rule switched off, so each test enables only the rule under test
+fn permissive() -> PasswordPolicy {
+    PasswordPolicy {
+        min_length: 0,
+        max_length: usize::MAX,
+        require_uppercase: false,
+        require_lowercase: false,
+        require_digit: false,
+        require_special: false,
+        banned_passwords: Vec::new(),
+        min_entropy_bits: None,
+        min_strength_score: None,
+    }
+}
+
+fn assert_single_violation(policy: &PasswordPolicy, password: &str, expected: &str) {
+    assert_eq!(policy.violations(password), vec![expected.to_string()]);
+}
+
+#[test]
+fn test_min_length() {
+    let policy = PasswordPolicy { min_length: 12, ..permissive() };
+    assert!(policy.validate("twelve chars").is_ok());
+    assert_single_violation(&policy, "short", "Password must be at least 12 characters long");
+}
+
+#[test]
+fn test_max_length() {
+    let policy = PasswordPolicy { max_length: 6, ..permissive() };
+    assert!(policy.validate("sixsix").is_ok());
+    assert_single_violation(&policy, "sevenss", "Password must be at most 6 characters long");
+}
+
+#[test]
+fn test_character_classes() {
+    let cases = [
+        (PasswordPolicy { require_uppercase: true, ..permissive() }, "Upper", "lower", "Password must contain at least one uppercase letter"),
+        (PasswordPolicy { require_lowercase: true, ..permissive() }, "lOWER", "UPPER", "Password must contain at least one lowercase letter"),
+        (PasswordPolicy { require_digit: true, ..permissive() }, "d1git", "digit", "Password must contain at least one digit"),
+        (PasswordPolicy { require_special: true, ..permissive() }, "spec!al", "special", "Password must contain at least one special character"),
+    ];
+    for (policy, passing, failing, message) in cases {
+        assert!(policy.validate(passing).is_ok(), "{} should pass", passing);
+        assert_single_violation(&policy, failing, message);
+    }
+}
+
+#[test]
+fn test_banned_passwords_ignore_case() {
+    let policy = PasswordPolicy { banned_passwords: vec!["Winter2030!".to_string()], ..permissive() };
+    assert!(policy.validate("Summer2030!").is_ok());
+    assert_single_violation(&policy, "winter2030!", "Password is not allowed");
+}
+
+#[test]
+fn test_min_entropy_bits() {
+    let policy = PasswordPolicy { min_entropy_bits: Some(50.0), ..permissive() };
+    assert!(entropy_bits("aaaa") < 50.0);
+    assert!(policy.validate("Xk9#mQ2$vL").is_ok());
+    let violations = policy.violations("aaaa");
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].starts_with("Password entropy is"));
+}
+
+#[test]
+fn test_min_strength_score() {
+    let policy = PasswordPolicy { min_strength_score: Some(3), ..permissive() };
+    assert!(policy.validate("Xk9#mQ2$vL").is_ok());
+    assert_single_violation(&policy, "abcdefgh", "Password strength score is 1, at least 3 required");
+}
+
+#[test]
+fn test_strength_estimate_penalizes_common_and_predictable_passwords() {
+    assert_eq!(estimate_strength("password", &[]), 0);
+    assert_eq!(estimate_strength("Password123!", &[]), 0);
+    assert_eq!(estimate_strength("Corporate1!", &["corporate".to_string()]), 0);
+    assert!(estimate_strength("aaaaaaaaaaaa", &[]) < estimate_strength("Xk9#mQ2$vL", &[]));
+    assert_eq!(estimate_strength("Xk9#mQ2$vL", &[]), 4);
+}
+
+#[test]
+fn test_every_failed_rule_is_reported() {
+    let result = PasswordPolicy::default().validate("abc");
+    let Err(AdminError::Validation(message)) = result else { panic!("expected validation error") };
+    assert_eq!(
+        message,
+        "Password must be at least 8 characters long; \
+         Password must contain at least one uppercase letter; \
+         Password must contain at least one digit; \
+         Password must contain at least one special character"
+    );
+}
+
+#[tokio::test]
+async fn test_auth_service_uses_configured_policy() {
+    let policy = PasswordPolicy { min_length: 16, ..PasswordPolicy::default() };
+    let mut auth = AuthService::new("secret".to_string(), 1).with_password_policy(policy);
+    let request = |password: &str| CreateUserRequest {
+        username: "alice".to_string(),
+        email: None,
+        password: password.to_string(),
+        role: "viewer".to_string(),
+    };
+
+    assert!(matches!(auth.create_user(request("Sh0rt-Pass!")).await, Err(AdminError::Validation(_))));
+    assert!(auth.create_user(request("Much-L0nger-Passphrase!")).await.is_ok());
+}
+
+#[test]
+fn test_removed_password_settings_are_rejected() {
+    let mut config = serde_json::to_value(AuthConfig::default()).unwrap();
+    assert!(serde_json::from_value::<AuthConfig>(config.clone()).is_ok());
+
+    // settings that moved into `password_policy` must not be silently ignored
+    config["min_password_length"] = serde_json::json!(12);
+    let error = serde_json::from_value::<AuthConfig>(config).unwrap_err();
+    assert!(error.to_string().contains("min_password_length"), "{}", error);
+}
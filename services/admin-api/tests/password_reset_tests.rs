@@ -0,0 +1,126 @@
+mod common;
+
+use admin_api::auth::LoginRequest;
+use admin_api::mailer::PasswordResetMailer;
+use admin_api::{AdminError, AuthService};
+use async_trait::async_trait;
+use axum::http::{Method, StatusCode};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde_json::json;
+use shared_utils::clock::MockClock;
+use std::sync::{Arc, Mutex};
+
+const NEW_PASSWORD: &str = "N3w-Passw0rd!";
+
+/// Records reset emails instead of sending them
+#[derive(Default)]
+struct CapturingMailer {
+    sent: Mutex<Vec<(String, String)>>,
+}
+
+impl CapturingMailer {
+    fn last_token(&self) -> String {
+        self.sent.lock().unwrap().last().expect("no reset email sent").1.clone()
+    }
+}
+
+#[async_trait]
+impl PasswordResetMailer for CapturingMailer {
+    async fn send_reset(&self, recipient: &str, _username: &str, token: &str, _expires_at: DateTime<Utc>) -> Result<(), AdminError> {
+        self.sent.lock().unwrap().push((recipient.to_string(), token.to_string()));
+        Ok(())
+    }
+}
+
+fn setup() -> (AuthService, Arc<CapturingMailer>, MockClock) {
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+    let mailer = Arc::new(CapturingMailer::default());
+    let auth = AuthService::new("secret".to_string(), 1)
+        .with_clock(Arc::new(clock.clone()))
+        .with_password_reset(mailer.clone(), Duration::minutes(30));
+    (auth, mailer, clock)
+}
+
+fn login(password: &str) -> LoginRequest {
+    LoginRequest { username: "admin".to_string(), password: password.to_string() }
+}
+
+#[tokio::test]
+async fn test_valid_reset_replaces_password() {
+    let (mut auth, mailer, _clock) = setup();
+    auth.request_password_reset("admin").await.unwrap();
+    assert_eq!(mailer.sent.lock().unwrap()[0].0, "admin@example.com");
+
+    auth.reset_password(&mailer.last_token(), NEW_PASSWORD).await.unwrap();
+
+    assert!(auth.login(login("admin123")).await.is_err());
+    assert_eq!(auth.login(login(NEW_PASSWORD)).await.unwrap().user.username, "admin");
+}
+
+#[tokio::test]
+async fn test_expired_token_is_rejected() {
+    let (mut auth, mailer, clock) = setup();
+    auth.request_password_reset("admin").await.unwrap();
+
+    clock.advance(Duration::minutes(31));
+    let result = auth.reset_password(&mailer.last_token(), NEW_PASSWORD).await;
+    assert!(matches!(result, Err(AdminError::Authentication(ref message)) if message == "Reset token has expired"));
+    assert!(auth.login(login("admin123")).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_consumed_token_cannot_be_reused() {
+    let (mut auth, mailer, _clock) = setup();
+    auth.request_password_reset("admin").await.unwrap();
+    let token = mailer.last_token();
+
+    auth.reset_password(&token, NEW_PASSWORD).await.unwrap();
+    let result = auth.reset_password(&token, "An0ther-Passw0rd!").await;
+    assert!(matches!(result, Err(AdminError::Authentication(ref message)) if message == "Reset token has already been used"));
+    assert!(auth.login(login(NEW_PASSWORD)).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_unknown_user_sends_nothing() {
+    let (mut auth, mailer, _clock) = setup();
+    auth.request_password_reset("nobody").await.unwrap();
+    assert!(mailer.sent.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_access_token_is_not_a_reset_token() {
+    let (mut auth, _mailer, _clock) = setup();
+    let access_token = auth.login(login("admin123")).await.unwrap().access_token;
+    let result = auth.reset_password(&access_token, NEW_PASSWORD).await;
+    assert!(matches!(result, Err(AdminError::Authentication(_))));
+}
+
+#[tokio::test]
+async fn test_reset_over_http_lets_user_log_in_with_new_password() {
+    let (auth, mailer, _clock) = setup();
+    let harness = common::harness_with(auth, "admin").await;
+
+    let (status, _) = harness.send(Method::POST, "/auth/password-reset", None, Some(json!({ "username": "admin" }))).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let confirm = json!({ "token": mailer.last_token(), "new_password": NEW_PASSWORD });
+    let (status, _) = harness.send(Method::POST, "/auth/password-reset/confirm", None, Some(confirm)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(harness.login("admin", "admin123").await, Err(StatusCode::UNAUTHORIZED));
+    assert!(harness.login("admin", NEW_PASSWORD).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_created_and_changed_passwords_log_in_over_http() {
+    let harness = common::harness("admin").await;
+    let create = json!({ "username": "dana", "email": null, "password": "Str0ng!Pass", "role": "viewer" });
+    let (status, user) = harness.request(Method::POST, "/users", Some(create)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(harness.login("dana", "Str0ng!Pass").await.is_ok());
+
+    let change = json!({ "current_password": "Str0ng!Pass", "new_password": NEW_PASSWORD });
+    let uri = format!("/users/{}/password", user["id"].as_str().unwrap());
+    assert_eq!(harness.request(Method::PUT, &uri, Some(change)).await.0, StatusCode::OK);
+    assert_eq!(harness.login("dana", "Str0ng!Pass").await, Err(StatusCode::UNAUTHORIZED));
+    assert!(harness.login("dana", NEW_PASSWORD).await.is_ok());
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{harness, Harness};
+use serde_json::{json, Value};
+use shared_types::{Reveal, VoteId, VoteResults};
+use vote_engine::VoteService;
+
+/// 创建一个带两个揭示的投票，并推进到已完成状态
+async fn completed_vote(harness: &Harness) -> String {
+    let vote_id = harness.create_vote("Budget").await;
+    for (voter, value) in [("alice", "yes"), ("bob", "no")] {
+        harness.votes.save_reveal(Reveal {
+            id: format!("{}-{}", vote_id, voter),
+            vote_id: vote_id.clone(),
+            voter: voter.to_string(),
+            value: json!(value),
+            salt: "pepper".to_string(),
+            created_at: chrono::Utc::now(),
+            ciphertext: None,
+        }).await.unwrap();
+    }
+    for _ in 0..3 {
+        harness.engine.advance_phase(&vote_id).await.unwrap();
+    }
+    vote_id
+}
+
+async fn post(harness: &Harness, uri: &str) -> (StatusCode, Value) {
+    harness.request(Method::POST, uri, None).await
+}
+
+async fn stored_results(harness: &Harness, vote_id: &str) -> Option<VoteResults> {
+    harness.votes.get_vote(&VoteId::parse(vote_id).unwrap()).await.unwrap().results
+}
+
+#[tokio::test]
+async fn test_recompute_without_change_writes_nothing() {
+    let harness = harness("admin").await;
+    let vote_id = completed_vote(&harness).await;
+    let uri = format!("/sessions/{}/recompute-results?confirm=true", vote_id);
+    let (status, _) = post(&harness, &uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let stored = stored_results(&harness, &vote_id).await.unwrap();
+    let audited = harness.audit_log.entries().await.unwrap().len();
+
+    let (status, body) = post(&harness, &format!("/sessions/{}/recompute-results", vote_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["changed"], false);
+    assert_eq!(body["applied"], false);
+    assert_eq!(stored_results(&harness, &vote_id).await.unwrap().calculated_at, stored.calculated_at);
+    assert_eq!(harness.audit_log.entries().await.unwrap().len(), audited);
+}
+
+#[tokio::test]
+async fn test_changed_recompute_requires_confirm_and_is_audited() {
+    let harness = harness("admin").await;
+    let vote_id = completed_vote(&harness).await;
+    // 模拟旧聚合器写入的错误结果
+    let wrong = VoteResults {
+        vote_id: vote_id.clone(),
+        total_votes: 2,
+        results: json!({ "\"yes\"": 2 }),
+        calculated_at: chrono::Utc::now(),
+        winner: None,
+        commitment_root: None,
+        reveal_secret_key: None,
+    };
+    harness.votes.update_vote_results(&VoteId::parse(vote_id.as_str()).unwrap(), &wrong).await.unwrap();
+
+    let (status, body) = post(&harness, &format!("/sessions/{}/recompute-results", vote_id)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "session.recompute_unconfirmed");
+    assert_eq!(body["details"]["after"]["results"], json!({ "\"yes\"": 1, "\"no\"": 1 }));
+    assert_eq!(stored_results(&harness, &vote_id).await.unwrap().results, wrong.results);
+    assert!(harness.audit_log.entries().await.unwrap().is_empty());
+
+    let (status, body) = post(&harness, &format!("/sessions/{}/recompute-results?confirm=true", vote_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["applied"], true);
+    assert_eq!(stored_results(&harness, &vote_id).await.unwrap().results, json!({ "\"yes\"": 1, "\"no\"": 1 }));
+
+    let entries = harness.audit_log.entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].session_id.as_deref(), Some(vote_id.as_str()));
+    assert_eq!(entries[0].metadata["before"]["results"], wrong.results);
+    assert_eq!(entries[0].metadata["after"]["results"], json!({ "\"yes\"": 1, "\"no\"": 1 }));
+}
+
+#[tokio::test]
+async fn test_recompute_only_applies_to_completed_votes() {
+    let harness = harness("admin").await;
+    let vote_id = completed_vote(&harness).await;
+    let open = harness.create_vote("Open").await;
+
+    let (status, body) = post(&harness, &format!("/sessions/{}/recompute-results?confirm=true", open)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "vote.invalid_state");
+
+    let viewer = common::harness("viewer").await;
+    let (status, _) = post(&viewer, &format!("/sessions/{}/recompute-results", vote_id)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_recomputes_apply_once() {
+    let harness = harness("admin").await;
+    let vote_id = completed_vote(&harness).await;
+    for _ in 0..20 {
+        let wrong = VoteResults {
+            vote_id: vote_id.clone(),
+            total_votes: 2,
+            results: json!({ "\"yes\"": 2 }),
+            calculated_at: chrono::Utc::now(),
+            winner: None,
+            commitment_root: None,
+            reveal_secret_key: None,
+        };
+        harness.votes.update_vote_results(&VoteId::parse(vote_id.as_str()).unwrap(), &wrong).await.unwrap();
+
+        // 比较与写入在同一把锁内，后到的一方看到的是已修正的结果
+        let (a, b) = tokio::join!(
+            harness.engine.recompute_results(&vote_id, true),
+            harness.engine.recompute_results(&vote_id, true),
+        );
+        let applied: Vec<bool> = [a.unwrap(), b.unwrap()].iter().map(|r| r.applied).collect();
+        assert_eq!(applied.iter().filter(|applied| **applied).count(), 1, "{:?}", applied);
+    }
+}
@@ -0,0 +1,64 @@
+use admin_api::auth::{LoginRequest, UpdateUserRequest};
+use admin_api::{AdminError, AuthService};
+
+fn login_request() -> LoginRequest {
+    LoginRequest { username: "admin".to_string(), password: "admin123".to_string() }
+}
+
+fn assert_revoked(result: Result<admin_api::auth::Claims, AdminError>) {
+    assert!(matches!(result, Err(AdminError::Authentication(ref message)) if message == "Token has been revoked"));
+}
+
+#[tokio::test]
+async fn test_revoked_token_fails_and_relogin_works() {
+    let mut auth = AuthService::new("secret".to_string(), 1);
+    let first = auth.login(login_request()).await.unwrap().access_token;
+    assert!(auth.verify_token(&first).is_ok());
+
+    auth.revoke_token(&first).unwrap();
+    assert_revoked(auth.verify_token(&first));
+
+    let second = auth.login(login_request()).await.unwrap().access_token;
+    assert!(auth.verify_token(&second).is_ok());
+}
+
+#[tokio::test]
+async fn test_revoke_all_for_user_covers_every_session() {
+    let mut auth = AuthService::new("secret".to_string(), 1);
+    let first = auth.login(login_request()).await.unwrap();
+    let second = auth.login(login_request()).await.unwrap();
+
+    assert_eq!(auth.revoke_all_for_user(first.user.id), 4);
+    assert_revoked(auth.verify_token(&first.access_token));
+    assert_revoked(auth.verify_token(&second.access_token));
+    assert_revoked(auth.verify_token(&second.refresh_token));
+}
+
+#[tokio::test]
+async fn test_disabling_user_revokes_tokens() {
+    let mut auth = AuthService::new("secret".to_string(), 1);
+    let response = auth.login(login_request()).await.unwrap();
+
+    let update = UpdateUserRequest { username: None, email: None, role: None, is_active: Some(false) };
+    auth.update_user(response.user.id, update).await.unwrap();
+    assert_revoked(auth.verify_token(&response.access_token));
+}
+
+#[tokio::test]
+async fn test_deleting_user_revokes_tokens() {
+    let mut auth = AuthService::new("secret".to_string(), 1);
+    let response = auth.login(login_request()).await.unwrap();
+
+    auth.delete_user(response.user.id).await.unwrap();
+    assert_revoked(auth.verify_token(&response.access_token));
+}
+
+#[tokio::test]
+async fn test_revocation_is_shared_between_clones() {
+    let mut auth = AuthService::new("secret".to_string(), 1);
+    let token = auth.login(login_request()).await.unwrap().access_token;
+
+    // Handlers operate on clones of the service
+    auth.clone().revoke_token(&token).unwrap();
+    assert_revoked(auth.verify_token(&token));
+}
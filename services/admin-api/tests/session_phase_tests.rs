@@ -0,0 +1,59 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{harness, Harness};
+
+async fn advance(harness: &Harness, vote_id: &str) -> (StatusCode, serde_json::Value) {
+    harness.request(Method::POST, &format!("/sessions/{}/advance", vote_id), None).await
+}
+
+#[tokio::test]
+async fn test_advance_moves_vote_to_next_phase() {
+    let harness = harness("admin").await;
+    let vote_id = harness.create_vote("Stuck vote").await;
+
+    let (status, body) = advance(&harness, &vote_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["session_id"], vote_id.as_str());
+    assert_eq!(body["status"], "CommitmentPhase");
+    assert_eq!(harness.engine.get_vote(&vote_id).await.unwrap().status, shared_types::VoteStatus::CommitmentPhase);
+}
+
+#[tokio::test]
+async fn test_advance_from_completed_conflicts() {
+    let harness = harness("admin").await;
+    let vote_id = harness.create_vote("Stuck vote").await;
+    for expected in ["CommitmentPhase", "RevealPhase", "Completed"] {
+        let (status, body) = advance(&harness, &vote_id).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], expected);
+    }
+
+    let (status, body) = advance(&harness, &vote_id).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "vote.invalid_state");
+}
+
+#[tokio::test]
+async fn test_results_of_vote_advanced_before_its_deadline_are_readable() {
+    let harness = harness("admin").await;
+    let vote_id = harness.create_vote("Stuck vote").await;
+    for _ in 0..3 {
+        assert_eq!(advance(&harness, &vote_id).await.0, StatusCode::OK);
+    }
+
+    let vote = harness.engine.get_vote(&vote_id).await.unwrap();
+    assert!(chrono::Utc::now() < vote.reveal_end);
+    let results = harness.engine.get_results(&vote_id).await.unwrap();
+    assert_eq!(results.calculated_at, vote.results.unwrap().calculated_at);
+}
+
+#[tokio::test]
+async fn test_advance_requires_manage_session_phase_permission() {
+    let harness = harness("viewer").await;
+    let vote_id = harness.create_vote("Stuck vote").await;
+
+    let (status, body) = advance(&harness, &vote_id).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "auth.permission_denied");
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Method, Request, StatusCode};
+use common::{harness, Harness};
+use serde_json::Value;
+
+const CSV: &str = "\
+username,email,role,initial_password
+alice,alice@example.com,moderator,Str0ng!Pass
+bob,,superuser,Str0ng!Pass
+carol,carol@example.com,viewer,Str0ng!Pass
+";
+
+async fn import(harness: &Harness, csv: &str, query: &str) -> (StatusCode, Value) {
+    let request = Request::post(format!("/users/import{}", query))
+        .header("Authorization", format!("Bearer {}", harness.token))
+        .header("Content-Type", "text/csv")
+        .body(Body::from(csv.to_string()))
+        .unwrap();
+    harness.call(request).await
+}
+
+#[tokio::test]
+async fn test_invalid_role_row_does_not_abort_import() {
+    let harness = harness("admin").await;
+    let (status, report) = import(&harness, CSV, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["dry_run"], false);
+    assert_eq!(report["succeeded"], 2);
+    assert_eq!(report["failed"], 1);
+
+    let rows = report["rows"].as_array().unwrap();
+    assert_eq!(rows[0]["username"], "alice");
+    assert_eq!(rows[0]["success"], true);
+    assert_eq!(rows[0]["user"]["role"], "moderator");
+    assert_eq!(rows[1]["line"], 3);
+    assert_eq!(rows[1]["success"], false);
+    assert_eq!(rows[1]["error"], "Unknown role: superuser");
+    assert_eq!(rows[2]["username"], "carol");
+    assert_eq!(rows[2]["success"], true);
+
+    // 导入的用户已持久化，可以查询和登录
+    let (status, users) = harness.request(Method::GET, "/users", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let usernames: Vec<&str> = users["items"].as_array().unwrap().iter().map(|u| u["username"].as_str().unwrap()).collect();
+    assert!(usernames.contains(&"alice") && usernames.contains(&"carol"), "{:?}", usernames);
+    assert!(!usernames.contains(&"bob"));
+    assert!(harness.login("alice", "Str0ng!Pass").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_weak_password_and_duplicate_rows_fail_individually() {
+    let csv = "\
+username,email,role,initial_password
+dave,,viewer,weak
+erin,,viewer,Str0ng!Pass
+erin,,viewer,Str0ng!Pass
+admin,,viewer,Str0ng!Pass
+";
+    let (_, report) = import(&harness("admin").await, csv, "").await;
+    let rows = report["rows"].as_array().unwrap();
+    assert!(rows[0]["error"].as_str().unwrap().contains("at least 8 characters"));
+    assert_eq!(rows[1]["success"], true);
+    assert_eq!(rows[2]["error"], "Duplicate username in import");
+    assert!(rows[3]["error"].as_str().unwrap().contains("Username already exists"));
+}
+
+#[tokio::test]
+async fn test_dry_run_validates_without_creating() {
+    let harness = harness("admin").await;
+    let (status, report) = import(&harness, CSV, "?dry_run=true").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["succeeded"], 2);
+    assert_eq!(report["failed"], 1);
+    assert!(report["rows"][0].get("user").is_none());
+    assert_eq!(harness.login("alice", "Str0ng!Pass").await, Err(StatusCode::UNAUTHORIZED));
+}
+
+#[tokio::test]
+async fn test_import_requires_authentication() {
+    let harness = harness("admin").await;
+    let request = Request::post("/users/import").body(Body::from(CSV)).unwrap();
+    assert_eq!(harness.call(request).await.0, StatusCode::UNAUTHORIZED);
+}
@@ -3,6 +3,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// 重试配置，与其他服务的出站webhook共用
+pub use shared_config::RetryConfig;
+
 /// 通知服务配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationConfig {
@@ -87,6 +90,9 @@ pub struct WebhookConfig {
     pub retry_interval: u64,
     /// 自定义headers
     pub default_headers: HashMap<String, String>,
+    /// 签名密钥，设置后对每次投递进行HMAC-SHA256签名
+    #[serde(default)]
+    pub secret: Option<String>,
 }
 
 impl Default for WebhookConfig {
@@ -96,6 +102,7 @@ impl Default for WebhookConfig {
             max_retries: 3,
             retry_interval: 5,
             default_headers: HashMap::new(),
+            secret: None,
         }
     }
 }
@@ -131,6 +138,9 @@ pub struct EventsConfig {
     pub queue_size: usize,
     /// 事件处理线程数
     pub worker_threads: usize,
+    /// 每出队多少条消息，等待中的低优先级消息提升一级；0 表示不提升
+    #[serde(default = "default_priority_aging_step")]
+    pub priority_aging_step: u64,
     /// 事件持久化
     pub persistence: EventPersistenceConfig,
     /// 事件过滤
@@ -142,12 +152,17 @@ impl Default for EventsConfig {
         Self {
             queue_size: 10000,
             worker_threads: 4,
+            priority_aging_step: default_priority_aging_step(),
             persistence: EventPersistenceConfig::default(),
             filtering: EventFilteringConfig::default(),
         }
     }
 }
 
+fn default_priority_aging_step() -> u64 {
+    16
+}
+
 /// 事件持久化配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventPersistenceConfig {
@@ -227,8 +242,25 @@ pub struct WebSocketConfig {
     pub max_connections: usize,
     /// 连接超时时间（秒）
     pub connection_timeout: u64,
-    /// 心跳间隔（秒）
+    /// 心跳间隔（秒），服务端按此间隔发送 ping，0 表示不发送
     pub heartbeat_interval: u64,
+    /// 连续未收到 pong 的 ping 数达到此值时断开连接
+    #[serde(default = "default_max_missed_pongs")]
+    pub max_missed_pongs: u32,
+    /// 双向都没有消息帧超过此时长（秒）时断开连接，0 表示不限制
+    #[serde(default)]
+    pub idle_timeout: u64,
+    /// 订阅会话时最多补发的历史事件数，0 表示不补发
+    #[serde(default = "default_catch_up_limit")]
+    pub catch_up_limit: usize,
+}
+
+fn default_max_missed_pongs() -> u32 {
+    3
+}
+
+fn default_catch_up_limit() -> usize {
+    100
 }
 
 impl Default for WebSocketConfig {
@@ -240,33 +272,9 @@ impl Default for WebSocketConfig {
             max_connections: 1000,
             connection_timeout: 30,
             heartbeat_interval: 30,
-        }
-    }
-}
-
-/// 重试配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RetryConfig {
-    /// 最大重试次数
-    pub max_retries: u32,
-    /// 初始重试间隔（秒）
-    pub initial_interval: u64,
-    /// 最大重试间隔（秒）
-    pub max_interval: u64,
-    /// 重试间隔倍数
-    pub multiplier: f64,
-    /// 随机化因子
-    pub jitter: f64,
-}
-
-impl Default for RetryConfig {
-    fn default() -> Self {
-        Self {
-            max_retries: 3,
-            initial_interval: 1,
-            max_interval: 60,
-            multiplier: 2.0,
-            jitter: 0.1,
+            max_missed_pongs: default_max_missed_pongs(),
+            idle_timeout: 0,
+            catch_up_limit: default_catch_up_limit(),
         }
     }
 }
@@ -336,3 +344,59 @@ impl Default for NotificationConfig {
         }
     }
 }
+
+impl NotificationConfig {
+    /// 从YAML文件加载配置
+    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let config: NotificationConfig = serde_yaml::from_str(&content)?;
+        Ok(config)
+    }
+
+    /// 从 `NOTIFICATION_CONFIG_FILE` 指定的文件加载配置，未设置时使用默认配置
+    pub fn load() -> anyhow::Result<Self> {
+        match std::env::var("NOTIFICATION_CONFIG_FILE") {
+            Ok(path) => Self::load_from_file(path),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// 校验配置
+    pub fn validate(&self) -> Result<(), crate::NotificationError> {
+        if self.server.port == 0 {
+            return Err(crate::NotificationError::Configuration("server.port must not be 0".to_string()));
+        }
+        if self.events.queue_size == 0 {
+            return Err(crate::NotificationError::Configuration("events.queue_size must be greater than 0".to_string()));
+        }
+        if self.logging.level.trim().is_empty() {
+            return Err(crate::NotificationError::Configuration("logging.level must not be empty".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 返回与 `other` 不同且需要重启才能生效的配置段
+    pub fn restart_required_changes(&self, other: &NotificationConfig) -> Vec<&'static str> {
+        fn differs<T: Serialize>(a: &T, b: &T) -> bool {
+            serde_json::to_value(a).ok() != serde_json::to_value(b).ok()
+        }
+
+        let mut changes = Vec::new();
+        if differs(&self.server, &other.server) {
+            changes.push("server");
+        }
+        if differs(&self.events, &other.events) {
+            changes.push("events");
+        }
+        if differs(&self.websocket, &other.websocket) {
+            changes.push("websocket");
+        }
+        if self.logging.format != other.logging.format
+            || self.logging.file_output != other.logging.file_output
+            || self.logging.file_path != other.logging.file_path
+        {
+            changes.push("logging.output");
+        }
+        changes
+    }
+}
@@ -0,0 +1,152 @@
+//! Dead-letter queue for notifications that failed permanently
+
+use crate::delivery::DeliveryAttempt;
+use crate::{NotificationError, NotificationMessage};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use event_store::{Event, EventSeverity, EventStorage, EventType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// 死信使用的自定义事件类型
+const DEAD_LETTER_EVENT_TYPE: &str = "DeadLetter";
+
+/// 死信
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub id: Uuid,
+    pub message: NotificationMessage,
+    /// 最终失败的提供者
+    pub provider: String,
+    pub last_error: String,
+    /// 提供者记录的投递尝试，未记录投递日志的提供者为空
+    pub attempts: Vec<DeliveryAttempt>,
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    pub fn new(message: NotificationMessage, provider: String, last_error: String, attempts: Vec<DeliveryAttempt>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            message,
+            provider,
+            last_error,
+            attempts,
+            dead_lettered_at: Utc::now(),
+        }
+    }
+}
+
+/// 死信存储 trait
+#[async_trait]
+pub trait DeadLetterStore: Send + Sync {
+    /// 写入死信
+    async fn push(&self, letter: DeadLetter) -> Result<(), NotificationError>;
+
+    /// 按进入时间列出所有死信
+    async fn list(&self) -> Result<Vec<DeadLetter>, NotificationError>;
+
+    /// 取出并删除死信
+    async fn take(&self, id: Uuid) -> Result<Option<DeadLetter>, NotificationError>;
+}
+
+/// 内存死信存储
+#[derive(Default)]
+pub struct MemoryDeadLetterStore {
+    letters: RwLock<HashMap<Uuid, DeadLetter>>,
+}
+
+impl MemoryDeadLetterStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DeadLetterStore for MemoryDeadLetterStore {
+    async fn push(&self, letter: DeadLetter) -> Result<(), NotificationError> {
+        self.letters.write().await.insert(letter.id, letter);
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetter>, NotificationError> {
+        let mut letters: Vec<DeadLetter> = self.letters.read().await.values().cloned().collect();
+        letters.sort_by_key(|l| l.dead_lettered_at);
+        Ok(letters)
+    }
+
+    async fn take(&self, id: Uuid) -> Result<Option<DeadLetter>, NotificationError> {
+        Ok(self.letters.write().await.remove(&id))
+    }
+}
+
+/// 基于事件存储的死信存储
+///
+/// 每条死信对应一条事件，事件ID即死信ID，取出时删除该事件
+pub struct EventStoreDeadLetterStore {
+    storage: Arc<dyn EventStorage>,
+}
+
+impl EventStoreDeadLetterStore {
+    pub fn new(storage: Arc<dyn EventStorage>) -> Self {
+        Self { storage }
+    }
+
+    fn decode(mut event: Event) -> Result<Option<DeadLetter>, NotificationError> {
+        match event.data.remove("dead_letter") {
+            Some(data) => Ok(Some(serde_json::from_value(data)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl DeadLetterStore for EventStoreDeadLetterStore {
+    async fn push(&self, letter: DeadLetter) -> Result<(), NotificationError> {
+        let mut event = Event::new(
+            EventType::Custom(DEAD_LETTER_EVENT_TYPE.to_string()),
+            EventSeverity::Error,
+            "notification-service".to_string(),
+            format!("Notification {} dead-lettered by {}", letter.message.id, letter.provider),
+            None,
+            None,
+        )
+        .with_correlation_id(letter.message.id)
+        .with_data("dead_letter".to_string(), serde_json::to_value(&letter)?);
+        event.id = letter.id;
+
+        self.storage.store_event(event).await?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetter>, NotificationError> {
+        let events = self.storage
+            .get_events_by_type(&EventType::Custom(DEAD_LETTER_EVENT_TYPE.to_string()))
+            .await?;
+
+        let mut letters = Vec::with_capacity(events.len());
+        for event in events {
+            if let Some(letter) = Self::decode(event)? {
+                letters.push(letter);
+            }
+        }
+        letters.sort_by_key(|l| l.dead_lettered_at);
+        Ok(letters)
+    }
+
+    async fn take(&self, id: Uuid) -> Result<Option<DeadLetter>, NotificationError> {
+        let Some(event) = self.storage.get_event(id).await? else {
+            return Ok(None);
+        };
+        if event.event_type != EventType::Custom(DEAD_LETTER_EVENT_TYPE.to_string()) {
+            return Ok(None);
+        }
+
+        let letter = Self::decode(event)?;
+        self.storage.delete_event(id).await?;
+        Ok(letter)
+    }
+}
@@ -0,0 +1,121 @@
+//! Webhook delivery log backed by the event store
+
+use crate::{NotificationError, NotificationMessage};
+use chrono::{DateTime, Utc};
+use event_store::{Event, EventSeverity, EventStorage, EventType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 投递记录使用的自定义事件类型
+const DELIVERY_EVENT_TYPE: &str = "WebhookDelivery";
+
+/// 投递结果
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Succeeded,
+    Failed,
+}
+
+/// 单次投递尝试
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    pub message: NotificationMessage,
+    pub url: String,
+    pub status: DeliveryStatus,
+    /// 收到响应时的HTTP状态码
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// 重放结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplaySummary {
+    pub succeeded: Vec<Uuid>,
+    pub failed: Vec<Uuid>,
+    /// 已达到最大重试次数而未重放的通知
+    pub skipped: Vec<Uuid>,
+}
+
+impl ReplaySummary {
+    pub fn merge(&mut self, other: ReplaySummary) {
+        self.succeeded.extend(other.succeeded);
+        self.failed.extend(other.failed);
+        self.skipped.extend(other.skipped);
+    }
+}
+
+/// Webhook投递日志
+///
+/// 每次尝试都作为一条事件写入事件存储，使用文件存储时重启后仍可重放
+#[derive(Clone)]
+pub struct DeliveryLog {
+    storage: Arc<dyn EventStorage>,
+}
+
+impl DeliveryLog {
+    pub fn new(storage: Arc<dyn EventStorage>) -> Self {
+        Self { storage }
+    }
+
+    /// 记录一次投递尝试
+    pub async fn record(&self, attempt: &DeliveryAttempt) -> Result<(), NotificationError> {
+        let severity = match attempt.status {
+            DeliveryStatus::Succeeded => EventSeverity::Info,
+            DeliveryStatus::Failed => EventSeverity::Warning,
+        };
+        let event = Event::new(
+            EventType::Custom(DELIVERY_EVENT_TYPE.to_string()),
+            severity,
+            "notification-service".to_string(),
+            format!("Webhook delivery of {} to {}", attempt.message.id, attempt.url),
+            None,
+            None,
+        )
+        .with_correlation_id(attempt.message.id)
+        .with_data("attempt".to_string(), serde_json::to_value(attempt)?);
+
+        self.storage.store_event(event).await?;
+        Ok(())
+    }
+
+    /// 按时间顺序返回所有投递尝试
+    pub async fn attempts(&self) -> Result<Vec<DeliveryAttempt>, NotificationError> {
+        let events = self.storage
+            .get_events_by_type(&EventType::Custom(DELIVERY_EVENT_TYPE.to_string()))
+            .await?;
+
+        let mut attempts = Vec::with_capacity(events.len());
+        for mut event in events {
+            if let Some(data) = event.data.remove("attempt") {
+                attempts.push(serde_json::from_value::<DeliveryAttempt>(data)?);
+            }
+        }
+        attempts.sort_by_key(|a| a.attempted_at);
+        Ok(attempts)
+    }
+
+    /// 某条通知的所有投递尝试
+    pub async fn attempts_for(&self, message_id: Uuid) -> Result<Vec<DeliveryAttempt>, NotificationError> {
+        let mut attempts = self.attempts().await?;
+        attempts.retain(|a| a.message.id == message_id);
+        Ok(attempts)
+    }
+
+    /// 最近一次尝试失败且发生在 `since` 之后的通知，每条通知只返回最近一次尝试
+    pub async fn failed_since(&self, since: DateTime<Utc>) -> Result<Vec<DeliveryAttempt>, NotificationError> {
+        let mut latest: HashMap<Uuid, DeliveryAttempt> = HashMap::new();
+        for attempt in self.attempts().await? {
+            latest.insert(attempt.message.id, attempt);
+        }
+
+        let mut failed: Vec<DeliveryAttempt> = latest
+            .into_values()
+            .filter(|a| a.status == DeliveryStatus::Failed && a.attempted_at >= since)
+            .collect();
+        failed.sort_by_key(|a| a.attempted_at);
+        Ok(failed)
+    }
+}
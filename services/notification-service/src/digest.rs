@@ -0,0 +1,120 @@
+//! Digest delivery: coalesce a subscriber's notifications into periodic summaries
+
+use crate::{EventSubscriber, NotificationMessage, NotificationPriority};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 摘要中保留的样例通知数
+const DIGEST_SAMPLE_SIZE: usize = 3;
+
+/// 投递方式
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DeliveryMode {
+    /// 每条通知立即投递
+    #[default]
+    Immediate,
+    /// 每 `interval` 秒将同类通知合并为一条摘要
+    Digest { interval: u64 },
+}
+
+struct Bucket {
+    subscriber: EventSubscriber,
+    window_end: DateTime<Utc>,
+    messages: Vec<NotificationMessage>,
+}
+
+/// 等待合并为摘要的通知，按接收者和通知类型分桶
+#[derive(Default)]
+pub struct DigestBuffer {
+    buckets: Mutex<HashMap<(String, String), Bucket>>,
+}
+
+impl DigestBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回 `Some` 时应立即投递；摘要模式下的非 `Critical` 通知进入缓冲区
+    pub fn add(
+        &self,
+        subscriber: Option<&EventSubscriber>,
+        message: NotificationMessage,
+        now: DateTime<Utc>,
+    ) -> Option<NotificationMessage> {
+        let Some(subscriber) = subscriber else {
+            return Some(message);
+        };
+        let DeliveryMode::Digest { interval } = subscriber.delivery_mode else {
+            return Some(message);
+        };
+        if message.priority == NotificationPriority::Critical {
+            return Some(message);
+        }
+
+        let key = (message.recipient.clone(), message.notification_type.to_string());
+        self.buckets
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| Bucket {
+                subscriber: subscriber.clone(),
+                window_end: now + Duration::seconds(interval as i64),
+                messages: Vec::new(),
+            })
+            .messages
+            .push(message);
+        None
+    }
+
+    /// 取出窗口已结束的摘要，连同所属订阅者一起返回
+    pub fn take_due(&self, now: DateTime<Utc>) -> Vec<(EventSubscriber, NotificationMessage)> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let due: Vec<_> = buckets
+            .iter()
+            .filter(|(_, bucket)| bucket.window_end <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        due.into_iter()
+            .filter_map(|key| buckets.remove(&key))
+            .map(|bucket| (bucket.subscriber, summarize(bucket.messages)))
+            .collect()
+    }
+
+    /// 缓冲区中等待合并的通知数
+    pub fn pending_count(&self) -> usize {
+        self.buckets.lock().unwrap().values().map(|b| b.messages.len()).sum()
+    }
+}
+
+/// 将同一桶内的通知合并为一条摘要
+fn summarize(messages: Vec<NotificationMessage>) -> NotificationMessage {
+    let first = &messages[0];
+    let notification_type = first.notification_type.clone();
+    let priority = messages
+        .iter()
+        .map(|m| m.priority.clone())
+        .max_by_key(NotificationPriority::rank)
+        .unwrap_or(NotificationPriority::Low);
+
+    let sample: Vec<serde_json::Value> = messages
+        .iter()
+        .take(DIGEST_SAMPLE_SIZE)
+        .map(|m| serde_json::json!({ "id": m.id, "title": m.title, "content": m.content }))
+        .collect();
+    let ids: Vec<serde_json::Value> = messages.iter().map(|m| serde_json::json!(m.id)).collect();
+
+    NotificationMessage::new(
+        notification_type.clone(),
+        priority,
+        format!("{} × {}", messages.len(), first.title),
+        format!("{} {} notifications since {}", messages.len(), notification_type, first.created_at),
+        first.recipient.clone(),
+    )
+    .with_metadata("digest_count".to_string(), serde_json::json!(messages.len()))
+    .with_metadata("digest_sample".to_string(), serde_json::Value::Array(sample))
+    .with_metadata("digest_message_ids".to_string(), serde_json::Value::Array(ids))
+}
@@ -1,6 +1,7 @@
 //! Event handling for notification service
 
 use crate::{NotificationType, NotificationMessage, EventSubscriber};
+use shared_types::Locale;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -48,6 +49,7 @@ pub struct EventHandler {
     event_sender: broadcast::Sender<NotificationEvent>,
     #[allow(dead_code)]
     event_receiver: broadcast::Receiver<NotificationEvent>,
+    message_sender: Option<broadcast::Sender<NotificationMessage>>,
 }
 
 impl EventHandler {
@@ -57,9 +59,16 @@ impl EventHandler {
             subscribers: HashMap::new(),
             event_sender: sender,
             event_receiver: receiver,
+            message_sender: None,
         }
     }
 
+    /// 把为订阅者生成的通知发到 `sender`，由服务按摘要和免打扰时段投递
+    pub fn with_message_sender(mut self, sender: broadcast::Sender<NotificationMessage>) -> Self {
+        self.message_sender = Some(sender);
+        self
+    }
+
     /// 订阅事件
     pub fn subscribe(&mut self, subscriber: EventSubscriber) -> Result<Uuid> {
         let id = subscriber.id;
@@ -114,13 +123,17 @@ impl EventHandler {
             }
 
             // 创建通知消息
-            let _message = self.create_notification_message(subscriber, event)?;
+            let message = self.create_notification_message(subscriber, event)?;
             
-            // 这里应该发送到通知队列，暂时只记录日志
             info!(
                 "Notifying subscriber {} (ID: {}) about event {} (ID: {})",
                 subscriber.name, subscriber_id, event.event_type, event.id
             );
+            if let Some(sender) = &self.message_sender {
+                if let Err(e) = sender.send(message) {
+                    warn!("No notification forwarder for subscriber {}: {}", subscriber.name, e);
+                }
+            }
             
             notified_count += 1;
         }
@@ -149,7 +162,7 @@ impl EventHandler {
         subscriber: &EventSubscriber,
         event: &NotificationEvent,
     ) -> Result<NotificationMessage> {
-        let (title, content) = self.generate_message_content(event);
+        let (title, content) = self.generate_message_content(event, subscriber.locale.unwrap_or_default());
         
         let message = NotificationMessage::new(
             event.event_type.clone(),
@@ -166,44 +179,29 @@ impl EventHandler {
         Ok(message)
     }
 
-    /// 生成消息内容
-    fn generate_message_content(&self, event: &NotificationEvent) -> (String, String) {
-        match &event.event_type {
-            NotificationType::SessionCreated => (
-                "新会话已创建".to_string(),
-                format!("会话 {} 已成功创建", 
-                       event.session_id.as_deref().unwrap_or("未知"))
-            ),
-            NotificationType::CommitmentSubmitted => (
-                "承诺已提交".to_string(),
-                format!("会话 {} 中有新的承诺提交", 
-                       event.session_id.as_deref().unwrap_or("未知"))
-            ),
-            NotificationType::RevealPhaseStarted => (
-                "揭示阶段开始".to_string(),
-                format!("会话 {} 的揭示阶段已开始", 
-                       event.session_id.as_deref().unwrap_or("未知"))
-            ),
-            NotificationType::RevealCompleted => (
-                "揭示阶段完成".to_string(),
-                format!("会话 {} 的揭示阶段已完成", 
-                       event.session_id.as_deref().unwrap_or("未知"))
-            ),
-            NotificationType::ResultGenerated => (
-                "结果已生成".to_string(),
-                format!("会话 {} 的结果已生成", 
-                       event.session_id.as_deref().unwrap_or("未知"))
-            ),
+    /// 按订阅者的语言生成消息内容
+    fn generate_message_content(&self, event: &NotificationEvent, locale: Locale) -> (String, String) {
+        let session_id = event.session_id.clone()
+            .unwrap_or_else(|| locale.template("notification.unknown").unwrap_or_default().to_string());
+        let (key, arg, value) = match &event.event_type {
+            NotificationType::SessionCreated => ("session_created", "session_id", session_id),
+            NotificationType::CommitmentSubmitted => ("commitment_submitted", "session_id", session_id),
+            NotificationType::RevealPhaseStarted => ("reveal_phase_started", "session_id", session_id),
+            NotificationType::RevealCompleted => ("reveal_completed", "session_id", session_id),
+            NotificationType::ResultGenerated => ("result_generated", "session_id", session_id),
             NotificationType::SystemError => (
-                "系统错误".to_string(),
-                format!("系统发生错误: {}", 
-                       event.data.get("error").and_then(|v| v.as_str()).unwrap_or("未知错误"))
-            ),
-            NotificationType::Custom(custom_type) => (
-                format!("自定义通知: {}", custom_type),
-                format!("收到自定义事件: {}", custom_type)
+                "system_error",
+                "error",
+                event.data.get("error").and_then(|v| v.as_str()).map(str::to_string)
+                    .unwrap_or_else(|| locale.template("notification.unknown_error").unwrap_or_default().to_string()),
             ),
-        }
+            NotificationType::Custom(custom_type) => ("custom", "custom_type", custom_type.clone()),
+        };
+        let args = [(arg, value.as_str())];
+        let render = |part: &str| {
+            locale.render(&format!("notification.{}.{}", key, part), &args).unwrap_or_default()
+        };
+        (render("title"), render("content"))
     }
 
     /// 获取事件优先级
@@ -230,6 +228,11 @@ impl EventHandler {
         self.subscribers.values().collect()
     }
 
+    /// 按名称查找订阅者，通知的 `recipient` 即订阅者名称
+    pub fn find_subscriber(&self, name: &str) -> Option<&EventSubscriber> {
+        self.subscribers.values().find(|s| s.name == name)
+    }
+
     /// 获取活跃订阅者数量
     pub fn get_active_subscriber_count(&self) -> usize {
         self.subscribers.values().filter(|s| s.active).count()
@@ -241,14 +244,3 @@ impl Default for EventHandler {
         Self::new()
     }
 }
-
-impl Clone for EventHandler {
-    fn clone(&self) -> Self {
-        let (sender, receiver) = broadcast::channel(1000);
-        Self {
-            subscribers: self.subscribers.clone(),
-            event_sender: sender,
-            event_receiver: receiver,
-        }
-    }
-}
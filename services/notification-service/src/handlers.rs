@@ -1,13 +1,14 @@
 //! HTTP handlers for notification service
 
-use crate::{NotificationMessage, EventSubscriber, NotificationType, NotificationPriority};
+use crate::{NotificationEvent, NotificationMessage, EventSubscriber, NotificationType, NotificationPriority};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json},
     routing::{get, post, delete},
     Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{info, error};
@@ -16,8 +17,9 @@ use uuid::Uuid;
 /// 通知服务状态
 #[derive(Clone)]
 pub struct NotificationServiceState {
-    pub event_handler: crate::EventHandler,
-    pub provider_manager: crate::ProviderManager,
+    pub event_handler: std::sync::Arc<std::sync::RwLock<crate::EventHandler>>,
+    pub provider_manager: std::sync::Arc<tokio::sync::RwLock<crate::ProviderManager>>,
+    pub delivery_log: crate::DeliveryLog,
     pub websocket_state: crate::WebSocketState,
 }
 
@@ -28,6 +30,10 @@ pub struct CreateSubscriptionRequest {
     pub event_types: Vec<NotificationType>,
     pub notification_providers: Vec<String>,
     pub filters: Option<HashMap<String, serde_json::Value>>,
+    pub quiet_hours: Option<crate::QuietHours>,
+    #[serde(default)]
+    pub delivery_mode: crate::DeliveryMode,
+    pub locale: Option<shared_types::Locale>,
 }
 
 /// 创建订阅响应
@@ -55,6 +61,13 @@ pub struct SendNotificationResponse {
     pub message: String,
 }
 
+/// 失败投递查询参数 / 重放请求
+#[derive(Debug, Default, Deserialize)]
+pub struct FailedDeliveriesQuery {
+    /// 只包含此时间之后的失败投递，默认全部
+    pub since: Option<DateTime<Utc>>,
+}
+
 /// 服务状态响应
 #[derive(Debug, Serialize)]
 pub struct ServiceStatusResponse {
@@ -69,11 +82,17 @@ pub struct ServiceStatusResponse {
 pub fn create_http_router(state: NotificationServiceState) -> Router {
     Router::new()
         .route("/health", get(health_check))
+        .route("/metrics", get(metrics_handler))
         .route("/status", get(get_service_status))
         .route("/subscriptions", post(create_subscription))
         .route("/subscriptions/:id", delete(delete_subscription))
         .route("/notifications", post(send_notification))
+        .route("/events", post(ingest_event))
+        .route("/notifications/failed", get(list_failed_notifications))
+        .route("/notifications/replay", post(replay_failed_notifications))
         .route("/subscribers", get(list_subscribers))
+        .route("/admin/dead-letters", get(list_dead_letters))
+        .route("/admin/dead-letters/:id/requeue", post(requeue_dead_letter))
         .with_state(state)
 }
 
@@ -86,13 +105,21 @@ async fn health_check() -> Json<serde_json::Value> {
     }))
 }
 
+/// 投递计数、投递耗时等指标，Prometheus 文本格式
+async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        shared_logging::metrics().render(),
+    )
+}
+
 /// 获取服务状态
 async fn get_service_status(
     State(state): State<NotificationServiceState>,
 ) -> Result<Json<ServiceStatusResponse>, StatusCode> {
-    let active_subscribers = state.event_handler.get_active_subscriber_count();
+    let active_subscribers = state.event_handler.read().unwrap().get_active_subscriber_count();
     let websocket_connections = state.websocket_state.get_connection_count().await;
-    let available_providers = state.provider_manager.get_provider_names();
+    let available_providers = state.provider_manager.read().await.get_provider_names();
     
     let response = ServiceStatusResponse {
         status: "running".to_string(),
@@ -107,7 +134,7 @@ async fn get_service_status(
 
 /// 创建事件订阅
 async fn create_subscription(
-    State(mut state): State<NotificationServiceState>,
+    State(state): State<NotificationServiceState>,
     Json(request): Json<CreateSubscriptionRequest>,
 ) -> Result<Json<CreateSubscriptionResponse>, StatusCode> {
     info!("Creating subscription for: {}", request.name);
@@ -131,7 +158,26 @@ async fn create_subscription(
         }
     }
     
-    match state.event_handler.subscribe(subscriber) {
+    if let crate::DeliveryMode::Digest { interval: 0 } = request.delivery_mode {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    subscriber = subscriber.with_delivery_mode(request.delivery_mode);
+    if let Some(locale) = request.locale {
+        subscriber = subscriber.with_locale(locale);
+    }
+    
+    // 设置免打扰时段
+    if let Some(quiet_hours) = request.quiet_hours {
+        match crate::QuietHours::new(quiet_hours.start, quiet_hours.end, &quiet_hours.timezone) {
+            Ok(quiet_hours) => subscriber = subscriber.with_quiet_hours(quiet_hours),
+            Err(e) => {
+                error!("Invalid quiet hours for {}: {}", subscriber.name, e);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        }
+    }
+    
+    match state.event_handler.write().unwrap().subscribe(subscriber) {
         Ok(subscriber_id) => {
             info!("Successfully created subscription: {}", subscriber_id);
             Ok(Json(CreateSubscriptionResponse {
@@ -146,14 +192,36 @@ async fn create_subscription(
     }
 }
 
+/// 接收其他服务转发的事件并发布给订阅者
+///
+/// 带会话ID的事件与 `NotificationService::publish_event` 一样先写入会话事件流，供迟到的订阅者补发
+async fn ingest_event(
+    State(state): State<NotificationServiceState>,
+    Json(event): Json<NotificationEvent>,
+) -> StatusCode {
+    if let (Some(_), Some(feed)) = (&event.session_id, &state.websocket_state.session_feed) {
+        if let Err(e) = feed.publish(event.clone()).await {
+            error!("Failed to record ingested event in session feed: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+    }
+    match state.event_handler.read().unwrap().publish_event(event) {
+        Ok(()) => StatusCode::ACCEPTED,
+        Err(e) => {
+            error!("Failed to publish ingested event: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
 /// 删除事件订阅
 async fn delete_subscription(
-    State(mut state): State<NotificationServiceState>,
+    State(state): State<NotificationServiceState>,
     Path(subscriber_id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     info!("Deleting subscription: {}", subscriber_id);
     
-    match state.event_handler.unsubscribe(subscriber_id) {
+    match state.event_handler.write().unwrap().unsubscribe(subscriber_id) {
         Ok(_) => {
             info!("Successfully deleted subscription: {}", subscriber_id);
             Ok(Json(serde_json::json!({
@@ -191,7 +259,7 @@ async fn send_notification(
     }
     
     // 发送到所有可用的提供者
-    let results = state.provider_manager.send_to_all_providers(&message).await;
+    let results = state.provider_manager.read().await.send_to_all_providers(&message).await;
     
     let mut success_count = 0;
     let mut failure_count = 0;
@@ -225,7 +293,71 @@ async fn send_notification(
 async fn list_subscribers(
     State(state): State<NotificationServiceState>,
 ) -> Result<Json<Vec<EventSubscriber>>, StatusCode> {
-    let subscribers = state.event_handler.get_subscribers();
-    let owned_subscribers: Vec<EventSubscriber> = subscribers.into_iter().cloned().collect();
+    let owned_subscribers: Vec<EventSubscriber> =
+        state.event_handler.read().unwrap().get_subscribers().into_iter().cloned().collect();
     Ok(Json(owned_subscribers))
 }
+
+/// 列出最近一次投递失败的通知
+async fn list_failed_notifications(
+    State(state): State<NotificationServiceState>,
+    Query(query): Query<FailedDeliveriesQuery>,
+) -> Result<Json<Vec<crate::DeliveryAttempt>>, StatusCode> {
+    let since = query.since.unwrap_or(DateTime::<Utc>::MIN_UTC);
+    match state.delivery_log.failed_since(since).await {
+        Ok(failed) => Ok(Json(failed)),
+        Err(e) => {
+            error!("Failed to read delivery log: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 重放投递失败的通知
+async fn replay_failed_notifications(
+    State(state): State<NotificationServiceState>,
+    request: Option<Json<FailedDeliveriesQuery>>,
+) -> Result<Json<crate::ReplaySummary>, StatusCode> {
+    let since = request.and_then(|Json(r)| r.since).unwrap_or(DateTime::<Utc>::MIN_UTC);
+    info!("Replaying failed notifications since {}", since);
+    
+    match state.provider_manager.read().await.replay_failed(since).await {
+        Ok(summary) => Ok(Json(summary)),
+        Err(e) => {
+            error!("Failed to replay notifications: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 列出死信
+async fn list_dead_letters(
+    State(state): State<NotificationServiceState>,
+) -> Result<Json<Vec<crate::DeadLetter>>, StatusCode> {
+    match state.provider_manager.read().await.list_dead_letters().await {
+        Ok(letters) => Ok(Json(letters)),
+        Err(e) => {
+            error!("Failed to read dead-letter queue: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// 重新投递死信
+async fn requeue_dead_letter(
+    State(state): State<NotificationServiceState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    info!("Requeueing dead letter: {}", id);
+    
+    match state.provider_manager.read().await.requeue(id).await {
+        Ok(()) => Ok(Json(serde_json::json!({
+            "message": "Dead letter delivered"
+        }))),
+        Err(crate::NotificationError::NotFound(_)) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            error!("Failed to requeue dead letter {}: {}", id, e);
+            Err(StatusCode::BAD_GATEWAY)
+        }
+    }
+}
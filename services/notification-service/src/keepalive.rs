@@ -0,0 +1,112 @@
+//! Server-side WebSocket keepalive: periodic pings, missed-pong and idle disconnects
+
+use crate::config::WebSocketConfig;
+use std::time::{Duration, Instant};
+
+/// 心跳配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepaliveConfig {
+    /// ping 间隔，为零时不发送 ping
+    pub ping_interval: Duration,
+    /// 连续未应答的 ping 达到此数时断开
+    pub max_missed_pongs: u32,
+    /// 双向都没有消息帧超过此时长时断开，为空表示不限制
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for KeepaliveConfig {
+    fn default() -> Self {
+        Self::from(&WebSocketConfig::default())
+    }
+}
+
+impl From<&WebSocketConfig> for KeepaliveConfig {
+    fn from(config: &WebSocketConfig) -> Self {
+        Self {
+            ping_interval: Duration::from_secs(config.heartbeat_interval),
+            max_missed_pongs: config.max_missed_pongs.max(1),
+            idle_timeout: (config.idle_timeout > 0).then(|| Duration::from_secs(config.idle_timeout)),
+        }
+    }
+}
+
+impl KeepaliveConfig {
+    /// 检查心跳的周期：发送 ping 时为 ping 间隔，否则为空闲超时；两者都未启用时为空
+    pub fn tick_interval(&self) -> Option<Duration> {
+        if !self.ping_interval.is_zero() {
+            Some(self.ping_interval)
+        } else {
+            self.idle_timeout
+        }
+    }
+}
+
+/// 心跳检查的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepaliveAction {
+    /// 发送一个 ping
+    Ping,
+    /// 无需操作
+    Wait,
+    /// 断开连接
+    Disconnect(DisconnectReason),
+}
+
+/// 心跳断开连接的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    MissedPongs,
+    Idle,
+}
+
+impl DisconnectReason {
+    /// 指标标签
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisconnectReason::MissedPongs => "missed_pongs",
+            DisconnectReason::Idle => "idle",
+        }
+    }
+}
+
+/// 单个连接的心跳状态
+#[derive(Debug)]
+pub struct Keepalive {
+    config: KeepaliveConfig,
+    /// 已发送但尚未收到 pong 的 ping 数
+    unanswered: u32,
+    last_activity: Instant,
+}
+
+impl Keepalive {
+    pub fn new(config: KeepaliveConfig, now: Instant) -> Self {
+        Self { config, unanswered: 0, last_activity: now }
+    }
+
+    /// 收到 pong，之前的 ping 都视为已应答
+    pub fn on_pong(&mut self) {
+        self.unanswered = 0;
+    }
+
+    /// 收到或发出消息帧
+    pub fn on_activity(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// 每个心跳周期调用一次，决定发送 ping 还是断开连接
+    pub fn tick(&mut self, now: Instant) -> KeepaliveAction {
+        if let Some(idle_timeout) = self.config.idle_timeout {
+            if now.duration_since(self.last_activity) >= idle_timeout {
+                return KeepaliveAction::Disconnect(DisconnectReason::Idle);
+            }
+        }
+        if self.config.ping_interval.is_zero() {
+            return KeepaliveAction::Wait;
+        }
+        if self.unanswered >= self.config.max_missed_pongs {
+            return KeepaliveAction::Disconnect(DisconnectReason::MissedPongs);
+        }
+        self.unanswered += 1;
+        KeepaliveAction::Ping
+    }
+}
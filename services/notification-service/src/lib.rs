@@ -8,14 +8,31 @@ pub mod handlers;
 pub mod providers;
 pub mod events;
 pub mod websocket;
+pub mod keepalive;
+pub mod delivery;
+pub mod dead_letter;
+pub mod signing;
+pub mod queue;
+pub mod quiet_hours;
+pub mod digest;
+pub mod session_feed;
 
 pub use config::NotificationConfig;
 pub use service::NotificationService;
 pub use events::{NotificationEvent, EventHandler};
 pub use providers::{NotificationProvider, EmailProvider, WebhookProvider, WebSocketProvider, ProviderManager};
-pub use websocket::WebSocketState;
+pub use websocket::{WebSocketState, WebSocketServer, WireFormat, SessionEventFrame, SubscriptionControl, ConnectionSubscriptions};
+pub use keepalive::{Keepalive, KeepaliveAction, KeepaliveConfig, DisconnectReason};
+pub use delivery::{DeliveryLog, DeliveryAttempt, DeliveryStatus, ReplaySummary};
+pub use dead_letter::{DeadLetter, DeadLetterStore, MemoryDeadLetterStore, EventStoreDeadLetterStore};
+pub use queue::NotificationQueue;
+pub use digest::{DeliveryMode, DigestBuffer};
+pub use session_feed::{CatchUp, SessionEventFeed, SessionSubscription};
+pub use quiet_hours::{QuietHours, DeferredNotifications, DeferredNotification};
+pub use signing::{sign_webhook_payload, verify_webhook_signature, SignatureError};
 
 use serde::{Deserialize, Serialize};
+use shared_types::Locale;
 use std::collections::HashMap;
 use std::fmt;
 use uuid::Uuid;
@@ -39,6 +56,28 @@ pub enum NotificationType {
     Custom(String),
 }
 
+impl NotificationType {
+    /// 除自定义类型外的全部通知类型
+    pub fn builtin() -> Vec<NotificationType> {
+        vec![
+            NotificationType::SessionCreated,
+            NotificationType::CommitmentSubmitted,
+            NotificationType::RevealPhaseStarted,
+            NotificationType::RevealCompleted,
+            NotificationType::ResultGenerated,
+            NotificationType::SystemError,
+        ]
+    }
+
+    /// 指标标签，自定义类型统一记为 `Custom` 以免标签数量无限增长
+    pub fn metric_label(&self) -> String {
+        match self {
+            NotificationType::Custom(_) => "Custom".to_string(),
+            builtin => builtin.to_string(),
+        }
+    }
+}
+
 impl fmt::Display for NotificationType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -62,6 +101,18 @@ pub enum NotificationPriority {
     Critical,
 }
 
+impl NotificationPriority {
+    /// 数值越大越紧急
+    pub fn rank(&self) -> u8 {
+        match self {
+            NotificationPriority::Low => 0,
+            NotificationPriority::Normal => 1,
+            NotificationPriority::High => 2,
+            NotificationPriority::Critical => 3,
+        }
+    }
+}
+
 /// 通知状态
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum NotificationStatus {
@@ -130,6 +181,15 @@ pub struct EventSubscriber {
     pub notification_providers: Vec<String>,
     pub filters: HashMap<String, serde_json::Value>,
     pub active: bool,
+    /// 免打扰时段，期间非紧急通知会被推迟
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    /// 投递方式，默认立即投递
+    #[serde(default)]
+    pub delivery_mode: DeliveryMode,
+    /// 通知语言偏好，未设置时使用英文
+    #[serde(default)]
+    pub locale: Option<Locale>,
 }
 
 impl EventSubscriber {
@@ -141,6 +201,9 @@ impl EventSubscriber {
             notification_providers: Vec::new(),
             filters: HashMap::new(),
             active: true,
+            quiet_hours: None,
+            delivery_mode: DeliveryMode::Immediate,
+            locale: None,
         }
     }
 
@@ -151,6 +214,11 @@ impl EventSubscriber {
         self
     }
 
+    pub fn unsubscribe_from_event(mut self, event_type: &NotificationType) -> Self {
+        self.event_types.retain(|subscribed| subscribed != event_type);
+        self
+    }
+
     pub fn add_provider(mut self, provider: String) -> Self {
         if !self.notification_providers.contains(&provider) {
             self.notification_providers.push(provider);
@@ -162,6 +230,21 @@ impl EventSubscriber {
         self.filters.insert(key, value);
         self
     }
+
+    pub fn with_quiet_hours(mut self, quiet_hours: QuietHours) -> Self {
+        self.quiet_hours = Some(quiet_hours);
+        self
+    }
+
+    pub fn with_delivery_mode(mut self, delivery_mode: DeliveryMode) -> Self {
+        self.delivery_mode = delivery_mode;
+        self
+    }
+
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
 }
 
 /// 通知服务错误
@@ -179,6 +262,9 @@ pub enum NotificationError {
     #[error("WebSocket error: {0}")]
     WebSocket(String),
     
+    #[error("Not found: {0}")]
+    NotFound(String),
+    
     #[error("Email error: {0}")]
     Email(#[from] lettre::error::Error),
     
@@ -191,6 +277,9 @@ pub enum NotificationError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
     
+    #[error("Event store error: {0}")]
+    EventStore(#[from] event_store::EventStoreError),
+    
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     
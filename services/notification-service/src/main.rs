@@ -1,36 +1,74 @@
 use notification_service::{NotificationService, NotificationConfig};
-use shared_logging::init_logging_from_env;
-use tracing::{info, error};
+use shared_logging::{init_logging_from_env, ShutdownSequence};
+use tracing::{info, warn, error};
 use anyhow::Result;
+use std::time::Duration;
+
+/// Timeout for each shutdown stage
+const SHUTDOWN_STAGE_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
     init_logging_from_env().map_err(|e| anyhow::anyhow!("Failed to initialize logging: {}", e))?;
-    
+
     info!("Starting Notification Service");
-    
+
     // Load configuration
-    let config = NotificationConfig::default();
+    let config = NotificationConfig::load()?;
+    config.validate()?;
     info!("Configuration loaded successfully");
-    
+
     // Create and start notification service
     let mut service = NotificationService::new(config).await?;
-    
+
     info!("Notification service is running...");
-    
+
     // Start the service
     if let Err(e) = service.start().await {
         error!("Failed to start notification service: {}", e);
         return Err(e.into());
     }
-    
+
+    // Forward SIGHUP to the main loop so the reload can borrow the service
+    let (reload_tx, mut reload_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+    #[cfg(unix)]
+    let _reload_handle = shared_logging::spawn_sighup_handler(move || {
+        let _ = reload_tx.send(());
+    })?;
+    #[cfg(not(unix))]
+    drop(reload_tx);
+
     // Keep the service running
-    tokio::signal::ctrl_c().await?;
+    loop {
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                result?;
+                break;
+            }
+            Some(()) = reload_rx.recv() => {
+                match NotificationConfig::load() {
+                    Ok(config) => {
+                        if let Err(e) = service.reload_config(config).await {
+                            warn!("Ignoring configuration reload: {}", e);
+                        }
+                    }
+                    Err(e) => warn!("Ignoring configuration reload: {}", e),
+                }
+            }
+        }
+    }
     info!("Notification service shutting down");
-    
-    service.shutdown().await?;
-    info!("Notification service stopped");
-    
+
+    // Stop the servers, forward what was already received, then deliver the queue
+    let mut sequence = ShutdownSequence::new(SHUTDOWN_STAGE_TIMEOUT);
+    service.register_shutdown(&mut sequence);
+    let report = sequence.run().await;
+    if report.is_clean() {
+        info!("Notification service stopped");
+    } else {
+        warn!("Notification service stopped with unfinished shutdown steps");
+    }
+
     Ok(())
 }
@@ -1,11 +1,17 @@
 //! Notification providers implementation
 
-use crate::{NotificationMessage, NotificationError};
+use crate::{NotificationMessage, NotificationError, NotificationStatus};
+use crate::delivery::{DeliveryAttempt, DeliveryLog, DeliveryStatus, ReplaySummary};
+use crate::dead_letter::{DeadLetter, DeadLetterStore};
+use crate::signing::{sign_webhook_payload, SIGNATURE_HEADER, TIMESTAMP_HEADER};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{info, warn, error};
 use lettre::AsyncTransport;
+use uuid::Uuid;
 
 /// 通知提供者 trait
 #[async_trait]
@@ -21,6 +27,16 @@ pub trait NotificationProvider: Send + Sync {
     
     /// 获取提供者配置
     fn get_config(&self) -> &dyn std::fmt::Debug;
+    
+    /// 重新发送 `since` 之后最近一次投递失败的通知；不记录投递日志的提供者无需重放
+    async fn replay_failed(&self, _since: DateTime<Utc>) -> Result<ReplaySummary, NotificationError> {
+        Ok(ReplaySummary::default())
+    }
+    
+    /// 某条通知的投递尝试记录，用于写入死信
+    async fn delivery_history(&self, _message_id: Uuid) -> Result<Vec<DeliveryAttempt>, NotificationError> {
+        Ok(Vec::new())
+    }
 }
 
 /// 邮件通知提供者
@@ -114,6 +130,7 @@ impl EmailProvider {
 pub struct WebhookProvider {
     config: WebhookConfig,
     client: reqwest::Client,
+    delivery_log: Option<DeliveryLog>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +140,9 @@ pub struct WebhookConfig {
     pub max_retries: u32,
     pub retry_interval: u64,
     pub headers: HashMap<String, String>,
+    /// 订阅者的签名密钥，见 [`crate::signing`]
+    #[serde(default)]
+    pub secret: Option<String>,
 }
 
 #[async_trait]
@@ -133,31 +153,12 @@ impl NotificationProvider for WebhookProvider {
 
     async fn send_notification(&self, message: &NotificationMessage) -> Result<(), NotificationError> {
         info!("Sending webhook notification to: {}", self.config.url);
-        
-        let mut request = self.client
-            .post(&self.config.url)
-            .timeout(std::time::Duration::from_secs(self.config.timeout))
-            .json(message);
-
-        // 添加自定义headers
-        for (key, value) in &self.config.headers {
-            request = request.header(key, value);
-        }
 
         let mut retry_count = 0;
         while retry_count <= self.config.max_retries {
-            match request.try_clone().unwrap().send().await {
-                Ok(response) => {
-                    if response.status().is_success() {
-                        info!("Webhook notification sent successfully to: {}", self.config.url);
-                        return Ok(());
-                    } else {
-                        warn!("Webhook request failed with status: {}", response.status());
-                    }
-                }
-                Err(e) => {
-                    error!("Webhook request failed: {}", e);
-                }
+            if self.deliver_once(message).await.is_ok() {
+                info!("Webhook notification sent successfully to: {}", self.config.url);
+                return Ok(());
             }
 
             retry_count += 1;
@@ -183,6 +184,41 @@ impl NotificationProvider for WebhookProvider {
     fn get_config(&self) -> &dyn std::fmt::Debug {
         &self.config
     }
+
+    async fn replay_failed(&self, since: DateTime<Utc>) -> Result<ReplaySummary, NotificationError> {
+        let mut summary = ReplaySummary::default();
+        let Some(ref delivery_log) = self.delivery_log else {
+            return Ok(summary);
+        };
+
+        for attempt in delivery_log.failed_since(since).await? {
+            let mut message = attempt.message;
+            if message.retry_count >= message.max_retries {
+                summary.skipped.push(message.id);
+                continue;
+            }
+
+            message.retry_count += 1;
+            message.status = NotificationStatus::Retrying;
+            match self.deliver_once(&message).await {
+                Ok(()) => summary.succeeded.push(message.id),
+                Err(_) => summary.failed.push(message.id),
+            }
+        }
+
+        info!(
+            "Replayed failed webhook notifications: {} succeeded, {} failed, {} skipped",
+            summary.succeeded.len(), summary.failed.len(), summary.skipped.len()
+        );
+        Ok(summary)
+    }
+
+    async fn delivery_history(&self, message_id: Uuid) -> Result<Vec<DeliveryAttempt>, NotificationError> {
+        match self.delivery_log {
+            Some(ref delivery_log) => delivery_log.attempts_for(message_id).await,
+            None => Ok(Vec::new()),
+        }
+    }
 }
 
 impl WebhookProvider {
@@ -190,6 +226,68 @@ impl WebhookProvider {
         Self {
             config,
             client: reqwest::Client::new(),
+            delivery_log: None,
+        }
+    }
+
+    /// 将每次投递尝试记录到投递日志
+    pub fn with_delivery_log(mut self, delivery_log: DeliveryLog) -> Self {
+        self.delivery_log = Some(delivery_log);
+        self
+    }
+
+    /// 投递一次并记录结果
+    async fn deliver_once(&self, message: &NotificationMessage) -> Result<(), NotificationError> {
+        // 签名需要覆盖实际发送的原始字节，因此只序列化一次
+        let body = serde_json::to_vec(message)?;
+        let mut request = self.client
+            .post(&self.config.url)
+            .timeout(std::time::Duration::from_secs(self.config.timeout))
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        if let Some(ref secret) = self.config.secret {
+            let timestamp = Utc::now().timestamp();
+            request = request
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, sign_webhook_payload(secret, timestamp, &body));
+        }
+
+        // 添加自定义headers
+        for (key, value) in &self.config.headers {
+            request = request.header(key, value);
+        }
+
+        let (status, response_status, failure) = match request.body(body).send().await {
+            Ok(response) if response.status().is_success() => {
+                (DeliveryStatus::Succeeded, Some(response.status().as_u16()), None)
+            }
+            Ok(response) => {
+                warn!("Webhook request failed with status: {}", response.status());
+                (DeliveryStatus::Failed, Some(response.status().as_u16()), Some(format!("HTTP {}", response.status())))
+            }
+            Err(e) => {
+                error!("Webhook request failed: {}", e);
+                (DeliveryStatus::Failed, None, Some(e.to_string()))
+            }
+        };
+
+        if let Some(ref delivery_log) = self.delivery_log {
+            let attempt = DeliveryAttempt {
+                message: message.clone(),
+                url: self.config.url.clone(),
+                status,
+                response_status,
+                error: failure.clone(),
+                attempted_at: Utc::now(),
+            };
+            if let Err(e) = delivery_log.record(&attempt).await {
+                error!("Failed to record webhook delivery for {}: {}", message.id, e);
+            }
+        }
+
+        match failure {
+            None => Ok(()),
+            Some(reason) => Err(NotificationError::Provider(reason)),
         }
     }
 }
@@ -275,15 +373,23 @@ impl WebSocketProvider {
 /// 通知提供者管理器
 pub struct ProviderManager {
     providers: HashMap<String, Box<dyn NotificationProvider>>,
+    dead_letters: Option<Arc<dyn DeadLetterStore>>,
 }
 
 impl ProviderManager {
     pub fn new() -> Self {
         Self {
             providers: HashMap::new(),
+            dead_letters: None,
         }
     }
 
+    /// 将最终投递失败的通知写入死信存储
+    pub fn with_dead_letter_store(mut self, dead_letters: Arc<dyn DeadLetterStore>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
     pub fn add_provider(&mut self, name: String, provider: Box<dyn NotificationProvider>) {
         info!("Adding notification provider: {}", name);
         self.providers.insert(name, provider);
@@ -305,22 +411,94 @@ impl ProviderManager {
 
     pub async fn send_notification(&self, provider_name: &str, message: &NotificationMessage) -> Result<(), NotificationError> {
         if let Some(provider) = self.providers.get(provider_name) {
-            provider.send_notification(message).await
+            self.dispatch(provider_name, provider.as_ref(), message).await
         } else {
             Err(NotificationError::Provider(format!("Provider not found: {}", provider_name)))
         }
     }
 
+    /// 在所有提供者上重放失败的通知
+    pub async fn replay_failed(&self, since: DateTime<Utc>) -> Result<ReplaySummary, NotificationError> {
+        let mut summary = ReplaySummary::default();
+        for provider in self.providers.values() {
+            summary.merge(provider.replay_failed(since).await?);
+        }
+        Ok(summary)
+    }
+
     pub async fn send_to_all_providers(&self, message: &NotificationMessage) -> Vec<(String, Result<(), NotificationError>)> {
         let mut results = Vec::new();
         
         for (name, provider) in &self.providers {
-            let result = provider.send_notification(message).await;
+            let result = self.dispatch(name, provider.as_ref(), message).await;
             results.push((name.clone(), result));
         }
         
         results
     }
+
+    /// 列出所有死信
+    pub async fn list_dead_letters(&self) -> Result<Vec<DeadLetter>, NotificationError> {
+        match self.dead_letters {
+            Some(ref dead_letters) => dead_letters.list().await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// 将死信重新交给原提供者投递，再次失败时会写入一条新的死信
+    pub async fn requeue(&self, dead_letter_id: Uuid) -> Result<(), NotificationError> {
+        let letter = match self.dead_letters {
+            Some(ref dead_letters) => dead_letters.take(dead_letter_id).await?,
+            None => None,
+        }
+        .ok_or_else(|| NotificationError::NotFound(format!("Dead letter not found: {}", dead_letter_id)))?;
+
+        let Some(provider) = self.providers.get(&letter.provider) else {
+            let provider_name = letter.provider.clone();
+            if let Some(ref dead_letters) = self.dead_letters {
+                dead_letters.push(letter).await?;
+            }
+            return Err(NotificationError::Provider(format!("Provider not found: {}", provider_name)));
+        };
+
+        info!("Requeueing dead-lettered notification {} via {}", letter.message.id, letter.provider);
+        let mut message = letter.message;
+        message.status = NotificationStatus::Retrying;
+        self.dispatch(&letter.provider, provider.as_ref(), &message).await
+    }
+
+    /// 通过提供者发送，提供者返回失败即视为重试已用尽；投递结果、重试次数与耗时计入指标
+    async fn dispatch(&self, name: &str, provider: &dyn NotificationProvider, message: &NotificationMessage) -> Result<(), NotificationError> {
+        let started_at = Utc::now();
+        let started = std::time::Instant::now();
+        let result = provider.send_notification(message).await;
+        let elapsed = started.elapsed();
+
+        let attempts = provider.delivery_history(message.id).await.unwrap_or_else(|history_err| {
+            warn!("Failed to read delivery history for {}: {}", message.id, history_err);
+            Vec::new()
+        });
+        // 提供者在本次发送中自行重试的次数；重新投递的死信本身也算一次重试
+        let retries = attempts.iter().filter(|attempt| attempt.attempted_at >= started_at).count().saturating_sub(1) as u64
+            + u64::from(message.status == NotificationStatus::Retrying);
+        let notification_type = message.notification_type.metric_label();
+        let metrics = shared_logging::metrics();
+        metrics.record_notification_delivery(name, &notification_type, result.is_ok(), retries, elapsed);
+
+        if let (Err(ref e), Some(ref dead_letters)) = (&result, &self.dead_letters) {
+            let mut failed = message.clone();
+            failed.status = NotificationStatus::Failed;
+            let letter = DeadLetter::new(failed, name.to_string(), e.to_string(), attempts);
+            match dead_letters.push(letter).await {
+                Ok(()) => {
+                    metrics.record_notification_dead_letter(name, &notification_type);
+                    warn!("Notification {} moved to dead-letter queue after failing via {}", message.id, name);
+                }
+                Err(store_err) => error!("Failed to dead-letter notification {}: {}", message.id, store_err),
+            }
+        }
+        result
+    }
 }
 
 impl Default for ProviderManager {
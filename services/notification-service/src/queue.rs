@@ -0,0 +1,123 @@
+//! Priority queue feeding the notification dispatcher
+//!
+//! Messages are taken in `Critical` > `High` > `Normal` > `Low` order. To keep
+//! low-priority messages from starving under sustained load, a waiting message
+//! is promoted one level for every `aging_step` messages dispatched ahead of it.
+//! Among messages at the same effective level the oldest goes first.
+
+use crate::{NotificationError, NotificationMessage};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+const LEVELS: usize = 4;
+
+struct Entry {
+    message: NotificationMessage,
+    seq: u64,
+    /// 入队时的已出队总数，用于计算等待期间被插队的次数
+    dispatched_before: u64,
+}
+
+#[derive(Default)]
+struct QueueState {
+    lanes: [VecDeque<Entry>; LEVELS],
+    next_seq: u64,
+    /// 已出队的消息总数
+    dispatched: u64,
+}
+
+/// 通知优先级队列
+pub struct NotificationQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+    capacity: usize,
+    aging_step: u64,
+}
+
+impl NotificationQueue {
+    /// `aging_step` 为 0 时不做老化，严格按优先级出队
+    pub fn new(capacity: usize, aging_step: u64) -> Self {
+        Self {
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+            capacity,
+            aging_step,
+        }
+    }
+
+    /// 入队，队列已满时返回错误
+    pub fn push(&self, message: NotificationMessage) -> Result<(), NotificationError> {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.lanes.iter().map(VecDeque::len).sum::<usize>() >= self.capacity {
+                return Err(NotificationError::Provider(format!(
+                    "Notification queue is full ({} messages)",
+                    self.capacity
+                )));
+            }
+            let entry = Entry {
+                message,
+                seq: state.next_seq,
+                dispatched_before: state.dispatched,
+            };
+            state.next_seq += 1;
+            let lane = entry.message.priority.rank() as usize;
+            state.lanes[lane].push_back(entry);
+        }
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// 取出当前应当投递的消息
+    pub fn try_pop(&self) -> Option<NotificationMessage> {
+        let mut state = self.state.lock().unwrap();
+
+        // 每条通道的队首等待最久，只需比较各队首
+        let mut best: Option<(usize, u64, u64)> = None;
+        for (lane, entries) in state.lanes.iter().enumerate() {
+            let Some(head) = entries.front() else { continue };
+            let effective = self.effective_rank(lane as u64, state.dispatched - head.dispatched_before);
+            let better = match best {
+                None => true,
+                Some((_, best_rank, best_seq)) => {
+                    effective > best_rank || (effective == best_rank && head.seq < best_seq)
+                }
+            };
+            if better {
+                best = Some((lane, effective, head.seq));
+            }
+        }
+
+        let (lane, _, _) = best?;
+        let entry = state.lanes[lane].pop_front()?;
+        state.dispatched += 1;
+        Some(entry.message)
+    }
+
+    /// 等待并取出下一条消息
+    pub async fn pop(&self) -> NotificationMessage {
+        loop {
+            let notified = self.notify.notified();
+            if let Some(message) = self.try_pop() {
+                return message;
+            }
+            notified.await;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().lanes.iter().map(VecDeque::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn effective_rank(&self, base: u64, waited: u64) -> u64 {
+        if self.aging_step == 0 {
+            return base;
+        }
+        (base + waited / self.aging_step).min(LEVELS as u64 - 1)
+    }
+}
@@ -0,0 +1,114 @@
+//! Per-subscriber quiet hours and deferred delivery
+
+use crate::{EventSubscriber, NotificationError, NotificationMessage, NotificationPriority};
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// 免打扰时段
+///
+/// `start`/`end` 为 `timezone` 时区下的本地时间，`start > end` 表示跨越午夜
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    /// IANA 时区名，例如 `Asia/Shanghai`
+    pub timezone: String,
+}
+
+impl QuietHours {
+    pub fn new(start: NaiveTime, end: NaiveTime, timezone: &str) -> Result<Self, NotificationError> {
+        let quiet_hours = Self { start, end, timezone: timezone.to_string() };
+        quiet_hours.tz()?;
+        Ok(quiet_hours)
+    }
+
+    fn tz(&self) -> Result<Tz, NotificationError> {
+        self.timezone
+            .parse()
+            .map_err(|_| NotificationError::Configuration(format!("Unknown timezone: {}", self.timezone)))
+    }
+
+    /// `now` 是否处于免打扰时段内
+    pub fn contains(&self, now: DateTime<Utc>) -> Result<bool, NotificationError> {
+        let local = now.with_timezone(&self.tz()?).time();
+        Ok(if self.start <= self.end {
+            self.start <= local && local < self.end
+        } else {
+            local >= self.start || local < self.end
+        })
+    }
+
+    /// `now` 之后时段结束的时刻
+    pub fn next_end(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>, NotificationError> {
+        let tz = self.tz()?;
+        let local = now.with_timezone(&tz);
+        let mut date = local.date_naive();
+        if local.time() >= self.end {
+            date = date.succ_opt().unwrap_or(date);
+        }
+
+        let end = date.and_time(self.end);
+        // 结束时刻落在夏令时跳过的区间内时顺延一小时
+        let resolved = tz
+            .from_local_datetime(&end)
+            .earliest()
+            .or_else(|| tz.from_local_datetime(&(end + Duration::hours(1))).earliest())
+            .ok_or_else(|| NotificationError::Configuration(format!("Cannot resolve {} in {}", end, self.timezone)))?;
+        Ok(resolved.with_timezone(&Utc))
+    }
+}
+
+/// 被推迟的通知
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeferredNotification {
+    pub message: NotificationMessage,
+    pub deliver_at: DateTime<Utc>,
+}
+
+/// 免打扰期间推迟投递的通知
+#[derive(Default)]
+pub struct DeferredNotifications {
+    deferred: Mutex<Vec<DeferredNotification>>,
+}
+
+impl DeferredNotifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 根据订阅者的免打扰时段决定是否立即投递
+    ///
+    /// 返回 `Some` 时应立即投递；非 `Critical` 通知在免打扰时段内被推迟到时段结束
+    pub fn schedule(
+        &self,
+        subscriber: Option<&EventSubscriber>,
+        message: NotificationMessage,
+        now: DateTime<Utc>,
+    ) -> Result<Option<NotificationMessage>, NotificationError> {
+        let Some(quiet_hours) = subscriber.and_then(|s| s.quiet_hours.as_ref()) else {
+            return Ok(Some(message));
+        };
+        if message.priority == NotificationPriority::Critical || !quiet_hours.contains(now)? {
+            return Ok(Some(message));
+        }
+
+        let deliver_at = quiet_hours.next_end(now)?;
+        self.deferred.lock().unwrap().push(DeferredNotification { message, deliver_at });
+        Ok(None)
+    }
+
+    /// 取出到期的通知
+    pub fn take_due(&self, now: DateTime<Utc>) -> Vec<NotificationMessage> {
+        let mut deferred = self.deferred.lock().unwrap();
+        let (due, pending): (Vec<_>, Vec<_>) = deferred.drain(..).partition(|d| d.deliver_at <= now);
+        *deferred = pending;
+        due.into_iter().map(|d| d.message).collect()
+    }
+
+    /// 尚未到期的通知
+    pub fn pending(&self) -> Vec<DeferredNotification> {
+        self.deferred.lock().unwrap().clone()
+    }
+}
@@ -2,20 +2,44 @@
 
 use crate::{
     NotificationConfig, NotificationError, EventHandler, ProviderManager, 
-    NotificationMessage, NotificationType, EventSubscriber
+    NotificationMessage, NotificationType, EventSubscriber, DeliveryLog,
+    DeadLetterStore, EventStoreDeadLetterStore, NotificationQueue, DeferredNotification, DeferredNotifications,
+    DigestBuffer, SessionEventFeed
 };
+use crate::keepalive::KeepaliveConfig;
 use crate::websocket::WebSocketServer;
+use event_store::EventStorage;
+use event_store::store::{FileEventStore, MemoryEventStore};
+use shared_utils::clock::{Clock, SystemClock};
 use uuid::Uuid;
 use anyhow::Result;
-use tokio::sync::broadcast;
+use std::path::PathBuf;
+use std::sync::Arc;
+use shared_logging::{ShutdownSequence, ShutdownStage};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{broadcast, oneshot, RwLock};
 use tokio::task::JoinHandle;
-use tracing::{info, error};
+use tracing::{info, warn, error};
+
+/// 检查推迟通知是否到期的间隔
+const DEFERRED_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// `shutdown` 中每个关闭阶段的超时
+const SHUTDOWN_STAGE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
 /// 通知服务
 pub struct NotificationService {
     config: NotificationConfig,
-    event_handler: EventHandler,
-    provider_manager: ProviderManager,
+    /// 转发任务按接收者查找订阅者，因此与之共享
+    event_handler: Arc<std::sync::RwLock<EventHandler>>,
+    provider_manager: Arc<RwLock<ProviderManager>>,
+    delivery_log: DeliveryLog,
+    dead_letters: Arc<dyn DeadLetterStore>,
+    queue: Arc<NotificationQueue>,
+    deferred: Arc<DeferredNotifications>,
+    digests: Arc<DigestBuffer>,
+    clock: Arc<dyn Clock>,
+    session_feed: Arc<SessionEventFeed>,
     websocket_server: Option<WebSocketServer>,
     event_sender: broadcast::Sender<NotificationMessage>,
     #[allow(dead_code)]
@@ -23,6 +47,12 @@ pub struct NotificationService {
     http_server_handle: Option<JoinHandle<()>>,
     websocket_server_handle: Option<JoinHandle<()>>,
     event_processor_handle: Option<JoinHandle<()>>,
+    event_forwarder_handle: Option<JoinHandle<()>>,
+    /// 通知转发任务转发完已接收的消息后退出
+    forwarder_stop: Option<oneshot::Sender<()>>,
+    deferred_flush_handle: Option<JoinHandle<()>>,
+    /// 已出队、正在发送的通知数
+    in_flight: Arc<AtomicUsize>,
 }
 
 impl NotificationService {
@@ -34,34 +64,70 @@ impl NotificationService {
         let (event_sender, event_receiver) = broadcast::channel(config.events.queue_size);
         
         // 创建事件处理器
-        let event_handler = EventHandler::new();
+        let event_handler = EventHandler::new().with_message_sender(event_sender.clone());
+        
+        // 创建Webhook投递日志
+        let delivery_log = DeliveryLog::new(Self::create_event_storage(&config, "webhook_deliveries.json").await?);
+        
+        // 创建死信存储
+        let dead_letters: Arc<dyn DeadLetterStore> =
+            Arc::new(EventStoreDeadLetterStore::new(Self::create_event_storage(&config, "dead_letters.json").await?));
         
         // 创建提供者管理器
-        let mut provider_manager = ProviderManager::new();
+        let mut provider_manager = ProviderManager::new().with_dead_letter_store(dead_letters.clone());
         
         // 初始化通知提供者
-        Self::initialize_providers(&mut provider_manager, &config).await?;
+        Self::initialize_providers(&mut provider_manager, &config, &delivery_log).await?;
+        
+        // 创建待投递通知的优先级队列
+        let queue = Arc::new(NotificationQueue::new(config.events.queue_size, config.events.priority_aging_step));
+        
+        // 创建会话事件流，事件持久化后才能补发给晚到的订阅者
+        let session_feed = Arc::new(SessionEventFeed::new(
+            Self::create_event_storage(&config, "session_events.json").await?,
+            config.events.queue_size,
+            config.websocket.catch_up_limit,
+        ));
         
         // 创建WebSocket服务器
         let websocket_server = if config.websocket.port > 0 {
-            Some(WebSocketServer::new(event_sender.clone()))
+            Some(WebSocketServer::new(event_sender.clone())
+                .with_session_feed(session_feed.clone())
+                .with_keepalive(KeepaliveConfig::from(&config.websocket)))
         } else {
             None
         };
         
         Ok(Self {
             config,
-            event_handler,
-            provider_manager,
+            event_handler: Arc::new(std::sync::RwLock::new(event_handler)),
+            provider_manager: Arc::new(RwLock::new(provider_manager)),
+            delivery_log,
+            dead_letters,
+            queue,
+            deferred: Arc::new(DeferredNotifications::new()),
+            digests: Arc::new(DigestBuffer::new()),
+            clock: Arc::new(SystemClock),
+            session_feed,
             websocket_server,
             event_sender,
             event_receiver,
             http_server_handle: None,
             websocket_server_handle: None,
             event_processor_handle: None,
+            event_forwarder_handle: None,
+            forwarder_stop: None,
+            deferred_flush_handle: None,
+            in_flight: Arc::new(AtomicUsize::new(0)),
         })
     }
     
+    /// 替换时钟（用于测试免打扰时段和摘要窗口等时间相关逻辑）
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+    
     /// 启动通知服务
     pub async fn start(&mut self) -> Result<(), NotificationError> {
         info!("Starting notification service");
@@ -81,38 +147,106 @@ impl NotificationService {
         Ok(())
     }
     
-    /// 关闭通知服务
+    /// 关闭通知服务，各阶段超时为 `SHUTDOWN_STAGE_TIMEOUT`
     pub async fn shutdown(&mut self) -> Result<(), NotificationError> {
         info!("Shutting down notification service");
         
-        // 关闭HTTP服务器
-        if let Some(handle) = self.http_server_handle.take() {
-            handle.abort();
-        }
-        
-        // 关闭WebSocket服务器
-        if let Some(handle) = self.websocket_server_handle.take() {
-            handle.abort();
-        }
-        
-        // 关闭事件处理器
-        if let Some(handle) = self.event_processor_handle.take() {
-            handle.abort();
-        }
+        let mut sequence = ShutdownSequence::new(SHUTDOWN_STAGE_TIMEOUT);
+        self.register_shutdown(&mut sequence);
+        sequence.run().await;
         
         info!("Notification service shutdown complete");
         Ok(())
     }
     
-    /// 发布事件
-    pub fn publish_event(&self, event_type: NotificationType, session_id: Option<String>, data: std::collections::HashMap<String, serde_json::Value>, source: String) -> Result<(), NotificationError> {
+    /// 待投递的通知数：队列中的加上正在发送的
+    pub fn pending_notifications(&self) -> usize {
+        self.queue.len() + self.in_flight.load(Ordering::SeqCst)
+    }
+    
+    /// 按关闭阶段登记关闭步骤：停止HTTP和WebSocket服务器，把已接收的消息转入队列，再投递完队列中的通知
+    ///
+    /// 免打扰时段推迟的通知和未到期的摘要不会提前发送，只记录其数量
+    pub fn register_shutdown(&mut self, sequence: &mut ShutdownSequence) {
+        let servers: Vec<JoinHandle<()>> =
+            [self.http_server_handle.take(), self.websocket_server_handle.take()].into_iter().flatten().collect();
+        sequence.add(ShutdownStage::StopAccepting, "notification-servers", async move {
+            for handle in servers {
+                handle.abort();
+            }
+        });
+        
+        let deferred_flush = self.deferred_flush_handle.take();
+        let forwarder_stop = self.forwarder_stop.take();
+        let forwarder = self.event_forwarder_handle.take();
+        sequence.add(ShutdownStage::DrainInFlight, "notification-intake", async move {
+            if let Some(handle) = deferred_flush {
+                handle.abort();
+            }
+            if let Some(stop) = forwarder_stop {
+                let _ = stop.send(());
+            }
+            if let Some(handle) = forwarder {
+                let _ = handle.await;
+            }
+        });
+        
+        let (queue, in_flight) = (self.queue.clone(), self.in_flight.clone());
+        let (deferred, digests) = (self.deferred.clone(), self.digests.clone());
+        let processor = self.event_processor_handle.take();
+        let flush = async move {
+            while queue.len() + in_flight.load(Ordering::SeqCst) > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            if let Some(handle) = processor {
+                handle.abort();
+            }
+            let held = deferred.pending().len() + digests.pending_count();
+            if held > 0 {
+                warn!("{} deferred or digest notifications are not sent before shutdown", held);
+            }
+        };
+        let (queue, in_flight) = (self.queue.clone(), self.in_flight.clone());
+        sequence.add_with_remaining(ShutdownStage::FlushQueues, "notification-queue", flush, move || {
+            queue.len() + in_flight.load(Ordering::SeqCst)
+        });
+    }
+    
+    /// 发布事件，带会话ID的事件同时写入会话事件流
+    pub async fn publish_event(&self, event_type: NotificationType, session_id: Option<String>, data: std::collections::HashMap<String, serde_json::Value>, source: String) -> Result<(), NotificationError> {
         let event = crate::NotificationEvent::new(event_type, session_id, data, source);
-        self.event_handler.publish_event(event).map_err(NotificationError::Other)
+        if event.session_id.is_some() {
+            self.session_feed.publish(event.clone()).await?;
+        }
+        self.event_handler.read().unwrap().publish_event(event).map_err(NotificationError::Other)
+    }
+    
+    /// 获取会话事件流
+    pub fn session_feed(&self) -> &Arc<SessionEventFeed> {
+        &self.session_feed
+    }
+    
+    /// 把通知发到广播通道，由转发任务按摘要和免打扰时段排入队列
+    pub fn broadcast(&self, message: NotificationMessage) -> Result<(), NotificationError> {
+        self.event_sender
+            .send(message)
+            .map(|_| ())
+            .map_err(|e| NotificationError::Other(anyhow::anyhow!("Failed to broadcast notification: {}", e)))
     }
     
-    /// 发送通知
+    /// 立即发送通知
+    ///
+    /// 接收者处于免打扰时段时，非紧急通知与 `enqueue_notification` 一样推迟到时段结束后入队
     pub async fn send_notification(&self, message: NotificationMessage) -> Result<(), NotificationError> {
-        let results = self.provider_manager.send_to_all_providers(&message).await;
+        let now = self.clock.now();
+        let scheduled = {
+            let handler = self.event_handler.read().unwrap();
+            self.deferred.schedule(handler.find_subscriber(&message.recipient), message, now)?
+        };
+        let Some(message) = scheduled else {
+            return Ok(());
+        };
+        let results = self.provider_manager.read().await.send_to_all_providers(&message).await;
         
         let mut success_count = 0;
         for (provider_name, result) in results {
@@ -134,25 +268,49 @@ impl NotificationService {
         Ok(())
     }
     
+    /// 将通知放入优先级队列，由事件处理器异步投递
+    ///
+    /// 摘要模式的接收者的非紧急通知先进入摘要缓冲区；接收者处于免打扰时段时，
+    /// 非紧急通知推迟到时段结束后再入队
+    pub fn enqueue_notification(&self, message: NotificationMessage) -> Result<(), NotificationError> {
+        let handler = self.event_handler.read().unwrap();
+        enqueue(&handler, &self.digests, &self.deferred, &self.queue, message, self.clock.now())
+    }
+    
+    /// 免打扰时段推迟、尚未到期的通知
+    pub fn deferred_notifications(&self) -> Vec<DeferredNotification> {
+        self.deferred.pending()
+    }
+    
+    /// 将已结束窗口的摘要和已到期的推迟通知放入队列，返回入队数量
+    pub fn flush_deferred(&self, now: chrono::DateTime<chrono::Utc>) -> usize {
+        flush_deferred(&self.digests, &self.deferred, &self.queue, now)
+    }
+    
+    /// 按服务时钟的当前时间投递到期通知，返回入队数量
+    pub fn flush_due(&self) -> usize {
+        self.flush_deferred(self.clock.now())
+    }
+    
     /// 添加事件订阅者
     pub fn subscribe(&mut self, subscriber: EventSubscriber) -> Result<Uuid, NotificationError> {
-        self.event_handler.subscribe(subscriber).map_err(NotificationError::Other)
+        self.event_handler.write().unwrap().subscribe(subscriber).map_err(NotificationError::Other)
     }
     
     /// 取消事件订阅
     pub fn unsubscribe(&mut self, subscriber_id: Uuid) -> Result<(), NotificationError> {
-        self.event_handler.unsubscribe(subscriber_id).map_err(NotificationError::Other)
+        self.event_handler.write().unwrap().unsubscribe(subscriber_id).map_err(NotificationError::Other)
     }
     
     /// 获取服务状态
     pub async fn get_status(&self) -> serde_json::Value {
-        let active_subscribers = self.event_handler.get_active_subscriber_count();
+        let active_subscribers = self.event_handler.read().unwrap().get_active_subscriber_count();
         let websocket_connections = if let Some(ref ws_server) = self.websocket_server {
             ws_server.get_connection_count().await
         } else {
             0
         };
-        let available_providers = self.provider_manager.get_provider_names();
+        let available_providers = self.provider_manager.read().await.get_provider_names();
         
         serde_json::json!({
             "status": "running",
@@ -167,8 +325,59 @@ impl NotificationService {
         })
     }
     
+    /// 重新加载配置
+    ///
+    /// 日志级别、重试策略和通知提供者会立即生效，其余变更需要重启服务
+    pub async fn reload_config(&mut self, config: NotificationConfig) -> Result<(), NotificationError> {
+        config.validate()?;
+        
+        for section in self.config.restart_required_changes(&config) {
+            warn!("Change to {} configuration requires a restart to take effect", section);
+        }
+        
+        if self.config.logging.level != config.logging.level {
+            shared_logging::reload_log_filter(&config.logging.level)
+                .map_err(|e| NotificationError::Configuration(format!("Invalid log level: {}", e)))?;
+        }
+        
+        if serde_json::to_value(&self.config.providers)? != serde_json::to_value(&config.providers)? {
+            let mut provider_manager = ProviderManager::new().with_dead_letter_store(self.dead_letters.clone());
+            Self::initialize_providers(&mut provider_manager, &config, &self.delivery_log).await?;
+            *self.provider_manager.write().await = provider_manager;
+            info!("Notification providers reloaded");
+        }
+        
+        self.config.logging = config.logging;
+        self.config.retry = config.retry;
+        self.config.providers = config.providers;
+        Ok(())
+    }
+    
+    /// 获取Webhook投递日志
+    pub fn delivery_log(&self) -> &DeliveryLog {
+        &self.delivery_log
+    }
+    
+    /// 获取死信存储
+    pub fn dead_letters(&self) -> &Arc<dyn DeadLetterStore> {
+        &self.dead_letters
+    }
+    
+    /// 创建事件存储，启用事件持久化时写入存储目录下的 `file_name`
+    async fn create_event_storage(config: &NotificationConfig, file_name: &str) -> Result<Arc<dyn EventStorage>, NotificationError> {
+        let storage: Arc<dyn EventStorage> = if config.events.persistence.enabled {
+            let path = PathBuf::from(&config.events.persistence.storage_path).join(file_name);
+            let store = FileEventStore::new(path);
+            store.load_from_file().await?;
+            Arc::new(store)
+        } else {
+            Arc::new(MemoryEventStore::new())
+        };
+        Ok(storage)
+    }
+    
     /// 初始化通知提供者
-    async fn initialize_providers(provider_manager: &mut ProviderManager, config: &NotificationConfig) -> Result<(), NotificationError> {
+    async fn initialize_providers(provider_manager: &mut ProviderManager, config: &NotificationConfig, delivery_log: &DeliveryLog) -> Result<(), NotificationError> {
         info!("Initializing notification providers");
         
         // 初始化邮件提供者
@@ -198,8 +407,10 @@ impl NotificationService {
                 max_retries: webhook_config.max_retries,
                 retry_interval: webhook_config.retry_interval,
                 headers: webhook_config.default_headers.clone(),
+                secret: webhook_config.secret.clone(),
             };
-            let webhook_provider = crate::WebhookProvider::new(provider_webhook_config);
+            let webhook_provider = crate::WebhookProvider::new(provider_webhook_config)
+                .with_delivery_log(delivery_log.clone());
             provider_manager.add_provider("webhook".to_string(), Box::new(webhook_provider));
         }
         
@@ -220,22 +431,26 @@ impl NotificationService {
         Ok(())
     }
     
-    /// 启动HTTP服务器
-    async fn start_http_server(&mut self) -> Result<(), NotificationError> {
-        info!("Starting HTTP server on {}:{}", self.config.server.host, self.config.server.port);
-        
-        let state = crate::handlers::NotificationServiceState {
+    /// HTTP处理器的状态，与服务共享同一个事件处理器，经HTTP创建的订阅对服务的投递立即生效
+    pub fn http_state(&self) -> crate::handlers::NotificationServiceState {
+        crate::handlers::NotificationServiceState {
             event_handler: self.event_handler.clone(),
             provider_manager: self.provider_manager.clone(),
+            delivery_log: self.delivery_log.clone(),
             websocket_state: if let Some(ref ws_server) = self.websocket_server {
                 ws_server.get_state().clone()
             } else {
                 // 创建一个临时的WebSocket状态
-                crate::WebSocketState::new(self.event_sender.clone())
+                crate::WebSocketState::new(self.event_sender.clone()).with_session_feed(self.session_feed.clone())
             },
-        };
+        }
+    }
+    
+    /// 启动HTTP服务器
+    async fn start_http_server(&mut self) -> Result<(), NotificationError> {
+        info!("Starting HTTP server on {}:{}", self.config.server.host, self.config.server.port);
         
-        let app = crate::handlers::create_http_router(state);
+        let app = crate::handlers::create_http_router(self.http_state());
         
         let listener = tokio::net::TcpListener::bind(format!("{}:{}", self.config.server.host, self.config.server.port))
             .await
@@ -279,15 +494,63 @@ impl NotificationService {
     async fn start_event_processor(&mut self) -> Result<(), NotificationError> {
         info!("Starting event processor");
         
+        // 广播通道中的消息与 `enqueue_notification` 一样经过摘要和免打扰时段后进入优先级队列，积压时按优先级投递
         let mut receiver = self.event_sender.subscribe();
+        let handler = self.event_handler.clone();
+        let (digests, deferred) = (self.digests.clone(), self.deferred.clone());
+        let queue = self.queue.clone();
+        let clock = self.clock.clone();
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        self.forwarder_stop = Some(stop_tx);
+        self.event_forwarder_handle = Some(tokio::spawn(async move {
+            let forward = |message: NotificationMessage| {
+                let id = message.id;
+                let handler = handler.read().unwrap();
+                if let Err(e) = enqueue(&handler, &digests, &deferred, &queue, message, clock.now()) {
+                    error!("Dropping notification {}: {}", id, e);
+                }
+            };
+            loop {
+                tokio::select! {
+                    received = receiver.recv() => match received {
+                        Ok(message) => forward(message),
+                        Err(_) => break,
+                    },
+                    _ = &mut stop_rx => {
+                        // 关闭时先转发通道中已有的消息
+                        while let Ok(message) = receiver.try_recv() {
+                            forward(message);
+                        }
+                        break;
+                    }
+                }
+            }
+        }));
+        
+        // 定期投递到期的摘要和免打扰时段结束的通知
+        let digests = self.digests.clone();
+        let deferred = self.deferred.clone();
+        let queue = self.queue.clone();
+        let clock = self.clock.clone();
+        self.deferred_flush_handle = Some(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(DEFERRED_FLUSH_INTERVAL).await;
+                flush_deferred(&digests, &deferred, &queue, clock.now());
+            }
+        }));
+        
+        let queue = self.queue.clone();
         let provider_manager = self.provider_manager.clone();
+        let in_flight = self.in_flight.clone();
         
         let handle = tokio::spawn(async move {
-            while let Ok(message) = receiver.recv().await {
-                info!("Processing notification message: {}", message.id);
+            loop {
+                let message = queue.pop().await;
+                in_flight.fetch_add(1, Ordering::SeqCst);
+                info!("Processing {:?} notification message: {}", message.priority, message.id);
                 
                 // 发送到所有提供者
-                let results = provider_manager.send_to_all_providers(&message).await;
+                let results = provider_manager.read().await.send_to_all_providers(&message).await;
                 
                 for (provider_name, result) in results {
                     match result {
@@ -299,6 +562,7 @@ impl NotificationService {
                         }
                     }
                 }
+                in_flight.fetch_sub(1, Ordering::SeqCst);
             }
         });
         
@@ -308,10 +572,51 @@ impl NotificationService {
     }
 }
 
-// 为ProviderManager实现Clone
-impl Clone for ProviderManager {
-    fn clone(&self) -> Self {
-        // 注意：这里简化了实现，实际应用中可能需要更复杂的克隆逻辑
-        Self::new()
+/// 摘要模式的接收者的非紧急通知进入摘要缓冲区，免打扰时段内的推迟到时段结束，其余直接入队
+fn enqueue(
+    handler: &EventHandler,
+    digests: &DigestBuffer,
+    deferred: &DeferredNotifications,
+    queue: &NotificationQueue,
+    message: NotificationMessage,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<(), NotificationError> {
+    let subscriber = handler.find_subscriber(&message.recipient);
+    let Some(message) = digests.add(subscriber, message, now) else {
+        return Ok(());
+    };
+    match deferred.schedule(subscriber, message, now)? {
+        Some(message) => queue.push(message),
+        None => Ok(()),
+    }
+}
+
+fn flush_deferred(
+    digests: &DigestBuffer,
+    deferred: &DeferredNotifications,
+    queue: &NotificationQueue,
+    now: chrono::DateTime<chrono::Utc>,
+) -> usize {
+    let mut due = Vec::new();
+    for (subscriber, digest) in digests.take_due(now) {
+        match deferred.schedule(Some(&subscriber), digest, now) {
+            Ok(Some(digest)) => due.push(digest),
+            Ok(None) => {}
+            Err(e) => error!("Failed to schedule digest for {}: {}", subscriber.name, e),
+        }
+    }
+    due.extend(deferred.take_due(now));
+    
+    let mut count = 0;
+    for message in due {
+        let id = message.id;
+        match queue.push(message) {
+            Ok(()) => count += 1,
+            Err(e) => error!("Dropping deferred notification {}: {}", id, e),
+        }
+    }
+    if count > 0 {
+        info!("Released {} digest or deferred notifications", count);
     }
+    count
 }
@@ -0,0 +1,164 @@
+//! Per-session event feed that replays stored history to late subscribers
+
+use crate::{NotificationError, NotificationEvent, NotificationType};
+use chrono::{DateTime, Utc};
+use event_store::{Event, EventSeverity, EventStorage, EventType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::warn;
+use uuid::Uuid;
+
+/// 订阅时补发的历史事件范围
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatchUp {
+    /// 只接收实时事件
+    #[default]
+    None,
+    /// 最近的 N 条事件
+    Last(usize),
+    /// 该时间之后（含）的事件
+    Since(DateTime<Utc>),
+}
+
+/// 会话事件流
+pub struct SessionEventFeed {
+    storage: Arc<dyn EventStorage>,
+    sender: broadcast::Sender<NotificationEvent>,
+    /// 单次补发的事件数上限
+    max_catch_up: usize,
+}
+
+impl SessionEventFeed {
+    pub fn new(storage: Arc<dyn EventStorage>, capacity: usize, max_catch_up: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { storage, sender, max_catch_up }
+    }
+
+    /// 持久化事件后广播给实时订阅者
+    pub async fn publish(&self, event: NotificationEvent) -> Result<(), NotificationError> {
+        self.storage.store_event(to_stored(&event)).await?;
+        // 没有订阅者时发送失败，无需处理
+        let _ = self.sender.send(event);
+        Ok(())
+    }
+
+    /// 订阅会话事件，先补发 `catch_up` 指定的历史事件，再接收实时事件
+    ///
+    /// 先订阅广播再读取历史，两者之间发布的事件会同时出现在历史和广播中，由订阅按事件ID去重
+    pub async fn subscribe(&self, session_id: &str, catch_up: CatchUp) -> Result<SessionSubscription, NotificationError> {
+        let live = self.sender.subscribe();
+
+        let history = match catch_up {
+            CatchUp::None => Vec::new(),
+            _ => self.storage.get_events_by_session(session_id).await?,
+        };
+        let mut backlog: Vec<NotificationEvent> = history.into_iter().map(from_stored).collect();
+        backlog.sort_by_key(|e| e.timestamp);
+        if let CatchUp::Since(since) = catch_up {
+            backlog.retain(|e| e.timestamp >= since);
+        }
+        let limit = match catch_up {
+            CatchUp::Last(n) => n.min(self.max_catch_up),
+            _ => self.max_catch_up,
+        };
+        let backlog: VecDeque<NotificationEvent> = backlog.split_off(backlog.len().saturating_sub(limit)).into();
+
+        Ok(SessionSubscription {
+            session_id: session_id.to_string(),
+            replayed: backlog.iter().map(|e| e.id).collect(),
+            backlog,
+            live,
+        })
+    }
+}
+
+impl std::fmt::Debug for SessionEventFeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionEventFeed")
+            .field("subscribers", &self.sender.receiver_count())
+            .field("max_catch_up", &self.max_catch_up)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 单个会话的订阅
+pub struct SessionSubscription {
+    session_id: String,
+    backlog: VecDeque<NotificationEvent>,
+    /// 已补发的事件ID，用于跳过广播中的重复事件
+    replayed: HashSet<Uuid>,
+    live: broadcast::Receiver<NotificationEvent>,
+}
+
+impl SessionSubscription {
+    /// 下一条事件，事件流关闭时返回 `None`
+    pub async fn next(&mut self) -> Option<NotificationEvent> {
+        if let Some(event) = self.backlog.pop_front() {
+            return Some(event);
+        }
+        loop {
+            match self.live.recv().await {
+                Ok(event) => {
+                    if event.session_id.as_deref() != Some(self.session_id.as_str()) {
+                        continue;
+                    }
+                    if self.replayed.remove(&event.id) {
+                        continue;
+                    }
+                    return Some(event);
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Subscriber for session {} lagged, {} events skipped", self.session_id, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+fn to_stored(event: &NotificationEvent) -> Event {
+    let event_type = match &event.event_type {
+        NotificationType::SessionCreated => EventType::SessionCreated,
+        NotificationType::CommitmentSubmitted => EventType::CommitmentSubmitted,
+        NotificationType::RevealPhaseStarted => EventType::RevealPhaseStarted,
+        NotificationType::RevealCompleted => EventType::RevealCompleted,
+        NotificationType::ResultGenerated => EventType::ResultGenerated,
+        NotificationType::SystemError => EventType::SystemError,
+        NotificationType::Custom(custom) => EventType::Custom(custom.clone()),
+    };
+    let mut stored = Event::new(
+        event_type,
+        EventSeverity::Info,
+        event.source.clone(),
+        format!("{} event", event.event_type),
+        event.session_id.clone(),
+        None,
+    );
+    stored.id = event.id;
+    stored.data = event.data.clone();
+    stored.timestamp = event.timestamp;
+    stored
+}
+
+fn from_stored(event: Event) -> NotificationEvent {
+    let event_type = match event.event_type {
+        EventType::SessionCreated => NotificationType::SessionCreated,
+        EventType::CommitmentSubmitted => NotificationType::CommitmentSubmitted,
+        EventType::RevealPhaseStarted => NotificationType::RevealPhaseStarted,
+        EventType::RevealCompleted => NotificationType::RevealCompleted,
+        EventType::ResultGenerated => NotificationType::ResultGenerated,
+        EventType::SystemError => NotificationType::SystemError,
+        EventType::Custom(custom) => NotificationType::Custom(custom),
+    };
+    NotificationEvent {
+        id: event.id,
+        event_type,
+        session_id: event.session_id,
+        data: event.data,
+        timestamp: event.timestamp,
+        source: event.source,
+    }
+}
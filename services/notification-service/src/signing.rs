@@ -0,0 +1,6 @@
+//! Webhook payload signing
+//!
+//! The scheme lives in [`shared_utils::signing`] so other services sign their callbacks the same
+//! way; see there for how consumers verify a delivery.
+
+pub use shared_utils::signing::*;
@@ -1,26 +1,161 @@
 //! WebSocket server for real-time notifications
 
-use crate::{NotificationMessage, NotificationError};
+use crate::keepalive::{Keepalive, KeepaliveAction, KeepaliveConfig};
+use crate::{CatchUp, EventSubscriber, NotificationMessage, NotificationError, NotificationType, SessionEventFeed};
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
-    response::Response,
+    http::{HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     routing::get,
     Router,
 };
-use futures_util::stream::StreamExt;
-use futures_util::sink::SinkExt;
+use futures_util::stream::{Stream, StreamExt};
+use futures_util::sink::{Sink, SinkExt};
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::{
     collections::HashMap,
-    sync::Arc,
+    fmt::Display,
+    sync::{Arc, Mutex, RwLock as StdRwLock},
+    time::Instant,
 };
 use tokio::sync::{broadcast, RwLock};
-use tracing::{info, warn, error, debug};
+use tracing::{info, error, debug, warn};
 use uuid::Uuid;
 
+/// JSON 文本帧的子协议名
+pub const JSON_PROTOCOL: &str = "json";
+/// MessagePack 二进制帧的子协议名
+pub const MSGPACK_PROTOCOL: &str = "msgpack";
+
+/// 连接使用的帧格式
+///
+/// 客户端通过 `?format=msgpack` 查询参数或 `Sec-WebSocket-Protocol: msgpack` 子协议选择，
+/// 查询参数优先，默认使用 JSON 文本帧
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl WireFormat {
+    /// 按名称解析，无法识别时返回 `None`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            JSON_PROTOCOL => Some(WireFormat::Json),
+            MSGPACK_PROTOCOL | "messagepack" => Some(WireFormat::MessagePack),
+            _ => None,
+        }
+    }
+
+    fn negotiate(query: Option<WireFormat>, protocol: Option<&HeaderValue>) -> Self {
+        query
+            .or_else(|| protocol.and_then(|p| p.to_str().ok()).and_then(Self::from_name))
+            .unwrap_or_default()
+    }
+
+    /// 编码为发往客户端的帧
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Message, NotificationError> {
+        match self {
+            WireFormat::Json => Ok(Message::Text(serde_json::to_string(value)?)),
+            WireFormat::MessagePack => rmp_serde::to_vec_named(value)
+                .map(Message::Binary)
+                .map_err(|e| NotificationError::WebSocket(format!("MessagePack encoding failed: {}", e))),
+        }
+    }
+
+    /// 解码客户端发来的帧，MessagePack 连接同时接受文本帧
+    fn decode(&self, message: &Message) -> Option<serde_json::Value> {
+        match (self, message) {
+            (_, Message::Text(text)) => serde_json::from_str(text).ok(),
+            (WireFormat::MessagePack, Message::Binary(bytes)) => rmp_serde::from_slice(bytes).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// 会话事件帧
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionEventFrame {
+    #[serde(rename = "type")]
+    pub frame_type: String,
+    pub event: crate::NotificationEvent,
+}
+
+/// 客户端调整事件类型订阅的控制消息：`{"action": "subscribe" | "unsubscribe", "types": [...]}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionControl {
+    pub action: String,
+    pub types: Vec<NotificationType>,
+}
+
+/// 连接上的事件类型订阅
+///
+/// 客户端首次发送控制消息前接收所有类型；首次 `subscribe` 后只接收列出的类型，
+/// 首次 `unsubscribe` 则从全部内置类型中去掉列出的类型
+#[derive(Debug, Clone)]
+pub struct ConnectionSubscriptions {
+    subscriber: Arc<StdRwLock<Option<EventSubscriber>>>,
+    name: String,
+}
+
+impl ConnectionSubscriptions {
+    pub fn new(connection_id: Uuid) -> Self {
+        Self {
+            subscriber: Arc::new(StdRwLock::new(None)),
+            name: format!("websocket:{}", connection_id),
+        }
+    }
+
+    /// 是否向该连接转发此类事件
+    pub fn accepts(&self, event_type: &NotificationType) -> bool {
+        match self.subscriber.read().unwrap().as_ref() {
+            Some(subscriber) => subscriber.event_types.contains(event_type),
+            None => true,
+        }
+    }
+
+    /// 当前订阅的事件类型，未调整过时为空
+    pub fn event_types(&self) -> Option<Vec<NotificationType>> {
+        self.subscriber.read().unwrap().as_ref().map(|subscriber| subscriber.event_types.clone())
+    }
+
+    /// 校验并应用控制消息，返回更新后订阅的事件类型
+    pub fn apply(&self, control: SubscriptionControl) -> Result<Vec<NotificationType>, NotificationError> {
+        if control.types.is_empty() {
+            return Err(NotificationError::EventSubscription("types must not be empty".to_string()));
+        }
+        let mut guard = self.subscriber.write().unwrap();
+        let subscriber = match (guard.take(), control.action.as_str()) {
+            (Some(subscriber), "subscribe" | "unsubscribe") => subscriber,
+            (None, "subscribe") => EventSubscriber::new(self.name.clone()),
+            (None, "unsubscribe") => NotificationType::builtin().into_iter()
+                .fold(EventSubscriber::new(self.name.clone()), EventSubscriber::subscribe_to_event),
+            (current, action) => {
+                *guard = current;
+                return Err(NotificationError::EventSubscription(format!("Unknown action: {}", action)));
+            }
+        };
+        let subscriber = match control.action.as_str() {
+            "subscribe" => control.types.into_iter().fold(subscriber, EventSubscriber::subscribe_to_event),
+            _ => control.types.iter().fold(subscriber, EventSubscriber::unsubscribe_from_event),
+        };
+        let event_types = subscriber.event_types.clone();
+        *guard = Some(subscriber);
+        Ok(event_types)
+    }
+}
+
+/// 连接查询参数
+#[derive(Debug, Deserialize)]
+struct ConnectParams {
+    format: Option<String>,
+}
+
 /// WebSocket连接信息
 #[derive(Debug, Clone)]
 pub struct WebSocketConnection {
@@ -34,6 +169,10 @@ pub struct WebSocketConnection {
 pub struct WebSocketState {
     pub connections: Arc<RwLock<HashMap<String, WebSocketConnection>>>,
     pub event_sender: broadcast::Sender<NotificationMessage>,
+    /// 会话事件流，未设置时不支持按会话订阅
+    pub session_feed: Option<Arc<SessionEventFeed>>,
+    /// 服务端心跳
+    pub keepalive: KeepaliveConfig,
 }
 
 impl WebSocketState {
@@ -41,9 +180,23 @@ impl WebSocketState {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
             event_sender,
+            session_feed: None,
+            keepalive: KeepaliveConfig::default(),
         }
     }
 
+    /// 启用按会话订阅
+    pub fn with_session_feed(mut self, feed: Arc<SessionEventFeed>) -> Self {
+        self.session_feed = Some(feed);
+        self
+    }
+
+    /// 设置服务端心跳
+    pub fn with_keepalive(mut self, keepalive: KeepaliveConfig) -> Self {
+        self.keepalive = keepalive;
+        self
+    }
+
     pub async fn add_connection(&self, recipient: String, connection: WebSocketConnection) {
         let mut connections = self.connections.write().await;
         connections.insert(recipient.clone(), connection);
@@ -86,15 +239,56 @@ pub fn create_websocket_router(state: WebSocketState) -> Router {
 /// WebSocket处理器
 async fn websocket_handler(
     ws: WebSocketUpgrade,
+    Query(params): Query<ConnectParams>,
     State(state): State<WebSocketState>,
 ) -> Response {
-    ws.on_upgrade(|socket| websocket_connection(socket, state))
+    let query_format = match params.format.as_deref() {
+        Some(name) => match WireFormat::from_name(name) {
+            Some(format) => Some(format),
+            None => return (StatusCode::BAD_REQUEST, format!("Unsupported format: {}", name)).into_response(),
+        },
+        None => None,
+    };
+    ws.protocols([MSGPACK_PROTOCOL, JSON_PROTOCOL])
+        .on_upgrade(move |socket| {
+            let format = WireFormat::negotiate(query_format, socket.protocol());
+            websocket_connection(socket, state, format)
+        })
 }
 
 /// 处理WebSocket连接
-async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
-    let (mut sender, mut receiver) = socket.split();
+async fn websocket_connection(socket: WebSocket, state: WebSocketState, format: WireFormat) {
+    let (sender, receiver) = socket.split();
+    state.serve(sender, receiver, format).await;
+}
+
+impl WebSocketState {
+    /// 在已建立的连接上收发消息，直到任一方向关闭或心跳断开连接
+    pub async fn serve<S, R, E>(&self, sender: S, receiver: R, format: WireFormat)
+    where
+        S: Sink<Message> + Unpin + Send + 'static,
+        S::Error: Display,
+        R: Stream<Item = Result<Message, E>> + Unpin + Send + 'static,
+        E: Display + Send + 'static,
+    {
+        serve_connection(self.clone(), sender, receiver, format).await
+    }
+}
+
+async fn serve_connection<S, R, E>(state: WebSocketState, mut sender: S, mut receiver: R, format: WireFormat)
+where
+    S: Sink<Message> + Unpin + Send + 'static,
+    S::Error: Display,
+    R: Stream<Item = Result<Message, E>> + Unpin + Send + 'static,
+    E: Display + Send + 'static,
+{
+    debug!("WebSocket connection using {:?} framing", format);
     let connection_id = Uuid::new_v4();
+    let keepalive_config = state.keepalive;
+    let subscriptions = ConnectionSubscriptions::new(connection_id);
+    let receive_subscriptions = subscriptions.clone();
+    let keepalive = Arc::new(Mutex::new(Keepalive::new(keepalive_config, Instant::now())));
+    let receive_keepalive = keepalive.clone();
     
     // 创建消息通道
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<NotificationMessage>();
@@ -109,13 +303,50 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
     let sender_tx_clone = sender_tx.clone();
     
     let receive_task = tokio::spawn(async move {
+        let mut session_tasks = Vec::new();
         while let Some(msg) = receiver.next().await {
             match msg {
-                Ok(Message::Text(text)) => {
-                    debug!("Received WebSocket message: {}", text);
+                Ok(message @ (Message::Text(_) | Message::Binary(_))) => {
+                    debug!("Received WebSocket message: {:?}", message);
+                    receive_keepalive.lock().unwrap().on_activity(Instant::now());
                     
                     // 解析消息
-                    if let Ok(data) = serde_json::from_str::<serde_json::Value>(&text) {
+                    if let Some(data) = format.decode(&message) {
+                        if data.get("action").is_some() {
+                            // 调整事件类型订阅
+                            let reply = match serde_json::from_value::<SubscriptionControl>(data)
+                                .map_err(|e| NotificationError::EventSubscription(format!("Invalid subscription message: {}", e)))
+                                .and_then(|control| receive_subscriptions.apply(control))
+                            {
+                                Ok(event_types) => serde_json::json!({ "type": "subscriptions_updated", "event_types": event_types }),
+                                Err(e) => serde_json::json!({ "type": "error", "message": e.to_string() }),
+                            };
+                            let sent = format.encode(&reply)
+                                .map(|frame| sender_tx_clone.send(frame).is_ok())
+                                .unwrap_or(false);
+                            if !sent {
+                                break;
+                            }
+                            continue;
+                        }
+                        if let Some(session_id) = data.get("session_id").and_then(|v| v.as_str()) {
+                            // 订阅会话事件，可选补发历史事件
+                            let catch_up = data.get("catch_up")
+                                .and_then(|v| serde_json::from_value::<CatchUp>(v.clone()).ok())
+                                .unwrap_or_default();
+                            match subscribe_session(&state_clone, session_id, catch_up, format, receive_subscriptions.clone(), sender_tx_clone.clone()).await {
+                                Ok(task) => session_tasks.push(task),
+                                Err(e) => {
+                                    let error = serde_json::json!({ "type": "error", "message": e.to_string() });
+                                    let sent = format.encode(&error)
+                                        .map(|frame| sender_tx_clone.send(frame).is_ok())
+                                        .unwrap_or(false);
+                                    if !sent {
+                                        break;
+                                    }
+                                }
+                            }
+                        }
                         if let Some(recipient) = data.get("recipient").and_then(|v| v.as_str()) {
                             // 注册连接
                             let connection = WebSocketConnection {
@@ -132,8 +363,8 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
                                 "recipient": recipient
                             });
                             
-                            if let Ok(ack_text) = serde_json::to_string(&ack) {
-                                if let Err(e) = sender_tx_clone.send(Message::Text(ack_text)) {
+                            if let Ok(ack_frame) = format.encode(&ack) {
+                                if let Err(e) = sender_tx_clone.send(ack_frame) {
                                     error!("Failed to queue acknowledgment: {}", e);
                                     break;
                                 }
@@ -152,10 +383,7 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
                     }
                 }
                 Ok(Message::Pong(_)) => {
-                    // 忽略pong消息
-                }
-                Ok(Message::Binary(_)) => {
-                    warn!("Received binary message, ignoring");
+                    receive_keepalive.lock().unwrap().on_pong();
                 }
                 Err(e) => {
                     error!("WebSocket error: {}", e);
@@ -163,16 +391,50 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
                 }
             }
         }
+        for task in session_tasks {
+            task.abort();
+        }
     });
 
-    // 发送消息到客户端的任务
+    // 发送消息到客户端的任务，同时负责心跳
     let send_task = tokio::spawn(async move {
+        let mut ticker = keepalive_config.tick_interval().map(|period| {
+            let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker
+        });
         loop {
             tokio::select! {
+                _ = async {
+                    match ticker.as_mut() {
+                        Some(ticker) => ticker.tick().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    let action = keepalive.lock().unwrap().tick(Instant::now());
+                    match action {
+                        KeepaliveAction::Ping => {
+                            if let Err(e) = sender.send(Message::Ping(Vec::new())).await {
+                                error!("Failed to send ping: {}", e);
+                                break;
+                            }
+                        }
+                        KeepaliveAction::Wait => {}
+                        KeepaliveAction::Disconnect(reason) => {
+                            warn!("Closing WebSocket connection {}: {}", connection_id, reason.as_str());
+                            shared_logging::metrics().record_keepalive_disconnect("notification-service", reason.as_str());
+                            let _ = sender.send(Message::Close(None)).await;
+                            break;
+                        }
+                    }
+                }
                 // 处理来自接收任务的消息（确认、pong等）
                 msg = sender_rx.recv() => {
                     match msg {
                         Some(message) => {
+                            if matches!(message, Message::Text(_) | Message::Binary(_)) {
+                                keepalive.lock().unwrap().on_activity(Instant::now());
+                            }
                             if let Err(e) = sender.send(message).await {
                                 error!("Failed to send message: {}", e);
                                 break;
@@ -185,6 +447,9 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
                 notification = rx.recv() => {
                     match notification {
                         Some(message) => {
+                            if !subscriptions.accepts(&message.notification_type) {
+                                continue;
+                            }
                             let notification_json = serde_json::json!({
                                 "type": "notification",
                                 "id": message.id,
@@ -196,8 +461,9 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
                                 "timestamp": message.created_at
                             });
 
-                            if let Ok(notification_text) = serde_json::to_string(&notification_json) {
-                                if let Err(e) = sender.send(Message::Text(notification_text)).await {
+                            if let Ok(notification_frame) = format.encode(&notification_json) {
+                                keepalive.lock().unwrap().on_activity(Instant::now());
+                                if let Err(e) = sender.send(notification_frame).await {
                                     error!("Failed to send notification: {}", e);
                                     break;
                                 }
@@ -210,15 +476,18 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
         }
     });
 
-    // 等待任务完成
+    // 等待任一任务完成，再停止另一个
+    let (mut receive_task, mut send_task) = (receive_task, send_task);
     tokio::select! {
-        _ = receive_task => {
+        _ = &mut receive_task => {
             info!("WebSocket receive task completed");
         }
-        _ = send_task => {
+        _ = &mut send_task => {
             info!("WebSocket send task completed");
         }
     }
+    receive_task.abort();
+    send_task.abort();
 
     // 清理连接
     // 注意：这里需要从连接映射中移除，但由于我们不知道recipient，
@@ -226,6 +495,38 @@ async fn websocket_connection(socket: WebSocket, state: WebSocketState) {
     info!("WebSocket connection {} closed", connection_id);
 }
 
+/// 订阅会话事件，先补发历史事件再转发实时事件
+async fn subscribe_session(
+    state: &WebSocketState,
+    session_id: &str,
+    catch_up: CatchUp,
+    format: WireFormat,
+    subscriptions: ConnectionSubscriptions,
+    sender: tokio::sync::mpsc::UnboundedSender<Message>,
+) -> Result<tokio::task::JoinHandle<()>, NotificationError> {
+    let feed = state.session_feed.as_ref()
+        .ok_or_else(|| NotificationError::WebSocket("Session subscriptions are not enabled".to_string()))?;
+    let mut subscription = feed.subscribe(session_id, catch_up).await?;
+    info!("Subscribed WebSocket connection to session: {}", session_id);
+
+    Ok(tokio::spawn(async move {
+        while let Some(event) = subscription.next().await {
+            if !subscriptions.accepts(&event.event_type) {
+                continue;
+            }
+            let frame = SessionEventFrame { frame_type: "session_event".to_string(), event };
+            match format.encode(&frame) {
+                Ok(message) => {
+                    if sender.send(message).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => error!("Failed to encode session event: {}", e),
+            }
+        }
+    }))
+}
+
 /// WebSocket服务器
 pub struct WebSocketServer {
     state: WebSocketState,
@@ -240,6 +541,20 @@ impl WebSocketServer {
         Self { state, router }
     }
 
+    /// 启用按会话订阅
+    pub fn with_session_feed(self, feed: Arc<SessionEventFeed>) -> Self {
+        let state = self.state.with_session_feed(feed);
+        let router = create_websocket_router(state.clone());
+        Self { state, router }
+    }
+
+    /// 设置服务端心跳
+    pub fn with_keepalive(self, keepalive: KeepaliveConfig) -> Self {
+        let state = self.state.with_keepalive(keepalive);
+        let router = create_websocket_router(state.clone());
+        Self { state, router }
+    }
+
     pub fn get_router(self) -> Router {
         self.router
     }
@@ -267,6 +582,8 @@ impl Clone for WebSocketState {
         Self {
             connections: Arc::clone(&self.connections),
             event_sender: self.event_sender.clone(),
+            session_feed: self.session_feed.clone(),
+            keepalive: self.keepalive,
         }
     }
 }
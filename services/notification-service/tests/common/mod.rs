@@ -0,0 +1,61 @@
+//! Local HTTP endpoints and provider fixtures shared by the notification service tests
+#![allow(dead_code)]
+
+use axum::body::Bytes;
+use axum::http::{HeaderMap, StatusCode};
+use axum::{extract::State, routing::post, Router};
+use notification_service::providers::WebhookConfig;
+use notification_service::WebhookProvider;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Headers and raw body of the last request a capturing endpoint received
+pub type Captured = Arc<Mutex<Option<(HeaderMap, Bytes)>>>;
+
+/// Serve `router` on an ephemeral local port
+pub async fn serve(router: Router) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    addr
+}
+
+/// Webhook endpoint that answers 200 while `healthy` is set and 500 otherwise
+pub async fn spawn_endpoint(healthy: Arc<AtomicBool>) -> String {
+    async fn hook(State(healthy): State<Arc<AtomicBool>>) -> StatusCode {
+        if healthy.load(Ordering::SeqCst) {
+            StatusCode::OK
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+
+    let addr = serve(Router::new().route("/hook", post(hook)).with_state(healthy)).await;
+    format!("http://{}/hook", addr)
+}
+
+/// Webhook endpoint that records the headers and raw body of the last request
+pub async fn spawn_capturing_endpoint(captured: Captured) -> String {
+    async fn hook(State(captured): State<Captured>, headers: HeaderMap, body: Bytes) {
+        *captured.lock().unwrap() = Some((headers, body));
+    }
+
+    let addr = serve(Router::new().route("/hook", post(hook)).with_state(captured)).await;
+    format!("http://{}/hook", addr)
+}
+
+/// Webhook provider for `url` with no retry delay
+pub fn webhook(url: String, max_retries: u32, secret: Option<&str>) -> WebhookProvider {
+    WebhookProvider::new(WebhookConfig {
+        url,
+        timeout: 5,
+        max_retries,
+        retry_interval: 0,
+        headers: HashMap::new(),
+        secret: secret.map(str::to_string),
+    })
+}
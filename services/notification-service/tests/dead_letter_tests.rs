@@ -0,0 +1,95 @@
+mod common;
+
+use common::{spawn_endpoint, webhook};
+use event_store::store::MemoryEventStore;
+use notification_service::{
+    DeadLetter, DeadLetterStore, DeliveryLog, EventStoreDeadLetterStore, MemoryDeadLetterStore,
+    NotificationError, NotificationMessage, NotificationPriority, NotificationType, ProviderManager,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+fn manager(url: String, dead_letters: Arc<dyn DeadLetterStore>) -> ProviderManager {
+    let webhook = webhook(url, 1, None).with_delivery_log(DeliveryLog::new(Arc::new(MemoryEventStore::new())));
+
+    let mut manager = ProviderManager::new().with_dead_letter_store(dead_letters);
+    manager.add_provider("webhook".to_string(), Box::new(webhook));
+    manager
+}
+
+fn message() -> NotificationMessage {
+    NotificationMessage::new(
+        NotificationType::SystemError,
+        NotificationPriority::High,
+        "Failure".to_string(),
+        "Something went wrong".to_string(),
+        "operator".to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_exhausted_message_lands_in_dead_letter_queue() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let manager = manager(spawn_endpoint(healthy).await, Arc::new(MemoryDeadLetterStore::new()));
+
+    let message = message();
+    assert!(manager.send_notification("webhook", &message).await.is_err());
+
+    let letters = manager.list_dead_letters().await.unwrap();
+    assert_eq!(letters.len(), 1);
+    assert_eq!(letters[0].message.id, message.id);
+    assert_eq!(letters[0].provider, "webhook");
+    assert!(letters[0].last_error.contains("after 1 retries"));
+    // initial attempt plus one retry
+    assert_eq!(letters[0].attempts.len(), 2);
+    assert_eq!(letters[0].attempts[1].error.as_deref(), Some("HTTP 500 Internal Server Error"));
+}
+
+#[tokio::test]
+async fn test_requeue_delivers_and_removes_dead_letter() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let manager = manager(spawn_endpoint(healthy.clone()).await, Arc::new(MemoryDeadLetterStore::new()));
+
+    assert!(manager.send_notification("webhook", &message()).await.is_err());
+    let letter_id = manager.list_dead_letters().await.unwrap()[0].id;
+
+    healthy.store(true, Ordering::SeqCst);
+    manager.requeue(letter_id).await.unwrap();
+    assert!(manager.list_dead_letters().await.unwrap().is_empty());
+
+    assert!(matches!(manager.requeue(letter_id).await, Err(NotificationError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_failed_requeue_is_dead_lettered_again() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let manager = manager(spawn_endpoint(healthy).await, Arc::new(MemoryDeadLetterStore::new()));
+
+    let message = message();
+    assert!(manager.send_notification("webhook", &message).await.is_err());
+    let first = manager.list_dead_letters().await.unwrap()[0].id;
+
+    assert!(manager.requeue(first).await.is_err());
+    let letters = manager.list_dead_letters().await.unwrap();
+    assert_eq!(letters.len(), 1);
+    assert_ne!(letters[0].id, first);
+    assert_eq!(letters[0].message.id, message.id);
+    assert_eq!(letters[0].attempts.len(), 4);
+}
+
+#[tokio::test]
+async fn test_event_store_dead_letter_store_round_trip() {
+    let store = EventStoreDeadLetterStore::new(Arc::new(MemoryEventStore::new()));
+    let letter = DeadLetter::new(message(), "email".to_string(), "SMTP down".to_string(), Vec::new());
+    let id = letter.id;
+
+    store.push(letter).await.unwrap();
+    let listed = store.list().await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].last_error, "SMTP down");
+
+    let taken = store.take(id).await.unwrap().unwrap();
+    assert_eq!(taken.provider, "email");
+    assert!(store.list().await.unwrap().is_empty());
+    assert!(store.take(id).await.unwrap().is_none());
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{serve, spawn_endpoint, webhook};
+use event_store::store::MemoryEventStore;
+use notification_service::handlers::{create_http_router, NotificationServiceState};
+use notification_service::{
+    DeliveryLog, EventHandler, MemoryDeadLetterStore, NotificationMessage, NotificationPriority, NotificationType,
+    ProviderManager, WebSocketState,
+};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+
+fn manager(url: String) -> ProviderManager {
+    let webhook = webhook(url, 1, None).with_delivery_log(DeliveryLog::new(Arc::new(MemoryEventStore::new())));
+
+    let mut manager = ProviderManager::new().with_dead_letter_store(Arc::new(MemoryDeadLetterStore::new()));
+    manager.add_provider("webhook".to_string(), Box::new(webhook));
+    manager
+}
+
+fn message(notification_type: NotificationType) -> NotificationMessage {
+    NotificationMessage::new(
+        notification_type,
+        NotificationPriority::Normal,
+        "Title".to_string(),
+        "Content".to_string(),
+        "operator".to_string(),
+    )
+}
+
+/// 从 Prometheus 文本中读取 webhook 提供者某个计数器的值
+fn counter(rendered: &str, name: &str, notification_type: &str) -> u64 {
+    let prefix = format!("{}{{notification_type=\"{}\",provider=\"webhook\"}} ", name, notification_type);
+    rendered.lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| value.parse().unwrap())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_successful_send_increments_sent_total() {
+    let url = spawn_endpoint(Arc::new(AtomicBool::new(true))).await;
+    let manager = manager(url);
+    let metrics = shared_logging::metrics();
+    let before = counter(&metrics.render(), "notifications_sent_total", "SessionCreated");
+
+    manager.send_notification("webhook", &message(NotificationType::SessionCreated)).await.unwrap();
+
+    let rendered = metrics.render();
+    assert_eq!(counter(&rendered, "notifications_sent_total", "SessionCreated"), before + 1);
+    assert_eq!(counter(&rendered, "notifications_failed_total", "SessionCreated"), 0);
+    assert!(rendered.contains("notification_delivery_duration_seconds_count{notification_type=\"SessionCreated\",provider=\"webhook\"}"));
+
+    // 通过 /metrics 端点导出
+    let state = NotificationServiceState {
+        event_handler: Arc::new(std::sync::RwLock::new(EventHandler::new())),
+        provider_manager: Arc::new(RwLock::new(manager)),
+        delivery_log: DeliveryLog::new(Arc::new(MemoryEventStore::new())),
+        websocket_state: WebSocketState::new(broadcast::channel(16).0),
+    };
+    let addr = serve(create_http_router(state)).await;
+    let body = reqwest::get(format!("http://{}/metrics", addr)).await.unwrap().text().await.unwrap();
+    assert!(counter(&body, "notifications_sent_total", "SessionCreated") > before);
+}
+
+#[tokio::test]
+async fn test_exhausted_send_counts_failure_retries_and_dead_letter() {
+    let url = spawn_endpoint(Arc::new(AtomicBool::new(false))).await;
+    let manager = manager(url);
+    let metrics = shared_logging::metrics();
+    let rendered = metrics.render();
+    let (failed, retried, dead_lettered) = (
+        counter(&rendered, "notifications_failed_total", "SystemError"),
+        counter(&rendered, "notifications_retried_total", "SystemError"),
+        counter(&rendered, "notifications_dead_lettered_total", "SystemError"),
+    );
+
+    assert!(manager.send_notification("webhook", &message(NotificationType::SystemError)).await.is_err());
+
+    let rendered = metrics.render();
+    assert_eq!(counter(&rendered, "notifications_failed_total", "SystemError"), failed + 1);
+    assert_eq!(counter(&rendered, "notifications_retried_total", "SystemError"), retried + 1);
+    assert_eq!(counter(&rendered, "notifications_dead_lettered_total", "SystemError"), dead_lettered + 1);
+    assert_eq!(counter(&rendered, "notifications_sent_total", "SystemError"), 0);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use common::{spawn_endpoint, webhook};
+use event_store::store::MemoryEventStore;
+use notification_service::{
+    DeliveryLog, DeliveryStatus, NotificationMessage, NotificationPriority, NotificationProvider,
+    NotificationType, WebhookProvider,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+fn provider(url: String, delivery_log: DeliveryLog) -> WebhookProvider {
+    webhook(url, 0, None).with_delivery_log(delivery_log)
+}
+
+fn message() -> NotificationMessage {
+    NotificationMessage::new(
+        NotificationType::ResultGenerated,
+        NotificationPriority::Normal,
+        "Results".to_string(),
+        "Vote results are available".to_string(),
+        "subscriber".to_string(),
+    )
+}
+
+fn since() -> chrono::DateTime<Utc> {
+    Utc::now() - Duration::minutes(1)
+}
+
+#[tokio::test]
+async fn test_failed_webhook_delivery_is_logged() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let url = spawn_endpoint(healthy).await;
+    let delivery_log = DeliveryLog::new(Arc::new(MemoryEventStore::new()));
+    let webhook = provider(url.clone(), delivery_log.clone());
+
+    let message = message();
+    assert!(webhook.send_notification(&message).await.is_err());
+
+    let failed = delivery_log.failed_since(since()).await.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].message.id, message.id);
+    assert_eq!(failed[0].url, url);
+    assert_eq!(failed[0].status, DeliveryStatus::Failed);
+    assert_eq!(failed[0].response_status, Some(500));
+}
+
+#[tokio::test]
+async fn test_replay_succeeds_after_endpoint_recovers() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let url = spawn_endpoint(healthy.clone()).await;
+    let delivery_log = DeliveryLog::new(Arc::new(MemoryEventStore::new()));
+    let webhook = provider(url, delivery_log.clone());
+
+    let message = message();
+    assert!(webhook.send_notification(&message).await.is_err());
+
+    healthy.store(true, Ordering::SeqCst);
+    let summary = webhook.replay_failed(since()).await.unwrap();
+    assert_eq!(summary.succeeded, vec![message.id]);
+    assert!(summary.failed.is_empty());
+
+    assert!(delivery_log.failed_since(since()).await.unwrap().is_empty());
+    let attempts = delivery_log.attempts().await.unwrap();
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[1].status, DeliveryStatus::Succeeded);
+    assert_eq!(attempts[1].message.retry_count, 1);
+}
+
+#[tokio::test]
+async fn test_replay_respects_max_retries() {
+    let healthy = Arc::new(AtomicBool::new(false));
+    let url = spawn_endpoint(healthy).await;
+    let delivery_log = DeliveryLog::new(Arc::new(MemoryEventStore::new()));
+    let webhook = provider(url, delivery_log.clone());
+
+    let message = message().with_max_retries(1);
+    assert!(webhook.send_notification(&message).await.is_err());
+
+    let first = webhook.replay_failed(since()).await.unwrap();
+    assert_eq!(first.failed, vec![message.id]);
+
+    let second = webhook.replay_failed(since()).await.unwrap();
+    assert!(second.failed.is_empty());
+    assert_eq!(second.skipped, vec![message.id]);
+    assert_eq!(delivery_log.attempts().await.unwrap().len(), 2);
+}
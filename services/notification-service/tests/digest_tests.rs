@@ -0,0 +1,104 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use notification_service::{
+    DeliveryMode, DigestBuffer, EventSubscriber, NotificationMessage, NotificationPriority, NotificationType,
+};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+}
+
+fn subscriber(delivery_mode: DeliveryMode) -> EventSubscriber {
+    EventSubscriber::new("bob".to_string()).with_delivery_mode(delivery_mode)
+}
+
+fn commitment(priority: NotificationPriority, n: u32) -> NotificationMessage {
+    NotificationMessage::new(
+        NotificationType::CommitmentSubmitted,
+        priority,
+        "Commitment submitted".to_string(),
+        format!("commitment #{}", n),
+        "bob".to_string(),
+    )
+}
+
+#[test]
+fn test_three_events_in_one_interval_produce_one_digest() {
+    let buffer = DigestBuffer::new();
+    let subscriber = subscriber(DeliveryMode::Digest { interval: 60 });
+
+    for (n, offset) in [(1, 0), (2, 10), (3, 59)] {
+        let message = commitment(NotificationPriority::Normal, n);
+        assert!(buffer.add(Some(&subscriber), message, start() + Duration::seconds(offset)).is_none());
+    }
+    assert_eq!(buffer.pending_count(), 3);
+
+    assert!(buffer.take_due(start() + Duration::seconds(59)).is_empty());
+    let due = buffer.take_due(start() + Duration::seconds(60));
+    assert_eq!(due.len(), 1);
+
+    let (owner, digest) = &due[0];
+    assert_eq!(owner.name, "bob");
+    assert_eq!(digest.recipient, "bob");
+    assert_eq!(digest.notification_type, NotificationType::CommitmentSubmitted);
+    assert_eq!(digest.metadata["digest_count"], 3);
+    assert_eq!(digest.metadata["digest_sample"].as_array().unwrap().len(), 3);
+    assert_eq!(digest.metadata["digest_sample"][0]["content"], "commitment #1");
+    assert_eq!(buffer.pending_count(), 0);
+}
+
+#[test]
+fn test_digest_uses_highest_priority_and_separates_types() {
+    let buffer = DigestBuffer::new();
+    let subscriber = subscriber(DeliveryMode::Digest { interval: 60 });
+
+    buffer.add(Some(&subscriber), commitment(NotificationPriority::Low, 1), start());
+    buffer.add(Some(&subscriber), commitment(NotificationPriority::High, 2), start());
+    let reveal = NotificationMessage::new(
+        NotificationType::RevealCompleted,
+        NotificationPriority::Normal,
+        "Reveal completed".to_string(),
+        String::new(),
+        "bob".to_string(),
+    );
+    buffer.add(Some(&subscriber), reveal, start());
+
+    let due = buffer.take_due(start() + Duration::seconds(60));
+    assert_eq!(due.len(), 2);
+    let commitments = due
+        .iter()
+        .map(|(_, digest)| digest)
+        .find(|digest| digest.notification_type == NotificationType::CommitmentSubmitted)
+        .unwrap();
+    assert_eq!(commitments.priority, NotificationPriority::High);
+    assert_eq!(commitments.metadata["digest_count"], 2);
+}
+
+#[test]
+fn test_immediate_and_critical_bypass_digest() {
+    let buffer = DigestBuffer::new();
+
+    let immediate = subscriber(DeliveryMode::Immediate);
+    assert!(buffer.add(Some(&immediate), commitment(NotificationPriority::Low, 1), start()).is_some());
+
+    let digest = subscriber(DeliveryMode::Digest { interval: 60 });
+    assert!(buffer.add(Some(&digest), commitment(NotificationPriority::Critical, 2), start()).is_some());
+    assert!(buffer.add(None, commitment(NotificationPriority::Low, 3), start()).is_some());
+    assert_eq!(buffer.pending_count(), 0);
+}
+
+#[test]
+fn test_delivery_mode_defaults_to_immediate() {
+    let subscriber: EventSubscriber = serde_json::from_value(serde_json::json!({
+        "id": uuid::Uuid::new_v4(),
+        "name": "carol",
+        "event_types": [],
+        "notification_providers": [],
+        "filters": {},
+        "active": true
+    }))
+    .unwrap();
+    assert_eq!(subscriber.delivery_mode, DeliveryMode::Immediate);
+
+    let mode: DeliveryMode = serde_json::from_str(r#"{"mode":"digest","interval":300}"#).unwrap();
+    assert_eq!(mode, DeliveryMode::Digest { interval: 300 });
+}
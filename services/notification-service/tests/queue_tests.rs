@@ -0,0 +1,86 @@
+use notification_service::{NotificationMessage, NotificationPriority, NotificationQueue, NotificationType};
+
+fn message(priority: NotificationPriority, title: &str) -> NotificationMessage {
+    NotificationMessage::new(
+        NotificationType::SystemError,
+        priority,
+        title.to_string(),
+        String::new(),
+        "operator".to_string(),
+    )
+}
+
+#[test]
+fn test_critical_is_delivered_before_low() {
+    let queue = NotificationQueue::new(100, 16);
+    queue.push(message(NotificationPriority::Low, "low")).unwrap();
+    queue.push(message(NotificationPriority::Normal, "normal")).unwrap();
+    queue.push(message(NotificationPriority::Critical, "critical")).unwrap();
+    queue.push(message(NotificationPriority::High, "high")).unwrap();
+
+    let order: Vec<String> = std::iter::from_fn(|| queue.try_pop()).map(|m| m.title).collect();
+    assert_eq!(order, vec!["critical", "high", "normal", "low"]);
+}
+
+#[test]
+fn test_same_priority_is_fifo() {
+    let queue = NotificationQueue::new(100, 0);
+    for title in ["a", "b", "c"] {
+        queue.push(message(NotificationPriority::Normal, title)).unwrap();
+    }
+
+    let order: Vec<String> = std::iter::from_fn(|| queue.try_pop()).map(|m| m.title).collect();
+    assert_eq!(order, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_aged_low_is_sent_under_sustained_high_priority_load() {
+    let aging_step = 4;
+    let queue = NotificationQueue::new(100, aging_step);
+    queue.push(message(NotificationPriority::Low, "low")).unwrap();
+
+    // Keep the queue topped up with critical messages; Low needs three promotions
+    let mut dispatched = 0;
+    loop {
+        queue.push(message(NotificationPriority::Critical, "critical")).unwrap();
+        let next = queue.try_pop().unwrap();
+        if next.title == "low" {
+            break;
+        }
+        dispatched += 1;
+        assert!(dispatched <= 3 * aging_step, "low-priority message starved");
+    }
+    assert_eq!(dispatched, 3 * aging_step);
+}
+
+#[test]
+fn test_without_aging_low_waits_for_critical_backlog() {
+    let queue = NotificationQueue::new(100, 0);
+    queue.push(message(NotificationPriority::Low, "low")).unwrap();
+    for _ in 0..50 {
+        queue.push(message(NotificationPriority::Critical, "critical")).unwrap();
+        assert_eq!(queue.try_pop().unwrap().title, "critical");
+    }
+    assert_eq!(queue.try_pop().unwrap().title, "low");
+}
+
+#[test]
+fn test_push_rejects_when_full() {
+    let queue = NotificationQueue::new(1, 16);
+    queue.push(message(NotificationPriority::Low, "first")).unwrap();
+    assert!(queue.push(message(NotificationPriority::Critical, "second")).is_err());
+    assert_eq!(queue.len(), 1);
+}
+
+#[tokio::test]
+async fn test_pop_waits_for_push() {
+    let queue = std::sync::Arc::new(NotificationQueue::new(10, 16));
+    let consumer = {
+        let queue = queue.clone();
+        tokio::spawn(async move { queue.pop().await })
+    };
+    tokio::task::yield_now().await;
+    queue.push(message(NotificationPriority::High, "late")).unwrap();
+    assert_eq!(consumer.await.unwrap().title, "late");
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,155 @@
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use notification_service::{
+    DeferredNotifications, EventSubscriber, NotificationConfig, NotificationMessage, NotificationPriority,
+    NotificationService, NotificationType, QuietHours,
+};
+use shared_utils::clock::MockClock;
+use std::sync::Arc;
+
+fn time(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+fn utc(h: u32, m: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 1, h, m, 0).unwrap()
+}
+
+/// Quiet from 22:00 to 07:00 Shanghai time (UTC+8), i.e. 14:00-23:00 UTC
+fn subscriber() -> EventSubscriber {
+    EventSubscriber::new("alice".to_string())
+        .with_quiet_hours(QuietHours::new(time(22, 0), time(7, 0), "Asia/Shanghai").unwrap())
+}
+
+fn message(priority: NotificationPriority) -> NotificationMessage {
+    NotificationMessage::new(
+        NotificationType::ResultGenerated,
+        priority,
+        "Results".to_string(),
+        "Vote results are available".to_string(),
+        "alice".to_string(),
+    )
+}
+
+#[test]
+fn test_overnight_window_is_timezone_aware() {
+    let quiet_hours = subscriber().quiet_hours.unwrap();
+    // 03:00 Shanghai
+    assert!(quiet_hours.contains(utc(19, 0)).unwrap());
+    // 21:59 Shanghai
+    assert!(!quiet_hours.contains(utc(13, 59)).unwrap());
+    // 07:00 Shanghai is the first minute outside the window
+    assert!(!quiet_hours.contains(utc(23, 0)).unwrap());
+    assert_eq!(quiet_hours.next_end(utc(19, 0)).unwrap(), utc(23, 0));
+}
+
+#[test]
+fn test_unknown_timezone_is_rejected() {
+    assert!(QuietHours::new(time(22, 0), time(7, 0), "Mars/Olympus").is_err());
+}
+
+#[test]
+fn test_normal_message_is_deferred_during_quiet_hours() {
+    let deferred = DeferredNotifications::new();
+    let subscriber = subscriber();
+    let now = utc(19, 0);
+
+    let message = message(NotificationPriority::Normal);
+    let id = message.id;
+    assert!(deferred.schedule(Some(&subscriber), message, now).unwrap().is_none());
+
+    let pending = deferred.pending();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].deliver_at, utc(23, 0));
+
+    assert!(deferred.take_due(utc(22, 59)).is_empty());
+    let due = deferred.take_due(utc(23, 0));
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, id);
+    assert!(deferred.pending().is_empty());
+}
+
+#[test]
+fn test_critical_message_bypasses_quiet_hours() {
+    let deferred = DeferredNotifications::new();
+    let subscriber = subscriber();
+
+    let message = message(NotificationPriority::Critical);
+    let id = message.id;
+    let delivered = deferred.schedule(Some(&subscriber), message, utc(19, 0)).unwrap();
+    assert_eq!(delivered.map(|m| m.id), Some(id));
+    assert!(deferred.pending().is_empty());
+}
+
+#[test]
+fn test_messages_outside_quiet_hours_are_delivered() {
+    let deferred = DeferredNotifications::new();
+
+    let during_day = deferred.schedule(Some(&subscriber()), message(NotificationPriority::Low), utc(3, 0)).unwrap();
+    assert!(during_day.is_some());
+
+    let no_subscriber = deferred.schedule(None, message(NotificationPriority::Low), utc(19, 0)).unwrap();
+    assert!(no_subscriber.is_some());
+    assert!(deferred.pending().is_empty());
+}
+
+#[tokio::test]
+async fn test_service_defers_and_flushes_by_its_clock() {
+    let mut config = NotificationConfig::default();
+    config.events.persistence.enabled = false;
+    let clock = MockClock::new(utc(19, 0));
+    let mut service = NotificationService::new(config).await.unwrap().with_clock(Arc::new(clock.clone()));
+    service.subscribe(subscriber()).unwrap();
+
+    service.enqueue_notification(message(NotificationPriority::Normal)).unwrap();
+    assert_eq!(service.flush_due(), 0);
+
+    clock.advance(chrono::Duration::minutes(239));
+    assert_eq!(service.flush_due(), 0);
+
+    // quiet hours end at 23:00 UTC
+    clock.advance(chrono::Duration::minutes(1));
+    assert_eq!(service.flush_due(), 1);
+}
+
+#[tokio::test]
+async fn test_broadcast_notifications_respect_quiet_hours() {
+    let mut config = NotificationConfig::default();
+    config.events.persistence.enabled = false;
+    config.server.port = 0;
+    config.websocket.port = 0;
+    let clock = MockClock::new(utc(19, 0));
+    let mut service = NotificationService::new(config).await.unwrap().with_clock(Arc::new(clock.clone()));
+    service.subscribe(subscriber()).unwrap();
+    service.start().await.unwrap();
+
+    service.broadcast(message(NotificationPriority::Normal)).unwrap();
+    let mut deferred = Vec::new();
+    for _ in 0..100 {
+        deferred = service.deferred_notifications();
+        if !deferred.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(deferred.len(), 1);
+    assert_eq!(deferred[0].deliver_at, utc(23, 0));
+    assert_eq!(service.pending_notifications(), 0);
+
+    clock.advance(chrono::Duration::minutes(240));
+    assert_eq!(service.flush_due(), 1);
+}
+
+#[tokio::test]
+async fn test_direct_send_defers_during_quiet_hours() {
+    let mut config = NotificationConfig::default();
+    config.events.persistence.enabled = false;
+    let clock = MockClock::new(utc(19, 0));
+    let mut service = NotificationService::new(config).await.unwrap().with_clock(Arc::new(clock.clone()));
+    service.subscribe(subscriber()).unwrap();
+
+    // deferred rather than sent, so no provider is needed for this to succeed
+    service.send_notification(message(NotificationPriority::Normal)).await.unwrap();
+    assert_eq!(service.deferred_notifications().len(), 1);
+    assert!(service.send_notification(message(NotificationPriority::Critical)).await.is_err());
+    assert_eq!(service.deferred_notifications().len(), 1);
+}
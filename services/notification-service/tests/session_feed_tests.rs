@@ -0,0 +1,159 @@
+mod common;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use event_store::store::MemoryEventStore;
+use event_store::{Event, EventStorage, EventStoreError, EventType};
+use notification_service::handlers::{create_http_router, NotificationServiceState};
+use notification_service::{
+    CatchUp, DeliveryLog, EventHandler, NotificationEvent, NotificationType, ProviderManager, SessionEventFeed,
+    SessionSubscription, WebSocketState,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use uuid::Uuid;
+
+/// Memory store whose session reads are delayed, widening the window between subscribing to the
+/// live stream and reading history
+struct SlowSessionReads {
+    inner: MemoryEventStore,
+    delay: Duration,
+}
+
+#[async_trait]
+impl EventStorage for SlowSessionReads {
+    async fn store_event(&self, event: Event) -> Result<(), EventStoreError> {
+        self.inner.store_event(event).await
+    }
+    async fn store_events(&self, events: Vec<Event>) -> Result<(), EventStoreError> {
+        self.inner.store_events(events).await
+    }
+    async fn get_event(&self, event_id: Uuid) -> Result<Option<Event>, EventStoreError> {
+        self.inner.get_event(event_id).await
+    }
+    async fn get_events_by_session(&self, session_id: &str) -> Result<Vec<Event>, EventStoreError> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.get_events_by_session(session_id).await
+    }
+    async fn get_events_by_user(&self, user_id: Uuid) -> Result<Vec<Event>, EventStoreError> {
+        self.inner.get_events_by_user(user_id).await
+    }
+    async fn get_events_by_time_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Event>, EventStoreError> {
+        self.inner.get_events_by_time_range(start, end).await
+    }
+    async fn get_events_by_type(&self, event_type: &EventType) -> Result<Vec<Event>, EventStoreError> {
+        self.inner.get_events_by_type(event_type).await
+    }
+    async fn get_all_events(&self) -> Result<Vec<Event>, EventStoreError> {
+        self.inner.get_all_events().await
+    }
+    async fn delete_event(&self, event_id: Uuid) -> Result<(), EventStoreError> {
+        self.inner.delete_event(event_id).await
+    }
+    async fn cleanup_expired_events(&self, before: DateTime<Utc>) -> Result<u64, EventStoreError> {
+        self.inner.cleanup_expired_events(before).await
+    }
+}
+
+fn commitment(session_id: &str, voter: &str) -> NotificationEvent {
+    NotificationEvent::new(
+        NotificationType::CommitmentSubmitted,
+        Some(session_id.to_string()),
+        HashMap::new(),
+        "vote-engine".to_string(),
+    )
+    .with_data("voter".to_string(), serde_json::json!(voter))
+}
+
+async fn next_voters(subscription: &mut SessionSubscription, count: usize) -> Vec<String> {
+    let mut voters = Vec::new();
+    for _ in 0..count {
+        let event = tokio::time::timeout(Duration::from_secs(1), subscription.next())
+            .await
+            .expect("event within timeout")
+            .expect("feed open");
+        assert_eq!(event.event_type, NotificationType::CommitmentSubmitted);
+        voters.push(event.data["voter"].as_str().unwrap().to_string());
+    }
+    voters
+}
+
+async fn assert_no_more_events(subscription: &mut SessionSubscription) {
+    let extra = tokio::time::timeout(Duration::from_millis(50), subscription.next()).await;
+    assert!(extra.is_err(), "unexpected extra event: {:?}", extra);
+}
+
+#[tokio::test]
+async fn test_late_subscriber_receives_history_then_live_events() {
+    let feed = SessionEventFeed::new(Arc::new(MemoryEventStore::new()), 100, 100);
+    for voter in ["alice", "bob"] {
+        feed.publish(commitment("vote-1", voter)).await.unwrap();
+    }
+    feed.publish(commitment("vote-2", "mallory")).await.unwrap();
+
+    let mut subscription = feed.subscribe("vote-1", CatchUp::Last(10)).await.unwrap();
+    feed.publish(commitment("vote-1", "carol")).await.unwrap();
+
+    assert_eq!(next_voters(&mut subscription, 3).await, ["alice", "bob", "carol"]);
+    assert_no_more_events(&mut subscription).await;
+}
+
+#[tokio::test]
+async fn test_events_published_while_catching_up_are_not_duplicated() {
+    let storage = SlowSessionReads { inner: MemoryEventStore::new(), delay: Duration::from_millis(100) };
+    let feed = Arc::new(SessionEventFeed::new(Arc::new(storage), 100, 100));
+    feed.publish(commitment("vote-1", "alice")).await.unwrap();
+
+    // "bob" is published after the subscriber joined the live stream but before history is read,
+    // so it is both replayed and broadcast
+    let subscribing = tokio::spawn({
+        let feed = feed.clone();
+        async move { feed.subscribe("vote-1", CatchUp::Last(10)).await.unwrap() }
+    });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    feed.publish(commitment("vote-1", "bob")).await.unwrap();
+    let mut subscription = subscribing.await.unwrap();
+    feed.publish(commitment("vote-1", "carol")).await.unwrap();
+
+    assert_eq!(next_voters(&mut subscription, 3).await, ["alice", "bob", "carol"]);
+    assert_no_more_events(&mut subscription).await;
+}
+
+#[tokio::test]
+async fn test_catch_up_is_limited() {
+    let feed = SessionEventFeed::new(Arc::new(MemoryEventStore::new()), 100, 2);
+    for voter in ["alice", "bob", "carol"] {
+        feed.publish(commitment("vote-1", voter)).await.unwrap();
+    }
+
+    let mut subscription = feed.subscribe("vote-1", CatchUp::Last(10)).await.unwrap();
+    assert_eq!(next_voters(&mut subscription, 2).await, ["bob", "carol"]);
+    assert_no_more_events(&mut subscription).await;
+
+    let mut subscription = feed.subscribe("vote-1", CatchUp::None).await.unwrap();
+    assert_no_more_events(&mut subscription).await;
+}
+
+#[tokio::test]
+async fn test_ingested_events_are_replayed_to_late_subscribers() {
+    let feed = Arc::new(SessionEventFeed::new(Arc::new(MemoryEventStore::new()), 100, 100));
+    let state = NotificationServiceState {
+        event_handler: Arc::new(std::sync::RwLock::new(EventHandler::new())),
+        provider_manager: Arc::new(RwLock::new(ProviderManager::new())),
+        delivery_log: DeliveryLog::new(Arc::new(MemoryEventStore::new())),
+        websocket_state: WebSocketState::new(broadcast::channel(16).0).with_session_feed(feed.clone()),
+    };
+    let addr = common::serve(create_http_router(state)).await;
+
+    let client = reqwest::Client::new();
+    for voter in ["alice", "bob"] {
+        let response = client.post(format!("http://{}/events", addr)).json(&commitment("vote-1", voter)).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+    }
+
+    let mut subscription = feed.subscribe("vote-1", CatchUp::Last(10)).await.unwrap();
+    assert_eq!(next_voters(&mut subscription, 2).await, ["alice", "bob"]);
+    assert_no_more_events(&mut subscription).await;
+}
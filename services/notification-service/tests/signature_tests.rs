@@ -0,0 +1,91 @@
+mod common;
+
+use axum::{body::Bytes, http::HeaderMap};
+use common::{spawn_capturing_endpoint, webhook, Captured};
+use notification_service::signing::{verify_webhook_signature_at, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use notification_service::{
+    sign_webhook_payload, verify_webhook_signature, NotificationMessage, NotificationPriority,
+    NotificationProvider, NotificationType, SignatureError,
+};
+use std::sync::{Arc, Mutex};
+
+const SECRET: &str = "subscriber-secret";
+
+fn message() -> NotificationMessage {
+    NotificationMessage::new(
+        NotificationType::ResultGenerated,
+        NotificationPriority::Normal,
+        "Results".to_string(),
+        "Vote results are available".to_string(),
+        "subscriber".to_string(),
+    )
+}
+
+async fn deliver(secret: Option<&str>) -> (HeaderMap, Bytes) {
+    let captured: Captured = Arc::new(Mutex::new(None));
+    let url = spawn_capturing_endpoint(captured.clone()).await;
+    webhook(url, 0, secret).send_notification(&message()).await.unwrap();
+    let delivered = captured.lock().unwrap().take();
+    delivered.unwrap()
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+    headers.get(name).unwrap().to_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_signed_delivery_verifies() {
+    let (headers, body) = deliver(Some(SECRET)).await;
+    let signature = header(&headers, SIGNATURE_HEADER);
+    let timestamp = header(&headers, TIMESTAMP_HEADER);
+
+    assert!(signature.starts_with("sha256="));
+    assert_eq!(verify_webhook_signature(SECRET, &body, signature, timestamp, 300), Ok(()));
+    assert_eq!(
+        verify_webhook_signature("other-secret", &body, signature, timestamp, 300),
+        Err(SignatureError::Mismatch)
+    );
+}
+
+#[tokio::test]
+async fn test_tampered_body_is_rejected() {
+    let (headers, body) = deliver(Some(SECRET)).await;
+    let signature = header(&headers, SIGNATURE_HEADER);
+    let timestamp = header(&headers, TIMESTAMP_HEADER);
+
+    let mut tampered = body.to_vec();
+    let last = tampered.len() - 2;
+    tampered[last] ^= 0x01;
+    assert_eq!(
+        verify_webhook_signature(SECRET, &tampered, signature, timestamp, 300),
+        Err(SignatureError::Mismatch)
+    );
+}
+
+#[tokio::test]
+async fn test_unsigned_delivery_has_no_signature_headers() {
+    let (headers, body) = deliver(None).await;
+    assert!(headers.get(SIGNATURE_HEADER).is_none());
+    assert!(headers.get(TIMESTAMP_HEADER).is_none());
+    assert!(serde_json::from_slice::<NotificationMessage>(&body).is_ok());
+}
+
+#[test]
+fn test_stale_timestamp_is_rejected() {
+    let body = br#"{"id":"x"}"#;
+    let signature = sign_webhook_payload(SECRET, 1_000, body);
+
+    assert_eq!(verify_webhook_signature_at(SECRET, body, &signature, "1000", 300, 1_200), Ok(()));
+    assert_eq!(
+        verify_webhook_signature_at(SECRET, body, &signature, "1000", 300, 1_301),
+        Err(SignatureError::Expired)
+    );
+    assert_eq!(
+        verify_webhook_signature_at(SECRET, body, &signature, "1001", 300, 1_200),
+        Err(SignatureError::Mismatch)
+    );
+    assert_eq!(
+        verify_webhook_signature_at(SECRET, body, "deadbeef", "1000", 300, 1_200),
+        Err(SignatureError::MalformedSignature)
+    );
+}
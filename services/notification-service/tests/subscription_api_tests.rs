@@ -0,0 +1,133 @@
+mod common;
+
+use chrono::{DateTime, TimeZone, Utc};
+use common::serve;
+use notification_service::handlers::create_http_router;
+use notification_service::{
+    NotificationConfig, NotificationMessage, NotificationPriority, NotificationService, NotificationType,
+};
+use serde_json::json;
+use shared_utils::clock::MockClock;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+fn utc(h: u32, m: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 1, h, m, 0).unwrap()
+}
+
+/// 19:00 UTC 处于上海时间 22:00-07:00 的免打扰时段内
+async fn service() -> (NotificationService, SocketAddr) {
+    let mut config = NotificationConfig::default();
+    config.events.persistence.enabled = false;
+    let service = NotificationService::new(config).await.unwrap().with_clock(Arc::new(MockClock::new(utc(19, 0))));
+    let addr = serve(create_http_router(service.http_state())).await;
+    (service, addr)
+}
+
+async fn subscribe(addr: SocketAddr, request: serde_json::Value) -> String {
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/subscriptions", addr))
+        .json(&request)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    body["subscriber_id"].as_str().unwrap().to_string()
+}
+
+fn message(recipient: &str) -> NotificationMessage {
+    NotificationMessage::new(
+        NotificationType::ResultGenerated,
+        NotificationPriority::Normal,
+        "Results".to_string(),
+        "Vote results are available".to_string(),
+        recipient.to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_http_subscription_quiet_hours_apply_to_service_delivery() {
+    let (service, addr) = service().await;
+    let subscriber_id = subscribe(addr, json!({
+        "name": "alice",
+        "event_types": [NotificationType::ResultGenerated],
+        "notification_providers": [],
+        "quiet_hours": { "start": "22:00:00", "end": "07:00:00", "timezone": "Asia/Shanghai" },
+    })).await;
+
+    service.enqueue_notification(message("alice")).unwrap();
+    assert_eq!(service.deferred_notifications().len(), 1);
+    assert_eq!(service.pending_notifications(), 0);
+
+    let response = reqwest::Client::new()
+        .delete(format!("http://{}/subscriptions/{}", addr, subscriber_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    // 取消订阅后不再按其免打扰时段推迟
+    service.enqueue_notification(message("alice")).unwrap();
+    assert_eq!(service.deferred_notifications().len(), 1);
+    assert_eq!(service.pending_notifications(), 1);
+}
+
+#[tokio::test]
+async fn test_http_subscription_digest_mode_applies_to_service_delivery() {
+    let (service, addr) = service().await;
+    subscribe(addr, json!({
+        "name": "bob",
+        "event_types": [NotificationType::ResultGenerated],
+        "notification_providers": [],
+        "delivery_mode": { "mode": "digest", "interval": 60 },
+    })).await;
+
+    for _ in 0..3 {
+        service.enqueue_notification(message("bob")).unwrap();
+    }
+    assert_eq!(service.pending_notifications(), 0);
+    assert!(service.deferred_notifications().is_empty());
+
+    assert_eq!(service.flush_deferred(utc(19, 0)), 0);
+    assert_eq!(service.flush_deferred(utc(19, 1)), 1);
+    assert_eq!(service.pending_notifications(), 1);
+}
+
+#[tokio::test]
+async fn test_http_subscription_locale_applies_to_event_notifications() {
+    let mut config = NotificationConfig::default();
+    config.events.persistence.enabled = false;
+    config.server.port = 0;
+    config.websocket.port = 0;
+    let mut service = NotificationService::new(config).await.unwrap().with_clock(Arc::new(MockClock::new(utc(19, 0))));
+    service.start().await.unwrap();
+    let addr = serve(create_http_router(service.http_state())).await;
+    subscribe(addr, json!({
+        "name": "carol",
+        "event_types": [NotificationType::SessionCreated],
+        "notification_providers": [],
+        "quiet_hours": { "start": "22:00:00", "end": "07:00:00", "timezone": "Asia/Shanghai" },
+        "locale": "zh",
+    })).await;
+
+    service
+        .publish_event(NotificationType::SessionCreated, Some("s1".to_string()), HashMap::new(), "test".to_string())
+        .await
+        .unwrap();
+
+    // 免打扰时段内的通知被推迟，便于检查生成的内容
+    let mut deferred = Vec::new();
+    for _ in 0..100 {
+        deferred = service.deferred_notifications();
+        if !deferred.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(deferred.len(), 1);
+    assert_eq!(deferred[0].message.recipient, "carol");
+    assert_eq!(deferred[0].message.title, "新会话已创建");
+    assert_eq!(deferred[0].message.content, "会话 s1 已成功创建");
+}
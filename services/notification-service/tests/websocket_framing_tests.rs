@@ -0,0 +1,120 @@
+use event_store::store::MemoryEventStore;
+use futures_util::{SinkExt, StreamExt};
+use notification_service::{
+    NotificationEvent, NotificationType, SessionEventFeed, SessionEventFrame, WebSocketServer,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn spawn_server() -> (String, Arc<SessionEventFeed>) {
+    let (sender, _) = broadcast::channel(16);
+    let feed = Arc::new(SessionEventFeed::new(Arc::new(MemoryEventStore::new()), 100, 100));
+    let router = WebSocketServer::new(sender).with_session_feed(feed.clone()).get_router();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    (format!("ws://{}/ws", addr), feed)
+}
+
+fn commitment(voter: &str) -> NotificationEvent {
+    NotificationEvent::new(
+        NotificationType::CommitmentSubmitted,
+        Some("vote-1".to_string()),
+        HashMap::new(),
+        "vote-engine".to_string(),
+    )
+    .with_data("voter".to_string(), serde_json::json!(voter))
+}
+
+fn subscribe_request() -> serde_json::Value {
+    serde_json::json!({ "session_id": "vote-1", "catch_up": { "last": 10 } })
+}
+
+/// The next text or binary frame
+async fn next_frame(client: &mut Client) -> Message {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(2), client.next())
+            .await
+            .expect("frame within timeout")
+            .expect("connection open")
+            .unwrap();
+        if message.is_text() || message.is_binary() {
+            return message;
+        }
+    }
+}
+
+fn decode_json(message: Message) -> SessionEventFrame {
+    match message {
+        Message::Text(text) => serde_json::from_str(&text).unwrap(),
+        other => panic!("expected a text frame, got {:?}", other),
+    }
+}
+
+fn decode_msgpack(message: Message) -> SessionEventFrame {
+    match message {
+        Message::Binary(bytes) => rmp_serde::from_slice(&bytes).unwrap(),
+        other => panic!("expected a binary frame, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_json_and_msgpack_clients_are_served_concurrently() {
+    let (url, feed) = spawn_server().await;
+    let replayed = commitment("alice");
+    feed.publish(replayed.clone()).await.unwrap();
+
+    // JSON by default
+    let (mut json_client, _) = connect_async(url.clone()).await.unwrap();
+    json_client.send(Message::Text(subscribe_request().to_string())).await.unwrap();
+
+    // MessagePack via query parameter
+    let (mut query_client, _) = connect_async(format!("{}?format=msgpack", url)).await.unwrap();
+    query_client.send(Message::Text(subscribe_request().to_string())).await.unwrap();
+
+    // MessagePack via subprotocol, also sending its subscription as MessagePack
+    let mut request = url.clone().into_client_request().unwrap();
+    request.headers_mut().insert("Sec-WebSocket-Protocol", "msgpack".parse().unwrap());
+    let (mut protocol_client, response) = connect_async(request).await.unwrap();
+    assert_eq!(response.headers()["Sec-WebSocket-Protocol"], "msgpack");
+    let command = rmp_serde::to_vec_named(&subscribe_request()).unwrap();
+    protocol_client.send(Message::Binary(command)).await.unwrap();
+
+    let frame = decode_json(next_frame(&mut json_client).await);
+    assert_eq!((frame.frame_type.as_str(), frame.event.id), ("session_event", replayed.id));
+    for client in [&mut query_client, &mut protocol_client] {
+        let frame = decode_msgpack(next_frame(client).await);
+        assert_eq!((frame.frame_type.as_str(), frame.event.id), ("session_event", replayed.id));
+    }
+
+    let live = commitment("bob");
+    feed.publish(live.clone()).await.unwrap();
+
+    let frame = decode_json(next_frame(&mut json_client).await);
+    assert_eq!(frame.event.id, live.id);
+    assert_eq!(frame.event.data["voter"], "bob");
+    for client in [&mut query_client, &mut protocol_client] {
+        let frame = decode_msgpack(next_frame(client).await);
+        assert_eq!(frame.event.id, live.id);
+        assert_eq!(frame.event.event_type, NotificationType::CommitmentSubmitted);
+        assert_eq!(frame.event.session_id.as_deref(), Some("vote-1"));
+        assert_eq!(frame.event.data["voter"], "bob");
+        assert_eq!(frame.event.timestamp, live.timestamp);
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_format_is_rejected() {
+    let (url, _) = spawn_server().await;
+    assert!(connect_async(format!("{}?format=xml", url)).await.is_err());
+}
@@ -0,0 +1,112 @@
+use axum::extract::ws::Message;
+use futures_util::{sink, stream};
+use notification_service::{
+    DisconnectReason, Keepalive, KeepaliveAction, KeepaliveConfig, WebSocketState, WireFormat,
+};
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+
+/// 模拟套接字：测试通过 `incoming` 发送客户端帧，从 `outgoing` 读取服务端发出的帧
+struct MockSocket {
+    incoming: mpsc::UnboundedSender<Message>,
+    outgoing: mpsc::UnboundedReceiver<Message>,
+}
+
+fn serve(keepalive: KeepaliveConfig) -> (MockSocket, tokio::task::JoinHandle<()>) {
+    let (incoming_tx, incoming_rx) = mpsc::unbounded_channel::<Message>();
+    let (outgoing_tx, outgoing_rx) = mpsc::unbounded_channel::<Message>();
+    let receiver = Box::pin(stream::unfold(incoming_rx, |mut rx| async move {
+        rx.recv().await.map(|message| (Ok::<_, Infallible>(message), rx))
+    }));
+    let sender = Box::pin(sink::unfold(outgoing_tx, |tx, message: Message| async move {
+        tx.send(message).map_err(|_| "client gone")?;
+        Ok::<_, &str>(tx)
+    }));
+
+    let (event_sender, _) = broadcast::channel(16);
+    let state = WebSocketState::new(event_sender).with_keepalive(keepalive);
+    let connection = tokio::spawn(async move { state.serve(sender, receiver, WireFormat::Json).await });
+    (MockSocket { incoming: incoming_tx, outgoing: outgoing_rx }, connection)
+}
+
+fn fast_keepalive(idle_timeout: Option<Duration>) -> KeepaliveConfig {
+    KeepaliveConfig { ping_interval: Duration::from_millis(20), max_missed_pongs: 2, idle_timeout }
+}
+
+fn keepalive_disconnects(reason: &str) -> u64 {
+    let rendered = shared_logging::metrics().render();
+    let prefix = format!(
+        "websocket_keepalive_disconnects_total{{reason=\"{}\",service=\"notification-service\"}} ",
+        reason
+    );
+    rendered.lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(|value| value.parse().unwrap())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_client_ignoring_pings_is_disconnected() {
+    let before = keepalive_disconnects("missed_pongs");
+    let (mut socket, connection) = serve(fast_keepalive(None));
+
+    let mut frames = Vec::new();
+    while let Ok(Some(frame)) = tokio::time::timeout(Duration::from_secs(2), socket.outgoing.recv()).await {
+        frames.push(frame);
+    }
+    assert!(matches!(frames.as_slice(), [Message::Ping(_), Message::Ping(_), Message::Close(_)]), "{:?}", frames);
+
+    tokio::time::timeout(Duration::from_secs(2), connection).await
+        .expect("connection closed by keepalive")
+        .unwrap();
+    assert!(keepalive_disconnects("missed_pongs") > before);
+    drop(socket.incoming);
+}
+
+#[tokio::test]
+async fn test_client_answering_pings_stays_connected() {
+    let (mut socket, connection) = serve(fast_keepalive(None));
+
+    for _ in 0..5 {
+        let frame = tokio::time::timeout(Duration::from_secs(2), socket.outgoing.recv()).await.unwrap().unwrap();
+        let Message::Ping(payload) = frame else { panic!("expected a ping, got {:?}", frame) };
+        socket.incoming.send(Message::Pong(payload)).unwrap();
+    }
+    assert!(!connection.is_finished());
+
+    drop(socket.incoming);
+    tokio::time::timeout(Duration::from_secs(2), connection).await.unwrap().unwrap();
+}
+
+#[test]
+fn test_idle_connection_is_disconnected_despite_pongs() {
+    let start = Instant::now();
+    let config = fast_keepalive(Some(Duration::from_millis(50)));
+    let mut keepalive = Keepalive::new(config, start);
+
+    assert_eq!(keepalive.tick(start + Duration::from_millis(20)), KeepaliveAction::Ping);
+    keepalive.on_pong();
+    keepalive.on_activity(start + Duration::from_millis(30));
+    assert_eq!(keepalive.tick(start + Duration::from_millis(40)), KeepaliveAction::Ping);
+    keepalive.on_pong();
+    assert_eq!(keepalive.tick(start + Duration::from_millis(60)), KeepaliveAction::Ping);
+    keepalive.on_pong();
+    assert_eq!(
+        keepalive.tick(start + Duration::from_millis(80)),
+        KeepaliveAction::Disconnect(DisconnectReason::Idle)
+    );
+}
+
+#[test]
+fn test_keepalive_config_from_notification_config() {
+    let mut config = notification_service::NotificationConfig::default().websocket;
+    assert_eq!(KeepaliveConfig::from(&config).idle_timeout, None);
+    config.heartbeat_interval = 0;
+    config.idle_timeout = 600;
+    let keepalive = KeepaliveConfig::from(&config);
+    assert_eq!(keepalive.tick_interval(), Some(Duration::from_secs(600)));
+
+    let mut keepalive = Keepalive::new(keepalive, Instant::now());
+    assert_eq!(keepalive.tick(Instant::now()), KeepaliveAction::Wait);
+}
@@ -0,0 +1,117 @@
+use event_store::store::MemoryEventStore;
+use futures_util::{SinkExt, StreamExt};
+use notification_service::{
+    ConnectionSubscriptions, NotificationEvent, NotificationType, SessionEventFeed, SubscriptionControl,
+    WebSocketServer,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use uuid::Uuid;
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect() -> (Client, Arc<SessionEventFeed>) {
+    let (sender, _) = broadcast::channel(16);
+    let feed = Arc::new(SessionEventFeed::new(Arc::new(MemoryEventStore::new()), 100, 100));
+    let router = WebSocketServer::new(sender).with_session_feed(feed.clone()).get_router();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    let (client, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+    (client, feed)
+}
+
+fn event(event_type: NotificationType) -> NotificationEvent {
+    NotificationEvent::new(event_type, Some("vote-1".to_string()), HashMap::new(), "vote-engine".to_string())
+}
+
+async fn send(client: &mut Client, body: Value) {
+    client.send(Message::Text(body.to_string())).await.unwrap();
+}
+
+/// The next JSON frame
+async fn next_json(client: &mut Client) -> Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(2), client.next())
+            .await
+            .expect("frame within timeout")
+            .expect("connection open")
+            .unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_unsubscribed_event_type_stops_arriving() {
+    let (mut client, feed) = connect().await;
+    send(&mut client, json!({ "session_id": "vote-1", "catch_up": { "last": 10 } })).await;
+
+    let commitment = event(NotificationType::CommitmentSubmitted);
+    feed.publish(commitment.clone()).await.unwrap();
+    let frame = next_json(&mut client).await;
+    assert_eq!(frame["event"]["id"], json!(commitment.id));
+
+    send(&mut client, json!({ "action": "unsubscribe", "types": ["CommitmentSubmitted"] })).await;
+    let ack = next_json(&mut client).await;
+    assert_eq!(ack["type"], "subscriptions_updated");
+    assert!(!ack["event_types"].as_array().unwrap().contains(&json!("CommitmentSubmitted")));
+
+    feed.publish(event(NotificationType::CommitmentSubmitted)).await.unwrap();
+    let result = event(NotificationType::ResultGenerated);
+    feed.publish(result.clone()).await.unwrap();
+    let frame = next_json(&mut client).await;
+    assert_eq!(frame["event"]["id"], json!(result.id));
+
+    // 重新订阅后恢复接收
+    send(&mut client, json!({ "action": "subscribe", "types": ["CommitmentSubmitted"] })).await;
+    assert_eq!(next_json(&mut client).await["type"], "subscriptions_updated");
+    let again = event(NotificationType::CommitmentSubmitted);
+    feed.publish(again.clone()).await.unwrap();
+    assert_eq!(next_json(&mut client).await["event"]["id"], json!(again.id));
+}
+
+#[tokio::test]
+async fn test_invalid_control_messages_are_rejected() {
+    let (mut client, _) = connect().await;
+
+    send(&mut client, json!({ "action": "mute", "types": ["SystemError"] })).await;
+    let reply = next_json(&mut client).await;
+    assert_eq!(reply["type"], "error");
+    assert!(reply["message"].as_str().unwrap().contains("Unknown action: mute"), "{}", reply);
+
+    send(&mut client, json!({ "action": "subscribe", "types": ["NoSuchType"] })).await;
+    let reply = next_json(&mut client).await;
+    assert_eq!(reply["type"], "error");
+    assert!(reply["message"].as_str().unwrap().contains("Invalid subscription message"), "{}", reply);
+
+    send(&mut client, json!({ "action": "subscribe", "types": [] })).await;
+    assert_eq!(next_json(&mut client).await["type"], "error");
+}
+
+#[test]
+fn test_first_subscribe_narrows_to_listed_types() {
+    let subscriptions = ConnectionSubscriptions::new(Uuid::new_v4());
+    assert!(subscriptions.accepts(&NotificationType::Custom("audit".to_string())));
+
+    let types = subscriptions.apply(SubscriptionControl {
+        action: "subscribe".to_string(),
+        types: vec![NotificationType::ResultGenerated, NotificationType::Custom("audit".to_string())],
+    }).unwrap();
+    assert_eq!(types.len(), 2);
+    assert!(subscriptions.accepts(&NotificationType::Custom("audit".to_string())));
+    assert!(!subscriptions.accepts(&NotificationType::SessionCreated));
+
+    // 未知动作不改变已有订阅
+    assert!(subscriptions.apply(SubscriptionControl { action: "reset".to_string(), types: vec![NotificationType::SessionCreated] }).is_err());
+    assert_eq!(subscriptions.event_types(), Some(types));
+}
@@ -0,0 +1,318 @@
+//! Results callbacks for votes created with a `completion_webhook_url`
+//!
+//! When such a vote moves to `Completed` its final `VoteResults` are POSTed to the URL once,
+//! signed like the notification service's webhooks (see `shared_utils::signing`), and retried
+//! under the shared `RetryConfig`. Callbacks only go to public addresses unless the host is
+//! allow-listed. Every change to a delivery is stored in the event store, so
+//! `GET /api/v1/votes/:id/completion-webhook` survives restarts and pending callbacks resume.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use event_store::{Event, EventSeverity, EventStorage, EventStoreError, EventType};
+use serde::{Deserialize, Serialize};
+use shared_config::CompletionWebhookConfig;
+use shared_types::{Vote, VoteResults, VoteStatus};
+use shared_utils::signing::{sign_webhook_payload, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use vote_engine::TransitionObserver;
+
+/// Custom event type of stored delivery records
+const DELIVERY_EVENT_TYPE: &str = "CompletionWebhookDelivery";
+
+/// Where a vote's completion callback stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionDeliveryStatus {
+    /// Being sent or waiting to retry
+    Pending,
+    Delivered,
+    /// Every attempt failed
+    Failed,
+}
+
+/// Delivery record of one vote's completion callback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionDelivery {
+    pub vote_id: String,
+    pub url: String,
+    pub status: CompletionDeliveryStatus,
+    pub attempts: u32,
+    /// HTTP status of the last response, if one was received
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Sends completion callbacks in the background and records how they went
+#[derive(Clone)]
+pub struct CompletionWebhooks {
+    config: Arc<CompletionWebhookConfig>,
+    storage: Arc<dyn EventStorage>,
+    /// Serializes the lookup and first record of a delivery so a vote is reported once
+    starting: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl CompletionWebhooks {
+    pub fn new(config: CompletionWebhookConfig, storage: Arc<dyn EventStorage>) -> Self {
+        Self { config: Arc::new(config), storage, starting: Arc::default() }
+    }
+
+    /// Check that callbacks to `url` may be sent, returning the addresses it resolves to
+    ///
+    /// Refuses every URL while no signing secret is configured, and URLs whose host resolves
+    /// to a private, loopback, link-local or otherwise non-public address unless the host is
+    /// in `allowed_hosts`.
+    pub async fn check_url(&self, url: &str) -> Result<Vec<SocketAddr>, String> {
+        if self.config.secret.is_none() {
+            return Err("Completion callbacks are disabled: no signing secret is configured".to_string());
+        }
+        let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid completion webhook URL: {}", e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err("Completion webhook URL must be http(s)".to_string());
+        }
+        let port = parsed.port_or_known_default().unwrap_or(80);
+        let (host, addrs) = match parsed.host() {
+            Some(url::Host::Ipv4(ip)) => (ip.to_string(), vec![SocketAddr::new(ip.into(), port)]),
+            Some(url::Host::Ipv6(ip)) => (ip.to_string(), vec![SocketAddr::new(ip.into(), port)]),
+            Some(url::Host::Domain(domain)) => {
+                let addrs: Vec<SocketAddr> = tokio::net::lookup_host((domain, port))
+                    .await
+                    .map_err(|e| format!("Cannot resolve {}: {}", domain, e))?
+                    .collect();
+                (domain.to_ascii_lowercase(), addrs)
+            }
+            None => return Err("Completion webhook URL has no host".to_string()),
+        };
+        if addrs.is_empty() {
+            return Err(format!("{} does not resolve to any address", host));
+        }
+        if !self.config.allowed_hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(&host)) {
+            if let Some(addr) = addrs.iter().find(|addr| !is_public(&addr.ip())) {
+                return Err(format!("{} resolves to non-public address {}", host, addr.ip()));
+            }
+        }
+        Ok(addrs)
+    }
+
+    /// Start delivering `results` to `url`; must be called inside a Tokio runtime
+    ///
+    /// Returns false without sending anything when a callback for the vote was already started,
+    /// so a vote completed by concurrent requests is still reported once.
+    pub async fn dispatch(&self, url: &str, results: &VoteResults) -> Result<bool, EventStoreError> {
+        let delivery = {
+            let _starting = self.starting.lock().await;
+            if self.delivery(&results.vote_id).await?.is_some() {
+                return Ok(false);
+            }
+            let delivery = CompletionDelivery {
+                vote_id: results.vote_id.clone(),
+                url: url.to_string(),
+                status: CompletionDeliveryStatus::Pending,
+                attempts: 0,
+                response_status: None,
+                last_error: None,
+                updated_at: Utc::now(),
+            };
+            self.record(&delivery, results).await?;
+            delivery
+        };
+        self.spawn(delivery, results.clone());
+        Ok(true)
+    }
+
+    /// Resume callbacks left pending by a previous run, returning how many were restarted
+    pub async fn resume_pending(&self) -> Result<usize, EventStoreError> {
+        let pending: Vec<_> = self.latest().await?
+            .into_values()
+            .filter(|(delivery, _)| delivery.status == CompletionDeliveryStatus::Pending)
+            .collect();
+        let resumed = pending.len();
+        for (delivery, results) in pending {
+            info!("Resuming completion callback for vote {} after {} attempts", delivery.vote_id, delivery.attempts);
+            self.spawn(delivery, results);
+        }
+        Ok(resumed)
+    }
+
+    /// Delivery record of the vote's callback, if one was started
+    pub async fn delivery(&self, vote_id: &str) -> Result<Option<CompletionDelivery>, EventStoreError> {
+        Ok(self.latest().await?.remove(vote_id).map(|(delivery, _)| delivery))
+    }
+
+    /// Latest record of every delivery, with the results it sends
+    async fn latest(&self) -> Result<HashMap<String, (CompletionDelivery, VoteResults)>, EventStoreError> {
+        let events = self.storage
+            .get_events_by_type(&EventType::Custom(DELIVERY_EVENT_TYPE.to_string()))
+            .await?;
+        let mut latest: HashMap<String, (CompletionDelivery, VoteResults)> = HashMap::new();
+        for mut event in events {
+            let (Some(delivery), Some(results)) = (event.data.remove("delivery"), event.data.remove("results")) else {
+                continue;
+            };
+            let delivery: CompletionDelivery = serde_json::from_value(delivery)?;
+            let results: VoteResults = serde_json::from_value(results)?;
+            // Attempts only grow and a finished delivery is final, whatever order events come back in
+            let newer = latest.get(&delivery.vote_id).is_none_or(|(seen, _)| progress(&delivery) >= progress(seen));
+            if newer {
+                latest.insert(delivery.vote_id.clone(), (delivery, results));
+            }
+        }
+        Ok(latest)
+    }
+
+    async fn record(&self, delivery: &CompletionDelivery, results: &VoteResults) -> Result<(), EventStoreError> {
+        let severity = match delivery.status {
+            CompletionDeliveryStatus::Failed => EventSeverity::Warning,
+            _ => EventSeverity::Info,
+        };
+        let event = Event::new(
+            EventType::Custom(DELIVERY_EVENT_TYPE.to_string()),
+            severity,
+            "vote-api".to_string(),
+            format!("Completion callback for vote {} to {}", delivery.vote_id, delivery.url),
+            None,
+            None,
+        )
+        .with_data("delivery".to_string(), serde_json::to_value(delivery)?)
+        .with_data("results".to_string(), serde_json::to_value(results)?);
+        self.storage.store_event(event).await
+    }
+
+    fn spawn(&self, delivery: CompletionDelivery, results: VoteResults) {
+        let this = self.clone();
+        tokio::spawn(async move { this.deliver(delivery, results).await });
+    }
+
+    async fn deliver(&self, mut delivery: CompletionDelivery, results: VoteResults) {
+        let body = match serde_json::to_vec(&results) {
+            Ok(body) => body,
+            Err(e) => {
+                delivery.status = CompletionDeliveryStatus::Failed;
+                delivery.last_error = Some(e.to_string());
+                delivery.updated_at = Utc::now();
+                self.record_or_warn(&delivery, &results).await;
+                return;
+            }
+        };
+        let retry = &self.config.retry;
+        for attempt in delivery.attempts..=retry.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(retry.delay(attempt)).await;
+            }
+            let (response_status, error) = match self.send_once(&delivery.url, &body).await {
+                Ok(status) if (200..300).contains(&status) => (Some(status), None),
+                Ok(status) => (Some(status), Some(format!("endpoint answered {}", status))),
+                Err(e) => (None, Some(e)),
+            };
+            let delivered = error.is_none();
+            let last_attempt = attempt == retry.max_retries;
+            delivery.attempts = attempt + 1;
+            delivery.response_status = response_status;
+            delivery.last_error = error.clone();
+            delivery.status = match (delivered, last_attempt) {
+                (true, _) => CompletionDeliveryStatus::Delivered,
+                (false, true) => CompletionDeliveryStatus::Failed,
+                (false, false) => CompletionDeliveryStatus::Pending,
+            };
+            delivery.updated_at = Utc::now();
+            self.record_or_warn(&delivery, &results).await;
+            match error {
+                None => {
+                    info!("Delivered completion callback for vote {} to {}", delivery.vote_id, delivery.url);
+                    return;
+                }
+                Some(e) => warn!(
+                    "Completion callback for vote {} to {} failed (attempt {}): {}",
+                    delivery.vote_id, delivery.url, attempt + 1, e
+                ),
+            }
+        }
+    }
+
+    async fn record_or_warn(&self, delivery: &CompletionDelivery, results: &VoteResults) {
+        if let Err(e) = self.record(delivery, results).await {
+            warn!("Failed to record completion callback for vote {}: {}", delivery.vote_id, e);
+        }
+    }
+
+    /// POST `body` to `url`, connecting only to the addresses checked for this attempt
+    ///
+    /// The host is resolved again for every attempt and the client is pinned to the result,
+    /// and redirects are not followed, so the endpoint cannot be moved onto an internal address.
+    async fn send_once(&self, url: &str, body: &[u8]) -> Result<u16, String> {
+        let addrs = self.check_url(url).await?;
+        let secret = self.config.secret.as_deref().unwrap_or_default();
+        let mut client = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+        if let Some(domain) = reqwest::Url::parse(url).ok().and_then(|u| u.domain().map(str::to_string)) {
+            client = client.resolve_to_addrs(&domain, &addrs);
+        }
+        let client = client.build().map_err(|e| e.to_string())?;
+        let timestamp = Utc::now().timestamp();
+        let response = client
+            .post(url)
+            .timeout(Duration::from_millis(self.config.timeout_ms))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign_webhook_payload(secret, timestamp, body))
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// Sends the callback of votes with a completion webhook once they complete
+#[async_trait]
+impl TransitionObserver for CompletionWebhooks {
+    async fn status_changed(&self, vote: &Vote, _from: &VoteStatus, _actor: &str) {
+        if vote.status != VoteStatus::Completed {
+            return;
+        }
+        let (Some(url), Some(results)) = (&vote.completion_webhook_url, &vote.results) else {
+            return;
+        };
+        if let Err(e) = self.dispatch(url, results).await {
+            warn!("Failed to start completion callback for vote {}: {}", vote.id, e);
+        }
+    }
+}
+
+/// Ordering of a vote's delivery records: more attempts first, then finished over pending
+fn progress(delivery: &CompletionDelivery) -> (u32, bool) {
+    (delivery.attempts, delivery.status != CompletionDeliveryStatus::Pending)
+}
+
+/// Whether `ip` is a routable public address
+fn is_public(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // 100.64.0.0/10, carrier-grade NAT
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public(&IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // fc00::/7 unique local, fe80::/10 link-local
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Wiring of the stores and engines behind the API
+//!
+//! `AppComponents::from_config` picks implementations from configuration; tests and alternate
+//! deployments can replace any component with `with_*` before handing them to `AppState::new`.
+
+use std::sync::Arc;
+use commitment_engine::{CommitmentEngine, algorithms::Sha256CommitmentAlgorithm};
+use event_store::{EventStorage, store::MemoryEventStore};
+use crate::notify::{HttpNotificationSink, NotificationSink};
+use shared_config::AppConfig;
+use template_system::DefaultTemplateRegistry;
+use tracing::info;
+use vote_engine::{VoteService, services::MemoryVoteService};
+use vote_store::{VoteStore, MemoryVoteStore, SqliteVoteStore, PostgresVoteStore};
+
+/// Stores and engines the application state is built from
+#[derive(Clone)]
+pub struct AppComponents {
+    pub vote_store: Arc<dyn VoteStore>,
+    pub vote_service: Arc<dyn VoteService>,
+    pub template_registry: Arc<DefaultTemplateRegistry>,
+    pub commitment_engine: Arc<CommitmentEngine>,
+    pub event_store: Arc<dyn EventStorage>,
+    /// Where vote events are forwarded; `None` keeps them in process
+    pub notification_sink: Option<Arc<dyn NotificationSink>>,
+}
+
+impl AppComponents {
+    /// Build components for the configured database
+    pub async fn from_config(config: &AppConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let vote_store: Arc<dyn VoteStore> = if config.database.url.starts_with("sqlite:") {
+            info!("Using SQLite vote store");
+            Arc::new(SqliteVoteStore::new(&config.database).await?)
+        } else if config.database.url.starts_with("postgresql:") || config.database.url.starts_with("postgres:") {
+            info!("Using PostgreSQL vote store");
+            Arc::new(PostgresVoteStore::new(&config.database).await?)
+        } else {
+            info!("Using in-memory vote store");
+            Arc::new(MemoryVoteStore::new())
+        };
+
+        let mut components = Self::in_memory().with_vote_store(vote_store);
+        if let Some(url) = &config.server.notifications.url {
+            info!("Forwarding vote events to {}", url);
+            components = components.with_notification_sink(Arc::new(HttpNotificationSink::new(url.clone())));
+        }
+        Ok(components)
+    }
+
+    /// Components that keep everything in process memory
+    pub fn in_memory() -> Self {
+        let commitment_algorithm = Arc::new(Sha256CommitmentAlgorithm::new());
+        Self {
+            vote_store: Arc::new(MemoryVoteStore::new()),
+            // TODO: Replace with proper vote service that uses the vote store
+            vote_service: Arc::new(MemoryVoteService::new()),
+            template_registry: Arc::new(DefaultTemplateRegistry::new()),
+            commitment_engine: Arc::new(CommitmentEngine::new(commitment_algorithm)),
+            event_store: Arc::new(MemoryEventStore::new()),
+            notification_sink: None,
+        }
+    }
+
+    pub fn with_vote_store(mut self, vote_store: Arc<dyn VoteStore>) -> Self {
+        self.vote_store = vote_store;
+        self
+    }
+
+    pub fn with_vote_service(mut self, vote_service: Arc<dyn VoteService>) -> Self {
+        self.vote_service = vote_service;
+        self
+    }
+
+    pub fn with_template_registry(mut self, template_registry: Arc<DefaultTemplateRegistry>) -> Self {
+        self.template_registry = template_registry;
+        self
+    }
+
+    pub fn with_commitment_engine(mut self, commitment_engine: Arc<CommitmentEngine>) -> Self {
+        self.commitment_engine = commitment_engine;
+        self
+    }
+
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStorage>) -> Self {
+        self.event_store = event_store;
+        self
+    }
+
+    pub fn with_notification_sink(mut self, notification_sink: Arc<dyn NotificationSink>) -> Self {
+        self.notification_sink = Some(notification_sink);
+        self
+    }
+}
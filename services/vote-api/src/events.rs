@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Buffered events per subscriber before slow readers start missing them
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Kinds of vote events; names match notification-service's `NotificationType`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VoteEventType {
+    SessionCreated,
+    CommitmentSubmitted,
+    RevealCompleted,
+    ResultGenerated,
+}
+
+/// A change to a vote, serialized in the same shape as notification-service's `NotificationEvent`
+/// so consumers can share a decoder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteEvent {
+    pub id: Uuid,
+    pub event_type: VoteEventType,
+    /// The vote the event belongs to
+    pub session_id: Option<String>,
+    pub data: HashMap<String, serde_json::Value>,
+    pub timestamp: DateTime<Utc>,
+    pub source: String,
+}
+
+impl VoteEvent {
+    pub fn new(event_type: VoteEventType, vote_id: &str, data: HashMap<String, serde_json::Value>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type,
+            session_id: Some(vote_id.to_string()),
+            data,
+            timestamp: Utc::now(),
+            source: "vote-api".to_string(),
+        }
+    }
+
+    pub fn vote_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+}
+
+/// Fan-out of vote events to live subscribers; events published with no subscribers are dropped
+#[derive(Clone)]
+pub struct VoteEvents {
+    sender: broadcast::Sender<VoteEvent>,
+}
+
+impl Default for VoteEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VoteEvents {
+    pub fn new() -> Self {
+        Self { sender: broadcast::channel(EVENT_CHANNEL_CAPACITY).0 }
+    }
+
+    pub fn publish(&self, event: VoteEvent) {
+        // No receivers is the normal idle state, not an error
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<VoteEvent> {
+        self.sender.subscribe()
+    }
+}
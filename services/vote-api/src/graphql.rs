@@ -0,0 +1,341 @@
+//! GraphQL endpoint over the vote engine
+//!
+//! Serves the same data as the REST routes, but lets a client fetch a vote together with its
+//! commitments, reveals and results in one request. Queries are bounded in depth, complexity and
+//! page size. Enabled with the `graphql` feature.
+
+use std::sync::Arc;
+
+use async_graphql::connection::{self, Connection, Edge};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Enum, InputObject, Object, Schema, SimpleObject};
+use axum::{Extension, Json};
+use chrono::{DateTime, Utc};
+use shared_types::{Commitment, ListQuery, Reveal, Vote, VoteError};
+
+use crate::state::AppState;
+
+/// Votes fetched per `list_votes` page when resolving a connection
+const PAGE_SIZE: u32 = 100;
+/// Most votes one connection page may return, and the page size when neither `first` nor `last` is given
+pub const MAX_PAGE_SIZE: usize = 100;
+/// Deepest selection set a query may nest
+pub const MAX_QUERY_DEPTH: usize = 8;
+/// Highest complexity a query may reach; connection fields count their children once per requested vote
+pub const MAX_QUERY_COMPLEXITY: usize = 2_000;
+
+pub type VoteSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema, resolving against `state`
+pub fn build_schema(state: Arc<AppState>) -> VoteSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Execute a GraphQL request posted as JSON
+pub async fn graphql_handler(
+    Extension(schema): Extension<VoteSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "shared_types::VoteStatus")]
+pub enum VoteStatus {
+    Created,
+    CommitmentPhase,
+    RevealPhase,
+    Completed,
+    Cancelled,
+}
+
+/// Narrows the `votes` listing, as the REST list query does
+#[derive(InputObject, Default)]
+pub struct VoteFilter {
+    status: Option<VoteStatus>,
+    creator: Option<String>,
+}
+
+/// A vote; commitments, reveals and results are only fetched when selected
+///
+/// Votes listed through the `votes` connection carry their commitments and reveals, loaded for
+/// the whole page at once, so selecting them does not cost a lookup per vote.
+pub struct VoteNode {
+    vote: Vote,
+    commitments: Option<Vec<Commitment>>,
+    reveals: Option<Vec<Reveal>>,
+}
+
+impl VoteNode {
+    fn new(vote: Vote) -> Self {
+        Self { vote, commitments: None, reveals: None }
+    }
+}
+
+#[Object(name = "Vote")]
+impl VoteNode {
+    async fn id(&self) -> &str {
+        &self.vote.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.vote.title
+    }
+
+    async fn description(&self) -> &str {
+        &self.vote.description
+    }
+
+    async fn template_id(&self) -> &str {
+        &self.vote.template_id
+    }
+
+    async fn template_params(&self) -> async_graphql::Json<&serde_json::Value> {
+        async_graphql::Json(&self.vote.template_params)
+    }
+
+    async fn creator(&self) -> &str {
+        &self.vote.creator
+    }
+
+    async fn status(&self) -> VoteStatus {
+        self.vote.status.clone().into()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.vote.created_at
+    }
+
+    async fn commitment_start(&self) -> DateTime<Utc> {
+        self.vote.commitment_start
+    }
+
+    async fn commitment_end(&self) -> DateTime<Utc> {
+        self.vote.commitment_end
+    }
+
+    async fn reveal_start(&self) -> DateTime<Utc> {
+        self.vote.reveal_start
+    }
+
+    async fn reveal_end(&self) -> DateTime<Utc> {
+        self.vote.reveal_end
+    }
+
+    /// Commitment hashes; salts stay private until the voter reveals
+    async fn commitments(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<CommitmentNode>> {
+        let commitments = match &self.commitments {
+            Some(commitments) => commitments.clone(),
+            None => state(ctx).vote_engine.list_commitments(&self.vote.id).await?,
+        };
+        Ok(commitments
+            .into_iter()
+            .map(|c| CommitmentNode { voter: c.voter, commitment_hash: c.commitment_hash, created_at: c.created_at })
+            .collect())
+    }
+
+    async fn reveals(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<RevealNode>> {
+        let reveals = match &self.reveals {
+            Some(reveals) => reveals.clone(),
+            None => state(ctx).vote_engine.list_reveals(&self.vote.id).await?,
+        };
+        Ok(reveals
+            .into_iter()
+            .map(|r| RevealNode {
+                voter: r.voter,
+                value: async_graphql::Json(r.value),
+                salt: r.salt,
+                created_at: r.created_at,
+            })
+            .collect())
+    }
+
+    /// Stored results; `null` until they have been calculated
+    async fn results(&self) -> Option<ResultsNode> {
+        self.vote.results.as_ref().map(|r| ResultsNode {
+            total_votes: r.total_votes,
+            results: async_graphql::Json(r.results.clone()),
+            winner: r.winner.as_ref().and_then(|w| w.winner.clone()),
+            tied: r.winner.as_ref().map(|w| w.tied.clone()).unwrap_or_default(),
+            commitment_root: r.commitment_root.clone(),
+            calculated_at: r.calculated_at,
+        })
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Commitment")]
+pub struct CommitmentNode {
+    voter: String,
+    commitment_hash: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Reveal")]
+pub struct RevealNode {
+    voter: String,
+    value: async_graphql::Json<serde_json::Value>,
+    salt: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "VoteResults")]
+pub struct ResultsNode {
+    total_votes: u32,
+    results: async_graphql::Json<serde_json::Value>,
+    winner: Option<String>,
+    tied: Vec<String>,
+    commitment_root: Option<String>,
+    calculated_at: DateTime<Utc>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Template")]
+pub struct TemplateNode {
+    id: String,
+    name: String,
+    description: String,
+    schema: async_graphql::Json<serde_json::Value>,
+    params_schema: async_graphql::Json<serde_json::Value>,
+}
+
+#[derive(SimpleObject)]
+pub struct VoteStats {
+    total_votes: u32,
+    created: u32,
+    commitment_phase: u32,
+    reveal_phase: u32,
+    completed: u32,
+    cancelled: u32,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn vote(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Option<VoteNode>> {
+        match state(ctx).vote_engine.get_vote(&id).await {
+            Ok(vote) => Ok(Some(VoteNode::new(vote))),
+            Err(VoteError::VoteNotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Votes as a Relay connection; cursors are positions in the listing
+    ///
+    /// `first` and `last` may ask for at most `MAX_PAGE_SIZE` votes, which is also the page size
+    /// when neither is given.
+    #[graphql(complexity = "first.or(last).map_or(MAX_PAGE_SIZE, |n| n.max(0) as usize) * child_complexity")]
+    async fn votes(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<VoteFilter>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> async_graphql::Result<Connection<usize, VoteNode>> {
+        for (name, requested) in [("first", first), ("last", last)] {
+            if requested.is_some_and(|n| n as usize > MAX_PAGE_SIZE) {
+                return Err(format!("`{}` may ask for at most {} votes", name, MAX_PAGE_SIZE).into());
+            }
+        }
+        let first = first.or(if last.is_none() { Some(MAX_PAGE_SIZE as i32) } else { None });
+        let look_ahead = ctx.look_ahead();
+        let selected = |field: &str| {
+            look_ahead.field("edges").field("node").field(field).exists() || look_ahead.field("nodes").field(field).exists()
+        };
+        let (with_commitments, with_reveals) = (selected("commitments"), selected("reveals"));
+        let state = state(ctx);
+        let VoteFilter { status, creator } = filter.unwrap_or_default();
+        let status: Option<shared_types::VoteStatus> = status.map(Into::into);
+        connection::query(after, before, first, last, |after, before, first, last| async move {
+            let list = |page: u32, page_size: u32| ListQuery { page, page_size, status: status.clone(), creator: creator.clone() };
+            let total = state.vote_engine.list_votes(list(0, 1)).await?.total as usize;
+
+            let mut start = after.map_or(0, |after| after + 1);
+            let mut end = before.unwrap_or(total).min(total);
+            if let Some(first) = first {
+                end = end.min(start + first);
+            }
+            if let Some(last) = last {
+                start = start.max(end.saturating_sub(last));
+            }
+            start = start.min(end);
+
+            let mut votes = Vec::with_capacity(end - start);
+            let mut page = start as u32 / PAGE_SIZE;
+            while votes.len() < end - start {
+                let items = state.vote_engine.list_votes(list(page, PAGE_SIZE)).await?.items;
+                if items.is_empty() {
+                    break;
+                }
+                let page_start = (page * PAGE_SIZE) as usize;
+                votes.extend(items.into_iter().enumerate().filter_map(|(i, vote)| {
+                    let position = page_start + i;
+                    (start..end).contains(&position).then_some((position, vote))
+                }));
+                page += 1;
+            }
+
+            let ids: Vec<String> = votes.iter().map(|(_, vote)| vote.id.clone()).collect();
+            let mut commitments = match with_commitments {
+                true => Some(state.vote_engine.list_commitments_for(&ids).await?),
+                false => None,
+            };
+            let mut reveals = match with_reveals {
+                true => Some(state.vote_engine.list_reveals_for(&ids).await?),
+                false => None,
+            };
+
+            let mut connection = Connection::new(start > 0, end < total);
+            connection.edges.extend(votes.into_iter().map(|(position, vote)| {
+                let node = VoteNode {
+                    commitments: commitments.as_mut().map(|all| all.remove(&vote.id).unwrap_or_default()),
+                    reveals: reveals.as_mut().map(|all| all.remove(&vote.id).unwrap_or_default()),
+                    vote,
+                };
+                Edge::new(position, node)
+            }));
+            Ok::<_, async_graphql::Error>(connection)
+        })
+        .await
+    }
+
+    async fn templates(&self, ctx: &Context<'_>) -> Vec<TemplateNode> {
+        state(ctx)
+            .template_registry
+            .templates()
+            .iter()
+            .map(|template| TemplateNode {
+                id: template.id().to_string(),
+                name: template.name().to_string(),
+                description: template.description().to_string(),
+                schema: async_graphql::Json(template.get_schema()),
+                params_schema: async_graphql::Json(template.params_schema()),
+            })
+            .collect()
+    }
+
+    /// Vote counts by status
+    async fn stats(&self, ctx: &Context<'_>) -> async_graphql::Result<VoteStats> {
+        let counts = state(ctx).vote_engine.vote_status_counts().await?;
+        Ok(VoteStats {
+            total_votes: counts.total,
+            created: counts.created,
+            commitment_phase: counts.commitment_phase,
+            reveal_phase: counts.reveal_phase,
+            completed: counts.completed,
+            cancelled: counts.cancelled,
+        })
+    }
+}
+
+fn state<'a>(ctx: &Context<'a>) -> &'a Arc<AppState> {
+    ctx.data_unchecked::<Arc<AppState>>()
+}
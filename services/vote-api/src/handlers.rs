@@ -1,16 +1,29 @@
 use axum::{
     extract::{Path, Query, State, WebSocketUpgrade},
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Json, Response,
+    },
 };
+use serde_json::json;
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
 use tracing::{info, error, debug};
 
+use event_store::{EventQuery, EventStoreError, QueryExecutor, QueryPlanner};
 use shared_types::*;
+use crate::completion::CompletionDelivery;
+use crate::events::VoteEventType;
+use crate::middleware::caller_id;
 use crate::state::AppState;
+use template_system::VoteTemplate;
 
 /// Health check handler
-pub async fn health_handler() -> Result<Json<HealthResponse>, StatusCode> {
+pub async fn health_handler() -> Result<Json<HealthResponse>, ApiError> {
     let mut services = std::collections::HashMap::new();
     services.insert("vote-api".to_string(), ServiceStatus {
         status: "healthy".to_string(),
@@ -27,25 +40,87 @@ pub async fn health_handler() -> Result<Json<HealthResponse>, StatusCode> {
     Ok(Json(response))
 }
 
+/// Request and query timings in the Prometheus text format
+pub async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        shared_logging::metrics().render(),
+    )
+}
+
+/// Change the service log level at runtime
+pub async fn set_log_level_handler(
+    Json(request): Json<SetLogLevelRequest>,
+) -> Result<Json<SetLogLevelResponse>, ApiError> {
+    match shared_logging::set_log_level(&request.level) {
+        Ok(()) => {
+            info!("Log level changed to {}", request.level);
+            let response = SetLogLevelResponse {
+                level: request.level,
+                success: true,
+                message: "Log level updated".to_string(),
+            };
+            Ok(Json(response))
+        }
+        Err(e) => {
+            error!("Failed to set log level {}: {}", request.level, e);
+            Err(ApiError::bad_request("log.invalid_level", e.to_string()))
+        }
+    }
+}
+
+/// Run an `EventQuery` against the event store, narrowing candidates through its indexes
+pub async fn query_events_handler(
+    State(state): State<Arc<AppState>>,
+    Json(query): Json<EventQuery>,
+) -> Result<Json<Paginated<event_store::Event>>, ApiError> {
+    let result = async {
+        let candidates = QueryPlanner::fetch_candidates(state.event_store.as_ref(), &query).await?;
+        QueryExecutor::execute_paginated(&query, &candidates)
+    }
+    .await;
+    match result {
+        Ok(result) => Ok(Json(result)),
+        Err(EventStoreError::Query(e)) => Err(ApiError::bad_request("events.invalid_query", e)),
+        Err(e) => {
+            error!("Failed to query events: {}", e);
+            Err(ApiError::internal(e.to_string()))
+        }
+    }
+}
+
 /// Create a new vote
 pub async fn create_vote_handler(
     State(state): State<Arc<AppState>>,
-    Json(request): Json<CreateVoteRequest>,
-) -> Result<Json<CreateVoteResponse>, StatusCode> {
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<CreateVoteRequest>,
+) -> Result<Json<CreateVoteResponse>, ApiError> {
     info!("Creating new vote: {}", request.config.title);
+    if let Some(url) = &request.config.completion_webhook_url {
+        state.completion_webhooks.check_url(url).await.map_err(|e| {
+            ApiError::validation(vec![FieldError::new("config.completion_webhook_url", e)])
+        })?;
+    }
     
-    match state.vote_engine.create_vote(request.config).await {
-        Ok(vote_id) => {
+    let creator = caller_id(&headers);
+    match state.vote_engine.create_vote_with_request_id(&creator, request.config, request.client_request_id).await {
+        Ok((vote_id, created)) => {
+            let message = if created {
+                state.record_change(&vote_id, VoteEventType::SessionCreated, HashMap::new()).await;
+                "Vote created successfully"
+            } else {
+                "Vote already created for this request"
+            };
             let response = CreateVoteResponse {
                 vote_id,
                 success: true,
-                message: "Vote created successfully".to_string(),
+                message: message.to_string(),
             };
             Ok(Json(response))
         }
         Err(e) => {
             error!("Failed to create vote: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e.into())
         }
     }
 }
@@ -54,7 +129,8 @@ pub async fn create_vote_handler(
 pub async fn get_vote_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
-) -> Result<Json<GetVoteResponse>, StatusCode> {
+    headers: HeaderMap,
+) -> Result<Conditional<GetVoteResponse>, ApiError> {
     debug!("Getting vote: {}", id);
     
     match state.vote_engine.get_vote(&id).await {
@@ -63,33 +139,125 @@ pub async fn get_vote_handler(
                 vote,
                 success: true,
             };
-            Ok(Json(response))
+            Conditional::new(&headers, response)
         }
         Err(e) => {
             error!("Failed to get vote {}: {}", id, e);
-            Err(StatusCode::NOT_FOUND)
+            Err(e.into())
         }
     }
 }
 
+/// Default and maximum hold time for `wait_vote_handler`
+const DEFAULT_WAIT: Duration = Duration::from_secs(30);
+const MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Long-poll until the vote changes past `since_version`, or 304 once the timeout elapses
+pub async fn wait_vote_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<WaitQuery>,
+) -> Result<Response, ApiError> {
+    let timeout = match query.timeout.as_deref() {
+        Some(raw) => parse_wait_timeout(raw)
+            .ok_or_else(|| ApiError::bad_request("wait.invalid_timeout", format!("Invalid timeout: {}", raw)))?,
+        None => DEFAULT_WAIT,
+    }
+    .min(MAX_WAIT);
+    debug!("Waiting up to {:?} for vote {} past version {}", timeout, id, query.since_version);
+
+    // Fail fast on unknown votes instead of holding the connection
+    state.vote_engine.get_vote(&id).await?;
+    // Waiters from before a restart catch up on changes stored since
+    state.vote_version(&id).await.map_err(|e| ApiError::internal(e.to_string()))?;
+
+    match state.watchers.wait_for_change(&id, query.since_version, timeout).await {
+        Some(version) => {
+            let vote = state.vote_engine.get_vote(&id).await?;
+            Ok(Json(WaitVoteResponse { vote, version, success: true }).into_response())
+        }
+        None => Ok(StatusCode::NOT_MODIFIED.into_response()),
+    }
+}
+
+fn parse_wait_timeout(raw: &str) -> Option<Duration> {
+    if let Some(ms) = raw.strip_suffix("ms") {
+        return ms.parse().ok().map(Duration::from_millis);
+    }
+    raw.strip_suffix('s').unwrap_or(raw).parse().ok().map(Duration::from_secs)
+}
+
 /// List votes with pagination
 pub async fn list_votes_handler(
     State(state): State<Arc<AppState>>,
     Query(query): Query<ListQuery>,
-) -> Result<Json<ListVotesResponse>, StatusCode> {
+) -> Result<Json<ListVotesResponse>, ApiError> {
     debug!("Listing votes: page={}, size={}", query.page, query.page_size);
     
     match state.vote_engine.list_votes(query).await {
         Ok(votes) => {
             let response = ListVotesResponse {
-                votes,
+                votes: votes.into(),
                 success: true,
             };
             Ok(Json(response))
         }
         Err(e) => {
             error!("Failed to list votes: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e.into())
+        }
+    }
+}
+
+/// Votes a voter has committed or revealed in, newest first
+pub async fn voter_activity_handler(
+    State(state): State<Arc<AppState>>,
+    Path(voter): Path<String>,
+    Query(query): Query<VoterActivityQuery>,
+) -> Result<Json<VoterActivityResponse>, ApiError> {
+    debug!("Getting activity for voter {}: page={}, size={}", voter, query.page, query.page_size);
+    
+    let votes = state.vote_engine.get_voter_activity(&voter, query.page, query.page_size).await?;
+    Ok(Json(VoterActivityResponse {
+        voter,
+        votes: votes.into(),
+        success: true,
+    }))
+}
+
+/// Cancel a vote that has not completed; the admin key's caller id is recorded as the actor
+pub async fn cancel_vote_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<GetVoteResponse>, ApiError> {
+    info!("Cancelling vote: {}", id);
+    
+    match state.vote_engine.cancel_vote(&id, &caller_id(&headers)).await {
+        Ok(vote) => {
+            state.wake_watchers(&id).await;
+            Ok(Json(GetVoteResponse { vote, success: true }))
+        }
+        Err(e) => {
+            error!("Failed to cancel vote {}: {}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Status changes, result computations and cancellation of a vote, oldest first
+pub async fn vote_history_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<VoteHistoryResponse>, ApiError> {
+    debug!("Getting history for vote: {}", id);
+    
+    state.vote_engine.get_vote(&id).await?;
+    match state.get_vote_history(&id).await {
+        Ok(entries) => Ok(Json(VoteHistoryResponse { vote_id: id, entries, success: true })),
+        Err(e) => {
+            error!("Failed to get history for vote {}: {}", id, e);
+            Err(ApiError::internal(e.to_string()))
         }
     }
 }
@@ -98,20 +266,27 @@ pub async fn list_votes_handler(
 pub async fn get_results_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
-) -> Result<Json<GetResultsResponse>, StatusCode> {
+    headers: HeaderMap,
+) -> Result<Conditional<GetResultsResponse>, ApiError> {
     debug!("Getting results for vote: {}", id);
     
+    let vote = state.vote_engine.get_vote(&id).await?;
+    let already_completed = matches!(vote.status, VoteStatus::Completed);
     match state.vote_engine.get_results(&id).await {
         Ok(results) => {
+            if !already_completed {
+                let data = HashMap::from([("total_votes".to_string(), json!(results.total_votes))]);
+                state.record_change(&id, VoteEventType::ResultGenerated, data).await;
+            }
             let response = GetResultsResponse {
                 results,
                 success: true,
             };
-            Ok(Json(response))
+            Conditional::new(&headers, response)
         }
         Err(e) => {
             error!("Failed to get results for vote {}: {}", id, e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e.into())
         }
     }
 }
@@ -120,15 +295,23 @@ pub async fn get_results_handler(
 pub async fn commit_vote_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
-    Json(request): Json<CommitRequest>,
-) -> Result<Json<CommitResponse>, StatusCode> {
+    ValidatedJson(request): ValidatedJson<CommitRequest>,
+) -> Result<Json<CommitResponse>, ApiError> {
     info!("Processing commitment for vote: {}", id);
     
+    let voter = request.voter.clone();
     match state.vote_engine.commit_vote(&id, request).await {
-        Ok(response) => Ok(Json(response)),
+        Ok(response) => {
+            let data = HashMap::from([
+                ("voter".to_string(), json!(voter)),
+                ("commitment_id".to_string(), json!(response.commitment_id)),
+            ]);
+            state.record_change(&id, VoteEventType::CommitmentSubmitted, data).await;
+            Ok(Json(response))
+        }
         Err(e) => {
             error!("Failed to process commitment for vote {}: {}", id, e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(e.into())
         }
     }
 }
@@ -137,15 +320,23 @@ pub async fn commit_vote_handler(
 pub async fn reveal_vote_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
-    Json(request): Json<RevealRequest>,
-) -> Result<Json<RevealResponse>, StatusCode> {
+    ValidatedJson(request): ValidatedJson<RevealRequest>,
+) -> Result<Json<RevealResponse>, ApiError> {
     info!("Processing reveal for vote: {}", id);
     
+    let voter = request.voter.clone();
     match state.vote_engine.reveal_vote(&id, request).await {
-        Ok(response) => Ok(Json(response)),
+        Ok(response) => {
+            let data = HashMap::from([
+                ("voter".to_string(), json!(voter)),
+                ("reveal_id".to_string(), json!(response.reveal_id)),
+            ]);
+            state.record_change(&id, VoteEventType::RevealCompleted, data).await;
+            Ok(Json(response))
+        }
         Err(e) => {
             error!("Failed to process reveal for vote {}: {}", id, e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(e.into())
         }
     }
 }
@@ -153,10 +344,14 @@ pub async fn reveal_vote_handler(
 /// List available templates
 pub async fn list_templates_handler(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     debug!("Listing templates");
     
-    let templates = state.template_registry.list();
+    let templates: Vec<serde_json::Value> = state.template_registry
+        .templates()
+        .iter()
+        .map(|template| template_descriptor(template.as_ref()))
+        .collect();
     let response = serde_json::json!({
         "templates": templates,
         "success": true
@@ -169,7 +364,7 @@ pub async fn list_templates_handler(
 pub async fn verify_results_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
-) -> Result<Json<VerifyResultsResponse>, StatusCode> {
+) -> Result<Json<VerifyResultsResponse>, ApiError> {
     debug!("Verifying results for vote: {}", id);
     
     match state.vote_engine.verify_results(&id).await {
@@ -182,7 +377,38 @@ pub async fn verify_results_handler(
         }
         Err(e) => {
             error!("Failed to verify results for vote {}: {}", id, e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(e.into())
+        }
+    }
+}
+
+/// Render the vote's results as a self-contained HTML report
+pub async fn vote_report_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Html<String>, ApiError> {
+    debug!("Rendering results report for vote: {}", id);
+
+    state.vote_engine.generate_vote_report(&id).await.map(Html).map_err(|e| {
+        error!("Failed to render report for vote {}: {}", id, e);
+        e.into()
+    })
+}
+
+/// Delivery status of the vote's completion callback
+pub async fn completion_webhook_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<CompletionDelivery>, ApiError> {
+    match state.completion_webhooks.delivery(&id).await {
+        Ok(Some(delivery)) => Ok(Json(delivery)),
+        Ok(None) => Err(
+            ApiError::not_found("completion_webhook.not_found", format!("No completion callback for vote {}", id))
+                .with_details(json!({ "id": id })),
+        ),
+        Err(e) => {
+            error!("Failed to read completion callback of vote {}: {}", id, e);
+            Err(ApiError::internal(e.to_string()))
         }
     }
 }
@@ -191,33 +417,39 @@ pub async fn verify_results_handler(
 pub async fn get_template_handler(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     debug!("Getting template: {}", id);
     
     match state.template_registry.get(&id) {
         Ok(template) => {
-            let response = serde_json::json!({
-                "id": template.id(),
-                "name": template.name(),
-                "description": template.description(),
-                "schema": template.get_schema(),
-                "success": true
-            });
+            let mut response = template_descriptor(template.as_ref());
+            response["success"] = serde_json::json!(true);
             Ok(Json(response))
         }
         Err(e) => {
             error!("Failed to get template {}: {}", id, e);
-            Err(StatusCode::NOT_FOUND)
+            Err(ApiError::not_found("template.not_found", e.to_string()).with_details(serde_json::json!({ "id": id })))
         }
     }
 }
 
+/// Template ID, name, description and the value and params schemas
+fn template_descriptor(template: &dyn VoteTemplate) -> serde_json::Value {
+    serde_json::json!({
+        "id": template.id(),
+        "name": template.name(),
+        "description": template.description(),
+        "schema": template.get_schema(),
+        "params_schema": template.params_schema(),
+    })
+}
+
 /// WebSocket handler for real-time updates
 pub async fn websocket_handler(
     State(_state): State<Arc<AppState>>,
     Path(id): Path<String>,
     ws: WebSocketUpgrade,
-) -> Result<axum::response::Response, StatusCode> {
+) -> Result<axum::response::Response, ApiError> {
     debug!("WebSocket connection for vote: {}", id);
     
     // TODO: Implement WebSocket handler for real-time vote updates
@@ -230,3 +462,31 @@ pub async fn websocket_handler(
         drop(socket);
     }))
 }
+
+/// Interval between keep-alive comments on idle event streams
+const SSE_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Server-sent event stream of a vote's commit, reveal and result events
+///
+/// Only events published after the client connects are sent. The stream ends when the client
+/// disconnects, which drops its broadcast receiver.
+pub async fn vote_events_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    state.vote_engine.get_vote(&id).await?;
+    debug!("Event stream opened for vote: {}", id);
+
+    let events = BroadcastStream::new(state.events.subscribe()).filter_map(move |received| {
+        // A lagging client skips the events it missed rather than closing the stream
+        let event = received.ok().filter(|event| event.vote_id() == Some(id.as_str()))?;
+        let sse = Event::default()
+            .id(event.id.to_string())
+            .event(format!("{:?}", event.event_type))
+            .json_data(&event)
+            .ok()?;
+        Some(Ok(sse))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(SSE_KEEP_ALIVE).text("keep-alive")))
+}
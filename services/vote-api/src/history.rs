@@ -0,0 +1,126 @@
+//! Vote history
+//!
+//! Every recorded vote change is also stored in the event store. Status changes are stored by
+//! [`HistoryRecorder`] from the engine's transitions, whatever caused them; a vote's history is
+//! rebuilt from those audit events.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use event_store::{Event, EventSeverity, EventStorage, EventType};
+use serde_json::json;
+use shared_types::{Vote, VoteHistoryEntry, VoteHistoryKind, VoteStatus};
+use tracing::warn;
+use vote_engine::TransitionObserver;
+pub use vote_engine::SYSTEM_ACTOR;
+
+use crate::events::{VoteEvent, VoteEventType};
+
+/// Audit event data key holding the vote's status after the change
+const STATUS_KEY: &str = "status";
+/// Audit event data key holding who caused the change
+const ACTOR_KEY: &str = "actor";
+/// Custom event type of status changes
+const STATUS_CHANGED: &str = "StatusChanged";
+
+/// The audit event stored for `event`; `status` is the vote's status after the change, given
+/// for its creation only since later status changes are recorded by [`HistoryRecorder`]
+pub fn audit_event(event: &VoteEvent, status: Option<&VoteStatus>) -> Event {
+    let event_type = match event.event_type {
+        VoteEventType::SessionCreated => EventType::SessionCreated,
+        VoteEventType::CommitmentSubmitted => EventType::CommitmentSubmitted,
+        VoteEventType::RevealCompleted => EventType::RevealCompleted,
+        VoteEventType::ResultGenerated => EventType::ResultGenerated,
+    };
+    let actor = event.data.get("voter").and_then(|v| v.as_str()).unwrap_or(SYSTEM_ACTOR);
+    let mut audit = Event::new(
+        event_type,
+        EventSeverity::Info,
+        event.source.clone(),
+        format!("{:?}", event.event_type),
+        event.session_id.clone(),
+        None,
+    );
+    audit.id = event.id;
+    audit.timestamp = event.timestamp;
+    audit.data = event.data.clone();
+    let audit = audit.with_data(ACTOR_KEY.to_string(), json!(actor));
+    match status {
+        Some(status) => audit.with_data(STATUS_KEY.to_string(), json!(status)),
+        None => audit,
+    }
+}
+
+/// Stores an audit event for every status change the engine persists, so phase changes made
+/// on a schedule, by an admin, or by cancellation all show up in the history
+#[derive(Clone)]
+pub struct HistoryRecorder {
+    event_store: Arc<dyn EventStorage>,
+}
+
+impl HistoryRecorder {
+    pub fn new(event_store: Arc<dyn EventStorage>) -> Self {
+        Self { event_store }
+    }
+}
+
+#[async_trait]
+impl TransitionObserver for HistoryRecorder {
+    async fn status_changed(&self, vote: &Vote, from: &VoteStatus, actor: &str) {
+        let event = Event::new(
+            EventType::Custom(STATUS_CHANGED.to_string()),
+            EventSeverity::Info,
+            "vote-api".to_string(),
+            format!("{:?} -> {:?}", from, vote.status),
+            Some(vote.id.clone()),
+            None,
+        )
+        .with_data(STATUS_KEY.to_string(), json!(vote.status))
+        .with_data(ACTOR_KEY.to_string(), json!(actor));
+        if let Err(e) = self.event_store.store_event(event).await {
+            warn!("Failed to store status change of vote {}: {}", vote.id, e);
+        }
+    }
+}
+
+/// Rebuild a vote's history from its audit events, oldest first
+///
+/// Commits and reveals only show up when they moved the vote to another phase.
+pub fn reconstruct(mut events: Vec<Event>) -> Vec<VoteHistoryEntry> {
+    events.sort_by_key(|e| (e.version, e.timestamp));
+    let mut entries = Vec::new();
+    let mut current: Option<VoteStatus> = None;
+    for event in events {
+        let status = event.data.get(STATUS_KEY).and_then(|s| serde_json::from_value::<VoteStatus>(s.clone()).ok());
+        let actor = event.data.get(ACTOR_KEY).and_then(|a| a.as_str()).unwrap_or(SYSTEM_ACTOR).to_string();
+        let entry = |kind, from_status, to_status, details| VoteHistoryEntry {
+            version: event.version,
+            kind,
+            from_status,
+            to_status,
+            actor: actor.clone(),
+            timestamp: event.timestamp,
+            details,
+        };
+
+        if event.event_type == EventType::SessionCreated {
+            entries.push(entry(VoteHistoryKind::Created, None, status.clone(), None));
+            current = status;
+            continue;
+        }
+        if event.event_type == EventType::ResultGenerated {
+            let details: serde_json::Map<_, _> = event.data.iter()
+                .filter(|(key, _)| *key != STATUS_KEY && *key != ACTOR_KEY)
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            let details = (!details.is_empty()).then(|| details.into());
+            entries.push(entry(VoteHistoryKind::ResultsComputed, None, None, details));
+        }
+        if let Some(to) = status.filter(|to| current.as_ref() != Some(to)) {
+            let kind = if to == VoteStatus::Cancelled { VoteHistoryKind::Cancelled } else { VoteHistoryKind::StatusChanged };
+            entries.push(entry(kind, current.take(), Some(to.clone()), None));
+            current = Some(to);
+        }
+    }
+    entries
+}
@@ -0,0 +1,22 @@
+//! Vote API Service
+//!
+//! HTTP front end for creating votes, collecting commitments and reveals, and serving results
+
+pub mod completion;
+pub mod components;
+pub mod events;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod routes;
+pub mod handlers;
+pub mod history;
+pub mod middleware;
+pub mod notify;
+pub mod state;
+pub mod watch;
+
+pub use routes::{cors_layer, create_router, with_http_layers};
+pub use completion::{CompletionDelivery, CompletionDeliveryStatus, CompletionWebhooks};
+pub use components::AppComponents;
+pub use notify::{CircuitBreaker, HttpNotificationSink, NotificationDispatcher, NotificationSink};
+pub use state::AppState;
@@ -1,19 +1,13 @@
 use axum::Router;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tower_http::cors::{CorsLayer, Any};
-use tower_http::trace::TraceLayer;
-use tracing::info;
+use std::time::Duration;
+use tracing::{info, warn};
 
-use shared_logging::init_logging_from_env;
+use shared_logging::{init_logging_from_env, reload_log_filter, ShutdownSequence, ShutdownStage};
 use shared_config::AppConfig;
-use crate::routes::create_router;
-use crate::state::AppState;
-
-mod routes;
-mod handlers;
-mod middleware;
-mod state;
+use vote_api::{create_router, with_http_layers, AppState};
+use vote_store::retention::{RetentionJob, RetentionPolicy};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -23,18 +17,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Starting Vote API service");
     
     // Load configuration
-    let config = AppConfig::load_from_env()?;
+    let config = AppConfig::load()?;
+    config.validate()?;
     
-    // Initialize application state
-    let state = AppState::new(config).await?;
+    // Re-read configuration on SIGHUP
+    #[cfg(unix)]
+    let _reload_handle = {
+        let mut current = config.clone();
+        shared_logging::spawn_sighup_handler(move || reload_config(&mut current))?
+    };
     
-    // Extract server configuration before moving state
+    // Initialize application state
+    let state = Arc::new(AppState::from_config(config).await?);
     let server_config = state.config.server.clone();
     
+    // Remove ended votes past the retention age
+    let retention = &state.config.database.retention;
+    if retention.enabled {
+        info!("Retention cleanup every {}s for votes older than {} days", retention.interval_seconds, retention.max_age_days);
+        RetentionJob::new(state.vote_store.clone(), RetentionPolicy::from_config(retention))
+            .spawn(Duration::from_secs(retention.interval_seconds));
+    }
+    
     // Create router
-    let app: Router = create_router(Arc::new(state))
-        .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any).allow_headers(Any))
-        .layer(TraceLayer::new_for_http());
+    let app: Router = with_http_layers(create_router(state.clone()), &server_config);
     
     // Start server
     let addr: SocketAddr = format!("{}:{}", server_config.bind, server_config.port)
@@ -44,13 +50,68 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Vote API listening on {}", addr);
     
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+    let mut server = tokio::spawn(async move {
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                let _ = stop_rx.await;
+            })
+            .await
+    });
+    
+    tokio::select! {
+        _ = shutdown_signal() => {}
+        result = &mut server => {
+            result??;
+            return Ok(());
+        }
+    }
+    
+    // Each stage gets as long as a request may take
+    let mut sequence = ShutdownSequence::new(Duration::from_secs(server_config.request_timeout_seconds));
+    sequence.add(ShutdownStage::StopAccepting, "http-listener", async move {
+        let _ = stop_tx.send(());
+    });
+    let server_abort = AbortOnDrop(server.abort_handle());
+    sequence.add(ShutdownStage::DrainInFlight, "http-requests", async move {
+        // dropped with this step when the stage times out, which stops the connections still open
+        let _server_abort = server_abort;
+        match server.await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("HTTP server stopped with error: {}", e),
+            Err(e) => warn!("HTTP server task failed: {}", e),
+        }
+    });
+    if let Some(notifier) = state.notifier.clone() {
+        let probe = notifier.clone();
+        sequence.add_with_remaining(
+            ShutdownStage::FlushQueues,
+            "notifications",
+            async move { notifier.flush().await },
+            move || probe.pending(),
+        );
+    }
+    let vote_store = state.vote_store.clone();
+    sequence.add(ShutdownStage::ClosePools, "vote-store", async move { vote_store.close().await });
+    if !sequence.run().await.is_clean() {
+        warn!("Vote API stopped with unfinished shutdown steps");
+    }
+    
+    #[cfg(feature = "otel")]
+    shared_logging::otel::shutdown();
     
     Ok(())
 }
 
+/// Aborts a task when dropped; a dropped `JoinHandle` would leave it running
+struct AbortOnDrop(tokio::task::AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
@@ -76,3 +137,26 @@ async fn shutdown_signal() {
     }
     info!("signal received, starting graceful shutdown");
 }
+
+/// Re-read configuration and apply the parts that can change at runtime
+#[cfg_attr(not(unix), allow(dead_code))]
+fn reload_config(current: &mut AppConfig) {
+    let new_config = match AppConfig::load().and_then(|c| c.validate().map(|_| c)) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("Ignoring configuration reload: {}", e);
+            return;
+        }
+    };
+
+    for section in current.restart_required_changes(&new_config) {
+        warn!("Change to {} configuration requires a restart to take effect", section);
+    }
+
+    if current.logging.level != new_config.logging.level {
+        match reload_log_filter(&new_config.logging.level) {
+            Ok(()) => current.logging.level = new_config.logging.level,
+            Err(e) => warn!("Failed to apply log level {}: {}", new_config.logging.level, e),
+        }
+    }
+}
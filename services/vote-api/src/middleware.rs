@@ -1,10 +1,21 @@
 use axum::{
-    extract::Request,
-    http::{HeaderMap, HeaderValue, StatusCode},
+    body::{to_bytes, Body, Bytes},
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use serde_json::Value;
+use shared_config::BodyLoggingConfig;
+use shared_types::ApiError;
+use shared_utils::crypto::hash_value;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{info, warn};
+use vote_engine::SYSTEM_CREATOR;
+
+/// Replacement for redacted field values in logged bodies
+const REDACTED: &str = "[REDACTED]";
 
 /// Request logging middleware
 #[allow(dead_code)]
@@ -39,6 +50,163 @@ pub async fn logging_middleware(
     Ok(response)
 }
 
+/// Let a request through only when it carries the configured admin key as a bearer token
+///
+/// Keys are compared through their SHA-256 digests so the comparison time does not depend on
+/// how much of the key matched. Without a configured key every request is refused.
+pub async fn admin_auth_middleware(
+    State(admin_key): State<Option<Arc<str>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(admin_key) = admin_key else {
+        return ApiError::forbidden("auth.admin_disabled", "Admin routes are disabled: no admin API key is configured")
+            .into_response();
+    };
+    match bearer_token(request.headers()) {
+        Some(key) if hash_value(key) == hash_value(&admin_key) => next.run(request).await,
+        _ => ApiError::unauthorized("auth.invalid_api_key", "A valid admin API key is required").into_response(),
+    }
+}
+
+/// The bearer token of the request's `Authorization` header, if any
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Who a request acts for: its bearer API key, identified by a digest prefix, or
+/// [`SYSTEM_CREATOR`] for requests without one
+pub fn caller_id(headers: &HeaderMap) -> String {
+    match bearer_token(headers) {
+        Some(key) => format!("api-key:{}", &hash_value(key)[..16]),
+        None => SYSTEM_CREATOR.to_string(),
+    }
+}
+
+/// Record each request's duration, warning when it takes longer than the threshold
+///
+/// Requests are labelled by their route pattern rather than the concrete path, so votes
+/// do not each get their own time series.
+pub async fn timing_middleware(
+    State(threshold): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    shared_logging::metrics().record_request(
+        "vote-api",
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+        threshold,
+    );
+    response
+}
+
+/// Debug middleware logging request and response bodies with sensitive fields redacted
+///
+/// The request body is buffered, up to `max_request_size`, and handed to the handler
+/// unchanged. Event streams are passed through without buffering their response.
+pub async fn body_logging_middleware(
+    State((config, max_request_size)): State<(Arc<BodyLoggingConfig>, usize)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    if !config.applies_to(&path) {
+        return next.run(request).await;
+    }
+    let method = request.method().clone();
+
+    let (parts, body) = request.into_parts();
+    let bytes = match to_bytes(body, max_request_size).await {
+        Ok(bytes) => bytes,
+        Err(e) => return ApiError::new(413, "request.too_large", e.to_string()).into_response(),
+    };
+    info!("Request body for {} {}: {}", method, path, loggable_body(&config, &bytes));
+    let response = next.run(Request::from_parts(parts, Body::from(bytes))).await;
+
+    let is_stream = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|ct| ct.as_bytes().starts_with(b"text/event-stream"));
+    if is_stream {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to read response body for {} {}: {}", method, path, e);
+            return ApiError::internal("Failed to read response body").into_response();
+        }
+    };
+    info!(
+        "Response body for {} {} ({}): {}",
+        method, path, parts.status, loggable_body(&config, &bytes)
+    );
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// The body as it should appear in the log: JSON with sensitive fields replaced, cut to length
+pub fn loggable_body(config: &BodyLoggingConfig, body: &Bytes) -> String {
+    if body.is_empty() {
+        return "<empty>".to_string();
+    }
+    let text = match serde_json::from_slice::<Value>(body) {
+        Ok(mut json) => {
+            redact(&mut json, &config.redact_fields);
+            json.to_string()
+        }
+        // Non-JSON bodies cannot be redacted field by field, so only their size is logged
+        Err(_) => return format!("<{} bytes, not JSON>", body.len()),
+    };
+    truncate(text, config.max_body_bytes)
+}
+
+fn redact(value: &mut Value, fields: &[String]) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if fields.iter().any(|f| f.eq_ignore_ascii_case(key)) {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact(field, fields);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, fields)),
+        _ => {}
+    }
+}
+
+fn truncate(mut text: String, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let total = text.len();
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    format!("{}... ({} bytes total)", text, total)
+}
+
 /// CORS middleware
 #[allow(dead_code)]
 pub async fn cors_middleware(
@@ -0,0 +1,188 @@
+//! Best-effort forwarding of vote events to the notification service
+//!
+//! Events are queued without blocking the request that produced them; a background task sends
+//! them on. A full queue, a failed send or an open circuit breaker drops the event, logs it and
+//! counts it in `notifications_dropped_total`, but never fails the vote operation.
+
+use async_trait::async_trait;
+use shared_config::NotificationSinkConfig;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::events::VoteEvent;
+
+/// Receiver of forwarded vote events
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    async fn send(&self, event: &VoteEvent) -> Result<(), String>;
+}
+
+/// Posts each event as JSON to the notification service's event ingest endpoint
+pub struct HttpNotificationSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl HttpNotificationSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { client: reqwest::Client::new(), url: url.into() }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for HttpNotificationSink {
+    async fn send(&self, event: &VoteEvent) -> Result<(), String> {
+        let response = self.client.post(&self.url).json(event).send().await.map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("notification service answered {}", response.status()))
+        }
+    }
+}
+
+/// Stops calling a failing receiver for a cooldown after `failure_threshold` consecutive failures
+///
+/// Once the cooldown has passed one trial send is let through; success closes the breaker,
+/// failure opens it for another cooldown.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self { failure_threshold: failure_threshold.max(1), cooldown, consecutive_failures: 0, open_until: None }
+    }
+
+    /// Whether a send may be attempted at `now`
+    pub fn allows(&self, now: Instant) -> bool {
+        self.open_until.is_none_or(|until| now >= until)
+    }
+
+    pub fn is_open(&self, now: Instant) -> bool {
+        !self.allows(now)
+    }
+
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.open_until = None;
+    }
+
+    pub fn record_failure(&mut self, now: Instant) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= self.failure_threshold {
+            self.open_until = Some(now + self.cooldown);
+        }
+    }
+}
+
+/// Queue in front of a `NotificationSink`, drained by a background task
+#[derive(Clone)]
+pub struct NotificationDispatcher {
+    sender: mpsc::Sender<VoteEvent>,
+    dropped: Arc<AtomicU64>,
+    /// Events queued or being sent
+    pending: Arc<AtomicUsize>,
+}
+
+impl NotificationDispatcher {
+    /// Start the background sender; must be called inside a Tokio runtime
+    pub fn spawn(sink: Arc<dyn NotificationSink>, config: &NotificationSinkConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_capacity.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let pending = Arc::new(AtomicUsize::new(0));
+        let breaker = CircuitBreaker::new(config.failure_threshold, Duration::from_secs(config.cooldown_seconds));
+        let timeout = Duration::from_millis(config.timeout_ms);
+        tokio::spawn(run_sender(receiver, sink, breaker, timeout, dropped.clone(), pending.clone()));
+        Self { sender, dropped, pending }
+    }
+
+    /// Queue `event` for sending; never waits, drops the event when the queue is full
+    pub fn emit(&self, event: VoteEvent) {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        if let Err(e) = self.sender.try_send(event) {
+            self.pending.fetch_sub(1, Ordering::SeqCst);
+            let event = match e {
+                mpsc::error::TrySendError::Full(event) | mpsc::error::TrySendError::Closed(event) => event,
+            };
+            record_drop(&self.dropped, &event, "queue_full");
+        }
+    }
+
+    /// Events dropped so far, for any reason
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Events queued or being sent
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// Wait until every event queued so far has been sent or dropped
+    pub async fn flush(&self) {
+        while self.pending() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
+
+async fn run_sender(
+    mut receiver: mpsc::Receiver<VoteEvent>,
+    sink: Arc<dyn NotificationSink>,
+    mut breaker: CircuitBreaker,
+    timeout: Duration,
+    dropped: Arc<AtomicU64>,
+    pending: Arc<AtomicUsize>,
+) {
+    while let Some(event) = receiver.recv().await {
+        send_one(&event, sink.as_ref(), &mut breaker, timeout, &dropped).await;
+        pending.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+async fn send_one(
+    event: &VoteEvent,
+    sink: &dyn NotificationSink,
+    breaker: &mut CircuitBreaker,
+    timeout: Duration,
+    dropped: &AtomicU64,
+) {
+    if !breaker.allows(Instant::now()) {
+        record_drop(dropped, event, "circuit_open");
+        return;
+    }
+    let result = match tokio::time::timeout(timeout, sink.send(event)).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("timed out after {:?}", timeout)),
+    };
+    match result {
+        Ok(()) => {
+            if breaker.consecutive_failures > 0 {
+                info!("Notification service reachable again");
+            }
+            breaker.record_success();
+        }
+        Err(e) => {
+            warn!("Failed to forward {:?} event for vote {:?}: {}", event.event_type, event.vote_id(), e);
+            breaker.record_failure(Instant::now());
+            if breaker.is_open(Instant::now()) {
+                warn!("Notification service unavailable, pausing forwarding for {:?}", breaker.cooldown);
+            }
+            record_drop(dropped, event, "send_failed");
+        }
+    }
+}
+
+fn record_drop(dropped: &AtomicU64, event: &VoteEvent, reason: &str) {
+    dropped.fetch_add(1, Ordering::Relaxed);
+    shared_logging::metrics().record_notification_drop("vote-api", reason);
+    warn!("Dropped {:?} notification for vote {:?}: {}", event.event_type, event.vote_id(), reason);
+}
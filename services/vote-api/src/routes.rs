@@ -1,15 +1,49 @@
 use axum::{
-    routing::{get, post},
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post, put},
     Router,
 };
+use shared_config::ServerConfig;
 use std::sync::Arc;
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate, SizeAbove},
+    CompressionLayer,
+};
+use axum::http::{HeaderName, HeaderValue, Method};
+use std::time::Duration;
+use shared_types::panic_response;
+use tower_http::catch_panic::CatchPanicLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::trace::TraceLayer;
+use tracing::warn;
 
+use crate::middleware::{admin_auth_middleware, body_logging_middleware, timing_middleware};
 use crate::state::AppState;
 use crate::handlers::*;
 
 /// Create the main router with all routes
+///
+/// `/metrics` and the `/admin` routes require `server.admin_api_key` as a bearer token. Every
+/// body is capped at `max_request_size`; commit and reveal bodies get the tighter
+/// `max_submission_request_size`. Oversized bodies are rejected with 413. With the `graphql`
+/// feature, `POST /graphql` serves the same data as a GraphQL schema.
 pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
+    let max_request_size = state.config.server.max_request_size;
+    let max_submission_size = state.config.server.max_submission_request_size;
+
+    let admin_key = state.config.server.admin_api_key.as_deref().map(Arc::from);
+
+    // Admin routes, behind the admin API key
+    let admin = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/admin/log-level", put(set_log_level_handler))
+        .route("/admin/events/query", post(query_events_handler))
+        .route("/admin/votes/:id/cancel", post(cancel_vote_handler))
+        .route_layer(middleware::from_fn_with_state(admin_key, admin_auth_middleware));
+
+    let router = Router::new()
         // Health check
         .route("/health", get(health_handler))
         
@@ -17,14 +51,26 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/api/v1/votes", post(create_vote_handler))
         .route("/api/v1/votes", get(list_votes_handler))
         .route("/api/v1/votes/:id", get(get_vote_handler))
+        .route("/api/v1/votes/:id/wait", get(wait_vote_handler))
+        .route("/api/v1/votes/:id/events", get(vote_events_handler))
+        .route("/api/v1/votes/:id/history", get(vote_history_handler))
         .route("/api/v1/votes/:id/results", get(get_results_handler))
         .route("/api/v1/votes/:id/verify", get(verify_results_handler))
+        .route("/api/v1/votes/:id/report", get(vote_report_handler))
+        .route("/api/v1/votes/:id/completion-webhook", get(completion_webhook_handler))
+        .route("/api/v1/voters/:id/activity", get(voter_activity_handler))
         
         // Commitment routes
-        .route("/api/v1/votes/:id/commit", post(commit_vote_handler))
+        .route(
+            "/api/v1/votes/:id/commit",
+            post(commit_vote_handler).layer(RequestBodyLimitLayer::new(max_submission_size)),
+        )
         
         // Reveal routes
-        .route("/api/v1/votes/:id/reveal", post(reveal_vote_handler))
+        .route(
+            "/api/v1/votes/:id/reveal",
+            post(reveal_vote_handler).layer(RequestBodyLimitLayer::new(max_submission_size)),
+        )
         
         // Template routes
         .route("/api/v1/templates", get(list_templates_handler))
@@ -32,6 +78,87 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         
         // WebSocket routes
         .route("/ws/votes/:id", get(websocket_handler))
-        
+        .merge(admin);
+
+    #[cfg(feature = "graphql")]
+    let router = router.route(
+        "/graphql",
+        post(crate::graphql::graphql_handler).layer(axum::Extension(crate::graphql::build_schema(state.clone()))),
+    );
+
+    router
+        // Replace axum's fixed 2MB extractor limit with the configured one
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(max_request_size))
         .with_state(state)
 }
+
+/// Wrap the router in the HTTP layers shared by every route
+///
+/// Panics are caught innermost, inside the correlation span, and become 500 responses that still
+/// get compressed and carry CORS headers. Error bodies are then localized from `Accept-Language`,
+/// and request timing wraps them so panics are timed as 500s.
+/// Compression sits next so trace spans and CORS headers see the final response. Body logging,
+/// when enabled, sits inside all of them and sees bodies uncompressed
+pub fn with_http_layers(router: Router, server: &ServerConfig) -> Router {
+    let slow_threshold = Duration::from_millis(server.slow_request_threshold_ms);
+    let compress_when = SizeAbove::new(server.compression_min_size)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE);
+
+    let router = if server.body_logging.enabled {
+        let state = (Arc::new(server.body_logging.clone()), server.max_request_size);
+        router.layer(middleware::from_fn_with_state(state, body_logging_middleware))
+    } else {
+        router
+    };
+
+    router
+        .layer(CatchPanicLayer::custom(panic_response))
+        .layer(middleware::from_fn(shared_types::localize_errors))
+        .layer(middleware::from_fn_with_state(slow_threshold, timing_middleware))
+        .layer(CompressionLayer::new().gzip(true).br(true).compress_when(compress_when))
+        .layer(cors_layer(server))
+        .layer(TraceLayer::new_for_http().make_span_with(|request: &axum::extract::Request| {
+            let correlation_id = request
+                .headers()
+                .get("x-correlation-id")
+                .and_then(|h| h.to_str().ok())
+                .map(str::to_string)
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            shared_logging::correlation_span(&correlation_id)
+        }))
+}
+
+/// Build the CORS policy from configuration; an empty origin list allows no cross-origin requests
+///
+/// Entries that do not parse as header values are skipped; `AppConfig::validate` rejects the
+/// unsafe combinations before we get here
+pub fn cors_layer(server: &ServerConfig) -> CorsLayer {
+    let origins = if server.cors_origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(parse_all(&server.cors_origins, |o| HeaderValue::from_str(o).ok()))
+    };
+
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(parse_all(&server.cors_methods, |m| Method::from_bytes(m.as_bytes()).ok()))
+        .allow_headers(parse_all(&server.cors_headers, |h| HeaderName::from_bytes(h.as_bytes()).ok()))
+        .allow_credentials(server.cors_allow_credentials)
+        .max_age(Duration::from_secs(server.cors_max_age_seconds))
+}
+
+fn parse_all<T>(entries: &[String], parse: impl Fn(&str) -> Option<T>) -> Vec<T> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let parsed = parse(entry);
+            if parsed.is_none() {
+                warn!("Ignoring invalid CORS entry: {}", entry);
+            }
+            parsed
+        })
+        .collect()
+}
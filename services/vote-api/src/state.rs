@@ -1,10 +1,19 @@
 use std::sync::Arc;
 use shared_config::AppConfig;
-use vote_engine::{VoteEngine, services::MemoryVoteService};
+use vote_engine::VoteEngine;
 use template_system::DefaultTemplateRegistry;
-use commitment_engine::{CommitmentEngine, algorithms::Sha256CommitmentAlgorithm};
-use vote_store::{VoteStore, MemoryVoteStore, SqliteVoteStore, PostgresVoteStore};
-use tracing::info;
+use commitment_engine::CommitmentEngine;
+use vote_store::VoteStore;
+use event_store::{EventStorage, EventStoreError};
+use shared_types::VoteHistoryEntry;
+use tracing::{info, warn};
+use crate::completion::CompletionWebhooks;
+use crate::components::AppComponents;
+use crate::history;
+use crate::events::{VoteEvent, VoteEventType, VoteEvents};
+use crate::notify::NotificationDispatcher;
+use crate::watch::VoteWatchers;
+use std::collections::HashMap;
 
 /// Application state containing all services and configuration
 #[derive(Clone)]
@@ -16,42 +25,91 @@ pub struct AppState {
     pub commitment_engine: Arc<CommitmentEngine>,
     #[allow(dead_code)]
     pub vote_store: Arc<dyn VoteStore>,
+    pub event_store: Arc<dyn EventStorage>,
+    pub watchers: Arc<VoteWatchers>,
+    pub events: VoteEvents,
+    /// Best-effort forwarding to the notification service, when a sink is configured
+    pub notifier: Option<NotificationDispatcher>,
+    /// Results callbacks of votes created with a completion webhook
+    pub completion_webhooks: CompletionWebhooks,
 }
 
 impl AppState {
-    pub async fn new(config: AppConfig) -> Result<Self, Box<dyn std::error::Error>> {
+    /// Assemble the state from already-built components
+    pub fn new(config: AppConfig, components: AppComponents) -> Self {
         info!("Initializing application state");
-        
-        // Initialize vote store based on configuration
-        let vote_store: Arc<dyn VoteStore> = if config.database.url.starts_with("sqlite:") {
-            info!("Using SQLite vote store");
-            Arc::new(SqliteVoteStore::new(&config.database).await?)
-        } else if config.database.url.starts_with("postgresql:") || config.database.url.starts_with("postgres:") {
-            info!("Using PostgreSQL vote store");
-            Arc::new(PostgresVoteStore::new(&config.database).await?)
-        } else {
-            info!("Using in-memory vote store");
-            Arc::new(MemoryVoteStore::new())
-        };
-        
-        // Initialize template registry
-        let template_registry = Arc::new(DefaultTemplateRegistry::new());
-        
-        // Initialize commitment engine
-        let commitment_algorithm = Arc::new(Sha256CommitmentAlgorithm::new());
-        let commitment_engine = Arc::new(CommitmentEngine::new(commitment_algorithm));
-        
-        // Initialize vote engine with memory service (for now)
-        // TODO: Replace with proper vote service that uses the vote store
-        let vote_service = Arc::new(MemoryVoteService::new());
-        let vote_engine = Arc::new(VoteEngine::new(vote_service));
-        
-        Ok(Self {
+
+        let notifier = components
+            .notification_sink
+            .map(|sink| NotificationDispatcher::spawn(sink, &config.server.notifications));
+        let completion_webhooks =
+            CompletionWebhooks::new(config.server.completion_webhooks.clone(), components.event_store.clone());
+        let mut vote_engine = VoteEngine::new(components.vote_service)
+            .with_transition_observer(Arc::new(history::HistoryRecorder::new(components.event_store.clone())))
+            .with_transition_observer(Arc::new(completion_webhooks.clone()));
+        if let Some(secret) = &config.server.reveal_key_secret {
+            vote_engine = vote_engine.with_key_encryption_key(secret);
+        }
+        Self {
             config,
-            vote_engine,
-            template_registry,
-            commitment_engine,
-            vote_store,
-        })
+            vote_engine: Arc::new(vote_engine),
+            template_registry: components.template_registry,
+            commitment_engine: components.commitment_engine,
+            vote_store: components.vote_store,
+            event_store: components.event_store,
+            watchers: Arc::new(VoteWatchers::new()),
+            events: VoteEvents::new(),
+            notifier,
+            completion_webhooks,
+        }
+    }
+
+    /// Build the components selected by `config` and assemble the state from them
+    pub async fn from_config(config: AppConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let components = AppComponents::from_config(&config).await?;
+        let state = Self::new(config, components);
+        let resumed = state.completion_webhooks.resume_pending().await?;
+        if resumed > 0 {
+            info!("Resumed {} pending completion callbacks", resumed);
+        }
+        Ok(state)
+    }
+
+    /// Record a change to a vote: store it as an audit event, wake long-poll waiters, notify
+    /// event-stream subscribers and queue the event for the notification service
+    pub async fn record_change(&self, vote_id: &str, event_type: VoteEventType, data: HashMap<String, serde_json::Value>) {
+        let event = VoteEvent::new(event_type, vote_id, data);
+        let status = match event.event_type {
+            VoteEventType::SessionCreated => self.vote_engine.get_vote(vote_id).await.ok().map(|vote| vote.status),
+            _ => None,
+        };
+        if let Err(e) = self.event_store.store_event(history::audit_event(&event, status.as_ref())).await {
+            warn!("Failed to store audit event for vote {}: {}", vote_id, e);
+        }
+        self.wake_watchers(vote_id).await;
+        if let Some(notifier) = &self.notifier {
+            notifier.emit(event.clone());
+        }
+        self.events.publish(event);
+    }
+
+    /// The vote's version: its event-stream version in the event store
+    pub async fn vote_version(&self, vote_id: &str) -> Result<u64, EventStoreError> {
+        let version = self.event_store.session_version(vote_id).await?;
+        self.watchers.observe(vote_id, version);
+        Ok(version)
+    }
+
+    /// Wake long-poll waiters on a vote whose events were just stored
+    pub async fn wake_watchers(&self, vote_id: &str) {
+        if let Err(e) = self.vote_version(vote_id).await {
+            warn!("Failed to read the version of vote {}: {}", vote_id, e);
+        }
+    }
+
+    /// Status changes, result computations and cancellation of a vote, oldest first
+    pub async fn get_vote_history(&self, vote_id: &str) -> Result<Vec<VoteHistoryEntry>, EventStoreError> {
+        let events = self.event_store.get_events_by_session(vote_id).await?;
+        Ok(history::reconstruct(events))
     }
 }
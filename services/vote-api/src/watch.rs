@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Per-vote change signals that long-poll requests can wait on
+///
+/// Versions are the vote's event-stream versions in the event store, so they survive restarts
+/// and agree between instances sharing a store. This only remembers the highest version seen and
+/// wakes waiters when it grows.
+#[derive(Default)]
+pub struct VoteWatchers {
+    versions: Mutex<HashMap<String, watch::Sender<u64>>>,
+}
+
+impl VoteWatchers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sender(&self, vote_id: &str) -> watch::Sender<u64> {
+        self.versions
+            .lock()
+            .unwrap()
+            .entry(vote_id.to_string())
+            .or_insert_with(|| watch::channel(0).0)
+            .clone()
+    }
+
+    /// Record the vote's persisted version, waking every waiter if it is newer than any seen
+    pub fn observe(&self, vote_id: &str, version: u64) {
+        self.sender(vote_id).send_if_modified(|seen| {
+            let newer = version > *seen;
+            if newer {
+                *seen = version;
+            }
+            newer
+        });
+    }
+
+    /// Wait until the version exceeds `since`, returning it, or `None` once `timeout` elapses
+    pub async fn wait_for_change(&self, vote_id: &str, since: u64, timeout: Duration) -> Option<u64> {
+        let mut receiver = self.sender(vote_id).subscribe();
+        let changed = async { receiver.wait_for(|version| *version > since).await.map(|v| *v) };
+        tokio::time::timeout(timeout, changed).await.ok()?.ok()
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::json;
+use shared_config::ServerConfig;
+use tower::ServiceExt;
+use vote_api::{create_router, AppState};
+
+async fn app() -> Router {
+    let config = common::config_with(ServerConfig { max_request_size: 4096, max_submission_request_size: 512, ..Default::default() });
+    create_router(std::sync::Arc::new(AppState::from_config(config).await.unwrap()))
+}
+
+async fn post(app: Router, uri: &str, body: serde_json::Value, with_length: bool) -> StatusCode {
+    let body = body.to_string();
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json");
+    if with_length {
+        request = request.header(header::CONTENT_LENGTH, body.len());
+    }
+    app.oneshot(request.body(Body::from(body)).unwrap()).await.unwrap().status()
+}
+
+fn create_body(description: String) -> serde_json::Value {
+    json!({
+        "config": {
+            "title": "Budget",
+            "description": description,
+            "template_id": "yes_no",
+            "template_params": {},
+            "commitment_duration_hours": 24,
+            "reveal_duration_hours": 24
+        }
+    })
+}
+
+fn commit_body(salt: String) -> serde_json::Value {
+    json!({ "voter": "alice", "commitment_hash": "a".repeat(64), "salt": salt })
+}
+
+#[tokio::test]
+async fn test_normal_create_passes() {
+    let status = post(app().await, "/api/v1/votes", create_body("Approve it".to_string()), true).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_oversized_create_is_rejected_with_413() {
+    let body = create_body("x".repeat(5000));
+    assert_eq!(post(app().await, "/api/v1/votes", body.clone(), true).await, StatusCode::PAYLOAD_TOO_LARGE);
+    // Without a Content-Length the limit is enforced while the body is read
+    assert_eq!(post(app().await, "/api/v1/votes", body, false).await, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_commit_has_its_own_tighter_limit() {
+    let oversized = commit_body("s".repeat(1000));
+    let status = post(app().await, "/api/v1/votes/missing/commit", oversized, false).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // A normal-sized body gets past the limit and fails on the unknown vote instead
+    let status = post(app().await, "/api/v1/votes/missing/commit", commit_body("pepper".to_string()), true).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,123 @@
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+use shared_config::{BodyLoggingConfig, ServerConfig};
+use tower::ServiceExt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use vote_api::with_http_layers;
+
+/// Collects the message of every event
+#[derive(Clone, Default)]
+struct LogCapture {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+struct MessageVisitor<'a>(&'a mut String);
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            *self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogCapture {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut message = String::new();
+        event.record(&mut MessageVisitor(&mut message));
+        self.messages.lock().unwrap().push(message);
+    }
+}
+
+impl LogCapture {
+    fn containing(&self, needle: &str) -> Vec<String> {
+        self.messages.lock().unwrap().iter().filter(|m| m.contains(needle)).cloned().collect()
+    }
+}
+
+fn app(body_logging: BodyLoggingConfig) -> Router {
+    let router = Router::new()
+        .route("/api/v1/login", post(|Json(body): Json<Value>| async move { Json(json!({ "received": body, "token": "issued-token" })) }))
+        .route("/api/v1/other", post(|Json(body): Json<Value>| async move { Json(body) }));
+    with_http_layers(router, &ServerConfig { body_logging, ..Default::default() })
+}
+
+fn enabled_for(routes: &[&str]) -> BodyLoggingConfig {
+    BodyLoggingConfig {
+        enabled: true,
+        routes: routes.iter().map(|r| r.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+async fn post_json(app: Router, uri: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_password_is_redacted_in_logged_body() {
+    let capture = LogCapture::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    let body = json!({ "username": "alice", "password": "hunter2", "profile": { "Salt": "s3cret" } });
+    let (status, echoed) = post_json(app(enabled_for(&["/api/v1/login"])), "/api/v1/login", body.clone()).await;
+
+    // The handler still sees the original body
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(echoed["received"], body);
+    assert_eq!(echoed["token"], "issued-token");
+
+    let requests = capture.containing("Request body for POST /api/v1/login");
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].contains(r#""username":"alice""#), "{}", requests[0]);
+    assert!(requests[0].contains(r#""password":"[REDACTED]""#), "{}", requests[0]);
+    assert!(requests[0].contains(r#""Salt":"[REDACTED]""#), "{}", requests[0]);
+    assert!(!requests[0].contains("hunter2") && !requests[0].contains("s3cret"));
+
+    let responses = capture.containing("Response body for POST /api/v1/login");
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].contains(r#""token":"[REDACTED]""#), "{}", responses[0]);
+    assert!(!responses[0].contains("hunter2") && !responses[0].contains("issued-token"));
+}
+
+#[tokio::test]
+async fn test_only_selected_routes_are_logged_and_bodies_are_truncated() {
+    let capture = LogCapture::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    let config = BodyLoggingConfig { max_body_bytes: 32, ..enabled_for(&["/api/v1/login"]) };
+    post_json(app(config.clone()), "/api/v1/other", json!({ "note": "unlogged" })).await;
+    assert!(capture.containing("/api/v1/other").is_empty());
+
+    post_json(app(config), "/api/v1/login", json!({ "note": "x".repeat(100) })).await;
+    let logged = capture.containing("Request body for POST /api/v1/login");
+    assert!(logged[0].contains("bytes total"), "{}", logged[0]);
+    assert!(!logged[0].contains(&"x".repeat(100)));
+}
+
+#[tokio::test]
+async fn test_body_logging_is_off_by_default() {
+    let capture = LogCapture::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    post_json(app(BodyLoggingConfig::default()), "/api/v1/login", json!({ "password": "hunter2" })).await;
+    assert!(capture.containing("body for").is_empty());
+}
@@ -0,0 +1,106 @@
+//! Fixtures shared by the vote API integration tests
+#![allow(dead_code)]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use shared_config::{AppConfig, DatabaseConfig, LoggingConfig, ServerConfig};
+use shared_types::{TieBreak, Vote, VoteConfig, VoteId, VoteStatus};
+use tower::ServiceExt;
+use vote_engine::{MemoryVoteService, VoteService};
+
+/// Configuration over in-memory stores with default server settings
+pub fn config() -> AppConfig {
+    config_with(ServerConfig::default())
+}
+
+/// Configuration over in-memory stores with the given server settings
+pub fn config_with(server: ServerConfig) -> AppConfig {
+    AppConfig {
+        server,
+        database: DatabaseConfig { url: "memory://".to_string(), ..Default::default() },
+        blockchain: None,
+        logging: LoggingConfig::default(),
+    }
+}
+
+/// A yes/no vote with one-hour phases
+pub fn vote_config(title: &str) -> VoteConfig {
+    VoteConfig {
+        title: title.to_string(),
+        description: "Approve the budget".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: json!({}),
+        commitment_duration_hours: 1,
+        reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }
+}
+
+/// Body of `POST /api/v1/votes` for a yes/no vote
+pub fn create_body() -> Value {
+    json!({
+        "config": {
+            "title": "Budget",
+            "description": "Approve the budget",
+            "template_id": "yes_no",
+            "template_params": {},
+            "commitment_duration_hours": 24,
+            "reveal_duration_hours": 24
+        }
+    })
+}
+
+/// Send a JSON request, returning the status and the JSON body (`Null` when empty)
+pub async fn send(app: &Router, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Create a vote through the API and return its id
+pub async fn create_vote(app: &Router) -> String {
+    let (status, body) = send(app, Method::POST, "/api/v1/votes", Some(create_body())).await;
+    assert_eq!(status, StatusCode::OK);
+    body["vote_id"].as_str().unwrap().to_string()
+}
+
+/// A vote whose reveal phase has ended, so fetching its results completes it
+pub async fn ended_vote(service: &MemoryVoteService, completion_webhook_url: Option<&str>) -> String {
+    let now = Utc::now();
+    let id = VoteId::generate().to_string();
+    service.create_vote(Vote {
+        id: id.clone(),
+        title: "Budget".to_string(),
+        description: "Approve the budget".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: json!({}),
+        creator: "system".to_string(),
+        created_at: now - Duration::hours(3),
+        commitment_start: now - Duration::hours(3),
+        commitment_end: now - Duration::hours(2),
+        reveal_start: now - Duration::hours(2),
+        reveal_end: now - Duration::hours(1),
+        status: VoteStatus::RevealPhase,
+        results: None,
+        tie_break: TieBreak::default(),
+        legal_hold: false,
+        completion_webhook_url: completion_webhook_url.map(str::to_string),
+        client_request_id: None,
+        reveal_public_key: None,
+    }).await.unwrap();
+    id
+}
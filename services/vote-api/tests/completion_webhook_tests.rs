@@ -0,0 +1,279 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::State,
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::json;
+use shared_config::{CompletionWebhookConfig, RetryConfig, ServerConfig};
+use shared_types::*;
+use shared_utils::signing::{verify_webhook_signature, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use vote_api::{create_router, AppComponents, AppState, CompletionDelivery, CompletionDeliveryStatus, CompletionWebhooks};
+use vote_engine::{services::MemoryVoteService, VoteService};
+
+const SECRET: &str = "integrator-secret";
+
+/// Callback endpoint answering 500 to the first `fail_first` requests
+#[derive(Clone, Default)]
+struct Receiver {
+    fail_first: usize,
+    received: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    let mut received = receiver.received.lock().unwrap();
+    received.push((headers, body));
+    if received.len() <= receiver.fail_first {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        StatusCode::OK
+    }
+}
+
+/// Serve `receiver` on a local port and return its callback URL
+async fn serve(receiver: Receiver) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/callback", listener.local_addr().unwrap());
+    let app = Router::new().route("/callback", post(receive)).with_state(receiver);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+/// Signed callbacks with immediate retries, allowed to reach the local test endpoints
+fn webhook_config() -> CompletionWebhookConfig {
+    CompletionWebhookConfig {
+        secret: Some(SECRET.to_string()),
+        allowed_hosts: vec!["127.0.0.1".to_string()],
+        timeout_ms: 1000,
+        retry: RetryConfig { max_retries: 3, initial_interval: 0, jitter: 0.0, ..Default::default() },
+    }
+}
+
+fn state(service: Arc<MemoryVoteService>) -> Arc<AppState> {
+    state_with(service, webhook_config())
+}
+
+fn state_with(service: Arc<MemoryVoteService>, completion_webhooks: CompletionWebhookConfig) -> Arc<AppState> {
+    let config = common::config_with(ServerConfig { completion_webhooks, ..Default::default() });
+    Arc::new(AppState::new(config, AppComponents::in_memory().with_vote_service(service)))
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+}
+
+async fn wait_for_delivery(webhooks: &CompletionWebhooks, vote_id: &str) -> CompletionDelivery {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let delivery = webhooks.delivery(vote_id).await.unwrap().expect("callback started");
+        if delivery.status != CompletionDeliveryStatus::Pending {
+            return delivery;
+        }
+        assert!(Instant::now() < deadline, "callback still pending after {} attempts", delivery.attempts);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_completion_sends_one_signed_callback() {
+    let receiver = Receiver::default();
+    let url = serve(receiver.clone()).await;
+    let service = Arc::new(MemoryVoteService::new());
+    let state = state(service.clone());
+    let app = create_router(state.clone());
+    let vote_id = common::ended_vote(&service, Some(&url)).await;
+
+    // Completing the vote sends the callback; later results requests do not
+    for _ in 0..2 {
+        let (status, _) = get(&app, &format!("/api/v1/votes/{}/results", vote_id)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let delivery = wait_for_delivery(&state.completion_webhooks, &vote_id).await;
+    assert_eq!(delivery.status, CompletionDeliveryStatus::Delivered);
+    assert_eq!(delivery.attempts, 1);
+
+    let received = receiver.received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    let (headers, body) = &received[0];
+    let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+    let timestamp = headers[TIMESTAMP_HEADER].to_str().unwrap();
+    verify_webhook_signature(SECRET, body, signature, timestamp, 300).unwrap();
+    let results: VoteResults = serde_json::from_slice(body).unwrap();
+    assert_eq!(results.vote_id, vote_id);
+
+    let (status, recorded) = get(&app, &format!("/api/v1/votes/{}/completion-webhook", vote_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(recorded["status"], "delivered");
+    assert_eq!(recorded["response_status"], 200);
+}
+
+#[tokio::test]
+async fn test_failing_callback_endpoint_is_retried() {
+    let receiver = Receiver { fail_first: 2, ..Default::default() };
+    let url = serve(receiver.clone()).await;
+    let service = Arc::new(MemoryVoteService::new());
+    let state = state(service.clone());
+    let app = create_router(state.clone());
+    let vote_id = common::ended_vote(&service, Some(&url)).await;
+
+    let (status, _) = get(&app, &format!("/api/v1/votes/{}/results", vote_id)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let delivery = wait_for_delivery(&state.completion_webhooks, &vote_id).await;
+    assert_eq!(delivery.status, CompletionDeliveryStatus::Delivered);
+    assert_eq!(delivery.attempts, 3);
+    assert_eq!(receiver.received.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_advancing_to_completed_sends_callback() {
+    let receiver = Receiver::default();
+    let url = serve(receiver.clone()).await;
+    let service = Arc::new(MemoryVoteService::new());
+    let state = state(service.clone());
+    let vote_id = common::ended_vote(&service, Some(&url)).await;
+
+    // An admin moving the vote on completes it without anyone reading the results
+    assert_eq!(state.vote_engine.advance_phase(&vote_id).await.unwrap(), VoteStatus::Completed);
+
+    let delivery = wait_for_delivery(&state.completion_webhooks, &vote_id).await;
+    assert_eq!(delivery.status, CompletionDeliveryStatus::Delivered);
+    let received = receiver.received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    let results: VoteResults = serde_json::from_slice(&received[0].1).unwrap();
+    let stored = service.get_vote(&VoteId::parse(&vote_id).unwrap()).await.unwrap().results.expect("results stored");
+    assert_eq!(results.vote_id, vote_id);
+    assert_eq!(results.calculated_at, stored.calculated_at);
+}
+
+#[tokio::test]
+async fn test_delivery_is_persisted_and_pending_callbacks_resume() {
+    // Nothing listens on the callback port yet, and the first run waits an hour before retrying
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    let url = format!("http://{}/callback", addr);
+    let mut config = webhook_config();
+    config.retry.initial_interval = 3600;
+    let service = Arc::new(MemoryVoteService::new());
+    let state = state_with(service.clone(), config);
+    let vote_id = common::ended_vote(&service, Some(&url)).await;
+    state.vote_engine.advance_phase(&vote_id).await.unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let restarted = CompletionWebhooks::new(webhook_config(), state.event_store.clone());
+    loop {
+        let delivery = restarted.delivery(&vote_id).await.unwrap().expect("delivery persisted");
+        if delivery.attempts == 1 {
+            assert_eq!(delivery.status, CompletionDeliveryStatus::Pending);
+            assert!(delivery.last_error.is_some());
+            break;
+        }
+        assert!(Instant::now() < deadline, "first attempt not recorded");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    // After a restart the endpoint is up and the pending callback picks up where it left off
+    let receiver = Receiver::default();
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    let app = Router::new().route("/callback", post(receive)).with_state(receiver.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    assert_eq!(restarted.resume_pending().await.unwrap(), 1);
+
+    let delivery = wait_for_delivery(&restarted, &vote_id).await;
+    assert_eq!(delivery.status, CompletionDeliveryStatus::Delivered);
+    assert_eq!(delivery.attempts, 2);
+    assert_eq!(receiver.received.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_callback_urls_must_reach_public_addresses() {
+    let webhooks = CompletionWebhooks::new(
+        CompletionWebhookConfig { allowed_hosts: vec![], ..webhook_config() },
+        Arc::new(event_store::store::MemoryEventStore::new()),
+    );
+    for url in [
+        "http://127.0.0.1:8080/hook",
+        "http://localhost/hook",
+        "http://10.1.2.3/hook",
+        "http://192.168.0.10/hook",
+        "http://169.254.169.254/latest/meta-data",
+        "http://100.64.0.1/hook",
+        "http://[::1]/hook",
+        "http://[fd00::1]/hook",
+        "http://[fe80::1]/hook",
+        "http://[::ffff:10.0.0.1]/hook",
+        "http://0.0.0.0/hook",
+    ] {
+        assert!(webhooks.check_url(url).await.is_err(), "{} should be refused", url);
+    }
+    assert!(webhooks.check_url("https://1.1.1.1/done").await.is_ok());
+    assert!(webhooks.check_url("https://[2606:4700::1111]/done").await.is_ok());
+
+    // Allow-listed hosts may be internal
+    let allowed = CompletionWebhooks::new(webhook_config(), Arc::new(event_store::store::MemoryEventStore::new()));
+    assert!(allowed.check_url("http://127.0.0.1:8080/hook").await.is_ok());
+
+    // Without a signing secret no callback is accepted
+    let unsigned = CompletionWebhooks::new(
+        CompletionWebhookConfig { secret: None, ..webhook_config() },
+        Arc::new(event_store::store::MemoryEventStore::new()),
+    );
+    assert!(unsigned.check_url("https://1.1.1.1/done").await.is_err());
+}
+
+#[tokio::test]
+async fn test_create_vote_accepts_completion_webhook_url() {
+    let app = create_router(state(Arc::new(MemoryVoteService::new())));
+    let create = |url: &str| {
+        let body = json!({ "config": {
+            "title": "Budget",
+            "description": "Approve the budget",
+            "template_id": "yes_no",
+            "template_params": {},
+            "commitment_duration_hours": 24,
+            "reveal_duration_hours": 24,
+            "completion_webhook_url": url
+        }});
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/v1/votes")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    for refused in ["ftp://example.com/done", "http://10.0.0.1/done", "http://127.0.0.2/done"] {
+        let response = app.clone().oneshot(create(refused)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY, "{}", refused);
+    }
+
+    let response = app.clone().oneshot(create("https://1.1.1.1/done")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let vote_id = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["vote_id"].as_str().unwrap().to_string();
+    let (_, vote) = get(&app, &format!("/api/v1/votes/{}", vote_id)).await;
+    assert_eq!(vote["vote"]["completion_webhook_url"], "https://1.1.1.1/done");
+
+    // no callback until the vote completes
+    let (status, _) = get(&app, &format!("/api/v1/votes/{}/completion-webhook", vote_id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Without a signing secret votes cannot ask for a callback at all
+    let unsigned = create_router(state_with(
+        Arc::new(MemoryVoteService::new()),
+        CompletionWebhookConfig { secret: None, ..webhook_config() },
+    ));
+    let response = unsigned.oneshot(create("https://1.1.1.1/done")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
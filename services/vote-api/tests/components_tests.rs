@@ -0,0 +1,42 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::{create_body, send};
+use serde_json::json;
+use shared_types::{ListQuery, VoteId};
+use std::sync::Arc;
+use vote_api::{create_router, AppComponents, AppState};
+use vote_engine::{MemoryVoteService, VoteService};
+use vote_store::MemoryVoteStore;
+
+#[tokio::test]
+async fn test_injected_components_serve_requests_end_to_end() {
+    let vote_service = Arc::new(MemoryVoteService::new());
+    let components = AppComponents::in_memory()
+        .with_vote_store(Arc::new(MemoryVoteStore::new()))
+        .with_vote_service(vote_service.clone());
+    let app = create_router(Arc::new(AppState::new(common::config(), components)));
+
+    let (status, created) = send(&app, Method::POST, "/api/v1/votes", Some(create_body())).await;
+    assert_eq!(status, StatusCode::OK);
+    let vote_id = VoteId::parse(created["vote_id"].as_str().unwrap()).unwrap();
+
+    // The vote landed in the injected service rather than one built by the state
+    assert_eq!(vote_service.get_vote(&vote_id).await.unwrap().title, "Budget");
+
+    let commit = json!({ "voter": "alice", "commitment_hash": "a".repeat(64), "salt": "pepper" });
+    let (status, _) = send(&app, Method::POST, &format!("/api/v1/votes/{}/commit", vote_id), Some(commit)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(vote_service.get_commitment(&vote_id, "alice").await.unwrap().is_some());
+
+    let (status, vote) = send(&app, Method::GET, &format!("/api/v1/votes/{}", vote_id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(vote["vote"]["id"], vote_id.as_str());
+}
+
+#[tokio::test]
+async fn test_from_config_uses_memory_store_for_memory_url() {
+    let components = AppComponents::from_config(&common::config()).await.unwrap();
+    let query = ListQuery { page: 0, page_size: 10, status: None, creator: None };
+    assert!(components.vote_store.list_votes(query).await.unwrap().items.is_empty());
+}
@@ -0,0 +1,54 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use shared_config::ServerConfig;
+use tower::ServiceExt;
+use vote_api::with_http_layers;
+
+fn app() -> Router {
+    let router = Router::new()
+        .route("/large", get(|| async { axum::Json(vec!["vote"; 1000]) }))
+        .route("/small", get(|| async { axum::Json(vec!["vote"]) }));
+    let server = ServerConfig { cors_origins: vec!["https://dashboard.example".to_string()], ..Default::default() };
+    with_http_layers(router, &server)
+}
+
+async fn get_with(path: &str, accept_encoding: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .uri(path)
+        .header(header::ACCEPT_ENCODING, accept_encoding)
+        .header(header::ORIGIN, "https://dashboard.example")
+        .body(Body::empty())
+        .unwrap();
+    app().oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_large_response_is_gzipped_when_requested() {
+    let response = get_with("/large", "gzip").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+}
+
+#[tokio::test]
+async fn test_brotli_is_negotiated() {
+    let response = get_with("/large", "br;q=1.0, gzip;q=0.5").await;
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+}
+
+#[tokio::test]
+async fn test_small_response_is_not_compressed() {
+    let response = get_with("/small", "gzip").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn test_identity_response_keeps_cors_headers() {
+    let response = get_with("/large", "identity").await;
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://dashboard.example");
+}
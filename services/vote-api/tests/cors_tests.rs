@@ -0,0 +1,71 @@
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use shared_config::{AppConfig, DatabaseConfig, LoggingConfig, ServerConfig};
+use tower::ServiceExt;
+use vote_api::with_http_layers;
+
+const ALLOWED: &str = "https://dashboard.example";
+
+fn server() -> ServerConfig {
+    ServerConfig {
+        cors_origins: vec![ALLOWED.to_string()],
+        cors_allow_credentials: true,
+        ..Default::default()
+    }
+}
+
+fn app(server: &ServerConfig) -> Router {
+    with_http_layers(Router::new().route("/votes", get(|| async { "[]" })), server)
+}
+
+async fn preflight(server: &ServerConfig, origin: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .method(Method::OPTIONS)
+        .uri("/votes")
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+        .body(Body::empty())
+        .unwrap();
+    app(server).oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_allowed_origin_is_reflected() {
+    let response = preflight(&server(), ALLOWED).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], ALLOWED);
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+}
+
+#[tokio::test]
+async fn test_disallowed_origin_is_not_reflected() {
+    let response = preflight(&server(), "https://evil.example").await;
+    assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+}
+
+#[tokio::test]
+async fn test_default_policy_allows_no_origins() {
+    let response = preflight(&ServerConfig::default(), ALLOWED).await;
+    assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+}
+
+#[test]
+fn test_wildcard_with_credentials_fails_validation() {
+    let config = AppConfig {
+        server: ServerConfig { cors_origins: vec!["*".to_string()], cors_allow_credentials: true, ..Default::default() },
+        database: DatabaseConfig::default(),
+        blockchain: None,
+        logging: LoggingConfig::default(),
+    };
+    let error = config.validate().unwrap_err();
+    assert!(error.to_string().contains("cors_allow_credentials"));
+
+    let config = AppConfig { server: ServerConfig { cors_allow_credentials: false, ..config.server }, ..config };
+    assert!(config.validate().is_ok());
+}
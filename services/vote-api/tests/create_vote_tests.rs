@@ -0,0 +1,64 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request},
+    Router,
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tower::ServiceExt;
+use vote_api::{create_router, AppComponents, AppState};
+
+fn app() -> Router {
+    create_router(Arc::new(AppState::new(common::config(), AppComponents::in_memory())))
+}
+
+/// Create a vote with `client_request_id`, as the holder of `api_key`; returns the response body
+async fn create(app: &Router, api_key: Option<&str>, client_request_id: &str) -> Value {
+    let body = json!({
+        "config": {
+            "title": "Budget",
+            "description": "Approve the budget",
+            "template_id": "yes_no",
+            "template_params": {},
+            "commitment_duration_hours": 24,
+            "reveal_duration_hours": 24
+        },
+        "client_request_id": client_request_id
+    });
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/votes")
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(key) = api_key {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", key));
+    }
+    let response = app.clone().oneshot(request.body(Body::from(body.to_string())).unwrap()).await.unwrap();
+    assert!(response.status().is_success(), "{}", response.status());
+    serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_client_request_ids_are_scoped_to_the_api_key() {
+    let app = app();
+    let alice = create(&app, Some("alice-key"), "req-1").await;
+    let bob = create(&app, Some("bob-key"), "req-1").await;
+    assert_ne!(alice["vote_id"], bob["vote_id"]);
+    assert_eq!(bob["message"], "Vote created successfully");
+
+    // a retry with the same key finds the first vote
+    let retried = create(&app, Some("alice-key"), "req-1").await;
+    assert_eq!(retried["vote_id"], alice["vote_id"]);
+    assert_eq!(retried["message"], "Vote already created for this request");
+
+    // callers without a key share the system namespace, apart from keyed callers
+    let anonymous = create(&app, None, "req-1").await;
+    assert_ne!(anonymous["vote_id"], alice["vote_id"]);
+    assert_eq!(create(&app, None, "req-1").await["vote_id"], anonymous["vote_id"]);
+
+    let uri = format!("/api/v1/votes/{}", alice["vote_id"].as_str().unwrap());
+    let response = app.clone().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+    let vote: Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert!(vote["vote"]["creator"].as_str().unwrap().starts_with("api-key:"));
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use vote_api::{create_router, AppComponents, AppState};
+use vote_engine::services::MemoryVoteService;
+
+fn app(service: Arc<MemoryVoteService>) -> Router {
+    create_router(Arc::new(AppState::new(common::config(), AppComponents::in_memory().with_vote_service(service))))
+}
+
+async fn get(app: &Router, uri: &str, if_none_match: Option<&str>) -> (StatusCode, Option<String>, Vec<u8>) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(etag) = if_none_match {
+        request = request.header(header::IF_NONE_MATCH, etag);
+    }
+    let response = app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let etag = response.headers().get(header::ETAG).map(|v| v.to_str().unwrap().to_string());
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, etag, body.to_vec())
+}
+
+#[tokio::test]
+async fn test_results_revalidate_with_304() {
+    let service = Arc::new(MemoryVoteService::new());
+    let app = app(service.clone());
+    let uri = format!("/api/v1/votes/{}/results", common::ended_vote(&service, None).await);
+
+    let (status, etag, body) = get(&app, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let etag = etag.expect("results carry an ETag");
+
+    // The tag follows the stored results, not the time of the request
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let (status, again, _) = get(&app, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(again.as_deref(), Some(etag.as_str()));
+
+    let (status, revalidated, empty) = get(&app, &uri, Some(&etag)).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(revalidated.as_deref(), Some(etag.as_str()));
+    assert!(empty.is_empty());
+    assert!(!body.is_empty());
+}
@@ -0,0 +1,210 @@
+#![cfg(feature = "graphql")]
+
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::json;
+use shared_types::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tower::ServiceExt;
+use vote_api::graphql::{MAX_PAGE_SIZE, MAX_QUERY_COMPLEXITY};
+use vote_api::{create_router, AppComponents, AppState};
+use vote_engine::{MemoryVoteService, VoteService};
+
+async fn graphql(app: &Router, query: &str, variables: serde_json::Value) -> serde_json::Value {
+    let request = Request::post("/graphql")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(json!({ "query": query, "variables": variables }).to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap()
+}
+
+async fn app_with_votes(count: usize) -> (Router, Vec<String>, Arc<MemoryVoteService>) {
+    let vote_service = Arc::new(MemoryVoteService::new());
+    let state = Arc::new(AppState::new(common::config(), AppComponents::in_memory().with_vote_service(vote_service.clone())));
+    let mut ids = Vec::new();
+    for i in 0..count {
+        ids.push(state.vote_engine.create_vote(common::vote_config(&format!("Vote {}", i))).await.unwrap());
+    }
+    (create_router(state), ids, vote_service)
+}
+
+#[tokio::test]
+async fn test_vote_with_reveals_in_one_request() {
+    let (app, ids, vote_service) = app_with_votes(1).await;
+    for (voter, value) in [("alice", "yes"), ("bob", "no")] {
+        vote_service.save_reveal(Reveal {
+            id: format!("r-{}", voter),
+            vote_id: ids[0].clone(),
+            voter: voter.to_string(),
+            value: json!(value),
+            salt: format!("{}-salt", voter),
+            created_at: Utc::now(),
+            ciphertext: None,
+        }).await.unwrap();
+    }
+
+    let body = graphql(&app, r#"
+        query($id: String!) {
+            vote(id: $id) {
+                id
+                title
+                status
+                reveals { voter value salt }
+                commitments { voter }
+                results { totalVotes }
+            }
+            missing: vote(id: "does-not-exist") { id }
+        }"#, json!({ "id": ids[0] })).await;
+
+    assert!(body.get("errors").is_none(), "{}", body);
+    let vote = &body["data"]["vote"];
+    assert_eq!(vote["id"], ids[0]);
+    assert_eq!(vote["title"], "Vote 0");
+    assert_eq!(vote["status"], "CREATED");
+    let mut reveals: Vec<_> = vote["reveals"].as_array().unwrap().iter()
+        .map(|r| (r["voter"].as_str().unwrap(), r["value"].as_str().unwrap()))
+        .collect();
+    reveals.sort();
+    assert_eq!(reveals, vec![("alice", "yes"), ("bob", "no")]);
+    assert_eq!(vote["commitments"], json!([]));
+    assert!(vote["results"].is_null());
+    assert!(body["data"]["missing"].is_null());
+}
+
+#[tokio::test]
+async fn test_votes_connection_pages_forward() {
+    let (app, _, _) = app_with_votes(3).await;
+    let query = r#"
+        query($after: String) {
+            votes(first: 2, after: $after) {
+                edges { cursor node { id } }
+                pageInfo { hasNextPage hasPreviousPage endCursor }
+            }
+            stats { totalVotes created }
+        }"#;
+
+    let first = graphql(&app, query, json!({})).await;
+    let page = &first["data"]["votes"];
+    assert_eq!(page["edges"].as_array().unwrap().len(), 2);
+    assert_eq!(page["pageInfo"]["hasNextPage"], true);
+    assert_eq!(page["pageInfo"]["hasPreviousPage"], false);
+    assert_eq!(first["data"]["stats"], json!({ "totalVotes": 3, "created": 3 }));
+
+    let second = graphql(&app, query, json!({ "after": page["pageInfo"]["endCursor"] })).await;
+    let rest = &second["data"]["votes"];
+    assert_eq!(rest["edges"].as_array().unwrap().len(), 1);
+    assert_eq!(rest["pageInfo"]["hasNextPage"], false);
+    assert_eq!(rest["pageInfo"]["hasPreviousPage"], true);
+
+    let mut seen: Vec<_> = page["edges"].as_array().unwrap().iter()
+        .chain(rest["edges"].as_array().unwrap())
+        .map(|e| e["node"]["id"].as_str().unwrap().to_string())
+        .collect();
+    seen.sort();
+    seen.dedup();
+    assert_eq!(seen.len(), 3);
+}
+
+/// Memory store that counts per-vote and batched commitment/reveal lookups
+#[derive(Default)]
+struct CountingVoteService {
+    inner: MemoryVoteService,
+    single_lookups: AtomicUsize,
+    batched_lookups: AtomicUsize,
+}
+
+#[async_trait]
+impl VoteService for CountingVoteService {
+    async fn create_vote(&self, vote: Vote) -> Result<(), VoteError> { self.inner.create_vote(vote).await }
+    async fn get_vote(&self, id: &VoteId) -> Result<Vote, VoteError> { self.inner.get_vote(id).await }
+    async fn list_votes(&self, query: ListQuery) -> Result<Page<Vote>, VoteError> { self.inner.list_votes(query).await }
+    async fn update_vote_status(&self, id: &VoteId, status: VoteStatus) -> Result<(), VoteError> { self.inner.update_vote_status(id, status).await }
+    async fn update_vote_results(&self, id: &VoteId, results: &VoteResults) -> Result<(), VoteError> { self.inner.update_vote_results(id, results).await }
+    async fn save_commitment(&self, commitment: Commitment) -> Result<(), VoteError> { self.inner.save_commitment(commitment).await }
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, VoteError> { self.inner.get_commitment(vote_id, voter).await }
+    async fn save_reveal(&self, reveal: Reveal) -> Result<(), VoteError> { self.inner.save_reveal(reveal).await }
+    async fn calculate_results(&self, vote: &Vote, reveals: &[Reveal]) -> Result<VoteResults, VoteError> { self.inner.calculate_results(vote, reveals).await }
+
+    async fn list_commitments(&self, vote_id: &VoteId) -> Result<Vec<Commitment>, VoteError> {
+        self.single_lookups.fetch_add(1, Ordering::SeqCst);
+        self.inner.list_commitments(vote_id).await
+    }
+
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, VoteError> {
+        self.single_lookups.fetch_add(1, Ordering::SeqCst);
+        self.inner.list_reveals(vote_id).await
+    }
+
+    async fn list_commitments_for(&self, vote_ids: &[VoteId]) -> Result<HashMap<String, Vec<Commitment>>, VoteError> {
+        self.batched_lookups.fetch_add(1, Ordering::SeqCst);
+        self.inner.list_commitments_for(vote_ids).await
+    }
+
+    async fn list_reveals_for(&self, vote_ids: &[VoteId]) -> Result<HashMap<String, Vec<Reveal>>, VoteError> {
+        self.batched_lookups.fetch_add(1, Ordering::SeqCst);
+        self.inner.list_reveals_for(vote_ids).await
+    }
+}
+
+#[tokio::test]
+async fn test_nested_commitments_and_reveals_load_once_per_page() {
+    let service = Arc::new(CountingVoteService::default());
+    let state = Arc::new(AppState::new(common::config(), AppComponents::in_memory().with_vote_service(service.clone())));
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let id = state.vote_engine.create_vote(common::vote_config(&format!("Vote {}", i))).await.unwrap();
+        service.save_commitment(Commitment {
+            id: format!("c-{}", i),
+            vote_id: id.clone(),
+            voter: format!("voter-{}", i),
+            commitment_hash: format!("hash-{}", i),
+            salt: "salt".to_string(),
+            created_at: Utc::now(),
+            range_proof: None,
+        }).await.unwrap();
+        ids.push(id);
+    }
+    let app = create_router(state);
+
+    let body = graphql(&app, "{ votes { edges { node { id commitments { voter } reveals { voter } } } } }", json!({})).await;
+    assert!(body.get("errors").is_none(), "{}", body);
+    let edges = body["data"]["votes"]["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 5);
+    for edge in edges {
+        let i = ids.iter().position(|id| *id == edge["node"]["id"]).unwrap();
+        assert_eq!(edge["node"]["commitments"], json!([{ "voter": format!("voter-{}", i) }]));
+        assert_eq!(edge["node"]["reveals"], json!([]));
+    }
+    assert_eq!(service.single_lookups.load(Ordering::SeqCst), 0);
+    assert_eq!(service.batched_lookups.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_queries_are_bounded() {
+    let (app, _, _) = app_with_votes(1).await;
+
+    let too_many = graphql(&app, &format!("{{ votes(first: {}) {{ edges {{ node {{ id }} }} }} }}", MAX_PAGE_SIZE + 1), json!({})).await;
+    assert!(too_many["errors"][0]["message"].as_str().unwrap().contains("at most"), "{}", too_many);
+
+    // introspection types nest without bound; the depth limit stops them
+    let deep = graphql(&app, "{ __schema { types { fields { type { ofType { ofType { ofType { ofType { name } } } } } } } } }", json!({})).await;
+    assert!(deep["errors"][0]["message"].as_str().unwrap().contains("nested too deep"), "{}", deep);
+
+    // one full page is fine, several aliased full pages are not
+    let page = "votes(first: 100) { edges { node { id title status commitments { voter commitmentHash createdAt } reveals { voter value salt createdAt } } } }";
+    let single = graphql(&app, &format!("{{ a: {} }}", page), json!({})).await;
+    assert!(single.get("errors").is_none(), "{}", single);
+    let aliased = graphql(&app, &format!("{{ a: {page} b: {page} c: {page} }}"), json!({})).await;
+    assert!(aliased["errors"][0]["message"].as_str().unwrap().contains("too complex"), "{} over {}", aliased, MAX_QUERY_COMPLEXITY);
+}
@@ -0,0 +1,47 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use shared_config::ServerConfig;
+use shared_types::{ApiError, VoteError};
+use tower::ServiceExt;
+use vote_api::with_http_layers;
+
+async fn missing_vote() -> ApiError {
+    VoteError::VoteNotFound { id: "vote-42".to_string() }.into()
+}
+
+async fn get_with_language(language: Option<&str>) -> (axum::http::HeaderMap, serde_json::Value) {
+    let app = with_http_layers(Router::new().route("/votes/missing", get(missing_vote)), &ServerConfig::default());
+    let mut request = Request::builder().uri("/votes/missing");
+    if let Some(language) = language {
+        request = request.header(header::ACCEPT_LANGUAGE, language);
+    }
+    let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let headers = response.headers().clone();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (headers, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_not_found_error_is_rendered_per_locale() {
+    let (_, english) = get_with_language(None).await;
+    let (headers, chinese) = get_with_language(Some("zh-CN,zh;q=0.9,en;q=0.8")).await;
+
+    assert_eq!(english["message"], "Vote not found: vote-42");
+    assert_eq!(chinese["message"], "未找到投票: vote-42");
+    assert_eq!(headers[header::CONTENT_LANGUAGE], "zh");
+    // the code and details clients branch on are unchanged
+    assert_eq!(chinese["code"], english["code"]);
+    assert_eq!(chinese["details"], english["details"]);
+}
+
+#[tokio::test]
+async fn test_unsupported_language_falls_back_to_english() {
+    let (headers, body) = get_with_language(Some("fr-FR, de;q=0.5")).await;
+    assert_eq!(body["message"], "Vote not found: vote-42");
+    assert_eq!(headers[header::CONTENT_LANGUAGE], "en");
+}
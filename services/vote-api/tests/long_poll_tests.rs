@@ -0,0 +1,97 @@
+mod common;
+
+use axum::{
+    http::{Method, StatusCode},
+    Router,
+};
+use common::{create_vote, send};
+use serde_json::json;
+use event_store::{store::MemoryEventStore, EventStorage};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use vote_api::{create_router, AppComponents, AppState};
+use vote_engine::MemoryVoteService;
+
+async fn app() -> Router {
+    create_router(Arc::new(AppState::from_config(common::config()).await.unwrap()))
+}
+
+#[tokio::test]
+async fn test_update_during_wait_releases_request() {
+    let app = app().await;
+    let id = create_vote(&app).await;
+
+    let (status, current) = send(&app, Method::GET, &format!("/api/v1/votes/{}/wait?since_version=0", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let version = current["version"].as_u64().unwrap();
+
+    let waiter = {
+        let app = app.clone();
+        let uri = format!("/api/v1/votes/{}/wait?since_version={}&timeout=10s", id, version);
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let (status, body) = send(&app, Method::GET, &uri, None).await;
+            (status, body, started.elapsed())
+        })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let commit = json!({ "voter": "alice", "commitment_hash": "a".repeat(64), "salt": "pepper" });
+    let (status, _) = send(&app, Method::POST, &format!("/api/v1/votes/{}/commit", id), Some(commit)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body, elapsed) = waiter.await.unwrap();
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["version"].as_u64().unwrap() > version);
+    assert_eq!(body["vote"]["status"], "CommitmentPhase");
+    assert!(elapsed < Duration::from_secs(2), "wait held for {:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_wait_without_change_times_out_with_304() {
+    let app = app().await;
+    let id = create_vote(&app).await;
+
+    let (_, current) = send(&app, Method::GET, &format!("/api/v1/votes/{}/wait?since_version=0", id), None).await;
+    let version = current["version"].as_u64().unwrap();
+
+    let started = Instant::now();
+    let uri = format!("/api/v1/votes/{}/wait?since_version={}&timeout=100ms", id, version);
+    let (status, _) = send(&app, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_wait_on_unknown_vote_is_404() {
+    let app = app().await;
+    let (status, body) = send(&app, Method::GET, "/api/v1/votes/missing/wait?timeout=5s", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "vote.not_found");
+}
+
+#[tokio::test]
+async fn test_versions_come_from_the_event_store_across_restarts() {
+    let vote_service = Arc::new(MemoryVoteService::new());
+    let event_store = Arc::new(MemoryEventStore::new());
+    let components = || {
+        AppComponents::in_memory().with_vote_service(vote_service.clone()).with_event_store(event_store.clone())
+    };
+    let before = create_router(Arc::new(AppState::new(common::config(), components())));
+    let id = create_vote(&before).await;
+    let (_, current) = send(&before, Method::GET, &format!("/api/v1/votes/{}/wait?since_version=0", id), None).await;
+    let version = current["version"].as_u64().unwrap();
+    assert_eq!(version, event_store.session_version(&id).await.unwrap());
+
+    let commit = json!({ "voter": "alice", "commitment_hash": "a".repeat(64), "salt": "pepper" });
+    let (status, _) = send(&before, Method::POST, &format!("/api/v1/votes/{}/commit", id), Some(commit)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // a fresh instance over the same stores sees the change the client has not
+    let after = create_router(Arc::new(AppState::new(common::config(), components())));
+    let uri = format!("/api/v1/votes/{}/wait?since_version={}&timeout=100ms", id, version);
+    let (status, body) = send(&after, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["version"].as_u64().unwrap(), event_store.session_version(&id).await.unwrap());
+    assert!(body["version"].as_u64().unwrap() > version);
+}
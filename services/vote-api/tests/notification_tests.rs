@@ -0,0 +1,131 @@
+mod common;
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::json;
+use shared_config::{NotificationSinkConfig, ServerConfig};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use vote_api::events::VoteEvent;
+use vote_api::{create_router, AppComponents, AppState, CircuitBreaker, NotificationSink};
+
+/// Sink standing in for an unreachable notification service
+struct FailingSink {
+    attempts: AtomicU64,
+    hang: bool,
+}
+
+#[async_trait]
+impl NotificationSink for FailingSink {
+    async fn send(&self, _event: &VoteEvent) -> Result<(), String> {
+        self.attempts.fetch_add(1, Ordering::SeqCst);
+        if self.hang {
+            std::future::pending::<()>().await;
+        }
+        Err("connection refused".to_string())
+    }
+}
+
+fn state(sink: Arc<FailingSink>) -> Arc<AppState> {
+    let notifications = NotificationSinkConfig { failure_threshold: 2, timeout_ms: 50, ..Default::default() };
+    let config = common::config_with(ServerConfig { notifications, ..Default::default() });
+    Arc::new(AppState::new(config, AppComponents::in_memory().with_notification_sink(sink)))
+}
+
+async fn call(app: &Router, uri: &str, body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = tokio::time::timeout(Duration::from_secs(1), app.clone().oneshot(request))
+        .await
+        .expect("request returns without waiting on notifications")
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+}
+
+/// Create a vote and commit for `voters`, asserting every call succeeds
+async fn create_and_commit(app: &Router, voters: &[&str]) {
+    let (status, created) = call(app, "/api/v1/votes", json!({
+        "config": {
+            "title": "Budget",
+            "description": "Approve the budget",
+            "template_id": "yes_no",
+            "template_params": {},
+            "commitment_duration_hours": 24,
+            "reveal_duration_hours": 24
+        }
+    }))
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let uri = format!("/api/v1/votes/{}/commit", created["vote_id"].as_str().unwrap());
+    for voter in voters {
+        let body = json!({ "voter": voter, "commitment_hash": "a".repeat(64), "salt": "pepper" });
+        let (status, _) = call(app, &uri, body).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
+
+async fn wait_for_drops(state: &AppState, expected: u64) {
+    let notifier = state.notifier.as_ref().unwrap();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while notifier.dropped() < expected {
+        assert!(Instant::now() < deadline, "only {} of {} events dropped", notifier.dropped(), expected);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_commit_succeeds_while_notification_sink_is_failing() {
+    let sink = Arc::new(FailingSink { attempts: AtomicU64::new(0), hang: false });
+    let state = state(sink.clone());
+    let app = create_router(state.clone());
+
+    create_and_commit(&app, &["alice", "bob", "carol", "dave"]).await;
+
+    // One creation and four commitments, all dropped; the breaker opened after two failures
+    wait_for_drops(&state, 5).await;
+    assert_eq!(sink.attempts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_commit_does_not_wait_for_a_hanging_sink() {
+    let sink = Arc::new(FailingSink { attempts: AtomicU64::new(0), hang: true });
+    let state = state(sink.clone());
+    let app = create_router(state.clone());
+
+    create_and_commit(&app, &["alice", "bob"]).await;
+
+    // Sends time out and count as failures like any other
+    wait_for_drops(&state, 3).await;
+    assert_eq!(sink.attempts.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_circuit_breaker_allows_a_trial_after_cooldown() {
+    let start = Instant::now();
+    let mut breaker = CircuitBreaker::new(2, Duration::from_secs(30));
+    breaker.record_failure(start);
+    assert!(breaker.allows(start));
+    breaker.record_failure(start);
+    assert!(breaker.is_open(start + Duration::from_secs(29)));
+
+    // The trial send fails: open for another cooldown
+    let trial = start + Duration::from_secs(30);
+    assert!(breaker.allows(trial));
+    breaker.record_failure(trial);
+    assert!(breaker.is_open(trial + Duration::from_secs(1)));
+
+    breaker.record_success();
+    assert!(breaker.allows(trial + Duration::from_secs(1)));
+}
@@ -0,0 +1,48 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use shared_config::ServerConfig;
+use tower::ServiceExt;
+use vote_api::with_http_layers;
+
+async fn panics() -> &'static str {
+    panic!("deliberate handler panic")
+}
+
+fn app() -> Router {
+    let router = Router::new()
+        .route("/panic", get(panics))
+        .route("/ok", get(|| async { "fine" }));
+    with_http_layers(router, &ServerConfig::default())
+}
+
+async fn get_path(app: &Router, path: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .uri(path)
+        .header("x-correlation-id", "panic-test")
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_handler_panic_becomes_500_json_error() {
+    let response = get_path(&app(), "/panic").await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+    let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+    assert_eq!(body["code"], "internal.panic");
+    // The panic message stays in the logs
+    assert!(!body.to_string().contains("deliberate"));
+}
+
+#[tokio::test]
+async fn test_router_keeps_serving_after_a_panic() {
+    let app = app();
+    assert_eq!(get_path(&app, "/panic").await.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(get_path(&app, "/ok").await.status(), StatusCode::OK);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use common::create_vote;
+use serde_json::json;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use tower::ServiceExt;
+use vote_api::{create_router, AppState};
+
+async fn app() -> Router {
+    create_router(std::sync::Arc::new(AppState::from_config(common::config()).await.unwrap()))
+}
+
+fn request(method: Method, uri: &str, body: Option<serde_json::Value>) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap()
+}
+
+async fn commit(app: &Router, vote_id: &str, voter: &str) {
+    let body = json!({ "voter": voter, "commitment_hash": "a".repeat(64), "salt": "pepper" });
+    let uri = format!("/api/v1/votes/{}/commit", vote_id);
+    let response = app.clone().oneshot(request(Method::POST, &uri, Some(body))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_subscriber_receives_event_emitted_after_subscribing() {
+    let app = app().await;
+    let id = create_vote(&app).await;
+    let other = create_vote(&app).await;
+
+    let response = app.clone().oneshot(request(Method::GET, &format!("/api/v1/votes/{}/events", id), None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+    let mut stream = response.into_body().into_data_stream();
+
+    // Events for other votes are filtered out
+    commit(&app, &other, "bob").await;
+    commit(&app, &id, "alice").await;
+
+    let frame = tokio::time::timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("no event within timeout")
+        .unwrap()
+        .unwrap();
+    let frame = String::from_utf8(frame.to_vec()).unwrap();
+    assert!(frame.contains("event: CommitmentSubmitted"), "{}", frame);
+
+    let data = frame.lines().find_map(|line| line.strip_prefix("data: ")).unwrap();
+    let event: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(event["session_id"], id.as_str());
+    assert_eq!(event["data"]["voter"], "alice");
+}
+
+#[tokio::test]
+async fn test_event_stream_for_unknown_vote_is_404() {
+    let app = app().await;
+    let response = app.oneshot(request(Method::GET, "/api/v1/votes/missing/events", None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
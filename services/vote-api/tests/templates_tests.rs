@@ -0,0 +1,51 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+use vote_api::{create_router, AppState};
+
+async fn app() -> Router {
+    create_router(std::sync::Arc::new(AppState::from_config(common::config()).await.unwrap()))
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_lists_every_default_template_with_schemas() {
+    let app = app().await;
+    let (status, body) = get(&app, "/api/v1/templates").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let templates = body["templates"].as_array().unwrap();
+    let ids: Vec<&str> = templates.iter().map(|t| t["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["multiple_choice", "numeric_range", "ranking", "yes_no"]);
+    for template in templates {
+        assert!(template["name"].is_string());
+        assert!(template["description"].is_string());
+        assert!(template["schema"]["type"].is_string());
+        assert_eq!(template["params_schema"]["type"], "object");
+    }
+}
+
+#[tokio::test]
+async fn test_get_template_includes_params_schema() {
+    let app = app().await;
+    let (status, body) = get(&app, "/api/v1/templates/multiple_choice").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], "multiple_choice");
+    assert_eq!(body["schema"]["type"], "string");
+    assert_eq!(body["params_schema"]["required"], serde_json::json!(["choices"]));
+
+    let (status, _) = get(&app, "/api/v1/templates/unknown").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,86 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use shared_config::ServerConfig;
+use tower::ServiceExt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use vote_api::with_http_layers;
+
+/// Collects the messages of WARN events
+#[derive(Clone, Default)]
+struct WarningCapture {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+struct MessageVisitor<'a>(&'a mut String);
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            *self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for WarningCapture {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() == Level::WARN {
+            let mut message = String::new();
+            event.record(&mut MessageVisitor(&mut message));
+            self.messages.lock().unwrap().push(message);
+        }
+    }
+}
+
+fn app() -> Router {
+    let router = Router::new()
+        .route("/slow/:id", get(|| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            "done"
+        }))
+        .route("/fast", get(|| async { "done" }));
+    with_http_layers(router, &ServerConfig { slow_request_threshold_ms: 20, ..Default::default() })
+}
+
+async fn get_path(app: &Router, path: &str) -> StatusCode {
+    let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_slow_handler_logs_slow_request_warning() {
+    let capture = WarningCapture::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+
+    let app = app();
+    assert_eq!(get_path(&app, "/fast").await, StatusCode::OK);
+    assert!(capture.messages.lock().unwrap().is_empty());
+
+    assert_eq!(get_path(&app, "/slow/42").await, StatusCode::OK);
+    let warnings = capture.messages.lock().unwrap().clone();
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert!(warnings[0].contains("Slow request: GET /slow/:id"), "{}", warnings[0]);
+}
+
+#[tokio::test]
+async fn test_request_timings_are_exported() {
+    let app = app();
+    assert_eq!(get_path(&app, "/slow/7").await, StatusCode::OK);
+
+    let metrics = Router::new().route("/metrics", get(vote_api::handlers::metrics_handler));
+    let response = metrics
+        .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = String::from_utf8(to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec()).unwrap();
+    assert!(body.contains(r#"http_request_duration_seconds_count{method="GET",route="/slow/:id",service="vote-api",status="200"}"#), "{}", body);
+    assert!(body.contains("http_slow_requests_total"), "{}", body);
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration as ChronoDuration, Utc};
+use serde_json::json;
+use shared_config::ServerConfig;
+use shared_types::*;
+use shared_utils::crypto::create_commitment;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use vote_api::events::VoteEventType;
+use vote_api::{create_router, AppComponents, AppState};
+use vote_engine::{services::MemoryVoteService, VoteService};
+
+const ADMIN_KEY: &str = "admin-secret";
+
+fn state(service: Arc<MemoryVoteService>) -> Arc<AppState> {
+    let config = common::config_with(ServerConfig { admin_api_key: Some(ADMIN_KEY.to_string()), ..Default::default() });
+    Arc::new(AppState::new(config, AppComponents::in_memory().with_vote_service(service)))
+}
+
+async fn send(app: &Router, method: Method, uri: &str, body: Option<serde_json::Value>) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_KEY));
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_else(Body::empty);
+    let response = app.clone().oneshot(request.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+}
+
+#[tokio::test]
+async fn test_history_follows_a_vote_through_its_phases() {
+    let service = Arc::new(MemoryVoteService::new());
+    let state = state(service.clone());
+    let app = create_router(state.clone());
+
+    // Short phases so the walk through them fits in a test
+    let now = Utc::now();
+    let id = VoteId::generate().to_string();
+    service.create_vote(Vote {
+        id: id.clone(),
+        title: "Budget".to_string(),
+        description: "Approve the budget".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: json!({}),
+        creator: "system".to_string(),
+        created_at: now,
+        commitment_start: now - ChronoDuration::seconds(1),
+        commitment_end: now + ChronoDuration::milliseconds(300),
+        reveal_start: now + ChronoDuration::milliseconds(300),
+        reveal_end: now + ChronoDuration::milliseconds(600),
+        status: VoteStatus::Created,
+        results: None,
+        tie_break: TieBreak::default(),
+        legal_hold: false,
+        completion_webhook_url: None,
+        client_request_id: None,
+        reveal_public_key: None,
+    }).await.unwrap();
+    state.record_change(&id, VoteEventType::SessionCreated, HashMap::new()).await;
+
+    let value = json!(true);
+    let salt = "pepper".to_string();
+    let commitment_hash = create_commitment(&serde_json::to_string(&value).unwrap(), &salt);
+    let (status, _) = send(&app, Method::POST, &format!("/api/v1/votes/{}/commit", id), Some(json!({
+        "voter": "alice", "commitment_hash": commitment_hash, "salt": salt,
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    let (status, _) = send(&app, Method::POST, &format!("/api/v1/votes/{}/reveal", id), Some(json!({
+        "voter": "alice", "value": value, "salt": salt,
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let (status, _) = send(&app, Method::GET, &format!("/api/v1/votes/{}/results", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    // Reading completed results again is not another change
+    send(&app, Method::GET, &format!("/api/v1/votes/{}/results", id), None).await;
+
+    let (status, body) = send(&app, Method::GET, &format!("/api/v1/votes/{}/history", id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let history: VoteHistoryResponse = serde_json::from_value(body).unwrap();
+    let steps: Vec<_> = history.entries.iter()
+        .map(|e| (e.kind.clone(), e.from_status.clone(), e.to_status.clone(), e.actor.as_str()))
+        .collect();
+    assert_eq!(steps, vec![
+        (VoteHistoryKind::Created, None, Some(VoteStatus::Created), "system"),
+        (VoteHistoryKind::StatusChanged, Some(VoteStatus::Created), Some(VoteStatus::CommitmentPhase), "alice"),
+        (VoteHistoryKind::StatusChanged, Some(VoteStatus::CommitmentPhase), Some(VoteStatus::RevealPhase), "alice"),
+        (VoteHistoryKind::StatusChanged, Some(VoteStatus::RevealPhase), Some(VoteStatus::Completed), "system"),
+        (VoteHistoryKind::ResultsComputed, None, None, "system"),
+    ]);
+    assert_eq!(history.entries[4].details, Some(json!({ "total_votes": 1 })));
+    assert!(history.entries.windows(2).all(|w| w[0].version <= w[1].version && w[0].timestamp <= w[1].timestamp));
+}
+
+#[tokio::test]
+async fn test_history_records_engine_transitions_and_cancellation() {
+    let state = state(Arc::new(MemoryVoteService::new()));
+    let app = create_router(state.clone());
+    let id = state.vote_engine.create_vote(common::vote_config("Budget")).await.unwrap();
+    state.record_change(&id, VoteEventType::SessionCreated, HashMap::new()).await;
+
+    // Advanced ahead of schedule without going through a vote-api handler
+    state.vote_engine.advance_phase(&id).await.unwrap();
+    let (status, body) = send(&app, Method::POST, &format!("/admin/votes/{}/cancel", id), None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["vote"]["status"], json!(VoteStatus::Cancelled));
+    let (status, _) = send(&app, Method::POST, &format!("/admin/votes/{}/cancel", id), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let history = state.get_vote_history(&id).await.unwrap();
+    let steps: Vec<_> = history.iter()
+        .map(|e| (e.kind.clone(), e.from_status.clone(), e.to_status.clone(), e.actor.starts_with("api-key:")))
+        .collect();
+    assert_eq!(steps, vec![
+        (VoteHistoryKind::Created, None, Some(VoteStatus::Created), false),
+        (VoteHistoryKind::StatusChanged, Some(VoteStatus::Created), Some(VoteStatus::CommitmentPhase), false),
+        (VoteHistoryKind::Cancelled, Some(VoteStatus::CommitmentPhase), Some(VoteStatus::Cancelled), true),
+    ]);
+}
+
+#[tokio::test]
+async fn test_history_of_unknown_vote_is_not_found() {
+    let app = create_router(state(Arc::new(MemoryVoteService::new())));
+    let uri = format!("/api/v1/votes/{}/history", VoteId::generate());
+    let (status, _) = send(&app, Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
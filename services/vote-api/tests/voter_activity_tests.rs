@@ -0,0 +1,69 @@
+mod common;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::Utc;
+use shared_types::Commitment;
+use std::sync::Arc;
+use tower::ServiceExt;
+use vote_api::{create_router, AppComponents, AppState};
+use vote_engine::{MemoryVoteService, VoteService};
+
+/// Three votes; alice committed in the first two, bob in the third
+async fn app() -> (Router, Vec<String>) {
+    let vote_service = Arc::new(MemoryVoteService::new());
+    let state = Arc::new(AppState::new(common::config(), AppComponents::in_memory().with_vote_service(vote_service.clone())));
+    let mut ids = Vec::new();
+    for (i, voter) in ["alice", "alice", "bob"].into_iter().enumerate() {
+        let id = state.vote_engine.create_vote(common::vote_config(&format!("Vote {}", i))).await.unwrap();
+        vote_service.save_commitment(Commitment {
+            id: format!("c-{}", i),
+            vote_id: id.clone(),
+            voter: voter.to_string(),
+            commitment_hash: "a".repeat(64),
+            salt: "salt".to_string(),
+            created_at: Utc::now(),
+            range_proof: None,
+        }).await.unwrap();
+        ids.push(id);
+    }
+    (create_router(state), ids)
+}
+
+async fn get(app: &Router, uri: &str) -> serde_json::Value {
+    let response = app.clone().oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_voter_active_in_two_votes_gets_both_listed() {
+    let (app, ids) = app().await;
+    let body = get(&app, "/api/v1/voters/alice/activity").await;
+    assert_eq!(body["voter"], "alice");
+    assert_eq!(body["votes"]["total"], 2);
+    let mut listed: Vec<&str> = body["votes"]["items"].as_array().unwrap().iter().map(|v| v["vote_id"].as_str().unwrap()).collect();
+    listed.sort();
+    let mut expected = vec![ids[0].as_str(), ids[1].as_str()];
+    expected.sort();
+    assert_eq!(listed, expected);
+    assert!(body["votes"]["items"][0]["committed_at"].is_string());
+    assert!(body["votes"]["items"][0]["revealed_at"].is_null());
+}
+
+#[tokio::test]
+async fn test_voter_activity_is_paginated() {
+    let (app, _) = app().await;
+    let first = get(&app, "/api/v1/voters/alice/activity?page=0&page_size=1").await;
+    let second = get(&app, "/api/v1/voters/alice/activity?page=1&page_size=1").await;
+    assert_eq!(first["votes"]["items"].as_array().unwrap().len(), 1);
+    assert_eq!(second["votes"]["items"].as_array().unwrap().len(), 1);
+    assert_ne!(first["votes"]["items"][0]["vote_id"], second["votes"]["items"][0]["vote_id"]);
+    assert_eq!(first["votes"]["total_pages"], 2);
+
+    let nobody = get(&app, "/api/v1/voters/carol/activity").await;
+    assert_eq!(nobody["votes"]["total"], 0);
+}
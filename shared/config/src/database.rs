@@ -1,12 +1,67 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DatabaseConfig {
     pub url: String,
     pub max_connections: u32,
     pub min_connections: u32,
     pub connection_timeout_seconds: u64,
     pub idle_timeout_seconds: u64,
+    /// Queries taking longer than this many milliseconds are logged with their SQL
+    #[serde(default = "default_slow_query_threshold_ms")]
+    pub slow_query_threshold_ms: u64,
+    /// Cleanup of completed and cancelled votes; off by default
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+/// How long ended votes are kept before the cleanup job removes them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    pub enabled: bool,
+    /// Completed and cancelled votes are removed this many days after their reveal phase ends
+    pub max_age_days: u64,
+    /// How often the cleanup job runs
+    pub interval_seconds: u64,
+    /// Directory removed votes are exported to as JSON first; `None` purges without a copy
+    #[serde(default)]
+    pub archive_dir: Option<String>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_age_days: 365,
+            interval_seconds: 3600,
+            archive_dir: None,
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("RETENTION_ENABLED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.enabled),
+            max_age_days: std::env::var("RETENTION_MAX_AGE_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.max_age_days),
+            interval_seconds: std::env::var("RETENTION_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.interval_seconds),
+            archive_dir: std::env::var("RETENTION_ARCHIVE_DIR").ok(),
+        }
+    }
+}
+
+fn default_slow_query_threshold_ms() -> u64 {
+    200
 }
 
 impl Default for DatabaseConfig {
@@ -17,6 +72,8 @@ impl Default for DatabaseConfig {
             min_connections: 1,
             connection_timeout_seconds: 30,
             idle_timeout_seconds: 600,
+            slow_query_threshold_ms: default_slow_query_threshold_ms(),
+            retention: RetentionConfig::default(),
         }
     }
 }
@@ -42,6 +99,11 @@ impl DatabaseConfig {
                 .unwrap_or_else(|_| "600".to_string())
                 .parse()
                 .unwrap_or(600),
+            slow_query_threshold_ms: std::env::var("DB_SLOW_QUERY_THRESHOLD_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_slow_query_threshold_ms),
+            retention: RetentionConfig::from_env(),
         }
     }
 }
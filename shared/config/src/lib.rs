@@ -2,16 +2,18 @@ pub mod server;
 pub mod database;
 pub mod blockchain;
 pub mod logging;
+pub mod retry;
 
 pub use server::*;
 pub use database::*;
 pub use blockchain::*;
 pub use logging::*;
+pub use retry::*;
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppConfig {
     pub server: ServerConfig,
     pub database: DatabaseConfig,
@@ -35,4 +37,63 @@ impl AppConfig {
         };
         Ok(config)
     }
+
+    /// Load configuration from the file named by `CONFIG_FILE`, falling back
+    /// to environment variables when it is not set.
+    pub fn load() -> anyhow::Result<Self> {
+        match std::env::var("CONFIG_FILE") {
+            Ok(path) => Self::load_from_file(path),
+            Err(_) => Self::load_from_env(),
+        }
+    }
+
+    /// Basic sanity checks performed before a configuration is applied.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.server.bind.trim().is_empty() {
+            anyhow::bail!("server.bind must not be empty");
+        }
+        if self.server.port == 0 {
+            anyhow::bail!("server.port must not be 0");
+        }
+        self.server.validate_cors().map_err(anyhow::Error::msg)?;
+        if self.database.url.trim().is_empty() {
+            anyhow::bail!("database.url must not be empty");
+        }
+        if self.database.retention.enabled && self.database.retention.interval_seconds == 0 {
+            anyhow::bail!("database.retention.interval_seconds must not be 0");
+        }
+        if self.logging.level.trim().is_empty() {
+            anyhow::bail!("logging.level must not be empty");
+        }
+        Ok(())
+    }
+
+    /// Sections that differ from `other` but cannot be applied without a restart.
+    ///
+    /// The log level is the only logging setting that can change at runtime;
+    /// format and output are wired into the subscriber at startup.
+    pub fn restart_required_changes(&self, other: &AppConfig) -> Vec<&'static str> {
+        let mut changes = Vec::new();
+        if self.server != other.server {
+            changes.push("server");
+        }
+        if self.database != other.database {
+            changes.push("database");
+        }
+        if self.blockchain != other.blockchain {
+            changes.push("blockchain");
+        }
+        if self.logging.format != other.logging.format
+            || self.logging.output != other.logging.output
+            || self.logging.file_path != other.logging.file_path
+            || self.logging.otlp_endpoint != other.logging.otlp_endpoint
+            || self.logging.service_name != other.logging.service_name
+        {
+            changes.push("logging.output");
+        }
+        if self.logging.sampling != other.logging.sampling {
+            changes.push("logging.sampling");
+        }
+        changes
+    }
 }
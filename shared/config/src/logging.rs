@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LoggingConfig {
     pub level: String,
     pub format: LogFormat,
@@ -8,19 +8,51 @@ pub struct LoggingConfig {
     pub file_path: Option<String>,
     pub max_file_size_mb: u64,
     pub max_files: u32,
+    /// OTLP collector endpoint used when `output` is `OpenTelemetry`
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// Service name reported on exported spans
+    #[serde(default)]
+    pub service_name: Option<String>,
+    /// Rules for emitting only a fraction of noisy low-severity events
+    #[serde(default)]
+    pub sampling: Vec<SamplingRule>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Emit only one in `rate` events at `level` or more verbose whose target starts with `target`
+///
+/// Warnings and errors are never sampled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SamplingRule {
+    pub target: String,
+    pub level: String,
+    pub rate: u32,
+}
+
+impl SamplingRule {
+    /// Parse `target:level:rate`, e.g. `vote_store:debug:100`
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().splitn(3, ':');
+        let target = parts.next()?.to_string();
+        let level = parts.next()?.to_string();
+        let rate = parts.next()?.parse().ok()?;
+        Some(Self { target, level, rate })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LogFormat {
     Json,
     Text,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LogOutput {
     Stdout,
     File,
     Both,
+    /// Stdout plus span export over OTLP (requires the `otel` feature of shared-logging)
+    OpenTelemetry,
 }
 
 impl Default for LoggingConfig {
@@ -32,6 +64,9 @@ impl Default for LoggingConfig {
             file_path: None,
             max_file_size_mb: 100,
             max_files: 5,
+            otlp_endpoint: None,
+            service_name: None,
+            sampling: Vec::new(),
         }
     }
 }
@@ -48,6 +83,7 @@ impl LoggingConfig {
             output: match std::env::var("LOG_OUTPUT").unwrap_or_else(|_| "stdout".to_string()).as_str() {
                 "file" => LogOutput::File,
                 "both" => LogOutput::Both,
+                "otel" | "opentelemetry" => LogOutput::OpenTelemetry,
                 _ => LogOutput::Stdout,
             },
             file_path: std::env::var("LOG_FILE_PATH").ok(),
@@ -59,6 +95,11 @@ impl LoggingConfig {
                 .unwrap_or_else(|_| "5".to_string())
                 .parse()
                 .unwrap_or(5),
+            otlp_endpoint: std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok(),
+            service_name: std::env::var("OTEL_SERVICE_NAME").ok(),
+            sampling: std::env::var("LOG_SAMPLING")
+                .map(|v| v.split(',').filter_map(SamplingRule::parse).collect())
+                .unwrap_or_default(),
         }
     }
 }
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Exponential backoff between delivery attempts, shared by outbound webhooks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Wait before the first retry, in seconds
+    pub initial_interval: u64,
+    /// Upper bound on any single wait, in seconds
+    pub max_interval: u64,
+    /// Factor the wait grows by after each retry
+    pub multiplier: f64,
+    /// Fraction of the wait it is randomly spread by, either way
+    pub jitter: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_interval: 1,
+            max_interval: 60,
+            multiplier: 2.0,
+            jitter: 0.1,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Wait before retry number `retry`, counting from 1
+    pub fn delay(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = (self.initial_interval as f64 * self.multiplier.powi(exponent)).min(self.max_interval as f64);
+        let spread = if self.jitter > 0.0 {
+            // clock nanoseconds are random enough to keep retrying clients from lining up
+            let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+            base * self.jitter * (nanos as f64 / 1e9 * 2.0 - 1.0)
+        } else {
+            0.0
+        };
+        Duration::from_secs_f64((base + spread).max(0.0))
+    }
+}
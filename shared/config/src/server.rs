@@ -1,12 +1,271 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::retry::RetryConfig;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub bind: String,
     pub port: u16,
+    /// Origins allowed to make cross-origin requests; empty denies all, `*` allows any
+    #[serde(default)]
     pub cors_origins: Vec<String>,
+    #[serde(default = "default_cors_methods")]
+    pub cors_methods: Vec<String>,
+    #[serde(default = "default_cors_headers")]
+    pub cors_headers: Vec<String>,
+    #[serde(default)]
+    pub cors_allow_credentials: bool,
+    /// How long browsers may cache a preflight response
+    #[serde(default = "default_cors_max_age_seconds")]
+    pub cors_max_age_seconds: u64,
+    /// Largest request body accepted by any route, in bytes
     pub max_request_size: usize,
+    /// Largest commit or reveal body, in bytes
+    #[serde(default = "default_max_submission_request_size")]
+    pub max_submission_request_size: usize,
     pub request_timeout_seconds: u64,
+    /// Responses smaller than this many bytes are sent uncompressed
+    #[serde(default = "default_compression_min_size")]
+    pub compression_min_size: u16,
+    /// Requests taking longer than this many milliseconds are logged as slow
+    #[serde(default = "default_slow_request_threshold_ms")]
+    pub slow_request_threshold_ms: u64,
+    /// Debug logging of request and response bodies; off unless enabled
+    #[serde(default)]
+    pub body_logging: BodyLoggingConfig,
+    /// Best-effort forwarding of vote events to the notification service; off unless a URL is set
+    #[serde(default)]
+    pub notifications: NotificationSinkConfig,
+    /// Signing and retries for the results callbacks of votes created with a completion webhook
+    #[serde(default)]
+    pub completion_webhooks: CompletionWebhookConfig,
+    /// Secret sealing the per-vote keys of votes with encrypted reveals; without it such votes are refused
+    #[serde(default)]
+    pub reveal_key_secret: Option<String>,
+    /// Bearer key required by `/metrics` and the `/admin` routes; while unset they answer 403
+    #[serde(default)]
+    pub admin_api_key: Option<String>,
+}
+
+/// Which request and response bodies are logged, and what is hidden in them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BodyLoggingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path prefixes whose bodies are logged; empty logs every route
+    #[serde(default)]
+    pub routes: Vec<String>,
+    /// JSON field names whose values are replaced before logging, matched case-insensitively
+    #[serde(default = "default_redact_fields")]
+    pub redact_fields: Vec<String>,
+    /// Logged bodies are cut off after this many bytes
+    #[serde(default = "default_max_logged_body_bytes")]
+    pub max_body_bytes: usize,
+}
+
+fn default_redact_fields() -> Vec<String> {
+    ["salt", "password", "new_password", "token", "refresh_token", "secret", "api_key", "authorization"]
+        .iter()
+        .map(|f| f.to_string())
+        .collect()
+}
+
+fn default_max_logged_body_bytes() -> usize {
+    4096
+}
+
+impl Default for BodyLoggingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            routes: Vec::new(),
+            redact_fields: default_redact_fields(),
+            max_body_bytes: default_max_logged_body_bytes(),
+        }
+    }
+}
+
+impl BodyLoggingConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("BODY_LOGGING_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            routes: env_list("BODY_LOGGING_ROUTES").unwrap_or_default(),
+            redact_fields: env_list("BODY_LOGGING_REDACT_FIELDS").unwrap_or_else(default_redact_fields),
+            max_body_bytes: std::env::var("BODY_LOGGING_MAX_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_max_logged_body_bytes),
+        }
+    }
+
+    /// Whether bodies on `path` are logged
+    pub fn applies_to(&self, path: &str) -> bool {
+        self.enabled && (self.routes.is_empty() || self.routes.iter().any(|prefix| path.starts_with(prefix.as_str())))
+    }
+}
+
+/// Where vote events are forwarded and how long to keep trying while the receiver is down
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationSinkConfig {
+    /// Event ingest URL of the notification service; unset disables forwarding
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Events waiting to be sent; events arriving while the queue is full are dropped
+    #[serde(default = "default_notification_queue_capacity")]
+    pub queue_capacity: usize,
+    /// Consecutive failed sends that open the circuit breaker
+    #[serde(default = "default_notification_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long the open breaker drops events before trying the receiver again
+    #[serde(default = "default_notification_cooldown_seconds")]
+    pub cooldown_seconds: u64,
+    /// Per-event send timeout
+    #[serde(default = "default_notification_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_notification_queue_capacity() -> usize {
+    1024
+}
+
+fn default_notification_failure_threshold() -> u32 {
+    5
+}
+
+fn default_notification_cooldown_seconds() -> u64 {
+    30
+}
+
+fn default_notification_timeout_ms() -> u64 {
+    2000
+}
+
+impl Default for NotificationSinkConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            queue_capacity: default_notification_queue_capacity(),
+            failure_threshold: default_notification_failure_threshold(),
+            cooldown_seconds: default_notification_cooldown_seconds(),
+            timeout_ms: default_notification_timeout_ms(),
+        }
+    }
+}
+
+impl NotificationSinkConfig {
+    pub fn from_env() -> Self {
+        Self {
+            url: std::env::var("NOTIFICATION_URL").ok().filter(|v| !v.is_empty()),
+            queue_capacity: std::env::var("NOTIFICATION_QUEUE_CAPACITY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_notification_queue_capacity),
+            failure_threshold: std::env::var("NOTIFICATION_FAILURE_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_notification_failure_threshold),
+            cooldown_seconds: std::env::var("NOTIFICATION_COOLDOWN_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_notification_cooldown_seconds),
+            timeout_ms: std::env::var("NOTIFICATION_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_notification_timeout_ms),
+        }
+    }
+}
+
+/// How completion callbacks are signed and retried
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletionWebhookConfig {
+    /// Signs each callback like the notification service's webhooks; votes asking for a
+    /// completion callback are refused while it is unset
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Hosts that may resolve to private, loopback or link-local addresses, such as an
+    /// integrator inside the same network; every other callback must reach a public address
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    /// Per-attempt request timeout
+    #[serde(default = "default_completion_webhook_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default)]
+    pub retry: RetryConfig,
+}
+
+fn default_completion_webhook_timeout_ms() -> u64 {
+    5000
+}
+
+impl Default for CompletionWebhookConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            allowed_hosts: Vec::new(),
+            timeout_ms: default_completion_webhook_timeout_ms(),
+            retry: RetryConfig::default(),
+        }
+    }
+}
+
+impl CompletionWebhookConfig {
+    pub fn from_env() -> Self {
+        let retry = RetryConfig::default();
+        Self {
+            secret: std::env::var("COMPLETION_WEBHOOK_SECRET").ok().filter(|v| !v.is_empty()),
+            allowed_hosts: std::env::var("COMPLETION_WEBHOOK_ALLOWED_HOSTS")
+                .map(|v| v.split(',').map(|h| h.trim().to_string()).filter(|h| !h.is_empty()).collect())
+                .unwrap_or_default(),
+            timeout_ms: std::env::var("COMPLETION_WEBHOOK_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_completion_webhook_timeout_ms),
+            retry: RetryConfig {
+                max_retries: std::env::var("COMPLETION_WEBHOOK_MAX_RETRIES")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(retry.max_retries),
+                ..retry
+            },
+        }
+    }
+}
+
+fn default_max_submission_request_size() -> usize {
+    64 * 1024
+}
+
+fn default_compression_min_size() -> u16 {
+    1024
+}
+
+fn default_slow_request_threshold_ms() -> u64 {
+    1000
+}
+
+fn default_cors_methods() -> Vec<String> {
+    ["GET", "POST", "PUT", "DELETE"].iter().map(|m| m.to_string()).collect()
+}
+
+fn default_cors_headers() -> Vec<String> {
+    ["content-type", "authorization", "x-correlation-id"].iter().map(|h| h.to_string()).collect()
+}
+
+fn default_cors_max_age_seconds() -> u64 {
+    600
+}
+
+fn env_list(name: &str) -> Option<Vec<String>> {
+    std::env::var(name).ok().map(|value| {
+        value
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    })
 }
 
 impl Default for ServerConfig {
@@ -14,9 +273,21 @@ impl Default for ServerConfig {
         Self {
             bind: "0.0.0.0".to_string(),
             port: 8080,
-            cors_origins: vec!["*".to_string()],
+            cors_origins: Vec::new(),
+            cors_methods: default_cors_methods(),
+            cors_headers: default_cors_headers(),
+            cors_allow_credentials: false,
+            cors_max_age_seconds: default_cors_max_age_seconds(),
             max_request_size: 1024 * 1024, // 1MB
+            max_submission_request_size: default_max_submission_request_size(),
             request_timeout_seconds: 30,
+            compression_min_size: default_compression_min_size(),
+            slow_request_threshold_ms: default_slow_request_threshold_ms(),
+            body_logging: BodyLoggingConfig::default(),
+            notifications: NotificationSinkConfig::default(),
+            completion_webhooks: CompletionWebhookConfig::default(),
+            reveal_key_secret: None,
+            admin_api_key: None,
         }
     }
 }
@@ -30,19 +301,67 @@ impl ServerConfig {
                 .unwrap_or_else(|_| "8080".to_string())
                 .parse()
                 .unwrap_or(8080),
-            cors_origins: std::env::var("CORS_ORIGINS")
-                .unwrap_or_else(|_| "*".to_string())
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .collect(),
+            cors_origins: env_list("CORS_ORIGINS").unwrap_or_default(),
+            cors_methods: env_list("CORS_METHODS").unwrap_or_else(default_cors_methods),
+            cors_headers: env_list("CORS_HEADERS").unwrap_or_else(default_cors_headers),
+            cors_allow_credentials: std::env::var("CORS_ALLOW_CREDENTIALS")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            cors_max_age_seconds: std::env::var("CORS_MAX_AGE_SECONDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_cors_max_age_seconds),
             max_request_size: std::env::var("MAX_REQUEST_SIZE")
                 .unwrap_or_else(|_| "1048576".to_string())
                 .parse()
                 .unwrap_or(1024 * 1024),
+            max_submission_request_size: std::env::var("MAX_SUBMISSION_REQUEST_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_max_submission_request_size),
             request_timeout_seconds: std::env::var("REQUEST_TIMEOUT_SECONDS")
                 .unwrap_or_else(|_| "30".to_string())
                 .parse()
                 .unwrap_or(30),
+            compression_min_size: std::env::var("COMPRESSION_MIN_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_compression_min_size),
+            slow_request_threshold_ms: std::env::var("SLOW_REQUEST_THRESHOLD_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_slow_request_threshold_ms),
+            body_logging: BodyLoggingConfig::from_env(),
+            notifications: NotificationSinkConfig::from_env(),
+            completion_webhooks: CompletionWebhookConfig::from_env(),
+            reveal_key_secret: std::env::var("REVEAL_KEY_SECRET").ok().filter(|s| !s.is_empty()),
+            admin_api_key: std::env::var("ADMIN_API_KEY").ok().filter(|s| !s.is_empty()),
+        }
+    }
+
+    /// Reject CORS settings a browser would refuse or that leak credentials to any origin
+    pub fn validate_cors(&self) -> Result<(), String> {
+        let any_origin = self.cors_origins.iter().any(|o| o == "*");
+        if any_origin && self.cors_allow_credentials {
+            return Err("server.cors_origins cannot contain \"*\" when cors_allow_credentials is true".to_string());
+        }
+        if any_origin && self.cors_origins.len() > 1 {
+            return Err("server.cors_origins must not mix \"*\" with explicit origins".to_string());
+        }
+        if let Some(origin) = self
+            .cors_origins
+            .iter()
+            .find(|o| *o != "*" && !(o.starts_with("http://") || o.starts_with("https://")))
+        {
+            return Err(format!("server.cors_origins entry {:?} must be \"*\" or an http(s) origin", origin));
+        }
+        if let Some(method) = self
+            .cors_methods
+            .iter()
+            .find(|m| m.is_empty() || !m.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            return Err(format!("server.cors_methods entry {:?} is not an HTTP method", method));
         }
+        Ok(())
     }
 }
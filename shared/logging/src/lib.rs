@@ -1,11 +1,29 @@
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod metrics;
+pub mod sampling;
+pub mod shutdown;
+
+pub use metrics::{metrics, Metrics};
+pub use sampling::SamplingLayer;
+pub use shutdown::{ShutdownReport, ShutdownSequence, ShutdownStage, StageReport};
+
 use shared_config::LoggingConfig;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, fmt};
+use std::sync::OnceLock;
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry, fmt};
+
+/// Handle to the live filter, set once by `init_logging`
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
 
 /// Initialize logging with the given configuration
 pub fn init_logging(config: &LoggingConfig) -> Result<(), Box<dyn std::error::Error>> {
-    let env_filter = EnvFilter::new(&config.level);
+    let (env_filter, handle) = reload::Layer::new(EnvFilter::new(&config.level));
+    // A second call fails in `init()` below, so the first handle stays authoritative
+    let _ = FILTER_HANDLE.set(handle);
     
-    let registry = tracing_subscriber::registry().with(env_filter);
+    let registry = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(SamplingLayer::new(&config.sampling)?);
     
     match config.output {
         shared_config::LogOutput::Stdout => {
@@ -93,6 +111,34 @@ pub fn init_logging(config: &LoggingConfig) -> Result<(), Box<dyn std::error::Er
                 registry.init();
             }
         }
+        shared_config::LogOutput::OpenTelemetry => {
+            #[cfg(feature = "otel")]
+            {
+                let provider = otel::otlp_tracer_provider(config)?;
+                let registry = registry.with(otel::layer(&provider));
+                otel::install(provider);
+
+                match config.format {
+                    shared_config::LogFormat::Json => {
+                        let json_layer = fmt::layer()
+                            .json()
+                            .with_target(false)
+                            .with_thread_ids(true)
+                            .with_thread_names(true);
+                        registry.with(json_layer).init();
+                    }
+                    shared_config::LogFormat::Text => {
+                        let fmt_layer = fmt::layer()
+                            .with_target(false)
+                            .with_thread_ids(true)
+                            .with_thread_names(true);
+                        registry.with(fmt_layer).init();
+                    }
+                }
+            }
+            #[cfg(not(feature = "otel"))]
+            return Err("OpenTelemetry output requires the `otel` feature of shared-logging".into());
+        }
     }
     
     Ok(())
@@ -104,8 +150,86 @@ pub fn init_default_logging() -> Result<(), Box<dyn std::error::Error>> {
     init_logging(&config)
 }
 
+/// Initialize text logging into `make_writer` instead of stdout or a file
+pub fn init_logging_with_writer<W>(level: &str, make_writer: W) -> Result<(), Box<dyn std::error::Error>>
+where
+    W: for<'a> fmt::MakeWriter<'a> + Send + Sync + 'static,
+{
+    let (env_filter, handle) = reload::Layer::new(EnvFilter::new(level));
+    let _ = FILTER_HANDLE.set(handle);
+
+    let fmt_layer = fmt::layer()
+        .with_writer(make_writer)
+        .with_ansi(false)
+        .with_target(false);
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(fmt_layer)
+        .try_init()?;
+    Ok(())
+}
+
 /// Initialize logging from environment variables
 pub fn init_logging_from_env() -> Result<(), Box<dyn std::error::Error>> {
     let config = LoggingConfig::from_env();
     init_logging(&config)
 }
+
+/// Root span for a unit of work, tagged with its correlation ID
+///
+/// The ID is recorded as a span field, so it shows up in fmt output and as an
+/// attribute on spans exported over OpenTelemetry.
+pub fn correlation_span(correlation_id: &str) -> tracing::Span {
+    tracing::info_span!("request", correlation_id = %correlation_id)
+}
+
+/// Levels accepted by `set_log_level`
+const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "off"];
+
+/// Change the global log level at runtime
+///
+/// Unlike `reload_log_filter` this only accepts a bare level name, which is
+/// what the admin endpoint exposes.
+pub fn set_log_level(level: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let level = level.trim().to_ascii_lowercase();
+    if !LOG_LEVELS.contains(&level.as_str()) {
+        return Err(format!(
+            "invalid log level '{}', expected one of: {}",
+            level,
+            LOG_LEVELS.join(", ")
+        )
+        .into());
+    }
+    reload_log_filter(&level)
+}
+
+/// Replace the active filter directives (e.g. `"info,vote_store=debug"`) without restarting
+pub fn reload_log_filter(directives: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or("logging has not been initialized")?;
+    let filter = EnvFilter::try_new(directives)?;
+    handle.reload(filter)?;
+    tracing::info!("Log filter reloaded: {}", directives);
+    Ok(())
+}
+
+/// Spawn a task that calls `on_reload` every time the process receives SIGHUP
+///
+/// The signal handler is installed before this returns, so a SIGHUP sent
+/// afterwards will never fall through to the default (terminate) action.
+#[cfg(unix)]
+pub fn spawn_sighup_handler<F>(mut on_reload: F) -> std::io::Result<tokio::task::JoinHandle<()>>
+where
+    F: FnMut() + Send + 'static,
+{
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = signal(SignalKind::hangup())?;
+    Ok(tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            tracing::info!("SIGHUP received, reloading configuration");
+            on_reload();
+        }
+    }))
+}
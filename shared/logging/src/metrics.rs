@@ -0,0 +1,219 @@
+//! Prometheus metrics for request and query timings, notification delivery, WebSocket keepalive
+//! disconnects and retention cleanup
+//!
+//! Both APIs and the SQL stores record into one process-wide registry, which the
+//! services expose in the Prometheus text format.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+use tracing::warn;
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// Timing metrics shared by the services and stores
+pub struct Metrics {
+    registry: Registry,
+    request_duration: HistogramVec,
+    slow_requests: IntCounterVec,
+    query_duration: HistogramVec,
+    slow_queries: IntCounterVec,
+    notifications_dropped: IntCounterVec,
+    notifications_sent: IntCounterVec,
+    notifications_failed: IntCounterVec,
+    notifications_retried: IntCounterVec,
+    notifications_dead_lettered: IntCounterVec,
+    notification_delivery_duration: HistogramVec,
+    keepalive_disconnects: IntCounterVec,
+    retention_votes: IntCounterVec,
+}
+
+/// The process-wide metrics registry
+pub fn metrics() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let request_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request duration in seconds"),
+            &["service", "method", "route", "status"],
+        )
+        .expect("valid request histogram");
+        let slow_requests = IntCounterVec::new(
+            Opts::new("http_slow_requests_total", "HTTP requests slower than the configured threshold"),
+            &["service", "method", "route"],
+        )
+        .expect("valid slow request counter");
+        let query_duration = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "Database query duration in seconds"),
+            &["backend", "operation"],
+        )
+        .expect("valid query histogram");
+        let slow_queries = IntCounterVec::new(
+            Opts::new("db_slow_queries_total", "Database queries slower than the configured threshold"),
+            &["backend", "operation"],
+        )
+        .expect("valid slow query counter");
+        let notifications_dropped = IntCounterVec::new(
+            Opts::new("notifications_dropped_total", "Notifications given up on without being delivered"),
+            &["service", "reason"],
+        )
+        .expect("valid dropped notification counter");
+        let delivery_counter = |name: &str, help: &str| {
+            IntCounterVec::new(Opts::new(name, help), &["provider", "notification_type"])
+                .expect("valid notification delivery counter")
+        };
+        let notifications_sent = delivery_counter("notifications_sent_total", "Notifications delivered by a provider");
+        let notifications_failed = delivery_counter("notifications_failed_total", "Notifications a provider failed to deliver");
+        let notifications_retried = delivery_counter("notifications_retried_total", "Delivery attempts beyond the first");
+        let notifications_dead_lettered =
+            delivery_counter("notifications_dead_lettered_total", "Failed notifications moved to the dead-letter queue");
+        let notification_delivery_duration = HistogramVec::new(
+            HistogramOpts::new("notification_delivery_duration_seconds", "Notification delivery duration in seconds, retries included"),
+            &["provider", "notification_type"],
+        )
+        .expect("valid notification delivery histogram");
+        let keepalive_disconnects = IntCounterVec::new(
+            Opts::new("websocket_keepalive_disconnects_total", "WebSocket connections closed by the server keepalive"),
+            &["service", "reason"],
+        )
+        .expect("valid keepalive disconnect counter");
+        let retention_votes = IntCounterVec::new(
+            Opts::new("retention_votes_total", "Votes considered by the retention cleanup job, by outcome"),
+            &["outcome"],
+        )
+        .expect("valid retention counter");
+
+        for collector in [&request_duration, &query_duration, &notification_delivery_duration] {
+            registry.register(Box::new(collector.clone())).expect("metric registered once");
+        }
+        for collector in [
+            &slow_requests,
+            &slow_queries,
+            &notifications_dropped,
+            &notifications_sent,
+            &notifications_failed,
+            &notifications_retried,
+            &notifications_dead_lettered,
+            &keepalive_disconnects,
+            &retention_votes,
+        ] {
+            registry.register(Box::new(collector.clone())).expect("metric registered once");
+        }
+
+        Self {
+            registry,
+            request_duration,
+            slow_requests,
+            query_duration,
+            slow_queries,
+            notifications_dropped,
+            notifications_sent,
+            notifications_failed,
+            notifications_retried,
+            notifications_dead_lettered,
+            notification_delivery_duration,
+            keepalive_disconnects,
+            retention_votes,
+        }
+    }
+
+    /// Record a finished request, warning when it took longer than `threshold`
+    pub fn record_request(
+        &self,
+        service: &str,
+        method: &str,
+        route: &str,
+        status: u16,
+        elapsed: Duration,
+        threshold: Duration,
+    ) {
+        self.request_duration
+            .with_label_values(&[service, method, route, &status.to_string()])
+            .observe(elapsed.as_secs_f64());
+        if elapsed > threshold {
+            self.slow_requests.with_label_values(&[service, method, route]).inc();
+            warn!(
+                "Slow request: {} {} took {:?} (threshold {:?}, status {})",
+                method, route, elapsed, threshold, status
+            );
+        }
+    }
+
+    /// Record a finished query, warning with its SQL when it took longer than `threshold`
+    pub fn record_query(&self, backend: &str, sql: &str, params: usize, elapsed: Duration, threshold: Duration) {
+        let operation = sql
+            .split_whitespace()
+            .next()
+            .unwrap_or("unknown")
+            .to_ascii_uppercase();
+        self.query_duration
+            .with_label_values(&[backend, &operation])
+            .observe(elapsed.as_secs_f64());
+        if elapsed > threshold {
+            self.slow_queries.with_label_values(&[backend, &operation]).inc();
+            warn!(
+                "Slow {} query took {:?} (threshold {:?}) with {} bound params: {}",
+                backend,
+                elapsed,
+                threshold,
+                params,
+                sql.split_whitespace().collect::<Vec<_>>().join(" ")
+            );
+        }
+    }
+
+    /// Count a notification dropped for `reason` instead of being delivered
+    pub fn record_notification_drop(&self, service: &str, reason: &str) {
+        self.notifications_dropped.with_label_values(&[service, reason]).inc();
+    }
+
+    /// Record one delivery through `provider`, with the attempts it took beyond the first
+    pub fn record_notification_delivery(
+        &self,
+        provider: &str,
+        notification_type: &str,
+        delivered: bool,
+        retries: u64,
+        elapsed: Duration,
+    ) {
+        let labels = [provider, notification_type];
+        let outcome = if delivered { &self.notifications_sent } else { &self.notifications_failed };
+        outcome.with_label_values(&labels).inc();
+        if retries > 0 {
+            self.notifications_retried.with_label_values(&labels).inc_by(retries);
+        }
+        self.notification_delivery_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Count a failed notification moved to the dead-letter queue
+    pub fn record_notification_dead_letter(&self, provider: &str, notification_type: &str) {
+        self.notifications_dead_lettered.with_label_values(&[provider, notification_type]).inc();
+    }
+
+    /// Count a WebSocket connection the keepalive closed for `reason` (missed pongs or idleness)
+    pub fn record_keepalive_disconnect(&self, service: &str, reason: &str) {
+        self.keepalive_disconnects.with_label_values(&[service, reason]).inc();
+    }
+
+    /// Count the votes one retention run cleaned, kept under legal hold, or failed to clean
+    pub fn record_retention_run(&self, cleaned: usize, held: usize, failed: usize) {
+        for (outcome, count) in [("cleaned", cleaned), ("held", held), ("failed", failed)] {
+            self.retention_votes.with_label_values(&[outcome]).inc_by(count as u64);
+        }
+    }
+
+    /// Everything recorded so far, in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding into a Vec cannot fail");
+        String::from_utf8(buffer).expect("Prometheus text format is UTF-8")
+    }
+}
@@ -0,0 +1,61 @@
+//! OpenTelemetry span export, enabled with the `otel` feature
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use opentelemetry_sdk::Resource;
+use shared_config::LoggingConfig;
+use std::sync::OnceLock;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// OTLP/HTTP collector endpoint used when none is configured
+const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4318/v1/traces";
+
+/// Service name used when none is configured
+const DEFAULT_SERVICE_NAME: &str = "decentralized-decision-vote";
+
+/// Provider installed by `init_logging`, kept so `shutdown` can flush it
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Build a tracer provider that batches spans to the configured OTLP endpoint
+pub fn otlp_tracer_provider(config: &LoggingConfig) -> Result<SdkTracerProvider, Box<dyn std::error::Error>> {
+    let endpoint = config.otlp_endpoint.as_deref().unwrap_or(DEFAULT_OTLP_ENDPOINT);
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()?;
+
+    let service_name = config
+        .service_name
+        .clone()
+        .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+    let resource = Resource::builder().with_service_name(service_name).build();
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build())
+}
+
+/// Tracing layer that records spans, including their fields, into `provider`
+pub fn layer<S>(provider: &SdkTracerProvider) -> OpenTelemetryLayer<S, Tracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+}
+
+pub(crate) fn install(provider: SdkTracerProvider) {
+    let _ = TRACER_PROVIDER.set(provider);
+}
+
+/// Flush pending spans and stop the exporter; call once before the process exits
+pub fn shutdown() {
+    if let Some(provider) = TRACER_PROVIDER.get() {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Failed to shut down OpenTelemetry tracer provider: {}", e);
+        }
+    }
+}
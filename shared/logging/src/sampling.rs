@@ -0,0 +1,73 @@
+//! Sampling of high-volume, low-severity events
+
+use shared_config::SamplingRule;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tracing::callsite::Identifier;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+struct CompiledRule {
+    target: String,
+    level: Level,
+    rate: u64,
+}
+
+/// Layer that lets through only one in N events matching a sampling rule
+///
+/// Counters are kept per callsite, so two different noisy log lines are each
+/// sampled independently. Warnings and errors always pass.
+pub struct SamplingLayer {
+    rules: Vec<CompiledRule>,
+    counters: Mutex<HashMap<Identifier, u64>>,
+}
+
+impl SamplingLayer {
+    pub fn new(rules: &[SamplingRule]) -> Result<Self, Box<dyn std::error::Error>> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let level = Level::from_str(&rule.level)
+                    .map_err(|_| format!("invalid sampling level '{}'", rule.level))?;
+                if rule.rate == 0 {
+                    return Err(format!("sampling rate for '{}' must be at least 1", rule.target));
+                }
+                Ok(CompiledRule {
+                    target: rule.target.clone(),
+                    level,
+                    rate: u64::from(rule.rate),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self {
+            rules,
+            counters: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SamplingLayer {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let metadata = event.metadata();
+        // Levels compare by verbosity, so this matches WARN and ERROR
+        if *metadata.level() <= Level::WARN {
+            return true;
+        }
+
+        let rule = self
+            .rules
+            .iter()
+            .find(|rule| metadata.target().starts_with(&rule.target) && *metadata.level() >= rule.level);
+        let Some(rule) = rule else {
+            return true;
+        };
+
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        let count = counters.entry(metadata.callsite()).or_insert(0);
+        let emit = count.is_multiple_of(rule.rate);
+        *count += 1;
+        emit
+    }
+}
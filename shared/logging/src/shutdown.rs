@@ -0,0 +1,168 @@
+//! Ordered shutdown of a service's subsystems
+//!
+//! Steps are grouped into stages that run one after another: stop accepting new work, drain
+//! what is in flight, flush queues, then close connection pools. Steps within a stage run
+//! concurrently under the stage's timeout; steps still running when it expires are cancelled
+//! and logged with whatever work they report as remaining, and the next stage starts anyway.
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+/// Shutdown stages, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShutdownStage {
+    /// Stop listeners and intake so no new work arrives
+    StopAccepting,
+    /// Let requests and tasks already running finish
+    DrainInFlight,
+    /// Hand queued notifications and events to their destination
+    FlushQueues,
+    /// Close database pools and other connections
+    ClosePools,
+}
+
+type StepFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type RemainingProbe = Box<dyn Fn() -> usize + Send + Sync>;
+
+struct Step {
+    name: String,
+    run: StepFuture,
+    remaining: Option<RemainingProbe>,
+}
+
+/// What happened in one stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageReport {
+    pub stage: ShutdownStage,
+    /// Steps that finished, in completion order
+    pub completed: Vec<String>,
+    /// Steps cancelled at the stage timeout
+    pub timed_out: Vec<String>,
+    /// Work left behind by timed-out steps that can report it
+    pub remaining: Vec<(String, usize)>,
+    pub elapsed: Duration,
+}
+
+/// What happened in every stage that had steps, in stage order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShutdownReport {
+    pub stages: Vec<StageReport>,
+}
+
+impl ShutdownReport {
+    /// Whether every step finished before its stage timed out
+    pub fn is_clean(&self) -> bool {
+        self.stages.iter().all(|s| s.timed_out.is_empty())
+    }
+}
+
+/// Steps to run at shutdown, grouped by stage
+pub struct ShutdownSequence {
+    default_timeout: Duration,
+    timeouts: HashMap<ShutdownStage, Duration>,
+    steps: BTreeMap<ShutdownStage, Vec<Step>>,
+}
+
+impl ShutdownSequence {
+    /// Every stage gets `default_timeout` unless overridden with `with_timeout`
+    pub fn new(default_timeout: Duration) -> Self {
+        Self { default_timeout, timeouts: HashMap::new(), steps: BTreeMap::new() }
+    }
+
+    pub fn with_timeout(mut self, stage: ShutdownStage, timeout: Duration) -> Self {
+        self.timeouts.insert(stage, timeout);
+        self
+    }
+
+    /// Run `step` during `stage`
+    pub fn add<F>(&mut self, stage: ShutdownStage, name: impl Into<String>, step: F) -> &mut Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.push(stage, Step { name: name.into(), run: Box::pin(step), remaining: None })
+    }
+
+    /// Run `step` during `stage`; if the stage times out first, `remaining` reports how much
+    /// work (queued items, open requests) the step left behind
+    pub fn add_with_remaining<F, R>(&mut self, stage: ShutdownStage, name: impl Into<String>, step: F, remaining: R) -> &mut Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+        R: Fn() -> usize + Send + Sync + 'static,
+    {
+        self.push(stage, Step { name: name.into(), run: Box::pin(step), remaining: Some(Box::new(remaining)) })
+    }
+
+    fn push(&mut self, stage: ShutdownStage, step: Step) -> &mut Self {
+        self.steps.entry(stage).or_default().push(step);
+        self
+    }
+
+    /// Run every stage in order; must be called inside a Tokio runtime
+    pub async fn run(self) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+        for (stage, steps) in self.steps {
+            let timeout = self.timeouts.get(&stage).copied().unwrap_or(self.default_timeout);
+            report.stages.push(run_stage(stage, steps, timeout).await);
+        }
+        report
+    }
+}
+
+async fn run_stage(stage: ShutdownStage, steps: Vec<Step>, timeout: Duration) -> StageReport {
+    let started = Instant::now();
+    info!("Shutdown stage {:?}: {} step(s), timeout {:?}", stage, steps.len(), timeout);
+
+    let mut names = Vec::new();
+    let mut probes = Vec::new();
+    let mut running = JoinSet::new();
+    for (index, step) in steps.into_iter().enumerate() {
+        names.push(step.name);
+        probes.push(step.remaining);
+        let run = step.run;
+        running.spawn(async move {
+            run.await;
+            index
+        });
+    }
+
+    let mut finished = vec![false; names.len()];
+    let mut completed = Vec::new();
+    let deadline = tokio::time::Instant::from_std(started + timeout);
+    while let Ok(Some(joined)) = tokio::time::timeout_at(deadline, running.join_next()).await {
+        match joined {
+            Ok(index) => {
+                finished[index] = true;
+                completed.push(names[index].clone());
+            }
+            // The panicked step is reported as unfinished below
+            Err(e) => warn!("Shutdown step panicked during {:?}: {}", stage, e),
+        }
+    }
+    running.abort_all();
+
+    let mut timed_out = Vec::new();
+    let mut remaining = Vec::new();
+    for (index, name) in names.into_iter().enumerate() {
+        if finished[index] {
+            continue;
+        }
+        match probes[index].as_ref().map(|probe| probe()) {
+            Some(left) => {
+                warn!("Shutdown step {} did not finish {:?} within {:?}; {} item(s) remain", name, stage, timeout, left);
+                remaining.push((name.clone(), left));
+            }
+            None => warn!("Shutdown step {} did not finish {:?} within {:?}", name, stage, timeout),
+        }
+        timed_out.push(name);
+    }
+
+    if timed_out.is_empty() {
+        info!("Shutdown stage {:?} finished in {:?}", stage, started.elapsed());
+    }
+    StageReport { stage, completed, timed_out, remaining, elapsed: started.elapsed() }
+}
@@ -0,0 +1,40 @@
+use shared_logging::{init_logging_with_writer, set_log_level};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct TestWriter(Arc<Mutex<Vec<u8>>>);
+
+impl TestWriter {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for TestWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_set_log_level_changes_recorded_events() {
+    let writer = TestWriter::default();
+    let make_writer = writer.clone();
+    init_logging_with_writer("info", move || make_writer.clone()).unwrap();
+
+    tracing::debug!("hidden before reload");
+    assert!(!writer.contents().contains("hidden before reload"));
+
+    set_log_level("debug").unwrap();
+    tracing::debug!("visible after reload");
+    assert!(writer.contents().contains("visible after reload"));
+
+    assert!(set_log_level("verbose").is_err());
+    assert!(set_log_level("").is_err());
+}
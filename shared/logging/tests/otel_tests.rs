@@ -0,0 +1,32 @@
+#![cfg(feature = "otel")]
+
+use opentelemetry::Value;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+use shared_logging::{correlation_span, otel};
+use tracing_subscriber::layer::SubscriberExt;
+
+#[test]
+fn test_span_is_exported_with_correlation_id() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry().with(otel::layer(&provider));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = correlation_span("corr-123");
+        let _guard = span.enter();
+        tracing::info!("handling request");
+    });
+    provider.force_flush().unwrap();
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].name, "request");
+    let correlation_id = spans[0]
+        .attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == "correlation_id")
+        .map(|kv| kv.value.clone());
+    assert_eq!(correlation_id, Some(Value::from("corr-123")));
+}
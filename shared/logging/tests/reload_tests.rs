@@ -0,0 +1,38 @@
+#![cfg(unix)]
+
+use shared_config::LoggingConfig;
+use shared_logging::{init_logging, reload_log_filter, spawn_sighup_handler};
+use std::time::Duration;
+use tracing::Level;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_sighup_updates_live_log_filter() {
+    let config = LoggingConfig {
+        level: "info".to_string(),
+        ..LoggingConfig::default()
+    };
+    init_logging(&config).unwrap();
+    assert!(!tracing::enabled!(Level::DEBUG));
+
+    let _handler = spawn_sighup_handler(|| {
+        reload_log_filter("debug").unwrap();
+    })
+    .unwrap();
+
+    let status = std::process::Command::new("kill")
+        .arg("-HUP")
+        .arg(std::process::id().to_string())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut reloaded = false;
+    for _ in 0..50 {
+        if tracing::enabled!(Level::DEBUG) {
+            reloaded = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert!(reloaded, "debug level was not enabled after SIGHUP");
+}
@@ -0,0 +1,60 @@
+use shared_config::SamplingRule;
+use shared_logging::SamplingLayer;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+#[derive(Clone, Default)]
+struct CountingLayer {
+    debug: Arc<AtomicUsize>,
+    error: Arc<AtomicUsize>,
+}
+
+impl<S: Subscriber> Layer<S> for CountingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        match *event.metadata().level() {
+            Level::DEBUG => self.debug.fetch_add(1, Ordering::SeqCst),
+            Level::ERROR => self.error.fetch_add(1, Ordering::SeqCst),
+            _ => 0,
+        };
+    }
+}
+
+#[test]
+fn test_sampling_emits_one_in_n_and_keeps_errors() {
+    let rule = SamplingRule {
+        target: "sampling_tests".to_string(),
+        level: "debug".to_string(),
+        rate: 10,
+    };
+    let counter = CountingLayer::default();
+    let subscriber = tracing_subscriber::registry()
+        .with(SamplingLayer::new(&[rule]).unwrap())
+        .with(counter.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        for i in 0..1000 {
+            tracing::debug!("Getting vote: {}", i);
+            tracing::error!("Failed to get vote: {}", i);
+        }
+    });
+
+    let debug = counter.debug.load(Ordering::SeqCst);
+    assert!((90..=110).contains(&debug), "expected about 100 debug events, got {}", debug);
+    assert_eq!(counter.error.load(Ordering::SeqCst), 1000);
+}
+
+#[test]
+fn test_sampling_rule_validation() {
+    let rule = |level: &str, rate| SamplingRule {
+        target: "vote_store".to_string(),
+        level: level.to_string(),
+        rate,
+    };
+    assert!(SamplingLayer::new(&[rule("debug", 100)]).is_ok());
+    assert!(SamplingLayer::new(&[rule("loud", 100)]).is_err());
+    assert!(SamplingLayer::new(&[rule("debug", 0)]).is_err());
+    assert_eq!(SamplingRule::parse("vote_store:debug:100"), Some(rule("debug", 100)));
+    assert_eq!(SamplingRule::parse("vote_store"), None);
+}
@@ -0,0 +1,87 @@
+use shared_logging::{ShutdownSequence, ShutdownStage};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Stub subsystem that records when each of its shutdown steps ran
+#[derive(Clone)]
+struct Stub {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Stub {
+    fn step(&self, action: &'static str, delay: Duration) -> impl std::future::Future<Output = ()> + Send + 'static {
+        let stub = self.clone();
+        async move {
+            tokio::time::sleep(delay).await;
+            stub.log.lock().unwrap().push(format!("{}:{}", stub.name, action));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_stages_run_in_order_regardless_of_registration_order() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let http = Stub { name: "http", log: log.clone() };
+    let notifications = Stub { name: "notifications", log: log.clone() };
+    let events = Stub { name: "events", log: log.clone() };
+    let db = Stub { name: "db", log: log.clone() };
+
+    let mut sequence = ShutdownSequence::new(Duration::from_secs(1));
+    // Registered back to front; a slow earlier stage must still finish before later ones start
+    sequence
+        .add(ShutdownStage::ClosePools, "db", db.step("close", Duration::ZERO))
+        .add(ShutdownStage::FlushQueues, "notifications", notifications.step("flush", Duration::from_millis(30)))
+        .add(ShutdownStage::FlushQueues, "events", events.step("flush", Duration::ZERO))
+        .add(ShutdownStage::DrainInFlight, "http", http.step("drain", Duration::from_millis(50)))
+        .add(ShutdownStage::StopAccepting, "http", http.step("stop", Duration::ZERO));
+    let report = sequence.run().await;
+
+    assert!(report.is_clean());
+    let stages: Vec<ShutdownStage> = report.stages.iter().map(|s| s.stage).collect();
+    assert_eq!(
+        stages,
+        [ShutdownStage::StopAccepting, ShutdownStage::DrainInFlight, ShutdownStage::FlushQueues, ShutdownStage::ClosePools]
+    );
+    // Steps in one stage run concurrently, so the quicker flush finishes first
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["http:stop", "http:drain", "events:flush", "notifications:flush", "db:close"]
+    );
+}
+
+#[tokio::test]
+async fn test_timed_out_stage_reports_remaining_work_and_later_stages_still_run() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let db = Stub { name: "db", log: log.clone() };
+    let queued = Arc::new(AtomicUsize::new(7));
+
+    let mut sequence = ShutdownSequence::new(Duration::from_secs(1))
+        .with_timeout(ShutdownStage::FlushQueues, Duration::from_millis(50));
+    let flushing = queued.clone();
+    sequence
+        .add_with_remaining(
+            ShutdownStage::FlushQueues,
+            "notifications",
+            async move {
+                // Makes some progress, then the receiver stops answering
+                flushing.fetch_sub(3, Ordering::SeqCst);
+                std::future::pending::<()>().await;
+            },
+            move || queued.load(Ordering::SeqCst),
+        )
+        .add(ShutdownStage::FlushQueues, "hung", std::future::pending::<()>())
+        .add(ShutdownStage::ClosePools, "db", db.step("close", Duration::ZERO));
+    let report = sequence.run().await;
+
+    assert!(!report.is_clean());
+    let flush = &report.stages[0];
+    assert_eq!(flush.stage, ShutdownStage::FlushQueues);
+    assert_eq!(flush.timed_out, ["notifications", "hung"]);
+    assert_eq!(flush.remaining, [("notifications".to_string(), 4)]);
+    assert!(flush.elapsed < Duration::from_millis(500));
+
+    assert_eq!(report.stages[1].completed, ["db"]);
+    assert_eq!(*log.lock().unwrap(), ["db:close"]);
+}
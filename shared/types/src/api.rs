@@ -6,6 +6,9 @@ use crate::vote::*;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateVoteRequest {
     pub config: VoteConfig,
+    /// Retries carrying the same id return the vote created by the first request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_request_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,9 +24,131 @@ pub struct GetVoteResponse {
     pub success: bool,
 }
 
+/// Query for `GET /votes/:id/wait`
+///
+/// `timeout` accepts seconds (`30`, `30s`) or milliseconds (`500ms`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WaitQuery {
+    #[serde(default)]
+    pub since_version: u64,
+    #[serde(default)]
+    pub timeout: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitVoteResponse {
+    pub vote: Vote,
+    /// Pass back as `since_version` to wait for the next change
+    pub version: u64,
+    pub success: bool,
+}
+
+// Pagination
+
+/// Response shape shared by every list endpoint.
+///
+/// `page` is zero-based. `next_cursor` is the offset of the first item on the
+/// next page, or `None` on the last page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u32,
+    pub total_pages: u32,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Paginated<T> {
+    pub fn new(items: Vec<T>, total: u64, page: u32, page_size: u32) -> Self {
+        let total_pages = if page_size == 0 {
+            0
+        } else {
+            total.div_ceil(page_size as u64) as u32
+        };
+        let next_offset = (page as u64 + 1) * page_size as u64;
+        let next_cursor = (page_size > 0 && next_offset < total).then(|| next_offset.to_string());
+        Self {
+            items,
+            total,
+            page,
+            page_size,
+            total_pages,
+            next_cursor,
+        }
+    }
+
+    /// Slice one page out of a fully materialized list
+    pub fn from_all(all: Vec<T>, page: u32, page_size: u32) -> Self {
+        let total = all.len() as u64;
+        let start = (page as usize).saturating_mul(page_size as usize);
+        let items = all.into_iter().skip(start).take(page_size as usize).collect();
+        Self::new(items, total, page, page_size)
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paginated<U> {
+        Paginated {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            page_size: self.page_size,
+            total_pages: self.total_pages,
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+impl<T> From<Page<T>> for Paginated<T> {
+    fn from(page: Page<T>) -> Self {
+        let mut paginated = Paginated::new(page.items, page.total as u64, page.page, page.page_size);
+        paginated.total_pages = page.total_pages;
+        paginated
+    }
+}
+
+impl<T> From<Paginated<T>> for Page<T> {
+    fn from(paginated: Paginated<T>) -> Self {
+        Page {
+            items: paginated.items,
+            total: paginated.total as u32,
+            page: paginated.page,
+            page_size: paginated.page_size,
+            total_pages: paginated.total_pages,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListVotesResponse {
-    pub votes: Page<Vote>,
+    pub votes: Paginated<Vote>,
+    pub success: bool,
+}
+
+/// Page of a voter's activity; defaults to the first 20 votes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoterActivityQuery {
+    #[serde(default)]
+    pub page: u32,
+    #[serde(default = "default_activity_page_size")]
+    pub page_size: u32,
+}
+
+fn default_activity_page_size() -> u32 {
+    20
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoterActivityResponse {
+    pub voter: String,
+    pub votes: Paginated<VoteParticipation>,
+    pub success: bool,
+}
+
+/// A vote's status changes, result computations and cancellation, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteHistoryResponse {
+    pub vote_id: String,
+    pub entries: Vec<VoteHistoryEntry>,
     pub success: bool,
 }
 
@@ -39,6 +164,20 @@ pub struct VerifyResultsResponse {
     pub success: bool,
 }
 
+// Admin types
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetLogLevelRequest {
+    pub level: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetLogLevelResponse {
+    pub level: String,
+    pub success: bool,
+    pub message: String,
+}
+
 // WebSocket message types
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
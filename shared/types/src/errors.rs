@@ -1,6 +1,10 @@
+use serde::Serialize;
+use serde_json::{json, Value};
 use thiserror::Error;
 use shared_utils::validation::ValidationError;
 
+use crate::ids::IdError;
+
 #[derive(Error, Debug)]
 pub enum VoteError {
     #[error("Vote not found: {id}")]
@@ -21,6 +25,9 @@ pub enum VoteError {
     #[error("Vote has already ended")]
     VoteEnded,
     
+    #[error("Vote has been cancelled")]
+    VoteCancelled,
+    
     #[error("Invalid commitment: {message}")]
     InvalidCommitment { message: String },
     
@@ -41,38 +48,126 @@ pub enum VoteError {
     
     #[error("Validation error: {0}")]
     ValidationError(#[from] ValidationError),
+
+    #[error("Invalid id: {0}")]
+    InvalidId(#[from] IdError),
 }
 
-#[derive(Error, Debug)]
-pub enum ApiError {
-    #[error("Bad request: {message}")]
-    BadRequest { message: String },
-    
-    #[error("Unauthorized: {message}")]
-    Unauthorized { message: String },
-    
-    #[error("Forbidden: {message}")]
-    Forbidden { message: String },
-    
-    #[error("Not found: {message}")]
-    NotFound { message: String },
-    
-    #[error("Internal server error: {message}")]
-    InternalError { message: String },
-    
-    #[error("Vote error: {0}")]
-    VoteError(#[from] VoteError),
+/// Error body returned by the HTTP services.
+///
+/// `code` is a stable, dot-separated identifier such as `vote.not_found`;
+/// clients should branch on it rather than on `message`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiError {
+    #[serde(skip)]
+    pub status: u16,
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
 }
 
 impl ApiError {
+    pub fn new(status: u16, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn bad_request(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(400, code, message)
+    }
+
+    pub fn unauthorized(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(401, code, message)
+    }
+
+    pub fn forbidden(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(403, code, message)
+    }
+
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(404, code, message)
+    }
+
+    pub fn conflict(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(409, code, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(500, "internal.error", message)
+    }
+
     pub fn status_code(&self) -> u16 {
-        match self {
-            ApiError::BadRequest { .. } => 400,
-            ApiError::Unauthorized { .. } => 401,
-            ApiError::Forbidden { .. } => 403,
-            ApiError::NotFound { .. } => 404,
-            ApiError::InternalError { .. } => 500,
-            ApiError::VoteError(_) => 500,
+        self.status
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<VoteError> for ApiError {
+    fn from(error: VoteError) -> Self {
+        let message = error.to_string();
+        match error {
+            VoteError::VoteNotFound { id } => {
+                ApiError::not_found("vote.not_found", message).with_details(json!({ "id": id }))
+            }
+            VoteError::InvalidConfig { .. } => ApiError::bad_request("vote.invalid_config", message),
+            VoteError::InvalidState { expected, actual } => ApiError::conflict("vote.invalid_state", message)
+                .with_details(json!({ "expected": expected, "actual": actual })),
+            VoteError::CommitmentPhaseNotActive => ApiError::conflict("commit.window_closed", message),
+            VoteError::RevealPhaseNotActive => ApiError::conflict("reveal.window_closed", message),
+            VoteError::VoteEnded => ApiError::conflict("vote.ended", message),
+            VoteError::VoteCancelled => ApiError::conflict("vote.cancelled", message),
+            VoteError::InvalidCommitment { .. } => ApiError::bad_request("commit.invalid", message),
+            VoteError::InvalidReveal { .. } => ApiError::bad_request("reveal.invalid", message),
+            VoteError::TemplateError { .. } => ApiError::bad_request("template.invalid", message),
+            VoteError::ValidationError(_) => ApiError::bad_request("request.invalid", message),
+            VoteError::InvalidId(_) => ApiError::bad_request("request.invalid_id", message),
+            VoteError::StorageError { .. } => ApiError::new(500, "storage.error", message),
+            VoteError::SerializationError(_) | VoteError::IoError(_) => ApiError::internal(message),
         }
     }
 }
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for ApiError {
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.status)
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        // Kept on the response so `localize_errors` can re-render the message
+        let mut response = (status, axum::Json(self.clone())).into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+/// Turn a handler panic into a 500 `internal.panic` error instead of a dropped connection,
+/// for `CatchPanicLayer::custom`; the panic message only goes to the log
+#[cfg(feature = "axum")]
+pub fn panic_response(payload: Box<dyn std::any::Any + Send + 'static>) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let message = payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or("non-string panic payload");
+    // Logged inside the request span, so the correlation ID is attached
+    tracing::error!(panic = %message, "Request handler panicked");
+    ApiError::new(500, "internal.panic", "Internal server error").into_response()
+}
@@ -0,0 +1,77 @@
+//! Weak ETags and `If-None-Match` handling for cacheable GET responses
+
+use serde::Serialize;
+use shared_utils::crypto::hash_value;
+
+/// A weak entity tag, rendered as `W/"<hash>"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag(String);
+
+impl ETag {
+    /// Tag derived from the serialized DTO, so any visible field change yields a new tag
+    pub fn weak_for<T: Serialize>(value: &T) -> Result<Self, serde_json::Error> {
+        let serialized = serde_json::to_string(value)?;
+        Ok(Self(format!("W/\"{}\"", &hash_value(&serialized)[..32])))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Weak comparison against an `If-None-Match` header value (a list of tags or `*`)
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+        let ours = opaque(&self.0);
+        if_none_match
+            .split(',')
+            .any(|candidate| candidate.trim() == "*" || opaque(candidate) == ours)
+    }
+}
+
+impl std::fmt::Display for ETag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// JSON response carrying an `ETag`, or a bodiless 304 when the client's copy is current
+#[cfg(feature = "axum")]
+#[derive(Debug)]
+pub enum Conditional<T> {
+    Fresh { etag: ETag, body: T },
+    NotModified { etag: ETag },
+}
+
+#[cfg(feature = "axum")]
+impl<T: Serialize> Conditional<T> {
+    pub fn new(headers: &axum::http::HeaderMap, body: T) -> Result<Self, crate::ApiError> {
+        let etag = ETag::weak_for(&body).map_err(|e| crate::ApiError::internal(e.to_string()))?;
+        let unchanged = headers
+            .get_all(axum::http::header::IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .any(|value| etag.matches(value));
+
+        Ok(if unchanged {
+            Conditional::NotModified { etag }
+        } else {
+            Conditional::Fresh { etag, body }
+        })
+    }
+}
+
+#[cfg(feature = "axum")]
+impl<T: Serialize> axum::response::IntoResponse for Conditional<T> {
+    fn into_response(self) -> axum::response::Response {
+        use axum::http::{header, StatusCode};
+
+        match self {
+            Conditional::Fresh { etag, body } => {
+                ([(header::ETAG, etag.to_string())], axum::Json(body)).into_response()
+            }
+            Conditional::NotModified { etag } => {
+                (StatusCode::NOT_MODIFIED, [(header::ETAG, etag.to_string())]).into_response()
+            }
+        }
+    }
+}
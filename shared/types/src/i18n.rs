@@ -0,0 +1,188 @@
+//! Locales and message catalogs for user-facing text
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::ApiError;
+
+/// Language user-facing messages are rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Zh,
+}
+
+impl Locale {
+    /// Match a language tag such as `zh-CN` or `en` on its primary subtag
+    pub fn parse(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Locale::En),
+            "zh" => Some(Locale::Zh),
+            _ => None,
+        }
+    }
+
+    /// Pick the supported locale with the highest q-value from an `Accept-Language` header,
+    /// falling back to English when none is supported
+    pub fn from_accept_language(header: &str) -> Self {
+        let mut best: Option<(Locale, f32)> = None;
+        for range in header.split(',') {
+            let mut parts = range.split(';');
+            let Some(locale) = parts.next().and_then(Locale::parse) else { continue };
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+                best = Some((locale, quality));
+            }
+        }
+        best.map_or(Locale::En, |(locale, _)| locale)
+    }
+
+    /// Value for the `Content-Language` header
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Zh => "zh",
+        }
+    }
+
+    /// Catalog template for `key`; falls back to the English template
+    pub fn template(self, key: &str) -> Option<&'static str> {
+        lookup(self.catalog(), key).or_else(|| lookup(EN, key))
+    }
+
+    /// Render the template for `key`, filling `{name}` placeholders from `args`
+    ///
+    /// Returns `None` when the key is unknown or an argument is missing.
+    pub fn render(self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        fill(self.template(key)?, |name| {
+            args.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+        })
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Zh => ZH,
+        }
+    }
+}
+
+impl ApiError {
+    /// The same error with its message rendered in `locale`
+    ///
+    /// Placeholders are filled from `details`. Codes without a catalog entry, or whose entry
+    /// needs a detail this error does not carry, keep their original message.
+    pub fn localized(&self, locale: Locale) -> ApiError {
+        let details = self.details.as_ref();
+        let message = locale.template(self.code).and_then(|template| {
+            fill(template, |name| match details?.get(name)? {
+                Value::String(s) => Some(s.clone()),
+                other => Some(other.to_string()),
+            })
+        });
+        ApiError { message: message.unwrap_or_else(|| self.message.clone()), ..self.clone() }
+    }
+}
+
+/// Middleware re-rendering `ApiError` bodies in the locale chosen by `Accept-Language`
+///
+/// Relies on `ApiError`'s response carrying the error in its extensions; other responses pass
+/// through untouched. Localized responses get a `Content-Language` header.
+#[cfg(feature = "axum")]
+pub async fn localize_errors(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::http::{header, HeaderValue};
+
+    let locale = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map_or(Locale::En, Locale::from_accept_language);
+    let response = next.run(request).await;
+    let Some(error) = response.extensions().get::<ApiError>() else { return response };
+    let Ok(body) = serde_json::to_vec(&error.localized(locale)) else { return response };
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(locale.tag()));
+    axum::response::Response::from_parts(parts, axum::body::Body::from(body))
+}
+
+fn lookup(catalog: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+fn fill(template: &str, arg: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = start + rest[start..].find('}')?;
+        out.push_str(&rest[..start]);
+        out.push_str(&arg(&rest[start + 1..end])?);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+const EN: &[(&str, &str)] = &[
+    // API errors, keyed by code
+    ("vote.not_found", "Vote not found: {id}"),
+    ("vote.invalid_state", "Vote is not in the correct state: expected {expected}, got {actual}"),
+    ("vote.ended", "Vote has already ended"),
+    ("vote.cancelled", "Vote has been cancelled"),
+    ("commit.window_closed", "Commitment phase is not active"),
+    ("reveal.window_closed", "Reveal phase is not active"),
+    ("internal.panic", "Internal server error"),
+    // Notifications
+    ("notification.session_created.title", "Session created"),
+    ("notification.session_created.content", "Session {session_id} has been created"),
+    ("notification.commitment_submitted.title", "Commitment submitted"),
+    ("notification.commitment_submitted.content", "A new commitment was submitted in session {session_id}"),
+    ("notification.reveal_phase_started.title", "Reveal phase started"),
+    ("notification.reveal_phase_started.content", "The reveal phase of session {session_id} has started"),
+    ("notification.reveal_completed.title", "Reveal phase completed"),
+    ("notification.reveal_completed.content", "The reveal phase of session {session_id} has completed"),
+    ("notification.result_generated.title", "Results generated"),
+    ("notification.result_generated.content", "Results for session {session_id} have been generated"),
+    ("notification.system_error.title", "System error"),
+    ("notification.system_error.content", "A system error occurred: {error}"),
+    ("notification.custom.title", "Custom notification: {custom_type}"),
+    ("notification.custom.content", "Received custom event: {custom_type}"),
+    ("notification.unknown", "unknown"),
+    ("notification.unknown_error", "unknown error"),
+];
+
+const ZH: &[(&str, &str)] = &[
+    ("vote.not_found", "未找到投票: {id}"),
+    ("vote.invalid_state", "投票状态不正确: 期望 {expected}，实际 {actual}"),
+    ("vote.ended", "投票已结束"),
+    ("vote.cancelled", "投票已取消"),
+    ("commit.window_closed", "承诺阶段未开放"),
+    ("reveal.window_closed", "揭示阶段未开放"),
+    ("internal.panic", "服务器内部错误"),
+    ("notification.session_created.title", "新会话已创建"),
+    ("notification.session_created.content", "会话 {session_id} 已成功创建"),
+    ("notification.commitment_submitted.title", "承诺已提交"),
+    ("notification.commitment_submitted.content", "会话 {session_id} 中有新的承诺提交"),
+    ("notification.reveal_phase_started.title", "揭示阶段开始"),
+    ("notification.reveal_phase_started.content", "会话 {session_id} 的揭示阶段已开始"),
+    ("notification.reveal_completed.title", "揭示阶段完成"),
+    ("notification.reveal_completed.content", "会话 {session_id} 的揭示阶段已完成"),
+    ("notification.result_generated.title", "结果已生成"),
+    ("notification.result_generated.content", "会话 {session_id} 的结果已生成"),
+    ("notification.system_error.title", "系统错误"),
+    ("notification.system_error.content", "系统发生错误: {error}"),
+    ("notification.custom.title", "自定义通知: {custom_type}"),
+    ("notification.custom.content", "收到自定义事件: {custom_type}"),
+    ("notification.unknown", "未知"),
+    ("notification.unknown_error", "未知错误"),
+];
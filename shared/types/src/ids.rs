@@ -0,0 +1,159 @@
+//! Validated identifier types
+//!
+//! Vote and session ids travel as plain strings on the wire, but inside the system they are
+//! distinct types, so a voter name or an arbitrary string cannot be passed where an id is
+//! expected. Both serialize as bare strings.
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Longest accepted id, in bytes
+pub const MAX_ID_LEN: usize = 128;
+
+/// Why a string is not a valid id
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum IdError {
+    #[error("{kind} must not be empty")]
+    Empty { kind: &'static str },
+
+    #[error("{kind} is longer than {max} bytes")]
+    TooLong { kind: &'static str, max: usize },
+
+    #[error("{kind} contains invalid character {ch:?}; only ASCII letters, digits, '-', '_', '.' and ':' are allowed")]
+    InvalidCharacter { kind: &'static str, ch: char },
+}
+
+fn validate(kind: &'static str, id: &str) -> Result<(), IdError> {
+    if id.is_empty() {
+        return Err(IdError::Empty { kind });
+    }
+    if id.len() > MAX_ID_LEN {
+        return Err(IdError::TooLong { kind, max: MAX_ID_LEN });
+    }
+    if let Some(ch) = id.chars().find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))) {
+        return Err(IdError::InvalidCharacter { kind, ch });
+    }
+    Ok(())
+}
+
+macro_rules! string_id {
+    ($(#[$meta:meta])* $name:ident, $kind:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// Validate `id` and wrap it
+            pub fn parse(id: impl Into<String>) -> Result<Self, IdError> {
+                let id = id.into();
+                validate($kind, &id)?;
+                Ok(Self(id))
+            }
+
+            /// A fresh random id
+            pub fn generate() -> Self {
+                Self(uuid::Uuid::new_v4().to_string())
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = IdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = IdError;
+
+            fn try_from(id: String) -> Result<Self, Self::Error> {
+                Self::parse(id)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = IdError;
+
+            fn try_from(id: &str) -> Result<Self, Self::Error> {
+                Self::parse(id)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+    };
+}
+
+string_id!(
+    /// Identifier of a vote
+    VoteId,
+    "vote id"
+);
+
+string_id!(
+    /// Identifier of an admin session, which manages one vote
+    SessionId,
+    "session id"
+);
@@ -1,7 +1,15 @@
 pub mod vote;
 pub mod api;
 pub mod errors;
+pub mod etag;
+pub mod i18n;
+pub mod ids;
+pub mod validate;
 
 pub use vote::*;
 pub use api::*;
 pub use errors::*;
+pub use etag::*;
+pub use i18n::*;
+pub use ids::*;
+pub use validate::*;
@@ -0,0 +1,226 @@
+//! Field-level validation of request bodies, run before handlers see them
+
+use crate::{ApiError, CommitRequest, CreateVoteRequest, RevealRequest, VoteConfig};
+use serde::Serialize;
+use serde_json::json;
+use shared_utils::validation::{
+    validate_not_empty, validate_not_null, validate_number_range, validate_string_length, ValidationError,
+};
+
+/// A single invalid field in a request body
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { field: field.into(), message: message.into() }
+    }
+
+    /// Nest the field under `prefix`, e.g. `title` -> `config.title`
+    fn prefixed(self, prefix: &str) -> Self {
+        Self { field: format!("{}.{}", prefix, self.field), message: self.message }
+    }
+}
+
+impl From<ValidationError> for FieldError {
+    fn from(error: ValidationError) -> Self {
+        let field = match &error {
+            ValidationError::RequiredFieldMissing { field }
+            | ValidationError::InvalidValue { field, .. }
+            | ValidationError::ValueTooLong { field, .. }
+            | ValidationError::ValueTooShort { field, .. } => field.clone(),
+        };
+        Self { field, message: error.to_string() }
+    }
+}
+
+/// Request bodies that can check their own fields
+pub trait Validate {
+    /// Every invalid field, in declaration order; empty when the body is valid
+    fn validate(&self) -> Vec<FieldError>;
+}
+
+/// Collects the first failure of each field's validator chain
+#[derive(Default)]
+struct Errors(Vec<FieldError>);
+
+impl Errors {
+    fn check(&mut self, result: Result<(), ValidationError>) {
+        if let Err(error) = result {
+            self.0.push(error.into());
+        }
+    }
+}
+
+impl Validate for VoteConfig {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Errors::default();
+        errors.check(
+            validate_not_empty(&self.title, "title")
+                .and_then(|_| validate_string_length(&self.title, "title", Some(1), Some(200))),
+        );
+        errors.check(
+            validate_not_empty(&self.description, "description")
+                .and_then(|_| validate_string_length(&self.description, "description", Some(1), Some(1000))),
+        );
+        errors.check(validate_not_empty(&self.template_id, "template_id"));
+        errors.check(validate_not_null(&self.template_params, "template_params"));
+        errors.check(validate_number_range(
+            self.commitment_duration_hours as f64,
+            "commitment_duration_hours",
+            Some(1.0),
+            Some(168.0),
+        ));
+        errors.check(validate_number_range(
+            self.reveal_duration_hours as f64,
+            "reveal_duration_hours",
+            Some(1.0),
+            Some(168.0),
+        ));
+        if let Some(url) = &self.completion_webhook_url {
+            errors.check(validate_string_length(url, "completion_webhook_url", Some(1), Some(2048)));
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                errors.0.push(FieldError::new("completion_webhook_url", "Completion webhook URL must be http(s)"));
+            }
+        }
+        errors.0
+    }
+}
+
+impl Validate for CreateVoteRequest {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Errors(self.config.validate().into_iter().map(|e| e.prefixed("config")).collect());
+        if let Some(id) = &self.client_request_id {
+            errors.check(validate_string_length(id, "client_request_id", Some(1), Some(128)));
+        }
+        errors.0
+    }
+}
+
+impl Validate for CommitRequest {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Errors::default();
+        errors.check(
+            validate_not_empty(&self.voter, "voter")
+                .and_then(|_| validate_string_length(&self.voter, "voter", Some(1), Some(100))),
+        );
+        errors.check(validate_string_length(&self.commitment_hash, "commitment_hash", Some(64), Some(64)));
+        if !self.commitment_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            errors.0.push(FieldError::new("commitment_hash", "Commitment hash must be hex-encoded"));
+        }
+        errors.check(validate_salt(&self.salt));
+        errors.0
+    }
+}
+
+impl Validate for RevealRequest {
+    fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Errors::default();
+        errors.check(
+            validate_not_empty(&self.voter, "voter")
+                .and_then(|_| validate_string_length(&self.voter, "voter", Some(1), Some(100))),
+        );
+        match &self.ciphertext {
+            Some(ciphertext) => {
+                errors.check(validate_ciphertext(ciphertext));
+                if !self.value.is_null() || !self.salt.is_empty() {
+                    errors.0.push(FieldError::new("ciphertext", "Send either ciphertext or value and salt, not both"));
+                }
+            }
+            None => {
+                errors.check(validate_not_null(&self.value, "value"));
+                errors.check(validate_salt(&self.salt));
+            }
+        }
+        errors.0
+    }
+}
+
+fn validate_salt(salt: &str) -> Result<(), ValidationError> {
+    validate_not_empty(salt, "salt")?;
+    validate_string_length(salt, "salt", Some(1), Some(100))
+}
+
+/// Longest accepted encrypted reveal, in hex characters
+const MAX_CIPHERTEXT_LEN: usize = 16 * 1024;
+
+fn validate_ciphertext(ciphertext: &str) -> Result<(), ValidationError> {
+    validate_not_empty(ciphertext, "ciphertext")?;
+    validate_string_length(ciphertext, "ciphertext", Some(1), Some(MAX_CIPHERTEXT_LEN))?;
+    if !shared_utils::crypto::is_ecies_ciphertext(ciphertext) {
+        return Err(ValidationError::InvalidValue {
+            field: "ciphertext".to_string(),
+            message: "Not an encrypted reveal".to_string(),
+        });
+    }
+    Ok(())
+}
+
+impl ApiError {
+    /// 422 carrying the per-field error list under `details.fields`
+    pub fn validation(errors: Vec<FieldError>) -> Self {
+        ApiError::new(422, "request.validation_failed", "Request body failed validation")
+            .with_details(json!({ "fields": errors }))
+    }
+}
+
+/// `axum::Json` that also runs [`Validate`] and rejects with a 422 before the handler runs
+#[cfg(feature = "axum")]
+#[derive(Debug, Clone)]
+pub struct ValidatedJson<T>(pub T);
+
+#[cfg(feature = "axum")]
+#[axum::async_trait]
+impl<T, S> axum::extract::FromRequest<S> for ValidatedJson<T>
+where
+    T: serde::de::DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: axum::extract::Request, state: &S) -> Result<Self, Self::Rejection> {
+        use axum::extract::rejection::JsonRejection;
+
+        // Content type and syntax are checked by `axum::Json`; binding to `T` goes through
+        // `serde_path_to_error` so shape errors can name the offending field
+        let axum::Json(body) = axum::Json::<serde_json::Value>::from_request(request, state)
+            .await
+            .map_err(|rejection| match rejection {
+                JsonRejection::MissingJsonContentType(_) => {
+                    ApiError::new(415, "request.unsupported_media_type", rejection.body_text())
+                }
+                _ if rejection.status() == axum::http::StatusCode::PAYLOAD_TOO_LARGE => {
+                    ApiError::new(413, "request.too_large", rejection.body_text())
+                }
+                _ => ApiError::bad_request("request.malformed_json", rejection.body_text()),
+            })?;
+
+        let value: T = serde_path_to_error::deserialize(body).map_err(|error| {
+            let message = error.inner().to_string();
+            let path = error.path().to_string();
+            let field = match (missing_field(&message), path.as_str()) {
+                (Some(name), ".") => name.to_string(),
+                (Some(name), path) => format!("{}.{}", path, name),
+                (None, path) => path.to_string(),
+            };
+            ApiError::validation(vec![FieldError::new(field, message)])
+        })?;
+
+        let errors = value.validate();
+        if errors.is_empty() {
+            Ok(Self(value))
+        } else {
+            Err(ApiError::validation(errors))
+        }
+    }
+}
+
+/// Pulls the field name out of serde's "missing field `title`" message
+#[cfg(feature = "axum")]
+fn missing_field(message: &str) -> Option<&str> {
+    let rest = message.strip_prefix("missing field `")?;
+    Some(&rest[..rest.find('`')?])
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vote {
@@ -16,9 +17,23 @@ pub struct Vote {
     pub reveal_end: DateTime<Utc>,
     pub status: VoteStatus,
     pub results: Option<VoteResults>,
+    #[serde(default)]
+    pub tie_break: TieBreak,
+    /// Exempts the vote from retention cleanup
+    #[serde(default)]
+    pub legal_hold: bool,
+    /// Receives the final results once the vote completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_webhook_url: Option<String>,
+    /// Client-supplied id that makes creation idempotent per creator
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_request_id: Option<String>,
+    /// Key (hex X25519) reveals must be encrypted to; set for votes with encrypted reveals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reveal_public_key: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VoteStatus {
     Created,
     CommitmentPhase,
@@ -27,6 +42,35 @@ pub enum VoteStatus {
     Cancelled,
 }
 
+impl VoteStatus {
+    /// Whether no further transitions are possible
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, VoteStatus::Completed | VoteStatus::Cancelled)
+    }
+
+    /// The phase that follows this one, or `None` once terminal
+    pub fn next_phase(&self) -> Option<VoteStatus> {
+        match self {
+            VoteStatus::Created => Some(VoteStatus::CommitmentPhase),
+            VoteStatus::CommitmentPhase => Some(VoteStatus::RevealPhase),
+            VoteStatus::RevealPhase => Some(VoteStatus::Completed),
+            VoteStatus::Completed | VoteStatus::Cancelled => None,
+        }
+    }
+
+    /// Legal transitions only move forward: to a later phase, straight to `Completed` once the
+    /// vote has ended, or to `Cancelled`, and never out of a terminal status
+    pub fn can_transition_to(&self, to: &VoteStatus) -> bool {
+        let rank = |status: &VoteStatus| match status {
+            VoteStatus::Created => 0,
+            VoteStatus::CommitmentPhase => 1,
+            VoteStatus::RevealPhase => 2,
+            VoteStatus::Completed | VoteStatus::Cancelled => 3,
+        };
+        !self.is_terminal() && rank(to) > rank(self)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoteConfig {
     pub title: String,
@@ -35,6 +79,53 @@ pub struct VoteConfig {
     pub template_params: serde_json::Value,
     pub commitment_duration_hours: u32,
     pub reveal_duration_hours: u32,
+    /// How to pick a single winner when the top options tie
+    #[serde(default)]
+    pub tie_break: TieBreak,
+    /// URL the final results are POSTed to when the vote completes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_webhook_url: Option<String>,
+    /// Take reveals encrypted to a per-vote key, opened only once the reveal phase is over
+    #[serde(default)]
+    pub encrypted_reveals: bool,
+}
+
+/// Policy for resolving a tie between the top options of a single-winner vote
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Pick one of the tied options using the vote's combined randomness seed
+    Random { seed_source: SeedSource },
+    /// Pick the tied option that appears first in the choice list
+    FirstListed,
+    /// Declare no winner and report the tied options as runoff candidates
+    Runoff,
+    /// Declare no winner
+    #[default]
+    NoWinner,
+}
+
+/// Public values combined into the seed for `TieBreak::Random`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedSource {
+    /// Salts disclosed in the reveals
+    #[default]
+    RevealSalts,
+    /// Commitment hashes submitted during the commitment phase
+    CommitmentHashes,
+}
+
+/// Single winner computed from an aggregate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WinnerOutcome {
+    pub winner: Option<String>,
+    /// Options sharing the top count; empty when there was no tie
+    pub tied: Vec<String>,
+    /// Policy used to resolve the tie, if one was needed
+    pub tie_break: Option<TieBreak>,
+    /// Hex seed used when the tie was broken randomly
+    pub seed: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +136,37 @@ pub struct Commitment {
     pub commitment_hash: String,
     pub salt: String,
     pub created_at: DateTime<Utc>,
+    /// Range proof submitted with the commitment, checked again against the reveal
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_proof: Option<RangeProof>,
+}
+
+/// On-chain record of a commitment hash, written after the commitment is accepted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentAnchor {
+    pub vote_id: String,
+    pub voter: String,
+    /// Commitment hash as submitted to the chain
+    pub commitment_hash: String,
+    /// Chain the hash was written to
+    pub chain: String,
+    pub tx_hash: String,
+    /// Block containing the transaction, once known
+    pub block_number: Option<u64>,
+    pub anchored_at: DateTime<Utc>,
+    /// Merkle root written on-chain when the commitment was anchored as part of a batch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_root: Option<String>,
+    /// Inclusion proof of the commitment hash under `batch_root`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub batch_proof: Vec<AnchorProofStep>,
+}
+
+/// One level of a batch inclusion proof, in the layout of the commitment engine's Merkle proofs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorProofStep {
+    pub sibling: String,
+    pub sibling_is_left: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +177,112 @@ pub struct Reveal {
     pub value: serde_json::Value,
     pub salt: String,
     pub created_at: DateTime<Utc>,
+    /// Encrypted opening of a reveal to a vote with encrypted reveals; `value` and `salt` stay
+    /// empty until the vote is tallied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ciphertext: Option<String>,
+}
+
+/// A vote one voter committed or revealed in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoteParticipation {
+    pub vote_id: String,
+    pub title: String,
+    pub status: VoteStatus,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub revealed_at: Option<DateTime<Utc>>,
+}
+
+impl VoteParticipation {
+    /// One page of `votes`, most recently created vote first, given each vote's creation time
+    pub fn newest_first(mut votes: Vec<(DateTime<Utc>, VoteParticipation)>, page: u32, page_size: u32) -> Page<VoteParticipation> {
+        votes.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.vote_id.cmp(&b.1.vote_id)));
+        Page::from_all(votes.into_iter().map(|(_, v)| v).collect(), page, page_size)
+    }
+
+    /// One page of the votes `voter` committed or revealed in, for stores that keep votes,
+    /// commitments and reveals in maps
+    pub fn page_in_memory<'a>(
+        voter: &str,
+        votes: &HashMap<String, Vote>,
+        commitments: impl IntoIterator<Item = &'a Commitment>,
+        reveals: impl IntoIterator<Item = &'a Reveal>,
+        page: u32,
+        page_size: u32,
+    ) -> Page<VoteParticipation> {
+        // when the voter committed and revealed, by vote id
+        type Times = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+        let mut activity: HashMap<&str, Times> = HashMap::new();
+        for commitment in commitments.into_iter().filter(|c| c.voter == voter) {
+            activity.entry(commitment.vote_id.as_str()).or_default().0 = Some(commitment.created_at);
+        }
+        for reveal in reveals.into_iter().filter(|r| r.voter == voter) {
+            activity.entry(reveal.vote_id.as_str()).or_default().1 = Some(reveal.created_at);
+        }
+
+        let participations = activity.into_iter()
+            .filter_map(|(vote_id, (committed_at, revealed_at))| {
+                let vote = votes.get(vote_id)?;
+                Some((vote.created_at, VoteParticipation {
+                    vote_id: vote.id.clone(),
+                    title: vote.title.clone(),
+                    status: vote.status.clone(),
+                    committed_at,
+                    revealed_at,
+                }))
+            })
+            .collect();
+        Self::newest_first(participations, page, page_size)
+    }
+}
+
+/// Number of votes in each status
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteStatusCounts {
+    pub total: u32,
+    pub created: u32,
+    pub commitment_phase: u32,
+    pub reveal_phase: u32,
+    pub completed: u32,
+    pub cancelled: u32,
+}
+
+impl VoteStatusCounts {
+    /// Count one more vote in `status`
+    pub fn add(&mut self, status: &VoteStatus) {
+        self.total += 1;
+        match status {
+            VoteStatus::Created => self.created += 1,
+            VoteStatus::CommitmentPhase => self.commitment_phase += 1,
+            VoteStatus::RevealPhase => self.reveal_phase += 1,
+            VoteStatus::Completed => self.completed += 1,
+            VoteStatus::Cancelled => self.cancelled += 1,
+        }
+    }
+}
+
+/// Kind of change recorded in a vote's history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteHistoryKind {
+    Created,
+    StatusChanged,
+    ResultsComputed,
+    Cancelled,
+}
+
+/// One change to a vote, reconstructed from its audit events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoteHistoryEntry {
+    /// Version of the audit event the entry was derived from
+    pub version: u64,
+    pub kind: VoteHistoryKind,
+    pub from_status: Option<VoteStatus>,
+    pub to_status: Option<VoteStatus>,
+    /// Who caused the change; `system` for changes the service made on its own
+    pub actor: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +291,15 @@ pub struct VoteResults {
     pub total_votes: u32,
     pub results: serde_json::Value,
     pub calculated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub winner: Option<WinnerOutcome>,
+    /// Merkle root over the vote's commitment hashes when results were calculated
+    #[serde(default)]
+    pub commitment_root: Option<String>,
+    /// Secret key of a vote with encrypted reveals, released with its results so anyone can
+    /// open the stored ciphertexts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reveal_secret_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +307,18 @@ pub struct CommitRequest {
     pub voter: String,
     pub commitment_hash: String,
     pub salt: String,
+    /// Proof that a numeric vote lies within the template's range, without disclosing it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_proof: Option<RangeProof>,
+}
+
+/// Bulletproofs range proof over a numeric vote value
+///
+/// `commitment` is a Pedersen commitment to `value - min`; both fields are hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeProof {
+    pub commitment: String,
+    pub proof: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,8 +331,26 @@ pub struct CommitResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RevealRequest {
     pub voter: String,
+    /// Omitted when the reveal is sent as `ciphertext`
+    #[serde(default)]
+    pub value: serde_json::Value,
+    #[serde(default)]
+    pub salt: String,
+    /// Blinding factor (hex) opening the commitment's range proof, if one was submitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_blinding: Option<String>,
+    /// A [`RevealOpening`] encrypted to the vote's `reveal_public_key`, in place of `value` and `salt`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ciphertext: Option<String>,
+}
+
+/// What an encrypted reveal decrypts to: the fields a plaintext reveal would carry, as JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevealOpening {
     pub value: serde_json::Value,
     pub salt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_blinding: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +377,18 @@ pub struct Page<T> {
     pub total_pages: u32,
 }
 
+impl<T> Page<T> {
+    /// Slice one page out of a fully materialized, already ordered list
+    pub fn from_all(all: Vec<T>, page: u32, page_size: u32) -> Self {
+        crate::Paginated::from_all(all, page, page_size).into()
+    }
+
+    /// A page of `items` out of `total`; an empty page size has no pages
+    pub fn new(items: Vec<T>, total: u32, page: u32, page_size: u32) -> Self {
+        crate::Paginated::new(items, total as u64, page, page_size).into()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationResult {
     pub vote_id: String,
@@ -126,6 +405,15 @@ pub struct CommitmentVerification {
     pub verified_commitments: u32,
     pub failed_commitments: u32,
     pub commitment_issues: Vec<String>,
+    /// Merkle root recomputed from the stored commitments
+    #[serde(default)]
+    pub commitment_root: Option<String>,
+    /// On-chain anchors found for the vote's commitments, when anchoring is enabled
+    #[serde(default)]
+    pub anchors: Vec<CommitmentAnchor>,
+    /// Voters whose commitment has no anchor yet; anchoring is best-effort, so this is not an issue
+    #[serde(default)]
+    pub unanchored_voters: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
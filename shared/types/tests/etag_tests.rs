@@ -0,0 +1,76 @@
+use chrono::Utc;
+use serde_json::json;
+use shared_types::*;
+
+fn results(total_votes: u32) -> GetResultsResponse {
+    GetResultsResponse {
+        results: VoteResults {
+            vote_id: "vote_1".to_string(),
+            total_votes,
+            results: json!({ "yes": total_votes }),
+            calculated_at: chrono::DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z").unwrap().with_timezone(&Utc),
+            winner: None,
+            commitment_root: None,
+            reveal_secret_key: None,
+        },
+        success: true,
+    }
+}
+
+#[test]
+fn test_etag_is_weak_and_stable() {
+    let etag = ETag::weak_for(&results(3)).unwrap();
+    assert!(etag.as_str().starts_with("W/\""));
+    assert_eq!(etag, ETag::weak_for(&results(3)).unwrap());
+    assert_ne!(etag, ETag::weak_for(&results(4)).unwrap());
+}
+
+#[test]
+fn test_if_none_match_comparison() {
+    let etag = ETag::weak_for(&results(3)).unwrap();
+    let strong = etag.as_str().trim_start_matches("W/").to_string();
+
+    assert!(etag.matches(etag.as_str()));
+    assert!(etag.matches(&strong));
+    assert!(etag.matches(&format!("\"other\", {}", etag)));
+    assert!(etag.matches("*"));
+    assert!(!etag.matches("W/\"other\""));
+}
+
+#[cfg(feature = "axum")]
+mod conditional {
+    use super::*;
+    use axum::{
+        http::{header, HeaderMap, HeaderValue, StatusCode},
+        response::IntoResponse,
+    };
+
+    fn get(if_none_match: Option<&str>, body: GetResultsResponse) -> axum::response::Response {
+        let mut headers = HeaderMap::new();
+        if let Some(tag) = if_none_match {
+            headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(tag).unwrap());
+        }
+        Conditional::new(&headers, body).unwrap().into_response()
+    }
+
+    #[test]
+    fn test_unchanged_resource_returns_304() {
+        let first = get(None, results(3));
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let second = get(Some(&etag), results(3));
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(second.headers()[header::ETAG], etag.as_str());
+    }
+
+    #[test]
+    fn test_changed_resource_returns_200_with_new_etag() {
+        let first = get(None, results(3));
+        let etag = first.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let second = get(Some(&etag), results(4));
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_ne!(second.headers()[header::ETAG], etag.as_str());
+    }
+}
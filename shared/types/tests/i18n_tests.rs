@@ -0,0 +1,31 @@
+use serde_json::json;
+use shared_types::{ApiError, Locale};
+
+#[test]
+fn test_accept_language_picks_highest_supported_quality() {
+    assert_eq!(Locale::from_accept_language("zh-CN"), Locale::Zh);
+    assert_eq!(Locale::from_accept_language("fr, zh;q=0.4, en;q=0.7"), Locale::En);
+    assert_eq!(Locale::from_accept_language("en;q=0.2, zh_TW;q=0.9"), Locale::Zh);
+    assert_eq!(Locale::from_accept_language("zh;q=0, fr"), Locale::En);
+    assert_eq!(Locale::from_accept_language(""), Locale::En);
+}
+
+#[test]
+fn test_localized_keeps_message_when_details_are_missing() {
+    let error = ApiError::not_found("vote.not_found", "Vote not found: a");
+    assert_eq!(error.localized(Locale::Zh).message, "Vote not found: a");
+
+    let error = error.with_details(json!({ "id": "a" }));
+    assert_eq!(error.localized(Locale::Zh).message, "未找到投票: a");
+
+    let unknown = ApiError::bad_request("request.invalid", "Validation error: title");
+    assert_eq!(unknown.localized(Locale::Zh).message, "Validation error: title");
+}
+
+#[test]
+fn test_notification_templates_fill_arguments() {
+    let args = [("session_id", "s-1")];
+    assert_eq!(Locale::Zh.render("notification.session_created.content", &args).unwrap(), "会话 s-1 已成功创建");
+    assert_eq!(Locale::En.render("notification.session_created.content", &args).unwrap(), "Session s-1 has been created");
+    assert_eq!(Locale::En.render("notification.session_created.content", &[]), None);
+}
@@ -0,0 +1,42 @@
+use shared_types::*;
+
+#[test]
+fn test_valid_ids_parse() {
+    let id = VoteId::parse("vote_1").unwrap();
+    assert_eq!(id.as_str(), "vote_1");
+    assert_eq!(id, "vote_1");
+    assert_eq!(id.to_string(), "vote_1");
+
+    let generated = VoteId::generate();
+    assert_eq!(VoteId::parse(generated.as_str()).unwrap(), generated);
+    assert!("session:2024.01-a".parse::<SessionId>().is_ok());
+}
+
+#[test]
+fn test_invalid_ids_are_rejected() {
+    assert_eq!(VoteId::parse(""), Err(IdError::Empty { kind: "vote id" }));
+    assert_eq!(
+        VoteId::parse("x".repeat(MAX_ID_LEN + 1)),
+        Err(IdError::TooLong { kind: "vote id", max: MAX_ID_LEN })
+    );
+    assert_eq!(
+        SessionId::parse("../etc/passwd"),
+        Err(IdError::InvalidCharacter { kind: "session id", ch: '/' })
+    );
+    assert!(VoteId::parse("vote 1").is_err());
+}
+
+#[test]
+fn test_ids_serialize_as_plain_strings() {
+    let id = VoteId::parse("vote_1").unwrap();
+    assert_eq!(serde_json::to_value(&id).unwrap(), "vote_1");
+    assert_eq!(serde_json::from_str::<VoteId>("\"vote_1\"").unwrap(), id);
+    assert!(serde_json::from_str::<VoteId>("\"\"").is_err());
+}
+
+#[test]
+fn test_api_error_from_invalid_id() {
+    let error = ApiError::from(VoteError::from(VoteId::parse("vote 1").unwrap_err()));
+    assert_eq!(error.status_code(), 400);
+    assert_eq!(error.code, "request.invalid_id");
+}
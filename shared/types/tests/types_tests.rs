@@ -18,6 +18,11 @@ fn test_vote_serialization() {
         reveal_end: Utc::now() + Duration::hours(48),
         status: VoteStatus::Created,
         results: None,
+        tie_break: TieBreak::default(),
+        legal_hold: false,
+        completion_webhook_url: None,
+        client_request_id: None,
+        reveal_public_key: None,
     };
 
     // Test serialization
@@ -57,6 +62,9 @@ fn test_vote_config_validation() {
         template_params: json!({}),
         commitment_duration_hours: 24,
         reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
 
     // Test serialization
@@ -75,6 +83,7 @@ fn test_commitment_serialization() {
         voter: "voter_1".to_string(),
         commitment_hash: "abc123".to_string(),
         salt: "salt123".to_string(),
+        range_proof: None,
         created_at: Utc::now(),
     };
 
@@ -95,6 +104,7 @@ fn test_reveal_serialization() {
         value: json!("yes"),
         salt: "salt123".to_string(),
         created_at: Utc::now(),
+        ciphertext: None,
     };
 
     let serialized = serde_json::to_string(&reveal).unwrap();
@@ -115,6 +125,9 @@ fn test_vote_results_serialization() {
             "no": 4
         }),
         calculated_at: Utc::now(),
+        winner: None,
+        commitment_root: None,
+        reveal_secret_key: None,
     };
 
     let serialized = serde_json::to_string(&results).unwrap();
@@ -131,6 +144,7 @@ fn test_commit_request_serialization() {
         voter: "voter_1".to_string(),
         commitment_hash: "hash123".to_string(),
         salt: "salt123".to_string(),
+        range_proof: None,
     };
 
     let serialized = serde_json::to_string(&request).unwrap();
@@ -161,6 +175,8 @@ fn test_reveal_request_serialization() {
         voter: "voter_1".to_string(),
         value: json!("yes"),
         salt: "salt123".to_string(),
+        range_blinding: None,
+        ciphertext: None,
     };
 
     let serialized = serde_json::to_string(&request).unwrap();
@@ -220,6 +236,11 @@ fn test_page_serialization() {
             reveal_end: Utc::now() + Duration::hours(48),
             status: VoteStatus::Created,
             results: None,
+            tie_break: TieBreak::default(),
+            legal_hold: false,
+            completion_webhook_url: None,
+            client_request_id: None,
+            reveal_public_key: None,
         },
         Vote {
             id: "vote_2".to_string(),
@@ -235,6 +256,11 @@ fn test_page_serialization() {
             reveal_end: Utc::now() + Duration::hours(48),
             status: VoteStatus::Created,
             results: None,
+            tie_break: TieBreak::default(),
+            legal_hold: false,
+            completion_webhook_url: None,
+            client_request_id: None,
+            reveal_public_key: None,
         },
     ];
 
@@ -254,6 +280,58 @@ fn test_page_serialization() {
     assert_eq!(deserialized.page, page.page);
 }
 
+#[test]
+fn test_paginated_serialization_shape() {
+    let paginated = Paginated::new(vec!["a".to_string(), "b".to_string()], 5, 0, 2);
+
+    assert_eq!(
+        serde_json::to_value(&paginated).unwrap(),
+        json!({
+            "items": ["a", "b"],
+            "total": 5,
+            "page": 0,
+            "page_size": 2,
+            "total_pages": 3,
+            "next_cursor": "2"
+        })
+    );
+
+    let last = Paginated::new(vec!["e".to_string()], 5, 2, 2);
+    assert_eq!(serde_json::to_value(&last).unwrap()["next_cursor"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_paginated_from_page_preserves_totals() {
+    let page = Page {
+        items: vec![1, 2, 3],
+        total: 13,
+        page: 1,
+        page_size: 3,
+        total_pages: 5,
+    };
+
+    let paginated: Paginated<i32> = page.into();
+    assert_eq!(paginated.items, vec![1, 2, 3]);
+    assert_eq!(paginated.total, 13);
+    assert_eq!(paginated.page, 1);
+    assert_eq!(paginated.page_size, 3);
+    assert_eq!(paginated.total_pages, 5);
+    assert_eq!(paginated.next_cursor.as_deref(), Some("6"));
+}
+
+#[test]
+fn test_paginated_from_all_slices_page() {
+    let paginated = Paginated::from_all((0..7).collect::<Vec<_>>(), 2, 3);
+    assert_eq!(paginated.items, vec![6]);
+    assert_eq!(paginated.total, 7);
+    assert_eq!(paginated.total_pages, 3);
+    assert!(paginated.next_cursor.is_none());
+
+    let empty: Paginated<i32> = Paginated::from_all(Vec::new(), 0, 20);
+    assert_eq!(empty.total_pages, 0);
+    assert!(empty.next_cursor.is_none());
+}
+
 #[test]
 fn test_json_value_handling() {
     // Test various JSON value types in template_params
@@ -277,6 +355,11 @@ fn test_json_value_handling() {
         reveal_end: Utc::now() + Duration::hours(48),
         status: VoteStatus::Created,
         results: None,
+        tie_break: TieBreak::default(),
+        legal_hold: false,
+        completion_webhook_url: None,
+        client_request_id: None,
+        reveal_public_key: None,
     };
 
     let serialized = serde_json::to_string(&vote).unwrap();
@@ -289,3 +372,23 @@ fn test_json_value_handling() {
     assert_eq!(deserialized.template_params["array"], json!([1, 2, 3]));
     assert_eq!(deserialized.template_params["object"]["nested"], "value");
 }
+
+#[test]
+fn test_api_error_from_vote_not_found() {
+    let error = ApiError::from(VoteError::VoteNotFound { id: "vote_1".to_string() });
+    assert_eq!(error.status_code(), 404);
+    assert_eq!(error.code, "vote.not_found");
+
+    let body = serde_json::to_value(&error).unwrap();
+    assert_eq!(body["code"], "vote.not_found");
+    assert_eq!(body["details"]["id"], "vote_1");
+    assert!(body.get("status").is_none());
+}
+
+#[test]
+fn test_api_error_window_codes() {
+    let commit = ApiError::from(VoteError::CommitmentPhaseNotActive);
+    assert_eq!((commit.status_code(), commit.code), (409, "commit.window_closed"));
+    let reveal = ApiError::from(VoteError::RevealPhaseNotActive);
+    assert_eq!((reveal.status_code(), reveal.code), (409, "reveal.window_closed"));
+}
@@ -0,0 +1,123 @@
+use serde_json::json;
+use shared_types::*;
+
+fn config() -> VoteConfig {
+    VoteConfig {
+        title: "Budget".to_string(),
+        description: "Approve the Q3 budget".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: json!({}),
+        commitment_duration_hours: 24,
+        reveal_duration_hours: 24,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }
+}
+
+fn fields(errors: &[FieldError]) -> Vec<&str> {
+    errors.iter().map(|e| e.field.as_str()).collect()
+}
+
+#[test]
+fn test_valid_requests_pass() {
+    assert!(CreateVoteRequest { config: config(), client_request_id: None }.validate().is_empty());
+    let commit = CommitRequest { voter: "alice".to_string(), commitment_hash: "a".repeat(64), salt: "s".to_string(), range_proof: None };
+    assert!(commit.validate().is_empty());
+}
+
+#[test]
+fn test_every_invalid_field_is_reported() {
+    let mut config = config();
+    config.title = "  ".to_string();
+    config.reveal_duration_hours = 0;
+
+    let errors = CreateVoteRequest { config, client_request_id: None }.validate();
+    assert_eq!(fields(&errors), vec!["config.title", "config.reveal_duration_hours"]);
+}
+
+#[test]
+fn test_client_request_id_length_is_checked() {
+    let request = |id: String| CreateVoteRequest { config: config(), client_request_id: Some(id) };
+    assert!(request("retry-1".to_string()).validate().is_empty());
+    assert_eq!(fields(&request(String::new()).validate()), vec!["client_request_id"]);
+    assert_eq!(fields(&request("x".repeat(129)).validate()), vec!["client_request_id"]);
+}
+
+#[test]
+fn test_invalid_salt_is_a_field_error() {
+    let reveal = RevealRequest { voter: "alice".to_string(), value: json!("yes"), salt: "x".repeat(101), range_blinding: None, ciphertext: None };
+    let errors = reveal.validate();
+    assert_eq!(fields(&errors), vec!["salt"]);
+
+    let commit = CommitRequest { voter: "alice".to_string(), commitment_hash: "zz".repeat(32), salt: String::new(), range_proof: None };
+    assert_eq!(fields(&commit.validate()), vec!["commitment_hash", "salt"]);
+}
+
+#[test]
+fn test_encrypted_reveal_replaces_value_and_salt() {
+    let reveal: RevealRequest = serde_json::from_value(json!({ "voter": "alice", "ciphertext": "ab".repeat(64) })).unwrap();
+    assert!(reveal.validate().is_empty());
+
+    let garbled = RevealRequest { ciphertext: Some("not hex".to_string()), ..reveal.clone() };
+    assert_eq!(fields(&garbled.validate()), vec!["ciphertext"]);
+
+    let both = RevealRequest { value: json!("yes"), salt: "pepper".to_string(), ..reveal };
+    assert_eq!(fields(&both.validate()), vec!["ciphertext"]);
+}
+
+#[cfg(feature = "axum")]
+mod extractor {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        extract::{FromRequest, Request},
+        response::IntoResponse,
+    };
+
+    async fn extract<T>(body: serde_json::Value) -> Result<T, serde_json::Value>
+    where
+        T: serde::de::DeserializeOwned + Validate,
+    {
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        match ValidatedJson::<T>::from_request(request, &()).await {
+            Ok(ValidatedJson(value)) => Ok(value),
+            Err(error) => {
+                let response = error.into_response();
+                assert_eq!(response.status(), 422);
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                Err(serde_json::from_slice(&body).unwrap())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_title_is_rejected_before_handler() {
+        let mut body = serde_json::to_value(CreateVoteRequest { config: config(), client_request_id: None }).unwrap();
+        body["config"].as_object_mut().unwrap().remove("title");
+
+        let error = extract::<CreateVoteRequest>(body).await.unwrap_err();
+        assert_eq!(error["code"], "request.validation_failed");
+        assert_eq!(error["details"]["fields"][0]["field"], "config.title");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_salt_is_rejected_before_handler() {
+        let body = json!({ "voter": "alice", "value": "yes", "salt": "" });
+
+        let error = extract::<RevealRequest>(body).await.unwrap_err();
+        assert_eq!(error["details"]["fields"][0]["field"], "salt");
+        assert_eq!(error["details"]["fields"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_valid_body_reaches_handler() {
+        let body = json!({ "voter": "alice", "value": "yes", "salt": "pepper" });
+        let reveal = extract::<RevealRequest>(body).await.unwrap();
+        assert_eq!(reveal.salt, "pepper");
+    }
+}
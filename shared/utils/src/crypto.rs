@@ -1,3 +1,8 @@
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use hkdf::Hkdf;
+use rand::RngCore;
 use sha2::{Sha256, Digest};
 use hex;
 use uuid::Uuid;
@@ -45,3 +50,162 @@ pub fn hash_with_algorithm(data: &str, algorithm: &str) -> String {
         }
     }
 }
+
+/// Combine public per-voter values (e.g. revealed salts) into a single hex seed.
+///
+/// Parts are sorted first so the seed does not depend on submission order, and
+/// anyone holding the same values can recompute it.
+pub fn combined_seed<S: AsRef<str>>(parts: &[S]) -> String {
+    let mut sorted: Vec<&str> = parts.iter().map(|p| p.as_ref()).collect();
+    sorted.sort_unstable();
+    let mut hasher = Sha256::new();
+    for part in sorted {
+        hasher.update(part.as_bytes());
+        hasher.update([0u8]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Bytes of an X25519 key, including the ephemeral key leading each ECIES ciphertext
+const ECIES_KEY_LEN: usize = 32;
+/// Bytes of the Poly1305 tag closing each ECIES or sealed ciphertext
+const ECIES_TAG_LEN: usize = 16;
+/// Bytes of the ChaCha20-Poly1305 nonce; random and leading each sealed ciphertext
+const SEAL_NONCE_LEN: usize = 12;
+/// HKDF info binding derived keys to this scheme
+const ECIES_INFO: &[u8] = b"decentralized-decision-vote/ecies/v2";
+
+/// X25519 key pair for ECIES, both halves hex encoded
+#[derive(Debug, Clone)]
+pub struct EciesKeyPair {
+    pub secret_key: String,
+    pub public_key: String,
+}
+
+/// Why an ECIES ciphertext could not be produced or opened
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EciesError {
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Malformed ciphertext")]
+    MalformedCiphertext,
+    #[error("Ciphertext failed authentication")]
+    AuthenticationFailed,
+}
+
+/// Generate a fresh X25519 key pair
+pub fn generate_ecies_keypair() -> EciesKeyPair {
+    let secret = random_key();
+    EciesKeyPair {
+        secret_key: hex::encode(secret),
+        public_key: hex::encode(MontgomeryPoint::mul_base_clamped(secret).as_bytes()),
+    }
+}
+
+/// Encrypt `plaintext` to the hex `public_key`.
+///
+/// The hex result is an ephemeral X25519 public key followed by a ChaCha20-Poly1305 ciphertext.
+/// The AEAD key and nonce are derived with HKDF-SHA256 from the X25519 shared secret; the
+/// ephemeral key is authenticated as associated data.
+pub fn ecies_encrypt(public_key: &str, plaintext: &[u8]) -> Result<String, EciesError> {
+    let recipient = decode_ecies_key(public_key)?;
+    let ephemeral = random_key();
+    let ephemeral_public = MontgomeryPoint::mul_base_clamped(ephemeral).to_bytes();
+    let shared = MontgomeryPoint(recipient).mul_clamped(ephemeral);
+    let (key, nonce) = ecies_keys(&shared, &ephemeral_public, &recipient)?;
+
+    let encrypted = ChaCha20Poly1305::new(&key)
+        .encrypt(&nonce, Payload { msg: plaintext, aad: &ephemeral_public })
+        .expect("ChaCha20-Poly1305 encrypts any message that fits in memory");
+    Ok(hex::encode([ephemeral_public.as_slice(), &encrypted].concat()))
+}
+
+/// Open a ciphertext produced by [`ecies_encrypt`] with the hex `secret_key`
+pub fn ecies_decrypt(secret_key: &str, ciphertext: &str) -> Result<Vec<u8>, EciesError> {
+    let secret = decode_ecies_key(secret_key)?;
+    let bytes = hex::decode(ciphertext).map_err(|_| EciesError::MalformedCiphertext)?;
+    if bytes.len() < ECIES_KEY_LEN + ECIES_TAG_LEN {
+        return Err(EciesError::MalformedCiphertext);
+    }
+    let (ephemeral_public, encrypted) = bytes.split_at(ECIES_KEY_LEN);
+    let ephemeral_public: [u8; ECIES_KEY_LEN] = ephemeral_public.try_into().expect("split at key length");
+    let recipient = MontgomeryPoint::mul_base_clamped(secret).to_bytes();
+    let shared = MontgomeryPoint(ephemeral_public).mul_clamped(secret);
+    let (key, nonce) = ecies_keys(&shared, &ephemeral_public, &recipient)?;
+
+    ChaCha20Poly1305::new(&key)
+        .decrypt(&nonce, Payload { msg: encrypted, aad: &ephemeral_public })
+        .map_err(|_| EciesError::AuthenticationFailed)
+}
+
+/// Whether `ciphertext` has the shape of an [`ecies_encrypt`] result; says nothing about whether it opens
+pub fn is_ecies_ciphertext(ciphertext: &str) -> bool {
+    ciphertext.len() >= 2 * (ECIES_KEY_LEN + ECIES_TAG_LEN) && hex::decode(ciphertext).is_ok()
+}
+
+/// Derive a 32-byte symmetric key for `purpose` from an operator-supplied secret of any length
+pub fn derive_key(secret: &str, purpose: &str) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, secret.as_bytes())
+        .expand(purpose.as_bytes(), &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+/// Encrypt `plaintext` at rest under `key` with ChaCha20-Poly1305, binding it to `aad`.
+///
+/// The hex result is a random nonce followed by the ciphertext and tag.
+pub fn seal(key: &[u8; 32], plaintext: &[u8], aad: &[u8]) -> String {
+    let mut nonce = [0u8; SEAL_NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let encrypted = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+        .expect("ChaCha20-Poly1305 encrypts any message that fits in memory");
+    hex::encode([nonce.as_slice(), &encrypted].concat())
+}
+
+/// Open a ciphertext produced by [`seal`] with the same key and associated data
+pub fn open(key: &[u8; 32], sealed: &str, aad: &[u8]) -> Result<Vec<u8>, EciesError> {
+    let bytes = hex::decode(sealed).map_err(|_| EciesError::MalformedCiphertext)?;
+    if bytes.len() < SEAL_NONCE_LEN + ECIES_TAG_LEN {
+        return Err(EciesError::MalformedCiphertext);
+    }
+    let (nonce, encrypted) = bytes.split_at(SEAL_NONCE_LEN);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: encrypted, aad })
+        .map_err(|_| EciesError::AuthenticationFailed)
+}
+
+fn random_key() -> [u8; ECIES_KEY_LEN] {
+    let mut key = [0u8; ECIES_KEY_LEN];
+    rand::thread_rng().fill_bytes(&mut key);
+    key
+}
+
+fn decode_ecies_key(key: &str) -> Result<[u8; ECIES_KEY_LEN], EciesError> {
+    hex::decode(key)
+        .map_err(|e| EciesError::InvalidKey(e.to_string()))?
+        .try_into()
+        .map_err(|_| EciesError::InvalidKey(format!("expected {} bytes", ECIES_KEY_LEN)))
+}
+
+/// AEAD key and nonce for one ciphertext, bound to both public keys
+///
+/// Every ciphertext uses a fresh ephemeral key, so the derived nonce is never reused under a key.
+fn ecies_keys(
+    shared: &MontgomeryPoint,
+    ephemeral_public: &[u8; ECIES_KEY_LEN],
+    recipient: &[u8; ECIES_KEY_LEN],
+) -> Result<(Key, Nonce), EciesError> {
+    // A low-order public key forces an all-zero shared secret
+    if shared.as_bytes() == &[0u8; 32] {
+        return Err(EciesError::InvalidKey("low-order public key".to_string()));
+    }
+    let salt = [ephemeral_public.as_slice(), recipient.as_slice()].concat();
+    let mut okm = [0u8; 32 + SEAL_NONCE_LEN];
+    Hkdf::<Sha256>::new(Some(&salt), shared.as_bytes())
+        .expand(ECIES_INFO, &mut okm)
+        .expect("44 bytes is a valid HKDF-SHA256 output length");
+    let (key, nonce) = okm.split_at(32);
+    Ok((*Key::from_slice(key), *Nonce::from_slice(nonce)))
+}
@@ -1,7 +1,11 @@
+pub mod clock;
 pub mod crypto;
 pub mod validation;
 pub mod serialization;
+pub mod signing;
 
+pub use clock::*;
 pub use crypto::*;
 pub use validation::*;
 pub use serialization::*;
+pub use signing::*;
@@ -0,0 +1,122 @@
+//! Fixed input/output vectors for the crypto helpers.
+//!
+//! Hash and signature outputs were computed independently with Python's hashlib/hmac; the ECIES and
+//! sealed ciphertexts open with Python's `cryptography` X25519/ChaCha20-Poly1305 under the RFC 7748
+//! key pair. Any change in output fails here.
+
+use shared_utils::*;
+
+/// RFC 7748 section 6.1, Alice's X25519 key pair
+const X25519_SECRET: &str = "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+const X25519_PUBLIC: &str = "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+
+#[test]
+fn test_hash_value_vectors() {
+    let vectors = [
+        ("", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        ("abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        ("héllo wörld", "a1003f7d04a4115711d0b48a2eaf1359ce565d2d2a6fd65098dfcffadeeef59f"),
+        ("投票 🗳️", "6fdd2432b3a13de6f46084ea4b9090fc20ea710242d60f724d2c5c378e7d6b31"),
+    ];
+    for (input, expected) in vectors {
+        assert_eq!(hash_value(input), expected, "hash_value({:?})", input);
+        assert_eq!(hash_with_algorithm(input, "SHA256"), expected, "hash_with_algorithm({:?})", input);
+    }
+    // Unknown algorithms fall back to SHA-256
+    assert_eq!(hash_with_algorithm("abc", "blake3"), hash_value("abc"));
+}
+
+#[test]
+fn test_commitment_vectors() {
+    let vectors = [
+        ("", "", "e7ac0786668e0ff0f02b62bd04f45ff636fd82db63b1104601c975dc005f3a67"),
+        ("yes", "7f3c2a10-salt", "1e6efa092c4fb7c695924e7aca226733e831dfd667cffc884c3e1ccb7d6ac0a3"),
+        ("赞成", "sál", "debc32ec8c36059bb3f7dfdeae0da96e4f98fec5a12e5776aa3ff119126cafae"),
+    ];
+    for (value, salt, expected) in vectors {
+        assert_eq!(create_commitment(value, salt), expected, "create_commitment({:?}, {:?})", value, salt);
+        assert!(verify_commitment(value, salt, expected));
+    }
+    assert!(!verify_commitment("no", "7f3c2a10-salt", vectors[1].2));
+}
+
+#[test]
+fn test_combined_seed_vectors() {
+    let empty: [&str; 0] = [];
+    assert_eq!(combined_seed(&empty), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+
+    let seed = "ac41139f5ec5ec83f33330fca8354937af10fce98b0b5fabe47cccd101d20a62";
+    assert_eq!(combined_seed(&["salt-b", "salt-a", "salt-c"]), seed);
+    assert_eq!(combined_seed(&["salt-c", "salt-b", "salt-a"]), seed);
+
+    assert_eq!(
+        combined_seed(&["日本", "ünïcode"]),
+        "796916d6dfc32ad01462c5225a9101fc19e04c7328d7c50f8f9de4a39ebe4a75"
+    );
+}
+
+#[test]
+fn test_webhook_signature_vectors() {
+    let vectors: [(&str, i64, &[u8], &str); 3] = [
+        ("", 0, b"", "sha256=b849d5a581847b281957065739df36df2463d1977ea8d6e1e4e6cf33fadc68c3"),
+        (
+            "whsec_test",
+            1_700_000_000,
+            br#"{"event":"vote.completed"}"#,
+            "sha256=dc950e73dca21d55d3ddcbf1c0c51912aa1054af29cf78179d98f8dbb5eb8361",
+        ),
+        (
+            "sécret",
+            1_700_000_000,
+            "{\"title\":\"投票\"}".as_bytes(),
+            "sha256=7b6b14f86549c515eb0aef660a6fa6407ecbd942690101733aabd31ed5402675",
+        ),
+    ];
+    for (secret, timestamp, body, expected) in vectors {
+        assert_eq!(sign_webhook_payload(secret, timestamp, body), expected);
+        assert_eq!(
+            verify_webhook_signature_at(secret, body, expected, &timestamp.to_string(), 300, timestamp),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn test_ecies_decryption_vectors() {
+    let vectors: [(&str, &[u8]); 2] = [
+        (
+            "1dfb197007376d9c5aa87ef59163201d25044cf639f8dff5f81ffd97e9221a46\
+             829610eb1e7732acc7e5bc139c82a034",
+            b"",
+        ),
+        (
+            "815b1e3a9239f7262af8c8e2bd15befc5d8357ecc53d87f526a5875272db5514\
+             0d64596d56fbbc09ba2e217d299b97e6\
+             1698d5430f2bfbe9b0a5",
+            "赞成 ✓".as_bytes(),
+        ),
+    ];
+    for (ciphertext, plaintext) in vectors {
+        assert!(is_ecies_ciphertext(ciphertext));
+        assert_eq!(ecies_decrypt(X25519_SECRET, ciphertext).unwrap(), plaintext);
+    }
+
+    // Encryption is randomised, but must still open under the fixed key pair
+    let fresh = ecies_encrypt(X25519_PUBLIC, "赞成 ✓".as_bytes()).unwrap();
+    assert_eq!(ecies_decrypt(X25519_SECRET, &fresh).unwrap(), "赞成 ✓".as_bytes());
+}
+
+#[test]
+fn test_sealing_vectors() {
+    let key = derive_key("kek", "purpose");
+    assert_eq!(hex::encode(key), "a95a08fb3cff1e61d08277093ae5435d96cb3318dbd3d70bb3235a686a95a4b3");
+
+    let sealed = "58e2809bdb65a5b8c6cc27c492d22d18b0ffa1edbcd3565254cc5cd97c6a1346dee1";
+    assert_eq!(open(&key, sealed, b"vote-1").unwrap(), b"secret");
+    assert_eq!(open(&key, sealed, b"vote-2"), Err(EciesError::AuthenticationFailed));
+    assert_eq!(open(&derive_key("other", "purpose"), sealed, b"vote-1"), Err(EciesError::AuthenticationFailed));
+
+    let fresh = seal(&key, b"secret", b"vote-1");
+    assert_ne!(fresh, sealed);
+    assert_eq!(open(&key, &fresh, b"vote-1").unwrap(), b"secret");
+}
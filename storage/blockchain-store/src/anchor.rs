@@ -0,0 +1,87 @@
+//! 承诺上链锚定
+//!
+//! 将投票承诺哈希写入区块链，得到的交易哈希与区块号作为锚定记录，
+//! 供结果验证时与存储中的承诺比对。批量模式下只写入一批承诺的 Merkle 根
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::json;
+use shared_types::{Commitment, CommitmentAnchor, VoteError};
+use vote_engine::{AnchorReceipt, CommitmentAnchorer};
+
+use crate::{BlockchainManager, BlockchainType};
+
+/// 通过 [`BlockchainManager`] 锚定承诺
+pub struct BlockchainAnchorer {
+    manager: Arc<BlockchainManager>,
+    blockchain_type: BlockchainType,
+}
+
+impl BlockchainAnchorer {
+    /// 锚定到配置中的默认区块链
+    pub fn new(manager: Arc<BlockchainManager>) -> Self {
+        let blockchain_type = manager.get_config().default_blockchain.clone();
+        Self { manager, blockchain_type }
+    }
+
+    /// 锚定到指定区块链
+    pub fn with_blockchain(mut self, blockchain_type: BlockchainType) -> Self {
+        self.blockchain_type = blockchain_type;
+        self
+    }
+
+    /// 承诺在链上的存储键
+    pub fn storage_key(vote_id: &str, voter: &str) -> String {
+        format!("commitment:{}:{}", vote_id, voter)
+    }
+
+    /// 批量承诺根在链上的存储键
+    pub fn batch_storage_key(root: &str) -> String {
+        format!("commitment-batch:{}", root)
+    }
+}
+
+#[async_trait]
+impl CommitmentAnchorer for BlockchainAnchorer {
+    async fn anchor(&self, commitment: &Commitment) -> Result<CommitmentAnchor, VoteError> {
+        let key = Self::storage_key(&commitment.vote_id, &commitment.voter);
+        let metadata = json!({
+            "vote_id": commitment.vote_id,
+            "voter": commitment.voter,
+            "commitment_id": commitment.id,
+        });
+        let transaction = self.manager
+            .store_data(&self.blockchain_type, &key, commitment.commitment_hash.as_bytes(), Some(metadata))
+            .await
+            .map_err(|e| VoteError::StorageError { message: format!("Failed to anchor commitment: {}", e) })?;
+
+        Ok(CommitmentAnchor {
+            vote_id: commitment.vote_id.clone(),
+            voter: commitment.voter.clone(),
+            commitment_hash: commitment.commitment_hash.clone(),
+            chain: format!("{:?}", self.blockchain_type),
+            tx_hash: transaction.tx_hash,
+            block_number: transaction.block_number,
+            anchored_at: transaction.timestamp,
+            batch_root: None,
+            batch_proof: Vec::new(),
+        })
+    }
+
+    async fn anchor_root(&self, root: &str, batch_size: usize) -> Result<AnchorReceipt, VoteError> {
+        let key = Self::batch_storage_key(root);
+        let metadata = json!({ "batch_size": batch_size });
+        let transaction = self.manager
+            .store_data(&self.blockchain_type, &key, root.as_bytes(), Some(metadata))
+            .await
+            .map_err(|e| VoteError::StorageError { message: format!("Failed to anchor commitment batch: {}", e) })?;
+
+        Ok(AnchorReceipt {
+            chain: format!("{:?}", self.blockchain_type),
+            tx_hash: transaction.tx_hash,
+            block_number: transaction.block_number,
+            anchored_at: transaction.timestamp,
+        })
+    }
+}
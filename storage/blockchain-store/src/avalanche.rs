@@ -10,7 +10,8 @@ use sha2::{Sha256, Digest};
 
 use crate::{
     BlockchainStorage, BlockchainClient, NetworkConfig, StorageTransaction, 
-    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError
+    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError,
+    ConfirmationPoller,
 };
 
 /// Avalanche 存储实现
@@ -131,8 +132,9 @@ impl BlockchainStorage for AvalancheStorage {
     }
 
     async fn get_metadata(&self, key: &str) -> Result<StorageMetadata> {
-        // 简化实现，实际应该从区块链查询
-        Err(BlockchainError::DataNotFound(format!("Metadata not found for key: {}", key)))
+        // 简化实现，写入交易的信息实际应该从区块链查询
+        let data = self.retrieve_data(key).await?;
+        Ok(StorageMetadata::for_data(key, &data, self.get_blockchain_type(), &self.network_config))
     }
 
     async fn exists(&self, key: &str) -> Result<bool> {
@@ -217,15 +219,11 @@ impl BlockchainClient for AvalancheStorage {
     }
 
     async fn wait_for_confirmation(&self, tx_hash: &str) -> Result<StorageTransaction> {
-        // 简化实现，实际需要轮询交易状态
-        Ok(StorageTransaction {
-            tx_hash: tx_hash.to_string(),
-            block_number: Some(12345),
-            gas_used: Some(25000),
-            status: TransactionStatus::Confirmed,
-            timestamp: chrono::Utc::now(),
-            data_hash: "".to_string(),
-            storage_key: "".to_string(),
-        })
+        let transaction = crate::ethereum::mined_transaction(&self.web3, tx_hash).await?;
+        if transaction.status == TransactionStatus::Reverted {
+            return Ok(transaction);
+        }
+        let required = self.network_config.required_confirmations(&self.get_blockchain_type());
+        ConfirmationPoller::default().wait(self, transaction, required).await
     }
 }
@@ -75,6 +75,7 @@ impl Default for BlockchainConfig {
             gas_limit: Some(21000),
             timeout_seconds: 30,
             retry_attempts: 3,
+            required_confirmations: None,
         });
 
         // 以太坊测试网配置
@@ -86,6 +87,7 @@ impl Default for BlockchainConfig {
             gas_limit: Some(21000),
             timeout_seconds: 30,
             retry_attempts: 3,
+            required_confirmations: None,
         });
 
         // Solana 主网配置
@@ -97,6 +99,7 @@ impl Default for BlockchainConfig {
             gas_limit: None,
             timeout_seconds: 30,
             retry_attempts: 3,
+            required_confirmations: None,
         });
 
         Self {
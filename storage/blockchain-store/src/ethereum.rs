@@ -2,22 +2,32 @@
 
 use async_trait::async_trait;
 use web3::{
-    types::{Address, H256, U256, Bytes, TransactionRequest},
+    types::{Address, BlockNumber, H256, U256, Bytes, TransactionId, TransactionRequest},
     Web3, Transport, Http,
 };
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 use sha2::{Sha256, Digest};
 
 use crate::{
     BlockchainStorage, BlockchainClient, NetworkConfig, StorageTransaction, 
-    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError
+    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError,
+    ConfirmationPoller,
 };
+use crate::nonce::{NonceManager, NonceSource, PendingTransaction};
+
+/// 网络未配置 gas 价格时使用的默认值（gwei）
+const DEFAULT_GAS_PRICE_GWEI: u128 = 20;
 
 /// 以太坊存储实现
 pub struct EthereumStorage {
     web3: Web3<Http>,
     network_config: NetworkConfig,
     contract_address: Option<Address>,
+    /// 发送交易的账户，设置后写入经过 nonce 管理
+    sender: Option<Address>,
+    nonces: Arc<NonceManager>,
 }
 
 impl EthereumStorage {
@@ -38,9 +48,85 @@ impl EthereumStorage {
             web3,
             network_config,
             contract_address: None,
+            sender: None,
+            nonces: Arc::new(NonceManager::new()),
         })
     }
 
+    /// 设置发送交易的账户
+    pub fn set_sender_address(&mut self, address: &str) -> Result<()> {
+        self.sender = Some(
+            Address::from_str(address)
+                .map_err(|e| BlockchainError::InvalidConfig(format!("Invalid sender address: {}", e)))?
+        );
+        Ok(())
+    }
+
+    /// 与使用同一账户的其他实例共享 nonce 管理器
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
+    /// 网络配置的 gas 价格（wei）
+    fn gas_price_wei(&self) -> u128 {
+        let gwei = self.network_config.gas_price.as_deref()
+            .and_then(|price| price.parse::<u128>().ok())
+            .unwrap_or(DEFAULT_GAS_PRICE_GWEI);
+        gwei * 1_000_000_000
+    }
+
+    /// 以更高手续费、相同 nonce 重发超过 `older_than` 仍未确认的交易，返回替换后的交易
+    ///
+    /// 替换交易沿用原交易的接收方、金额和数据；节点查不到原交易时无法重建，返回错误
+    pub async fn replace_stuck_transactions(&self, older_than: Duration) -> Result<Vec<PendingTransaction>> {
+        let Some(sender) = self.sender else {
+            return Ok(Vec::new());
+        };
+        let account = format!("{:?}", sender);
+        let mut replaced = Vec::new();
+        for stuck in self.nonces.stuck(&account, older_than).await {
+            let original_hash = H256::from_str(&stuck.tx_hash)
+                .map_err(|e| BlockchainError::TransactionFailed(format!("Invalid transaction hash {}: {}", stuck.tx_hash, e)))?;
+            let original = self.web3.eth().transaction(TransactionId::Hash(original_hash)).await
+                .map_err(|e| BlockchainError::Network(format!("Failed to get transaction {}: {}", stuck.tx_hash, e)))?
+                .ok_or_else(|| BlockchainError::TransactionFailed(format!(
+                    "Cannot replace transaction {}: the node does not know it", stuck.tx_hash
+                )))?;
+            let gas_price = self.nonces.bumped_gas_price(&account, stuck.nonce).await?;
+            tracing::warn!(
+                "Replacing stuck transaction {} (nonce {}) from {} with gas price {}",
+                stuck.tx_hash, stuck.nonce, account, gas_price
+            );
+            let request = TransactionRequest {
+                from: sender,
+                to: original.to,
+                gas: Some(original.gas),
+                gas_price: Some(U256::from(gas_price)),
+                value: Some(original.value),
+                data: Some(original.input),
+                nonce: Some(U256::from(stuck.nonce)),
+                ..Default::default()
+            };
+            let tx_hash = format!("{:?}", self.send(request).await?);
+            self.nonces.record_pending(&account, stuck.nonce, &tx_hash, gas_price).await;
+            replaced.push(PendingTransaction {
+                tx_hash,
+                gas_price,
+                submitted_at: chrono::Utc::now(),
+                replacements: stuck.replacements + 1,
+                ..stuck
+            });
+        }
+        Ok(replaced)
+    }
+
+    /// 由节点签名并发送交易，返回交易哈希
+    async fn send(&self, request: TransactionRequest) -> Result<H256> {
+        self.web3.eth().send_transaction(request).await
+            .map_err(|e| BlockchainError::TransactionFailed(format!("Failed to send transaction: {}", e)))
+    }
+
     /// 设置智能合约地址
     pub fn set_contract_address(&mut self, address: &str) -> Result<()> {
         self.contract_address = Some(
@@ -94,6 +180,41 @@ impl BlockchainStorage for EthereumStorage {
             ));
         }
 
+        // 设置了发送账户时，数据以分配到的 nonce 真正发送上链
+        if let Some(sender) = self.sender {
+            let account = format!("{:?}", sender);
+            let nonce = self.nonces.allocate(self, &account).await?;
+            let gas_price = self.gas_price_wei();
+            let request = TransactionRequest {
+                from: sender,
+                // 没有合约时把数据写入发给自己的交易
+                to: Some(self.contract_address.unwrap_or(sender)),
+                gas_price: Some(U256::from(gas_price)),
+                data: Some(Bytes(data.to_vec())),
+                nonce: Some(U256::from(nonce)),
+                ..Default::default()
+            };
+            return match self.send(request).await {
+                Ok(tx_hash) => {
+                    let tx_hash = format!("{:?}", tx_hash);
+                    self.nonces.record_pending(&account, nonce, &tx_hash, gas_price).await;
+                    Ok(StorageTransaction {
+                        tx_hash,
+                        block_number: None,
+                        gas_used: None,
+                        status: TransactionStatus::Pending,
+                        timestamp: chrono::Utc::now(),
+                        data_hash: hex::encode(Sha256::digest(data)),
+                        storage_key: key.to_string(),
+                    })
+                }
+                Err(e) => {
+                    self.nonces.release(&account, nonce).await;
+                    Err(e)
+                }
+            };
+        }
+
         if let Some(_contract) = self.contract_address {
             self.store_via_contract(key, data).await
         } else {
@@ -131,8 +252,9 @@ impl BlockchainStorage for EthereumStorage {
     }
 
     async fn get_metadata(&self, key: &str) -> Result<StorageMetadata> {
-        // 简化实现，实际应该从区块链查询
-        Err(BlockchainError::DataNotFound(format!("Metadata not found for key: {}", key)))
+        // 简化实现，写入交易的信息实际应该从区块链查询
+        let data = self.retrieve_data(key).await?;
+        Ok(StorageMetadata::for_data(key, &data, self.get_blockchain_type(), &self.network_config))
     }
 
     async fn exists(&self, key: &str) -> Result<bool> {
@@ -217,15 +339,57 @@ impl BlockchainClient for EthereumStorage {
     }
 
     async fn wait_for_confirmation(&self, tx_hash: &str) -> Result<StorageTransaction> {
-        // 简化实现，实际需要轮询交易状态
-        Ok(StorageTransaction {
-            tx_hash: tx_hash.to_string(),
-            block_number: Some(12345),
-            gas_used: Some(21000),
-            status: TransactionStatus::Confirmed,
-            timestamp: chrono::Utc::now(),
-            data_hash: "".to_string(),
-            storage_key: "".to_string(),
-        })
+        let transaction = mined_transaction(&self.web3, tx_hash).await?;
+        let transaction = if transaction.status == TransactionStatus::Reverted {
+            transaction
+        } else {
+            let required = self.network_config.required_confirmations(&self.get_blockchain_type());
+            ConfirmationPoller::default().wait(self, transaction, required).await?
+        };
+        // 交易上链后（含回滚）释放其占用的 nonce 跟踪
+        if let (Some(sender), TransactionStatus::Confirmed | TransactionStatus::Reverted) = (self.sender, &transaction.status) {
+            let account = format!("{:?}", sender);
+            let confirmed = self.nonces.pending(&account).await.into_iter().find(|pending| pending.tx_hash == tx_hash);
+            if let Some(pending) = confirmed {
+                self.nonces.confirm(&account, pending.nonce).await;
+            }
+        }
+        Ok(transaction)
+    }
+}
+
+/// 按收据查询交易所在区块，EVM 兼容链共用
+///
+/// 还没有收据的交易尚未上链，返回错误；收据状态为 0 的交易标记为 `Reverted`
+pub(crate) async fn mined_transaction<T: Transport>(web3: &Web3<T>, tx_hash: &str) -> Result<StorageTransaction> {
+    let hash = H256::from_str(tx_hash)
+        .map_err(|e| BlockchainError::TransactionFailed(format!("Invalid transaction hash {}: {}", tx_hash, e)))?;
+    let receipt = web3.eth().transaction_receipt(hash).await
+        .map_err(|e| BlockchainError::Network(format!("Failed to get receipt for {}: {}", tx_hash, e)))?
+        .ok_or_else(|| BlockchainError::DataNotFound(format!("Transaction {} has not been mined", tx_hash)))?;
+    let status = if receipt.status.map(|status| status.as_u64()) == Some(0) {
+        TransactionStatus::Reverted
+    } else {
+        TransactionStatus::Pending
+    };
+    Ok(StorageTransaction {
+        tx_hash: tx_hash.to_string(),
+        block_number: receipt.block_number.map(|number| number.as_u64()),
+        gas_used: receipt.gas_used.map(|gas| gas.as_u64()),
+        status,
+        timestamp: chrono::Utc::now(),
+        data_hash: String::new(),
+        storage_key: String::new(),
+    })
+}
+
+#[async_trait]
+impl NonceSource for EthereumStorage {
+    async fn pending_nonce(&self, account: &str) -> Result<u64> {
+        let address = Address::from_str(account)
+            .map_err(|e| BlockchainError::InvalidConfig(format!("Invalid address: {}", e)))?;
+        let count = self.web3.eth().transaction_count(address, Some(BlockNumber::Pending)).await
+            .map_err(|e| BlockchainError::Network(format!("Failed to get transaction count: {}", e)))?;
+        Ok(count.as_u64())
     }
 }
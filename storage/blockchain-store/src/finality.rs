@@ -0,0 +1,114 @@
+//! 各链的最终性要求与确认轮询
+
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    BlockchainClient, BlockchainError, BlockchainType, NetworkConfig, Result, StorageMetadata,
+    StorageTransaction, TransactionStatus,
+};
+
+impl BlockchainType {
+    /// 该链默认需要的确认数（以太坊系为区块，Solana 为 slot）
+    ///
+    /// 基于 Tendermint 的链、Avalanche 与 Sui 出块即最终，L2 以排序器确认为准
+    pub fn default_required_confirmations(&self) -> u64 {
+        match self {
+            BlockchainType::Ethereum => 12,
+            BlockchainType::Solana => 32,
+            BlockchainType::Polygon => 128,
+            BlockchainType::BSC => 15,
+            BlockchainType::Arbitrum | BlockchainType::Optimism => 1,
+            BlockchainType::Cosmos | BlockchainType::Archway | BlockchainType::Injective => 1,
+            BlockchainType::Avalanche | BlockchainType::Sui => 1,
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// 网络配置了 `required_confirmations` 时以其为准，否则使用链类型的默认值
+    pub fn required_confirmations(&self, blockchain_type: &BlockchainType) -> u64 {
+        self.required_confirmations
+            .unwrap_or_else(|| blockchain_type.default_required_confirmations())
+    }
+}
+
+impl StorageMetadata {
+    /// 已读取数据的元信息，`required_confirmations` 取 `network` 对该链类型的要求
+    ///
+    /// 不含写入交易的信息：`tx_hash` 为空，`block_number` 为 `None`，`created_at` 为读取时刻
+    pub fn for_data(key: &str, data: &[u8], blockchain_type: BlockchainType, network: &NetworkConfig) -> Self {
+        Self {
+            key: key.to_string(),
+            data_hash: hex::encode(Sha256::digest(data)),
+            size: data.len() as u64,
+            required_confirmations: network.required_confirmations(&blockchain_type),
+            blockchain_type,
+            network: network.name.clone(),
+            tx_hash: String::new(),
+            block_number: None,
+            created_at: chrono::Utc::now(),
+            access_count: 0,
+        }
+    }
+}
+
+/// 交易在 `current_height` 时已获得的确认数，交易所在区块本身算一次
+pub fn confirmations(block_number: u64, current_height: u64) -> u64 {
+    if current_height < block_number {
+        0
+    } else {
+        current_height - block_number + 1
+    }
+}
+
+/// 轮询区块高度，直到交易获得所需确认数
+#[derive(Debug, Clone)]
+pub struct ConfirmationPoller {
+    /// 两次查询区块高度之间的间隔
+    pub poll_interval: Duration,
+    /// 最多查询次数，用尽后返回超时错误
+    pub max_polls: u32,
+}
+
+impl Default for ConfirmationPoller {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(2),
+            max_polls: 300,
+        }
+    }
+}
+
+impl ConfirmationPoller {
+    /// 等待 `transaction` 获得 `required` 个确认，成功时状态置为 `Confirmed`
+    ///
+    /// 没有区块号的交易无法计算确认数，原样返回
+    pub async fn wait<C: BlockchainClient + ?Sized>(
+        &self,
+        client: &C,
+        mut transaction: StorageTransaction,
+        required: u64,
+    ) -> Result<StorageTransaction> {
+        let Some(block_number) = transaction.block_number else {
+            return Ok(transaction);
+        };
+        let mut seen = 0;
+        for poll in 0..self.max_polls {
+            let height = client.get_block_height().await?;
+            seen = confirmations(block_number, height);
+            if seen >= required {
+                transaction.status = TransactionStatus::Confirmed;
+                return Ok(transaction);
+            }
+            if poll + 1 < self.max_polls {
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        }
+        Err(BlockchainError::Timeout(format!(
+            "transaction {} has {} of {} required confirmations",
+            transaction.tx_hash, seen, required
+        )))
+    }
+}
@@ -7,7 +7,7 @@ use sha2::{Sha256, Digest};
 
 use crate::{
     BlockchainStorage, BlockchainClient, NetworkConfig, StorageTransaction, 
-    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError
+    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError,
 };
 
 /// Injective 存储实现
@@ -111,8 +111,9 @@ impl BlockchainStorage for InjectiveStorage {
     }
 
     async fn get_metadata(&self, key: &str) -> Result<StorageMetadata> {
-        // 简化实现，实际应该从 Injective 查询
-        Err(BlockchainError::DataNotFound(format!("Metadata not found for key: {}", key)))
+        // 简化实现，写入交易的信息实际应该从 Injective 查询
+        let data = self.retrieve_data(key).await?;
+        Ok(StorageMetadata::for_data(key, &data, self.get_blockchain_type(), &self.network_config))
     }
 
     async fn exists(&self, key: &str) -> Result<bool> {
@@ -189,15 +190,9 @@ impl BlockchainClient for InjectiveStorage {
     }
 
     async fn wait_for_confirmation(&self, tx_hash: &str) -> Result<StorageTransaction> {
-        // 简化实现，实际需要轮询交易状态
-        Ok(StorageTransaction {
-            tx_hash: tx_hash.to_string(),
-            block_number: Some(12345),
-            gas_used: Some(180000),
-            status: TransactionStatus::Confirmed,
-            timestamp: chrono::Utc::now(),
-            data_hash: "".to_string(),
-            storage_key: "".to_string(),
-        })
+        // 没有 Injective 客户端就查不到交易所在区块，不能按确认数判断最终性
+        Err(BlockchainError::Unknown(format!(
+            "Cannot look up transaction {} on Injective: confirmation tracking is not supported", tx_hash
+        )))
     }
 }
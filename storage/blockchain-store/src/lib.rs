@@ -14,11 +14,19 @@ pub mod sui;
 pub mod error;
 pub mod traits;
 pub mod manager;
+pub mod anchor;
+pub mod multi_chain;
+pub mod finality;
+pub mod nonce;
 
 pub use config::BlockchainConfig;
 pub use error::{BlockchainError, Result};
 pub use traits::{BlockchainStorage, BlockchainClient};
 pub use manager::BlockchainManager;
+pub use anchor::BlockchainAnchorer;
+pub use multi_chain::{MultiChainStorage, MultiChainReceipt, ChainTransaction, ChainFailure};
+pub use finality::ConfirmationPoller;
+pub use nonce::{NonceManager, NonceSource, PendingTransaction};
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -49,6 +57,9 @@ pub struct NetworkConfig {
     pub gas_limit: Option<u64>,
     pub timeout_seconds: u64,
     pub retry_attempts: u32,
+    /// 交易视为最终所需的确认数，为空时使用链类型的默认值
+    #[serde(default)]
+    pub required_confirmations: Option<u64>,
 }
 
 /// 存储交易信息
@@ -84,6 +95,9 @@ pub struct StorageMetadata {
     pub block_number: Option<u64>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub access_count: u64,
+    /// 写入时该网络要求的确认数
+    #[serde(default)]
+    pub required_confirmations: u64,
 }
 
 /// 区块链存储统计
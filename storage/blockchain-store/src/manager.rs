@@ -10,7 +10,7 @@ use async_trait::async_trait;
 use crate::{
     BlockchainConfig, BlockchainType, NetworkConfig, 
     BlockchainStorage, BlockchainClient, StorageTransaction, 
-    StorageMetadata, StorageStats, Result
+    StorageMetadata, StorageStats, BlockchainError, Result
 };
 use crate::ethereum::EthereumStorage;
 use crate::solana::SolanaStorage;
@@ -118,13 +118,17 @@ impl BlockchainManager {
         data: &[u8],
         metadata: Option<serde_json::Value>,
     ) -> Result<StorageTransaction> {
-        let storage = self.get_storage(blockchain_type).await?;
+        let storages = self.storages.read().await;
+        let storage = storages.get(blockchain_type)
+            .ok_or_else(|| BlockchainError::DataNotFound(format!("Storage for {:?} not found", blockchain_type)))?;
         storage.store_data(key, data, metadata).await
     }
 
     /// 从指定区块链检索数据
     pub async fn retrieve_data(&self, blockchain_type: &BlockchainType, key: &str) -> Result<Vec<u8>> {
-        let storage = self.get_storage(blockchain_type).await?;
+        let storages = self.storages.read().await;
+        let storage = storages.get(blockchain_type)
+            .ok_or_else(|| BlockchainError::DataNotFound(format!("Storage for {:?} not found", blockchain_type)))?;
         storage.retrieve_data(key).await
     }
 
@@ -0,0 +1,390 @@
+//! 多链冗余存储
+//!
+//! 把同一份数据并发写入多条链，达到法定确认数即视为持久化成功，
+//! 个别链失败不影响整体结果；读取时发现缺少数据的链会在后台补写（读修复）
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tokio::task::JoinSet;
+
+use crate::{
+    BlockchainError, BlockchainStorage, BlockchainType, NetworkConfig, NetworkStats, Result,
+    StorageMetadata, StorageStats, StorageTransaction, TransactionStatus,
+};
+
+/// 每条链上每个键默认最多读修复的次数
+pub const DEFAULT_MAX_REPAIR_ATTEMPTS: u32 = 3;
+
+/// 单条链上的写入结果
+#[derive(Debug, Clone)]
+pub struct ChainTransaction {
+    pub network: String,
+    pub blockchain_type: BlockchainType,
+    pub transaction: StorageTransaction,
+}
+
+/// 单条链的写入失败，未确认的交易也计入其中
+#[derive(Debug, Clone)]
+pub struct ChainFailure {
+    pub network: String,
+    pub error: String,
+}
+
+/// 一次多链写入的汇总
+#[derive(Debug, Clone)]
+pub struct MultiChainReceipt {
+    /// 已确认或已发送待确认（`Pending`）的交易，按返回先后排列
+    pub confirmed: Vec<ChainTransaction>,
+    /// 在返回前已失败的链；达到法定数后仍在进行的写入不会出现在这里
+    pub failures: Vec<ChainFailure>,
+    pub required_confirmations_count: usize,
+}
+
+impl MultiChainReceipt {
+    /// 合并为一笔交易：`tx_hash` 为 `网络:交易哈希` 以逗号连接，`gas_used` 为各链之和；
+    /// 不同链的区块号不可比较，因此 `block_number` 为空；任一链仍待确认时 `status` 为 `Pending`
+    pub fn combined(&self) -> StorageTransaction {
+        let first = &self.confirmed[0].transaction;
+        let tx_hash = self.confirmed.iter()
+            .map(|c| format!("{}:{}", c.network, c.transaction.tx_hash))
+            .collect::<Vec<_>>()
+            .join(",");
+        let gas_used = self.confirmed.iter().filter_map(|c| c.transaction.gas_used).reduce(|a, b| a + b);
+        let timestamp = self.confirmed.iter().map(|c| c.transaction.timestamp).max().unwrap_or(first.timestamp);
+        let status = if self.confirmed.iter().any(|c| c.transaction.status == TransactionStatus::Pending) {
+            TransactionStatus::Pending
+        } else {
+            TransactionStatus::Confirmed
+        };
+        StorageTransaction {
+            tx_hash,
+            block_number: None,
+            gas_used,
+            status,
+            timestamp,
+            data_hash: first.data_hash.clone(),
+            storage_key: first.storage_key.clone(),
+        }
+    }
+}
+
+/// 把写入分发到多个存储实例，`required_confirmations_count` 条链确认即成功
+///
+/// 读取类操作依次尝试各链，返回第一个成功的结果
+pub struct MultiChainStorage {
+    storages: Vec<Arc<dyn BlockchainStorage>>,
+    required_confirmations_count: usize,
+    network: NetworkConfig,
+    /// (网络名, 键) -> 已发起的读修复次数
+    repair_attempts: Arc<Mutex<HashMap<(String, String), u32>>>,
+    max_repair_attempts: u32,
+}
+
+impl MultiChainStorage {
+    /// 法定确认数须在 1 到存储实例数之间
+    pub fn new(storages: Vec<Arc<dyn BlockchainStorage>>, required_confirmations_count: usize) -> Result<Self> {
+        if required_confirmations_count == 0 || required_confirmations_count > storages.len() {
+            return Err(BlockchainError::InvalidConfig(format!(
+                "required_confirmations_count must be between 1 and {}, got {}",
+                storages.len(), required_confirmations_count
+            )));
+        }
+        let names: Vec<&str> = storages.iter().map(|s| s.get_network_config().name.as_str()).collect();
+        let network = NetworkConfig {
+            name: format!("multi-chain({})", names.join(",")),
+            rpc_url: String::new(),
+            chain_id: None,
+            gas_price: None,
+            gas_limit: None,
+            timeout_seconds: storages.iter().map(|s| s.get_network_config().timeout_seconds).max().unwrap_or(0),
+            retry_attempts: 0,
+            required_confirmations: None,
+        };
+        Ok(Self {
+            storages,
+            required_confirmations_count,
+            network,
+            repair_attempts: Arc::new(Mutex::new(HashMap::new())),
+            max_repair_attempts: DEFAULT_MAX_REPAIR_ATTEMPTS,
+        })
+    }
+
+    /// 设置每条链上每个键的读修复次数上限，0 表示关闭读修复
+    pub fn with_max_repair_attempts(mut self, max_repair_attempts: u32) -> Self {
+        self.max_repair_attempts = max_repair_attempts;
+        self
+    }
+
+    pub fn required_confirmations_count(&self) -> usize {
+        self.required_confirmations_count
+    }
+
+    /// 已对 `network` 上的 `key` 发起的读修复次数
+    pub fn repair_attempts(&self, network: &str, key: &str) -> u32 {
+        self.repair_attempts.lock().unwrap()
+            .get(&(network.to_string(), key.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// 在后台把 `data` 补写到缺少它的链上
+    ///
+    /// 只在读取时有链报告数据不存在（`missing`）才触发。补写前先读取其余链（`unchecked`），
+    /// 至少法定数量的链返回相同数据才补写，单条链返回的数据不会被复制到其他链；
+    /// 每条链每个键的补写次数受 `max_repair_attempts` 限制
+    fn spawn_read_repair(
+        &self,
+        key: &str,
+        data: &[u8],
+        mut missing: Vec<Arc<dyn BlockchainStorage>>,
+        unchecked: Vec<Arc<dyn BlockchainStorage>>,
+    ) {
+        if self.max_repair_attempts == 0 || missing.is_empty() {
+            return;
+        }
+        let (key, data) = (key.to_string(), data.to_vec());
+        let repair_attempts = self.repair_attempts.clone();
+        let max_repair_attempts = self.max_repair_attempts;
+        let required = self.required_confirmations_count;
+        tokio::spawn(async move {
+            let mut agreeing = 1;
+            for storage in unchecked {
+                match storage.retrieve_data(&key).await {
+                    Ok(other) if other == data => agreeing += 1,
+                    Err(BlockchainError::DataNotFound(_)) => missing.push(storage),
+                    _ => {}
+                }
+            }
+            if agreeing < required {
+                tracing::warn!("Read repair of {} skipped: only {} of {} required chains agree on the data", key, agreeing, required);
+                return;
+            }
+            for storage in missing {
+                let network = storage.get_network_config().name.clone();
+                let attempt = {
+                    let mut attempts = repair_attempts.lock().unwrap();
+                    let count = attempts.entry((network.clone(), key.clone())).or_insert(0);
+                    if *count >= max_repair_attempts {
+                        None
+                    } else {
+                        *count += 1;
+                        Some(*count)
+                    }
+                };
+                let Some(attempt) = attempt else {
+                    tracing::warn!("Read repair of {} on {} skipped after {} attempts", key, network, max_repair_attempts);
+                    continue;
+                };
+                match storage.store_data(&key, &data, None).await {
+                    Ok(transaction) => tracing::info!(
+                        "Read repair of {} on {} submitted as {} (attempt {})",
+                        key, network, transaction.tx_hash, attempt
+                    ),
+                    Err(e) => tracing::warn!("Read repair of {} on {} failed (attempt {}): {}", key, network, attempt, e),
+                }
+            }
+        });
+    }
+
+    /// 并发写入所有链，达到法定确认数即返回，其余链的写入在后台继续完成；
+    /// 剩余链已不可能凑够法定数时立即返回错误
+    pub async fn store_data_with_receipt(
+        &self,
+        key: &str,
+        data: &[u8],
+        metadata: Option<serde_json::Value>,
+    ) -> Result<MultiChainReceipt> {
+        let mut pending = JoinSet::new();
+        for storage in &self.storages {
+            let storage = storage.clone();
+            let (key, data, metadata) = (key.to_string(), data.to_vec(), metadata.clone());
+            pending.spawn(async move {
+                let result = storage.store_data(&key, &data, metadata).await;
+                (storage, result)
+            });
+        }
+        self.collect_quorum(pending).await
+    }
+
+    /// 等待各链结果直到确认数达到法定数，或失败数使之无法达到
+    ///
+    /// 已发送待确认的交易（如设置了发送账户的以太坊写入）计为已提交，
+    /// 只有报错或回滚、失败的交易计为失败
+    async fn collect_quorum(
+        &self,
+        mut pending: JoinSet<(Arc<dyn BlockchainStorage>, Result<StorageTransaction>)>,
+    ) -> Result<MultiChainReceipt> {
+        let total = self.storages.len();
+        let mut receipt = MultiChainReceipt {
+            confirmed: Vec::new(),
+            failures: Vec::new(),
+            required_confirmations_count: self.required_confirmations_count,
+        };
+        while let Some(joined) = pending.join_next().await {
+            match joined {
+                Ok((storage, Ok(transaction)))
+                    if matches!(transaction.status, TransactionStatus::Confirmed | TransactionStatus::Pending) =>
+                {
+                    receipt.confirmed.push(ChainTransaction {
+                        network: storage.get_network_config().name.clone(),
+                        blockchain_type: storage.get_blockchain_type(),
+                        transaction,
+                    });
+                }
+                Ok((storage, Ok(transaction))) => receipt.failures.push(ChainFailure {
+                    network: storage.get_network_config().name.clone(),
+                    error: format!("transaction {} is {:?}", transaction.tx_hash, transaction.status),
+                }),
+                Ok((storage, Err(e))) => receipt.failures.push(ChainFailure {
+                    network: storage.get_network_config().name.clone(),
+                    error: e.to_string(),
+                }),
+                Err(e) => receipt.failures.push(ChainFailure { network: "unknown".to_string(), error: e.to_string() }),
+            }
+            if receipt.confirmed.len() >= self.required_confirmations_count {
+                pending.detach_all();
+                for failure in &receipt.failures {
+                    tracing::warn!("Multi-chain write to {} failed: {}", failure.network, failure.error);
+                }
+                return Ok(receipt);
+            }
+            if total - receipt.failures.len() < self.required_confirmations_count {
+                pending.detach_all();
+                break;
+            }
+        }
+        let failures = receipt.failures.iter()
+            .map(|f| format!("{}: {}", f.network, f.error))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(BlockchainError::TransactionFailed(format!(
+            "{} of {} chains confirmed, {} required ({})",
+            receipt.confirmed.len(), total, self.required_confirmations_count, failures
+        )))
+    }
+}
+
+#[async_trait]
+impl BlockchainStorage for MultiChainStorage {
+    async fn store_data(
+        &self,
+        key: &str,
+        data: &[u8],
+        metadata: Option<serde_json::Value>,
+    ) -> Result<StorageTransaction> {
+        Ok(self.store_data_with_receipt(key, data, metadata).await?.combined())
+    }
+
+    /// 返回第一条有数据的链上的结果，同时在后台读修复缺少数据的链
+    async fn retrieve_data(&self, key: &str) -> Result<Vec<u8>> {
+        let mut missing = Vec::new();
+        let mut last_error = None;
+        for (index, storage) in self.storages.iter().enumerate() {
+            match storage.retrieve_data(key).await {
+                Ok(data) => {
+                    self.spawn_read_repair(key, &data, missing, self.storages[index + 1..].to_vec());
+                    return Ok(data);
+                }
+                Err(e) => {
+                    if matches!(e, BlockchainError::DataNotFound(_)) {
+                        missing.push(storage.clone());
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| BlockchainError::DataNotFound(key.to_string())))
+    }
+
+    /// 至少法定数量的链上数据与期望哈希一致才算通过
+    async fn verify_data(&self, key: &str, expected_hash: &str) -> Result<bool> {
+        let mut verified = 0;
+        for storage in &self.storages {
+            if matches!(storage.verify_data(key, expected_hash).await, Ok(true)) {
+                verified += 1;
+            }
+        }
+        Ok(verified >= self.required_confirmations_count)
+    }
+
+    async fn get_metadata(&self, key: &str) -> Result<StorageMetadata> {
+        let mut last_error = None;
+        for storage in &self.storages {
+            match storage.get_metadata(key).await {
+                Ok(metadata) => return Ok(metadata),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| BlockchainError::DataNotFound(key.to_string())))
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        for storage in &self.storages {
+            if matches!(storage.exists(key).await, Ok(true)) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// 与写入相同，法定数量的链确认删除即成功
+    async fn delete_data(&self, key: &str) -> Result<StorageTransaction> {
+        let mut pending = JoinSet::new();
+        for storage in &self.storages {
+            let (storage, key) = (storage.clone(), key.to_string());
+            pending.spawn(async move {
+                let result = storage.delete_data(&key).await;
+                (storage, result)
+            });
+        }
+        Ok(self.collect_quorum(pending).await?.combined())
+    }
+
+    /// 合并各链统计；无法获取统计的链被跳过
+    async fn get_stats(&self) -> Result<StorageStats> {
+        let mut merged = StorageStats {
+            total_transactions: 0,
+            total_data_size: 0,
+            average_gas_used: 0.0,
+            success_rate: 0.0,
+            last_updated: chrono::Utc::now(),
+            by_network: HashMap::new(),
+        };
+        let (mut gas, mut successes) = (0.0, 0.0);
+        for storage in &self.storages {
+            let Ok(stats) = storage.get_stats().await else { continue };
+            merged.total_transactions += stats.total_transactions;
+            merged.total_data_size += stats.total_data_size;
+            gas += stats.average_gas_used * stats.total_transactions as f64;
+            successes += stats.success_rate * stats.total_transactions as f64;
+            for (network, network_stats) in stats.by_network {
+                let entry = merged.by_network.entry(network).or_insert(NetworkStats {
+                    transaction_count: 0,
+                    total_gas_used: 0,
+                    success_count: 0,
+                    failure_count: 0,
+                });
+                entry.transaction_count += network_stats.transaction_count;
+                entry.total_gas_used += network_stats.total_gas_used;
+                entry.success_count += network_stats.success_count;
+                entry.failure_count += network_stats.failure_count;
+            }
+        }
+        if merged.total_transactions > 0 {
+            merged.average_gas_used = gas / merged.total_transactions as f64;
+            merged.success_rate = successes / merged.total_transactions as f64;
+        }
+        Ok(merged)
+    }
+
+    /// 第一个存储实例的区块链类型
+    fn get_blockchain_type(&self) -> BlockchainType {
+        self.storages[0].get_blockchain_type()
+    }
+
+    fn get_network_config(&self) -> &NetworkConfig {
+        &self.network
+    }
+}
@@ -0,0 +1,143 @@
+//! 账户 nonce 管理
+//!
+//! 同一账户并发发送交易时，由本地顺序分配 nonce，避免重复使用或留下空洞导致后续交易卡住；
+//! 长时间未确认的交易可以用相同 nonce、更高手续费替换
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::{BlockchainError, Result};
+
+/// 默认的替换交易手续费涨幅（百分比），与 geth 交易池的最低涨幅一致
+pub const DEFAULT_FEE_BUMP_PERCENT: u64 = 10;
+
+/// 查询账户在链上的下一个可用 nonce（含交易池中的待确认交易）
+#[async_trait]
+pub trait NonceSource: Send + Sync {
+    async fn pending_nonce(&self, account: &str) -> Result<u64>;
+}
+
+/// 已发送、尚未确认的交易
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTransaction {
+    pub nonce: u64,
+    pub tx_hash: String,
+    pub gas_price: u128,
+    pub submitted_at: chrono::DateTime<chrono::Utc>,
+    /// 以提高手续费的方式替换过的次数
+    pub replacements: u32,
+}
+
+#[derive(Debug, Default)]
+struct AccountNonces {
+    next: u64,
+    /// 已分配但未发送成功、可重新分配的 nonce
+    released: BTreeSet<u64>,
+    pending: BTreeMap<u64, PendingTransaction>,
+}
+
+/// 按账户分配连续 nonce 并跟踪待确认交易
+pub struct NonceManager {
+    accounts: Mutex<HashMap<String, AccountNonces>>,
+    fee_bump_percent: u64,
+}
+
+impl Default for NonceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self {
+            accounts: Mutex::new(HashMap::new()),
+            fee_bump_percent: DEFAULT_FEE_BUMP_PERCENT,
+        }
+    }
+
+    /// 设置替换交易时的手续费涨幅（百分比）
+    pub fn with_fee_bump_percent(mut self, fee_bump_percent: u64) -> Self {
+        self.fee_bump_percent = fee_bump_percent;
+        self
+    }
+
+    /// 为账户分配下一个 nonce
+    ///
+    /// 账户首次使用时从 `source` 同步链上 nonce；优先复用被释放的 nonce，以免留下空洞
+    pub async fn allocate(&self, source: &dyn NonceSource, account: &str) -> Result<u64> {
+        let mut accounts = self.accounts.lock().await;
+        if !accounts.contains_key(account) {
+            let next = source.pending_nonce(account).await?;
+            accounts.insert(account.to_string(), AccountNonces { next, ..Default::default() });
+        }
+        let nonces = accounts.get_mut(account).expect("account was just inserted");
+        if let Some(nonce) = nonces.released.pop_first() {
+            return Ok(nonce);
+        }
+        let nonce = nonces.next;
+        nonces.next += 1;
+        Ok(nonce)
+    }
+
+    /// 交易未能发送时归还 nonce，供下一次分配复用
+    pub async fn release(&self, account: &str, nonce: u64) {
+        if let Some(nonces) = self.accounts.lock().await.get_mut(account) {
+            if nonce < nonces.next && !nonces.pending.contains_key(&nonce) {
+                nonces.released.insert(nonce);
+            }
+        }
+    }
+
+    /// 记录已发送的交易；同一 nonce 再次记录视为替换
+    pub async fn record_pending(&self, account: &str, nonce: u64, tx_hash: &str, gas_price: u128) {
+        let mut accounts = self.accounts.lock().await;
+        let nonces = accounts.entry(account.to_string()).or_default();
+        nonces.next = nonces.next.max(nonce + 1);
+        nonces.released.remove(&nonce);
+        let replacements = nonces.pending.get(&nonce).map(|p| p.replacements + 1).unwrap_or(0);
+        nonces.pending.insert(nonce, PendingTransaction {
+            nonce,
+            tx_hash: tx_hash.to_string(),
+            gas_price,
+            submitted_at: chrono::Utc::now(),
+            replacements,
+        });
+    }
+
+    /// 交易已确认；同一账户更小的 nonce 必然也已上链，一并移除
+    pub async fn confirm(&self, account: &str, nonce: u64) {
+        if let Some(nonces) = self.accounts.lock().await.get_mut(account) {
+            nonces.pending.retain(|pending_nonce, _| *pending_nonce > nonce);
+            nonces.released.retain(|released| *released > nonce);
+        }
+    }
+
+    /// 账户的待确认交易，按 nonce 排列
+    pub async fn pending(&self, account: &str) -> Vec<PendingTransaction> {
+        self.accounts.lock().await.get(account)
+            .map(|nonces| nonces.pending.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// 发送超过 `older_than` 仍未确认的交易
+    pub async fn stuck(&self, account: &str, older_than: Duration) -> Vec<PendingTransaction> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::from_std(older_than).unwrap_or(chrono::Duration::zero());
+        self.pending(account).await.into_iter()
+            .filter(|pending| pending.submitted_at <= cutoff)
+            .collect()
+    }
+
+    /// 替换 `nonce` 上的交易应使用的手续费：至少比原交易高 `fee_bump_percent`
+    pub async fn bumped_gas_price(&self, account: &str, nonce: u64) -> Result<u128> {
+        let accounts = self.accounts.lock().await;
+        let pending = accounts.get(account)
+            .and_then(|nonces| nonces.pending.get(&nonce))
+            .ok_or_else(|| BlockchainError::DataNotFound(format!("No pending transaction with nonce {} for {}", nonce, account)))?;
+        let bumped = pending.gas_price.saturating_mul(100 + self.fee_bump_percent as u128).div_ceil(100);
+        Ok(bumped.max(pending.gas_price + 1))
+    }
+}
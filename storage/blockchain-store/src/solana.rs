@@ -13,7 +13,8 @@ use sha2::{Sha256, Digest};
 
 use crate::{
     BlockchainStorage, BlockchainClient, NetworkConfig, StorageTransaction, 
-    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError
+    StorageMetadata, StorageStats, BlockchainType, TransactionStatus, Result, BlockchainError,
+    ConfirmationPoller,
 };
 
 /// Solana 存储实现
@@ -111,8 +112,9 @@ impl BlockchainStorage for SolanaStorage {
     }
 
     async fn get_metadata(&self, key: &str) -> Result<StorageMetadata> {
-        // 简化实现，实际应该从 Solana 查询
-        Err(BlockchainError::DataNotFound(format!("Metadata not found for key: {}", key)))
+        // 简化实现，写入交易的信息实际应该从 Solana 查询
+        let data = self.retrieve_data(key).await?;
+        Ok(StorageMetadata::for_data(key, &data, self.get_blockchain_type(), &self.network_config))
     }
 
     async fn exists(&self, key: &str) -> Result<bool> {
@@ -197,15 +199,28 @@ impl BlockchainClient for SolanaStorage {
     }
 
     async fn wait_for_confirmation(&self, tx_hash: &str) -> Result<StorageTransaction> {
-        // 简化实现，实际需要轮询交易状态
-        Ok(StorageTransaction {
+        let signature = Signature::from_str(tx_hash)
+            .map_err(|e| BlockchainError::TransactionFailed(format!("Invalid signature {}: {}", tx_hash, e)))?;
+        let processed = self.client.get_signature_statuses(&[signature])
+            .map_err(|e| BlockchainError::Network(format!("Failed to get status of {}: {}", tx_hash, e)))?
+            .value
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or_else(|| BlockchainError::DataNotFound(format!("Transaction {} has not been processed", tx_hash)))?;
+        let transaction = StorageTransaction {
             tx_hash: tx_hash.to_string(),
-            block_number: Some(12345),
-            gas_used: Some(5000),
-            status: TransactionStatus::Confirmed,
+            block_number: Some(processed.slot),
+            gas_used: None,
+            status: if processed.err.is_some() { TransactionStatus::Failed } else { TransactionStatus::Pending },
             timestamp: chrono::Utc::now(),
-            data_hash: "".to_string(),
-            storage_key: "".to_string(),
-        })
+            data_hash: String::new(),
+            storage_key: String::new(),
+        };
+        if transaction.status == TransactionStatus::Failed {
+            return Ok(transaction);
+        }
+        let required = self.network_config.required_confirmations(&self.get_blockchain_type());
+        ConfirmationPoller::default().wait(self, transaction, required).await
     }
 }
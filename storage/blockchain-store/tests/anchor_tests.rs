@@ -0,0 +1,191 @@
+//! 承诺上链锚定测试
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use blockchain_store::{
+    BlockchainAnchorer, BlockchainConfig, BlockchainError, BlockchainManager, BlockchainStorage,
+    BlockchainType, NetworkConfig, Result, StorageMetadata, StorageStats, StorageTransaction,
+    TransactionStatus,
+};
+use shared_types::*;
+use vote_engine::{Anchoring, AnchorStore, MemoryAnchorStore, MemoryVoteService, VoteEngine, VoteService};
+
+/// 模拟区块链：每次写入落在下一个区块
+struct MockChainStorage {
+    network: NetworkConfig,
+    next_block: AtomicU64,
+    data: tokio::sync::RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl MockChainStorage {
+    fn new() -> Self {
+        Self {
+            network: NetworkConfig {
+                name: "Mock Chain".to_string(),
+                rpc_url: "http://localhost:0".to_string(),
+                chain_id: None,
+                gas_price: None,
+                gas_limit: None,
+                timeout_seconds: 1,
+                retry_attempts: 0,
+                required_confirmations: None,
+            },
+            next_block: AtomicU64::new(1),
+            data: tokio::sync::RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl BlockchainStorage for MockChainStorage {
+    async fn store_data(&self, key: &str, data: &[u8], _metadata: Option<serde_json::Value>) -> Result<StorageTransaction> {
+        let block = self.next_block.fetch_add(1, Ordering::SeqCst);
+        self.data.write().await.insert(key.to_string(), data.to_vec());
+        Ok(StorageTransaction {
+            tx_hash: format!("0x{:064x}", block),
+            block_number: Some(block),
+            gas_used: Some(21000),
+            status: TransactionStatus::Confirmed,
+            timestamp: chrono::Utc::now(),
+            data_hash: hex::encode(data),
+            storage_key: key.to_string(),
+        })
+    }
+
+    async fn retrieve_data(&self, key: &str) -> Result<Vec<u8>> {
+        self.data.read().await.get(key).cloned()
+            .ok_or_else(|| BlockchainError::DataNotFound(key.to_string()))
+    }
+
+    async fn verify_data(&self, key: &str, expected_hash: &str) -> Result<bool> {
+        Ok(self.retrieve_data(key).await? == expected_hash.as_bytes())
+    }
+
+    async fn get_metadata(&self, key: &str) -> Result<StorageMetadata> {
+        Err(BlockchainError::DataNotFound(key.to_string()))
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.data.read().await.contains_key(key))
+    }
+
+    async fn delete_data(&self, key: &str) -> Result<StorageTransaction> {
+        Err(BlockchainError::DataNotFound(key.to_string()))
+    }
+
+    async fn get_stats(&self) -> Result<StorageStats> {
+        Ok(StorageStats {
+            total_transactions: self.next_block.load(Ordering::SeqCst) - 1,
+            total_data_size: 0,
+            average_gas_used: 21000.0,
+            success_rate: 1.0,
+            last_updated: chrono::Utc::now(),
+            by_network: HashMap::new(),
+        })
+    }
+
+    fn get_blockchain_type(&self) -> BlockchainType {
+        BlockchainType::Ethereum
+    }
+
+    fn get_network_config(&self) -> &NetworkConfig {
+        &self.network
+    }
+}
+
+#[tokio::test]
+async fn test_committed_hash_is_anchored_on_chain() {
+    let manager = Arc::new(BlockchainManager::new(BlockchainConfig::default()));
+    manager.add_storage(BlockchainType::Ethereum, Box::new(MockChainStorage::new())).await.unwrap();
+    let anchorer = BlockchainAnchorer::new(manager.clone()).with_blockchain(BlockchainType::Ethereum);
+
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let engine = VoteEngine::new(Arc::new(MemoryVoteService::new()))
+        .with_anchoring(Anchoring::new(Arc::new(anchorer), anchors.clone()));
+
+    let vote_id = engine.create_vote(VoteConfig {
+        title: "Anchored".to_string(),
+        description: "Anchored on the mock chain".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: serde_json::json!({}),
+        commitment_duration_hours: 1,
+        reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }).await.unwrap();
+    let commitment_hash = "ab".repeat(32);
+    engine.commit_vote(&vote_id, CommitRequest {
+        voter: "alice".to_string(),
+        commitment_hash: commitment_hash.clone(),
+        salt: "salt".to_string(),
+        range_proof: None,
+    }).await.unwrap();
+
+    let mut recorded = Vec::new();
+    for _ in 0..1000 {
+        recorded = anchors.list_anchors(&vote_id).await.unwrap();
+        if !recorded.is_empty() {
+            break;
+        }
+        tokio::task::yield_now().await;
+    }
+
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].commitment_hash, commitment_hash);
+    assert_eq!(recorded[0].block_number, Some(1));
+    assert_eq!(recorded[0].chain, "Ethereum");
+
+    let key = BlockchainAnchorer::storage_key(&vote_id, "alice");
+    let on_chain = manager.retrieve_data(&BlockchainType::Ethereum, &key).await.unwrap();
+    assert_eq!(on_chain, commitment_hash.as_bytes());
+}
+
+#[tokio::test]
+async fn test_reconcile_resubmits_unanchored_commitment() {
+    let manager = Arc::new(BlockchainManager::new(BlockchainConfig::default()));
+    manager.add_storage(BlockchainType::Ethereum, Box::new(MockChainStorage::new())).await.unwrap();
+    let anchorer = BlockchainAnchorer::new(manager.clone()).with_blockchain(BlockchainType::Ethereum);
+
+    let service = Arc::new(MemoryVoteService::new());
+    let anchors = Arc::new(MemoryAnchorStore::new());
+    let engine = VoteEngine::new(service.clone())
+        .with_anchoring(Anchoring::new(Arc::new(anchorer), anchors.clone()));
+
+    let vote_id = engine.create_vote(VoteConfig {
+        title: "Reconciled".to_string(),
+        description: "One commitment missed the chain".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: serde_json::json!({}),
+        commitment_duration_hours: 1,
+        reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
+    }).await.unwrap();
+
+    // 绕过引擎直接写入，模拟锚定失败的承诺
+    let commitment_hash = "cd".repeat(32);
+    service.save_commitment(Commitment {
+        id: "unanchored".to_string(),
+        vote_id: vote_id.clone(),
+        voter: "bob".to_string(),
+        commitment_hash: commitment_hash.clone(),
+        salt: "salt".to_string(),
+        created_at: chrono::Utc::now(),
+        range_proof: None,
+    }).await.unwrap();
+
+    let report = engine.reconcile_anchors(&vote_id).await.unwrap();
+    assert_eq!(report.checked, 1);
+    assert_eq!(report.resubmitted.len(), 1);
+    assert_eq!(report.resubmitted[0].block_number, Some(1));
+
+    let key = BlockchainAnchorer::storage_key(&vote_id, "bob");
+    let on_chain = manager.retrieve_data(&BlockchainType::Ethereum, &key).await.unwrap();
+    assert_eq!(on_chain, commitment_hash.as_bytes());
+    assert_eq!(engine.reconcile_anchors(&vote_id).await.unwrap().confirmed, 1);
+}
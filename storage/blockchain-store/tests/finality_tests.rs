@@ -0,0 +1,146 @@
+//! 各链最终性要求测试
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blockchain_store::{
+    BlockchainClient, BlockchainConfig, BlockchainError, BlockchainType, ConfirmationPoller, Result,
+    StorageMetadata, StorageTransaction, TransactionStatus,
+};
+
+/// 模拟客户端：每查询一次区块高度前进一个区块
+struct AdvancingChain {
+    height: AtomicU64,
+    polls: AtomicU64,
+}
+
+impl AdvancingChain {
+    fn at(height: u64) -> Self {
+        Self { height: AtomicU64::new(height), polls: AtomicU64::new(0) }
+    }
+
+    fn polls(&self) -> u64 {
+        self.polls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl BlockchainClient for AdvancingChain {
+    async fn connect(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    async fn get_block_height(&self) -> Result<u64> {
+        self.polls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.height.fetch_add(1, Ordering::SeqCst))
+    }
+
+    async fn get_balance(&self, _address: &str) -> Result<u128> {
+        Ok(0)
+    }
+
+    async fn estimate_gas(&self, _data: &[u8]) -> Result<u64> {
+        Ok(0)
+    }
+
+    async fn send_transaction(&self, _data: &[u8]) -> Result<String> {
+        Ok("0x1".to_string())
+    }
+
+    async fn wait_for_confirmation(&self, tx_hash: &str) -> Result<StorageTransaction> {
+        Err(BlockchainError::DataNotFound(tx_hash.to_string()))
+    }
+}
+
+fn pending_at(block_number: u64) -> StorageTransaction {
+    StorageTransaction {
+        tx_hash: "0x1".to_string(),
+        block_number: Some(block_number),
+        gas_used: None,
+        status: TransactionStatus::Pending,
+        timestamp: chrono::Utc::now(),
+        data_hash: String::new(),
+        storage_key: "vote-1".to_string(),
+    }
+}
+
+fn fast_poller(max_polls: u32) -> ConfirmationPoller {
+    ConfirmationPoller { poll_interval: Duration::ZERO, max_polls }
+}
+
+#[test]
+fn test_networks_use_chain_defaults_unless_overridden() {
+    let mut config = BlockchainConfig::default();
+    let ethereum = config.get_network_config("ethereum_mainnet").unwrap();
+    assert_eq!(ethereum.required_confirmations(&BlockchainType::Ethereum), 12);
+    let solana = config.get_network_config("solana_mainnet").unwrap();
+    assert_eq!(solana.required_confirmations(&BlockchainType::Solana), 32);
+
+    let mut overridden = config.get_network_config("ethereum_goerli").unwrap().clone();
+    overridden.required_confirmations = Some(3);
+    config.add_network("ethereum_goerli".to_string(), overridden);
+    let goerli = config.get_network_config("ethereum_goerli").unwrap();
+    assert_eq!(goerli.required_confirmations(&BlockchainType::Ethereum), 3);
+}
+
+#[test]
+fn test_network_config_without_override_deserializes() {
+    let network: blockchain_store::NetworkConfig = serde_json::from_value(serde_json::json!({
+        "name": "Solana Devnet",
+        "rpc_url": "https://api.devnet.solana.com",
+        "chain_id": null,
+        "gas_price": null,
+        "gas_limit": null,
+        "timeout_seconds": 30,
+        "retry_attempts": 3
+    })).unwrap();
+    assert_eq!(network.required_confirmations, None);
+    assert_eq!(network.required_confirmations(&BlockchainType::Solana), 32);
+}
+
+#[test]
+fn test_metadata_reports_the_networks_requirement() {
+    let config = BlockchainConfig::default();
+    let solana = config.get_network_config("solana_mainnet").unwrap();
+    let metadata = StorageMetadata::for_data("vote-1", b"payload", BlockchainType::Solana, solana);
+    assert_eq!(metadata.required_confirmations, 32);
+    assert_eq!(metadata.size, 7);
+    assert_eq!(metadata.network, solana.name);
+
+    let mut overridden = config.get_network_config("ethereum_mainnet").unwrap().clone();
+    overridden.required_confirmations = Some(3);
+    let metadata = StorageMetadata::for_data("vote-1", b"payload", BlockchainType::Ethereum, &overridden);
+    assert_eq!(metadata.required_confirmations, 3);
+}
+
+#[tokio::test]
+async fn test_poller_waits_for_each_chains_requirement() {
+    let config = BlockchainConfig::default();
+    for (network, blockchain_type, expected) in [
+        ("ethereum_mainnet", BlockchainType::Ethereum, 12),
+        ("solana_mainnet", BlockchainType::Solana, 32),
+    ] {
+        let required = config.get_network_config(network).unwrap().required_confirmations(&blockchain_type);
+        let chain = AdvancingChain::at(100);
+        let confirmed = fast_poller(100).wait(&chain, pending_at(100), required).await.unwrap();
+        assert_eq!(confirmed.status, TransactionStatus::Confirmed);
+        assert_eq!(chain.polls(), expected, "{}", network);
+    }
+}
+
+#[tokio::test]
+async fn test_poller_times_out_before_finality() {
+    let chain = AdvancingChain::at(100);
+    let err = fast_poller(5).wait(&chain, pending_at(100), 12).await.unwrap_err();
+    assert!(matches!(err, BlockchainError::Timeout(_)), "{:?}", err);
+    assert_eq!(chain.polls(), 5);
+}
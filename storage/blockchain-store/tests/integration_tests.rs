@@ -21,6 +21,7 @@ fn create_test_config() -> BlockchainConfig {
             gas_limit: Some(150000),
             timeout_seconds: 30,
             retry_attempts: 3,
+            required_confirmations: None,
         }
     );
 
@@ -34,6 +35,7 @@ fn create_test_config() -> BlockchainConfig {
             gas_limit: Some(180000),
             timeout_seconds: 30,
             retry_attempts: 3,
+            required_confirmations: None,
         }
     );
 
@@ -47,6 +49,7 @@ fn create_test_config() -> BlockchainConfig {
             gas_limit: Some(25000),
             timeout_seconds: 30,
             retry_attempts: 3,
+            required_confirmations: None,
         }
     );
 
@@ -60,6 +63,7 @@ fn create_test_config() -> BlockchainConfig {
             gas_limit: None,
             timeout_seconds: 30,
             retry_attempts: 3,
+            required_confirmations: None,
         }
     );
 
@@ -0,0 +1,260 @@
+//! 多链冗余存储测试
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use blockchain_store::{
+    BlockchainError, BlockchainStorage, BlockchainType, MultiChainStorage, NetworkConfig, Result,
+    StorageMetadata, StorageStats, StorageTransaction, TransactionStatus,
+};
+
+/// 模拟链：`outcome` 为空时写入报网络错误，否则以该状态返回交易
+struct MockChain {
+    network: NetworkConfig,
+    outcome: Option<TransactionStatus>,
+    data: tokio::sync::RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl MockChain {
+    fn with_outcome(name: &str, outcome: Option<TransactionStatus>) -> Arc<dyn BlockchainStorage> {
+        Arc::new(Self {
+            network: NetworkConfig {
+                name: name.to_string(),
+                rpc_url: "http://localhost:0".to_string(),
+                chain_id: None,
+                gas_price: None,
+                gas_limit: None,
+                timeout_seconds: 1,
+                retry_attempts: 0,
+                required_confirmations: None,
+            },
+            outcome,
+            data: tokio::sync::RwLock::new(HashMap::new()),
+        })
+    }
+
+    fn confirming(name: &str) -> Arc<dyn BlockchainStorage> {
+        Self::with_outcome(name, Some(TransactionStatus::Confirmed))
+    }
+
+    fn failing(name: &str) -> Arc<dyn BlockchainStorage> {
+        Self::with_outcome(name, None)
+    }
+}
+
+#[async_trait]
+impl BlockchainStorage for MockChain {
+    async fn store_data(&self, key: &str, data: &[u8], _metadata: Option<serde_json::Value>) -> Result<StorageTransaction> {
+        let status = self.outcome.clone()
+            .ok_or_else(|| BlockchainError::Network(format!("{} unreachable", self.network.name)))?;
+        if status == TransactionStatus::Confirmed {
+            self.data.write().await.insert(key.to_string(), data.to_vec());
+        }
+        Ok(StorageTransaction {
+            tx_hash: format!("0x{}", self.network.name),
+            block_number: Some(7),
+            gas_used: Some(21000),
+            status,
+            timestamp: chrono::Utc::now(),
+            data_hash: hex::encode(data),
+            storage_key: key.to_string(),
+        })
+    }
+
+    async fn retrieve_data(&self, key: &str) -> Result<Vec<u8>> {
+        self.data.read().await.get(key).cloned()
+            .ok_or_else(|| BlockchainError::DataNotFound(key.to_string()))
+    }
+
+    async fn verify_data(&self, key: &str, expected_hash: &str) -> Result<bool> {
+        Ok(hex::encode(self.retrieve_data(key).await?) == expected_hash)
+    }
+
+    async fn get_metadata(&self, key: &str) -> Result<StorageMetadata> {
+        Err(BlockchainError::DataNotFound(key.to_string()))
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool> {
+        Ok(self.data.read().await.contains_key(key))
+    }
+
+    async fn delete_data(&self, key: &str) -> Result<StorageTransaction> {
+        Err(BlockchainError::DataNotFound(key.to_string()))
+    }
+
+    async fn get_stats(&self) -> Result<StorageStats> {
+        Ok(StorageStats {
+            total_transactions: self.data.read().await.len() as u64,
+            total_data_size: 0,
+            average_gas_used: 21000.0,
+            success_rate: 1.0,
+            last_updated: chrono::Utc::now(),
+            by_network: HashMap::new(),
+        })
+    }
+
+    fn get_blockchain_type(&self) -> BlockchainType {
+        BlockchainType::Ethereum
+    }
+
+    fn get_network_config(&self) -> &NetworkConfig {
+        &self.network
+    }
+}
+
+#[tokio::test]
+async fn test_quorum_met_despite_one_failing_chain() {
+    let storage = MultiChainStorage::new(vec![
+        MockChain::confirming("alpha"),
+        MockChain::failing("beta"),
+        MockChain::confirming("gamma"),
+    ], 2).unwrap();
+
+    let receipt = storage.store_data_with_receipt("vote-1", b"result", None).await.unwrap();
+    let mut networks: Vec<&str> = receipt.confirmed.iter().map(|c| c.network.as_str()).collect();
+    networks.sort();
+    assert_eq!(networks, vec!["alpha", "gamma"]);
+
+    let tx = receipt.combined();
+    assert_eq!(tx.status, TransactionStatus::Confirmed);
+    assert_eq!(tx.gas_used, Some(42000));
+    assert_eq!(tx.block_number, None);
+    assert_eq!(tx.tx_hash.split(',').count(), 2);
+    assert!(tx.tx_hash.contains("alpha:0xalpha"));
+
+    assert_eq!(storage.retrieve_data("vote-1").await.unwrap(), b"result");
+    assert!(storage.verify_data("vote-1", &hex::encode(b"result")).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_pending_transactions_count_towards_the_quorum() {
+    let storage = MultiChainStorage::new(vec![
+        MockChain::confirming("alpha"),
+        MockChain::with_outcome("beta", Some(TransactionStatus::Pending)),
+        MockChain::with_outcome("gamma", Some(TransactionStatus::Failed)),
+    ], 2).unwrap();
+
+    let receipt = storage.store_data_with_receipt("vote-1", b"result", None).await.unwrap();
+    let mut networks: Vec<&str> = receipt.confirmed.iter().map(|c| c.network.as_str()).collect();
+    networks.sort();
+    assert_eq!(networks, vec!["alpha", "beta"]);
+    // 仍有链待确认时合并后的交易也是待确认
+    assert_eq!(receipt.combined().status, TransactionStatus::Pending);
+}
+
+#[tokio::test]
+async fn test_quorum_not_met_reports_failures() {
+    let storage = MultiChainStorage::new(vec![
+        MockChain::confirming("alpha"),
+        MockChain::failing("beta"),
+        MockChain::with_outcome("gamma", Some(TransactionStatus::Reverted)),
+    ], 2).unwrap();
+
+    let err = storage.store_data("vote-1", b"result", None).await.unwrap_err();
+    let message = match err {
+        BlockchainError::TransactionFailed(message) => message,
+        other => panic!("unexpected error: {:?}", other),
+    };
+    assert!(message.contains("2 required"), "{}", message);
+    assert!(message.contains("beta: "), "{}", message);
+    assert!(message.contains("gamma: transaction 0xgamma is Reverted"), "{}", message);
+
+    // 单链上的数据不足以通过多链校验
+    assert!(!storage.verify_data("vote-1", &hex::encode(b"result")).await.unwrap());
+}
+
+#[test]
+fn test_required_confirmations_must_fit_the_chain_count() {
+    assert!(matches!(
+        MultiChainStorage::new(vec![MockChain::confirming("alpha")], 2),
+        Err(BlockchainError::InvalidConfig(_))
+    ));
+    assert!(matches!(
+        MultiChainStorage::new(vec![MockChain::confirming("alpha")], 0),
+        Err(BlockchainError::InvalidConfig(_))
+    ));
+}
+
+/// 等待后台读修复推进到 `attempts` 次
+async fn wait_for_repairs(storage: &MultiChainStorage, network: &str, key: &str, attempts: u32) {
+    for _ in 0..1000 {
+        if storage.repair_attempts(network, key) >= attempts {
+            break;
+        }
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn test_read_repairs_chains_missing_the_data() {
+    let (alpha, beta, gamma, delta) =
+        (MockChain::confirming("alpha"), MockChain::confirming("beta"), MockChain::confirming("gamma"), MockChain::confirming("delta"));
+    beta.store_data("vote-1", b"result", None).await.unwrap();
+    gamma.store_data("vote-1", b"result", None).await.unwrap();
+    let storage = MultiChainStorage::new(vec![alpha.clone(), beta.clone(), gamma.clone(), delta.clone()], 2).unwrap();
+
+    assert_eq!(storage.retrieve_data("vote-1").await.unwrap(), b"result");
+
+    // alpha 在读取时报告缺失；gamma 与 beta 一致凑够法定数，delta 在读取成功后才被检查
+    wait_for_repairs(&storage, "delta", "vote-1", 1).await;
+    for _ in 0..1000 {
+        if delta.exists("vote-1").await.unwrap() {
+            break;
+        }
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(storage.repair_attempts("alpha", "vote-1"), 1);
+    assert_eq!(storage.repair_attempts("beta", "vote-1"), 0);
+    assert_eq!(storage.repair_attempts("gamma", "vote-1"), 0);
+    assert_eq!(storage.repair_attempts("delta", "vote-1"), 1);
+    assert_eq!(alpha.retrieve_data("vote-1").await.unwrap(), b"result");
+    assert_eq!(delta.retrieve_data("vote-1").await.unwrap(), b"result");
+}
+
+#[tokio::test]
+async fn test_read_without_a_miss_does_not_repair() {
+    let (alpha, beta) = (MockChain::confirming("alpha"), MockChain::confirming("beta"));
+    alpha.store_data("vote-1", b"result", None).await.unwrap();
+    let storage = MultiChainStorage::new(vec![alpha, beta.clone()], 1).unwrap();
+
+    assert_eq!(storage.retrieve_data("vote-1").await.unwrap(), b"result");
+    for _ in 0..100 {
+        tokio::task::yield_now().await;
+    }
+    // 第一条链已有数据，后面的链不会被查询
+    assert_eq!(storage.repair_attempts("beta", "vote-1"), 0);
+    assert!(!beta.exists("vote-1").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_read_repair_requires_a_quorum_to_agree() {
+    let (alpha, beta, gamma) = (MockChain::confirming("alpha"), MockChain::confirming("beta"), MockChain::confirming("gamma"));
+    beta.store_data("vote-1", b"forged", None).await.unwrap();
+    gamma.store_data("vote-1", b"result", None).await.unwrap();
+    let storage = MultiChainStorage::new(vec![alpha.clone(), beta, gamma], 2).unwrap();
+
+    // 读取仍返回第一条有数据的链，但两条链不一致，不能据此补写
+    assert_eq!(storage.retrieve_data("vote-1").await.unwrap(), b"forged");
+    for _ in 0..100 {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(storage.repair_attempts("alpha", "vote-1"), 0);
+    assert!(!alpha.exists("vote-1").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_read_repair_attempts_are_capped() {
+    let (alpha, beta) = (MockChain::confirming("alpha"), MockChain::failing("beta"));
+    alpha.store_data("vote-1", b"result", None).await.unwrap();
+    let storage = MultiChainStorage::new(vec![beta, alpha], 1).unwrap().with_max_repair_attempts(2);
+
+    for read in 1..=4 {
+        assert_eq!(storage.retrieve_data("vote-1").await.unwrap(), b"result");
+        wait_for_repairs(&storage, "beta", "vote-1", read.min(2)).await;
+    }
+    for _ in 0..100 {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(storage.repair_attempts("beta", "vote-1"), 2);
+}
@@ -0,0 +1,110 @@
+//! 账户 nonce 管理测试
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blockchain_store::{BlockchainError, NonceManager, NonceSource, Result};
+
+const ACCOUNT: &str = "0x00000000000000000000000000000000000000aa";
+
+/// 模拟客户端：链上已有 `chain_nonce` 笔交易
+struct MockClient {
+    chain_nonce: u64,
+    queries: AtomicU32,
+}
+
+impl MockClient {
+    fn new(chain_nonce: u64) -> Self {
+        Self { chain_nonce, queries: AtomicU32::new(0) }
+    }
+}
+
+#[async_trait]
+impl NonceSource for MockClient {
+    async fn pending_nonce(&self, _account: &str) -> Result<u64> {
+        self.queries.fetch_add(1, Ordering::SeqCst);
+        Ok(self.chain_nonce)
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_allocations_are_sequential() {
+    let client = Arc::new(MockClient::new(7));
+    let nonces = Arc::new(NonceManager::new());
+
+    let mut handles = Vec::new();
+    for _ in 0..20 {
+        let (client, nonces) = (client.clone(), nonces.clone());
+        handles.push(tokio::spawn(async move { nonces.allocate(client.as_ref(), ACCOUNT).await.unwrap() }));
+    }
+    let mut allocated = Vec::new();
+    for handle in handles {
+        allocated.push(handle.await.unwrap());
+    }
+    allocated.sort();
+
+    assert_eq!(allocated, (7..27).collect::<Vec<_>>());
+    // 只在账户首次使用时查询链上 nonce
+    assert_eq!(client.queries.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_released_nonce_is_reused_to_avoid_gaps() {
+    let client = MockClient::new(0);
+    let nonces = NonceManager::new();
+    let first = nonces.allocate(&client, ACCOUNT).await.unwrap();
+    let failed = nonces.allocate(&client, ACCOUNT).await.unwrap();
+    let third = nonces.allocate(&client, ACCOUNT).await.unwrap();
+    nonces.record_pending(ACCOUNT, first, "0x01", 100).await;
+    nonces.record_pending(ACCOUNT, third, "0x03", 100).await;
+
+    nonces.release(ACCOUNT, failed).await;
+    assert_eq!(nonces.allocate(&client, ACCOUNT).await.unwrap(), failed);
+    assert_eq!(nonces.allocate(&client, ACCOUNT).await.unwrap(), 3);
+
+    // 已发送的交易占用的 nonce 不会被归还
+    nonces.release(ACCOUNT, first).await;
+    assert_eq!(nonces.allocate(&client, ACCOUNT).await.unwrap(), 4);
+}
+
+#[tokio::test]
+async fn test_stuck_transaction_is_replaced_with_bumped_fee() {
+    let client = MockClient::new(0);
+    let nonces = NonceManager::new();
+    let stuck_nonce = nonces.allocate(&client, ACCOUNT).await.unwrap();
+    nonces.record_pending(ACCOUNT, stuck_nonce, "0xstuck", 20_000_000_000).await;
+    let next = nonces.allocate(&client, ACCOUNT).await.unwrap();
+    nonces.record_pending(ACCOUNT, next, "0xqueued", 20_000_000_000).await;
+
+    assert!(nonces.stuck(ACCOUNT, Duration::from_secs(3600)).await.is_empty());
+    let stuck = nonces.stuck(ACCOUNT, Duration::ZERO).await;
+    assert_eq!(stuck[0].tx_hash, "0xstuck");
+
+    let gas_price = nonces.bumped_gas_price(ACCOUNT, stuck_nonce).await.unwrap();
+    assert_eq!(gas_price, 22_000_000_000);
+    nonces.record_pending(ACCOUNT, stuck_nonce, "0xreplacement", gas_price).await;
+
+    let pending = nonces.pending(ACCOUNT).await;
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].nonce, stuck_nonce);
+    assert_eq!(pending[0].tx_hash, "0xreplacement");
+    assert_eq!(pending[0].gas_price, 22_000_000_000);
+    assert_eq!(pending[0].replacements, 1);
+
+    // 替换交易上链后，更早的 nonce 一并视为已确认
+    nonces.confirm(ACCOUNT, next).await;
+    assert!(nonces.pending(ACCOUNT).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_fee_bump_requires_a_pending_transaction() {
+    let nonces = NonceManager::new().with_fee_bump_percent(25);
+    let err = nonces.bumped_gas_price(ACCOUNT, 0).await.unwrap_err();
+    assert!(matches!(err, BlockchainError::DataNotFound(_)), "{:?}", err);
+
+    nonces.record_pending(ACCOUNT, 0, "0x01", 3).await;
+    // 涨幅向上取整，且至少高出 1 wei
+    assert_eq!(nonces.bumped_gas_price(ACCOUNT, 0).await.unwrap(), 4);
+}
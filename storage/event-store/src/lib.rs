@@ -5,8 +5,8 @@ pub mod query;
 pub mod replay;
 pub mod index;
 
-pub use store::{EventStore, EventStoreError};
-pub use query::{EventQuery, QueryBuilder, QueryResult};
+pub use store::{Durability, EventStore, EventStoreError, EvictionPolicy};
+pub use query::{EventQuery, IndexLookup, QueryBuilder, QueryExecutor, QueryPlanner, QueryResult};
 pub use replay::{EventReplayer, ReplayOptions, ReplayResult};
 pub use index::{EventIndex, IndexManager};
 
@@ -60,6 +60,26 @@ impl std::fmt::Display for EventType {
     }
 }
 
+/// 解析 `Display` 的输出；未知名称视为自定义事件
+impl std::str::FromStr for EventType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "SessionCreated" => EventType::SessionCreated,
+            "CommitmentSubmitted" => EventType::CommitmentSubmitted,
+            "RevealPhaseStarted" => EventType::RevealPhaseStarted,
+            "RevealCompleted" => EventType::RevealCompleted,
+            "ResultGenerated" => EventType::ResultGenerated,
+            "SystemError" => EventType::SystemError,
+            other => {
+                let custom = other.strip_prefix("Custom(").and_then(|c| c.strip_suffix(')')).unwrap_or(other);
+                EventType::Custom(custom.to_string())
+            }
+        })
+    }
+}
+
 impl std::fmt::Display for EventSeverity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -86,6 +106,8 @@ pub struct Event {
     pub timestamp: DateTime<Utc>,
     pub correlation_id: Option<Uuid>,
     pub causation_id: Option<Uuid>,
+    /// 会话内从 1 开始单调递增的版本，0 表示由存储分配下一个版本；
+    /// 版本只在会话内有意义，没有会话的事件版本始终为 0
     pub version: u64,
 }
 
@@ -110,7 +132,7 @@ impl Event {
             timestamp: Utc::now(),
             correlation_id: None,
             causation_id: None,
-            version: 1,
+            version: 0,
         }
     }
 
@@ -128,12 +150,19 @@ impl Event {
         self.causation_id = Some(causation_id);
         self
     }
+
+    /// 指定期望的版本，不是会话的下一个版本时存储返回 `VersionConflict`；
+    /// 没有会话的事件忽略该版本
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
 }
 
 /// 事件存储 trait
 #[async_trait]
 pub trait EventStorage: Send + Sync {
-    /// 存储事件
+    /// 存储事件，带会话的事件按会话分配或校验版本
     async fn store_event(&self, event: Event) -> Result<(), EventStoreError>;
     
     /// 批量存储事件
@@ -166,5 +195,13 @@ pub trait EventStorage: Send + Sync {
     
     /// 清理过期事件
     async fn cleanup_expired_events(&self, before: DateTime<Utc>) -> Result<u64, EventStoreError>;
+    
+    /// 会话当前的版本，尚无事件时为 0；下一条事件的版本应为该值加 1
+    ///
+    /// 默认取会话现存事件的最大版本，删除事件后可能回退；记录已分配版本的实现应覆盖
+    async fn session_version(&self, session_id: &str) -> Result<u64, EventStoreError> {
+        let events = self.get_events_by_session(session_id).await?;
+        Ok(events.iter().map(|e| e.version).max().unwrap_or(0))
+    }
 }
 
@@ -1,6 +1,7 @@
 //! Event query system
 
-use crate::{Event, EventType, EventSeverity, EventStoreError};
+use crate::{Event, EventStorage, EventType, EventSeverity, EventStoreError};
+use shared_types::Paginated;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -187,6 +188,10 @@ pub struct QueryResult {
     pub total_count: usize,
     pub has_more: bool,
     pub execution_time_ms: u64,
+    /// 执行时检查的事件数
+    pub events_examined: usize,
+    /// `Contains`/`Regex` 条件的求值次数
+    pub text_evaluations: usize,
 }
 
 /// 查询构建器
@@ -300,6 +305,50 @@ impl Default for QueryBuilder {
     }
 }
 
+/// 可由存储索引直接定位的等值条件
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexLookup {
+    EventType(EventType),
+    SessionId(String),
+    UserId(Uuid),
+}
+
+/// 查询计划器
+pub struct QueryPlanner;
+
+impl QueryPlanner {
+    /// 找出可用索引缩小候选集的等值条件
+    ///
+    /// 只识别顶层或 AND 下的 `EventType`、`SessionId`、`UserId` 等值条件；
+    /// 索引命中的事件是扫描结果的超集，调用方仍需对候选集执行完整查询
+    pub fn index_lookup(query: &EventQuery) -> Option<IndexLookup> {
+        query.expression.as_ref().and_then(Self::lookup_in)
+    }
+
+    /// 取查询的候选事件：条件可走索引时只取索引命中的事件，否则取全部事件
+    pub async fn fetch_candidates(storage: &dyn EventStorage, query: &EventQuery) -> Result<Vec<Event>, EventStoreError> {
+        match Self::index_lookup(query) {
+            Some(IndexLookup::EventType(event_type)) => storage.get_events_by_type(&event_type).await,
+            Some(IndexLookup::SessionId(session_id)) => storage.get_events_by_session(&session_id).await,
+            Some(IndexLookup::UserId(user_id)) => storage.get_events_by_user(user_id).await,
+            None => storage.get_all_events().await,
+        }
+    }
+
+    fn lookup_in(expression: &QueryExpression) -> Option<IndexLookup> {
+        match expression {
+            QueryExpression::Condition(field, QueryCondition::Equals(value)) => match field {
+                QueryField::EventType => serde_json::from_value(value.clone()).ok().map(IndexLookup::EventType),
+                QueryField::SessionId => value.as_str().map(|s| IndexLookup::SessionId(s.to_string())),
+                QueryField::UserId => serde_json::from_value(value.clone()).ok().map(IndexLookup::UserId),
+                _ => None,
+            },
+            QueryExpression::Composite(QueryOperator::And, expressions) => expressions.iter().find_map(Self::lookup_in),
+            _ => None,
+        }
+    }
+}
+
 /// 查询执行器
 pub struct QueryExecutor;
 
@@ -320,8 +369,9 @@ impl QueryExecutor {
         };
         
         // 应用表达式过滤
+        let mut text_evaluations = 0;
         if let Some(ref expression) = query.expression {
-            filtered_events = Self::apply_expression(expression, &filtered_events)?;
+            filtered_events = Self::apply_expression(expression, &filtered_events, &mut text_evaluations)?;
         }
         
         // 应用排序
@@ -343,16 +393,39 @@ impl QueryExecutor {
             total_count,
             has_more,
             execution_time_ms: execution_time,
+            events_examined: events.len(),
+            text_evaluations,
         })
     }
 
+    /// 执行查询并返回统一的分页结构
+    ///
+    /// 页码由 `offset / limit` 得出，`next_cursor` 为下一页的 offset
+    pub fn execute_paginated(query: &EventQuery, events: &[Event]) -> Result<Paginated<Event>, EventStoreError> {
+        let result = Self::execute(query, events)?;
+        let limit = query.pagination.limit;
+        let page = query.pagination.offset.checked_div(limit).unwrap_or(0);
+
+        let mut paginated = Paginated::new(result.events, result.total_count as u64, page as u32, limit as u32);
+        // offset 不一定是 limit 的整数倍，游标以实际 offset 为准
+        let next_offset = query.pagination.offset + limit;
+        paginated.next_cursor = result.has_more.then(|| next_offset.to_string());
+        Ok(paginated)
+    }
+
     /// 应用查询表达式
+    ///
+    /// `text_evaluations` 累计 `Contains`/`Regex` 条件的求值次数
     fn apply_expression(
         expression: &QueryExpression,
         events: &[Event],
+        text_evaluations: &mut usize,
     ) -> Result<Vec<Event>, EventStoreError> {
         match expression {
             QueryExpression::Condition(field, condition) => {
+                if matches!(condition, QueryCondition::Contains(_) | QueryCondition::Regex(_)) {
+                    *text_evaluations += events.len();
+                }
                 Ok(events
                     .iter()
                     .filter(|event| Self::evaluate_condition(event, field, condition))
@@ -362,16 +435,20 @@ impl QueryExecutor {
             QueryExpression::Composite(operator, expressions) => {
                 match operator {
                     QueryOperator::And => {
+                        // 各子条件相互独立，按代价从低到高应用，尽早缩小工作集
+                        let mut ordered = Vec::new();
+                        Self::flatten_and(expressions, &mut ordered);
+                        ordered.sort_by_key(|expr| Self::cost(expr));
                         let mut result = events.to_vec();
-                        for expr in expressions {
-                            result = Self::apply_expression(expr, &result)?;
+                        for expr in ordered {
+                            result = Self::apply_expression(expr, &result, text_evaluations)?;
                         }
                         Ok(result)
                     }
                     QueryOperator::Or => {
                         let mut result = Vec::new();
                         for expr in expressions {
-                            let expr_result = Self::apply_expression(expr, events)?;
+                            let expr_result = Self::apply_expression(expr, events, text_evaluations)?;
                             for event in expr_result {
                                 if !result.iter().any(|e: &Event| e.id == event.id) {
                                     result.push(event);
@@ -384,7 +461,7 @@ impl QueryExecutor {
                         if expressions.len() != 1 {
                             return Err(EventStoreError::Query("NOT operator requires exactly one expression".to_string()));
                         }
-                        let excluded = Self::apply_expression(&expressions[0], events)?;
+                        let excluded = Self::apply_expression(&expressions[0], events, text_evaluations)?;
                         let excluded_ids: std::collections::HashSet<Uuid> = excluded.iter().map(|e| e.id).collect();
                         Ok(events
                             .iter()
@@ -397,8 +474,31 @@ impl QueryExecutor {
         }
     }
 
+    /// 展开嵌套的 AND，`QueryBuilder` 逐个追加条件时会生成左深的 AND 树
+    fn flatten_and<'a>(expressions: &'a [QueryExpression], out: &mut Vec<&'a QueryExpression>) {
+        for expr in expressions {
+            match expr {
+                QueryExpression::Composite(QueryOperator::And, nested) => Self::flatten_and(nested, out),
+                _ => out.push(expr),
+            }
+        }
+    }
+
+    /// 表达式的相对求值代价：可走索引的等值条件最低，文本匹配最高
+    fn cost(expression: &QueryExpression) -> u8 {
+        match expression {
+            QueryExpression::Condition(field, condition) => match condition {
+                QueryCondition::Equals(_) if matches!(field, QueryField::EventType | QueryField::SessionId | QueryField::UserId) => 0,
+                QueryCondition::Contains(_) => 2,
+                QueryCondition::Regex(_) => 3,
+                _ => 1,
+            },
+            QueryExpression::Composite(_, expressions) => expressions.iter().map(Self::cost).max().unwrap_or(0).max(1),
+        }
+    }
+
     /// 评估查询条件
-    fn evaluate_condition(event: &Event, field: &QueryField, condition: &QueryCondition) -> bool {
+    pub fn evaluate_condition(event: &Event, field: &QueryField, condition: &QueryCondition) -> bool {
         let value = Self::get_field_value(event, field);
         
         match condition {
@@ -491,7 +591,7 @@ impl QueryExecutor {
     }
 
     /// 应用排序
-    fn apply_sorting(events: &mut Vec<Event>, sort_rules: &[SortRule]) {
+    fn apply_sorting(events: &mut [Event], sort_rules: &[SortRule]) {
         events.sort_by(|a, b| {
             for rule in sort_rules {
                 let comparison = match rule.field {
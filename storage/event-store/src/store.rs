@@ -1,13 +1,17 @@
 //! Event storage implementations
 
 use crate::{EventStorage, Event, EventType};
+use crate::query::{EventQuery, QueryExecutor, QueryPlanner, QueryResult};
+use shared_types::Paginated;
 use anyhow::Result;
 use async_trait::async_trait;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{info, error};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 /// 事件存储错误
@@ -22,6 +26,9 @@ pub enum EventStoreError {
     #[error("Query error: {0}")]
     Query(String),
     
+    #[error("Version conflict in session {session_id}: expected {expected}, got {actual}")]
+    VersionConflict { session_id: String, expected: u64, actual: u64 },
+    
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
     
@@ -32,12 +39,37 @@ pub enum EventStoreError {
     Other(#[from] anyhow::Error),
 }
 
+/// 超出容量时的淘汰策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// 淘汰时间戳最早的事件
+    #[default]
+    DropOldest,
+}
+
+/// 按 (时间戳, 事件ID) 排序的事件
+type TimeIndex = BTreeSet<(DateTime<Utc>, Uuid)>;
+
+/// 按写入序号排序的事件ID，删除为 O(log n)
+type SequenceIndex<K> = HashMap<K, BTreeMap<u64, Uuid>>;
+
 /// 内存事件存储
 pub struct MemoryEventStore {
     events: Arc<RwLock<HashMap<Uuid, Event>>>,
-    session_index: Arc<RwLock<HashMap<String, Vec<Uuid>>>>,
-    user_index: Arc<RwLock<HashMap<Uuid, Vec<Uuid>>>>,
-    type_index: Arc<RwLock<HashMap<EventType, Vec<Uuid>>>>,
+    session_index: Arc<RwLock<SequenceIndex<String>>>,
+    user_index: Arc<RwLock<SequenceIndex<Uuid>>>,
+    type_index: Arc<RwLock<SequenceIndex<EventType>>>,
+    /// 每个事件写入索引时分配的序号
+    sequences: Arc<RwLock<HashMap<Uuid, u64>>>,
+    next_sequence: Arc<AtomicU64>,
+    /// 每个会话已分配的最大版本，删除或淘汰事件后不回退
+    session_versions: Arc<RwLock<HashMap<String, u64>>>,
+    /// 按时间戳排序的事件，用于淘汰最早的事件
+    time_index: Arc<RwLock<TimeIndex>>,
+    /// 事件数上限，`None` 表示不限
+    max_events: Option<usize>,
+    eviction_policy: EvictionPolicy,
+    evictions: Arc<AtomicU64>,
 }
 
 impl MemoryEventStore {
@@ -47,41 +79,160 @@ impl MemoryEventStore {
             session_index: Arc::new(RwLock::new(HashMap::new())),
             user_index: Arc::new(RwLock::new(HashMap::new())),
             type_index: Arc::new(RwLock::new(HashMap::new())),
+            sequences: Arc::new(RwLock::new(HashMap::new())),
+            next_sequence: Arc::new(AtomicU64::new(0)),
+            session_versions: Arc::new(RwLock::new(HashMap::new())),
+            time_index: Arc::new(RwLock::new(TimeIndex::new())),
+            max_events: None,
+            eviction_policy: EvictionPolicy::default(),
+            evictions: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// 最多保留 `max_events` 条事件，超出时按 `policy` 淘汰
+    pub fn with_max_events(mut self, max_events: usize, policy: EvictionPolicy) -> Self {
+        self.max_events = Some(max_events);
+        self.eviction_policy = policy;
+        self
+    }
+
+    /// 累计淘汰的事件数
+    pub fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
     async fn update_indexes(&self, event: &Event) {
+        let sequence = self.next_sequence.fetch_add(1, Ordering::Relaxed);
+        self.sequences.write().await.insert(event.id, sequence);
+
         // 更新会话索引
         if let Some(session_id) = &event.session_id {
             let mut session_index = self.session_index.write().await;
-            session_index.entry(session_id.clone()).or_insert_with(Vec::new).push(event.id);
+            session_index.entry(session_id.clone()).or_default().insert(sequence, event.id);
         }
 
         // 更新用户索引
         if let Some(user_id) = event.user_id {
             let mut user_index = self.user_index.write().await;
-            user_index.entry(user_id).or_insert_with(Vec::new).push(event.id);
+            user_index.entry(user_id).or_default().insert(sequence, event.id);
         }
 
         // 更新类型索引
-        let mut type_index = self.type_index.write().await;
-        type_index.entry(event.event_type.clone()).or_insert_with(Vec::new).push(event.id);
+        {
+            let mut type_index = self.type_index.write().await;
+            type_index.entry(event.event_type.clone()).or_default().insert(sequence, event.id);
+        }
+
+        self.time_index.write().await.insert((event.timestamp, event.id));
+    }
+
+    async fn remove_from_indexes(&self, event: &Event) {
+        let event_id = event.id;
+        self.time_index.write().await.remove(&(event.timestamp, event_id));
+        let Some(sequence) = self.sequences.write().await.remove(&event_id) else { return };
+
+        if let Some(session_id) = &event.session_id {
+            let mut session_index = self.session_index.write().await;
+            if let Some(event_ids) = session_index.get_mut(session_id) {
+                event_ids.remove(&sequence);
+                if event_ids.is_empty() {
+                    session_index.remove(session_id);
+                }
+            }
+        }
+
+        if let Some(user_id) = event.user_id {
+            let mut user_index = self.user_index.write().await;
+            if let Some(event_ids) = user_index.get_mut(&user_id) {
+                event_ids.remove(&sequence);
+                if event_ids.is_empty() {
+                    user_index.remove(&user_id);
+                }
+            }
+        }
+
+        {
+            let mut type_index = self.type_index.write().await;
+            if let Some(event_ids) = type_index.get_mut(&event.event_type) {
+                event_ids.remove(&sequence);
+                if event_ids.is_empty() {
+                    type_index.remove(&event.event_type);
+                }
+            }
+        }
+    }
+
+    /// 超出容量时淘汰事件
+    async fn evict_over_capacity(&self) {
+        let Some(max_events) = self.max_events else { return };
+        loop {
+            let evicted = {
+                let mut events = self.events.write().await;
+                if events.len() <= max_events {
+                    break;
+                }
+                let oldest = match self.eviction_policy {
+                    EvictionPolicy::DropOldest => self.time_index.read().await.first().map(|(_, id)| *id),
+                };
+                match oldest.and_then(|id| events.remove(&id)) {
+                    Some(event) => event,
+                    None => break,
+                }
+            };
+            self.remove_from_indexes(&evicted).await;
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+            debug!("Evicted event {} to stay within {} events", evicted.id, max_events);
+        }
+    }
+}
+
+impl Default for MemoryEventStore {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[async_trait]
 impl EventStorage for MemoryEventStore {
-    async fn store_event(&self, event: Event) -> Result<(), EventStoreError> {
+    async fn store_event(&self, mut event: Event) -> Result<(), EventStoreError> {
         let event_id = event.id;
         
         // 存储事件
-        {
+        let replaced = {
             let mut events = self.events.write().await;
-            events.insert(event_id, event.clone());
-        }
+            if let Some(session_id) = &event.session_id {
+                let mut session_versions = self.session_versions.write().await;
+                let existing = events.get(&event_id).filter(|old| old.session_id == event.session_id);
+                match existing {
+                    // 覆盖同一事件时沿用原版本
+                    Some(old) if event.version == 0 || event.version == old.version => event.version = old.version,
+                    _ => {
+                        let next = session_versions.get(session_id).copied().unwrap_or(0) + 1;
+                        if event.version == 0 {
+                            event.version = next;
+                        } else if event.version != next {
+                            return Err(EventStoreError::VersionConflict {
+                                session_id: session_id.clone(),
+                                expected: next,
+                                actual: event.version,
+                            });
+                        }
+                        session_versions.insert(session_id.clone(), next);
+                    }
+                }
+            } else {
+                // 版本只在会话内有意义
+                event.version = 0;
+            }
+            events.insert(event_id, event.clone())
+        };
         
-        // 更新索引
+        // 更新索引，同ID的旧事件先移出索引
+        if let Some(replaced) = replaced {
+            self.remove_from_indexes(&replaced).await;
+        }
         self.update_indexes(&event).await;
+        self.evict_over_capacity().await;
         
         info!("Stored event: {} of type {:?}", event_id, event.event_type);
         Ok(())
@@ -101,7 +252,7 @@ impl EventStorage for MemoryEventStore {
 
     async fn get_events_by_session(&self, session_id: &str) -> Result<Vec<Event>, EventStoreError> {
         let session_index = self.session_index.read().await;
-        let event_ids = session_index.get(session_id).cloned().unwrap_or_default();
+        let event_ids: Vec<Uuid> = session_index.get(session_id).map(|ids| ids.values().copied().collect()).unwrap_or_default();
         
         let events = self.events.read().await;
         let mut result = Vec::new();
@@ -119,7 +270,7 @@ impl EventStorage for MemoryEventStore {
 
     async fn get_events_by_user(&self, user_id: Uuid) -> Result<Vec<Event>, EventStoreError> {
         let user_index = self.user_index.read().await;
-        let event_ids = user_index.get(&user_id).cloned().unwrap_or_default();
+        let event_ids: Vec<Uuid> = user_index.get(&user_id).map(|ids| ids.values().copied().collect()).unwrap_or_default();
         
         let events = self.events.read().await;
         let mut result = Vec::new();
@@ -154,7 +305,7 @@ impl EventStorage for MemoryEventStore {
 
     async fn get_events_by_type(&self, event_type: &EventType) -> Result<Vec<Event>, EventStoreError> {
         let type_index = self.type_index.read().await;
-        let event_ids = type_index.get(event_type).cloned().unwrap_or_default();
+        let event_ids: Vec<Uuid> = type_index.get(event_type).map(|ids| ids.values().copied().collect()).unwrap_or_default();
         
         let events = self.events.read().await;
         let mut result = Vec::new();
@@ -187,24 +338,7 @@ impl EventStorage for MemoryEventStore {
         
         if let Some(event) = event {
             // 从索引中移除
-            if let Some(session_id) = &event.session_id {
-                let mut session_index = self.session_index.write().await;
-                if let Some(event_ids) = session_index.get_mut(session_id) {
-                    event_ids.retain(|&id| id != event_id);
-                }
-            }
-            
-            if let Some(user_id) = event.user_id {
-                let mut user_index = self.user_index.write().await;
-                if let Some(event_ids) = user_index.get_mut(&user_id) {
-                    event_ids.retain(|&id| id != event_id);
-                }
-            }
-            
-            let mut type_index = self.type_index.write().await;
-            if let Some(event_ids) = type_index.get_mut(&event.event_type) {
-                event_ids.retain(|&id| id != event_id);
-            }
+            self.remove_from_indexes(&event).await;
             
             info!("Deleted event: {}", event_id);
         }
@@ -231,12 +365,38 @@ impl EventStorage for MemoryEventStore {
         info!("Cleaned up {} expired events", count);
         Ok(count)
     }
+
+    async fn session_version(&self, session_id: &str) -> Result<u64, EventStoreError> {
+        Ok(self.session_versions.read().await.get(session_id).copied().unwrap_or(0))
+    }
+}
+
+/// 文件写入的持久性保证
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Durability {
+    /// 写入后不 fsync：调用返回时数据可能仍在操作系统缓存中，崩溃或断电可能丢失最近写入的事件
+    #[default]
+    None,
+    /// 每次写入都先写临时文件、fsync 后替换原文件并 fsync 目录：调用返回 Ok 时事件已落盘，
+    /// 崩溃后文件要么是旧内容要么是新内容，不会出现半截文件
+    Fsync,
+    /// 与 `Fsync` 相同的保证，但并发写入者合并为一次 fsync：等待中的写入者若已被
+    /// 其他写入者的 fsync 覆盖则直接返回，以少量延迟换取更少的磁盘同步
+    FsyncBatch,
 }
 
 /// 文件事件存储
 pub struct FileEventStore {
     file_path: PathBuf,
     memory_store: MemoryEventStore,
+    durability: Durability,
+    /// 串行化落盘写入，`FsyncBatch` 下同时用于合并写入者
+    sync_lock: tokio::sync::Mutex<()>,
+    /// 已写入内存的变更序号
+    write_seq: AtomicU64,
+    /// 已落盘的最大变更序号
+    synced_seq: AtomicU64,
+    fsyncs: AtomicU64,
 }
 
 impl FileEventStore {
@@ -244,9 +404,25 @@ impl FileEventStore {
         Self {
             file_path,
             memory_store: MemoryEventStore::new(),
+            durability: Durability::None,
+            sync_lock: tokio::sync::Mutex::new(()),
+            write_seq: AtomicU64::new(0),
+            synced_seq: AtomicU64::new(0),
+            fsyncs: AtomicU64::new(0),
         }
     }
 
+    /// 设置写入的持久性保证
+    pub fn with_durability(mut self, durability: Durability) -> Self {
+        self.durability = durability;
+        self
+    }
+
+    /// 已执行的文件 fsync 次数
+    pub fn fsyncs(&self) -> u64 {
+        self.fsyncs.load(Ordering::Relaxed)
+    }
+
     /// 从文件加载事件
     pub async fn load_from_file(&self) -> Result<(), EventStoreError> {
         if !self.file_path.exists() {
@@ -255,10 +431,20 @@ impl FileEventStore {
         }
 
         let content = tokio::fs::read_to_string(&self.file_path).await?;
-        let events: Vec<Event> = serde_json::from_str(&content)?;
-
-        for event in events {
-            self.memory_store.store_event(event).await?;
+        let mut events: Vec<Event> = serde_json::from_str(&content)?;
+        // 按版本顺序重放，每个会话内的版本依次递增
+        events.sort_by_key(|e| (e.version, e.timestamp));
+
+        for mut event in events {
+            match self.memory_store.store_event(event.clone()).await {
+                Err(EventStoreError::VersionConflict { session_id, .. }) => {
+                    // 旧文件中的事件版本恒为 1，重新分配
+                    warn!("Reassigning version of event {} in session {}", event.id, session_id);
+                    event.version = 0;
+                    self.memory_store.store_event(event).await?;
+                }
+                result => result?,
+            }
         }
         
         info!("Loaded {} events from file", self.memory_store.events.read().await.len());
@@ -267,8 +453,31 @@ impl FileEventStore {
 
     /// 保存事件到文件
     pub async fn save_to_file(&self) -> Result<(), EventStoreError> {
+        // 变更已写入内存，记下其序号
+        let seq = self.write_seq.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.durability == Durability::None {
+            let events = self.memory_store.get_all_events().await?;
+            self.write_events(&events).await?;
+            info!("Saved {} events to file", events.len());
+            return Ok(());
+        }
+
+        let _guard = self.sync_lock.lock().await;
+        if self.durability == Durability::FsyncBatch && self.synced_seq.load(Ordering::SeqCst) >= seq {
+            // 等锁期间其他写入者的 fsync 已包含本次变更
+            return Ok(());
+        }
+        // 读取快照前取序号，快照包含该序号及之前的全部变更
+        let covered = self.write_seq.load(Ordering::SeqCst);
         let events = self.memory_store.get_all_events().await?;
-        let content = serde_json::to_string_pretty(&events)?;
+        self.write_events_durably(&events).await?;
+        self.synced_seq.fetch_max(covered, Ordering::SeqCst);
+        info!("Saved and synced {} events to file", events.len());
+        Ok(())
+    }
+
+    async fn write_events(&self, events: &[Event]) -> Result<(), EventStoreError> {
+        let content = serde_json::to_string_pretty(events)?;
         
         // 确保目录存在
         if let Some(parent) = self.file_path.parent() {
@@ -276,7 +485,35 @@ impl FileEventStore {
         }
         
         tokio::fs::write(&self.file_path, content).await?;
-        info!("Saved {} events to file", events.len());
+        Ok(())
+    }
+
+    /// 写临时文件并 fsync，再原子替换目标文件并 fsync 所在目录
+    async fn write_events_durably(&self, events: &[Event]) -> Result<(), EventStoreError> {
+        let content = serde_json::to_string_pretty(events)?;
+        let path = self.file_path.clone();
+        tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+            use std::io::Write;
+            let parent = path.parent().filter(|p| !p.as_os_str().is_empty()).map(PathBuf::from);
+            if let Some(parent) = &parent {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+            tmp_name.push(".tmp");
+            let tmp_path = path.with_file_name(tmp_name);
+
+            let mut file = std::fs::File::create(&tmp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+            std::fs::rename(&tmp_path, &path)?;
+            // 目录项的变更也需同步，否则崩溃后可能看不到替换后的文件
+            #[cfg(unix)]
+            std::fs::File::open(parent.unwrap_or_else(|| PathBuf::from(".")))?.sync_all()?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| EventStoreError::Storage(e.to_string()))??;
+        self.fsyncs.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
 }
@@ -334,6 +571,10 @@ impl EventStorage for FileEventStore {
         self.save_to_file().await?;
         Ok(count)
     }
+
+    async fn session_version(&self, session_id: &str) -> Result<u64, EventStoreError> {
+        self.memory_store.session_version(session_id).await
+    }
 }
 
 /// 事件存储管理器
@@ -390,6 +631,18 @@ impl EventStore {
         self.storage.get_all_events().await
     }
 
+    /// 按查询条件分页获取事件
+    pub async fn query(&self, query: &EventQuery) -> Result<Paginated<Event>, EventStoreError> {
+        let candidates = QueryPlanner::fetch_candidates(self.storage.as_ref(), query).await?;
+        QueryExecutor::execute_paginated(query, &candidates)
+    }
+
+    /// 执行查询并返回执行统计
+    pub async fn execute(&self, query: &EventQuery) -> Result<QueryResult, EventStoreError> {
+        let candidates = QueryPlanner::fetch_candidates(self.storage.as_ref(), query).await?;
+        QueryExecutor::execute(query, &candidates)
+    }
+
     /// 删除事件
     pub async fn delete_event(&self, event_id: Uuid) -> Result<(), EventStoreError> {
         self.storage.delete_event(event_id).await
@@ -406,11 +659,23 @@ impl EventStore {
         Self::new(storage)
     }
 
+    /// 创建最多保留 `max_events` 条事件的内存存储，超出时淘汰最早的事件
+    pub fn new_memory_bounded(max_events: usize) -> Self {
+        let storage = Box::new(MemoryEventStore::new().with_max_events(max_events, EvictionPolicy::DropOldest));
+        Self::new(storage)
+    }
+
     /// 创建文件存储
     pub fn new_file(file_path: PathBuf) -> Self {
         let storage = Box::new(FileEventStore::new(file_path));
         Self::new(storage)
     }
+
+    /// 创建带持久性保证的文件存储
+    pub fn new_file_with_durability(file_path: PathBuf, durability: Durability) -> Self {
+        let storage = Box::new(FileEventStore::new(file_path).with_durability(durability));
+        Self::new(storage)
+    }
 }
 
 impl Default for EventStore {
@@ -0,0 +1,70 @@
+use event_store::store::FileEventStore;
+use event_store::{Durability, Event, EventSeverity, EventStorage, EventType};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn event(i: usize) -> Event {
+    Event::new(
+        EventType::CommitmentSubmitted,
+        EventSeverity::Info,
+        "vote-engine".to_string(),
+        format!("event {}", i),
+        Some("session-1".to_string()),
+        None,
+    )
+}
+
+fn temp_path() -> PathBuf {
+    std::env::temp_dir().join(format!("event-store-{}", Uuid::new_v4())).join("events.json")
+}
+
+async fn reload(path: &Path) -> usize {
+    let store = FileEventStore::new(path.to_path_buf());
+    store.load_from_file().await.unwrap();
+    store.get_all_events().await.unwrap().len()
+}
+
+#[tokio::test]
+async fn test_fsync_mode_syncs_every_write() {
+    let path = temp_path();
+    let store = FileEventStore::new(path.clone()).with_durability(Durability::Fsync);
+    for i in 0..3 {
+        store.store_event(event(i)).await.unwrap();
+        assert_eq!(store.fsyncs(), i as u64 + 1);
+    }
+    assert_eq!(reload(&path).await, 3);
+    // 原子替换后不留下临时文件
+    assert!(!path.with_file_name("events.json.tmp").exists());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn test_default_mode_does_not_sync() {
+    let path = temp_path();
+    let store = FileEventStore::new(path.clone());
+    store.store_event(event(0)).await.unwrap();
+    assert_eq!(store.fsyncs(), 0);
+    assert_eq!(reload(&path).await, 1);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn test_batch_mode_groups_concurrent_writers() {
+    let path = temp_path();
+    let store = Arc::new(FileEventStore::new(path.clone()).with_durability(Durability::FsyncBatch));
+    let writers: Vec<_> = (0..20)
+        .map(|i| {
+            let store = store.clone();
+            tokio::spawn(async move { store.store_event(event(i)).await })
+        })
+        .collect();
+    for writer in writers {
+        writer.await.unwrap().unwrap();
+    }
+    // 等锁的写入者由领先者的 fsync 覆盖
+    assert!(store.fsyncs() >= 1);
+    assert!(store.fsyncs() < 20, "expected grouped fsyncs, got {}", store.fsyncs());
+    assert_eq!(reload(&path).await, 20);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,60 @@
+use chrono::{Duration, Utc};
+use event_store::store::MemoryEventStore;
+use event_store::{Event, EventSeverity, EventStorage, EventType, EvictionPolicy};
+use uuid::Uuid;
+
+fn event(i: i64, user: Uuid) -> Event {
+    let mut event = Event::new(
+        if i % 2 == 0 { EventType::CommitmentSubmitted } else { EventType::RevealCompleted },
+        EventSeverity::Info,
+        "vote-engine".to_string(),
+        format!("event {}", i),
+        Some(format!("session-{}", i % 3)),
+        Some(user),
+    );
+    event.timestamp = Utc::now() - Duration::hours(1) + Duration::seconds(i);
+    event
+}
+
+#[tokio::test]
+async fn test_inserting_beyond_cap_evicts_oldest_and_keeps_indexes_consistent() {
+    let store = MemoryEventStore::new().with_max_events(5, EvictionPolicy::DropOldest);
+    let user = Uuid::new_v4();
+    // stored out of timestamp order, so eviction has to go by timestamp rather than arrival
+    let order = [3, 0, 4, 1, 2, 7, 5, 6];
+    let events: Vec<Event> = order.iter().map(|&i| event(i, user)).collect();
+    for e in &events {
+        store.store_event(e.clone()).await.unwrap();
+    }
+
+    let remaining = store.get_all_events().await.unwrap();
+    assert_eq!(remaining.iter().map(|e| e.message.as_str()).collect::<Vec<_>>(), ["event 3", "event 4", "event 5", "event 6", "event 7"]);
+    assert_eq!(store.evictions(), 3);
+
+    // every index lookup agrees with the remaining events
+    assert_eq!(store.get_events_by_user(user).await.unwrap().len(), 5);
+    let mut by_type = store.get_events_by_type(&EventType::CommitmentSubmitted).await.unwrap();
+    by_type.extend(store.get_events_by_type(&EventType::RevealCompleted).await.unwrap());
+    assert_eq!(by_type.len(), 5);
+    let mut by_session = 0;
+    for session in 0..3 {
+        let events = store.get_events_by_session(&format!("session-{}", session)).await.unwrap();
+        assert!(events.iter().all(|e| e.session_id.as_deref() == Some(format!("session-{}", session).as_str())));
+        by_session += events.len();
+    }
+    assert_eq!(by_session, 5);
+    for evicted in events.iter().filter(|e| ["event 0", "event 1", "event 2"].contains(&e.message.as_str())) {
+        assert!(store.get_event(evicted.id).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_unbounded_store_keeps_everything() {
+    let store = MemoryEventStore::new();
+    let user = Uuid::new_v4();
+    for i in 0..50 {
+        store.store_event(event(i, user)).await.unwrap();
+    }
+    assert_eq!(store.get_all_events().await.unwrap().len(), 50);
+    assert_eq!(store.evictions(), 0);
+}
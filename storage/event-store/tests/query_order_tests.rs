@@ -0,0 +1,83 @@
+use chrono::{Duration, Utc};
+use event_store::query::{QueryCondition, QueryExpression, QueryField, QueryOperator};
+use event_store::{Event, EventSeverity, EventType, QueryBuilder, QueryExecutor};
+use uuid::Uuid;
+
+fn events() -> Vec<Event> {
+    let start = Utc::now();
+    (0..50)
+        .map(|i| {
+            let mut event = Event::new(
+                if i % 2 == 0 { EventType::CommitmentSubmitted } else { EventType::RevealCompleted },
+                EventSeverity::Info,
+                "vote-engine".to_string(),
+                format!("voter-{} {}", i, if i % 3 == 0 { "retried" } else { "accepted" }),
+                Some(format!("session-{}", i % 5)),
+                None,
+            );
+            event.timestamp = start + Duration::milliseconds(i);
+            event
+        })
+        .collect()
+}
+
+/// Each event checked against every condition independently, in the order given
+fn naive(events: &[Event], conditions: &[(QueryField, QueryCondition)]) -> Vec<Uuid> {
+    let mut matched: Vec<&Event> = events
+        .iter()
+        .filter(|e| conditions.iter().all(|(field, condition)| QueryExecutor::evaluate_condition(e, field, condition)))
+        .collect();
+    matched.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+    matched.iter().map(|e| e.id).collect()
+}
+
+#[test]
+fn test_selective_conditions_run_before_text_matches() {
+    let events = events();
+    let conditions = vec![
+        (QueryField::Message, QueryCondition::Regex("retried".to_string())),
+        (QueryField::Source, QueryCondition::Contains("engine".to_string())),
+        (QueryField::SessionId, QueryCondition::Equals(serde_json::json!("session-3"))),
+    ];
+    let mut builder = QueryBuilder::new().paginate(0, 1000);
+    for (field, condition) in conditions.clone() {
+        builder = builder.where_field(field, condition);
+    }
+
+    let result = QueryExecutor::execute(&builder.build(), &events).unwrap();
+    assert_eq!(result.events.iter().map(|e| e.id).collect::<Vec<_>>(), naive(&events, &conditions));
+    assert_eq!(result.total_count, 4);
+    // both text conditions only see the 10 events of session-3; in the written order the regex
+    // alone would be evaluated against all 50
+    assert_eq!(result.text_evaluations, 20);
+}
+
+#[test]
+fn test_reordering_keeps_or_and_not_semantics() {
+    let events = events();
+    let expression = QueryExpression::Composite(QueryOperator::And, vec![
+        QueryExpression::Composite(QueryOperator::Not, vec![
+            QueryExpression::Condition(QueryField::Message, QueryCondition::Contains("retried".to_string())),
+        ]),
+        QueryExpression::Composite(QueryOperator::Or, vec![
+            QueryExpression::Condition(QueryField::SessionId, QueryCondition::Equals(serde_json::json!("session-1"))),
+            QueryExpression::Condition(QueryField::SessionId, QueryCondition::Equals(serde_json::json!("session-2"))),
+        ]),
+        QueryExpression::Condition(QueryField::EventType, QueryCondition::Equals(serde_json::to_value(EventType::RevealCompleted).unwrap())),
+    ]);
+    let query = QueryBuilder::new().paginate(0, 1000).build().with_expression(expression);
+
+    let result = QueryExecutor::execute(&query, &events).unwrap();
+    let expected: Vec<Uuid> = {
+        let mut matched: Vec<&Event> = events
+            .iter()
+            .filter(|e| !e.message.contains("retried"))
+            .filter(|e| matches!(e.session_id.as_deref(), Some("session-1" | "session-2")))
+            .filter(|e| e.event_type == EventType::RevealCompleted)
+            .collect();
+        matched.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        matched.iter().map(|e| e.id).collect()
+    };
+    assert_eq!(result.events.iter().map(|e| e.id).collect::<Vec<_>>(), expected);
+    assert!(!expected.is_empty());
+}
@@ -0,0 +1,82 @@
+use chrono::{Duration, Utc};
+use event_store::query::{QueryCondition, QueryField, SortDirection, SortField};
+use event_store::{Event, EventQuery, EventSeverity, EventStore, EventType, IndexLookup, QueryBuilder, QueryExecutor, QueryPlanner};
+use uuid::Uuid;
+
+const TYPES: [EventType; 3] = [EventType::SessionCreated, EventType::CommitmentSubmitted, EventType::RevealCompleted];
+
+/// 60 events across three types, three sessions and two users, with distinct timestamps
+async fn populated_store(user: Uuid) -> (EventStore, Vec<Event>) {
+    let store = EventStore::new_memory();
+    let start = Utc::now();
+    let mut events = Vec::new();
+    for i in 0..60 {
+        let mut event = Event::new(
+            TYPES[i % 3].clone(),
+            if i % 4 == 0 { EventSeverity::Warning } else { EventSeverity::Info },
+            "vote-engine".to_string(),
+            format!("event {}", i),
+            Some(format!("session-{}", i % 5)),
+            if i % 2 == 0 { Some(user) } else { Some(Uuid::new_v4()) },
+        );
+        event.timestamp = start + Duration::milliseconds(i as i64);
+        store.store_event(event.clone()).await.unwrap();
+        events.push(event);
+    }
+    (store, events)
+}
+
+fn ids(events: &[Event]) -> Vec<Uuid> {
+    events.iter().map(|e| e.id).collect()
+}
+
+#[tokio::test]
+async fn test_indexed_queries_match_the_scan() {
+    let user = Uuid::new_v4();
+    let (store, all) = populated_store(user).await;
+
+    let queries: Vec<EventQuery> = vec![
+        QueryBuilder::new().event_type_equals(EventType::CommitmentSubmitted).paginate(0, 1000).build(),
+        QueryBuilder::new().session_id_equals("session-2".to_string()).paginate(0, 1000).build(),
+        QueryBuilder::new().user_id_equals(user).paginate(0, 1000).build(),
+        QueryBuilder::new()
+            .event_type_equals(EventType::RevealCompleted)
+            .severity_equals(EventSeverity::Warning)
+            .session_id_equals("session-3".to_string())
+            .order_by(SortField::Timestamp, SortDirection::Ascending)
+            .paginate(0, 1000)
+            .build(),
+        QueryBuilder::new().event_type_equals(EventType::SessionCreated).paginate(2, 5).build(),
+    ];
+    for query in &queries {
+        assert!(QueryPlanner::index_lookup(query).is_some(), "{:?}", query.expression);
+        let indexed = store.execute(query).await.unwrap();
+        let scanned = QueryExecutor::execute(query, &all).unwrap();
+        assert_eq!(ids(&indexed.events), ids(&scanned.events));
+        assert_eq!(indexed.total_count, scanned.total_count);
+        assert_eq!(indexed.has_more, scanned.has_more);
+        assert!(indexed.events_examined < scanned.events_examined);
+    }
+
+    let paginated = store.query(&queries[1]).await.unwrap();
+    assert_eq!(paginated.total, 12);
+}
+
+#[test]
+fn test_only_indexable_equalities_are_planned() {
+    let planned = QueryBuilder::new()
+        .source_contains("engine".to_string())
+        .event_type_equals(EventType::Custom("audit".to_string()))
+        .build();
+    assert_eq!(QueryPlanner::index_lookup(&planned), Some(IndexLookup::EventType(EventType::Custom("audit".to_string()))));
+
+    let unplanned = [
+        QueryBuilder::new().severity_equals(EventSeverity::Info).build(),
+        QueryBuilder::new().where_field(QueryField::SessionId, QueryCondition::Equals(serde_json::Value::Null)).build(),
+        QueryBuilder::new().where_field(QueryField::EventType, QueryCondition::NotEquals(serde_json::json!("SystemError"))).build(),
+        EventQuery::new(),
+    ];
+    for query in &unplanned {
+        assert_eq!(QueryPlanner::index_lookup(query), None);
+    }
+}
@@ -0,0 +1,104 @@
+use event_store::store::{FileEventStore, MemoryEventStore};
+use event_store::{Event, EventSeverity, EventStorage, EventStoreError, EventType};
+use uuid::Uuid;
+
+fn event(session_id: Option<&str>) -> Event {
+    Event::new(
+        EventType::CommitmentSubmitted,
+        EventSeverity::Info,
+        "vote-engine".to_string(),
+        "commitment".to_string(),
+        session_id.map(str::to_string),
+        None,
+    )
+}
+
+async fn versions(store: &dyn EventStorage, session_id: &str) -> Vec<u64> {
+    let mut versions: Vec<u64> = store
+        .get_events_by_session(session_id)
+        .await
+        .unwrap()
+        .iter()
+        .map(|e| e.version)
+        .collect();
+    versions.sort();
+    versions
+}
+
+#[tokio::test]
+async fn test_versions_are_assigned_sequentially_per_session() {
+    let store = MemoryEventStore::new();
+    for _ in 0..3 {
+        store.store_event(event(Some("vote-1"))).await.unwrap();
+    }
+    store.store_event(event(Some("vote-2"))).await.unwrap();
+    store.store_event(event(None)).await.unwrap();
+
+    assert_eq!(versions(&store, "vote-1").await, [1, 2, 3]);
+    assert_eq!(versions(&store, "vote-2").await, [1]);
+    assert_eq!(store.session_version("vote-1").await.unwrap(), 3);
+    assert_eq!(store.session_version("vote-3").await.unwrap(), 0);
+
+    // 期望的下一个版本被接受
+    store.store_event(event(Some("vote-1")).with_version(4)).await.unwrap();
+    assert_eq!(versions(&store, "vote-1").await, [1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn test_duplicate_and_out_of_order_versions_are_rejected() {
+    let store = MemoryEventStore::new();
+    store.store_event(event(Some("vote-1"))).await.unwrap();
+    store.store_event(event(Some("vote-1"))).await.unwrap();
+
+    let duplicate = store.store_event(event(Some("vote-1")).with_version(2)).await;
+    assert!(matches!(
+        duplicate,
+        Err(EventStoreError::VersionConflict { ref session_id, expected: 3, actual: 2 }) if session_id == "vote-1"
+    ));
+    let skipped = store.store_event(event(Some("vote-1")).with_version(5)).await;
+    assert!(matches!(skipped, Err(EventStoreError::VersionConflict { expected: 3, actual: 5, .. })));
+    assert_eq!(versions(&store, "vote-1").await, [1, 2]);
+
+    // 删除事件后版本不回退
+    let latest = store.get_events_by_session("vote-1").await.unwrap().into_iter().find(|e| e.version == 2).unwrap();
+    store.delete_event(latest.id).await.unwrap();
+    let reused = store.store_event(event(Some("vote-1")).with_version(2)).await;
+    assert!(matches!(reused, Err(EventStoreError::VersionConflict { expected: 3, .. })));
+
+    // 覆盖同一事件保留原版本
+    let mut first = store.get_events_by_session("vote-1").await.unwrap().remove(0);
+    first.message = "updated".to_string();
+    store.store_event(first.clone()).await.unwrap();
+    assert_eq!(store.get_event(first.id).await.unwrap().unwrap().version, 1);
+}
+
+#[tokio::test]
+async fn test_sessionless_events_are_not_versioned() {
+    let store = MemoryEventStore::new();
+    let unversioned = event(None);
+    let versioned = event(None).with_version(7);
+    let ids = [unversioned.id, versioned.id];
+    store.store_event(unversioned).await.unwrap();
+    store.store_event(versioned).await.unwrap();
+
+    for id in ids {
+        assert_eq!(store.get_event(id).await.unwrap().unwrap().version, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_file_store_keeps_versions_across_reload() {
+    let path = std::env::temp_dir().join(format!("event-store-{}", Uuid::new_v4())).join("events.json");
+    let store = FileEventStore::new(path.clone());
+    for _ in 0..3 {
+        store.store_event(event(Some("vote-1"))).await.unwrap();
+    }
+
+    let reloaded = FileEventStore::new(path.clone());
+    reloaded.load_from_file().await.unwrap();
+    assert_eq!(versions(&reloaded, "vote-1").await, [1, 2, 3]);
+    let conflict = reloaded.store_event(event(Some("vote-1")).with_version(3)).await;
+    assert!(matches!(conflict, Err(EventStoreError::VersionConflict { expected: 4, .. })));
+    reloaded.store_event(event(Some("vote-1")).with_version(4)).await.unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
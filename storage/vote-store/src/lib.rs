@@ -2,6 +2,10 @@ pub mod traits;
 pub mod memory;
 pub mod sqlite;
 pub mod postgres;
+pub mod migrate;
+pub mod retention;
+pub mod retry;
+mod timing;
 
 pub use traits::*;
 pub use memory::*;
@@ -12,6 +12,8 @@ pub struct MemoryVoteStore {
     votes: Arc<RwLock<HashMap<String, Vote>>>,
     commitments: Arc<RwLock<HashMap<String, Commitment>>>,
     reveals: Arc<RwLock<HashMap<String, Reveal>>>,
+    /// Soft-deleted votes, kept out of every read
+    deleted: Arc<RwLock<HashMap<String, Vote>>>,
 }
 
 impl Default for MemoryVoteStore {
@@ -26,6 +28,7 @@ impl MemoryVoteStore {
             votes: Arc::new(RwLock::new(HashMap::new())),
             commitments: Arc::new(RwLock::new(HashMap::new())),
             reveals: Arc::new(RwLock::new(HashMap::new())),
+            deleted: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 }
@@ -39,10 +42,10 @@ impl VoteStore for MemoryVoteStore {
         Ok(())
     }
 
-    async fn get_vote(&self, id: &str) -> Result<Vote, StoreError> {
+    async fn get_vote(&self, id: &VoteId) -> Result<Vote, StoreError> {
         debug!("Getting vote: {}", id);
         let votes = self.votes.read().await;
-        votes.get(id)
+        votes.get(id.as_str())
             .cloned()
             .ok_or_else(|| StoreError::VoteNotFound { id: id.to_string() })
     }
@@ -86,10 +89,10 @@ impl VoteStore for MemoryVoteStore {
         })
     }
 
-    async fn update_vote_status(&self, id: &str, status: VoteStatus) -> Result<(), StoreError> {
+    async fn update_vote_status(&self, id: &VoteId, status: VoteStatus) -> Result<(), StoreError> {
         debug!("Updating vote status: {} -> {:?}", id, status);
         let mut votes = self.votes.write().await;
-        if let Some(vote) = votes.get_mut(id) {
+        if let Some(vote) = votes.get_mut(id.as_str()) {
             vote.status = status;
             Ok(())
         } else {
@@ -97,10 +100,21 @@ impl VoteStore for MemoryVoteStore {
         }
     }
 
-    async fn update_vote_results(&self, id: &str, results: &VoteResults) -> Result<(), StoreError> {
+    async fn set_legal_hold(&self, id: &VoteId, hold: bool) -> Result<(), StoreError> {
+        debug!("Setting legal hold: {} -> {}", id, hold);
+        let mut votes = self.votes.write().await;
+        if let Some(vote) = votes.get_mut(id.as_str()) {
+            vote.legal_hold = hold;
+            Ok(())
+        } else {
+            Err(StoreError::VoteNotFound { id: id.to_string() })
+        }
+    }
+
+    async fn update_vote_results(&self, id: &VoteId, results: &VoteResults) -> Result<(), StoreError> {
         debug!("Updating vote results: {}", id);
         let mut votes = self.votes.write().await;
-        if let Some(vote) = votes.get_mut(id) {
+        if let Some(vote) = votes.get_mut(id.as_str()) {
             vote.results = Some(results.clone());
             Ok(())
         } else {
@@ -115,20 +129,20 @@ impl VoteStore for MemoryVoteStore {
         Ok(())
     }
 
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, StoreError> {
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, StoreError> {
         debug!("Getting commitment: {}:{}", vote_id, voter);
         let commitments = self.commitments.read().await;
         let commitment = commitments.values()
-            .find(|c| c.vote_id == vote_id && c.voter == voter)
+            .find(|c| c.vote_id == vote_id.as_str() && c.voter == voter)
             .cloned();
         Ok(commitment)
     }
 
-    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, StoreError> {
+    async fn list_commitments(&self, vote_id: &VoteId) -> Result<Vec<Commitment>, StoreError> {
         debug!("Listing commitments for vote: {}", vote_id);
         let commitments = self.commitments.read().await;
         let vote_commitments: Vec<Commitment> = commitments.values()
-            .filter(|c| c.vote_id == vote_id)
+            .filter(|c| c.vote_id == vote_id.as_str())
             .cloned()
             .collect();
         Ok(vote_commitments)
@@ -141,40 +155,56 @@ impl VoteStore for MemoryVoteStore {
         Ok(())
     }
 
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, StoreError> {
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, StoreError> {
         debug!("Listing reveals for vote: {}", vote_id);
         let reveals = self.reveals.read().await;
         let vote_reveals: Vec<Reveal> = reveals.values()
-            .filter(|r| r.vote_id == vote_id)
+            .filter(|r| r.vote_id == vote_id.as_str())
             .cloned()
             .collect();
         Ok(vote_reveals)
     }
 
-    async fn get_reveal(&self, vote_id: &str, voter: &str) -> Result<Option<Reveal>, StoreError> {
+    async fn get_reveal(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Reveal>, StoreError> {
         debug!("Getting reveal: {}:{}", vote_id, voter);
         let reveals = self.reveals.read().await;
         let reveal = reveals.values()
-            .find(|r| r.vote_id == vote_id && r.voter == voter)
+            .find(|r| r.vote_id == vote_id.as_str() && r.voter == voter)
             .cloned();
         Ok(reveal)
     }
 
-    async fn delete_vote(&self, id: &str) -> Result<(), StoreError> {
+    async fn delete_vote(&self, id: &VoteId) -> Result<(), StoreError> {
         debug!("Deleting vote: {}", id);
         let mut votes = self.votes.write().await;
-        votes.remove(id);
+        votes.remove(id.as_str());
         
         // Also remove related commitments and reveals
         let mut commitments = self.commitments.write().await;
-        commitments.retain(|_, c| c.vote_id != id);
+        commitments.retain(|_, c| c.vote_id != id.as_str());
         
         let mut reveals = self.reveals.write().await;
-        reveals.retain(|_, r| r.vote_id != id);
+        reveals.retain(|_, r| r.vote_id != id.as_str());
         
         Ok(())
     }
 
+    async fn soft_delete_vote(&self, id: &VoteId) -> Result<(), StoreError> {
+        debug!("Soft-deleting vote: {}", id);
+        let mut votes = self.votes.write().await;
+        let vote = votes.remove(id.as_str()).ok_or_else(|| StoreError::VoteNotFound { id: id.to_string() })?;
+        self.deleted.write().await.insert(vote.id.clone(), vote);
+        Ok(())
+    }
+
+    async fn get_voter_activity(&self, voter: &str, page: u32, page_size: u32) -> Result<Page<VoteParticipation>, StoreError> {
+        debug!("Getting activity for voter: {}", voter);
+        let votes = self.votes.read().await;
+        let commitments = self.commitments.read().await;
+        let reveals = self.reveals.read().await;
+        Ok(VoteParticipation::page_in_memory(voter, &votes, commitments.values(), reveals.values(), page, page_size))
+    }
+
     async fn get_stats(&self) -> Result<StoreStats, StoreError> {
         debug!("Getting storage stats");
         let votes = self.votes.read().await;
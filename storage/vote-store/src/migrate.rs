@@ -0,0 +1,206 @@
+use std::path::PathBuf;
+
+use chrono::SubsecRound;
+use shared_types::*;
+use tracing::info;
+
+use crate::traits::{StoreError, StoreStats, VoteBundle, VoteStore};
+
+/// Options for copying votes from one store to another
+#[derive(Debug, Clone)]
+pub struct MigrateOptions {
+    /// Read and count everything without writing to the destination
+    pub dry_run: bool,
+    /// Votes fetched per `list_votes` page
+    pub page_size: u32,
+    /// File recording the last migrated vote id, so an interrupted run can resume
+    pub checkpoint: Option<PathBuf>,
+}
+
+impl Default for MigrateOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            page_size: 100,
+            checkpoint: None,
+        }
+    }
+}
+
+/// What a migration run copied
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    /// Vote id the run resumed after, if a checkpoint was found
+    pub resumed_after: Option<String>,
+    pub votes: u32,
+    pub commitments: u32,
+    pub reveals: u32,
+}
+
+/// Outcome of comparing two stores after a migration
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VerificationReport {
+    pub source: StoreStats,
+    pub destination: StoreStats,
+    /// Vote ids whose bundles were compared record by record
+    pub spot_checked: Vec<String>,
+    /// Human-readable differences; empty when the stores match
+    pub mismatches: Vec<String>,
+}
+
+impl VerificationReport {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Copy every vote, with its commitments and reveals, from `source` to `destination`.
+///
+/// Votes are copied oldest first. With a checkpoint configured, the id of each copied
+/// vote is recorded after it is written and a later run skips everything up to it.
+pub async fn migrate(
+    source: &dyn VoteStore,
+    destination: &dyn VoteStore,
+    options: &MigrateOptions,
+) -> Result<MigrationReport, StoreError> {
+    let votes = all_votes(source, options.page_size).await?;
+    let resumed_after = match &options.checkpoint {
+        Some(path) if path.exists() => Some(std::fs::read_to_string(path)?.trim().to_string()),
+        _ => None,
+    };
+    let start = match &resumed_after {
+        Some(id) => votes.iter().position(|vote| &vote.id == id)
+            .map(|index| index + 1)
+            .ok_or_else(|| StoreError::VoteNotFound { id: id.clone() })?,
+        None => 0,
+    };
+    if let Some(id) = &resumed_after {
+        info!("Resuming migration after vote {} ({} of {} already copied)", id, start, votes.len());
+    }
+
+    let mut report = MigrationReport {
+        dry_run: options.dry_run,
+        resumed_after,
+        ..Default::default()
+    };
+    for vote in &votes[start..] {
+        let bundle = source.export_vote(&VoteId::parse(vote.id.clone())?).await?;
+        report.votes += 1;
+        report.commitments += bundle.commitments.len() as u32;
+        report.reveals += bundle.reveals.len() as u32;
+        if options.dry_run {
+            continue;
+        }
+
+        destination.import_vote(bundle).await?;
+        if let Some(path) = &options.checkpoint {
+            std::fs::write(path, &vote.id)?;
+        }
+    }
+
+    info!(
+        "Migrated {} votes, {} commitments, {} reveals{}",
+        report.votes, report.commitments, report.reveals,
+        if options.dry_run { " (dry run)" } else { "" }
+    );
+    Ok(report)
+}
+
+/// Compare record counts and, for up to `spot_checks` votes spread across the
+/// source, the full exported bundles.
+///
+/// Timestamps are compared at microsecond precision, the finest that every backend keeps.
+pub async fn verify(
+    source: &dyn VoteStore,
+    destination: &dyn VoteStore,
+    spot_checks: usize,
+) -> Result<VerificationReport, StoreError> {
+    let source_stats = source.get_stats().await?;
+    let destination_stats = destination.get_stats().await?;
+    let mut mismatches = Vec::new();
+    for (name, expected, actual) in [
+        ("votes", source_stats.total_votes, destination_stats.total_votes),
+        ("commitments", source_stats.total_commitments, destination_stats.total_commitments),
+        ("reveals", source_stats.total_reveals, destination_stats.total_reveals),
+    ] {
+        if expected != actual {
+            mismatches.push(format!("{} count differs: source {}, destination {}", name, expected, actual));
+        }
+    }
+
+    let votes = all_votes(source, 100).await?;
+    let mut spot_checked = Vec::new();
+    if spot_checks > 0 && !votes.is_empty() {
+        let step = votes.len().div_ceil(spot_checks);
+        for vote in votes.iter().step_by(step) {
+            spot_checked.push(vote.id.clone());
+            let id = VoteId::parse(vote.id.clone())?;
+            let expected = normalized(source.export_vote(&id).await?);
+            match destination.export_vote(&id).await {
+                Ok(actual) => {
+                    let actual = normalized(actual);
+                    if serde_json::to_value(&expected)? != serde_json::to_value(&actual)? {
+                        mismatches.push(format!("vote {} differs between source and destination", vote.id));
+                    }
+                }
+                Err(StoreError::VoteNotFound { .. }) => {
+                    mismatches.push(format!("vote {} is missing from the destination", vote.id));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    Ok(VerificationReport {
+        source: source_stats,
+        destination: destination_stats,
+        spot_checked,
+        mismatches,
+    })
+}
+
+/// Every vote in the store, oldest first
+async fn all_votes(store: &dyn VoteStore, page_size: u32) -> Result<Vec<Vote>, StoreError> {
+    let page_size = page_size.max(1);
+    let mut votes = Vec::new();
+    let mut page = 0;
+    loop {
+        let result = store.list_votes(ListQuery { page, page_size, status: None, creator: None }).await?;
+        let fetched = result.items.len();
+        votes.extend(result.items);
+        if fetched < page_size as usize {
+            break;
+        }
+        page += 1;
+    }
+    votes.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+    votes.dedup_by(|a, b| a.id == b.id);
+    Ok(votes)
+}
+
+/// Bundle with records in a fixed order and timestamps truncated to microseconds
+fn normalized(mut bundle: VoteBundle) -> VoteBundle {
+    let vote = &mut bundle.vote;
+    for timestamp in [
+        &mut vote.created_at,
+        &mut vote.commitment_start,
+        &mut vote.commitment_end,
+        &mut vote.reveal_start,
+        &mut vote.reveal_end,
+    ] {
+        *timestamp = timestamp.trunc_subsecs(6);
+    }
+    if let Some(results) = &mut vote.results {
+        results.calculated_at = results.calculated_at.trunc_subsecs(6);
+    }
+    for commitment in &mut bundle.commitments {
+        commitment.created_at = commitment.created_at.trunc_subsecs(6);
+    }
+    for reveal in &mut bundle.reveals {
+        reveal.created_at = reveal.created_at.trunc_subsecs(6);
+    }
+    bundle.commitments.sort_by(|a, b| a.voter.cmp(&b.voter));
+    bundle.reveals.sort_by(|a, b| a.voter.cmp(&b.voter));
+    bundle
+}
@@ -1,14 +1,33 @@
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use sqlx::{Execute, PgPool, Row};
 use shared_types::*;
 use shared_config::DatabaseConfig;
 use tracing::{debug, info};
 
+use crate::retry::RetryPolicy;
+use crate::timing::QueryTimer;
 use crate::traits::{VoteStore, StoreError, StoreStats};
 
+/// Columns added after a table's first release, as (table, column, definition).
+///
+/// `CREATE TABLE IF NOT EXISTS` leaves an existing table alone, so databases created by an
+/// earlier release get these through `ALTER TABLE` instead.
+const ADDED_COLUMNS: &[(&str, &str, &str)] = &[
+    ("votes", "tie_break", "TEXT"),
+    ("votes", "legal_hold", "BOOLEAN NOT NULL DEFAULT FALSE"),
+    ("votes", "completion_webhook_url", "TEXT"),
+    ("votes", "client_request_id", "TEXT"),
+    ("votes", "reveal_public_key", "TEXT"),
+    ("votes", "deleted_at", "TIMESTAMPTZ"),
+    ("commitments", "range_proof", "TEXT"),
+    ("reveals", "ciphertext", "TEXT"),
+];
+
 /// PostgreSQL implementation of VoteStore
 pub struct PostgresVoteStore {
     pool: PgPool,
+    timer: QueryTimer,
+    retry: RetryPolicy,
 }
 
 impl PostgresVoteStore {
@@ -21,11 +40,17 @@ impl PostgresVoteStore {
                 message: format!("Failed to connect to PostgreSQL: {}", e),
             })?;
         
-        let store = Self { pool };
+        let store = Self { pool, timer: QueryTimer::new("postgres", config.slow_query_threshold_ms), retry: RetryPolicy::default() };
         store.init_tables().await?;
         
         Ok(store)
     }
+
+    /// Replace how writes are retried after transient errors such as serialization failures
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
     
     async fn init_tables(&self) -> Result<(), StoreError> {
         info!("Initializing PostgreSQL tables");
@@ -46,7 +71,13 @@ impl PostgresVoteStore {
                 reveal_start TIMESTAMPTZ NOT NULL,
                 reveal_end TIMESTAMPTZ NOT NULL,
                 status VARCHAR(50) NOT NULL,
-                results JSONB
+                results JSONB,
+                tie_break TEXT,
+                legal_hold BOOLEAN NOT NULL DEFAULT FALSE,
+                completion_webhook_url TEXT,
+                client_request_id TEXT,
+                reveal_public_key TEXT,
+                deleted_at TIMESTAMPTZ
             )
             "#
         )
@@ -63,6 +94,7 @@ impl PostgresVoteStore {
                 commitment_hash VARCHAR(255) NOT NULL,
                 salt VARCHAR(255) NOT NULL,
                 created_at TIMESTAMPTZ NOT NULL,
+                range_proof TEXT,
                 UNIQUE(vote_id, voter)
             )
             "#
@@ -80,6 +112,7 @@ impl PostgresVoteStore {
                 value JSONB NOT NULL,
                 salt VARCHAR(255) NOT NULL,
                 created_at TIMESTAMPTZ NOT NULL,
+                ciphertext TEXT,
                 UNIQUE(vote_id, voter)
             )
             "#
@@ -87,6 +120,12 @@ impl PostgresVoteStore {
         .execute(&self.pool)
         .await?;
         
+        for (table, column, definition) in ADDED_COLUMNS {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN IF NOT EXISTS {} {}", table, column, definition))
+                .execute(&self.pool)
+                .await?;
+        }
+        
         // Create indexes
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_commitments_vote_id ON commitments(vote_id)")
             .execute(&self.pool)
@@ -96,10 +135,22 @@ impl PostgresVoteStore {
             .execute(&self.pool)
             .await?;
         
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_commitments_voter ON commitments(voter)")
+            .execute(&self.pool)
+            .await?;
+        
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_reveals_voter ON reveals(voter)")
+            .execute(&self.pool)
+            .await?;
+        
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_votes_creator ON votes(creator)")
             .execute(&self.pool)
             .await?;
         
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_votes_client_request ON votes(creator, client_request_id)")
+            .execute(&self.pool)
+            .await?;
+        
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_votes_status ON votes(status)")
             .execute(&self.pool)
             .await?;
@@ -131,6 +182,12 @@ impl PostgresVoteStore {
             _ => VoteStatus::Created,
         }
     }
+    
+    /// A commitment's stored range proof; one that fails to load is an error, not a missing proof
+    fn range_proof(row: &sqlx::postgres::PgRow) -> Result<Option<RangeProof>, StoreError> {
+        let stored: Option<String> = row.try_get("range_proof")?;
+        Ok(stored.map(|s| serde_json::from_str(&s)).transpose()?)
+    }
 }
 
 #[async_trait]
@@ -138,43 +195,49 @@ impl VoteStore for PostgresVoteStore {
     async fn create_vote(&self, vote: Vote) -> Result<(), StoreError> {
         debug!("Creating vote: {}", vote.id);
         
-        sqlx::query(
-            r#"
-            INSERT INTO votes (
-                id, title, description, template_id, template_params, creator,
-                created_at, commitment_start, commitment_end, reveal_start, reveal_end,
-                status, results
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-            "#
-        )
-        .bind(&vote.id)
-        .bind(&vote.title)
-        .bind(&vote.description)
-        .bind(&vote.template_id)
-        .bind(&vote.template_params)
-        .bind(&vote.creator)
-        .bind(vote.created_at)
-        .bind(vote.commitment_start)
-        .bind(vote.commitment_end)
-        .bind(vote.reveal_start)
-        .bind(vote.reveal_end)
-        .bind(Self::vote_status_to_string(&vote.status))
-        .bind(serde_json::to_string(&vote.results).unwrap_or_default())
-        .execute(&self.pool)
-        .await?;
+        self.retry.run("create_vote", || {
+            let query = sqlx::query(
+                r#"
+                INSERT INTO votes (
+                    id, title, description, template_id, template_params, creator,
+                    created_at, commitment_start, commitment_end, reveal_start, reveal_end,
+                    status, results, tie_break, legal_hold, completion_webhook_url,
+                    client_request_id, reveal_public_key
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+                "#
+            )
+            .bind(&vote.id)
+            .bind(&vote.title)
+            .bind(&vote.description)
+            .bind(&vote.template_id)
+            .bind(&vote.template_params)
+            .bind(&vote.creator)
+            .bind(vote.created_at)
+            .bind(vote.commitment_start)
+            .bind(vote.commitment_end)
+            .bind(vote.reveal_start)
+            .bind(vote.reveal_end)
+            .bind(Self::vote_status_to_string(&vote.status))
+            .bind(serde_json::to_string(&vote.results).unwrap_or_default())
+            .bind(serde_json::to_string(&vote.tie_break).unwrap_or_default())
+            .bind(vote.legal_hold)
+            .bind(&vote.completion_webhook_url)
+            .bind(&vote.client_request_id)
+            .bind(&vote.reveal_public_key);
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
         
         Ok(())
     }
 
-    async fn get_vote(&self, id: &str) -> Result<Vote, StoreError> {
+    async fn get_vote(&self, id: &VoteId) -> Result<Vote, StoreError> {
         debug!("Getting vote: {}", id);
         
-        let row = sqlx::query(
-            "SELECT * FROM votes WHERE id = $1"
+        let query = sqlx::query(
+            "SELECT * FROM votes WHERE id = $1 AND deleted_at IS NULL"
         )
-        .bind(id)
-        .fetch_one(&self.pool)
-        .await
+        .bind(id.as_str());
+        let row = self.timer.time(query.sql(), query.fetch_one(&self.pool)).await
         .map_err(|_| StoreError::VoteNotFound { id: id.to_string() })?;
         
         let vote = Vote {
@@ -198,6 +261,13 @@ impl VoteStore for PostgresVoteStore {
                     None
                 }
             },
+            tie_break: row.try_get::<Option<String>, _>("tie_break").ok().flatten()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+            legal_hold: row.try_get("legal_hold").unwrap_or(false),
+            completion_webhook_url: row.try_get("completion_webhook_url").ok().flatten(),
+            client_request_id: row.try_get("client_request_id").ok().flatten(),
+            reveal_public_key: row.try_get("reveal_public_key").ok().flatten(),
         };
         
         Ok(vote)
@@ -206,7 +276,7 @@ impl VoteStore for PostgresVoteStore {
     async fn list_votes(&self, query: ListQuery) -> Result<Page<Vote>, StoreError> {
         debug!("Listing votes: page={}, size={}", query.page, query.page_size);
         
-        let mut sql = "SELECT * FROM votes WHERE 1=1".to_string();
+        let mut sql = "SELECT * FROM votes WHERE deleted_at IS NULL".to_string();
         let mut param_count = 0;
         
         if let Some(_status) = &query.status {
@@ -235,7 +305,7 @@ impl VoteStore for PostgresVoteStore {
         query_builder = query_builder.bind(query.page_size as i64);
         query_builder = query_builder.bind((query.page * query.page_size) as i64);
         
-        let rows = query_builder.fetch_all(&self.pool).await?;
+        let rows = self.timer.time(&sql, query_builder.fetch_all(&self.pool)).await?;
         
         let mut items = Vec::new();
         for row in rows {
@@ -260,14 +330,20 @@ impl VoteStore for PostgresVoteStore {
                         None
                     }
                 },
+                tie_break: row.try_get::<Option<String>, _>("tie_break").ok().flatten()
+                    .and_then(|s| serde_json::from_str(&s).ok())
+                    .unwrap_or_default(),
+                legal_hold: row.try_get("legal_hold").unwrap_or(false),
+                completion_webhook_url: row.try_get("completion_webhook_url").ok().flatten(),
+                client_request_id: row.try_get("client_request_id").ok().flatten(),
+                reveal_public_key: row.try_get("reveal_public_key").ok().flatten(),
             };
             items.push(vote);
         }
         
         // Get total count
-        let count_row = sqlx::query("SELECT COUNT(*) as count FROM votes")
-            .fetch_one(&self.pool)
-            .await?;
+        let count_query = sqlx::query("SELECT COUNT(*) as count FROM votes WHERE deleted_at IS NULL");
+        let count_row = self.timer.time(count_query.sql(), count_query.fetch_one(&self.pool)).await?;
         let total = count_row.get::<i64, _>("count") as u32;
         let total_pages = total.div_ceil(query.page_size);
         
@@ -280,26 +356,44 @@ impl VoteStore for PostgresVoteStore {
         })
     }
 
-    async fn update_vote_status(&self, id: &str, status: VoteStatus) -> Result<(), StoreError> {
+    async fn update_vote_status(&self, id: &VoteId, status: VoteStatus) -> Result<(), StoreError> {
         debug!("Updating vote status: {} -> {:?}", id, status);
         
-        sqlx::query("UPDATE votes SET status = $1 WHERE id = $2")
-            .bind(Self::vote_status_to_string(&status))
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        self.retry.run("update_vote_status", || {
+            let query = sqlx::query("UPDATE votes SET status = $1 WHERE id = $2")
+                .bind(Self::vote_status_to_string(&status))
+                .bind(id.as_str());
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
         
         Ok(())
     }
 
-    async fn update_vote_results(&self, id: &str, results: &VoteResults) -> Result<(), StoreError> {
+    async fn set_legal_hold(&self, id: &VoteId, hold: bool) -> Result<(), StoreError> {
+        debug!("Setting legal hold: {} -> {}", id, hold);
+        
+        let result = self.retry.run("set_legal_hold", || {
+            let query = sqlx::query("UPDATE votes SET legal_hold = $1 WHERE id = $2")
+                .bind(hold)
+                .bind(id.as_str());
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
+        if result.rows_affected() == 0 {
+            return Err(StoreError::VoteNotFound { id: id.to_string() });
+        }
+        
+        Ok(())
+    }
+
+    async fn update_vote_results(&self, id: &VoteId, results: &VoteResults) -> Result<(), StoreError> {
         debug!("Updating vote results: {}", id);
         
-        sqlx::query("UPDATE votes SET results = $1 WHERE id = $2")
-            .bind(serde_json::to_string(results).unwrap_or_default())
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        self.retry.run("update_vote_results", || {
+            let query = sqlx::query("UPDATE votes SET results = $1 WHERE id = $2")
+                .bind(serde_json::to_string(results).unwrap_or_default())
+                .bind(id.as_str());
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
         
         Ok(())
     }
@@ -307,40 +401,43 @@ impl VoteStore for PostgresVoteStore {
     async fn save_commitment(&self, commitment: Commitment) -> Result<(), StoreError> {
         debug!("Saving commitment: {}", commitment.id);
         
-        sqlx::query(
-            r#"
-            INSERT INTO commitments (
-                id, vote_id, voter, commitment_hash, salt, created_at
-            ) VALUES ($1, $2, $3, $4, $5, $6)
-            ON CONFLICT (vote_id, voter) DO UPDATE SET
-                id = EXCLUDED.id,
-                commitment_hash = EXCLUDED.commitment_hash,
-                salt = EXCLUDED.salt,
-                created_at = EXCLUDED.created_at
-            "#
-        )
-        .bind(&commitment.id)
-        .bind(&commitment.vote_id)
-        .bind(&commitment.voter)
-        .bind(&commitment.commitment_hash)
-        .bind(&commitment.salt)
-        .bind(commitment.created_at)
-        .execute(&self.pool)
-        .await?;
+        let range_proof = commitment.range_proof.as_ref().map(serde_json::to_string).transpose()?;
+        self.retry.run("save_commitment", || {
+            let query = sqlx::query(
+                r#"
+                INSERT INTO commitments (
+                    id, vote_id, voter, commitment_hash, salt, created_at, range_proof
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (vote_id, voter) DO UPDATE SET
+                    id = EXCLUDED.id,
+                    commitment_hash = EXCLUDED.commitment_hash,
+                    salt = EXCLUDED.salt,
+                    created_at = EXCLUDED.created_at,
+                    range_proof = EXCLUDED.range_proof
+                "#
+            )
+            .bind(&commitment.id)
+            .bind(&commitment.vote_id)
+            .bind(&commitment.voter)
+            .bind(&commitment.commitment_hash)
+            .bind(&commitment.salt)
+            .bind(commitment.created_at)
+            .bind(&range_proof);
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
         
         Ok(())
     }
 
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, StoreError> {
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, StoreError> {
         debug!("Getting commitment: {}:{}", vote_id, voter);
         
-        let row = sqlx::query(
+        let query = sqlx::query(
             "SELECT * FROM commitments WHERE vote_id = $1 AND voter = $2"
         )
-        .bind(vote_id)
-        .bind(voter)
-        .fetch_optional(&self.pool)
-        .await?;
+        .bind(vote_id.as_str())
+        .bind(voter);
+        let row = self.timer.time(query.sql(), query.fetch_optional(&self.pool)).await?;
         
         if let Some(row) = row {
             let commitment = Commitment {
@@ -350,6 +447,7 @@ impl VoteStore for PostgresVoteStore {
                 commitment_hash: row.get("commitment_hash"),
                 salt: row.get("salt"),
                 created_at: row.get("created_at"),
+                range_proof: Self::range_proof(&row)?,
             };
             Ok(Some(commitment))
         } else {
@@ -357,15 +455,14 @@ impl VoteStore for PostgresVoteStore {
         }
     }
 
-    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, StoreError> {
+    async fn list_commitments(&self, vote_id: &VoteId) -> Result<Vec<Commitment>, StoreError> {
         debug!("Listing commitments for vote: {}", vote_id);
         
-        let rows = sqlx::query(
+        let query = sqlx::query(
             "SELECT * FROM commitments WHERE vote_id = $1 ORDER BY created_at"
         )
-        .bind(vote_id)
-        .fetch_all(&self.pool)
-        .await?;
+        .bind(vote_id.as_str());
+        let rows = self.timer.time(query.sql(), query.fetch_all(&self.pool)).await?;
         
         let mut commitments = Vec::new();
         for row in rows {
@@ -376,6 +473,7 @@ impl VoteStore for PostgresVoteStore {
                 commitment_hash: row.get("commitment_hash"),
                 salt: row.get("salt"),
                 created_at: row.get("created_at"),
+                range_proof: Self::range_proof(&row)?,
             };
             commitments.push(commitment);
         }
@@ -386,39 +484,41 @@ impl VoteStore for PostgresVoteStore {
     async fn save_reveal(&self, reveal: Reveal) -> Result<(), StoreError> {
         debug!("Saving reveal: {}", reveal.id);
         
-        sqlx::query(
-            r#"
-            INSERT INTO reveals (
-                id, vote_id, voter, value, salt, created_at
-            ) VALUES ($1, $2, $3, $4, $5, $6)
-            ON CONFLICT (vote_id, voter) DO UPDATE SET
-                id = EXCLUDED.id,
-                value = EXCLUDED.value,
-                salt = EXCLUDED.salt,
-                created_at = EXCLUDED.created_at
-            "#
-        )
-        .bind(&reveal.id)
-        .bind(&reveal.vote_id)
-        .bind(&reveal.voter)
-        .bind(&reveal.value)
-        .bind(&reveal.salt)
-        .bind(reveal.created_at)
-        .execute(&self.pool)
-        .await?;
+        self.retry.run("save_reveal", || {
+            let query = sqlx::query(
+                r#"
+                INSERT INTO reveals (
+                    id, vote_id, voter, value, salt, created_at, ciphertext
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (vote_id, voter) DO UPDATE SET
+                    id = EXCLUDED.id,
+                    value = EXCLUDED.value,
+                    salt = EXCLUDED.salt,
+                    created_at = EXCLUDED.created_at,
+                    ciphertext = EXCLUDED.ciphertext
+                "#
+            )
+            .bind(&reveal.id)
+            .bind(&reveal.vote_id)
+            .bind(&reveal.voter)
+            .bind(&reveal.value)
+            .bind(&reveal.salt)
+            .bind(reveal.created_at)
+            .bind(&reveal.ciphertext);
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
         
         Ok(())
     }
 
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, StoreError> {
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, StoreError> {
         debug!("Listing reveals for vote: {}", vote_id);
         
-        let rows = sqlx::query(
+        let query = sqlx::query(
             "SELECT * FROM reveals WHERE vote_id = $1 ORDER BY created_at"
         )
-        .bind(vote_id)
-        .fetch_all(&self.pool)
-        .await?;
+        .bind(vote_id.as_str());
+        let rows = self.timer.time(query.sql(), query.fetch_all(&self.pool)).await?;
         
         let mut reveals = Vec::new();
         for row in rows {
@@ -429,6 +529,7 @@ impl VoteStore for PostgresVoteStore {
                 value: row.get("value"),
                 salt: row.get("salt"),
                 created_at: row.get("created_at"),
+                ciphertext: row.try_get("ciphertext").ok().flatten(),
             };
             reveals.push(reveal);
         }
@@ -436,16 +537,15 @@ impl VoteStore for PostgresVoteStore {
         Ok(reveals)
     }
 
-    async fn get_reveal(&self, vote_id: &str, voter: &str) -> Result<Option<Reveal>, StoreError> {
+    async fn get_reveal(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Reveal>, StoreError> {
         debug!("Getting reveal: {}:{}", vote_id, voter);
         
-        let row = sqlx::query(
+        let query = sqlx::query(
             "SELECT * FROM reveals WHERE vote_id = $1 AND voter = $2"
         )
-        .bind(vote_id)
-        .bind(voter)
-        .fetch_optional(&self.pool)
-        .await?;
+        .bind(vote_id.as_str())
+        .bind(voter);
+        let row = self.timer.time(query.sql(), query.fetch_optional(&self.pool)).await?;
         
         if let Some(row) = row {
             let reveal = Reveal {
@@ -455,6 +555,7 @@ impl VoteStore for PostgresVoteStore {
                 value: row.get("value"),
                 salt: row.get("salt"),
                 created_at: row.get("created_at"),
+                ciphertext: row.try_get("ciphertext").ok().flatten(),
             };
             Ok(Some(reveal))
         } else {
@@ -462,56 +563,120 @@ impl VoteStore for PostgresVoteStore {
         }
     }
 
-    async fn delete_vote(&self, id: &str) -> Result<(), StoreError> {
+    async fn delete_vote(&self, id: &VoteId) -> Result<(), StoreError> {
         debug!("Deleting vote: {}", id);
         
         // Delete in order to respect foreign key constraints
-        sqlx::query("DELETE FROM reveals WHERE vote_id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        self.retry.run("delete_vote", || {
+            let query = sqlx::query("DELETE FROM reveals WHERE vote_id = $1")
+                .bind(id.as_str());
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
+        
+        self.retry.run("delete_vote", || {
+            let query = sqlx::query("DELETE FROM commitments WHERE vote_id = $1")
+                .bind(id.as_str());
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
+        
+        self.retry.run("delete_vote", || {
+            let query = sqlx::query("DELETE FROM votes WHERE id = $1")
+                .bind(id.as_str());
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
         
-        sqlx::query("DELETE FROM commitments WHERE vote_id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        
-        sqlx::query("DELETE FROM votes WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        Ok(())
+    }
+
+    async fn soft_delete_vote(&self, id: &VoteId) -> Result<(), StoreError> {
+        debug!("Soft-deleting vote: {}", id);
+        
+        let result = self.retry.run("soft_delete_vote", || {
+            let query = sqlx::query("UPDATE votes SET deleted_at = $1 WHERE id = $2 AND deleted_at IS NULL")
+                .bind(chrono::Utc::now())
+                .bind(id.as_str());
+            self.timer.time(query.sql(), query.execute(&self.pool))
+        }).await?;
+        if result.rows_affected() == 0 {
+            return Err(StoreError::VoteNotFound { id: id.to_string() });
+        }
         
         Ok(())
     }
 
+    async fn get_voter_activity(&self, voter: &str, page: u32, page_size: u32) -> Result<Page<VoteParticipation>, StoreError> {
+        debug!("Getting activity for voter: {}", voter);
+        
+        let query = sqlx::query(
+            r#"
+            SELECT v.id, v.title, v.status,
+                   c.created_at AS committed_at, r.created_at AS revealed_at
+            FROM votes v
+            LEFT JOIN commitments c ON c.vote_id = v.id AND c.voter = $1
+            LEFT JOIN reveals r ON r.vote_id = v.id AND r.voter = $1
+            WHERE v.deleted_at IS NULL AND v.id IN (
+                SELECT vote_id FROM commitments WHERE voter = $1
+                UNION SELECT vote_id FROM reveals WHERE voter = $1
+            )
+            ORDER BY v.created_at DESC, v.id
+            LIMIT $2 OFFSET $3
+            "#
+        )
+        .bind(voter)
+        .bind(page_size as i64)
+        .bind(page as i64 * page_size as i64);
+        let rows = self.timer.time(query.sql(), query.fetch_all(&self.pool)).await?;
+        
+        let mut items = Vec::new();
+        for row in rows {
+            let participation = VoteParticipation {
+                vote_id: row.get("id"),
+                title: row.get("title"),
+                status: Self::string_to_vote_status(row.get::<String, _>("status").as_str()),
+                committed_at: row.get("committed_at"),
+                revealed_at: row.get("revealed_at"),
+            };
+            items.push(participation);
+        }
+        
+        let query = sqlx::query(
+            r#"
+            SELECT COUNT(*) AS count FROM votes
+            WHERE deleted_at IS NULL AND id IN (
+                SELECT vote_id FROM commitments WHERE voter = $1
+                UNION SELECT vote_id FROM reveals WHERE voter = $1
+            )
+            "#
+        )
+        .bind(voter);
+        let total = self.timer.time(query.sql(), query.fetch_one(&self.pool)).await?
+            .get::<i64, _>("count") as u32;
+        Ok(Page::new(items, total, page, page_size))
+    }
+
     async fn get_stats(&self) -> Result<StoreStats, StoreError> {
         debug!("Getting storage stats");
         
-        let votes_count = sqlx::query("SELECT COUNT(*) as count FROM votes")
-            .fetch_one(&self.pool)
-            .await?
+        let query = sqlx::query("SELECT COUNT(*) as count FROM votes WHERE deleted_at IS NULL");
+        let votes_count = self.timer.time(query.sql(), query.fetch_one(&self.pool)).await?
             .get::<i64, _>("count") as u32;
         
-        let commitments_count = sqlx::query("SELECT COUNT(*) as count FROM commitments")
-            .fetch_one(&self.pool)
-            .await?
+        let query = sqlx::query("SELECT COUNT(*) as count FROM commitments");
+        let commitments_count = self.timer.time(query.sql(), query.fetch_one(&self.pool)).await?
             .get::<i64, _>("count") as u32;
         
-        let reveals_count = sqlx::query("SELECT COUNT(*) as count FROM reveals")
-            .fetch_one(&self.pool)
-            .await?
+        let query = sqlx::query("SELECT COUNT(*) as count FROM reveals");
+        let reveals_count = self.timer.time(query.sql(), query.fetch_one(&self.pool)).await?
             .get::<i64, _>("count") as u32;
         
-        let active_votes = sqlx::query(
-            "SELECT COUNT(*) as count FROM votes WHERE status IN ('created', 'commitment_phase', 'reveal_phase')"
-        )
-        .fetch_one(&self.pool)
-        .await?
+        let query = sqlx::query(
+            "SELECT COUNT(*) as count FROM votes WHERE deleted_at IS NULL AND status IN ('created', 'commitment_phase', 'reveal_phase')"
+        );
+        let active_votes = self.timer.time(query.sql(), query.fetch_one(&self.pool)).await?
         .get::<i64, _>("count") as u32;
         
-        let completed_votes = sqlx::query("SELECT COUNT(*) as count FROM votes WHERE status = 'completed'")
-            .fetch_one(&self.pool)
-            .await?
+        let query = sqlx::query("SELECT COUNT(*) as count FROM votes WHERE deleted_at IS NULL AND status = 'completed'");
+        let completed_votes = self.timer.time(query.sql(), query.fetch_one(&self.pool)).await?
             .get::<i64, _>("count") as u32;
         
         Ok(StoreStats {
@@ -522,4 +687,8 @@ impl VoteStore for PostgresVoteStore {
             completed_votes,
         })
     }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
 }
@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use shared_config::RetentionConfig;
+use shared_types::*;
+use tracing::{info, warn};
+
+use crate::traits::{StoreError, VoteStore};
+
+/// Votes fetched per `list_votes` page while looking for expired votes
+const PAGE_SIZE: u32 = 100;
+
+/// Which ended votes the cleanup job removes
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+    /// Completed and cancelled votes whose reveal phase ended longer ago than this are removed
+    pub max_age: chrono::Duration,
+    /// Directory each vote is exported to as `<id>.json` before it is purged; without one,
+    /// expired votes are only soft-deleted so nothing is lost
+    pub archive_dir: Option<PathBuf>,
+}
+
+impl RetentionPolicy {
+    pub fn new(max_age: chrono::Duration) -> Self {
+        Self { max_age, archive_dir: None }
+    }
+
+    pub fn with_archive_dir(mut self, archive_dir: impl Into<PathBuf>) -> Self {
+        self.archive_dir = Some(archive_dir.into());
+        self
+    }
+
+    pub fn from_config(config: &RetentionConfig) -> Self {
+        Self {
+            max_age: chrono::Duration::days(config.max_age_days as i64),
+            archive_dir: config.archive_dir.as_ref().map(PathBuf::from),
+        }
+    }
+
+    /// Whether `vote` has ended and is past the retention age at `now`
+    pub fn is_expired(&self, vote: &Vote, now: DateTime<Utc>) -> bool {
+        vote.status.is_terminal() && vote.reveal_end + self.max_age < now
+    }
+}
+
+/// What one cleanup run did
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct RetentionReport {
+    /// Ids of the votes removed: purged after archiving, soft-deleted otherwise
+    pub cleaned: Vec<String>,
+    /// Ids of expired votes kept because of a legal hold
+    pub held: Vec<String>,
+    /// Expired votes that could not be archived or removed; they are retried next run
+    pub failed: usize,
+}
+
+/// Removes ended votes past the retention age, purging them once exported when an archive is
+/// set and soft-deleting them otherwise
+pub struct RetentionJob {
+    store: Arc<dyn VoteStore>,
+    policy: RetentionPolicy,
+}
+
+impl RetentionJob {
+    pub fn new(store: Arc<dyn VoteStore>, policy: RetentionPolicy) -> Self {
+        Self { store, policy }
+    }
+
+    /// Clean up every vote expired at `now`
+    ///
+    /// Candidates are collected before anything is removed so deletions do not shift the pages.
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<RetentionReport, StoreError> {
+        let mut expired = Vec::new();
+        for status in [VoteStatus::Completed, VoteStatus::Cancelled] {
+            let mut page = 0;
+            loop {
+                let query = ListQuery {
+                    page,
+                    page_size: PAGE_SIZE,
+                    status: Some(status.clone()),
+                    creator: None,
+                };
+                let votes = self.store.list_votes(query).await?.items;
+                if votes.is_empty() {
+                    break;
+                }
+                expired.extend(votes.into_iter().filter(|v| self.policy.is_expired(v, now)));
+                page += 1;
+            }
+        }
+
+        let mut report = RetentionReport::default();
+        for vote in expired {
+            if vote.legal_hold {
+                report.held.push(vote.id);
+                continue;
+            }
+            match self.clean(&vote).await {
+                Ok(()) => report.cleaned.push(vote.id),
+                Err(e) => {
+                    warn!("Failed to clean up vote {}: {}", vote.id, e);
+                    report.failed += 1;
+                }
+            }
+        }
+
+        info!(
+            "Retention run cleaned {} votes, kept {} under legal hold, {} failed",
+            report.cleaned.len(),
+            report.held.len(),
+            report.failed
+        );
+        shared_logging::metrics().record_retention_run(report.cleaned.len(), report.held.len(), report.failed);
+        Ok(report)
+    }
+
+    async fn clean(&self, vote: &Vote) -> Result<(), StoreError> {
+        let id = VoteId::parse(vote.id.clone())?;
+        let Some(dir) = &self.policy.archive_dir else {
+            return self.store.soft_delete_vote(&id).await;
+        };
+        let bundle = self.store.export_vote(&id).await?;
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(dir.join(format!("{}.json", id)), serde_json::to_vec_pretty(&bundle)?).await?;
+        self.store.delete_vote(&id).await
+    }
+
+    /// Spawn a task that runs the cleanup every `interval`
+    pub fn spawn(self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once(Utc::now()).await {
+                    warn!("Retention run failed: {}", e);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,87 @@
+//! Retrying SQL writes that failed on transient errors
+//!
+//! Serialization failures and deadlocks (common under Postgres `SERIALIZABLE`) and a busy or
+//! locked SQLite database say nothing about the statement itself; running it again usually works.
+
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// Postgres SQLSTATE for `serialization_failure`
+pub const SERIALIZATION_FAILURE: &str = "40001";
+/// Postgres SQLSTATE for `deadlock_detected`
+pub const DEADLOCK_DETECTED: &str = "40P01";
+/// SQLite primary result codes `SQLITE_BUSY` and `SQLITE_LOCKED`
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+
+/// Whether `error` is worth retrying as-is; anything else is permanent
+pub fn is_transient(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Database(db) => db.code().is_some_and(|code| is_transient_code(&code)),
+        _ => false,
+    }
+}
+
+/// Postgres reports five-character SQLSTATEs; SQLite reports (extended) result codes whose low byte
+/// is the primary code
+fn is_transient_code(code: &str) -> bool {
+    match code {
+        SERIALIZATION_FAILURE | DEADLOCK_DETECTED => true,
+        _ if code.len() < 5 => code.parse::<i32>().is_ok_and(|c| matches!(c & 0xff, SQLITE_BUSY | SQLITE_LOCKED)),
+        _ => false,
+    }
+}
+
+/// How often and how patiently the SQL stores retry a write after a transient error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first; `1` disables retrying
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for every further one
+    pub base_delay: Duration,
+    /// Upper bound on any single wait
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 4, base_delay: Duration::from_millis(20), max_delay: Duration::from_millis(500) }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (starting at 1)
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Run `attempt` until it succeeds, fails permanently or the attempts run out, returning the last error
+    ///
+    /// `attempt` is called afresh each time, so it must rebuild its statement rather than reuse one.
+    pub async fn run<T, F, Fut>(&self, operation: &str, mut attempt: F) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let mut retry = 0;
+        loop {
+            match attempt().await {
+                Err(error) if is_transient(&error) && retry + 1 < self.max_attempts => {
+                    retry += 1;
+                    let delay = self.delay(retry);
+                    warn!("Transient error in {}, retry {} of {} in {:?}: {}", operation, retry, self.max_attempts - 1, delay, error);
+                    tokio::time::sleep(delay).await;
+                }
+                Err(error) => {
+                    if retry > 0 {
+                        warn!("Giving up on {} after {} attempts: {}", operation, retry + 1, error);
+                    }
+                    return Err(error);
+                }
+                Ok(value) => return Ok(value),
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Query timing for the SQL stores
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Times each query, feeding the query-duration metric and logging slow ones with their SQL
+pub(crate) struct QueryTimer {
+    backend: &'static str,
+    threshold: Duration,
+}
+
+impl QueryTimer {
+    pub(crate) fn new(backend: &'static str, threshold_ms: u64) -> Self {
+        Self { backend, threshold: Duration::from_millis(threshold_ms) }
+    }
+
+    /// Await `query`, recording how long it took
+    pub(crate) async fn time<T>(&self, sql: &str, query: impl Future<Output = T>) -> T {
+        let started = Instant::now();
+        let output = query.await;
+        shared_logging::metrics().record_query(self.backend, sql, bound_params(sql), started.elapsed(), self.threshold);
+        output
+    }
+}
+
+/// Number of parameters bound to `sql`: the highest `$n` for Postgres, the count of `?` for SQLite
+fn bound_params(sql: &str) -> usize {
+    let numbered = sql
+        .split('$')
+        .skip(1)
+        .filter_map(|rest| {
+            let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse::<usize>().ok()
+        })
+        .max();
+    numbered.unwrap_or_else(|| sql.matches('?').count())
+}
@@ -8,40 +8,124 @@ pub trait VoteStore: Send + Sync {
     async fn create_vote(&self, vote: Vote) -> Result<(), StoreError>;
     
     /// Get a vote by ID
-    async fn get_vote(&self, id: &str) -> Result<Vote, StoreError>;
+    async fn get_vote(&self, id: &VoteId) -> Result<Vote, StoreError>;
     
     /// List votes with pagination
     async fn list_votes(&self, query: ListQuery) -> Result<Page<Vote>, StoreError>;
     
     /// Update vote status
-    async fn update_vote_status(&self, id: &str, status: VoteStatus) -> Result<(), StoreError>;
+    async fn update_vote_status(&self, id: &VoteId, status: VoteStatus) -> Result<(), StoreError>;
+    
+    /// Place or lift a legal hold, which exempts the vote from retention cleanup
+    async fn set_legal_hold(&self, id: &VoteId, hold: bool) -> Result<(), StoreError>;
     
     /// Update vote results
-    async fn update_vote_results(&self, id: &str, results: &VoteResults) -> Result<(), StoreError>;
+    async fn update_vote_results(&self, id: &VoteId, results: &VoteResults) -> Result<(), StoreError>;
     
     /// Save a commitment
     async fn save_commitment(&self, commitment: Commitment) -> Result<(), StoreError>;
     
     /// Get a commitment by vote ID and voter
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, StoreError>;
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, StoreError>;
     
     /// List commitments for a vote
-    async fn list_commitments(&self, vote_id: &str) -> Result<Vec<Commitment>, StoreError>;
+    async fn list_commitments(&self, vote_id: &VoteId) -> Result<Vec<Commitment>, StoreError>;
     
     /// Save a reveal
     async fn save_reveal(&self, reveal: Reveal) -> Result<(), StoreError>;
     
     /// List reveals for a vote
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, StoreError>;
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, StoreError>;
     
     /// Get reveal by vote ID and voter
-    async fn get_reveal(&self, vote_id: &str, voter: &str) -> Result<Option<Reveal>, StoreError>;
+    async fn get_reveal(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Reveal>, StoreError>;
     
     /// Delete a vote (for cleanup)
-    async fn delete_vote(&self, id: &str) -> Result<(), StoreError>;
+    async fn delete_vote(&self, id: &VoteId) -> Result<(), StoreError>;
+    
+    /// Hide a vote from every read while keeping it and its commitments and reveals stored
+    async fn soft_delete_vote(&self, id: &VoteId) -> Result<(), StoreError>;
     
     /// Get storage statistics
     async fn get_stats(&self) -> Result<StoreStats, StoreError>;
+
+    /// Save many commitments; backends may override this to write them in one batch
+    async fn save_commitments(&self, commitments: Vec<Commitment>) -> Result<(), StoreError> {
+        for commitment in commitments {
+            self.save_commitment(commitment).await?;
+        }
+        Ok(())
+    }
+
+    /// Save many reveals; backends may override this to write them in one batch
+    async fn save_reveals(&self, reveals: Vec<Reveal>) -> Result<(), StoreError> {
+        for reveal in reveals {
+            self.save_reveal(reveal).await?;
+        }
+        Ok(())
+    }
+
+    /// Export a vote together with its commitments and reveals
+    async fn export_vote(&self, id: &VoteId) -> Result<VoteBundle, StoreError> {
+        Ok(VoteBundle {
+            vote: self.get_vote(id).await?,
+            commitments: self.list_commitments(id).await?,
+            reveals: self.list_reveals(id).await?,
+        })
+    }
+
+    /// Import an exported vote; importing the same bundle twice leaves one copy
+    async fn import_vote(&self, bundle: VoteBundle) -> Result<(), StoreError> {
+        let id = VoteId::parse(bundle.vote.id.clone())?;
+        match self.get_vote(&id).await {
+            Ok(_) => {
+                self.update_vote_status(&id, bundle.vote.status.clone()).await?;
+                if let Some(results) = &bundle.vote.results {
+                    self.update_vote_results(&id, results).await?;
+                }
+            }
+            Err(StoreError::VoteNotFound { .. }) => self.create_vote(bundle.vote).await?,
+            Err(e) => return Err(e),
+        }
+        self.save_commitments(bundle.commitments).await?;
+        self.save_reveals(bundle.reveals).await
+    }
+
+    /// One page of the votes `voter` has a commitment or reveal in, newest first
+    ///
+    /// The default checks each vote in turn; backends should override it with a lookup on an
+    /// index over `voter`.
+    async fn get_voter_activity(&self, voter: &str, page: u32, page_size: u32) -> Result<Page<VoteParticipation>, StoreError> {
+        let mut votes = Vec::new();
+        let mut listed_page = 0;
+        loop {
+            let listed = self.list_votes(ListQuery { page: listed_page, page_size: 100, status: None, creator: None }).await?;
+            for vote in listed.items {
+                let id = VoteId::parse(vote.id.clone())?;
+                let committed_at = self.get_commitment(&id, voter).await?.map(|c| c.created_at);
+                let revealed_at = self.get_reveal(&id, voter).await?.map(|r| r.created_at);
+                if committed_at.is_some() || revealed_at.is_some() {
+                    let participation = VoteParticipation { vote_id: vote.id, title: vote.title, status: vote.status, committed_at, revealed_at };
+                    votes.push((vote.created_at, participation));
+                }
+            }
+            listed_page += 1;
+            if listed_page >= listed.total_pages {
+                return Ok(VoteParticipation::newest_first(votes, page, page_size));
+            }
+        }
+    }
+
+    /// Close connection pools; the store must not be used afterwards
+    async fn close(&self) {}
+}
+
+/// A vote with everything recorded against it, as moved between stores
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VoteBundle {
+    pub vote: Vote,
+    pub commitments: Vec<Commitment>,
+    pub reveals: Vec<Reveal>,
 }
 
 /// Storage statistics
@@ -83,4 +167,7 @@ pub enum StoreError {
     
     #[error("Parse error: {0}")]
     ParseError(#[from] chrono::format::ParseError),
+
+    #[error("Invalid id: {0}")]
+    InvalidId(#[from] IdError),
 }
@@ -0,0 +1,157 @@
+use chrono::{Duration, Utc};
+use shared_config::DatabaseConfig;
+use shared_types::*;
+use vote_store::migrate::{migrate, verify, MigrateOptions};
+use vote_store::{MemoryVoteStore, SqliteVoteStore, VoteStore};
+
+async fn sqlite_store(dir: &tempfile::TempDir) -> SqliteVoteStore {
+    let config = DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", dir.path().join("votes.db").display()),
+        ..Default::default()
+    };
+    SqliteVoteStore::new(&config).await.unwrap()
+}
+
+/// Votes created one minute apart, each with `i + 1` commitments and reveals
+async fn populate(store: &dyn VoteStore, count: usize) -> Vec<VoteId> {
+    let start = Utc::now() - Duration::days(1);
+    let mut ids = Vec::new();
+    for i in 0..count {
+        let created_at = start + Duration::minutes(i as i64);
+        let vote = Vote {
+            id: format!("vote-{}", i),
+            title: format!("Vote {}", i),
+            description: "Migration fixture".to_string(),
+            template_id: "multiple_choice".to_string(),
+            template_params: serde_json::json!({"choices": ["a", "b"]}),
+            creator: "migrator".to_string(),
+            created_at,
+            commitment_start: created_at,
+            commitment_end: created_at + Duration::hours(1),
+            reveal_start: created_at + Duration::hours(1),
+            reveal_end: created_at + Duration::hours(2),
+            status: VoteStatus::Completed,
+            results: Some(VoteResults {
+                vote_id: format!("vote-{}", i),
+                total_votes: i as u32 + 1,
+                results: serde_json::json!({"a": i + 1}),
+                calculated_at: created_at + Duration::hours(2),
+                winner: None,
+                commitment_root: None,
+                reveal_secret_key: None,
+            }),
+            tie_break: TieBreak::FirstListed,
+            legal_hold: false,
+            completion_webhook_url: None,
+            client_request_id: None,
+            reveal_public_key: None,
+        };
+        store.create_vote(vote.clone()).await.unwrap();
+
+        for j in 0..=i {
+            let voter = format!("voter-{}", j);
+            store.save_commitment(Commitment {
+                id: format!("{}-c{}", vote.id, j),
+                vote_id: vote.id.clone(),
+                voter: voter.clone(),
+                commitment_hash: format!("hash-{}-{}", i, j),
+                salt: format!("salt-{}-{}", i, j),
+                created_at: created_at + Duration::minutes(10),
+                range_proof: None,
+            }).await.unwrap();
+            store.save_reveal(Reveal {
+                id: format!("{}-r{}", vote.id, j),
+                vote_id: vote.id.clone(),
+                voter,
+                value: serde_json::json!("a"),
+                salt: format!("salt-{}-{}", i, j),
+                created_at: created_at + Duration::minutes(70),
+                ciphertext: None,
+            }).await.unwrap();
+        }
+        ids.push(VoteId::parse(vote.id).unwrap());
+    }
+    ids
+}
+
+#[tokio::test]
+async fn test_migrate_sqlite_to_memory() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = sqlite_store(&dir).await;
+    populate(&source, 5).await;
+    let destination = MemoryVoteStore::new();
+
+    let report = migrate(&source, &destination, &MigrateOptions::default()).await.unwrap();
+    assert_eq!(report.votes, 5);
+    assert_eq!(report.commitments, 15);
+    assert_eq!(report.reveals, 15);
+
+    let verification = verify(&source, &destination, 5).await.unwrap();
+    assert!(verification.is_consistent(), "{:?}", verification.mismatches);
+    assert_eq!(verification.spot_checked.len(), 5);
+
+    let migrated = destination.get_vote(&VoteId::parse("vote-3").unwrap()).await.unwrap();
+    assert_eq!(migrated.status, VoteStatus::Completed);
+    assert_eq!(migrated.tie_break, TieBreak::FirstListed);
+    assert_eq!(migrated.results.unwrap().total_votes, 4);
+}
+
+#[tokio::test]
+async fn test_dry_run_writes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = sqlite_store(&dir).await;
+    populate(&source, 3).await;
+    let destination = MemoryVoteStore::new();
+
+    let options = MigrateOptions { dry_run: true, ..Default::default() };
+    let report = migrate(&source, &destination, &options).await.unwrap();
+    assert_eq!(report.votes, 3);
+    assert_eq!(destination.get_stats().await.unwrap().total_votes, 0);
+
+    let verification = verify(&source, &destination, 1).await.unwrap();
+    assert!(!verification.is_consistent());
+}
+
+#[tokio::test]
+async fn test_migration_resumes_from_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = sqlite_store(&dir).await;
+    let ids = populate(&source, 4).await;
+    let destination = MemoryVoteStore::new();
+    let checkpoint = dir.path().join("checkpoint");
+
+    // Simulate a run that stopped after the second vote
+    for id in &ids[..2] {
+        destination.import_vote(source.export_vote(id).await.unwrap()).await.unwrap();
+    }
+    std::fs::write(&checkpoint, ids[1].as_str()).unwrap();
+
+    let options = MigrateOptions { checkpoint: Some(checkpoint.clone()), ..Default::default() };
+    let report = migrate(&source, &destination, &options).await.unwrap();
+    assert_eq!(report.resumed_after.as_deref(), Some("vote-1"));
+    assert_eq!(report.votes, 2);
+    assert_eq!(std::fs::read_to_string(&checkpoint).unwrap(), "vote-3");
+
+    let verification = verify(&source, &destination, 4).await.unwrap();
+    assert!(verification.is_consistent(), "{:?}", verification.mismatches);
+
+    // A finished run resumes past the last vote and copies nothing
+    let report = migrate(&source, &destination, &options).await.unwrap();
+    assert_eq!(report.votes, 0);
+}
+
+#[tokio::test]
+async fn test_import_is_idempotent() {
+    let source = MemoryVoteStore::new();
+    let ids = populate(&source, 2).await;
+    let destination = MemoryVoteStore::new();
+
+    let bundle = source.export_vote(&ids[1]).await.unwrap();
+    destination.import_vote(bundle.clone()).await.unwrap();
+    destination.import_vote(bundle).await.unwrap();
+
+    let stats = destination.get_stats().await.unwrap();
+    assert_eq!(stats.total_votes, 1);
+    assert_eq!(stats.total_commitments, 2);
+    assert_eq!(stats.total_reveals, 2);
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use shared_types::*;
+use vote_store::retention::{RetentionJob, RetentionPolicy};
+use vote_store::{MemoryVoteStore, StoreError, VoteBundle, VoteStore};
+
+/// A vote in `status` whose reveal phase ended `days_ago` days ago, with one commitment and reveal
+async fn ended_vote(store: &dyn VoteStore, id: &str, status: VoteStatus, days_ago: i64) -> VoteId {
+    let reveal_end = Utc::now() - Duration::days(days_ago);
+    let created_at = reveal_end - Duration::hours(2);
+    store.create_vote(Vote {
+        id: id.to_string(),
+        title: id.to_string(),
+        description: "Retention fixture".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: serde_json::json!({}),
+        creator: "tester".to_string(),
+        created_at,
+        commitment_start: created_at,
+        commitment_end: created_at + Duration::hours(1),
+        reveal_start: created_at + Duration::hours(1),
+        reveal_end,
+        status,
+        results: None,
+        tie_break: TieBreak::default(),
+        legal_hold: false,
+        completion_webhook_url: None,
+        client_request_id: None,
+        reveal_public_key: None,
+    }).await.unwrap();
+    store.save_commitment(Commitment {
+        id: format!("{}-c", id),
+        vote_id: id.to_string(),
+        voter: "alice".to_string(),
+        commitment_hash: "hash".to_string(),
+        salt: "salt".to_string(),
+        created_at,
+        range_proof: None,
+    }).await.unwrap();
+    store.save_reveal(Reveal {
+        id: format!("{}-r", id),
+        vote_id: id.to_string(),
+        voter: "alice".to_string(),
+        value: serde_json::json!("yes"),
+        salt: "salt".to_string(),
+        created_at: reveal_end,
+        ciphertext: None,
+    }).await.unwrap();
+    VoteId::parse(id).unwrap()
+}
+
+#[tokio::test]
+async fn test_expired_vote_is_cleaned_and_held_vote_is_retained() {
+    let store = Arc::new(MemoryVoteStore::new());
+    let expired = ended_vote(store.as_ref(), "expired", VoteStatus::Completed, 40).await;
+    let held = ended_vote(store.as_ref(), "held", VoteStatus::Completed, 40).await;
+    let cancelled = ended_vote(store.as_ref(), "cancelled", VoteStatus::Cancelled, 40).await;
+    let recent = ended_vote(store.as_ref(), "recent", VoteStatus::Completed, 5).await;
+    let running = ended_vote(store.as_ref(), "running", VoteStatus::RevealPhase, 40).await;
+    store.set_legal_hold(&held, true).await.unwrap();
+
+    let job = RetentionJob::new(store.clone(), RetentionPolicy::new(Duration::days(30)));
+    let report = job.run_once(Utc::now()).await.unwrap();
+
+    let mut cleaned = report.cleaned.clone();
+    cleaned.sort();
+    assert_eq!(cleaned, vec!["cancelled".to_string(), "expired".to_string()]);
+    assert_eq!(report.held, vec!["held".to_string()]);
+    assert_eq!(report.failed, 0);
+
+    // without an archive the votes are only soft-deleted: hidden, but their records are kept
+    for id in [&expired, &cancelled] {
+        assert!(matches!(store.get_vote(id).await, Err(StoreError::VoteNotFound { .. })));
+        assert_eq!(store.list_commitments(id).await.unwrap().len(), 1);
+    }
+    let listed = store.list_votes(ListQuery { page: 0, page_size: 10, status: None, creator: None }).await.unwrap();
+    assert_eq!(listed.total, 3);
+    for id in [&held, &recent, &running] {
+        assert!(store.get_vote(id).await.is_ok());
+    }
+    assert!(store.get_vote(&held).await.unwrap().legal_hold);
+
+    // lifting the hold lets the next run clean it
+    store.set_legal_hold(&held, false).await.unwrap();
+    let report = job.run_once(Utc::now()).await.unwrap();
+    assert_eq!(report.cleaned, vec!["held".to_string()]);
+}
+
+#[tokio::test]
+async fn test_archive_dir_gets_an_export_before_purge() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(MemoryVoteStore::new());
+    let id = ended_vote(store.as_ref(), "archived", VoteStatus::Completed, 10).await;
+
+    let policy = RetentionPolicy::new(Duration::days(7)).with_archive_dir(dir.path());
+    let report = RetentionJob::new(store.clone(), policy).run_once(Utc::now()).await.unwrap();
+    assert_eq!(report.cleaned, vec!["archived".to_string()]);
+    assert!(store.get_vote(&id).await.is_err());
+    // archived votes are purged with everything recorded against them
+    assert!(store.list_commitments(&id).await.unwrap().is_empty());
+
+    let exported: VoteBundle = serde_json::from_slice(&std::fs::read(dir.path().join("archived.json")).unwrap()).unwrap();
+    assert_eq!(exported.vote.id, "archived");
+    assert_eq!(exported.commitments.len(), 1);
+    assert_eq!(exported.reveals.len(), 1);
+}
+
+#[tokio::test]
+async fn test_sqlite_soft_delete_hides_the_vote_and_keeps_its_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = shared_config::DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", dir.path().join("votes.db").display()),
+        ..Default::default()
+    };
+    let store = Arc::new(vote_store::SqliteVoteStore::new(&config).await.unwrap());
+    let expired = ended_vote(store.as_ref(), "expired", VoteStatus::Completed, 40).await;
+    let recent = ended_vote(store.as_ref(), "recent", VoteStatus::Completed, 5).await;
+
+    let job = RetentionJob::new(store.clone(), RetentionPolicy::new(Duration::days(30)));
+    assert_eq!(job.run_once(Utc::now()).await.unwrap().cleaned, vec!["expired".to_string()]);
+
+    assert!(matches!(store.get_vote(&expired).await, Err(StoreError::VoteNotFound { .. })));
+    assert_eq!(store.list_commitments(&expired).await.unwrap().len(), 1);
+    let listed = store.list_votes(ListQuery { page: 0, page_size: 10, status: None, creator: None }).await.unwrap();
+    assert_eq!(listed.items.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(), vec![recent.as_str()]);
+    assert_eq!(listed.total, 1);
+    assert_eq!(store.get_stats().await.unwrap().total_votes, 1);
+    let activity = store.get_voter_activity("alice", 0, 20).await.unwrap();
+    assert_eq!(activity.items.iter().map(|v| v.vote_id.as_str()).collect::<Vec<_>>(), vec!["recent"]);
+
+    // a second run finds nothing left to clean
+    assert!(job.run_once(Utc::now()).await.unwrap().cleaned.is_empty());
+    assert!(matches!(store.soft_delete_vote(&expired).await, Err(StoreError::VoteNotFound { .. })));
+}
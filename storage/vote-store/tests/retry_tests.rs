@@ -0,0 +1,102 @@
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use sqlx::error::{DatabaseError, ErrorKind};
+use vote_store::retry::*;
+
+/// A database error carrying only a code, as a driver would report it
+#[derive(Debug)]
+struct SimulatedDbError(&'static str);
+
+impl std::fmt::Display for SimulatedDbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "simulated database error {}", self.0)
+    }
+}
+
+impl std::error::Error for SimulatedDbError {}
+
+impl DatabaseError for SimulatedDbError {
+    fn message(&self) -> &str { "simulated" }
+    fn code(&self) -> Option<Cow<'_, str>> { Some(Cow::Borrowed(self.0)) }
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) { self }
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) { self }
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> { self }
+    fn kind(&self) -> ErrorKind { ErrorKind::Other }
+}
+
+fn db_error(code: &'static str) -> sqlx::Error {
+    sqlx::Error::Database(Box::new(SimulatedDbError(code)))
+}
+
+/// A write that fails with `code` on its first `failures` attempts, then succeeds
+struct FaultyWrite {
+    code: &'static str,
+    failures: u32,
+    attempts: AtomicU32,
+}
+
+impl FaultyWrite {
+    fn new(code: &'static str, failures: u32) -> Self {
+        Self { code, failures, attempts: AtomicU32::new(0) }
+    }
+
+    async fn execute(&self) -> Result<u64, sqlx::Error> {
+        let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempt <= self.failures { Err(db_error(self.code)) } else { Ok(1) }
+    }
+
+    fn attempts(&self) -> u32 {
+        self.attempts.load(Ordering::SeqCst)
+    }
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(1), max_delay: Duration::from_millis(4) }
+}
+
+#[test]
+fn test_transient_errors_are_classified() {
+    assert!(is_transient(&db_error(SERIALIZATION_FAILURE)));
+    assert!(is_transient(&db_error(DEADLOCK_DETECTED)));
+    // SQLITE_BUSY, SQLITE_LOCKED and the extended SQLITE_BUSY_SNAPSHOT
+    assert!(is_transient(&db_error("5")));
+    assert!(is_transient(&db_error("6")));
+    assert!(is_transient(&db_error("517")));
+
+    // unique violations, SQLite constraint failures and non-database errors are permanent
+    assert!(!is_transient(&db_error("23505")));
+    assert!(!is_transient(&db_error("2067")));
+    assert!(!is_transient(&sqlx::Error::RowNotFound));
+}
+
+#[tokio::test]
+async fn test_write_failing_once_with_serialization_error_succeeds_on_retry() {
+    let write = FaultyWrite::new(SERIALIZATION_FAILURE, 1);
+    let result = fast_policy().run("save_commitment", || write.execute()).await;
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(write.attempts(), 2);
+}
+
+#[tokio::test]
+async fn test_permanent_errors_are_not_retried() {
+    let write = FaultyWrite::new("23505", 1);
+    let error = fast_policy().run("save_commitment", || write.execute()).await.unwrap_err();
+    assert_eq!(error.as_database_error().and_then(|e| e.code()).as_deref(), Some("23505"));
+    assert_eq!(write.attempts(), 1);
+}
+
+#[tokio::test]
+async fn test_retries_are_capped() {
+    let write = FaultyWrite::new(DEADLOCK_DETECTED, 10);
+    let error = fast_policy().run("update_vote_status", || write.execute()).await.unwrap_err();
+    assert!(is_transient(&error));
+    assert_eq!(write.attempts(), 3);
+}
+
+#[test]
+fn test_backoff_doubles_up_to_the_cap() {
+    let policy = RetryPolicy { max_attempts: 10, base_delay: Duration::from_millis(20), max_delay: Duration::from_millis(100) };
+    let delays: Vec<u128> = (1..=5).map(|retry| policy.delay(retry).as_millis()).collect();
+    assert_eq!(delays, vec![20, 40, 80, 100, 100]);
+}
@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration, Utc};
+use shared_config::DatabaseConfig;
+use shared_types::*;
+use sqlx::SqlitePool;
+use vote_store::{SqliteVoteStore, StoreError, VoteStore};
+
+/// Tables as the first release created them, before any column was added
+const BASELINE_SCHEMA: &[&str] = &[
+    "CREATE TABLE votes (
+        id TEXT PRIMARY KEY, title TEXT NOT NULL, description TEXT NOT NULL, template_id TEXT NOT NULL,
+        template_params TEXT NOT NULL, creator TEXT NOT NULL, created_at TEXT NOT NULL,
+        commitment_start TEXT NOT NULL, commitment_end TEXT NOT NULL, reveal_start TEXT NOT NULL,
+        reveal_end TEXT NOT NULL, status TEXT NOT NULL, results TEXT
+    )",
+    "CREATE TABLE commitments (
+        id TEXT PRIMARY KEY, vote_id TEXT NOT NULL, voter TEXT NOT NULL, commitment_hash TEXT NOT NULL,
+        salt TEXT NOT NULL, created_at TEXT NOT NULL, UNIQUE(vote_id, voter)
+    )",
+    "CREATE TABLE reveals (
+        id TEXT PRIMARY KEY, vote_id TEXT NOT NULL, voter TEXT NOT NULL, value TEXT NOT NULL,
+        salt TEXT NOT NULL, created_at TEXT NOT NULL, UNIQUE(vote_id, voter)
+    )",
+];
+
+fn database(dir: &tempfile::TempDir) -> DatabaseConfig {
+    DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", dir.path().join("votes.db").display()),
+        ..Default::default()
+    }
+}
+
+/// A database left behind by the first release, holding one vote
+async fn baseline_database(config: &DatabaseConfig, created_at: DateTime<Utc>) {
+    let pool = SqlitePool::connect(&config.url).await.unwrap();
+    for statement in BASELINE_SCHEMA {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    sqlx::query("INSERT INTO votes VALUES ('legacy', 'Legacy', 'Before upgrade', 'yes_no', '{}', 'system', ?, ?, ?, ?, ?, 'completed', NULL)")
+        .bind(created_at.to_rfc3339())
+        .bind(created_at.to_rfc3339())
+        .bind((created_at + Duration::hours(1)).to_rfc3339())
+        .bind((created_at + Duration::hours(1)).to_rfc3339())
+        .bind((created_at + Duration::hours(2)).to_rfc3339())
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+}
+
+#[tokio::test]
+async fn test_sqlite_store_upgrades_baseline_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = database(&dir);
+    let created_at = DateTime::from_timestamp(Utc::now().timestamp() - 86_400, 0).unwrap();
+    baseline_database(&config, created_at).await;
+
+    let store = SqliteVoteStore::new(&config).await.unwrap();
+    let legacy = store.get_vote(&VoteId::parse("legacy").unwrap()).await.unwrap();
+    assert_eq!(legacy.status, VoteStatus::Completed);
+    assert_eq!(legacy.tie_break, TieBreak::default());
+    assert!(!legacy.legal_hold);
+
+    // Every added column is writable and read back
+    let upgraded = Vote {
+        id: "upgraded".to_string(),
+        tie_break: TieBreak::FirstListed,
+        legal_hold: true,
+        completion_webhook_url: Some("https://hooks.example/done".to_string()),
+        client_request_id: Some("request-1".to_string()),
+        reveal_public_key: Some("ab".repeat(32)),
+        ..legacy
+    };
+    store.create_vote(upgraded.clone()).await.unwrap();
+    let vote_id = VoteId::parse("upgraded").unwrap();
+    let stored = store.get_vote(&vote_id).await.unwrap();
+    assert_eq!(stored.tie_break, TieBreak::FirstListed);
+    assert!(stored.legal_hold);
+    assert_eq!(stored.completion_webhook_url, upgraded.completion_webhook_url);
+    assert_eq!(stored.client_request_id, upgraded.client_request_id);
+    assert_eq!(stored.reveal_public_key, upgraded.reveal_public_key);
+
+    store.save_commitment(Commitment {
+        id: "c-alice".to_string(),
+        vote_id: "upgraded".to_string(),
+        voter: "alice".to_string(),
+        commitment_hash: "a".repeat(64),
+        salt: "salt".to_string(),
+        created_at,
+        range_proof: None,
+    }).await.unwrap();
+    store.save_reveal(Reveal {
+        id: "r-alice".to_string(),
+        vote_id: "upgraded".to_string(),
+        voter: "alice".to_string(),
+        value: serde_json::Value::Null,
+        salt: String::new(),
+        created_at,
+        ciphertext: Some("cd".repeat(48)),
+    }).await.unwrap();
+    let reveal = store.get_reveal(&vote_id, "alice").await.unwrap().unwrap();
+    assert_eq!(reveal.ciphertext, Some("cd".repeat(48)));
+    store.close().await;
+
+    // Opening the upgraded database again finds nothing left to add
+    let reopened = SqliteVoteStore::new(&config).await.unwrap();
+    assert_eq!(reopened.get_vote(&vote_id).await.unwrap().client_request_id.as_deref(), Some("request-1"));
+}
+
+#[tokio::test]
+async fn test_unreadable_range_proof_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = database(&dir);
+    let created_at = DateTime::from_timestamp(Utc::now().timestamp() - 86_400, 0).unwrap();
+    baseline_database(&config, created_at).await;
+    let store = SqliteVoteStore::new(&config).await.unwrap();
+    store.save_commitment(Commitment {
+        id: "c-alice".to_string(),
+        vote_id: "legacy".to_string(),
+        voter: "alice".to_string(),
+        commitment_hash: "a".repeat(64),
+        salt: "salt".to_string(),
+        created_at,
+        range_proof: None,
+    }).await.unwrap();
+
+    let pool = SqlitePool::connect(&config.url).await.unwrap();
+    sqlx::query("UPDATE commitments SET range_proof = 'not a proof' WHERE id = 'c-alice'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+
+    // a proof that fails to load must not read as a commitment without one
+    let vote_id = VoteId::parse("legacy").unwrap();
+    assert!(matches!(store.get_commitment(&vote_id, "alice").await, Err(StoreError::SerializationError(_))));
+    assert!(matches!(store.list_commitments(&vote_id).await, Err(StoreError::SerializationError(_))));
+}
@@ -0,0 +1,112 @@
+use chrono::{DateTime, Duration, Utc};
+use shared_config::DatabaseConfig;
+use shared_types::*;
+use vote_store::{MemoryVoteStore, SqliteVoteStore, VoteStore};
+
+async fn sqlite_store(dir: &tempfile::TempDir) -> SqliteVoteStore {
+    let config = DatabaseConfig {
+        url: format!("sqlite:{}?mode=rwc", dir.path().join("votes.db").display()),
+        ..Default::default()
+    };
+    SqliteVoteStore::new(&config).await.unwrap()
+}
+
+fn vote(id: &str, created_at: DateTime<Utc>, status: VoteStatus) -> Vote {
+    Vote {
+        id: id.to_string(),
+        title: format!("Title of {}", id),
+        description: "Activity fixture".to_string(),
+        template_id: "yes_no".to_string(),
+        template_params: serde_json::json!({}),
+        creator: "system".to_string(),
+        created_at,
+        commitment_start: created_at,
+        commitment_end: created_at + Duration::hours(1),
+        reveal_start: created_at + Duration::hours(1),
+        reveal_end: created_at + Duration::hours(2),
+        status,
+        results: None,
+        tie_break: TieBreak::default(),
+        legal_hold: false,
+        completion_webhook_url: None,
+        client_request_id: None,
+        reveal_public_key: None,
+    }
+}
+
+fn commitment(vote_id: &str, voter: &str, created_at: DateTime<Utc>) -> Commitment {
+    Commitment {
+        id: format!("{}-c-{}", vote_id, voter),
+        vote_id: vote_id.to_string(),
+        voter: voter.to_string(),
+        commitment_hash: "a".repeat(64),
+        salt: "salt".to_string(),
+        created_at,
+        range_proof: None,
+    }
+}
+
+fn reveal(vote_id: &str, voter: &str, created_at: DateTime<Utc>) -> Reveal {
+    Reveal {
+        id: format!("{}-r-{}", vote_id, voter),
+        vote_id: vote_id.to_string(),
+        voter: voter.to_string(),
+        value: serde_json::json!("yes"),
+        salt: "salt".to_string(),
+        created_at,
+        ciphertext: None,
+    }
+}
+
+/// alice commits in `older` and commits and reveals in `newer`; bob only takes part in `other`
+async fn check_voter_in_two_votes(store: &dyn VoteStore) {
+    // whole seconds so the timestamps survive the SQL round trip unchanged
+    let start = DateTime::from_timestamp(Utc::now().timestamp() - 86_400, 0).unwrap();
+    store.create_vote(vote("older", start, VoteStatus::CommitmentPhase)).await.unwrap();
+    store.create_vote(vote("newer", start + Duration::minutes(5), VoteStatus::Completed)).await.unwrap();
+    store.create_vote(vote("other", start + Duration::minutes(10), VoteStatus::Completed)).await.unwrap();
+    store.save_commitment(commitment("older", "alice", start + Duration::minutes(1))).await.unwrap();
+    store.save_commitment(commitment("newer", "alice", start + Duration::minutes(6))).await.unwrap();
+    store.save_reveal(reveal("newer", "alice", start + Duration::minutes(70))).await.unwrap();
+    store.save_commitment(commitment("other", "bob", start + Duration::minutes(11))).await.unwrap();
+
+    let activity = store.get_voter_activity("alice", 0, 20).await.unwrap();
+    assert_eq!(activity.total, 2);
+    assert_eq!(activity.items, vec![
+        VoteParticipation {
+            vote_id: "newer".to_string(),
+            title: "Title of newer".to_string(),
+            status: VoteStatus::Completed,
+            committed_at: Some(start + Duration::minutes(6)),
+            revealed_at: Some(start + Duration::minutes(70)),
+        },
+        VoteParticipation {
+            vote_id: "older".to_string(),
+            title: "Title of older".to_string(),
+            status: VoteStatus::CommitmentPhase,
+            committed_at: Some(start + Duration::minutes(1)),
+            revealed_at: None,
+        },
+    ]);
+
+    // pages are cut by the store, in the same order
+    let second = store.get_voter_activity("alice", 1, 1).await.unwrap();
+    assert_eq!(second.items.iter().map(|v| v.vote_id.as_str()).collect::<Vec<_>>(), vec!["older"]);
+    assert_eq!((second.total, second.total_pages), (2, 2));
+    assert!(store.get_voter_activity("alice", 2, 1).await.unwrap().items.is_empty());
+
+    let none = store.get_voter_activity("carol", 0, 20).await.unwrap();
+    assert!(none.items.is_empty());
+    assert_eq!(none.total, 0);
+}
+
+#[tokio::test]
+async fn test_memory_store_lists_voter_activity_across_votes() {
+    check_voter_in_two_votes(&MemoryVoteStore::new()).await;
+}
+
+#[tokio::test]
+async fn test_sqlite_store_lists_voter_activity_across_votes() {
+    let dir = tempfile::tempdir().unwrap();
+    check_voter_in_two_votes(&sqlite_store(&dir).await).await;
+}
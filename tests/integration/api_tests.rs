@@ -21,6 +21,9 @@ async fn test_vote_lifecycle_integration() {
         }),
         commitment_duration_hours: 1,
         reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
     
     let vote_id = test_env.vote_engine.create_vote(config).await.unwrap();
@@ -43,6 +46,7 @@ async fn test_vote_lifecycle_integration() {
             voter: voter.to_string(),
             commitment_hash,
             salt,
+            range_proof: None,
         };
         
         let response = test_env.vote_engine.commit_vote(&vote_id, request).await.unwrap();
@@ -61,6 +65,8 @@ async fn test_vote_lifecycle_integration() {
             voter: voter.to_string(),
             value: serde_json::json!(reveals[i]),
             salt: format!("salt_{}", voter),
+            range_blinding: None,
+            ciphertext: None,
         };
         
         let response = test_env.vote_engine.reveal_vote(&vote_id, request).await.unwrap();
@@ -97,6 +103,9 @@ async fn test_concurrent_votes() {
                 template_params: serde_json::json!({}),
                 commitment_duration_hours: 1,
                 reveal_duration_hours: 1,
+                tie_break: TieBreak::default(),
+                completion_webhook_url: None,
+                encrypted_reveals: false,
             };
             
             engine.create_vote(config).await
@@ -129,6 +138,9 @@ async fn test_vote_listing_and_pagination() {
             template_params: serde_json::json!({}),
             commitment_duration_hours: 1,
             reveal_duration_hours: 1,
+            tie_break: TieBreak::default(),
+            completion_webhook_url: None,
+            encrypted_reveals: false,
         };
         
         let vote_id = test_env.vote_engine.create_vote(config).await.unwrap();
@@ -181,6 +193,7 @@ async fn test_error_handling() {
         voter: "test_voter".to_string(),
         commitment_hash: "test_hash".to_string(),
         salt: "test_salt".to_string(),
+        range_proof: None,
     };
     
     let result = test_env.vote_engine.commit_vote("non-existent", request).await;
@@ -201,6 +214,9 @@ async fn test_vote_validation() {
         template_params: serde_json::json!({}),
         commitment_duration_hours: 0, // Invalid duration
         reveal_duration_hours: 0,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
     
     let result = test_env.vote_engine.create_vote(invalid_config).await;
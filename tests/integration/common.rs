@@ -51,7 +51,7 @@ impl VoteService for TestVoteService {
         self.storage.save_vote(vote).await
     }
 
-    async fn get_vote(&self, vote_id: &str) -> Result<Vote, VoteError> {
+    async fn get_vote(&self, vote_id: &VoteId) -> Result<Vote, VoteError> {
         self.storage.get_vote(vote_id).await
     }
 
@@ -59,13 +59,13 @@ impl VoteService for TestVoteService {
         self.storage.list_votes(query).await
     }
 
-    async fn update_vote_status(&self, vote_id: &str, status: VoteStatus) -> Result<(), VoteError> {
+    async fn update_vote_status(&self, vote_id: &VoteId, status: VoteStatus) -> Result<(), VoteError> {
         let mut vote = self.storage.get_vote(vote_id).await?;
         vote.status = status;
         self.storage.save_vote(vote).await
     }
 
-    async fn update_vote_results(&self, vote_id: &str, results: &VoteResults) -> Result<(), VoteError> {
+    async fn update_vote_results(&self, vote_id: &VoteId, results: &VoteResults) -> Result<(), VoteError> {
         let mut vote = self.storage.get_vote(vote_id).await?;
         vote.results = Some(results.clone());
         self.storage.save_vote(vote).await
@@ -75,7 +75,7 @@ impl VoteService for TestVoteService {
         self.storage.save_commitment(commitment).await
     }
 
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, VoteError> {
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, VoteError> {
         self.storage.get_commitment(vote_id, voter).await
     }
 
@@ -83,7 +83,7 @@ impl VoteService for TestVoteService {
         self.storage.save_reveal(reveal).await
     }
 
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, VoteError> {
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, VoteError> {
         self.storage.list_reveals(vote_id).await
     }
 
@@ -101,6 +101,9 @@ impl VoteService for TestVoteService {
             total_votes: reveals.len() as u32,
             results: serde_json::to_value(results).unwrap(),
             calculated_at: chrono::Utc::now(),
+            winner: None,
+            commitment_root: None,
+            reveal_secret_key: None,
         })
     }
 }
@@ -130,7 +133,7 @@ impl VoteStore for MemoryVoteStore {
         Ok(())
     }
 
-    async fn get_vote(&self, vote_id: &str) -> Result<Vote, VoteError> {
+    async fn get_vote(&self, vote_id: &VoteId) -> Result<Vote, VoteError> {
         let votes = self.votes.read().unwrap();
         votes.get(vote_id)
             .cloned()
@@ -167,7 +170,7 @@ impl VoteStore for MemoryVoteStore {
         Ok(())
     }
 
-    async fn get_commitment(&self, vote_id: &str, voter: &str) -> Result<Option<Commitment>, VoteError> {
+    async fn get_commitment(&self, vote_id: &VoteId, voter: &str) -> Result<Option<Commitment>, VoteError> {
         let commitments = self.commitments.read().unwrap();
         Ok(commitments.values()
             .find(|c| c.vote_id == vote_id && c.voter == voter)
@@ -180,7 +183,7 @@ impl VoteStore for MemoryVoteStore {
         Ok(())
     }
 
-    async fn list_reveals(&self, vote_id: &str) -> Result<Vec<Reveal>, VoteError> {
+    async fn list_reveals(&self, vote_id: &VoteId) -> Result<Vec<Reveal>, VoteError> {
         let reveals = self.reveals.read().unwrap();
         Ok(reveals.values()
             .filter(|r| r.vote_id == vote_id)
@@ -26,6 +26,9 @@ async fn test_concurrent_vote_creation() {
                 template_params: serde_json::json!({}),
                 commitment_duration_hours: 1,
                 reveal_duration_hours: 1,
+                tie_break: TieBreak::default(),
+                completion_webhook_url: None,
+                encrypted_reveals: false,
             };
             
             engine.create_vote(config).await
@@ -67,6 +70,9 @@ async fn test_concurrent_commitments() {
         template_params: serde_json::json!({}),
         commitment_duration_hours: 1,
         reveal_duration_hours: 1,
+        tie_break: TieBreak::default(),
+        completion_webhook_url: None,
+        encrypted_reveals: false,
     };
     
     let vote_id = test_env.vote_engine.create_vote(config).await.unwrap();
@@ -84,6 +90,7 @@ async fn test_concurrent_commitments() {
                 voter: format!("voter_{}", i),
                 commitment_hash: format!("commitment_{}", i),
                 salt: format!("salt_{}", i),
+                range_proof: None,
             };
             
             engine.commit_vote(&vote_id, request).await
@@ -134,6 +141,9 @@ async fn test_memory_usage_under_load() {
                 template_params: serde_json::json!({}),
                 commitment_duration_hours: 1,
                 reveal_duration_hours: 1,
+                tie_break: TieBreak::default(),
+                completion_webhook_url: None,
+                encrypted_reveals: false,
             };
             
             let vote_id = engine.create_vote(config).await?;
@@ -143,6 +153,7 @@ async fn test_memory_usage_under_load() {
                 voter: format!("voter_{}", i),
                 commitment_hash: format!("commitment_{}", i),
                 salt: format!("salt_{}", i),
+                range_proof: None,
             };
             
             engine.commit_vote(&vote_id, request).await?;
@@ -191,6 +202,9 @@ async fn test_response_time_consistency() {
             template_params: serde_json::json!({}),
             commitment_duration_hours: 1,
             reveal_duration_hours: 1,
+            tie_break: TieBreak::default(),
+            completion_webhook_url: None,
+            encrypted_reveals: false,
         };
         
         let _vote_id = test_env.vote_engine.create_vote(config).await.unwrap();
@@ -247,6 +261,9 @@ async fn test_sustained_load() {
             template_params: serde_json::json!({}),
             commitment_duration_hours: 1,
             reveal_duration_hours: 1,
+            tie_break: TieBreak::default(),
+            completion_webhook_url: None,
+            encrypted_reveals: false,
         };
         
         let _vote_id = test_env.vote_engine.create_vote(config).await.unwrap();
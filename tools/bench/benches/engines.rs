@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ddv_bench::{commitment_engine, generate_commitments, select_winner};
+use tokio::runtime::Runtime;
+
+const PARTICIPANT_COUNTS: &[usize] = &[10, 100, 1000];
+
+fn commit_generation(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let engine = commitment_engine();
+
+    let mut group = c.benchmark_group("commit_generation");
+    for &participants in PARTICIPANT_COUNTS {
+        group.throughput(Throughput::Elements(participants as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(participants), &participants, |b, &participants| {
+            b.iter(|| runtime.block_on(generate_commitments(&engine, participants)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn selection(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let engine = commitment_engine();
+
+    let mut group = c.benchmark_group("selection");
+    for &participants in PARTICIPANT_COUNTS {
+        let committed = runtime.block_on(generate_commitments(&engine, participants)).unwrap();
+        group.throughput(Throughput::Elements(participants as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(participants), &committed, |b, committed| {
+            b.iter(|| runtime.block_on(select_winner(&engine, committed)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, commit_generation, selection);
+criterion_main!(benches);
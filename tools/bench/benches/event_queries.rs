@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use event_store::{Event, EventQuery, EventSeverity, EventStore, EventType, QueryBuilder, QueryExecutor};
+use tokio::runtime::Runtime;
+
+const EVENT_COUNTS: &[usize] = &[1_000, 10_000];
+const EVENT_TYPES: usize = 20;
+
+async fn populate(count: usize) -> (EventStore, Vec<Event>) {
+    let store = EventStore::new_memory();
+    let mut events = Vec::with_capacity(count);
+    for i in 0..count {
+        let event = Event::new(
+            EventType::Custom(format!("type-{}", i % EVENT_TYPES)),
+            EventSeverity::Info,
+            "bench".to_string(),
+            format!("event {}", i),
+            Some(format!("session-{}", i % 100)),
+            None,
+        );
+        store.store_event(event.clone()).await.unwrap();
+        events.push(event);
+    }
+    (store, events)
+}
+
+fn type_query() -> EventQuery {
+    QueryBuilder::new()
+        .event_type_equals(EventType::Custom("type-3".to_string()))
+        .severity_equals(EventSeverity::Info)
+        .build()
+}
+
+/// Full scan over every event against the index-narrowed path, for the same single-type query
+fn type_equality(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let query = type_query();
+
+    let mut group = c.benchmark_group("event_type_query");
+    for &count in EVENT_COUNTS {
+        let (store, events) = runtime.block_on(populate(count));
+        let scanned = QueryExecutor::execute(&query, &events).unwrap();
+        let indexed = runtime.block_on(store.execute(&query)).unwrap();
+        assert_eq!(indexed.total_count, scanned.total_count);
+        println!(
+            "{} events: scan examined {}, index examined {}",
+            count, scanned.events_examined, indexed.events_examined
+        );
+
+        group.bench_with_input(BenchmarkId::new("scan", count), &events, |b, events| {
+            b.iter(|| QueryExecutor::execute(&query, events).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("indexed", count), &store, |b, store| {
+            b.iter(|| runtime.block_on(store.execute(&query)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, type_equality);
+criterion_main!(benches);
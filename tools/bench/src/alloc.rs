@@ -0,0 +1,104 @@
+//! Heap usage tracking
+//!
+//! `TrackingAllocator` wraps the system allocator and counts live and peak bytes. It is only
+//! installed as the global allocator with the `mem-profiling` feature; without it the counters
+//! below stay at zero and allocation goes straight to `System`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator that keeps current and peak allocated byte counts
+#[derive(Debug, Default)]
+pub struct TrackingAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl TrackingAllocator {
+    pub const fn new() -> Self {
+        Self { current: AtomicUsize::new(0), peak: AtomicUsize::new(0) }
+    }
+
+    /// Bytes currently allocated through this allocator
+    pub fn current(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    /// Highest `current` value since creation or the last `reset_peak`
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Start a new peak measurement from the current usage
+    pub fn reset_peak(&self) {
+        self.peak.store(self.current(), Ordering::Relaxed);
+    }
+
+    fn record_alloc(&self, size: usize) {
+        let current = self.current.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(&self, size: usize) {
+        self.current.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.record_dealloc(layout.size());
+            self.record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "mem-profiling")]
+#[global_allocator]
+static GLOBAL: TrackingAllocator = TrackingAllocator::new();
+
+#[cfg(not(feature = "mem-profiling"))]
+static GLOBAL: TrackingAllocator = TrackingAllocator::new();
+
+/// Whether the counters reflect real allocations
+pub const fn enabled() -> bool {
+    cfg!(feature = "mem-profiling")
+}
+
+/// Bytes currently allocated by the process; zero without `mem-profiling`
+pub fn current_allocated() -> usize {
+    GLOBAL.current()
+}
+
+/// Peak bytes allocated since the last `reset_peak`; zero without `mem-profiling`
+pub fn peak_allocated() -> usize {
+    GLOBAL.peak()
+}
+
+/// Start a new peak measurement from the current usage
+pub fn reset_peak() {
+    GLOBAL.reset_peak();
+}
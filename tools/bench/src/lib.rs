@@ -0,0 +1,119 @@
+//! Benchmark workloads for the commit-reveal and selection engines
+//!
+//! The same workloads back the Criterion harness (`cargo bench -p ddv-bench`) and the `ddv-bench`
+//! binary, whose JSON output matches the records `tests/analysis/test_result_analyzer.rs` loads.
+//! Build with `--features mem-profiling` to record real heap usage per round.
+
+pub mod alloc;
+
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use commitment_engine::{CommitmentData, CommitmentEngine, CommitmentError, Sha256CommitmentAlgorithm};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared_types::{SeedSource, TieBreak, WinnerOutcome};
+use shared_utils::crypto::combined_seed;
+use template_system::{MultipleChoiceTemplate, VoteTemplate};
+
+/// Options every benchmark participant chooses between
+pub const CHOICES: &[&str] = &["alpha", "beta", "gamma", "delta"];
+
+/// A voter with the value they committed to and the commitment itself
+#[derive(Debug, Clone)]
+pub struct Participant {
+    pub voter: String,
+    pub choice: String,
+    pub commitment: CommitmentData,
+}
+
+/// Winner of a selection round together with the seed that broke any tie
+#[derive(Debug, Clone)]
+pub struct Selection {
+    pub outcome: WinnerOutcome,
+    pub seed: String,
+}
+
+/// One measured round, in the shape of the analyzer's `TestResult`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchRecord {
+    pub test_name: String,
+    pub participant_count: usize,
+    pub execution_time_ms: u64,
+    pub success: bool,
+    pub winner: String,
+    pub random_seed: String,
+    pub memory_usage_mb: f64,
+    pub timestamp: u64,
+}
+
+/// Commitment engine configured the way the services build it
+pub fn commitment_engine() -> CommitmentEngine {
+    CommitmentEngine::new(Arc::new(Sha256CommitmentAlgorithm::new()))
+}
+
+/// Template parameters for a multiple-choice vote over [`CHOICES`]
+pub fn choice_params() -> Value {
+    serde_json::json!({ "choices": CHOICES })
+}
+
+/// Commit phase: one commitment per participant
+pub async fn generate_commitments(engine: &CommitmentEngine, participants: usize) -> Result<Vec<Participant>, CommitmentError> {
+    let mut committed = Vec::with_capacity(participants);
+    for i in 0..participants {
+        let voter = format!("voter-{}", i);
+        let choice = CHOICES[i % CHOICES.len()].to_string();
+        let commitment = engine.create_commitment(&choice, &voter).await?;
+        committed.push(Participant { voter, choice, commitment });
+    }
+    Ok(committed)
+}
+
+/// Reveal and selection: verify every opening, tally, and pick a single winner with a random tie-break
+pub async fn select_winner(engine: &CommitmentEngine, participants: &[Participant]) -> anyhow::Result<Selection> {
+    for participant in participants {
+        let valid = engine
+            .verify_commitment(&participant.choice, &participant.commitment.salt, &participant.commitment.commitment_hash)
+            .await?;
+        anyhow::ensure!(valid, "commitment of {} does not open", participant.voter);
+    }
+
+    let template = MultipleChoiceTemplate::new();
+    let params = choice_params();
+    let values: Vec<Value> = participants.iter().map(|p| Value::String(p.choice.clone())).collect();
+    let aggregate = template.aggregate(&values, &params).await?;
+
+    let salts: Vec<&str> = participants.iter().map(|p| p.commitment.salt.as_str()).collect();
+    let seed = combined_seed(&salts);
+    let policy = TieBreak::Random { seed_source: SeedSource::RevealSalts };
+    let outcome = template.winner(&aggregate, &params, &policy, &seed)?;
+    Ok(Selection { outcome, seed })
+}
+
+/// Run a full commit-and-select round, recording its duration and peak heap growth
+pub async fn run_round(engine: &CommitmentEngine, test_name: &str, participants: usize) -> BenchRecord {
+    let start_bytes = alloc::current_allocated();
+    alloc::reset_peak();
+    let start = Instant::now();
+    let result = match generate_commitments(engine, participants).await {
+        Ok(committed) => select_winner(engine, &committed).await,
+        Err(e) => Err(e.into()),
+    };
+    let execution_time_ms = start.elapsed().as_millis() as u64;
+    let memory_usage_mb = alloc::peak_allocated().saturating_sub(start_bytes) as f64 / (1024.0 * 1024.0);
+
+    let (success, winner, random_seed) = match result {
+        Ok(selection) => (true, selection.outcome.winner.unwrap_or_default(), selection.seed),
+        Err(_) => (false, String::new(), String::new()),
+    };
+    BenchRecord {
+        test_name: test_name.to_string(),
+        participant_count: participants,
+        execution_time_ms,
+        success,
+        winner,
+        random_seed,
+        memory_usage_mb,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+    }
+}
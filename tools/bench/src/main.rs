@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use ddv_bench::{commitment_engine, run_round, BenchRecord};
+
+/// Run commit-and-select rounds and emit one JSON record per round
+#[derive(Parser, Debug)]
+#[command(name = "ddv-bench", version, about)]
+struct Args {
+    /// Participant counts to benchmark, comma separated
+    #[arg(long, value_delimiter = ',', default_values_t = [10, 100, 1000])]
+    participants: Vec<usize>,
+
+    /// Rounds per participant count
+    #[arg(long, default_value_t = 20)]
+    iterations: usize,
+
+    /// Write the records here instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let engine = commitment_engine();
+
+    let mut records: Vec<BenchRecord> = Vec::new();
+    for &participants in &args.participants {
+        let mut total_ms = 0;
+        for iteration in 0..args.iterations {
+            let record = run_round(&engine, &format!("bench_{}_{}", participants, iteration), participants).await;
+            total_ms += record.execution_time_ms;
+            records.push(record);
+        }
+
+        let average_ms = total_ms as f64 / args.iterations.max(1) as f64;
+        let throughput = if total_ms == 0 {
+            f64::INFINITY
+        } else {
+            (participants * args.iterations) as f64 / (total_ms as f64 / 1000.0)
+        };
+        eprintln!(
+            "{:>6} participants: {:.2} ms/round, {:.0} participants/s",
+            participants, average_ms, throughput
+        );
+    }
+
+    let json = serde_json::to_string_pretty(&records)?;
+    match args.output {
+        Some(path) => std::fs::write(path, json)?,
+        None => println!("{}", json),
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use ddv_bench::alloc::{self, TrackingAllocator};
+use std::alloc::{GlobalAlloc, Layout};
+
+#[test]
+fn test_tracking_allocator_counts_current_and_peak_bytes() {
+    let tracker = TrackingAllocator::new();
+    let layout = Layout::from_size_align(4096, 8).unwrap();
+
+    unsafe {
+        let first = tracker.alloc(layout);
+        let second = tracker.alloc(layout);
+        assert_eq!(tracker.current(), 8192);
+        tracker.dealloc(first, layout);
+        assert_eq!(tracker.current(), 4096);
+        assert_eq!(tracker.peak(), 8192);
+
+        tracker.reset_peak();
+        assert_eq!(tracker.peak(), 4096);
+        tracker.dealloc(second, layout);
+    }
+    assert_eq!(tracker.current(), 0);
+}
+
+#[cfg(feature = "mem-profiling")]
+#[test]
+fn test_allocating_a_vec_increases_current_bytes() {
+    const SIZE: usize = 1 << 20;
+    let before = alloc::current_allocated();
+    let buffer: Vec<u8> = Vec::with_capacity(SIZE);
+    // Other test-harness threads may allocate concurrently, so only bound from below
+    assert!(alloc::current_allocated() >= before + SIZE);
+    assert!(alloc::peak_allocated() >= before + SIZE);
+    drop(buffer);
+}
+
+#[cfg(not(feature = "mem-profiling"))]
+#[test]
+fn test_counters_stay_zero_without_the_feature() {
+    let _buffer: Vec<u8> = Vec::with_capacity(1 << 20);
+    assert!(!alloc::enabled());
+    assert_eq!(alloc::current_allocated(), 0);
+}
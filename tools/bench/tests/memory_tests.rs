@@ -0,0 +1,24 @@
+//! Heap usage of a full commit-and-select round, measured by the tracking allocator
+//!
+//! Only built with `--features mem-profiling`. Kept to a single test so no other test thread
+//! allocates while a round is measured.
+#![cfg(feature = "mem-profiling")]
+
+use ddv_bench::{commitment_engine, run_round};
+
+/// Generous per-participant budget: a commitment, its salt and the revealed value
+const BYTES_PER_PARTICIPANT: f64 = 0.01 * 1024.0 * 1024.0;
+
+#[tokio::test]
+async fn test_round_memory_grows_linearly_with_participants() {
+    let engine = commitment_engine();
+    for participants in [10, 50, 100, 500, 1000] {
+        let record = run_round(&engine, &format!("memory_test_{}", participants), participants).await;
+        assert!(record.success, "round with {} participants failed", participants);
+
+        let bytes = record.memory_usage_mb * 1024.0 * 1024.0;
+        assert!(bytes > 0.0, "no heap allocation recorded for {} participants", participants);
+        let budget = participants as f64 * BYTES_PER_PARTICIPANT * 2.0;
+        assert!(bytes < budget, "{} participants used {} bytes, over the budget of {}", participants, bytes, budget);
+    }
+}
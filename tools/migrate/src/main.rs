@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use clap::Parser;
+use shared_config::DatabaseConfig;
+use tracing::info;
+use vote_store::migrate::{migrate, verify, MigrateOptions};
+use vote_store::{PostgresVoteStore, SqliteVoteStore, VoteStore};
+
+/// Copy every vote, commitment and reveal from one vote store to another
+#[derive(Parser, Debug)]
+#[command(name = "ddv-migrate", version, about)]
+struct Args {
+    /// Source database URL (sqlite: or postgres:)
+    #[arg(long)]
+    from: String,
+
+    /// Destination database URL (sqlite: or postgres:)
+    #[arg(long)]
+    to: String,
+
+    /// Read the source and report what would be copied without writing anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// File recording the last migrated vote id; rerunning with it resumes an interrupted migration
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+
+    /// Votes fetched from the source per page
+    #[arg(long, default_value_t = 100)]
+    page_size: u32,
+
+    /// Skip copying and only compare the two stores
+    #[arg(long)]
+    verify_only: bool,
+
+    /// Votes whose records are compared one by one during verification
+    #[arg(long, default_value_t = 10)]
+    spot_checks: usize,
+}
+
+async fn open_store(url: &str) -> anyhow::Result<Arc<dyn VoteStore>> {
+    let config = DatabaseConfig {
+        url: url.to_string(),
+        ..Default::default()
+    };
+    let store: Arc<dyn VoteStore> = if url.starts_with("sqlite:") {
+        Arc::new(SqliteVoteStore::new(&config).await?)
+    } else if url.starts_with("postgresql:") || url.starts_with("postgres:") {
+        Arc::new(PostgresVoteStore::new(&config).await?)
+    } else {
+        bail!("Unsupported database URL: {}", url);
+    };
+    Ok(store)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    shared_logging::init_logging_with_writer("info", std::io::stderr)
+        .map_err(|e| anyhow::anyhow!("Failed to initialize logging: {}", e))?;
+
+    let source = open_store(&args.from).await.context("Failed to open source store")?;
+    let destination = open_store(&args.to).await.context("Failed to open destination store")?;
+
+    if !args.verify_only {
+        let options = MigrateOptions {
+            dry_run: args.dry_run,
+            page_size: args.page_size,
+            checkpoint: args.checkpoint.clone(),
+        };
+        let report = migrate(source.as_ref(), destination.as_ref(), &options).await?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        if args.dry_run {
+            return Ok(());
+        }
+    }
+
+    info!("Verifying destination against source");
+    let verification = verify(source.as_ref(), destination.as_ref(), args.spot_checks).await?;
+    println!("{}", serde_json::to_string_pretty(&verification)?);
+    if !verification.is_consistent() {
+        bail!("Verification found {} mismatches", verification.mismatches.len());
+    }
+    Ok(())
+}